@@ -0,0 +1,77 @@
+//! Scheduler benchmarks
+//!
+//! Times `SimpleScheduler`, HEFT list scheduling, the GA and the CP
+//! solver on generated job shops of growing size and on Fisher and
+//! Thompson's ft06 (optimum makespan 55). Run with `cargo bench`; for
+//! solution quality next to run time use `u_ras::compare`.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use std::hint::black_box;
+use std::time::Duration;
+use u_ras::compare::{generate_job_shop, CompareOptions, SchedulerKind};
+use u_ras::models::{Activity, Resource, Task};
+use u_ras::scheduler::ScheduleRequest;
+
+/// ft06: (machine, time) per operation, jobs in rows
+const FT06: [[(usize, i64); 6]; 6] = [
+    [(2, 1), (0, 3), (1, 6), (3, 7), (5, 3), (4, 6)],
+    [(1, 8), (2, 5), (4, 10), (5, 10), (0, 10), (3, 4)],
+    [(2, 5), (3, 4), (5, 8), (0, 9), (1, 1), (4, 7)],
+    [(1, 5), (0, 5), (2, 5), (3, 3), (4, 8), (5, 9)],
+    [(2, 9), (1, 3), (4, 5), (5, 4), (0, 3), (3, 1)],
+    [(1, 3), (3, 3), (5, 9), (0, 10), (4, 4), (2, 1)],
+];
+
+fn ft06() -> ScheduleRequest {
+    let tasks = FT06
+        .iter()
+        .enumerate()
+        .map(|(j, ops)| {
+            let task_id = format!("J{}", j);
+            let mut task = Task::new(&task_id);
+            for (k, &(machine, time)) in ops.iter().enumerate() {
+                let mut activity = Activity::new(&format!("{}.{}", task_id, k), &task_id, k as i32)
+                    .with_time(0, time, 0)
+                    .with_resources("machine", vec![format!("M{}", machine)]);
+                if k > 0 {
+                    activity = activity.with_predecessor(&format!("{}.{}", task_id, k - 1));
+                }
+                task = task.with_activity(activity);
+            }
+            task
+        })
+        .collect();
+    let resources = (0..6)
+        .map(|m| Resource::primary(&format!("M{}", m)))
+        .collect();
+    ScheduleRequest::new(tasks, resources)
+}
+
+fn bench_instance(c: &mut Criterion, group: &str, label: &str, request: &ScheduleRequest) {
+    let options = CompareOptions::default().with_time_limit_ms(2_000);
+    let mut group = c.benchmark_group(group);
+    group.sample_size(10);
+    group.measurement_time(Duration::from_secs(5));
+    for scheduler in SchedulerKind::ALL {
+        group.bench_with_input(
+            BenchmarkId::new(scheduler.name(), label),
+            request,
+            |b, request| b.iter(|| scheduler.run(black_box(request), &options)),
+        );
+    }
+    group.finish();
+}
+
+fn generated(c: &mut Criterion) {
+    for (jobs, machines) in [(10, 5), (20, 10), (50, 10)] {
+        let request = generate_job_shop(jobs, machines, 1);
+        bench_instance(c, "job_shop", &format!("{}x{}", jobs, machines), &request);
+    }
+}
+
+fn benchmark(c: &mut Criterion) {
+    bench_instance(c, "ft06", "6x6", &ft06());
+}
+
+criterion_group!(benches, generated, benchmark);
+criterion_main!(benches);
@@ -0,0 +1,326 @@
+//! u-ras - Command-line scheduling engine (feature `cli`)
+//!
+//! Solves, validates and measures instance files without writing Rust:
+//!
+//! ```text
+//! u-ras solve problem.json --algorithm ga --time-limit 60 --out schedule.json --report report.html
+//! u-ras validate problem.json --schedule schedule.json
+//! u-ras kpi problem.json schedule.json
+//! ```
+//!
+//! Problems are `ScheduleRequest` JSON and schedules `Schedule` JSON, of
+//! any supported format version; written schedules are versioned.
+
+use clap::{Parser, Subcommand, ValueEnum};
+use std::error::Error;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use u_ras::format::Versioned;
+use u_ras::ga::{GaParams, GaScheduler};
+use u_ras::models::Schedule;
+use u_ras::report::HtmlReport;
+use u_ras::scheduler::{HeftScheduler, ScheduleKpi, ScheduleRequest, SimpleScheduler};
+use u_ras::validation::{
+    validate_compatibility, validate_deadlines, validate_eligibility, validate_schedule,
+    ValidationResult,
+};
+
+#[derive(Parser)]
+#[command(
+    name = "u-ras",
+    version,
+    about = "Resource allocation and scheduling engine"
+)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Solve a problem file
+    Solve {
+        /// Problem (ScheduleRequest JSON)
+        problem: PathBuf,
+        /// Scheduling algorithm
+        #[arg(long, value_enum, default_value_t = Algorithm::Simple)]
+        algorithm: Algorithm,
+        /// Time limit in seconds (GA)
+        #[arg(long)]
+        time_limit: Option<f64>,
+        /// Random seed (GA)
+        #[arg(long)]
+        seed: Option<u64>,
+        /// Schedule output file (stdout if omitted)
+        #[arg(long)]
+        out: Option<PathBuf>,
+        /// HTML report output file
+        #[arg(long)]
+        report: Option<PathBuf>,
+    },
+    /// Check a problem strictly, and a schedule against it if given
+    Validate {
+        /// Problem (ScheduleRequest JSON)
+        problem: PathBuf,
+        /// Schedule (Schedule JSON)
+        #[arg(long)]
+        schedule: Option<PathBuf>,
+    },
+    /// Print the KPIs of a schedule as JSON
+    Kpi {
+        /// Problem (ScheduleRequest JSON)
+        problem: PathBuf,
+        /// Schedule (Schedule JSON)
+        schedule: PathBuf,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Algorithm {
+    /// Priority-based greedy (honors transition matrices)
+    Simple,
+    /// HEFT list scheduling
+    Heft,
+    /// Genetic algorithm (balanced preset)
+    Ga,
+}
+
+fn main() -> ExitCode {
+    match run(Cli::parse()) {
+        Ok(code) => code,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::from(2)
+        }
+    }
+}
+
+fn run(cli: Cli) -> Result<ExitCode, Box<dyn Error>> {
+    match cli.command {
+        Command::Solve {
+            problem,
+            algorithm,
+            time_limit,
+            seed,
+            out,
+            report,
+        } => {
+            let request: ScheduleRequest = load(&problem)?;
+            let schedule = solve(&request, algorithm, time_limit, seed);
+            eprintln!(
+                "{:?}: makespan {} ms, {} assignments, {} violations",
+                algorithm,
+                schedule.makespan_ms,
+                schedule.assignment_count(),
+                schedule.violations.len()
+            );
+            let json = schedule.to_versioned_json()?;
+            match out {
+                Some(path) => write(&path, &json)?,
+                None => println!("{}", json),
+            }
+            if let Some(path) = report {
+                let title = problem.file_stem().map_or_else(
+                    || "Schedule Report".to_string(),
+                    |s| s.to_string_lossy().into_owned(),
+                );
+                HtmlReport::new(&title)
+                    .write(&path, &schedule, &request.tasks, &request.resources)
+                    .map_err(|e| format!("{}: {}", path.display(), e))?;
+            }
+            Ok(ExitCode::SUCCESS)
+        }
+        Command::Validate { problem, schedule } => {
+            let text = read(&problem)?;
+            let request = match ScheduleRequest::from_json_strict(&text) {
+                Ok(request) => request,
+                Err(e) => {
+                    for problem in &e.problems {
+                        println!("error: {}", problem);
+                    }
+                    println!("{} problem(s)", e.problems.len());
+                    return Ok(ExitCode::FAILURE);
+                }
+            };
+            let mut results = vec![validate_eligibility(
+                &request.tasks,
+                &request.resources,
+                &request.compatibility,
+            )];
+            if let Some(path) = schedule {
+                let schedule: Schedule = load(&path)?;
+                results.push(validate_schedule(&schedule, &request.tasks, &[]));
+                results.push(validate_deadlines(&schedule, &request.tasks));
+                results.push(validate_compatibility(
+                    &schedule,
+                    &request.tasks,
+                    &request.resources,
+                    &request.compatibility,
+                ));
+                for violation in &schedule.violations {
+                    println!(
+                        "warning: {:?} {}: {}",
+                        violation.violation_type, violation.entity_id, violation.message
+                    );
+                }
+            }
+            Ok(report_results(&results))
+        }
+        Command::Kpi { problem, schedule } => {
+            let request: ScheduleRequest = load(&problem)?;
+            let schedule: Schedule = load(&schedule)?;
+            let kpi = ScheduleKpi::calculate(&schedule, &request.tasks);
+            println!("{}", serde_json::to_string_pretty(&kpi)?);
+            Ok(ExitCode::SUCCESS)
+        }
+    }
+}
+
+fn solve(
+    request: &ScheduleRequest,
+    algorithm: Algorithm,
+    time_limit: Option<f64>,
+    seed: Option<u64>,
+) -> Schedule {
+    match algorithm {
+        Algorithm::Simple => SimpleScheduler::new().schedule_request(request),
+        Algorithm::Heft => HeftScheduler::new().schedule(
+            &request.eligible_tasks(),
+            &request.resources,
+            request.start_time_ms,
+        ),
+        Algorithm::Ga => {
+            let mut params = GaParams::balanced();
+            if let Some(seconds) = time_limit {
+                params.time_limit_ms = Some((seconds * 1000.0) as i64);
+            }
+            let mut ga = GaScheduler::new(params)
+                .with_changeover_penalty(request.transition_matrices.clone(), 1.0);
+            if let Some(seed) = seed {
+                ga = ga.with_seed(seed);
+            }
+            ga.schedule(
+                &request.eligible_tasks(),
+                &request.resources,
+                request.start_time_ms,
+            )
+        }
+    }
+}
+
+/// Print errors and warnings; failure if any check failed
+fn report_results(results: &[ValidationResult]) -> ExitCode {
+    let mut errors = 0;
+    for result in results {
+        for error in &result.errors {
+            println!("error: {}: {}", error.code, error.message);
+            errors += 1;
+        }
+        for warning in &result.warnings {
+            println!("warning: {}", warning);
+        }
+    }
+    if errors == 0 {
+        println!("ok");
+        ExitCode::SUCCESS
+    } else {
+        println!("{} problem(s)", errors);
+        ExitCode::FAILURE
+    }
+}
+
+fn load<T: Versioned>(path: &Path) -> Result<T, String> {
+    T::from_versioned_json(&read(path)?).map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+fn read(path: &Path) -> Result<String, String> {
+    std::fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+fn write(path: &Path, text: &str) -> Result<(), String> {
+    std::fs::write(path, text).map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::CommandFactory;
+    use u_ras::models::{Activity, ActivityDuration, Resource, Task};
+
+    #[test]
+    fn test_cli_definition() {
+        Cli::command().debug_assert();
+        let cli = Cli::parse_from([
+            "u-ras",
+            "solve",
+            "p.json",
+            "--algorithm",
+            "ga",
+            "--time-limit",
+            "60",
+        ]);
+        assert!(matches!(
+            cli.command,
+            Command::Solve {
+                algorithm: Algorithm::Ga,
+                time_limit: Some(t),
+                ..
+            } if t == 60.0
+        ));
+    }
+
+    #[test]
+    fn test_solve_validate_kpi() {
+        let dir = std::env::temp_dir().join(format!("u-ras-cli-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let problem = dir.join("problem.json");
+        let schedule = dir.join("schedule.json");
+        let report = dir.join("report.html");
+        let task = Task::new("T1").with_activity(
+            Activity::new("A1", "T1", 0)
+                .with_duration(ActivityDuration::fixed(1000))
+                .with_resources("machine", vec!["M1".into()]),
+        );
+        let request = ScheduleRequest::new(vec![task], vec![Resource::primary("M1")]);
+        write(&problem, &request.to_versioned_json().unwrap()).unwrap();
+
+        let code = run(Cli::parse_from([
+            "u-ras".as_ref(),
+            "solve".as_ref(),
+            problem.as_os_str(),
+            "--out".as_ref(),
+            schedule.as_os_str(),
+            "--report".as_ref(),
+            report.as_os_str(),
+        ]))
+        .unwrap();
+        assert_eq!(code, ExitCode::SUCCESS);
+        let solved: Schedule = load(&schedule).unwrap();
+        assert_eq!(solved.makespan_ms, 1000);
+        assert!(read(&report).unwrap().contains("<html"));
+
+        for args in [
+            vec!["validate".as_ref(), problem.as_os_str()],
+            vec![
+                "validate".as_ref(),
+                problem.as_os_str(),
+                "--schedule".as_ref(),
+                schedule.as_os_str(),
+            ],
+            vec!["kpi".as_ref(), problem.as_os_str(), schedule.as_os_str()],
+        ] {
+            let cli = Cli::parse_from(std::iter::once("u-ras".as_ref()).chain(args));
+            assert_eq!(run(cli).unwrap(), ExitCode::SUCCESS);
+        }
+
+        write(
+            &problem,
+            r#"{"tasks": [], "resources": [], "start_time": 0}"#,
+        )
+        .unwrap();
+        let cli = Cli::parse_from(["u-ras".as_ref(), "validate".as_ref(), problem.as_os_str()]);
+        assert_eq!(run(cli).unwrap(), ExitCode::FAILURE);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
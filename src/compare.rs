@@ -0,0 +1,432 @@
+//! Compare - Pick a scheduler for an instance shape
+//!
+//! Runs several schedulers on the same request and reports quality
+//! (KPIs, violations) next to run time, so users can choose an algorithm
+//! by measuring on their own instances instead of guessing:
+//!
+//! ```
+//! use u_ras::compare::{compare_schedulers, generate_job_shop, CompareOptions};
+//!
+//! let request = generate_job_shop(6, 4, 7);
+//! let comparison = compare_schedulers(&request, &CompareOptions::default().with_time_limit_ms(500));
+//! println!("{}", comparison.to_table());
+//! assert!(comparison.best().is_some());
+//! ```
+//!
+//! `benches/schedulers.rs` measures the same schedulers with criterion
+//! across instance sizes (`cargo bench`).
+
+use crate::cp::{CpModel, CpSolver, IntervalVar, SimpleCpSolver, SolverConfig};
+use crate::ga::{GaParams, GaScheduler};
+use crate::graph::PrecedenceGraph;
+use crate::models::{Activity, Assignment, Resource, Schedule, Task, Violation};
+use crate::parallel::stream_rng;
+use crate::scheduler::{HeftScheduler, ScheduleKpi, ScheduleRequest, SimpleScheduler};
+use crate::telemetry::Stopwatch;
+use rand::seq::SliceRandom;
+use rand::Rng;
+use std::collections::HashMap;
+
+/// Scheduler under comparison
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SchedulerKind {
+    /// `SimpleScheduler` (priority greedy, with transition matrices)
+    Simple,
+    /// `HeftScheduler` (list scheduling by upward rank)
+    Heft,
+    /// `GaScheduler` with the balanced preset
+    Ga,
+    /// `SimpleCpSolver` on an interval model of the request
+    Cp,
+}
+
+impl SchedulerKind {
+    /// All schedulers, cheapest first
+    pub const ALL: [SchedulerKind; 4] = [
+        SchedulerKind::Simple,
+        SchedulerKind::Heft,
+        SchedulerKind::Cp,
+        SchedulerKind::Ga,
+    ];
+
+    /// Name used in tables ("simple", "heft", "ga", "cp")
+    pub fn name(&self) -> &'static str {
+        match self {
+            SchedulerKind::Simple => "simple",
+            SchedulerKind::Heft => "heft",
+            SchedulerKind::Ga => "ga",
+            SchedulerKind::Cp => "cp",
+        }
+    }
+
+    /// Schedule a request
+    ///
+    /// The time limit and seed apply to the GA and CP solver.
+    pub fn run(&self, request: &ScheduleRequest, options: &CompareOptions) -> Schedule {
+        match self {
+            SchedulerKind::Simple => SimpleScheduler::new().schedule_request(request),
+            SchedulerKind::Heft => HeftScheduler::new().schedule(
+                &request.eligible_tasks(),
+                &request.resources,
+                request.start_time_ms,
+            ),
+            SchedulerKind::Ga => GaScheduler::new(GaParams {
+                time_limit_ms: Some(options.time_limit_ms),
+                ..GaParams::balanced()
+            })
+            .with_seed(options.seed)
+            .with_changeover_penalty(request.transition_matrices.clone(), 1.0)
+            .schedule(
+                &request.eligible_tasks(),
+                &request.resources,
+                request.start_time_ms,
+            ),
+            SchedulerKind::Cp => cp_schedule(request, options.time_limit_ms),
+        }
+    }
+}
+
+/// Comparison settings
+#[derive(Debug, Clone)]
+pub struct CompareOptions {
+    /// Schedulers to run, in order
+    pub schedulers: Vec<SchedulerKind>,
+    /// Time limit per run of the GA and CP solver (ms)
+    pub time_limit_ms: i64,
+    /// Random seed of the GA
+    pub seed: u64,
+}
+
+impl Default for CompareOptions {
+    fn default() -> Self {
+        Self {
+            schedulers: SchedulerKind::ALL.to_vec(),
+            time_limit_ms: 10_000,
+            seed: 0,
+        }
+    }
+}
+
+impl CompareOptions {
+    /// Run only these schedulers
+    pub fn with_schedulers(mut self, schedulers: &[SchedulerKind]) -> Self {
+        self.schedulers = schedulers.to_vec();
+        self
+    }
+
+    /// Set time limit per run
+    pub fn with_time_limit_ms(mut self, time_limit_ms: i64) -> Self {
+        self.time_limit_ms = time_limit_ms;
+        self
+    }
+
+    /// Set random seed
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+}
+
+/// One scheduler's result
+#[derive(Debug, Clone)]
+pub struct SchedulerRun {
+    /// Scheduler
+    pub scheduler: SchedulerKind,
+    /// Produced schedule
+    pub schedule: Schedule,
+    /// Schedule KPIs
+    pub kpi: ScheduleKpi,
+    /// Wall-clock run time (ms)
+    pub elapsed_ms: i64,
+}
+
+/// Results of all schedulers on one request
+#[derive(Debug, Clone)]
+pub struct SchedulerComparison {
+    /// Runs in option order
+    pub runs: Vec<SchedulerRun>,
+}
+
+impl SchedulerComparison {
+    /// Run of a scheduler
+    pub fn get(&self, scheduler: SchedulerKind) -> Option<&SchedulerRun> {
+        self.runs.iter().find(|r| r.scheduler == scheduler)
+    }
+
+    /// Fewest violations, then shortest makespan, then fastest
+    pub fn best(&self) -> Option<&SchedulerRun> {
+        self.runs
+            .iter()
+            .min_by_key(|r| (r.schedule.violations.len(), r.kpi.makespan_ms, r.elapsed_ms))
+    }
+
+    /// Plain-text table, one row per scheduler
+    pub fn to_table(&self) -> String {
+        let mut table = format!(
+            "{:<10} {:>12} {:>14} {:>10} {:>10}\n",
+            "scheduler", "makespan_ms", "tardiness_ms", "violations", "elapsed_ms"
+        );
+        for r in &self.runs {
+            table.push_str(&format!(
+                "{:<10} {:>12} {:>14} {:>10} {:>10}\n",
+                r.scheduler.name(),
+                r.kpi.makespan_ms,
+                r.kpi.total_tardiness_ms,
+                r.schedule.violations.len(),
+                r.elapsed_ms
+            ));
+        }
+        table
+    }
+}
+
+/// Run every scheduler of the options on a request
+pub fn compare_schedulers(
+    request: &ScheduleRequest,
+    options: &CompareOptions,
+) -> SchedulerComparison {
+    let runs = options
+        .schedulers
+        .iter()
+        .map(|&scheduler| {
+            let clock = Stopwatch::start();
+            let schedule = scheduler.run(request, options);
+            let elapsed_ms = clock.elapsed_ms();
+            SchedulerRun {
+                scheduler,
+                kpi: ScheduleKpi::calculate(&schedule, &request.tasks),
+                schedule,
+                elapsed_ms,
+            }
+        })
+        .collect();
+    SchedulerComparison { runs }
+}
+
+/// Random job shop instance
+///
+/// Each of `jobs` tasks visits every one of `machines` resources once in
+/// a random order, with processing times uniform in 1..=99 seconds
+/// (Taillard's generator shape). The same seed gives the same instance.
+pub fn generate_job_shop(jobs: usize, machines: usize, seed: u64) -> ScheduleRequest {
+    let mut rng = stream_rng(seed, 0);
+    let resources: Vec<Resource> = (0..machines)
+        .map(|m| Resource::primary(&format!("M{}", m)))
+        .collect();
+    let tasks = (0..jobs)
+        .map(|j| {
+            let task_id = format!("J{}", j);
+            let mut route: Vec<usize> = (0..machines).collect();
+            route.shuffle(&mut rng);
+            let mut task = Task::new(&task_id);
+            for (k, m) in route.into_iter().enumerate() {
+                let mut activity = Activity::new(&format!("{}.{}", task_id, k), &task_id, k as i32)
+                    .with_time(0, rng.gen_range(1..=99) * 1000, 0)
+                    .with_resources("machine", vec![format!("M{}", m)]);
+                if k > 0 {
+                    activity = activity.with_predecessor(&format!("{}.{}", task_id, k - 1));
+                }
+                task = task.with_activity(activity);
+            }
+            task
+        })
+        .collect();
+    ScheduleRequest::new(tasks, resources)
+}
+
+/// Solve a request with the CP solver
+///
+/// Each activity is an interval with one optional alternative per
+/// candidate resource (of its first requirement); alternatives on a
+/// resource do not overlap. Durations are nominal: efficiencies,
+/// calendars and setups are not modeled.
+fn cp_schedule(request: &ScheduleRequest, time_limit_ms: i64) -> Schedule {
+    let tasks = request.eligible_tasks();
+    let start_ms = request.start_time_ms;
+    let release = |task: &Task| {
+        task.release_time
+            .map_or(start_ms, |t| t.timestamp_millis().max(start_ms))
+    };
+    let horizon = tasks.iter().map(release).max().unwrap_or(start_ms)
+        + tasks
+            .iter()
+            .flat_map(|t| &t.activities)
+            .map(|a| a.duration.total_ms())
+            .sum::<i64>();
+
+    let mut model = CpModel::new("compare", horizon);
+    let mut on_resource: HashMap<&str, Vec<String>> = HashMap::new();
+    for task in &tasks {
+        let earliest = release(task);
+        for activity in &task.activities {
+            let duration = activity.duration.total_ms();
+            model.add_interval(IntervalVar::new(
+                activity.id.clone(),
+                earliest,
+                horizon,
+                duration,
+                horizon + duration,
+            ));
+            let Some(requirement) = activity.resource_requirements.first() else {
+                continue;
+            };
+            let mut alternatives = Vec::new();
+            for candidate in &requirement.candidates {
+                let name = format!("{}@{}", activity.id, candidate);
+                model.add_interval(
+                    IntervalVar::new(
+                        name.clone(),
+                        earliest,
+                        horizon,
+                        duration,
+                        horizon + duration,
+                    )
+                    .as_optional(format!("{}?", name)),
+                );
+                on_resource
+                    .entry(candidate.as_str())
+                    .or_default()
+                    .push(name.clone());
+                alternatives.push(name);
+            }
+            if !alternatives.is_empty() {
+                model.add_alternative(activity.id.clone(), alternatives);
+            }
+        }
+    }
+    for intervals in on_resource.into_values() {
+        model.add_no_overlap(intervals);
+    }
+    for (before, after) in PrecedenceGraph::from_tasks(&tasks).edges() {
+        model.add_precedence(before.to_string(), after.to_string(), 0);
+    }
+    model.minimize_makespan();
+
+    let config = SolverConfig {
+        time_limit_ms,
+        ..SolverConfig::default()
+    };
+    let solution = SimpleCpSolver::new().solve(&model, &config);
+    let mut schedule = Schedule::new();
+    if !solution.is_solution_found() {
+        schedule.violations.push(Violation::resource_unavailable(
+            "",
+            &format!("CP solver found no solution ({:?})", solution.status),
+        ));
+        return schedule;
+    }
+    for task in &tasks {
+        for activity in &task.activities {
+            let candidates = activity
+                .resource_requirements
+                .first()
+                .map_or(&[][..], |r| &r.candidates[..]);
+            let resource = candidates
+                .iter()
+                .find(|c| {
+                    solution
+                        .intervals
+                        .get(&format!("{}@{}", activity.id, c))
+                        .is_some_and(|i| i.is_present)
+                })
+                .map_or("", String::as_str);
+            if let Some(interval) = solution.intervals.get(&activity.id) {
+                schedule.add_assignment(Assignment::new(
+                    &activity.id,
+                    &task.id,
+                    resource,
+                    interval.start,
+                    interval.end,
+                ));
+            }
+        }
+    }
+    schedule.makespan_ms = schedule
+        .assignments
+        .iter()
+        .map(|a| a.end_ms)
+        .max()
+        .unwrap_or(start_ms)
+        - start_ms;
+    schedule
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_job_shop() {
+        let request = generate_job_shop(5, 3, 11);
+        assert_eq!(request.tasks.len(), 5);
+        assert_eq!(request.resources.len(), 3);
+        for task in &request.tasks {
+            let mut machines: Vec<String> = task
+                .activities
+                .iter()
+                .flat_map(|a| a.candidate_resources())
+                .collect();
+            machines.sort();
+            assert_eq!(machines, vec!["M0", "M1", "M2"]);
+        }
+        let again = generate_job_shop(5, 3, 11);
+        assert_eq!(
+            again.tasks[4].activities[2].duration.process_ms,
+            request.tasks[4].activities[2].duration.process_ms
+        );
+    }
+
+    #[test]
+    fn test_compare_schedulers() {
+        let request = generate_job_shop(6, 4, 3);
+        let options = CompareOptions::default()
+            .with_time_limit_ms(1000)
+            .with_seed(5);
+        let comparison = compare_schedulers(&request, &options);
+
+        assert_eq!(comparison.runs.len(), 4);
+        let longest: i64 = request
+            .tasks
+            .iter()
+            .flat_map(|t| &t.activities)
+            .map(|a| a.duration.total_ms())
+            .max()
+            .unwrap();
+        for run in &comparison.runs {
+            assert_eq!(run.schedule.assignment_count(), 24, "{:?}", run.scheduler);
+            assert!(run.kpi.makespan_ms >= longest);
+            assert!(run.schedule.violations.is_empty(), "{:?}", run.scheduler);
+        }
+        let best = comparison.best().unwrap();
+        assert!(comparison
+            .runs
+            .iter()
+            .all(|r| r.kpi.makespan_ms >= best.kpi.makespan_ms));
+        assert_eq!(comparison.to_table().lines().count(), 5);
+    }
+
+    #[test]
+    fn test_cp_respects_precedence_and_machines() {
+        let request = generate_job_shop(4, 3, 1);
+        let schedule = SchedulerKind::Cp.run(&request, &CompareOptions::default());
+
+        for task in &request.tasks {
+            for pair in task.activities.windows(2) {
+                let first = schedule.assignment_for_activity(&pair[0].id).unwrap();
+                let second = schedule.assignment_for_activity(&pair[1].id).unwrap();
+                assert!(first.end_ms <= second.start_ms);
+            }
+        }
+        let mut by_machine: HashMap<&str, Vec<(i64, i64)>> = HashMap::new();
+        for a in &schedule.assignments {
+            by_machine
+                .entry(a.resource_id.as_str())
+                .or_default()
+                .push((a.start_ms, a.end_ms));
+        }
+        for runs in by_machine.values_mut() {
+            runs.sort();
+            assert!(runs.windows(2).all(|w| w[0].1 <= w[1].0));
+        }
+    }
+}
@@ -3,9 +3,11 @@
 //! Domain-agnostic constraint programming infrastructure
 
 mod model;
+mod propagation;
 mod solver;
 mod variables;
 
 pub use model::*;
+pub use propagation::*;
 pub use solver::*;
 pub use variables::*;
@@ -1,8 +1,9 @@
 //! CP Model - Constraint Programming Model Definition
 
 use crate::cp::variables::*;
+use crate::models::{RenewablePool, Task};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 /// CP 제약 조건
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -39,6 +40,26 @@ pub enum Constraint {
         main: String,
         alternatives: Vec<String>,
     },
+    /// 동일 대안 선택 (두 주 간격이 같은 위치의 대안을 선택, 예: 같은 차량)
+    SameAlternative { main1: String, main2: String },
+    /// 인계 동기화 (after 시작이 before 종료 ± tolerance 이내)
+    Handoff {
+        before: String,
+        after: String,
+        tolerance: i64,
+    },
+    /// 최대 지연 선행 (before 종료 <= after 시작 <= before 종료 + max_delay)
+    MaxDelay {
+        before: String,
+        after: String,
+        max_delay: i64,
+    },
+    /// 겹침 허용 선행 (after 시작 >= before 종료 - lead, after 종료 >= before 종료)
+    Overlap {
+        before: String,
+        after: String,
+        lead: i64,
+    },
 }
 
 /// 전환 행렬 (Setup Time)
@@ -94,6 +115,13 @@ pub enum Objective {
     MinimizeWeightedSum { terms: Vec<(String, f64)> },
     /// 다목적 (계층적)
     Hierarchical { objectives: Vec<Objective> },
+    /// Makespan + 가중 비용 최소화 (간격 이름 -> 수행 비용)
+    ///
+    /// 대안 선택 시 종료 시간에 `weight` × 비용을 더해 비교
+    MinimizeMakespanAndCost {
+        costs: HashMap<String, f64>,
+        weight: f64,
+    },
 }
 
 /// CP 모델
@@ -174,6 +202,61 @@ impl CpModel {
         });
     }
 
+    /// 전역 피크 부하 제약 추가
+    ///
+    /// 간격 이름은 활동 ID와 일치해야 함. 수요는 올림, 상한은 내림하여
+    /// 정수 누적 제약으로 보수적으로 변환. 활동당 한 번만 계산하며
+    /// 자원별 대안/갱 간격(`활동@자원`)은 제외
+    pub fn add_peak_load(&mut self, tasks: &[Task], quantity: &str, max_load: f64) {
+        let mut intervals = Vec::new();
+        let mut demands = Vec::new();
+        let mut seen = HashSet::new();
+        for activity in tasks.iter().flat_map(|t| t.activities.iter()) {
+            let demand = activity.demand(quantity);
+            if demand > 0.0
+                && self.intervals.contains_key(&activity.id)
+                && seen.insert(activity.id.as_str())
+            {
+                intervals.push(activity.id.clone());
+                demands.push(demand.ceil() as i64);
+            }
+        }
+        self.add_cumulative(intervals, demands, max_load.floor() as i64);
+    }
+
+    /// 재생 가능 자원 풀 제약 추가 (RCPSP)
+    ///
+    /// 풀마다 활동 수요(단위)와 풀 용량으로 누적 제약을 추가.
+    /// 간격 이름은 활동 ID와 일치해야 하며, 정의되지 않은 활동은 제외
+    pub fn add_renewable_pools(&mut self, tasks: &[Task], pools: &[RenewablePool]) {
+        for pool in pools {
+            let mut intervals = Vec::new();
+            let mut demands = Vec::new();
+            for activity in tasks.iter().flat_map(|t| t.activities.iter()) {
+                let units = activity.pool_demand(&pool.id);
+                if units > 0 && self.intervals.contains_key(&activity.id) {
+                    intervals.push(activity.id.clone());
+                    demands.push(units as i64);
+                }
+            }
+            if !intervals.is_empty() {
+                self.add_cumulative(intervals, demands, pool.capacity as i64);
+            }
+        }
+    }
+
+    /// 상호 배제 그룹 추가 (자원과 무관하게 그룹 활동은 동시에 수행 불가)
+    ///
+    /// 간격 이름은 활동 ID와 일치해야 하며, 정의되지 않은 활동은 제외
+    pub fn add_mutual_exclusion(&mut self, activity_ids: &[String]) {
+        let intervals = activity_ids
+            .iter()
+            .filter(|id| self.intervals.contains_key(*id))
+            .cloned()
+            .collect();
+        self.add_no_overlap(intervals);
+    }
+
     /// 선행 제약 추가
     pub fn add_precedence(&mut self, before: String, after: String, min_delay: i64) {
         self.constraints.push(Constraint::Precedence {
@@ -183,6 +266,65 @@ impl CpModel {
         });
     }
 
+    /// 대안 제약 추가 (주 간격은 대안 중 하나로 수행)
+    pub fn add_alternative(&mut self, main: String, alternatives: Vec<String>) {
+        self.constraints
+            .push(Constraint::Alternative { main, alternatives });
+    }
+
+    /// 픽업-배송 쌍 추가
+    ///
+    /// 픽업이 배송보다 먼저 끝나고, 두 활동이 같은 위치의 대안(차량)을
+    /// 선택해야 함. 시간 창은 간격 변수의 시작/종료 범위로 표현
+    pub fn add_pickup_delivery(&mut self, pickup: String, delivery: String) {
+        self.add_precedence(pickup.clone(), delivery.clone(), 0);
+        self.constraints.push(Constraint::SameAlternative {
+            main1: pickup,
+            main2: delivery,
+        });
+    }
+
+    /// 인계 동기화 제약 추가
+    pub fn add_handoff(&mut self, before: String, after: String, tolerance: i64) {
+        self.constraints.push(Constraint::Handoff {
+            before,
+            after,
+            tolerance,
+        });
+    }
+
+    /// 최대 지연 제약 추가 (after는 before 종료 후 max_delay 이내에 시작)
+    pub fn add_max_delay(&mut self, before: String, after: String, max_delay: i64) {
+        self.constraints.push(Constraint::MaxDelay {
+            before,
+            after,
+            max_delay,
+        });
+    }
+
+    /// 겹침 허용 선행 제약 추가 (후속 작업이 선행 작업 종료 lead 전부터 시작 가능)
+    pub fn add_overlap(&mut self, before: String, after: String, lead: i64) {
+        self.constraints.push(Constraint::Overlap {
+            before,
+            after,
+            lead,
+        });
+    }
+
+    /// 갱 제약 추가 (모든 간격이 동시에 시작, 예: 여러 자원을 동시에 점유하는 작업)
+    ///
+    /// 각 간격은 서로 다른 자원의 비중첩/누적 제약에 속해야 함
+    pub fn add_gang(&mut self, intervals: Vec<String>) {
+        if let Some((first, rest)) = intervals.split_first() {
+            for other in rest {
+                self.constraints.push(Constraint::SameStart {
+                    interval1: first.clone(),
+                    interval2: other.clone(),
+                });
+            }
+        }
+    }
+
     /// 목적 함수 설정
     pub fn set_objective(&mut self, objective: Objective) {
         self.objective = Some(objective);
@@ -193,6 +335,15 @@ impl CpModel {
         self.objective = Some(Objective::MinimizeMakespan);
     }
 
+    /// Makespan + 가중 비용 최소화 설정
+    ///
+    /// `costs`는 간격 이름별 수행 비용으로 호출자가 계산해 전달
+    /// (예: 대안 간격마다 `CostModel::resource_time_cost`). `weight`는
+    /// 비용 1단위에 해당하는 makespan (ms)
+    pub fn minimize_cost(&mut self, costs: HashMap<String, f64>, weight: f64) {
+        self.objective = Some(Objective::MinimizeMakespanAndCost { costs, weight });
+    }
+
     /// 모델 유효성 검사
     pub fn validate(&self) -> Result<(), String> {
         // 모든 제약의 간격이 정의되어 있는지 확인
@@ -225,7 +376,49 @@ impl CpModel {
                         return Err(format!("Undefined interval: {}", after));
                     }
                 }
-                _ => {}
+                Constraint::Alternative { main, alternatives } => {
+                    for name in std::iter::once(main).chain(alternatives) {
+                        if !self.intervals.contains_key(name) {
+                            return Err(format!("Undefined interval: {}", name));
+                        }
+                    }
+                }
+                Constraint::SameAlternative { main1: a, main2: b }
+                | Constraint::SameStart {
+                    interval1: a,
+                    interval2: b,
+                }
+                | Constraint::SameEnd {
+                    interval1: a,
+                    interval2: b,
+                }
+                | Constraint::Handoff {
+                    before: a,
+                    after: b,
+                    ..
+                }
+                | Constraint::MaxDelay {
+                    before: a,
+                    after: b,
+                    ..
+                }
+                | Constraint::Overlap {
+                    before: a,
+                    after: b,
+                    ..
+                } => {
+                    for name in [a, b] {
+                        if !self.intervals.contains_key(name) {
+                            return Err(format!("Undefined interval: {}", name));
+                        }
+                    }
+                }
+            }
+        }
+        // 비용이 매겨진 간격도 정의되어 있어야 함
+        if let Some(Objective::MinimizeMakespanAndCost { costs, .. }) = &self.objective {
+            if let Some(name) = costs.keys().find(|n| !self.intervals.contains_key(*n)) {
+                return Err(format!("Undefined interval: {}", name));
             }
         }
         Ok(())
@@ -279,6 +472,37 @@ mod tests {
         assert!(model.validate().is_ok());
     }
 
+    #[test]
+    fn test_peak_load_constraint() {
+        use crate::models::Activity;
+
+        let tasks = vec![Task::new("T1")
+            .with_activity(Activity::new("A1", "T1", 1).with_demand("power", 40.5))
+            .with_activity(Activity::new("A2", "T1", 2))];
+
+        let mut model = CpModel::new("test", 1_000_000);
+        model.add_interval(IntervalVar::new("A1", 0, 100_000, 50_000, 200_000));
+        model.add_interval(IntervalVar::new("A2", 0, 100_000, 50_000, 200_000));
+        // 갱 활동의 자원별 간격은 수요를 다시 더하지 않음
+        for gang in ["A1@M1", "A1@M2"] {
+            model.add_interval(IntervalVar::new(gang, 0, 100_000, 50_000, 200_000));
+        }
+        model.add_peak_load(&tasks, "power", 100.0);
+
+        match &model.constraints[0] {
+            Constraint::Cumulative {
+                intervals,
+                demands,
+                capacity,
+            } => {
+                assert_eq!(intervals, &vec!["A1".to_string()]);
+                assert_eq!(demands, &vec![41]);
+                assert_eq!(*capacity, 100);
+            }
+            _ => panic!("Wrong constraint type"),
+        }
+    }
+
     #[test]
     fn test_validation_error() {
         let mut model = CpModel::new("test", 1_000_000);
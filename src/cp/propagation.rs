@@ -0,0 +1,417 @@
+//! CP Propagation - 이접(disjunctive) 자원 전파
+//!
+//! NoOverlap 제약에 대한 과부하 검사, edge-finding, not-first/not-last
+//! 규칙과 선행 제약 경계 전파. 모든 규칙은 건전(sound)하다: 어떤 실행
+//! 가능한 해도 제거하지 않고 시작 하한/종료 상한만 조인다.
+//!
+//! 집합 Ω는 작업 구간(task interval, est >= a 이고 lct <= b 인 작업들)과
+//! 그 구간에서 대상 작업 i를 뺀 집합으로 열거하므로 작업 수 n에 대해
+//! O(n⁴ log n)이다 (자원당 작업 수가 수십 개인 현실적 모델 대상).
+
+use crate::cp::model::{Constraint, CpModel};
+use crate::parallel::Parallelism;
+use std::collections::{HashMap, HashSet};
+
+/// 작업의 시간창 (est = 최조 시작, lct = 최지 종료)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TaskWindow {
+    /// 최조 시작
+    pub est: i64,
+    /// 최지 종료
+    pub lct: i64,
+    /// 기간
+    pub duration: i64,
+}
+
+impl TaskWindow {
+    pub fn new(est: i64, lct: i64, duration: i64) -> Self {
+        Self { est, lct, duration }
+    }
+
+    /// 최조 종료
+    pub fn ect(&self) -> i64 {
+        self.est + self.duration
+    }
+
+    /// 최지 시작
+    pub fn lst(&self) -> i64 {
+        self.lct - self.duration
+    }
+
+    /// 시간축 반전 (est/lct 규칙을 lct/est 규칙으로 재사용)
+    fn mirrored(&self) -> Self {
+        Self::new(-self.lct, -self.est, self.duration)
+    }
+}
+
+/// 전파 중 발견된 모순 (실행 불가능)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PropagationConflict {
+    /// 관련 간격 (모델 전파 시) 또는 빈 목록
+    pub intervals: Vec<String>,
+    /// 설명
+    pub reason: String,
+}
+
+impl std::fmt::Display for PropagationConflict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} ({})", self.reason, self.intervals.join(", "))
+    }
+}
+
+impl std::error::Error for PropagationConflict {}
+
+fn conflict(reason: &str) -> PropagationConflict {
+    PropagationConflict {
+        intervals: Vec::new(),
+        reason: reason.to_string(),
+    }
+}
+
+/// 작업 구간 Ω(a, b)의 멤버 (est >= a, lct <= b)
+fn task_intervals(tasks: &[TaskWindow]) -> Vec<Vec<usize>> {
+    let mut ests: Vec<i64> = tasks.iter().map(|t| t.est).collect();
+    let mut lcts: Vec<i64> = tasks.iter().map(|t| t.lct).collect();
+    ests.sort_unstable();
+    ests.dedup();
+    lcts.sort_unstable();
+    lcts.dedup();
+
+    let mut sets = Vec::new();
+    for &a in &ests {
+        for &b in &lcts {
+            let members: Vec<usize> = (0..tasks.len())
+                .filter(|&j| tasks[j].est >= a && tasks[j].lct <= b)
+                .collect();
+            if !members.is_empty() {
+                sets.push(members);
+            }
+        }
+    }
+    sets
+}
+
+/// Ω의 최조 완료 하한: max over a' (a' + Σ p_j, est_j >= a')
+fn ect_bound(tasks: &[TaskWindow], members: &[usize]) -> i64 {
+    let mut by_est: Vec<&TaskWindow> = members.iter().map(|&j| &tasks[j]).collect();
+    by_est.sort_unstable_by_key(|t| std::cmp::Reverse(t.est));
+    let mut work = 0;
+    let mut bound = i64::MIN;
+    for task in by_est {
+        work += task.duration;
+        bound = bound.max(task.est + work);
+    }
+    bound
+}
+
+/// 작업 i에 대한 후보 Ω: 각 작업 구간에서 i를 뺀 비어 있지 않은 집합
+fn sets_without(sets: &[Vec<usize>], i: usize) -> impl Iterator<Item = Vec<usize>> + '_ {
+    sets.iter()
+        .map(move |members| {
+            members
+                .iter()
+                .copied()
+                .filter(|&j| j != i)
+                .collect::<Vec<_>>()
+        })
+        .filter(|members| !members.is_empty())
+}
+
+/// 과부하 검사: est(Ω) + p(Ω) > lct(Ω)이면 실행 불가능
+pub fn overload_check(tasks: &[TaskWindow]) -> Result<(), PropagationConflict> {
+    for members in task_intervals(tasks) {
+        let lct = members.iter().map(|&j| tasks[j].lct).max().unwrap_or(0);
+        if ect_bound(tasks, &members) > lct {
+            return Err(conflict("disjunctive overload"));
+        }
+    }
+    Ok(())
+}
+
+/// Edge-finding (양방향)
+///
+/// est(Ω ∪ {i}) + p(Ω ∪ {i}) > lct(Ω)이면 i는 Ω의 모든 작업 뒤에 끝나므로
+/// est_i >= ECT(Ω). 반전된 시간축에서 같은 규칙으로 lct_i <= LST(Ω).
+/// 경계가 바뀌었으면 true.
+pub fn edge_finding(tasks: &mut [TaskWindow]) -> Result<bool, PropagationConflict> {
+    let forward = edge_finding_est(tasks)?;
+    let mut mirrored: Vec<TaskWindow> = tasks.iter().map(TaskWindow::mirrored).collect();
+    let backward = edge_finding_est(&mut mirrored)?;
+    for (task, m) in tasks.iter_mut().zip(&mirrored) {
+        task.lct = task.lct.min(-m.est);
+    }
+    check_windows(tasks)?;
+    Ok(forward || backward)
+}
+
+fn edge_finding_est(tasks: &mut [TaskWindow]) -> Result<bool, PropagationConflict> {
+    let sets = task_intervals(tasks);
+    let mut changed = false;
+    for i in 0..tasks.len() {
+        for members in sets_without(&sets, i) {
+            let lct = members.iter().map(|&j| tasks[j].lct).max().unwrap_or(0);
+            let est = members.iter().map(|&j| tasks[j].est).min().unwrap_or(0);
+            let work: i64 = members.iter().map(|&j| tasks[j].duration).sum();
+            let ect = ect_bound(tasks, &members);
+            if ect > lct {
+                return Err(conflict("disjunctive overload"));
+            }
+            if tasks[i].est < ect && est.min(tasks[i].est) + work + tasks[i].duration > lct {
+                tasks[i].est = ect;
+                changed = true;
+            }
+        }
+    }
+    Ok(changed)
+}
+
+/// Not-first / not-last (양방향)
+///
+/// est(Ω) + p(Ω) > lst_i이면 i는 Ω ∪ {i}에서 마지막일 수 없으므로
+/// lct_i <= max_{j∈Ω} lst_j (not-last). 반전된 시간축에서 not-first:
+/// est_i >= min_{j∈Ω} ect_j. 경계가 바뀌었으면 true.
+pub fn not_first_not_last(tasks: &mut [TaskWindow]) -> Result<bool, PropagationConflict> {
+    let last = not_last(tasks);
+    let mut mirrored: Vec<TaskWindow> = tasks.iter().map(TaskWindow::mirrored).collect();
+    let first = not_last(&mut mirrored);
+    for (task, m) in tasks.iter_mut().zip(&mirrored) {
+        task.est = task.est.max(-m.lct);
+    }
+    check_windows(tasks)?;
+    Ok(last || first)
+}
+
+fn not_last(tasks: &mut [TaskWindow]) -> bool {
+    let sets = task_intervals(tasks);
+    let mut changed = false;
+    for i in 0..tasks.len() {
+        for members in sets_without(&sets, i) {
+            let ect = ect_bound(tasks, &members);
+            let max_lst = members.iter().map(|&j| tasks[j].lst()).max().unwrap_or(0);
+            if ect > tasks[i].lst() && max_lst < tasks[i].lct {
+                tasks[i].lct = max_lst;
+                changed = true;
+            }
+        }
+    }
+    changed
+}
+
+fn check_windows(tasks: &[TaskWindow]) -> Result<(), PropagationConflict> {
+    if tasks.iter().any(|t| t.ect() > t.lct) {
+        return Err(conflict("empty time window"));
+    }
+    Ok(())
+}
+
+/// 이접 자원 하나의 고정점 전파
+pub fn propagate_disjunctive(tasks: &mut [TaskWindow]) -> Result<bool, PropagationConflict> {
+    check_windows(tasks)?;
+    overload_check(tasks)?;
+    let mut changed = false;
+    loop {
+        let round = edge_finding(tasks)? | not_first_not_last(tasks)?;
+        if !round {
+            return Ok(changed);
+        }
+        changed = true;
+    }
+}
+
+/// 모델 전체 전파 (NoOverlap + 선행 제약, 고정점)
+///
+/// 간격별 조여진 시간창을 반환한다. 대안/선택적 간격은 존재 여부가
+/// 정해지지 않았으므로 NoOverlap 전파에서 제외하며, 전환 시간은
+/// 무시한다 (더 약하지만 건전).
+pub fn propagate(model: &CpModel) -> Result<HashMap<String, TaskWindow>, PropagationConflict> {
+    propagate_with(model, &Parallelism::sequential())
+}
+
+/// 모델 전체 전파, NoOverlap 자원별 전파를 `parallelism` 풀에서 병렬 실행
+///
+/// 각 라운드에서 모든 NoOverlap 제약을 같은 시간창에서 전파한 뒤
+/// 제약 순서대로 반영하므로 결과는 스레드 수와 무관하다.
+pub fn propagate_with(
+    model: &CpModel,
+    parallelism: &Parallelism,
+) -> Result<HashMap<String, TaskWindow>, PropagationConflict> {
+    model.validate().map_err(|reason| PropagationConflict {
+        intervals: Vec::new(),
+        reason,
+    })?;
+    let mut windows: HashMap<String, TaskWindow> = model
+        .intervals
+        .iter()
+        .map(|(name, var)| {
+            let duration = var.duration.fixed.unwrap_or(var.duration.min);
+            let lct = var.end.max.min(var.start.max.saturating_add(duration));
+            (name.clone(), TaskWindow::new(var.start.min, lct, duration))
+        })
+        .collect();
+    let optional: HashSet<&str> = model
+        .constraints
+        .iter()
+        .flat_map(|c| match c {
+            Constraint::Alternative { alternatives, .. } => alternatives.iter().collect(),
+            _ => Vec::new(),
+        })
+        .map(|s| s.as_str())
+        .chain(
+            model
+                .intervals
+                .iter()
+                .filter(|(_, v)| v.is_optional)
+                .map(|(n, _)| n.as_str()),
+        )
+        .collect();
+    let resources: Vec<Vec<&String>> = model
+        .constraints
+        .iter()
+        .filter_map(|c| match c {
+            Constraint::NoOverlap { intervals, .. } => Some(
+                intervals
+                    .iter()
+                    .filter(|n| !optional.contains(n.as_str()))
+                    .collect(),
+            ),
+            _ => None,
+        })
+        .collect();
+
+    // 경계 값은 유한하므로 변화는 유한 번; 안전을 위해 반복 상한을 둔다
+    for _ in 0..=model.intervals.len() * 4 + 8 {
+        let mut changed = false;
+        let snapshot = &windows;
+        let tightened = parallelism.map_indexed(resources.len(), |r| {
+            let names = &resources[r];
+            let mut tasks: Vec<TaskWindow> = names.iter().map(|n| snapshot[n.as_str()]).collect();
+            match propagate_disjunctive(&mut tasks) {
+                Ok(true) => Ok(Some(tasks)),
+                Ok(false) => Ok(None),
+                Err(mut e) => {
+                    e.intervals = names.iter().map(|n| n.to_string()).collect();
+                    Err(e)
+                }
+            }
+        });
+        for (names, tasks) in resources.iter().zip(tightened) {
+            let Some(tasks) = tasks? else {
+                continue;
+            };
+            // 여러 자원에 걸친 간격은 가장 좁은 시간창을 취한다
+            for (name, task) in names.iter().zip(tasks) {
+                let w = windows.get_mut(name.as_str()).expect("window per interval");
+                w.est = w.est.max(task.est);
+                w.lct = w.lct.min(task.lct);
+            }
+            changed = true;
+        }
+        for constraint in &model.constraints {
+            if let Constraint::Precedence {
+                before,
+                after,
+                min_delay,
+            } = constraint
+            {
+                let (b, a) = (windows[before], windows[after]);
+                let est = b.ect() + min_delay;
+                let lct = a.lst() - min_delay;
+                if est > a.est || lct < b.lct {
+                    windows.insert(
+                        after.clone(),
+                        TaskWindow {
+                            est: est.max(a.est),
+                            ..a
+                        },
+                    );
+                    windows.insert(
+                        before.clone(),
+                        TaskWindow {
+                            lct: lct.min(b.lct),
+                            ..b
+                        },
+                    );
+                    changed = true;
+                }
+                for name in [before, after] {
+                    let w = windows[name];
+                    if w.ect() > w.lct {
+                        return Err(PropagationConflict {
+                            intervals: vec![before.clone(), after.clone()],
+                            reason: "precedence window".to_string(),
+                        });
+                    }
+                }
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+    Ok(windows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cp::variables::IntervalVar;
+
+    #[test]
+    fn test_edge_finding() {
+        // 두 작업이 [0, 10)을 채우므로 세 번째 작업은 그 뒤에 시작해야 함
+        let mut tasks = vec![
+            TaskWindow::new(0, 10, 5),
+            TaskWindow::new(0, 10, 5),
+            TaskWindow::new(0, 20, 3),
+        ];
+        assert!(edge_finding(&mut tasks).unwrap());
+        assert_eq!(tasks[2].est, 10);
+        assert_eq!((tasks[0].est, tasks[0].lct), (0, 10));
+
+        // 과부하
+        let mut tasks = vec![TaskWindow::new(0, 8, 5), TaskWindow::new(0, 8, 5)];
+        assert!(propagate_disjunctive(&mut tasks).is_err());
+    }
+
+    #[test]
+    fn test_not_first_not_last() {
+        // Ω = {[0,12) p=4, [0,12) p=4}, i = [0,10) p=3:
+        // est(Ω) + p(Ω) = 8 > lst_i = 7 → i는 마지막 불가, lct_i <= max lst_j = 8
+        let mut tasks = vec![
+            TaskWindow::new(0, 12, 4),
+            TaskWindow::new(0, 12, 4),
+            TaskWindow::new(0, 10, 3),
+        ];
+        assert!(not_first_not_last(&mut tasks).unwrap());
+        assert_eq!((tasks[2].est, tasks[2].lct), (0, 8));
+        assert_eq!(tasks[0], TaskWindow::new(0, 12, 4));
+    }
+
+    #[test]
+    fn test_propagate_model() {
+        let mut model = CpModel::new("jobshop", 100);
+        model.add_interval(IntervalVar::new("a", 0, 100, 5, 10));
+        model.add_interval(IntervalVar::new("b", 0, 100, 5, 10));
+        model.add_interval(IntervalVar::new("c", 0, 100, 3, 100));
+        model.add_interval(IntervalVar::new("d", 0, 100, 2, 100));
+        model.add_no_overlap(vec!["a".into(), "b".into(), "c".into()]);
+        model.add_precedence("c".into(), "d".into(), 1);
+
+        let windows = propagate(&model).unwrap();
+        assert_eq!(windows["c"].est, 10);
+        // c가 밀리면서 d도 선행 제약으로 밀림
+        assert_eq!(windows["d"].est, 14);
+        // 워커 풀에서 전파해도 같은 시간창
+        assert_eq!(
+            propagate_with(&model, &Parallelism::threads(2)).unwrap(),
+            windows
+        );
+
+        model.add_interval(IntervalVar::new("e", 0, 100, 4, 10));
+        model.add_no_overlap(vec!["a".into(), "e".into()]);
+        model.add_no_overlap(vec!["b".into(), "e".into()]);
+        model.add_no_overlap(vec!["a".into(), "b".into(), "e".into()]);
+        let conflict = propagate(&model).unwrap_err();
+        assert!(conflict.intervals.contains(&"e".to_string()));
+    }
+}
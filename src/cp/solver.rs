@@ -1,6 +1,11 @@
 //! CP Solver - Constraint Programming Solver Interface
 
-use crate::cp::model::CpModel;
+use crate::cp::model::{Constraint, CpModel, Objective};
+use crate::cp::propagation::{propagate_with, TaskWindow};
+use crate::cp::variables::IntervalVar;
+use crate::parallel::Parallelism;
+use crate::scheduler::{relative_gap, Incumbent, TerminationReason};
+use crate::telemetry::{self, Stopwatch};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -39,7 +44,7 @@ pub struct IntervalSolution {
 pub struct CpSolution {
     /// 상태
     pub status: SolverStatus,
-    /// 목적 함수 값
+    /// 목적 함수 값 (현재 해)
     pub objective_value: Option<f64>,
     /// 간격 변수 해
     pub intervals: HashMap<String, IntervalSolution>,
@@ -51,6 +56,15 @@ pub struct CpSolution {
     pub solve_time_ms: i64,
     /// 탐색된 노드 수
     pub num_nodes: u64,
+    /// 목적 함수 하한 (증명된 최선 경계)
+    #[serde(default)]
+    pub best_bound: Option<f64>,
+    /// 현재 해와 하한의 상대 갭
+    #[serde(default)]
+    pub gap: Option<f64>,
+    /// 종료 사유
+    #[serde(default)]
+    pub termination: TerminationReason,
 }
 
 impl CpSolution {
@@ -64,6 +78,15 @@ impl CpSolution {
             bool_vars: HashMap::new(),
             solve_time_ms: 0,
             num_nodes: 0,
+            best_bound: None,
+            gap: None,
+            termination: match status {
+                SolverStatus::Infeasible | SolverStatus::ModelInvalid => {
+                    TerminationReason::NoSolution
+                }
+                SolverStatus::Timeout => TerminationReason::TimeLimit,
+                _ => TerminationReason::Completed,
+            },
         }
     }
 
@@ -90,12 +113,15 @@ pub struct SolverConfig {
     pub time_limit_ms: i64,
     /// 최대 탐색 노드 수
     pub max_nodes: u64,
-    /// 병렬 스레드 수
+    /// 병렬 스레드 수 (외부 솔버용; 내장 솔버는 `parallelism` 풀을 사용)
     pub num_workers: usize,
     /// 로그 출력
     pub log_search: bool,
     /// 첫 해만 찾기
     pub stop_after_first: bool,
+    /// 전파 워커가 실행될 스레드 풀 (직렬화하지 않음)
+    #[serde(skip)]
+    pub parallelism: Parallelism,
 }
 
 impl Default for SolverConfig {
@@ -106,14 +132,47 @@ impl Default for SolverConfig {
             num_workers: 4,
             log_search: false,
             stop_after_first: false,
+            parallelism: Parallelism::sequential(),
         }
     }
 }
 
+impl SolverConfig {
+    /// 전파 워커가 실행될 스레드 풀 설정
+    pub fn with_parallelism(mut self, parallelism: Parallelism) -> Self {
+        self.num_workers = parallelism.workers();
+        self.parallelism = parallelism;
+        self
+    }
+}
+
 /// CP Solver 트레이트
 pub trait CpSolver {
     /// 모델 해결
     fn solve(&self, model: &CpModel, config: &SolverConfig) -> CpSolution;
+
+    /// 모델 해결 (개선된 해마다 `on_incumbent` 호출)
+    ///
+    /// 기본 구현은 최종 해를 한 번만 보고한다.
+    fn solve_streaming(
+        &self,
+        model: &CpModel,
+        config: &SolverConfig,
+        on_incumbent: &mut dyn FnMut(Incumbent<CpSolution>),
+    ) -> CpSolution {
+        let solution = self.solve(model, config);
+        if solution.is_solution_found() {
+            on_incumbent(Incumbent {
+                solution: solution.clone(),
+                objective: solution
+                    .objective_value
+                    .unwrap_or(solution.makespan() as f64),
+                iteration: solution.num_nodes as usize,
+                elapsed_ms: solution.solve_time_ms,
+            });
+        }
+        solution
+    }
 }
 
 /// 기본 CP Solver (간단한 휴리스틱)
@@ -132,41 +191,676 @@ impl Default for SimpleCpSolver {
 }
 
 impl CpSolver for SimpleCpSolver {
-    fn solve(&self, model: &CpModel, _config: &SolverConfig) -> CpSolution {
+    fn solve(&self, model: &CpModel, config: &SolverConfig) -> CpSolution {
+        let clock = Stopwatch::start();
+        let mut solution = self.place(model, None, &config.parallelism);
+        solution.solve_time_ms = clock.elapsed_ms();
+        telemetry::cp_solve(&solution);
+        solution
+    }
+}
+
+impl SimpleCpSolver {
+    /// 수정된 모델 재해결 (이전 해를 웜 스타트로 재사용)
+    ///
+    /// 이미 푼 모델에 간격/제약을 추가한 뒤 호출한다. 배치 순서대로
+    /// 진행하며 이전 위치가 여전히 모든 제약을 만족하는 단순 간격은
+    /// 그대로 유지하고 (노드로 세지 않음), 나머지만 다시 배치한다.
+    /// 대안, 갱, 인계 간격은 항상 다시 배치한다.
+    pub fn resolve(
+        &self,
+        model: &CpModel,
+        previous: &CpSolution,
+        config: &SolverConfig,
+    ) -> CpSolution {
+        let clock = Stopwatch::start();
+        let warm = previous.is_solution_found().then_some(previous);
+        let mut solution = self.place(model, warm, &config.parallelism);
+        solution.solve_time_ms = clock.elapsed_ms();
+        telemetry::cp_solve(&solution);
+        solution
+    }
+
+    /// 그리디 배치 (노드 = 배치 결정 1회)
+    fn place(
+        &self,
+        model: &CpModel,
+        previous: Option<&CpSolution>,
+        parallelism: &Parallelism,
+    ) -> CpSolution {
         // 모델 유효성 검사
         if model.validate().is_err() {
             return CpSolution::empty(SolverStatus::ModelInvalid);
         }
 
-        // 간단한 그리디 휴리스틱
-        // 실제 구현에서는 CP solver를 사용
+        // 단일 간격이 용량을 초과하면 실행 불가능
+        for constraint in &model.constraints {
+            if let Constraint::Cumulative {
+                demands, capacity, ..
+            } = constraint
+            {
+                if demands.iter().any(|d| d > capacity) {
+                    return CpSolution::empty(SolverStatus::Infeasible);
+                }
+            }
+        }
+
+        // 이접 전파 (edge-finding, not-first/not-last)로 시간창을 조임
+        let tightened = match propagate_with(model, parallelism) {
+            Ok(windows) => tighten(model, &windows),
+            Err(_) => return CpSolution::empty(SolverStatus::Infeasible),
+        };
+        let model = &tightened;
+
+        let order = match placement_order(model) {
+            Some(order) => order,
+            None => return CpSolution::empty(SolverStatus::Infeasible), // 선행 순환
+        };
+
+        // 그리디 배치: 선행/비중첩/누적 제약을 만족하는 가장 이른 시작
         let mut solution = CpSolution::empty(SolverStatus::Feasible);
-        let mut current_time: HashMap<String, i64> = HashMap::new();
+        let mut within_bounds = true;
+        let alternatives_of = alternatives(model);
+        let gangs_of = gangs(model);
+        let mut chosen: HashMap<&str, usize> = HashMap::new();
 
-        // 모든 간격을 순차적으로 배치 (매우 단순한 휴리스틱)
-        for (name, interval) in &model.intervals {
-            let start = interval.start.min;
+        for name in order {
+            if solution.intervals.contains_key(name) {
+                continue; // 갱 멤버로 이미 배치됨
+            }
+            let interval = &model.intervals[name];
             let duration = interval.duration.fixed.unwrap_or(interval.duration.min);
-            let end = start + duration;
+            let earliest = precedence_earliest(model, &solution, name);
+
+            // 웜 스타트: 이전 위치가 여전히 유효하면 재사용
+            let reusable = previous
+                .and_then(|p| p.intervals.get(name.as_str()))
+                .filter(|prev| {
+                    prev.is_present
+                        && prev.duration == duration
+                        && prev.start >= earliest
+                        && prev.start <= interval.start.max
+                        && prev.end <= interval.end.max
+                        && !gangs_of.contains_key(name.as_str())
+                        && !alternatives_of.contains_key(name.as_str())
+                        && !is_handoff_target(model, name)
+                        && earliest_feasible_start(model, &solution, name, prev.start, duration)
+                            == prev.start
+                });
+            if let Some(prev) = reusable {
+                solution.intervals.insert(name.clone(), prev.clone());
+                continue;
+            }
+            solution.num_nodes += 1;
+
+            if let Some(members) = gangs_of.get(name.as_str()) {
+                let start = gang_start(model, &solution, members, earliest);
+                for member in members {
+                    let var = &model.intervals[member];
+                    let member_duration = var.duration.fixed.unwrap_or(var.duration.min);
+                    if start > var.start.max || start + member_duration > var.end.max {
+                        within_bounds = false;
+                    }
+                    solution.intervals.insert(
+                        member.clone(),
+                        IntervalSolution {
+                            start,
+                            end: start + member_duration,
+                            duration: member_duration,
+                            is_present: true,
+                        },
+                    );
+                }
+                continue;
+            }
+
+            if let Some(alts) = alternatives_of.get(name.as_str()) {
+                // 동일 대안 제약이 걸린 상대가 이미 배치되었으면 같은 위치만 허용
+                let pinned = model.constraints.iter().find_map(|c| match c {
+                    Constraint::SameAlternative { main1, main2 } if main1 == name => {
+                        chosen.get(main2.as_str()).copied()
+                    }
+                    Constraint::SameAlternative { main1, main2 } if main2 == name => {
+                        chosen.get(main1.as_str()).copied()
+                    }
+                    _ => None,
+                });
+
+                // 비용 목적이면 종료 시간 + 가중 비용, 아니면 가장 이른 시작
+                let mut best: Option<(f64, i64, usize)> = None;
+                for (idx, alt) in alts.iter().enumerate() {
+                    if pinned.is_some_and(|k| k != idx) {
+                        continue;
+                    }
+                    let var = &model.intervals[alt];
+                    let alt_duration = var.duration.fixed.unwrap_or(var.duration.min);
+                    let start = earliest_feasible_start(
+                        model,
+                        &solution,
+                        alt,
+                        earliest.max(var.start.min),
+                        alt_duration,
+                    );
+                    let score = match cost_term(model, alt) {
+                        Some(cost) => (start + alt_duration) as f64 + cost,
+                        None => start as f64,
+                    };
+                    if best.is_none_or(|(s, _, _)| score < s) {
+                        best = Some((score, start, idx));
+                    }
+                }
+                let Some((_, start, idx)) = best else {
+                    return CpSolution::empty(SolverStatus::Infeasible);
+                };
+
+                for (i, alt) in alts.iter().enumerate() {
+                    let var = &model.intervals[alt];
+                    let alt_duration = var.duration.fixed.unwrap_or(var.duration.min);
+                    if i == idx && (start > var.start.max || start + alt_duration > var.end.max) {
+                        within_bounds = false;
+                    }
+                    solution.intervals.insert(
+                        alt.clone(),
+                        IntervalSolution {
+                            start,
+                            end: start + alt_duration,
+                            duration: alt_duration,
+                            is_present: i == idx,
+                        },
+                    );
+                }
+                let alt_duration = solution.intervals[&alts[idx]].duration;
+                if start > interval.start.max || start + alt_duration > interval.end.max {
+                    within_bounds = false;
+                }
+                solution.intervals.insert(
+                    name.clone(),
+                    IntervalSolution {
+                        start,
+                        end: start + alt_duration,
+                        duration: alt_duration,
+                        is_present: true,
+                    },
+                );
+                chosen.insert(name.as_str(), idx);
+                continue;
+            }
+
+            // 최대 지연은 before 종료 이후로만 허용 오차를 두는 인계로 처리
+            let handoff = model.constraints.iter().find_map(|c| match c {
+                Constraint::Handoff {
+                    before,
+                    after,
+                    tolerance,
+                }
+                | Constraint::MaxDelay {
+                    before,
+                    after,
+                    max_delay: tolerance,
+                } if after == name => solution
+                    .intervals
+                    .get(before)
+                    .map(|b| (before.as_str(), b.end, *tolerance)),
+                _ => None,
+            });
+            let start = match handoff {
+                Some((before, before_end, tolerance)) => {
+                    let earliest = earliest.max(before_end - tolerance);
+                    place_handoff(
+                        model,
+                        &mut solution,
+                        name,
+                        before,
+                        earliest,
+                        duration,
+                        tolerance,
+                    )
+                    .unwrap_or_else(|| {
+                        // 동기화 실패: 가장 이른 위치에 두고 상태를 Unknown으로
+                        within_bounds = false;
+                        earliest_feasible_start(model, &solution, name, earliest, duration)
+                    })
+                }
+                None => earliest_feasible_start(model, &solution, name, earliest, duration),
+            };
+            if start > interval.start.max || start + duration > interval.end.max {
+                within_bounds = false;
+            }
 
             solution.intervals.insert(
                 name.clone(),
                 IntervalSolution {
                     start,
-                    end,
+                    end: start + duration,
                     duration,
                     is_present: true,
                 },
             );
+        }
 
-            current_time.insert(name.clone(), end);
+        // 도메인 경계를 벗어나면 실행 가능성을 보장할 수 없음
+        if !within_bounds {
+            solution.status = SolverStatus::Unknown;
         }
+        let cost: f64 = solution
+            .intervals
+            .iter()
+            .filter(|(_, sol)| sol.is_present)
+            .filter_map(|(name, _)| cost_term(model, name))
+            .sum();
+        solution.objective_value = Some(solution.makespan() as f64 + cost);
 
-        solution.objective_value = Some(solution.makespan() as f64);
+        // 하한과 같으면 makespan 최소화 문제의 최적해
+        let bound = makespan_bound(model) as f64;
+        solution.best_bound = Some(bound);
+        solution.gap = relative_gap(solution.makespan() as f64, bound);
+        if solution.status == SolverStatus::Feasible
+            && matches!(model.objective, Some(Objective::MinimizeMakespan))
+            && solution.gap == Some(0.0)
+        {
+            solution.status = SolverStatus::Optimal;
+            solution.termination = TerminationReason::ProvenOptimal;
+        }
         solution
     }
 }
 
+/// 비용 목적의 가중 간격 비용 (비용 목적이 아니면 None)
+fn cost_term(model: &CpModel, name: &str) -> Option<f64> {
+    match &model.objective {
+        Some(Objective::MinimizeMakespanAndCost { costs, weight }) => {
+            Some(weight * costs.get(name).copied().unwrap_or(0.0))
+        }
+        _ => None,
+    }
+}
+
+/// Makespan 하한: 가장 이른 종료와 비중첩 자원의 총 작업량 중 최대
+///
+/// 선택적 간격과 대안 간격은 제외한다.
+fn makespan_bound(model: &CpModel) -> i64 {
+    let alternatives_of = alternatives(model);
+    let excluded: std::collections::HashSet<&str> = alternatives_of
+        .iter()
+        .flat_map(|(main, members)| {
+            std::iter::once(*main).chain(members.iter().map(String::as_str))
+        })
+        .collect();
+    let fixed = |name: &str| {
+        model
+            .intervals
+            .get(name)
+            .filter(|v| !v.is_optional && !excluded.contains(name))
+    };
+    let duration = |v: &IntervalVar| v.duration.fixed.unwrap_or(v.duration.min);
+
+    let earliest_end = model
+        .intervals
+        .keys()
+        .filter_map(|n| fixed(n))
+        .map(|v| (v.start.min + duration(v)).max(v.end.min))
+        .max()
+        .unwrap_or(0);
+    let load = model
+        .constraints
+        .iter()
+        .filter_map(|c| match c {
+            Constraint::NoOverlap { intervals, .. } => {
+                let members: Vec<_> = intervals.iter().filter_map(|n| fixed(n)).collect();
+                let head = members.iter().map(|v| v.start.min).min()?;
+                Some(head + members.iter().map(|v| duration(v)).sum::<i64>())
+            }
+            _ => None,
+        })
+        .max()
+        .unwrap_or(0);
+    earliest_end.max(load)
+}
+
+/// 전파된 시간창을 반영한 모델 (시작 하한, 종료 상한)
+fn tighten(model: &CpModel, windows: &HashMap<String, TaskWindow>) -> CpModel {
+    let mut tightened = model.clone();
+    for (name, var) in tightened.intervals.iter_mut() {
+        if let Some(window) = windows.get(name) {
+            var.start.min = var.start.min.max(window.est);
+            var.end.max = var.end.max.min(window.lct);
+        }
+    }
+    tightened
+}
+
+/// 인계/최대 지연 제약의 후행 간격인지 여부
+fn is_handoff_target(model: &CpModel, name: &str) -> bool {
+    model.constraints.iter().any(|c| {
+        matches!(c, Constraint::Handoff { after, .. } | Constraint::MaxDelay { after, .. } if after == name)
+    })
+}
+
+/// 주 간격별 대안 간격 목록
+fn alternatives(model: &CpModel) -> HashMap<&str, &Vec<String>> {
+    model
+        .constraints
+        .iter()
+        .filter_map(|c| match c {
+            Constraint::Alternative { main, alternatives } => Some((main.as_str(), alternatives)),
+            _ => None,
+        })
+        .collect()
+}
+
+/// 동시 시작 제약으로 묶인 갱 (간격 이름 -> 정렬된 멤버 목록)
+fn gangs(model: &CpModel) -> HashMap<&str, Vec<String>> {
+    let mut adjacency: HashMap<&str, Vec<&str>> = HashMap::new();
+    for constraint in &model.constraints {
+        if let Constraint::SameStart {
+            interval1,
+            interval2,
+        } = constraint
+        {
+            adjacency
+                .entry(interval1.as_str())
+                .or_default()
+                .push(interval2.as_str());
+            adjacency
+                .entry(interval2.as_str())
+                .or_default()
+                .push(interval1.as_str());
+        }
+    }
+
+    let mut groups: HashMap<&str, Vec<String>> = HashMap::new();
+    for &root in adjacency.keys() {
+        if groups.contains_key(root) {
+            continue;
+        }
+        let mut members = vec![root];
+        let mut i = 0;
+        while i < members.len() {
+            for &next in &adjacency[members[i]] {
+                if !members.contains(&next) {
+                    members.push(next);
+                }
+            }
+            i += 1;
+        }
+        let mut names: Vec<String> = members.iter().map(|m| m.to_string()).collect();
+        names.sort();
+        for member in members {
+            groups.insert(member, names.clone());
+        }
+    }
+    groups
+}
+
+/// 선행 제약과 시작 하한에 따른 가장 이른 시작
+fn precedence_earliest(model: &CpModel, solution: &CpSolution, name: &str) -> i64 {
+    let var = &model.intervals[name];
+    let duration = var.duration.fixed.unwrap_or(var.duration.min);
+    let mut earliest = var.start.min;
+    for constraint in &model.constraints {
+        match constraint {
+            Constraint::Precedence {
+                before,
+                after,
+                min_delay,
+            } if after == name => {
+                if let Some(pred) = solution.intervals.get(before) {
+                    earliest = earliest.max(pred.end + min_delay);
+                }
+            }
+            Constraint::MaxDelay { before, after, .. } if after == name => {
+                if let Some(pred) = solution.intervals.get(before) {
+                    earliest = earliest.max(pred.end);
+                }
+            }
+            // 겹침 허용: lead 만큼 먼저 시작하되 선행 작업보다 먼저 끝나지 않음
+            Constraint::Overlap {
+                before,
+                after,
+                lead,
+            } if after == name => {
+                if let Some(pred) = solution.intervals.get(before) {
+                    earliest = earliest.max(pred.end - lead).max(pred.end - duration);
+                }
+            }
+            _ => {}
+        }
+    }
+    earliest
+}
+
+/// 갱의 모든 멤버가 동시에 시작할 수 있는 가장 이른 시간
+fn gang_start(model: &CpModel, solution: &CpSolution, members: &[String], earliest: i64) -> i64 {
+    let mut start = members
+        .iter()
+        .map(|m| precedence_earliest(model, solution, m))
+        .fold(earliest, i64::max);
+
+    // 각 멤버의 가장 이른 시작이 모두 같아질 때까지 반복 (종료 시점 수로 유한)
+    for _ in 0..=solution.intervals.len() {
+        let next = members
+            .iter()
+            .map(|m| {
+                let var = &model.intervals[m];
+                let duration = var.duration.fixed.unwrap_or(var.duration.min);
+                earliest_feasible_start(model, solution, m, start, duration)
+            })
+            .fold(start, i64::max);
+        if next == start {
+            break;
+        }
+        start = next;
+    }
+    start
+}
+
+/// 선행 제약에 따른 위상 정렬 (동순위는 최소 시작, 이름 순)
+///
+/// 대안 간격은 주 간격과 함께 배치되므로 제외
+fn placement_order(model: &CpModel) -> Option<Vec<&String>> {
+    let members: std::collections::HashSet<&String> =
+        alternatives(model).into_values().flatten().collect();
+    let mut names: Vec<&String> = model
+        .intervals
+        .keys()
+        .filter(|n| !members.contains(n))
+        .collect();
+    names.sort_by_key(|n| (model.intervals[*n].start.min, (*n).clone()));
+
+    let mut in_degree: HashMap<&str, usize> = names.iter().map(|n| (n.as_str(), 0)).collect();
+    for constraint in &model.constraints {
+        if let Constraint::Precedence { after, .. }
+        | Constraint::Handoff { after, .. }
+        | Constraint::MaxDelay { after, .. }
+        | Constraint::Overlap { after, .. } = constraint
+        {
+            *in_degree.entry(after.as_str()).or_insert(0) += 1;
+        }
+    }
+
+    let mut order = Vec::with_capacity(names.len());
+    let mut placed: std::collections::HashSet<&str> = std::collections::HashSet::new();
+    while order.len() < names.len() {
+        let next = names
+            .iter()
+            .find(|n| !placed.contains(n.as_str()) && in_degree[n.as_str()] == 0)?;
+        placed.insert(next.as_str());
+        order.push(*next);
+
+        for constraint in &model.constraints {
+            if let Constraint::Precedence { before, after, .. }
+            | Constraint::Handoff { before, after, .. }
+            | Constraint::MaxDelay { before, after, .. }
+            | Constraint::Overlap { before, after, .. } = constraint
+            {
+                if before == *next {
+                    if let Some(d) = in_degree.get_mut(after.as_str()) {
+                        *d -= 1;
+                    }
+                }
+            }
+        }
+    }
+    Some(order)
+}
+
+/// 이미 배치된 간격과 충돌하지 않는 가장 이른 시작 시간
+fn earliest_feasible_start(
+    model: &CpModel,
+    solution: &CpSolution,
+    name: &str,
+    earliest: i64,
+    duration: i64,
+) -> i64 {
+    // 후보: earliest 및 배치된 간격의 종료 시점
+    let mut candidates: Vec<i64> = solution
+        .intervals
+        .values()
+        .filter(|s| s.is_present)
+        .map(|s| s.end)
+        .filter(|&end| end > earliest)
+        .collect();
+    candidates.push(earliest);
+    candidates.sort();
+    candidates.dedup();
+
+    candidates
+        .into_iter()
+        .find(|&start| fits(model, solution, name, start, start + duration))
+        .unwrap_or(earliest)
+}
+
+/// 인계 동기화 배치: after 시작이 before 종료 ± tolerance 이내가 되도록
+/// 가장 이른 위치를 찾고, 필요하면 before를 뒤로 이동
+fn place_handoff(
+    model: &CpModel,
+    solution: &mut CpSolution,
+    name: &str,
+    before: &str,
+    earliest: i64,
+    duration: i64,
+    tolerance: i64,
+) -> Option<i64> {
+    let mut candidates: Vec<i64> = solution
+        .intervals
+        .values()
+        .filter(|s| s.is_present)
+        .map(|s| s.end)
+        .filter(|&end| end > earliest)
+        .collect();
+    candidates.push(earliest);
+    candidates.sort();
+    candidates.dedup();
+
+    let placed_before = solution.intervals[before].clone();
+    for t in candidates {
+        if !fits(model, solution, name, t, t + duration) {
+            continue;
+        }
+        if t <= placed_before.end + tolerance {
+            return Some(t);
+        }
+
+        // before가 t - tolerance에 끝나도록 이동 가능한지 검사
+        let new_end = t - tolerance;
+        let new_start = new_end - placed_before.duration;
+        let mut trial = solution.clone();
+        trial.intervals.remove(before);
+        if !fits(model, &trial, before, new_start, new_end) {
+            continue;
+        }
+        let successors_ok = model.constraints.iter().all(|c| match c {
+            Constraint::Precedence {
+                before: b,
+                after,
+                min_delay,
+            } if b == before => trial
+                .intervals
+                .get(after)
+                .is_none_or(|s| s.start >= new_end + min_delay),
+            Constraint::MaxDelay {
+                before: b, after, ..
+            } if b == before => trial
+                .intervals
+                .get(after)
+                .is_none_or(|s| s.start >= new_end),
+            Constraint::Overlap {
+                before: b,
+                after,
+                lead,
+            } if b == before => trial
+                .intervals
+                .get(after)
+                .is_none_or(|s| s.start >= new_end - lead && s.end >= new_end),
+            _ => true,
+        });
+        if !successors_ok {
+            continue;
+        }
+        trial.intervals.insert(
+            before.to_string(),
+            IntervalSolution {
+                start: new_start,
+                end: new_end,
+                ..placed_before
+            },
+        );
+        if fits(model, &trial, name, t, t + duration) {
+            *solution = trial;
+            return Some(t);
+        }
+    }
+    None
+}
+
+/// 간격을 [start, end)에 배치할 수 있는지 검사
+fn fits(model: &CpModel, solution: &CpSolution, name: &str, start: i64, end: i64) -> bool {
+    for constraint in &model.constraints {
+        match constraint {
+            Constraint::NoOverlap { intervals, .. } if intervals.iter().any(|i| i == name) => {
+                let clash = intervals
+                    .iter()
+                    .filter_map(|other| solution.intervals.get(other))
+                    .any(|s| s.is_present && s.start < end && s.end > start);
+                if clash {
+                    return false;
+                }
+            }
+            Constraint::Cumulative {
+                intervals,
+                demands,
+                capacity,
+            } => {
+                let own = match intervals.iter().position(|i| i == name) {
+                    Some(idx) => demands[idx],
+                    None => continue,
+                };
+                // 부하는 간격 시작 시점에서만 증가하므로 해당 시점만 검사
+                let placed: Vec<(&IntervalSolution, i64)> = intervals
+                    .iter()
+                    .zip(demands)
+                    .filter_map(|(other, d)| solution.intervals.get(other).map(|s| (s, *d)))
+                    .filter(|(s, _)| s.is_present && s.start < end && s.end > start)
+                    .collect();
+                let mut points: Vec<i64> = placed.iter().map(|(s, _)| s.start.max(start)).collect();
+                points.push(start);
+                for t in points {
+                    let load: i64 = placed
+                        .iter()
+                        .filter(|(s, _)| s.start <= t && s.end > t)
+                        .map(|(_, d)| d)
+                        .sum();
+                    if load + own > *capacity {
+                        return false;
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    true
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -187,6 +881,110 @@ mod tests {
         assert_eq!(solution.intervals.len(), 2);
     }
 
+    #[test]
+    fn test_solve_streaming() {
+        let mut model = CpModel::new("test", 1_000_000);
+        model.add_interval(IntervalVar::new("op1", 0, 100_000, 50_000, 200_000));
+        model.minimize_makespan();
+
+        let mut incumbents = Vec::new();
+        let solution =
+            SimpleCpSolver::new().solve_streaming(&model, &SolverConfig::default(), &mut |i| {
+                incumbents.push(i)
+            });
+
+        // 그리디 솔버는 최종 해 하나만 보고
+        assert_eq!(incumbents.len(), 1);
+        assert_eq!(incumbents[0].solution.makespan(), solution.makespan());
+    }
+
+    #[test]
+    fn test_disjunctive_propagation() {
+        // 10 ms 창에 5 + 5 + 4 ms: 그리디 전에 과부하로 실행 불가능 판정
+        let mut model = CpModel::new("test", 100);
+        model.add_interval(IntervalVar::new("a", 0, 10, 5, 10));
+        model.add_interval(IntervalVar::new("b", 0, 10, 5, 10));
+        model.add_interval(IntervalVar::new("e", 0, 10, 4, 10));
+        model.add_no_overlap(vec!["a".into(), "b".into(), "e".into()]);
+        let solution = SimpleCpSolver::new().solve(&model, &SolverConfig::default());
+        assert_eq!(solution.status, SolverStatus::Infeasible);
+
+        // edge-finding: 마감이 늦은 a2가 a, b 뒤로 밀려 a, b가 마감을 지킴
+        // (전파 없이는 이름 순으로 a, a2, b가 배치되어 b가 마감 초과)
+        let mut model = CpModel::new("test", 100);
+        model.add_interval(IntervalVar::new("a2", 0, 100, 3, 100));
+        model.add_interval(IntervalVar::new("a", 0, 10, 5, 10));
+        model.add_interval(IntervalVar::new("b", 0, 10, 5, 10));
+        model.add_no_overlap(vec!["a".into(), "b".into(), "a2".into()]);
+        let solution = SimpleCpSolver::new().solve(&model, &SolverConfig::default());
+        assert_eq!(solution.status, SolverStatus::Feasible);
+        assert_eq!(solution.intervals["a2"].start, 10);
+    }
+
+    #[test]
+    fn test_bound_and_gap() {
+        // 선행 사슬 a -> b (15 ms)와 c가 같은 자원 (a, c)
+        let mut model = CpModel::new("test", 100);
+        model.add_interval(IntervalVar::new("a", 0, 100, 5, 100));
+        model.add_interval(IntervalVar::new("b", 0, 100, 10, 100));
+        model.add_interval(IntervalVar::new("c", 0, 100, 8, 100));
+        model.add_precedence("a".into(), "b".into(), 0);
+        model.add_no_overlap(vec!["a".into(), "c".into()]);
+        let solution = SimpleCpSolver::new().solve(&model, &SolverConfig::default());
+
+        // 목적 함수가 없으면 하한만 보고
+        assert_eq!(solution.status, SolverStatus::Feasible);
+        assert_eq!(solution.best_bound, Some(15.0));
+        assert_eq!(solution.gap, Some(0.0));
+        assert_eq!(solution.termination, TerminationReason::Completed);
+
+        model.minimize_makespan();
+        let solution = SimpleCpSolver::new().solve(&model, &SolverConfig::default());
+        assert_eq!(solution.status, SolverStatus::Optimal);
+        assert_eq!(solution.termination, TerminationReason::ProvenOptimal);
+
+        let infeasible = CpSolution::empty(SolverStatus::Infeasible);
+        assert_eq!(infeasible.termination, TerminationReason::NoSolution);
+    }
+
+    #[test]
+    fn test_resolve_incremental() {
+        let mut model = CpModel::new("test", 1_000_000);
+        for i in 1..=4 {
+            model.add_interval(IntervalVar::new(
+                format!("op{}", i),
+                0,
+                100_000,
+                10_000,
+                200_000,
+            ));
+        }
+        model.add_no_overlap(vec!["op1".into(), "op2".into()]);
+        model.minimize_makespan();
+        let solver = SimpleCpSolver::new();
+        let config = SolverConfig::default();
+        let first = solver.solve(&model, &config);
+
+        // 새 간격과 선행 제약 추가: op3 이후 op5, op4는 op3 이후로 이동
+        model.add_interval(IntervalVar::new("op5", 0, 100_000, 5_000, 200_000));
+        model.add_precedence("op3".into(), "op5".into(), 0);
+        model.add_precedence("op3".into(), "op4".into(), 0);
+        let second = solver.resolve(&model, &first, &config);
+
+        assert!(second.is_solution_found());
+        for name in ["op1", "op2", "op3"] {
+            assert_eq!(second.intervals[name].start, first.intervals[name].start);
+        }
+        assert_eq!(second.intervals["op4"].start, 10_000);
+        assert_eq!(second.intervals["op5"].start, 10_000);
+        // op4, op5만 다시 배치
+        assert_eq!(second.num_nodes, 2);
+
+        // 처음부터 푼 결과와 같은 makespan
+        let scratch = solver.solve(&model, &config);
+        assert_eq!(second.makespan(), scratch.makespan());
+    }
+
     #[test]
     fn test_solution_makespan() {
         let mut solution = CpSolution::empty(SolverStatus::Feasible);
@@ -214,6 +1012,262 @@ mod tests {
         assert_eq!(solution.makespan(), 80_000);
     }
 
+    #[test]
+    fn test_solver_respects_constraints() {
+        let mut model = CpModel::new("test", 1_000_000);
+
+        model.add_interval(IntervalVar::new("op1", 0, 100_000, 50_000, 200_000));
+        model.add_interval(IntervalVar::new("op2", 0, 100_000, 30_000, 200_000));
+        model.add_interval(IntervalVar::new("op3", 0, 100_000, 10_000, 200_000));
+        model.add_no_overlap(vec!["op1".into(), "op2".into()]);
+        model.add_precedence("op2".into(), "op3".into(), 5_000);
+
+        let solution = SimpleCpSolver::new().solve(&model, &SolverConfig::default());
+        let op1 = &solution.intervals["op1"];
+        let op2 = &solution.intervals["op2"];
+        let op3 = &solution.intervals["op3"];
+
+        assert!(op1.end <= op2.start || op2.end <= op1.start);
+        assert!(op3.start >= op2.end + 5_000);
+    }
+
+    #[test]
+    fn test_solver_cumulative() {
+        let mut model = CpModel::new("test", 1_000_000);
+
+        model.add_interval(IntervalVar::new("a", 0, 100_000, 10_000, 200_000));
+        model.add_interval(IntervalVar::new("b", 0, 100_000, 10_000, 200_000));
+        model.add_interval(IntervalVar::new("c", 0, 100_000, 10_000, 200_000));
+        model.add_cumulative(vec!["a".into(), "b".into(), "c".into()], vec![2, 2, 1], 3);
+
+        let solution = SimpleCpSolver::new().solve(&model, &SolverConfig::default());
+        assert!(solution.is_solution_found());
+        // a와 b는 동시에 실행될 수 없음
+        let (a, b) = (&solution.intervals["a"], &solution.intervals["b"]);
+        assert!(a.end <= b.start || b.end <= a.start);
+        assert_eq!(solution.makespan(), 20_000);
+
+        model.add_cumulative(vec!["a".into()], vec![5], 3);
+        let solution = SimpleCpSolver::new().solve(&model, &SolverConfig::default());
+        assert_eq!(solution.status, SolverStatus::Infeasible);
+    }
+
+    #[test]
+    fn test_renewable_pools() {
+        use crate::models::{Activity, RenewablePool, Task};
+
+        let tasks = vec![Task::new("P")
+            .with_activity(Activity::new("a", "P", 1).with_pool_demand("crew", 3))
+            .with_activity(Activity::new("b", "P", 2).with_pool_demand("crew", 2))
+            .with_activity(Activity::new("c", "P", 3).with_pool_demand("crew", 1))];
+        let mut model = CpModel::new("test", 1_000_000);
+        for name in ["a", "b", "c"] {
+            model.add_interval(IntervalVar::new(name, 0, 100_000, 10_000, 200_000));
+        }
+        model.add_renewable_pools(&tasks, &[RenewablePool::new("crew", 4)]);
+
+        let solution = SimpleCpSolver::new().solve(&model, &SolverConfig::default());
+        assert!(solution.is_solution_found());
+        // a(3)와 b(2)는 동시에 실행될 수 없음
+        let (a, b) = (&solution.intervals["a"], &solution.intervals["b"]);
+        assert!(a.end <= b.start || b.end <= a.start);
+        assert_eq!(solution.makespan(), 20_000);
+    }
+
+    #[test]
+    fn test_gang() {
+        let mut model = CpModel::new("lift", 1_000_000);
+
+        // 크레인 2대가 동시에 필요한 인양 작업; crane1은 먼저 다른 작업 수행
+        model.add_interval(IntervalVar::new("a-other", 0, 0, 10_000, 200_000));
+        model.add_interval(IntervalVar::new("lift-c1", 0, 100_000, 5_000, 200_000));
+        model.add_interval(IntervalVar::new("lift-c2", 0, 100_000, 5_000, 200_000));
+        model.add_no_overlap(vec!["a-other".into(), "lift-c1".into()]);
+        model.add_no_overlap(vec!["lift-c2".into()]);
+        model.add_gang(vec!["lift-c1".into(), "lift-c2".into()]);
+
+        let solution = SimpleCpSolver::new().solve(&model, &SolverConfig::default());
+        assert_eq!(solution.status, SolverStatus::Feasible);
+        assert_eq!(solution.intervals["lift-c1"].start, 10_000);
+        assert_eq!(solution.intervals["lift-c2"].start, 10_000);
+    }
+
+    #[test]
+    fn test_pickup_delivery() {
+        let mut model = CpModel::new("vrptw", 1_000_000);
+
+        // 두 배송 건, 차량 V1/V2 대안
+        for shipment in ["S1", "S2"] {
+            for stop in ["P", "D"] {
+                let main = format!("{}-{}", shipment, stop);
+                model.add_interval(IntervalVar::new(&main, 0, 100_000, 10_000, 200_000));
+                let alts: Vec<String> = ["V1", "V2"]
+                    .iter()
+                    .map(|v| format!("{}@{}", main, v))
+                    .collect();
+                for alt in &alts {
+                    model.add_interval(
+                        IntervalVar::new(alt, 0, 100_000, 10_000, 200_000).as_optional(alt),
+                    );
+                }
+                model.add_alternative(main, alts);
+            }
+            model.add_pickup_delivery(format!("{}-P", shipment), format!("{}-D", shipment));
+        }
+        // 배송 시간 창: S2 배송은 50_000 이후
+        model.intervals.get_mut("S2-D").unwrap().start.min = 50_000;
+
+        let v1 = ["S1-P@V1", "S1-D@V1", "S2-P@V1", "S2-D@V1"];
+        let v2 = ["S1-P@V2", "S1-D@V2", "S2-P@V2", "S2-D@V2"];
+        model.add_no_overlap(v1.iter().map(|s| s.to_string()).collect());
+        model.add_no_overlap(v2.iter().map(|s| s.to_string()).collect());
+
+        let solution = SimpleCpSolver::new().solve(&model, &SolverConfig::default());
+        assert!(solution.is_solution_found());
+
+        for shipment in ["S1", "S2"] {
+            let (p, d) = (
+                &solution.intervals[&format!("{}-P", shipment)],
+                &solution.intervals[&format!("{}-D", shipment)],
+            );
+            assert!(d.start >= p.end);
+            for v in ["V1", "V2"] {
+                assert_eq!(
+                    solution.intervals[&format!("{}-P@{}", shipment, v)].is_present,
+                    solution.intervals[&format!("{}-D@{}", shipment, v)].is_present
+                );
+            }
+        }
+        assert!(solution.intervals["S2-D"].start >= 50_000);
+    }
+
+    #[test]
+    fn test_cost_objective() {
+        use crate::models::Resource;
+        use crate::scheduler::CostModel;
+
+        const HOUR: i64 = 3_600_000;
+        let resources = [
+            Resource::primary("Fast").with_cost(100.0),
+            Resource::primary("Cheap").with_cost(10.0),
+        ];
+        let mut model = CpModel::new("cost", 100 * HOUR);
+        model.add_interval(IntervalVar::new("op", 0, 10 * HOUR, HOUR, 20 * HOUR));
+        // 빠른 자원은 1시간, 저렴한 자원은 2시간
+        for (resource, duration) in [("Fast", HOUR), ("Cheap", 2 * HOUR)] {
+            let alt = format!("op@{}", resource);
+            model.add_interval(
+                IntervalVar::new(&alt, 0, 10 * HOUR, duration, 20 * HOUR).as_optional(&alt),
+            );
+        }
+        model.add_alternative("op".into(), vec!["op@Fast".into(), "op@Cheap".into()]);
+
+        model.minimize_makespan();
+        let fastest = SimpleCpSolver::new().solve(&model, &SolverConfig::default());
+        assert!(fastest.intervals["op@Fast"].is_present);
+
+        let cost_model = CostModel::new();
+        let costs = resources
+            .iter()
+            .map(|r| {
+                let alt = format!("op@{}", r.id);
+                let duration = model.intervals[&alt].duration.min;
+                (alt, cost_model.resource_time_cost(r, duration))
+            })
+            .collect();
+        model.minimize_cost(costs, 100_000.0);
+        let cheapest = SimpleCpSolver::new().solve(&model, &SolverConfig::default());
+        assert!(cheapest.intervals["op@Cheap"].is_present);
+        assert!(!cheapest.intervals["op@Fast"].is_present);
+        assert_eq!(cheapest.makespan(), 2 * HOUR);
+        assert_eq!(
+            cheapest.objective_value,
+            Some((2 * HOUR) as f64 + 100_000.0 * 20.0)
+        );
+
+        // 정의되지 않은 간격의 비용은 모델 오류
+        model.minimize_cost(HashMap::from([("unknown".to_string(), 1.0)]), 1.0);
+        assert!(model.validate().is_err());
+    }
+
+    #[test]
+    fn test_handoff() {
+        let mut model = CpModel::new("transfer", 1_000_000);
+
+        // 수술(OR) 후 이송(porter): porter는 먼저 다른 이송으로 30_000까지 점유
+        model.add_interval(IntervalVar::new("surgery", 0, 100_000, 10_000, 200_000));
+        model.add_interval(IntervalVar::new("a-other", 0, 100_000, 30_000, 200_000));
+        model.add_interval(IntervalVar::new("transfer", 0, 100_000, 5_000, 200_000));
+        model.add_no_overlap(vec!["a-other".into(), "transfer".into()]);
+        model.add_handoff("surgery".into(), "transfer".into(), 1_000);
+
+        let solution = SimpleCpSolver::new().solve(&model, &SolverConfig::default());
+        assert!(solution.is_solution_found());
+
+        let (surgery, transfer) = (
+            &solution.intervals["surgery"],
+            &solution.intervals["transfer"],
+        );
+        assert!((transfer.start - surgery.end).abs() <= 1_000);
+        assert!(transfer.start >= 30_000);
+    }
+
+    #[test]
+    fn test_overlap() {
+        let mut model = CpModel::new("transfer-batch", 1_000_000);
+
+        // 절단 10_000 중 마지막 4_000과 용접이 겹칠 수 있음; 짧은 검사는 절단보다 먼저 끝날 수 없음
+        model.add_interval(IntervalVar::new("cut", 0, 100_000, 10_000, 200_000));
+        model.add_interval(IntervalVar::new("weld", 0, 100_000, 10_000, 200_000));
+        model.add_interval(IntervalVar::new("inspect", 0, 100_000, 1_000, 200_000));
+        model.add_overlap("cut".into(), "weld".into(), 4_000);
+        model.add_overlap("cut".into(), "inspect".into(), 4_000);
+
+        let solution = SimpleCpSolver::new().solve(&model, &SolverConfig::default());
+        assert!(solution.is_solution_found());
+        assert_eq!(solution.intervals["weld"].start, 6_000);
+        assert_eq!(solution.intervals["inspect"].start, 9_000);
+        assert_eq!(solution.makespan(), 16_000);
+    }
+
+    #[test]
+    fn test_max_delay() {
+        let mut model = CpModel::new("paint", 1_000_000);
+
+        // 도장 후 30_000 이내에 건조로 투입; 건조로는 60_000까지 점유
+        model.add_interval(IntervalVar::new("coat", 0, 100_000, 10_000, 200_000));
+        model.add_interval(IntervalVar::new("a-other", 0, 100_000, 60_000, 200_000));
+        model.add_interval(IntervalVar::new("bake", 0, 100_000, 20_000, 200_000));
+        model.add_no_overlap(vec!["a-other".into(), "bake".into()]);
+        model.add_max_delay("coat".into(), "bake".into(), 30_000);
+
+        let solution = SimpleCpSolver::new().solve(&model, &SolverConfig::default());
+        assert!(solution.is_solution_found());
+
+        let (coat, bake) = (&solution.intervals["coat"], &solution.intervals["bake"]);
+        assert_eq!(bake.start, 60_000);
+        // 도장이 뒤로 밀려 지연 한도를 지킴
+        assert_eq!(coat.end, 30_000);
+    }
+
+    #[test]
+    fn test_mutual_exclusion() {
+        let mut model = CpModel::new("crane", 1_000_000);
+
+        // 서로 다른 작업조이지만 크레인 하나를 공유하는 인양 작업
+        for name in ["lift1", "lift2", "lift3"] {
+            model.add_interval(IntervalVar::new(name, 0, 100_000, 10_000, 200_000));
+        }
+        model.add_mutual_exclusion(&["lift1".into(), "lift3".into(), "unknown".into()]);
+        assert!(model.validate().is_ok());
+
+        let solution = SimpleCpSolver::new().solve(&model, &SolverConfig::default());
+        assert!(solution.is_solution_found());
+        let (a, b) = (&solution.intervals["lift1"], &solution.intervals["lift3"]);
+        assert!(a.end <= b.start || b.end <= a.start);
+        assert_eq!(solution.makespan(), 20_000);
+    }
+
     #[test]
     fn test_invalid_model() {
         let mut model = CpModel::new("test", 1_000_000);
@@ -0,0 +1,155 @@
+//! Due date assignment - Internal deadlines for tasks without one
+//!
+//! Standard rules that derive a due date from a task's release time and
+//! work content, so due-date rules (EDD, MST, CR, ATC) can rank tasks
+//! that came without deadlines.
+
+use crate::models::Task;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Due date assignment rule
+///
+/// Each rule has one tightness parameter: smaller values give tighter
+/// due dates.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DueDateRule {
+    /// TWK - Total Work content: release + factor * total work
+    TotalWork { factor: f64 },
+    /// SLK - Slack: release + total work + allowance
+    Slack { allowance_ms: i64 },
+    /// NOP - Number of Operations: release + per_operation_ms * operations
+    NumberOfOperations { per_operation_ms: i64 },
+}
+
+impl DueDateRule {
+    /// Short rule name (TWK, SLK, NOP)
+    pub fn name(&self) -> &'static str {
+        match self {
+            DueDateRule::TotalWork { .. } => "TWK",
+            DueDateRule::Slack { .. } => "SLK",
+            DueDateRule::NumberOfOperations { .. } => "NOP",
+        }
+    }
+
+    /// Due date of a task released at `release_ms` (epoch ms)
+    pub fn due_date_ms(&self, task: &Task, release_ms: i64) -> i64 {
+        let work: i64 = task.activities.iter().map(|a| a.duration.total_ms()).sum();
+        let allowance = match self {
+            DueDateRule::TotalWork { factor } => (factor * work as f64).round() as i64,
+            DueDateRule::Slack { allowance_ms } => work + allowance_ms,
+            DueDateRule::NumberOfOperations { per_operation_ms } => {
+                per_operation_ms * task.activities.len() as i64
+            }
+        };
+        release_ms + allowance
+    }
+}
+
+/// Assigns internal due dates to tasks
+///
+/// The release is the task's release time, else the assignment time.
+/// Existing deadlines are kept unless overwriting is enabled.
+#[derive(Debug, Clone)]
+pub struct DueDateAssigner {
+    /// Rule deriving due dates
+    pub rule: DueDateRule,
+    /// Replace deadlines tasks already have
+    pub overwrite: bool,
+}
+
+impl DueDateAssigner {
+    /// Create assigner keeping existing deadlines
+    pub fn new(rule: DueDateRule) -> Self {
+        Self {
+            rule,
+            overwrite: false,
+        }
+    }
+
+    /// Replace existing deadlines too
+    pub fn with_overwrite(mut self, overwrite: bool) -> Self {
+        self.overwrite = overwrite;
+        self
+    }
+
+    /// Due date of one task
+    pub fn due_date(&self, task: &Task, now: DateTime<Utc>) -> DateTime<Utc> {
+        let release = task.release_time.unwrap_or(now).timestamp_millis();
+        DateTime::from_timestamp_millis(self.rule.due_date_ms(task, release)).unwrap_or(now)
+    }
+
+    /// Copies of `tasks` with assigned deadlines
+    pub fn assign(&self, tasks: &[Task], now: DateTime<Utc>) -> Vec<Task> {
+        tasks
+            .iter()
+            .map(|task| {
+                let mut task = task.clone();
+                if self.overwrite || task.deadline.is_none() {
+                    task.deadline = Some(self.due_date(&task, now));
+                }
+                task
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Activity, ActivityDuration};
+    use chrono::TimeZone;
+
+    fn make_task(id: &str, durations: &[i64]) -> Task {
+        durations
+            .iter()
+            .enumerate()
+            .fold(Task::new(id), |task, (i, &ms)| {
+                task.with_activity(
+                    Activity::new(&format!("{}-A{}", id, i + 1), id, i as i32 + 1)
+                        .with_duration(ActivityDuration::fixed(ms)),
+                )
+            })
+    }
+
+    #[test]
+    fn test_due_date_rules() {
+        let task = make_task("T1", &[1000, 3000]);
+
+        assert_eq!(
+            DueDateRule::TotalWork { factor: 1.5 }.due_date_ms(&task, 100),
+            6100
+        );
+        assert_eq!(
+            DueDateRule::Slack { allowance_ms: 500 }.due_date_ms(&task, 100),
+            4600
+        );
+        assert_eq!(
+            DueDateRule::NumberOfOperations {
+                per_operation_ms: 2000
+            }
+            .due_date_ms(&task, 100),
+            4100
+        );
+        assert_eq!(DueDateRule::Slack { allowance_ms: 0 }.name(), "SLK");
+    }
+
+    #[test]
+    fn test_assign_keeps_existing_deadlines() {
+        let now = Utc.timestamp_millis_opt(10_000).unwrap();
+        let fixed = Utc.timestamp_millis_opt(99_000).unwrap();
+        let tasks = vec![
+            make_task("T1", &[1000]).with_release_time(Utc.timestamp_millis_opt(2000).unwrap()),
+            make_task("T2", &[1000]).with_deadline(fixed),
+        ];
+        let assigner = DueDateAssigner::new(DueDateRule::TotalWork { factor: 2.0 });
+
+        let assigned = assigner.assign(&tasks, now);
+        assert_eq!(assigned[0].deadline.unwrap().timestamp_millis(), 4000);
+        assert_eq!(assigned[1].deadline, Some(fixed));
+
+        let assigned = assigner.with_overwrite(true).assign(&tasks, now);
+        assert_eq!(assigned[1].deadline.unwrap().timestamp_millis(), 12_000);
+    }
+}
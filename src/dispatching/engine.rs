@@ -1,10 +1,12 @@
 //! RuleEngine - Multi-layer dispatching with tie-breaking
 
-use crate::models::Task;
+use crate::models::{PriorityClass, Task};
 use super::{BoxedRule, DispatchingRule, SchedulingContext, RuleScore};
+use serde::{Deserialize, Serialize};
 
 /// How to evaluate multiple rules
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum EvaluationMode {
     /// Apply rules sequentially; use next rule only on ties
     #[default]
@@ -120,32 +122,66 @@ impl RuleEngine {
     /// Sort tasks by priority (lowest score first)
     ///
     /// Returns a new vector with tasks sorted by their dispatching priority.
+    /// Higher priority classes always come first; rules order tasks within
+    /// a class.
     pub fn sort<'a>(&self, tasks: &[&'a Task], context: &SchedulingContext) -> Vec<&'a Task> {
-        if tasks.is_empty() || self.rules.is_empty() {
-            return tasks.to_vec();
+        if tasks.is_empty() {
+            return Vec::new();
         }
 
-        let mut scored: Vec<_> = tasks
+        let raw: Vec<Vec<RuleScore>> = tasks
             .iter()
-            .map(|&task| {
-                // Sequential mode uses raw scores; Weighted mode uses weighted scores
-                let scores = match self.mode {
-                    EvaluationMode::Sequential => self.evaluate_raw(task, context),
-                    EvaluationMode::Weighted => self.evaluate(task, context),
-                };
-                (task, scores)
-            })
+            .map(|task| self.evaluate_raw(task, context))
             .collect();
+        self.rank(tasks, &raw).into_iter().map(|i| tasks[i]).collect()
+    }
+
+    /// Score every task under every rule, with the resulting ranking
+    ///
+    /// Rows follow the input order; `ranking` is the order `sort`
+    /// returns, so the matrix shows why a task came first.
+    pub fn score_matrix(&self, tasks: &[&Task], context: &SchedulingContext) -> ScoreMatrix {
+        let scores: Vec<Vec<RuleScore>> = tasks
+            .iter()
+            .map(|task| self.evaluate_raw(task, context))
+            .collect();
+        let ranking = self.rank(tasks, &scores);
+
+        ScoreMatrix {
+            rules: self.rules.iter().map(|wr| wr.rule.name().to_string()).collect(),
+            weights: self.rules.iter().map(|wr| wr.weight).collect(),
+            mode: self.mode,
+            task_ids: tasks.iter().map(|t| t.id.clone()).collect(),
+            classes: tasks.iter().map(|t| t.priority_class).collect(),
+            scores,
+            ranking,
+            epsilon: self.epsilon,
+        }
+    }
+
+    /// Indices of `tasks` in priority order (stable for ties)
+    fn rank(&self, tasks: &[&Task], raw: &[Vec<RuleScore>]) -> Vec<usize> {
+        let mut order: Vec<usize> = (0..tasks.len()).collect();
+        // Strict tiers: the higher class wins before any rule is consulted
+        let class = |i: usize| std::cmp::Reverse(tasks[i].priority_class);
+        if self.rules.is_empty() {
+            order.sort_by_key(|&i| class(i));
+            return order;
+        }
 
         match self.mode {
             EvaluationMode::Sequential => {
-                scored.sort_by(|(task_a, scores_a), (task_b, scores_b)| {
+                order.sort_by(|&a, &b| {
+                    if tasks[a].priority_class != tasks[b].priority_class {
+                        return class(a).cmp(&class(b));
+                    }
                     // Compare rule by rule until we find a difference
-                    for (score_a, score_b) in scores_a.iter().zip(scores_b.iter()) {
+                    for (score_a, score_b) in raw[a].iter().zip(raw[b].iter()) {
                         if (score_a - score_b).abs() > self.epsilon {
-                            return score_a.partial_cmp(score_b).unwrap();
+                            return score_a.total_cmp(score_b);
                         }
                     }
+                    let (task_a, task_b) = (tasks[a], tasks[b]);
                     // All rules tied, use final tie-breaker
                     match self.tie_breaker {
                         TieBreaker::NextRule => std::cmp::Ordering::Equal,
@@ -160,15 +196,23 @@ impl RuleEngine {
                 });
             }
             EvaluationMode::Weighted => {
-                scored.sort_by(|(_, scores_a), (_, scores_b)| {
-                    let sum_a: f64 = scores_a.iter().sum();
-                    let sum_b: f64 = scores_b.iter().sum();
-                    sum_a.partial_cmp(&sum_b).unwrap()
+                let totals: Vec<f64> = raw
+                    .iter()
+                    .map(|scores| {
+                        scores.iter().zip(&self.rules).map(|(s, wr)| s * wr.weight).sum()
+                    })
+                    .collect();
+                // NaN totals (e.g. 0 * inf) rank last, whatever their sign bit
+                order.sort_by(|&a, &b| {
+                    class(a)
+                        .cmp(&class(b))
+                        .then(totals[a].is_nan().cmp(&totals[b].is_nan()))
+                        .then(totals[a].total_cmp(&totals[b]))
                 });
             }
         }
 
-        scored.into_iter().map(|(task, _)| task).collect()
+        order
     }
 
     /// Select the highest priority task (first after sorting)
@@ -177,6 +221,96 @@ impl RuleEngine {
     }
 }
 
+/// Per-task, per-rule scores of one ranking (`RuleEngine::score_matrix`)
+///
+/// Scores are raw (unweighted); lower is better.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScoreMatrix {
+    /// Rule names, one per column
+    pub rules: Vec<String>,
+    /// Rule weights (0 for tie-breakers)
+    pub weights: Vec<f64>,
+    /// How the rules were combined
+    pub mode: EvaluationMode,
+    /// Task IDs, one per row (input order)
+    pub task_ids: Vec<String>,
+    /// Priority class by row
+    #[serde(default)]
+    pub classes: Vec<PriorityClass>,
+    /// Raw scores by row and rule
+    pub scores: Vec<Vec<RuleScore>>,
+    /// Rows in priority order
+    pub ranking: Vec<usize>,
+    /// Tolerance for equal scores
+    pub epsilon: f64,
+}
+
+impl ScoreMatrix {
+    /// Task IDs in priority order
+    pub fn ranked_task_ids(&self) -> Vec<&str> {
+        self.ranking.iter().map(|&i| self.task_ids[i].as_str()).collect()
+    }
+
+    /// Position of a task in the ranking (0 = first)
+    pub fn position(&self, task_id: &str) -> Option<usize> {
+        self.ranking.iter().position(|&i| self.task_ids[i] == task_id)
+    }
+
+    /// Weighted score sum of a row
+    pub fn total(&self, row: usize) -> f64 {
+        self.scores[row].iter().zip(&self.weights).map(|(s, w)| s * w).sum()
+    }
+
+    /// Rule (column) that put ranking position `position` ahead of the next
+    ///
+    /// In sequential mode the first rule whose scores differ; in weighted
+    /// mode the rule contributing most to the gap. None for the last
+    /// position, for ties and when the priority classes differ.
+    pub fn deciding_rule(&self, position: usize) -> Option<usize> {
+        let (&a, &b) = (self.ranking.get(position)?, self.ranking.get(position + 1)?);
+        if self.classes.get(a) != self.classes.get(b) {
+            return None;
+        }
+        let (row_a, row_b) = (&self.scores[a], &self.scores[b]);
+        match self.mode {
+            EvaluationMode::Sequential => (0..self.rules.len())
+                .find(|&k| (row_a[k] - row_b[k]).abs() > self.epsilon),
+            EvaluationMode::Weighted => (0..self.rules.len())
+                .map(|k| (k, (row_b[k] - row_a[k]) * self.weights[k]))
+                .filter(|&(_, gain)| gain > self.epsilon)
+                .max_by(|x, y| x.1.total_cmp(&y.1))
+                .map(|(k, _)| k),
+        }
+    }
+
+    /// CSV export: one row per task in ranking order
+    ///
+    /// Columns: rank, task, one score per rule, weighted total.
+    pub fn to_csv(&self) -> String {
+        let quote = |s: &str| {
+            if s.contains([',', '"', '\n']) {
+                format!("\"{}\"", s.replace('"', "\"\""))
+            } else {
+                s.to_string()
+            }
+        };
+        let mut csv = String::from("rank,task");
+        for rule in &self.rules {
+            csv.push(',');
+            csv.push_str(&quote(rule));
+        }
+        csv.push_str(",total\n");
+        for (rank, &row) in self.ranking.iter().enumerate() {
+            csv.push_str(&format!("{},{}", rank + 1, quote(&self.task_ids[row])));
+            for score in &self.scores[row] {
+                csv.push_str(&format!(",{}", score));
+            }
+            csv.push_str(&format!(",{}\n", self.total(row)));
+        }
+        csv
+    }
+}
+
 #[cfg(test)]
 mod engine_tests {
     use super::*;
@@ -0,0 +1,561 @@
+//! DispatchEnv - Dispatching simulation as a reinforcement-learning environment
+//!
+//! Gym-like `reset` / `step` interface over a non-delay dispatching
+//! simulation: whenever a resource falls idle with work waiting, the
+//! agent picks which waiting task it runs next. Observations describe
+//! every waiting task with a [`FeatureSet`], so a policy trained here is
+//! deployed unchanged as a [`PolicyRule`](super::rules::PolicyRule) with
+//! the same features.
+//!
+//! The simulation runs each task's activities in sequence on one of
+//! their candidate resources with efficiency-scaled durations; calendars
+//! and sequence-dependent setups are not modeled. Time advances on the
+//! [`simulation`](crate::simulation) kernel: a task joins the queue of
+//! every candidate resource when its next activity becomes ready, and
+//! candidates are listed in arrival order.
+
+use super::rules::FeatureSet;
+use super::{RuleEngine, SchedulingContext};
+use crate::models::{Assignment, Resource, Schedule, Task};
+use crate::simulation::{EventQueue, QueueReport, ResourceQueue, ResourceQueueStats};
+use chrono::DateTime;
+use std::collections::HashMap;
+
+/// What the reward of a step measures
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RewardKind {
+    /// Minus the growth of the schedule length (episode sum = -makespan)
+    #[default]
+    Makespan,
+    /// Minus the tardiness of tasks finished by the step (episode sum =
+    /// -total tardiness)
+    Tardiness,
+}
+
+/// Waiting task the agent can dispatch
+#[derive(Debug, Clone, PartialEq)]
+pub struct Candidate {
+    /// Task ID
+    pub task_id: String,
+    /// Activity that would start
+    pub activity_id: String,
+    /// Feature vector (`Observation::feature_names` order)
+    pub features: Vec<f64>,
+}
+
+/// State at a decision point
+#[derive(Debug, Clone, PartialEq)]
+pub struct Observation {
+    /// Current time (epoch ms)
+    pub time_ms: i64,
+    /// Idle resource to load
+    pub resource_id: String,
+    /// Waiting tasks; the action is an index into this list
+    pub candidates: Vec<Candidate>,
+    /// Feature names
+    pub feature_names: Vec<String>,
+    /// Tasks not finished yet
+    pub open_tasks: usize,
+}
+
+/// Outcome of one step
+#[derive(Debug, Clone, PartialEq)]
+pub struct Step {
+    /// Next decision point (no candidates once done)
+    pub observation: Observation,
+    /// Reward of the step (ms, negative costs)
+    pub reward: f64,
+    /// Whether the episode ended
+    pub done: bool,
+}
+
+/// Invalid step
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StepError {
+    /// Episode is over; call `reset`
+    Done,
+    /// Action is not an index into the candidates
+    InvalidAction { action: usize, available: usize },
+}
+
+impl std::fmt::Display for StepError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StepError::Done => write!(f, "episode is done"),
+            StepError::InvalidAction { action, available } => {
+                write!(
+                    f,
+                    "action {} out of range ({} candidates)",
+                    action, available
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for StepError {}
+
+/// Dispatching environment over a fixed problem
+#[derive(Debug, Clone)]
+pub struct DispatchEnv {
+    tasks: Vec<Task>,
+    resources: Vec<Resource>,
+    start_time_ms: i64,
+    features: FeatureSet,
+    reward: RewardKind,
+    resource_index: HashMap<String, usize>,
+    state: EnvState,
+}
+
+/// Simulation event
+#[derive(Debug, Clone, Copy)]
+enum EnvEvent {
+    /// Task's next activity can start
+    Ready(usize),
+    /// Resource finished its activity
+    Free(usize),
+}
+
+/// Mutable episode state
+#[derive(Debug, Clone)]
+struct EnvState {
+    /// Index of each task's next activity (in sequence order)
+    next: Vec<usize>,
+    /// Activity indices of each task in sequence order
+    order: Vec<Vec<usize>>,
+    /// Earliest start of each task's next activity
+    ready_ms: Vec<i64>,
+    /// Future events and clock
+    events: EventQueue<EnvEvent>,
+    /// Waiting task indices per resource
+    queues: Vec<ResourceQueue<usize>>,
+    /// Idle resource to load at the current decision
+    decision: Option<usize>,
+    schedule: Schedule,
+}
+
+impl Default for EnvState {
+    fn default() -> Self {
+        Self {
+            next: Vec::new(),
+            order: Vec::new(),
+            ready_ms: Vec::new(),
+            events: EventQueue::new(0),
+            queues: Vec::new(),
+            decision: None,
+            schedule: Schedule::new(),
+        }
+    }
+}
+
+impl DispatchEnv {
+    /// Create environment with standard features and makespan reward
+    pub fn new(tasks: Vec<Task>, resources: Vec<Resource>, start_time_ms: i64) -> Self {
+        let resource_index = resources
+            .iter()
+            .enumerate()
+            .map(|(i, r)| (r.id.clone(), i))
+            .collect();
+        let mut env = Self {
+            tasks,
+            resources,
+            start_time_ms,
+            features: FeatureSet::standard(),
+            reward: RewardKind::default(),
+            resource_index,
+            state: EnvState::default(),
+        };
+        env.reset();
+        env
+    }
+
+    /// Set observation features
+    pub fn with_features(mut self, features: FeatureSet) -> Self {
+        self.features = features;
+        self.reset();
+        self
+    }
+
+    /// Set reward
+    pub fn with_reward(mut self, reward: RewardKind) -> Self {
+        self.reward = reward;
+        self
+    }
+
+    /// Start a new episode
+    pub fn reset(&mut self) -> Observation {
+        let order: Vec<Vec<usize>> = self
+            .tasks
+            .iter()
+            .map(|t| {
+                let mut idx: Vec<usize> = (0..t.activities.len()).collect();
+                idx.sort_by_key(|&i| t.activities[i].sequence);
+                idx
+            })
+            .collect();
+        let mut events = EventQueue::new(self.start_time_ms);
+        let ready_ms: Vec<i64> = self
+            .tasks
+            .iter()
+            .map(|t| {
+                t.release_time
+                    .map_or(self.start_time_ms, |r| r.timestamp_millis())
+                    .max(self.start_time_ms)
+            })
+            .collect();
+        for (t, &at) in ready_ms.iter().enumerate() {
+            events.schedule_at(at, EnvEvent::Ready(t));
+        }
+        self.state = EnvState {
+            next: vec![0; self.tasks.len()],
+            order,
+            ready_ms,
+            events,
+            queues: self
+                .resources
+                .iter()
+                .map(|_| ResourceQueue::new(1, self.start_time_ms))
+                .collect(),
+            decision: None,
+            schedule: Schedule::new(),
+        };
+        for t in 0..self.tasks.len() {
+            self.skip_unrunnable(t);
+        }
+        self.advance();
+        self.observation()
+    }
+
+    /// Dispatch candidate `action` on the idle resource
+    pub fn step(&mut self, action: usize) -> Result<Step, StepError> {
+        let r = self.state.decision.ok_or(StepError::Done)?;
+        let available = self.state.queues[r].len();
+        if action >= available {
+            return Err(StepError::InvalidAction { action, available });
+        }
+        self.state.decision = None;
+        let now = self.state.events.now();
+        let (_, t) = self.state.queues[r]
+            .take(now, action)
+            .expect("action in range");
+        for queue in &mut self.state.queues {
+            queue.remove_where(now, |&w| w == t);
+        }
+        self.state.queues[r].seize(now);
+
+        let before = self.state.schedule.makespan_ms.max(self.start_time_ms);
+        let (task, resource) = (&self.tasks[t], &self.resources[r]);
+        let activity = &task.activities[self.state.order[t][self.state.next[t]]];
+        let d = &activity.duration;
+        let end = now + d.setup_ms + resource.processing_time(d.process_ms) + d.teardown_ms;
+        self.state.schedule.add_assignment(
+            Assignment::new(&activity.id, &task.id, &resource.id, now, end).with_setup(d.setup_ms),
+        );
+        self.state.ready_ms[t] = end;
+        self.state.next[t] += 1;
+
+        let reward = match self.reward {
+            RewardKind::Makespan => {
+                -((self.state.schedule.makespan_ms.max(before) - before) as f64)
+            }
+            RewardKind::Tardiness => {
+                if self.state.next[t] == task.activities.len() {
+                    let due = task.due_date().map_or(i64::MAX, |d| d.timestamp_millis());
+                    -((end - due).max(0) as f64)
+                } else {
+                    0.0
+                }
+            }
+        };
+
+        self.skip_unrunnable(t);
+        self.state.events.schedule_at(end, EnvEvent::Free(r));
+        if !self.is_finished(t) {
+            self.state.events.schedule_at(end, EnvEvent::Ready(t));
+        }
+        self.advance();
+        Ok(Step {
+            observation: self.observation(),
+            reward,
+            done: self.is_done(),
+        })
+    }
+
+    /// Current observation
+    pub fn observation(&self) -> Observation {
+        let (time_ms, resource_id, candidates) = match self.state.decision {
+            Some(r) => {
+                let now = self.state.events.now();
+                let context = self.context(now);
+                let candidates = self.state.queues[r]
+                    .iter()
+                    .map(|&t| Candidate {
+                        task_id: self.tasks[t].id.clone(),
+                        activity_id: self.next_activity(t).id.clone(),
+                        features: self.features.extract(&self.tasks[t], &context),
+                    })
+                    .collect();
+                (now, self.resources[r].id.clone(), candidates)
+            }
+            None => (
+                self.state.schedule.makespan_ms.max(self.start_time_ms),
+                String::new(),
+                Vec::new(),
+            ),
+        };
+        Observation {
+            time_ms,
+            resource_id,
+            candidates,
+            feature_names: self.features.names(),
+            open_tasks: (0..self.tasks.len())
+                .filter(|&t| !self.is_finished(t))
+                .count(),
+        }
+    }
+
+    /// Whether the episode ended (nothing left that can be dispatched)
+    pub fn is_done(&self) -> bool {
+        self.state.decision.is_none()
+    }
+
+    /// Schedule built so far
+    pub fn schedule(&self) -> &Schedule {
+        &self.state.schedule
+    }
+
+    /// Queueing statistics of every resource up to the current time
+    /// (the makespan once done)
+    pub fn queue_report(&self) -> QueueReport {
+        let now = self.state.events.now();
+        QueueReport {
+            resources: self
+                .resources
+                .iter()
+                .zip(&self.state.queues)
+                .map(|(resource, queue)| ResourceQueueStats {
+                    resource_id: resource.id.clone(),
+                    stats: queue.stats(now),
+                })
+                .collect(),
+        }
+    }
+
+    /// Scheduling context at a decision time
+    ///
+    /// Remaining work and arrival (ready) times of the open tasks, as a
+    /// deployed rule would see them.
+    pub fn context(&self, now_ms: i64) -> SchedulingContext {
+        let mut context =
+            SchedulingContext::new(DateTime::from_timestamp_millis(now_ms).unwrap_or_default());
+        for (t, task) in self
+            .tasks
+            .iter()
+            .enumerate()
+            .filter(|&(t, _)| !self.is_finished(t))
+        {
+            let remaining: i64 = self.state.order[t][self.state.next[t]..]
+                .iter()
+                .map(|&i| task.activities[i].duration.total_ms())
+                .sum();
+            context = context.with_remaining_work(task.id.clone(), remaining);
+            if let Some(ready) = DateTime::from_timestamp_millis(self.state.ready_ms[t]) {
+                context = context.with_arrival_time(task.id.clone(), ready);
+            }
+        }
+        context
+    }
+
+    /// Run an episode dispatching with a rule engine, e.g. a baseline or
+    /// a trained `PolicyRule`
+    pub fn rollout(&mut self, engine: &RuleEngine) -> Schedule {
+        let mut observation = self.reset();
+        while !self.is_done() {
+            let context = self.context(observation.time_ms);
+            let waiting: Vec<&Task> = observation
+                .candidates
+                .iter()
+                .filter_map(|c| self.tasks.iter().find(|t| t.id == c.task_id))
+                .collect();
+            let best = engine.select_best(&waiting, &context);
+            let action = best
+                .and_then(|b| waiting.iter().position(|t| std::ptr::eq(*t, b)))
+                .unwrap_or(0);
+            observation = match self.step(action) {
+                Ok(step) => step.observation,
+                Err(_) => break,
+            };
+        }
+        self.state.schedule.clone()
+    }
+
+    fn next_activity(&self, t: usize) -> &crate::models::Activity {
+        &self.tasks[t].activities[self.state.order[t][self.state.next[t]]]
+    }
+
+    fn is_finished(&self, t: usize) -> bool {
+        self.state.next[t] >= self.tasks[t].activities.len()
+    }
+
+    /// Resource indices that can run a task's next activity
+    fn candidates(&self, t: usize) -> Vec<usize> {
+        let mut found: Vec<usize> = self
+            .next_activity(t)
+            .candidate_resources()
+            .iter()
+            .filter_map(|c| self.resource_index.get(c.as_str()).copied())
+            .collect();
+        found.sort_unstable();
+        found.dedup();
+        found
+    }
+
+    /// Skip activities no known resource can run
+    fn skip_unrunnable(&mut self, t: usize) {
+        while !self.is_finished(t) && self.candidates(t).is_empty() {
+            self.state.next[t] += 1;
+        }
+    }
+
+    fn apply(&mut self, event: EnvEvent) {
+        match event {
+            EnvEvent::Ready(t) => {
+                let now = self.state.events.now();
+                for r in self.candidates(t) {
+                    self.state.queues[r].enqueue(now, t);
+                }
+            }
+            EnvEvent::Free(r) => {
+                let now = self.state.events.now();
+                self.state.queues[r].release(now);
+            }
+        }
+    }
+
+    /// Move to the next decision point: the earliest time an idle
+    /// resource has a waiting activity it can run
+    fn advance(&mut self) {
+        loop {
+            while let Some(event) = self.state.events.pop_due() {
+                self.apply(event);
+            }
+            let idle = self
+                .state
+                .queues
+                .iter()
+                .position(|q| q.is_idle() && !q.is_empty());
+            if idle.is_some() {
+                self.state.decision = idle;
+                return;
+            }
+            match self.state.events.pop() {
+                Some((_, event)) => self.apply(event),
+                None => return,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dispatching::rules::{Feature, LinearModel, PolicyRule, Spt};
+    use crate::models::{Activity, ActivityDuration};
+
+    fn job(id: &str, ms: &[i64], deadline_ms: Option<i64>) -> Task {
+        let mut task = Task::new(id);
+        for (k, &d) in ms.iter().enumerate() {
+            task = task.with_activity(
+                Activity::new(&format!("{}-{}", id, k + 1), id, k as i32 + 1)
+                    .with_duration(ActivityDuration::fixed(d))
+                    .with_resources("machine", vec![format!("M{}", k + 1)]),
+            );
+        }
+        task.deadline = deadline_ms.and_then(DateTime::from_timestamp_millis);
+        task
+    }
+
+    fn env() -> DispatchEnv {
+        DispatchEnv::new(
+            vec![
+                job("T1", &[3000, 1000], Some(4000)),
+                job("T2", &[1000, 2500], Some(9000)),
+            ],
+            vec![Resource::primary("M1"), Resource::primary("M2")],
+            0,
+        )
+    }
+
+    #[test]
+    fn test_episode() {
+        let mut env = env();
+        let obs = env.reset();
+        assert_eq!(obs.time_ms, 0);
+        assert_eq!(obs.resource_id, "M1");
+        assert_eq!(obs.candidates.len(), 2);
+        assert_eq!(obs.feature_names.len(), obs.candidates[0].features.len());
+        assert_eq!(
+            env.step(5),
+            Err(StepError::InvalidAction {
+                action: 5,
+                available: 2
+            })
+        );
+
+        // Short job first: T2 on M1, then T1 on M1 while T2 moves to M2
+        let mut total = 0.0;
+        let mut step = env.step(1).unwrap();
+        total += step.reward;
+        assert_eq!(step.observation.candidates[0].task_id, "T1");
+        while !step.done {
+            step = env.step(0).unwrap();
+            total += step.reward;
+        }
+        assert_eq!(env.step(0), Err(StepError::Done));
+        assert_eq!(env.schedule().assignment_count(), 4);
+        assert_eq!(env.schedule().makespan_ms, 5000);
+        assert_eq!(total, -5000.0);
+        assert_eq!(step.observation.open_tasks, 0);
+
+        // T1 waits 1 s for M1; M2 idles before T2 and between T2 and T1
+        let report = env.queue_report();
+        let m1 = report.get("M1").unwrap();
+        assert_eq!(m1.duration_ms(), 5000);
+        assert_eq!(m1.waits_ms, vec![0, 1000]);
+        assert_eq!(m1.blocked_ms, 1000);
+        assert_eq!(m1.utilization(), 0.8);
+        assert_eq!(report.get("M2").unwrap().starved_ms, 1500);
+        assert_eq!(report.bottleneck().unwrap().resource_id, "M1");
+    }
+
+    #[test]
+    fn test_tardiness_reward_and_rollout() {
+        let mut env = env().with_reward(RewardKind::Tardiness);
+        // SPT runs T2 first and makes T1 late by 1 s
+        let spt = env.rollout(&RuleEngine::new().with_rule(Spt));
+        assert_eq!(spt.assignment_for_activity("T1-2").unwrap().end_ms, 5000);
+
+        let mut obs = env.reset();
+        let mut tardiness = 0.0;
+        while !env.is_done() {
+            let step = env.step(0).unwrap();
+            tardiness -= step.reward;
+            obs = step.observation;
+        }
+        assert!(obs.candidates.is_empty());
+        assert_eq!(tardiness, 0.0);
+
+        // A linear policy on slack deploys through PolicyRule
+        let features = FeatureSet::of(&[Feature::SlackMs]);
+        let policy = RuleEngine::new().with_rule(PolicyRule::new(
+            features.clone(),
+            LinearModel::new(vec![1.0]),
+        ));
+        let schedule = env.clone().with_features(features).rollout(&policy);
+        assert_eq!(
+            schedule.assignment_for_activity("T1-1").unwrap().start_ms,
+            0
+        );
+        assert_eq!(schedule.makespan_ms, 6500);
+    }
+}
@@ -8,6 +8,9 @@
 //! - **Time-based**: SPT, LPT, LWKR, MWKR
 //! - **Due Date**: EDD, MST, CR, S/RO
 //! - **Queue/Load**: FIFO, WINQ, LPUL
+//! - **Attribute**: ATTR_VALUE, ATTR_FLAG, ATTR_RANK
+//! - **Policy**: user-supplied scoring model over task features
+//! - **Wrappers**: Aging
 //!
 //! # Example
 //!
@@ -23,12 +26,16 @@
 //! ```
 
 mod context;
+mod due_dates;
 mod engine;
+mod env;
 mod rule;
 pub mod rules;
 
 pub use context::*;
+pub use due_dates::*;
 pub use engine::*;
+pub use env::*;
 pub use rule::*;
 
 #[cfg(test)]
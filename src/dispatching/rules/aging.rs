@@ -0,0 +1,107 @@
+//! Aging - Starvation prevention for any dispatching rule
+//!
+//! Wraps a base rule and improves a task's score the longer it waits.
+
+use crate::dispatching::{DispatchingRule, RuleScore, SchedulingContext};
+use crate::models::Task;
+
+/// Milliseconds per hour
+const MS_PER_HOUR: f64 = 3_600_000.0;
+
+/// Aging - base rule score lowered by time spent waiting
+///
+/// Waiting time runs from the task's arrival (`arrival_times`, else its
+/// release time) to the current time, so a long-queued low-priority task
+/// eventually overtakes newcomers.
+///
+/// Score = base score - rate_per_hour * hours waited
+/// `rate_per_hour` is in the base rule's score units.
+#[derive(Debug, Clone)]
+pub struct Aging<R: DispatchingRule> {
+    /// Rule being aged
+    pub base: R,
+    /// Score improvement per hour of waiting
+    pub rate_per_hour: f64,
+}
+
+impl<R: DispatchingRule> Aging<R> {
+    /// Age `base` by `rate_per_hour` score units per hour waited
+    pub fn new(base: R, rate_per_hour: f64) -> Self {
+        Self {
+            base,
+            rate_per_hour,
+        }
+    }
+
+    /// Time the task has been waiting (ms, never negative)
+    pub fn waiting_ms(task: &Task, context: &SchedulingContext) -> i64 {
+        let arrival = context
+            .get_arrival_time(&task.id)
+            .or(task.release_time)
+            .map(|t| t.timestamp_millis());
+        arrival.map_or(0, |a| (context.current_time.timestamp_millis() - a).max(0))
+    }
+}
+
+impl<R: DispatchingRule> DispatchingRule for Aging<R> {
+    fn name(&self) -> &'static str {
+        "AGING"
+    }
+
+    fn description(&self) -> &'static str {
+        "Aging - improve the base score the longer a task waits"
+    }
+
+    fn evaluate(&self, task: &Task, context: &SchedulingContext) -> RuleScore {
+        let hours = Self::waiting_ms(task, context) as f64 / MS_PER_HOUR;
+        self.base.evaluate(task, context) - self.rate_per_hour * hours
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dispatching::rules::Spt;
+    use crate::models::{Activity, ActivityDuration};
+    use chrono::{TimeZone, Utc};
+
+    const HOUR: i64 = 3_600_000;
+
+    fn make_task(id: &str, duration_ms: i64) -> Task {
+        Task::new(id).with_activity(
+            Activity::new(&format!("{}-A1", id), id, 1)
+                .with_duration(ActivityDuration::fixed(duration_ms)),
+        )
+    }
+
+    #[test]
+    fn test_long_task_eventually_served() {
+        let long = make_task("long", 4 * HOUR);
+        let short = make_task("short", HOUR);
+        // One hour of SPT score recovered per hour waited
+        let aging = Aging::new(Spt, HOUR as f64);
+
+        let early = SchedulingContext::new(Utc.timestamp_millis_opt(HOUR).unwrap())
+            .with_arrival_time("long", Utc.timestamp_millis_opt(0).unwrap())
+            .with_arrival_time("short", Utc.timestamp_millis_opt(HOUR).unwrap());
+        assert!(aging.evaluate(&short, &early) < aging.evaluate(&long, &early));
+
+        // After waiting five hours the long task overtakes a fresh arrival
+        let late = SchedulingContext::new(Utc.timestamp_millis_opt(5 * HOUR).unwrap())
+            .with_arrival_time("long", Utc.timestamp_millis_opt(0).unwrap())
+            .with_arrival_time("short", Utc.timestamp_millis_opt(5 * HOUR).unwrap());
+        assert!(aging.evaluate(&long, &late) < aging.evaluate(&short, &late));
+    }
+
+    #[test]
+    fn test_no_arrival_means_no_aging() {
+        let task = make_task("task", HOUR);
+        let ctx = SchedulingContext::new(Utc.timestamp_millis_opt(10 * HOUR).unwrap());
+
+        assert_eq!(Aging::<Spt>::waiting_ms(&task, &ctx), 0);
+        assert_eq!(
+            Aging::new(Spt, 1000.0).evaluate(&task, &ctx),
+            Spt.evaluate(&task, &ctx)
+        );
+    }
+}
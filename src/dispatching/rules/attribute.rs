@@ -0,0 +1,268 @@
+//! Attribute-based dispatching rules
+//!
+//! Rules that rank tasks by their custom attributes, so business
+//! priorities (revenue, hazard flags, customer tiers) need no Rust rule.
+//! A key is looked up on the task first, then on its activities in
+//! sequence order.
+
+use crate::dispatching::{DispatchingRule, RuleScore, SchedulingContext};
+use crate::models::Task;
+
+/// Attribute `key` of a task, else of its first activity that has it
+pub fn task_attribute<'a>(task: &'a Task, key: &str) -> Option<&'a str> {
+    task.attributes
+        .get(key)
+        .or_else(|| task.activities.iter().find_map(|a| a.attributes.get(key)))
+        .map(|v| v.as_str())
+}
+
+/// Whether an attribute value reads as true ("true", "yes", "y", "on", "1")
+pub fn is_truthy(value: &str) -> bool {
+    matches!(
+        value.trim().to_ascii_lowercase().as_str(),
+        "true" | "yes" | "y" | "on" | "1"
+    )
+}
+
+/// ATTR_VALUE - Order by a numeric attribute
+///
+/// Prioritizes tasks by a number stored in an attribute, e.g. revenue
+/// (highest first) or risk score (lowest first). Tasks without the
+/// attribute, or with a non-numeric value, use `default_value`.
+///
+/// Score = -value (highest first) or value (lowest first)
+#[derive(Debug, Clone)]
+pub struct AttrValue {
+    /// Attribute key
+    pub key: String,
+    /// Larger values first
+    pub descending: bool,
+    /// Value of tasks without the attribute
+    pub default_value: f64,
+}
+
+impl AttrValue {
+    /// Largest value first (missing = 0)
+    pub fn highest_first(key: &str) -> Self {
+        Self {
+            key: key.to_string(),
+            descending: true,
+            default_value: 0.0,
+        }
+    }
+
+    /// Smallest value first (missing = 0)
+    pub fn lowest_first(key: &str) -> Self {
+        Self {
+            key: key.to_string(),
+            descending: false,
+            default_value: 0.0,
+        }
+    }
+
+    /// Set value of tasks without the attribute
+    pub fn with_default(mut self, value: f64) -> Self {
+        self.default_value = value;
+        self
+    }
+
+    /// Attribute value of a task
+    pub fn value(&self, task: &Task) -> f64 {
+        task_attribute(task, &self.key)
+            .and_then(|v| v.trim().parse::<f64>().ok())
+            .filter(|v| v.is_finite())
+            .unwrap_or(self.default_value)
+    }
+}
+
+impl DispatchingRule for AttrValue {
+    fn name(&self) -> &'static str {
+        "ATTR_VALUE"
+    }
+
+    fn description(&self) -> &'static str {
+        "Attribute Value - order by a numeric task attribute"
+    }
+
+    fn evaluate(&self, task: &Task, _context: &SchedulingContext) -> RuleScore {
+        let value = self.value(task);
+        if self.descending {
+            -value
+        } else {
+            value
+        }
+    }
+}
+
+/// ATTR_FLAG - Penalize (or favor) a boolean attribute
+///
+/// Adds `weight` to the score of tasks whose flag is set, e.g. delay
+/// hazardous jobs (positive weight) or expedite flagged orders (negative
+/// weight). Best used in front of or weighted with another rule.
+///
+/// Score = weight if flag set, else 0
+#[derive(Debug, Clone)]
+pub struct AttrFlag {
+    /// Attribute key
+    pub key: String,
+    /// Score of flagged tasks (positive = later)
+    pub weight: f64,
+}
+
+impl AttrFlag {
+    /// Flagged tasks go after unflagged ones
+    pub fn penalize(key: &str) -> Self {
+        Self {
+            key: key.to_string(),
+            weight: 1.0,
+        }
+    }
+
+    /// Flagged tasks go before unflagged ones
+    pub fn favor(key: &str) -> Self {
+        Self {
+            key: key.to_string(),
+            weight: -1.0,
+        }
+    }
+
+    /// Set score of flagged tasks
+    pub fn with_weight(mut self, weight: f64) -> Self {
+        self.weight = weight;
+        self
+    }
+
+    /// Whether the task has the flag set
+    pub fn is_set(&self, task: &Task) -> bool {
+        task_attribute(task, &self.key).is_some_and(is_truthy)
+    }
+}
+
+impl DispatchingRule for AttrFlag {
+    fn name(&self) -> &'static str {
+        "ATTR_FLAG"
+    }
+
+    fn description(&self) -> &'static str {
+        "Attribute Flag - penalize or favor tasks with a boolean attribute"
+    }
+
+    fn evaluate(&self, task: &Task, _context: &SchedulingContext) -> RuleScore {
+        if self.is_set(task) {
+            self.weight
+        } else {
+            0.0
+        }
+    }
+}
+
+/// ATTR_RANK - Order by a categorical attribute
+///
+/// Ranks tasks by the position of their attribute value in a list, e.g.
+/// customer tier `["gold", "silver", "bronze"]`. Values are matched
+/// case-insensitively; unlisted and missing values go last.
+///
+/// Score = index of the value in `order`
+#[derive(Debug, Clone)]
+pub struct AttrRank {
+    /// Attribute key
+    pub key: String,
+    /// Values from highest to lowest priority
+    pub order: Vec<String>,
+}
+
+impl AttrRank {
+    /// Rank by `key` in the given value order
+    pub fn new(key: &str, order: &[&str]) -> Self {
+        Self {
+            key: key.to_string(),
+            order: order.iter().map(|v| v.to_string()).collect(),
+        }
+    }
+}
+
+impl DispatchingRule for AttrRank {
+    fn name(&self) -> &'static str {
+        "ATTR_RANK"
+    }
+
+    fn description(&self) -> &'static str {
+        "Attribute Rank - order by a categorical task attribute"
+    }
+
+    fn evaluate(&self, task: &Task, _context: &SchedulingContext) -> RuleScore {
+        task_attribute(task, &self.key)
+            .and_then(|v| {
+                self.order
+                    .iter()
+                    .position(|o| o.eq_ignore_ascii_case(v.trim()))
+            })
+            .unwrap_or(self.order.len()) as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dispatching::rules::Fifo;
+    use crate::dispatching::RuleEngine;
+    use crate::models::Activity;
+
+    fn make_task(id: &str, attrs: &[(&str, &str)]) -> Task {
+        attrs
+            .iter()
+            .fold(Task::new(id), |t, (k, v)| t.with_attribute(k, v))
+    }
+
+    fn ids(tasks: Vec<&Task>) -> Vec<&str> {
+        tasks.iter().map(|t| t.id.as_str()).collect()
+    }
+
+    #[test]
+    fn test_value_and_flag() {
+        let a = make_task("A", &[("revenue", "1200"), ("hazardous", "yes")]);
+        let b = make_task("B", &[("revenue", "800")]);
+        let c = make_task("C", &[("revenue", "n/a")]);
+        // Activity attributes count when the task has none
+        let d = Task::new("D")
+            .with_activity(Activity::new("D1", "D", 1).with_attribute("revenue", "950"));
+        let ctx = SchedulingContext::default();
+        let tasks = vec![&a, &b, &c, &d];
+
+        let by_revenue = RuleEngine::new().with_rule(AttrValue::highest_first("revenue"));
+        assert_eq!(ids(by_revenue.sort(&tasks, &ctx)), vec!["A", "D", "B", "C"]);
+
+        let cheapest = AttrValue::lowest_first("revenue").with_default(f64::INFINITY);
+        assert_eq!(cheapest.value(&c), f64::INFINITY);
+        assert_eq!(cheapest.evaluate(&b, &ctx), 800.0);
+
+        // A large hazard penalty outweighs the revenue lead
+        let safe_first = RuleEngine::new()
+            .with_weighted_rule(AttrFlag::penalize("hazardous").with_weight(1000.0), 1.0)
+            .with_weighted_rule(AttrValue::highest_first("revenue"), 1.0)
+            .with_mode(crate::dispatching::EvaluationMode::Weighted);
+        assert_eq!(ids(safe_first.sort(&tasks, &ctx))[..2], ["D", "B"]);
+        assert!(AttrFlag::favor("hazardous").evaluate(&a, &ctx) < 0.0);
+        assert_eq!(AttrFlag::penalize("hazardous").evaluate(&b, &ctx), 0.0);
+    }
+
+    #[test]
+    fn test_rank() {
+        let gold = make_task("G", &[("tier", "Gold")]);
+        let bronze = make_task("B", &[("tier", "bronze")]);
+        let other = make_task("O", &[("tier", "trial")]);
+        let none = make_task("N", &[]);
+        let ctx = SchedulingContext::default();
+
+        let rank = AttrRank::new("tier", &["gold", "silver", "bronze"]);
+        assert_eq!(rank.evaluate(&gold, &ctx), 0.0);
+        assert_eq!(rank.evaluate(&bronze, &ctx), 2.0);
+        assert_eq!(rank.evaluate(&other, &ctx), 3.0);
+
+        let engine = RuleEngine::new().with_rule(rank).with_tie_breaker(Fifo);
+        assert_eq!(
+            ids(engine.sort(&[&none, &other, &bronze, &gold], &ctx)),
+            vec!["G", "B", "N", "O"]
+        );
+    }
+}
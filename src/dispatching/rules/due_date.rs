@@ -1,6 +1,7 @@
 //! Due date-based dispatching rules
 //!
-//! Rules that prioritize tasks based on deadline urgency.
+//! Rules that prioritize tasks based on deadline urgency. The due date is
+//! the task's target date if set, else its deadline.
 
 use crate::models::Task;
 use crate::dispatching::{DispatchingRule, SchedulingContext, RuleScore};
@@ -25,7 +26,7 @@ impl DispatchingRule for Edd {
     }
 
     fn evaluate(&self, task: &Task, _context: &SchedulingContext) -> RuleScore {
-        task.deadline
+        task.due_date()
             .map(|d| d.timestamp_millis() as f64)
             .unwrap_or(f64::MAX)
     }
@@ -51,7 +52,7 @@ impl DispatchingRule for Mst {
     }
 
     fn evaluate(&self, task: &Task, context: &SchedulingContext) -> RuleScore {
-        let deadline = match task.deadline {
+        let deadline = match task.due_date() {
             Some(d) => d.timestamp_millis(),
             None => return f64::MAX, // No deadline = lowest priority
         };
@@ -92,7 +93,7 @@ impl DispatchingRule for Cr {
     }
 
     fn evaluate(&self, task: &Task, context: &SchedulingContext) -> RuleScore {
-        let deadline = match task.deadline {
+        let deadline = match task.due_date() {
             Some(d) => d.timestamp_millis(),
             None => return f64::MAX,
         };
@@ -123,7 +124,7 @@ impl DispatchingRule for Cr {
 /// Formula: ATC_j = (w_j / p_j) * exp(-max(d_j - p_j - t, 0) / (k * p_avg))
 ///
 /// Where:
-/// - w_j = weight (derived from priority)
+/// - w_j = weight (task.weight, derived from priority if not set)
 /// - p_j = processing time of task j
 /// - d_j = due date of task j
 /// - t = current time
@@ -172,11 +173,10 @@ impl DispatchingRule for Atc {
             return f64::MAX;
         }
 
-        // Weight from priority (lower priority value = higher weight)
-        let weight = 1000.0 / (task.priority as f64 + 1.0);
+        let weight = task.dispatch_weight();
 
         // Get due date or return SPT-like score for tasks without deadline
-        let deadline_ms = match task.deadline {
+        let deadline_ms = match task.due_date() {
             Some(d) => d.timestamp_millis() as f64,
             None => {
                 // No deadline: use pure weighted SPT
@@ -230,7 +230,7 @@ impl DispatchingRule for Sro {
     }
 
     fn evaluate(&self, task: &Task, context: &SchedulingContext) -> RuleScore {
-        let deadline = match task.deadline {
+        let deadline = match task.due_date() {
             Some(d) => d.timestamp_millis(),
             None => return f64::MAX,
         };
@@ -267,6 +267,9 @@ mod tests {
             name: id.to_string(),
             category: String::new(),
             priority: 0,
+            weight: None,
+            priority_class: Default::default(),
+            target_date: None,
             deadline: Some(Utc.timestamp_millis_opt(deadline_ms).unwrap()),
             release_time: None,
             activities: vec![
@@ -274,6 +277,7 @@ mod tests {
                     .with_duration(ActivityDuration::fixed(duration_ms))
             ],
             attributes: Default::default(),
+            loads: Default::default(),
         }
     }
 
@@ -296,10 +300,14 @@ mod tests {
             name: "without".to_string(),
             category: String::new(),
             priority: 0,
+            weight: None,
+            priority_class: Default::default(),
+            target_date: None,
             deadline: None,
             release_time: None,
             activities: vec![],
             attributes: Default::default(),
+            loads: Default::default(),
         };
 
         let ctx = SchedulingContext::at_epoch();
@@ -347,6 +355,9 @@ mod tests {
             name: "multi".to_string(),
             category: String::new(),
             priority: 0,
+            weight: None,
+            priority_class: Default::default(),
+            target_date: None,
             deadline: Some(Utc.timestamp_millis_opt(10000).unwrap()),
             release_time: None,
             activities: vec![
@@ -356,6 +367,7 @@ mod tests {
                     .with_duration(ActivityDuration::fixed(2500)),
             ],
             attributes: Default::default(),
+            loads: Default::default(),
         };
 
         let ctx = SchedulingContext::at_epoch();
@@ -403,6 +415,9 @@ mod tests {
             name: "no_dl".to_string(),
             category: String::new(),
             priority: 0,
+            weight: None,
+            priority_class: Default::default(),
+            target_date: None,
             deadline: None,
             release_time: None,
             activities: vec![
@@ -410,6 +425,7 @@ mod tests {
                     .with_duration(ActivityDuration::fixed(5000))
             ],
             attributes: Default::default(),
+            loads: Default::default(),
         };
 
         let ctx = SchedulingContext::at_epoch();
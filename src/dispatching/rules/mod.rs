@@ -18,11 +18,28 @@
 //! - [`Fifo`] - First In First Out
 //! - [`Winq`] - Work In Next Queue
 //! - [`Lpul`] - Least Planned Utilization Level
+//!
+//! # Attribute Rules
+//! - [`AttrValue`] - Numeric attribute (e.g., revenue)
+//! - [`AttrFlag`] - Boolean attribute (e.g., hazardous)
+//! - [`AttrRank`] - Categorical attribute (e.g., customer tier)
+//!
+//! # Policy Rules
+//! - [`PolicyRule`] - Feature vector scored by a user-supplied model
+//!
+//! # Wrappers
+//! - [`Aging`] - Improves any rule's score with waiting time
 
 mod time_based;
 mod due_date;
 mod queue_load;
+mod attribute;
+mod policy;
+mod aging;
 
 pub use time_based::*;
 pub use due_date::*;
 pub use queue_load::*;
+pub use attribute::*;
+pub use policy::*;
+pub use aging::*;
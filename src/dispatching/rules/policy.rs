@@ -0,0 +1,397 @@
+//! Policy - Dispatching by a learned or external scoring model
+//!
+//! [`PolicyRule`] turns a task and the scheduling context into a feature
+//! vector ([`FeatureSet`]) and lets a [`PolicyModel`] score it. Models can
+//! be closures ([`FnModel`]), linear weights ([`LinearModel`]), lookup
+//! tables ([`LookupModel`]) or any type implementing the trait, e.g. a
+//! wrapper around an ONNX session, so ML-driven dispatching plugs into
+//! the `RuleEngine` like any built-in rule.
+
+use super::aging::Aging;
+use super::attribute::task_attribute;
+use crate::dispatching::{BoxedRule, DispatchingRule, RuleScore, SchedulingContext};
+use crate::models::Task;
+use std::fmt;
+
+/// Built-in task features
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Feature {
+    /// Total activity duration (ms)
+    ProcessingMs,
+    /// Remaining work from the context, else total duration (ms)
+    RemainingMs,
+    /// Deadline minus current time (ms, 0 without deadline)
+    DueInMs,
+    /// Due-in time minus remaining work (ms, 0 without deadline)
+    SlackMs,
+    /// 1 if the task has a deadline, else 0
+    HasDeadline,
+    /// Task priority
+    Priority,
+    /// Number of activities
+    ActivityCount,
+    /// Time since arrival or release (ms)
+    WaitingMs,
+    /// Queue length at the next operation
+    NextQueue,
+}
+
+impl Feature {
+    /// All built-in features
+    pub const ALL: [Feature; 9] = [
+        Feature::ProcessingMs,
+        Feature::RemainingMs,
+        Feature::DueInMs,
+        Feature::SlackMs,
+        Feature::HasDeadline,
+        Feature::Priority,
+        Feature::ActivityCount,
+        Feature::WaitingMs,
+        Feature::NextQueue,
+    ];
+
+    /// Feature name
+    pub fn name(&self) -> &'static str {
+        match self {
+            Feature::ProcessingMs => "processing_ms",
+            Feature::RemainingMs => "remaining_ms",
+            Feature::DueInMs => "due_in_ms",
+            Feature::SlackMs => "slack_ms",
+            Feature::HasDeadline => "has_deadline",
+            Feature::Priority => "priority",
+            Feature::ActivityCount => "activity_count",
+            Feature::WaitingMs => "waiting_ms",
+            Feature::NextQueue => "next_queue",
+        }
+    }
+
+    /// Feature value of a task
+    pub fn value(&self, task: &Task, context: &SchedulingContext) -> f64 {
+        let processing: i64 = task.activities.iter().map(|a| a.duration.total_ms()).sum();
+        let remaining = context
+            .remaining_work
+            .get(&task.id)
+            .copied()
+            .unwrap_or(processing);
+        let due_in = task
+            .due_date()
+            .map(|d| d.timestamp_millis() - context.current_time.timestamp_millis());
+        match self {
+            Feature::ProcessingMs => processing as f64,
+            Feature::RemainingMs => remaining as f64,
+            Feature::DueInMs => due_in.unwrap_or(0) as f64,
+            Feature::SlackMs => due_in.map_or(0, |d| d - remaining) as f64,
+            Feature::HasDeadline => {
+                if due_in.is_some() {
+                    1.0
+                } else {
+                    0.0
+                }
+            }
+            Feature::Priority => task.priority as f64,
+            Feature::ActivityCount => task.activities.len() as f64,
+            Feature::WaitingMs => Aging::<BoxedRule>::waiting_ms(task, context) as f64,
+            Feature::NextQueue => context
+                .next_queue_length
+                .get(&task.id)
+                .copied()
+                .unwrap_or(0) as f64,
+        }
+    }
+}
+
+/// Feature vector layout: built-in features, then numeric attributes
+///
+/// Attributes are read from the task, else its activities; missing or
+/// non-numeric values become 0.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FeatureSet {
+    /// Built-in features
+    pub features: Vec<Feature>,
+    /// Numeric attribute keys
+    pub attributes: Vec<String>,
+}
+
+impl FeatureSet {
+    /// All built-in features, no attributes
+    pub fn standard() -> Self {
+        Self {
+            features: Feature::ALL.to_vec(),
+            attributes: Vec::new(),
+        }
+    }
+
+    /// Create from a list of built-in features
+    pub fn of(features: &[Feature]) -> Self {
+        Self {
+            features: features.to_vec(),
+            attributes: Vec::new(),
+        }
+    }
+
+    /// Append a numeric attribute feature
+    pub fn with_attribute(mut self, key: &str) -> Self {
+        self.attributes.push(key.to_string());
+        self
+    }
+
+    /// Number of features
+    pub fn len(&self) -> usize {
+        self.features.len() + self.attributes.len()
+    }
+
+    /// Whether there are no features
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Feature names in vector order
+    pub fn names(&self) -> Vec<String> {
+        self.features
+            .iter()
+            .map(|f| f.name().to_string())
+            .chain(self.attributes.iter().map(|k| format!("attr:{}", k)))
+            .collect()
+    }
+
+    /// Feature vector of a task
+    pub fn extract(&self, task: &Task, context: &SchedulingContext) -> Vec<f64> {
+        self.features
+            .iter()
+            .map(|f| f.value(task, context))
+            .chain(self.attributes.iter().map(|k| {
+                task_attribute(task, k)
+                    .and_then(|v| v.trim().parse::<f64>().ok())
+                    .filter(|v| v.is_finite())
+                    .unwrap_or(0.0)
+            }))
+            .collect()
+    }
+}
+
+/// Scoring model behind a [`PolicyRule`]
+///
+/// Receives the feature vector in [`FeatureSet::names`] order and
+/// returns a score; lower is dispatched first.
+pub trait PolicyModel: Send + Sync + fmt::Debug {
+    /// Score a feature vector
+    fn score(&self, features: &[f64]) -> f64;
+}
+
+/// Model from a closure
+pub struct FnModel<F>(pub F);
+
+impl<F> fmt::Debug for FnModel<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("FnModel")
+    }
+}
+
+impl<F: Fn(&[f64]) -> f64 + Send + Sync> PolicyModel for FnModel<F> {
+    fn score(&self, features: &[f64]) -> f64 {
+        (self.0)(features)
+    }
+}
+
+/// Linear model: bias + sum of weight * feature
+///
+/// Missing weights count as 0.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LinearModel {
+    /// Weight per feature
+    pub weights: Vec<f64>,
+    /// Constant term
+    pub bias: f64,
+}
+
+impl LinearModel {
+    /// Create linear model without bias
+    pub fn new(weights: Vec<f64>) -> Self {
+        Self { weights, bias: 0.0 }
+    }
+
+    /// Set constant term
+    pub fn with_bias(mut self, bias: f64) -> Self {
+        self.bias = bias;
+        self
+    }
+}
+
+impl PolicyModel for LinearModel {
+    fn score(&self, features: &[f64]) -> f64 {
+        self.bias
+            + self
+                .weights
+                .iter()
+                .zip(features)
+                .map(|(w, x)| w * x)
+                .sum::<f64>()
+    }
+}
+
+/// Lookup table over one feature
+///
+/// `scores[i]` applies below `breakpoints[i]`, the last score at or above
+/// the last breakpoint (so `scores` has one more entry than
+/// `breakpoints`), e.g. a tabulated policy by slack band.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LookupModel {
+    /// Index of the feature looked up
+    pub feature: usize,
+    /// Ascending band limits
+    pub breakpoints: Vec<f64>,
+    /// Score per band
+    pub scores: Vec<f64>,
+}
+
+impl LookupModel {
+    /// Create table; `scores` must have one more entry than `breakpoints`
+    pub fn new(feature: usize, breakpoints: Vec<f64>, scores: Vec<f64>) -> Self {
+        Self {
+            feature,
+            breakpoints,
+            scores,
+        }
+    }
+}
+
+impl PolicyModel for LookupModel {
+    fn score(&self, features: &[f64]) -> f64 {
+        let x = features.get(self.feature).copied().unwrap_or(0.0);
+        let band = self.breakpoints.iter().take_while(|&&b| x >= b).count();
+        self.scores
+            .get(band)
+            .or(self.scores.last())
+            .copied()
+            .unwrap_or(0.0)
+    }
+}
+
+/// POLICY - Score tasks with an external model
+///
+/// Score = model.score(features.extract(task, context))
+#[derive(Debug)]
+pub struct PolicyRule {
+    /// Feature vector layout
+    pub features: FeatureSet,
+    /// Scoring model
+    pub model: Box<dyn PolicyModel>,
+}
+
+impl PolicyRule {
+    /// Score `features` with `model`
+    pub fn new<M: PolicyModel + 'static>(features: FeatureSet, model: M) -> Self {
+        Self {
+            features,
+            model: Box::new(model),
+        }
+    }
+
+    /// Score with a closure over the feature vector
+    pub fn from_fn<F>(features: FeatureSet, f: F) -> Self
+    where
+        F: Fn(&[f64]) -> f64 + Send + Sync + 'static,
+    {
+        Self::new(features, FnModel(f))
+    }
+}
+
+impl DispatchingRule for PolicyRule {
+    fn name(&self) -> &'static str {
+        "POLICY"
+    }
+
+    fn description(&self) -> &'static str {
+        "Policy - score task features with an external model"
+    }
+
+    fn evaluate(&self, task: &Task, context: &SchedulingContext) -> RuleScore {
+        let score = self.model.score(&self.features.extract(task, context));
+        // A model failure (NaN) must not break sorting
+        if score.is_nan() {
+            f64::MAX
+        } else {
+            score
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dispatching::RuleEngine;
+    use crate::models::{Activity, ActivityDuration};
+    use chrono::{TimeZone, Utc};
+
+    fn make_task(id: &str, duration_ms: i64, deadline_ms: Option<i64>) -> Task {
+        let mut task = Task::new(id).with_activity(
+            Activity::new(&format!("{}-A1", id), id, 1)
+                .with_duration(ActivityDuration::fixed(duration_ms)),
+        );
+        task.deadline = deadline_ms.map(|d| Utc.timestamp_millis_opt(d).unwrap());
+        task
+    }
+
+    #[test]
+    fn test_feature_extraction() {
+        let task = make_task("T1", 3000, Some(10_000))
+            .with_priority(4)
+            .with_attribute("revenue", "250.5");
+        let ctx = SchedulingContext::new(Utc.timestamp_millis_opt(2000).unwrap())
+            .with_remaining_work("T1", 2500)
+            .with_next_queue("T1", 3);
+
+        let features = FeatureSet::standard()
+            .with_attribute("revenue")
+            .with_attribute("missing");
+        assert_eq!(features.len(), 11);
+        assert_eq!(features.names()[2], "due_in_ms");
+        assert_eq!(features.names()[9], "attr:revenue");
+        assert_eq!(
+            features.extract(&task, &ctx),
+            vec![3000.0, 2500.0, 8000.0, 5500.0, 1.0, 4.0, 1.0, 0.0, 3.0, 250.5, 0.0]
+        );
+    }
+
+    #[test]
+    fn test_policy_models_rank_tasks() {
+        let urgent = make_task("U", 4000, Some(5000));
+        let relaxed = make_task("R", 1000, Some(50_000));
+        let open = make_task("O", 2000, None);
+        let ctx = SchedulingContext::default();
+        let tasks = vec![&relaxed, &open, &urgent];
+        let ids = |engine: &RuleEngine| -> Vec<String> {
+            engine
+                .sort(&tasks, &ctx)
+                .iter()
+                .map(|t| t.id.clone())
+                .collect()
+        };
+
+        // Linear model over slack (open tasks get a large constant slack)
+        let slack = FeatureSet::of(&[Feature::SlackMs, Feature::HasDeadline]);
+        let linear = RuleEngine::new().with_rule(PolicyRule::new(
+            slack.clone(),
+            LinearModel::new(vec![1.0, -1e6]).with_bias(1e6),
+        ));
+        assert_eq!(ids(&linear), vec!["U", "R", "O"]);
+
+        // Closure: shortest processing first
+        let spt = RuleEngine::new().with_rule(PolicyRule::from_fn(
+            FeatureSet::of(&[Feature::ProcessingMs]),
+            |x| x[0],
+        ));
+        assert_eq!(ids(&spt), vec!["R", "O", "U"]);
+
+        // Lookup by slack band: < 5 s critical, else normal
+        let table = LookupModel::new(0, vec![5000.0], vec![0.0, 1.0]);
+        assert_eq!(table.score(&[1000.0]), 0.0);
+        assert_eq!(table.score(&[5000.0]), 1.0);
+        let rule = PolicyRule::new(slack, table);
+        assert_eq!(rule.evaluate(&urgent, &ctx), 0.0);
+        assert_eq!(rule.evaluate(&relaxed, &ctx), 1.0);
+
+        let broken = PolicyRule::from_fn(FeatureSet::standard(), |_| f64::NAN);
+        assert_eq!(broken.evaluate(&urgent, &ctx), f64::MAX);
+    }
+}
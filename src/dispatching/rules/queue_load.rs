@@ -111,6 +111,9 @@ mod tests {
             name: id.to_string(),
             category: String::new(),
             priority: 0,
+            weight: None,
+            priority_class: Default::default(),
+            target_date: None,
             deadline: None,
             release_time: None,
             activities: vec![
@@ -118,6 +121,7 @@ mod tests {
                     .with_duration(ActivityDuration::fixed(1000))
             ],
             attributes: Default::default(),
+            loads: Default::default(),
         }
     }
 
@@ -127,6 +131,9 @@ mod tests {
             name: id.to_string(),
             category: String::new(),
             priority: 0,
+            weight: None,
+            priority_class: Default::default(),
+            target_date: None,
             deadline: None,
             release_time: None,
             activities: vec![
@@ -135,6 +142,7 @@ mod tests {
                     .with_resources("machine", resource_ids.into_iter().map(String::from).collect())
             ],
             attributes: Default::default(),
+            loads: Default::default(),
         }
     }
 
@@ -159,10 +167,14 @@ mod tests {
             name: "task".to_string(),
             category: String::new(),
             priority: 0,
+            weight: None,
+            priority_class: Default::default(),
+            target_date: None,
             deadline: None,
             release_time: Some(Utc.timestamp_millis_opt(5000).unwrap()),
             activities: vec![],
             attributes: Default::default(),
+            loads: Default::default(),
         };
 
         let ctx = SchedulingContext::at_epoch(); // No arrival time set
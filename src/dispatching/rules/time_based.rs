@@ -96,7 +96,7 @@ impl DispatchingRule for Lwkr {
 /// Minimizes weighted total completion time.
 ///
 /// Score = -weight/processing_time (negative because higher ratio = higher priority)
-/// Uses task.priority as weight (lower priority value = higher weight in scheduling)
+/// Uses task.weight, derived from priority if not set (lower priority value = lower weight)
 #[derive(Debug, Clone, Copy, Default)]
 pub struct Wspt;
 
@@ -119,9 +119,7 @@ impl DispatchingRule for Wspt {
             return f64::MAX; // Avoid division by zero
         }
 
-        // Use priority as weight (lower priority = higher weight)
-        // We invert priority so that priority=1 has higher weight than priority=10
-        let weight = 1000.0 / (task.priority as f64 + 1.0);
+        let weight = task.dispatch_weight();
 
         // Negative because higher ratio should have lower (better) score
         -(weight / processing_time)
@@ -179,10 +177,14 @@ mod tests {
             name: id.to_string(),
             category: String::new(),
             priority: 0,
+            weight: None,
+            priority_class: Default::default(),
+            target_date: None,
             deadline: None,
             release_time: None,
             activities,
             attributes: Default::default(),
+            loads: Default::default(),
         }
     }
 
@@ -239,7 +241,7 @@ mod tests {
         assert!(mwkr.evaluate(&task2, &ctx) < mwkr.evaluate(&task1, &ctx));
     }
 
-    fn make_task_with_priority(id: &str, durations: &[i64], priority: i32) -> Task {
+    fn make_task_with_weight(id: &str, durations: &[i64], weight: f64) -> Task {
         let activities = durations
             .iter()
             .enumerate()
@@ -253,48 +255,61 @@ mod tests {
             id: id.to_string(),
             name: id.to_string(),
             category: String::new(),
-            priority,
+            priority: 0,
+            weight: Some(weight),
+            priority_class: Default::default(),
+            target_date: None,
             deadline: None,
             release_time: None,
             activities,
             attributes: Default::default(),
+            loads: Default::default(),
         }
     }
 
     #[test]
     fn test_wspt_prioritizes_high_weight_short_time() {
-        // High priority (low value = high weight) short task
-        let high_priority_short = make_task_with_priority("hp_short", &[1000], 1);
-        // Low priority (high value = low weight) long task
-        let low_priority_long = make_task_with_priority("lp_long", &[5000], 10);
+        let high_weight_short = make_task_with_weight("hw_short", &[1000], 5.0);
+        let low_weight_long = make_task_with_weight("lw_long", &[5000], 1.0);
 
         let ctx = SchedulingContext::default();
         let wspt = Wspt;
 
         // High weight / short time should have lower (better) score
-        assert!(wspt.evaluate(&high_priority_short, &ctx) < wspt.evaluate(&low_priority_long, &ctx));
+        assert!(wspt.evaluate(&high_weight_short, &ctx) < wspt.evaluate(&low_weight_long, &ctx));
     }
 
     #[test]
     fn test_wspt_weight_vs_time_tradeoff() {
-        // Short task with low priority
-        let short_low = make_task_with_priority("short_low", &[1000], 10);
-        // Long task with high priority
-        let long_high = make_task_with_priority("long_high", &[10000], 1);
+        // Ratios: short_low = 1 / 1000 = 0.001, long_high = 20 / 10000 = 0.002
+        let short_low = make_task_with_weight("short_low", &[1000], 1.0);
+        let long_high = make_task_with_weight("long_high", &[10000], 20.0);
 
         let ctx = SchedulingContext::default();
         let wspt = Wspt;
 
-        // WSPT considers ratio: short_low = (1000/11) / 1000 = 0.091
-        // long_high = (1000/2) / 10000 = 0.05
-        // short_low has better ratio
+        // The heavier long task has the better ratio
         let score_short = wspt.evaluate(&short_low, &ctx);
         let score_long = wspt.evaluate(&long_high, &ctx);
+        assert!(score_long < score_short);
+        assert!((score_short + 0.001).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_wspt_priority_fallback() {
+        // Without weights: short_low = (1000/11) / 1000 = 0.091,
+        // long_high = (1000/2) / 10000 = 0.05
+        let mut short_low = make_task_with_weight("short_low", &[1000], 1.0);
+        short_low.weight = None;
+        short_low.priority = 10;
+        let mut long_high = make_task_with_weight("long_high", &[10000], 1.0);
+        long_high.weight = None;
+        long_high.priority = 1;
+
+        let ctx = SchedulingContext::default();
+        let wspt = Wspt;
 
-        // Both should be valid finite negative values
-        assert!(score_short.is_finite());
-        assert!(score_long.is_finite());
-        assert!(score_short < 0.0);
-        assert!(score_long < 0.0);
+        assert!(wspt.evaluate(&short_low, &ctx) < wspt.evaluate(&long_high, &ctx));
+        assert!((wspt.evaluate(&short_low, &ctx) + 1000.0 / 11.0 / 1000.0).abs() < 1e-12);
     }
 }
@@ -1,7 +1,7 @@
 //! Integration tests for dispatching rules
 
 use super::*;
-use crate::models::{Activity, ActivityDuration, Task};
+use crate::models::{Activity, ActivityDuration, PriorityClass, Task};
 use chrono::{TimeZone, Utc};
 
 fn make_task(id: &str, duration_ms: i64, deadline_ms: Option<i64>) -> Task {
@@ -10,6 +10,9 @@ fn make_task(id: &str, duration_ms: i64, deadline_ms: Option<i64>) -> Task {
         name: id.to_string(),
         category: String::new(),
         priority: 0,
+        weight: None,
+        priority_class: Default::default(),
+        target_date: None,
         deadline: deadline_ms.map(|d| Utc.timestamp_millis_opt(d).unwrap()),
         release_time: None,
         activities: vec![
@@ -17,6 +20,7 @@ fn make_task(id: &str, duration_ms: i64, deadline_ms: Option<i64>) -> Task {
                 .with_duration(ActivityDuration::fixed(duration_ms))
         ],
         attributes: Default::default(),
+        loads: Default::default(),
     }
 }
 
@@ -99,6 +103,80 @@ fn test_rule_engine_weighted_mode() {
     assert_eq!(sorted[0].id, "T2");
 }
 
+#[test]
+fn test_score_matrix() {
+    let t1 = make_task("T1", 5000, Some(10000));
+    let t2 = make_task("T2", 2000, Some(10000));
+    let t3 = make_task("T3", 1000, Some(4000));
+
+    let engine = RuleEngine::new()
+        .with_rule(rules::Edd)
+        .with_tie_breaker(rules::Spt);
+    let ctx = SchedulingContext::default();
+    let tasks: Vec<&Task> = vec![&t1, &t2, &t3];
+
+    let matrix = engine.score_matrix(&tasks, &ctx);
+    assert_eq!(matrix.rules, vec!["EDD", "SPT"]);
+    assert_eq!(matrix.task_ids, vec!["T1", "T2", "T3"]);
+    assert_eq!(matrix.scores[1], vec![10000.0, 2000.0]);
+    // Same order as sort
+    let sorted: Vec<&str> = engine.sort(&tasks, &ctx).iter().map(|t| t.id.as_str()).collect();
+    assert_eq!(matrix.ranked_task_ids(), sorted);
+    assert_eq!(matrix.position("T2"), Some(1));
+
+    // T3 leads on EDD; T2 beats T1 only on the SPT tie-breaker
+    assert_eq!(matrix.deciding_rule(0), Some(0));
+    assert_eq!(matrix.deciding_rule(1), Some(1));
+    assert_eq!(matrix.deciding_rule(2), None);
+
+    let csv = matrix.to_csv();
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(lines[0], "rank,task,EDD,SPT,total");
+    assert_eq!(lines[1], "1,T3,4000,1000,4000");
+
+    // Weighted mode names the largest contribution
+    let weighted = RuleEngine::new()
+        .with_mode(EvaluationMode::Weighted)
+        .with_weighted_rule(rules::Edd, 0.1)
+        .with_weighted_rule(rules::Spt, 1.0)
+        .score_matrix(&tasks, &ctx);
+    assert_eq!(weighted.ranked_task_ids(), vec!["T3", "T2", "T1"]);
+    assert_eq!(weighted.deciding_rule(0), Some(1));
+    assert!((weighted.total(0) - 6000.0).abs() < 1e-9);
+    let json = serde_json::to_string(&weighted).unwrap();
+    assert_eq!(serde_json::from_str::<ScoreMatrix>(&json).unwrap(), weighted);
+}
+
+#[test]
+fn test_priority_classes_are_strict() {
+    let routine = make_task("R", 1000, Some(1000));
+    let mut urgent = make_task("U", 5000, None);
+    urgent.priority_class = PriorityClass::URGENT;
+    let mut emergency = make_task("E", 9000, None);
+    emergency.priority_class = PriorityClass::EMERGENCY;
+    let mut urgent_short = make_task("U2", 2000, None);
+    urgent_short.priority_class = PriorityClass::URGENT;
+    let tasks: Vec<&Task> = vec![&routine, &urgent, &emergency, &urgent_short];
+    let ctx = SchedulingContext::default();
+
+    // SPT only orders within a class, in both modes
+    for engine in [
+        RuleEngine::new().with_rule(rules::Spt),
+        RuleEngine::new().with_mode(EvaluationMode::Weighted).with_rule(rules::Spt),
+        RuleEngine::new(),
+    ] {
+        let sorted: Vec<&str> = engine.sort(&tasks, &ctx).iter().map(|t| t.id.as_str()).collect();
+        let expected = if engine.is_empty() { ["E", "U", "U2", "R"] } else { ["E", "U2", "U", "R"] };
+        assert_eq!(sorted, expected);
+    }
+
+    // Class boundaries have no deciding rule
+    let matrix = RuleEngine::new().with_rule(rules::Spt).score_matrix(&tasks, &ctx);
+    assert_eq!(matrix.deciding_rule(0), None);
+    assert_eq!(matrix.deciding_rule(1), Some(0));
+    assert_eq!(matrix.deciding_rule(2), None);
+}
+
 #[test]
 fn test_rule_engine_select_best() {
     let t1 = make_task("T1", 5000, None);
@@ -181,3 +259,22 @@ fn test_complex_scenario_with_context() {
     assert_eq!(sorted[1].id, "urgent");
     assert_eq!(sorted[2].id, "short");
 }
+
+#[test]
+fn test_weighted_mode_nan_total() {
+    let mut priced = make_task("P", 5000, None);
+    priced.attributes.insert("revenue".to_string(), "10".to_string());
+    let unpriced = make_task("U", 1000, None);
+
+    // 0 * inf = NaN for the task without revenue; it ranks last
+    let engine = RuleEngine::new()
+        .with_mode(EvaluationMode::Weighted)
+        .with_weighted_rule(rules::AttrValue::lowest_first("revenue").with_default(f64::INFINITY), 0.0)
+        .with_weighted_rule(rules::Spt, 1.0);
+    let ctx = SchedulingContext::default();
+
+    let tasks: Vec<&Task> = vec![&unpriced, &priced];
+    let sorted = engine.sort(&tasks, &ctx);
+    assert_eq!(sorted[0].id, "P");
+    assert_eq!(sorted[1].id, "U");
+}
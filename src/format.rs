@@ -0,0 +1,298 @@
+//! Format - Versioned problem and schedule documents
+//!
+//! Saved `ScheduleRequest`s and `Schedule`s carry a top-level
+//! `format_version`. Loading upgrades older documents step by step to
+//! `FORMAT_VERSION` before deserializing, so instances saved today keep
+//! loading as the model structs evolve:
+//!
+//! ```
+//! use u_ras::format::Versioned;
+//! use u_ras::models::Schedule;
+//!
+//! let json = Schedule::new().to_versioned_json().unwrap();
+//! assert!(json.contains("\"format_version\": 1"));
+//! let schedule = Schedule::from_versioned_json(&json).unwrap();
+//! assert!(schedule.assignments.is_empty());
+//! ```
+//!
+//! Version history:
+//! - 0: no `format_version` (0.2 and earlier, hand-written files). Fields
+//!   such files often omit are filled with the constructor defaults.
+//! - 1: `format_version` field introduced.
+//!
+//! Changing the serialized shape of a model means bumping
+//! `FORMAT_VERSION` and adding the step from the previous version to
+//! `migrate`.
+
+use crate::models::Schedule;
+use crate::scheduler::ScheduleRequest;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+use std::fmt;
+
+/// Version written by this crate
+pub const FORMAT_VERSION: u32 = 1;
+
+/// Top-level field holding the version
+pub const VERSION_FIELD: &str = "format_version";
+
+/// Kind of versioned document
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DocumentKind {
+    /// `ScheduleRequest`
+    Problem,
+    /// `Schedule`
+    Schedule,
+}
+
+/// Versioned document error
+#[derive(Debug)]
+pub enum FormatError {
+    /// Not valid JSON of the expected shape
+    Json(serde_json::Error),
+    /// Written by a newer version of the crate
+    UnsupportedVersion(u32),
+    /// Document is not an object or its version field is malformed
+    InvalidDocument(String),
+}
+
+impl fmt::Display for FormatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FormatError::Json(e) => write!(f, "{}", e),
+            FormatError::UnsupportedVersion(version) => write!(
+                f,
+                "format version {} is newer than supported version {}",
+                version, FORMAT_VERSION
+            ),
+            FormatError::InvalidDocument(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl std::error::Error for FormatError {}
+
+impl From<serde_json::Error> for FormatError {
+    fn from(e: serde_json::Error) -> Self {
+        FormatError::Json(e)
+    }
+}
+
+/// Model saved as a versioned document
+pub trait Versioned: Serialize + DeserializeOwned {
+    /// Which migrations apply
+    const KIND: DocumentKind;
+
+    /// Serialize as pretty JSON stamped with `FORMAT_VERSION`
+    fn to_versioned_json(&self) -> Result<String, FormatError> {
+        let mut value = serde_json::to_value(self)?;
+        let Value::Object(fields) = &mut value else {
+            return Err(FormatError::InvalidDocument(
+                "document is not a JSON object".to_string(),
+            ));
+        };
+        fields.insert(VERSION_FIELD.to_string(), json!(FORMAT_VERSION));
+        Ok(serde_json::to_string_pretty(&value)?)
+    }
+
+    /// Deserialize JSON of any supported version
+    fn from_versioned_json(text: &str) -> Result<Self, FormatError> {
+        let mut value: Value = serde_json::from_str(text)?;
+        migrate(&mut value, Self::KIND)?;
+        Ok(serde_json::from_value(value)?)
+    }
+}
+
+impl Versioned for ScheduleRequest {
+    const KIND: DocumentKind = DocumentKind::Problem;
+}
+
+impl Versioned for Schedule {
+    const KIND: DocumentKind = DocumentKind::Schedule;
+}
+
+/// Upgrade a document in place to `FORMAT_VERSION`
+///
+/// Removes the version field and returns the version the document was
+/// written with (0 if it had none).
+pub fn migrate(value: &mut Value, kind: DocumentKind) -> Result<u32, FormatError> {
+    let Value::Object(fields) = value else {
+        return Err(FormatError::InvalidDocument(
+            "document is not a JSON object".to_string(),
+        ));
+    };
+    let version = match fields.remove(VERSION_FIELD) {
+        None => 0,
+        Some(v) => v
+            .as_u64()
+            .and_then(|v| u32::try_from(v).ok())
+            .ok_or_else(|| {
+                FormatError::InvalidDocument(format!("invalid {}: {}", VERSION_FIELD, v))
+            })?,
+    };
+    if version > FORMAT_VERSION {
+        return Err(FormatError::UnsupportedVersion(version));
+    }
+
+    for from in version..FORMAT_VERSION {
+        match (from, kind) {
+            (0, DocumentKind::Problem) => problem_v0(fields),
+            (0, DocumentKind::Schedule) => schedule_v0(fields),
+            _ => {}
+        }
+    }
+    Ok(version)
+}
+
+/// Version 0 problem: fill omitted fields with constructor defaults
+fn problem_v0(request: &mut Map<String, Value>) {
+    fill(request, "start_time_ms", json!(0));
+    fill(request, "resources", json!([]));
+    for resource in objects(request.get_mut("resources")) {
+        let id = resource.get("id").cloned().unwrap_or(json!(""));
+        fill(resource, "name", id);
+        fill(resource, "capacity", json!(1));
+        fill(resource, "efficiency", json!(1.0));
+        fill(resource, "skills", json!([]));
+        fill(resource, "attributes", json!({}));
+    }
+    fill(request, "tasks", json!([]));
+    for task in objects(request.get_mut("tasks")) {
+        let id = task.get("id").cloned().unwrap_or(json!(""));
+        fill(task, "name", id.clone());
+        fill(task, "category", json!(""));
+        fill(task, "priority", json!(1));
+        fill(task, "attributes", json!({}));
+        fill(task, "activities", json!([]));
+        for (sequence, activity) in objects(task.get_mut("activities")).enumerate() {
+            fill(activity, "task_id", id.clone());
+            fill(activity, "sequence", json!(sequence));
+            fill(activity, "duration", json!({}));
+            if let Some(Value::Object(duration)) = activity.get_mut("duration") {
+                fill(duration, "setup_ms", json!(0));
+                fill(duration, "process_ms", json!(0));
+                fill(duration, "teardown_ms", json!(0));
+            }
+            fill(activity, "resource_requirements", json!([]));
+            for requirement in objects(activity.get_mut("resource_requirements")) {
+                fill(requirement, "quantity", json!(1));
+                fill(requirement, "candidates", json!([]));
+                fill(requirement, "required_skills", json!([]));
+            }
+            fill(activity, "predecessors", json!([]));
+            fill(activity, "splittable", json!(false));
+            fill(activity, "min_split_ms", json!(0));
+            fill(activity, "attributes", json!({}));
+        }
+    }
+}
+
+/// Version 0 schedule: fill omitted fields, derive a missing makespan
+fn schedule_v0(schedule: &mut Map<String, Value>) {
+    fill(schedule, "assignments", json!([]));
+    let mut makespan = 0;
+    for assignment in objects(schedule.get_mut("assignments")) {
+        fill(assignment, "setup_ms", json!(0));
+        if let Some(end) = assignment.get("end_ms").and_then(Value::as_i64) {
+            makespan = makespan.max(end);
+        }
+    }
+    fill(schedule, "makespan_ms", json!(makespan));
+    fill(schedule, "violations", json!([]));
+}
+
+/// Insert a field if the document does not have it
+fn fill(object: &mut Map<String, Value>, key: &str, default: Value) {
+    object.entry(key).or_insert(default);
+}
+
+/// Objects of an array field (other elements are left to serde to reject)
+fn objects(array: Option<&mut Value>) -> impl Iterator<Item = &mut Map<String, Value>> {
+    array
+        .and_then(Value::as_array_mut)
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_object_mut)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Activity, Assignment, Resource, ResourceType, Task};
+
+    #[test]
+    fn test_round_trip() {
+        let request = ScheduleRequest::new(
+            vec![Task::new("T1").with_activity(Activity::new("A1", "T1", 0).with_time(0, 500, 0))],
+            vec![Resource::new("M1", ResourceType::Primary)],
+        );
+        let json = request.to_versioned_json().unwrap();
+        let value: Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value[VERSION_FIELD], json!(FORMAT_VERSION));
+
+        let loaded = ScheduleRequest::from_versioned_json(&json).unwrap();
+        assert_eq!(loaded.tasks[0].activities[0].duration.process_ms, 500);
+    }
+
+    #[test]
+    fn test_migrate_v0_problem() {
+        let json = r#"{
+            "tasks": [{
+                "id": "T1",
+                "activities": [
+                    {"id": "A1", "duration": {"process_ms": 1000},
+                     "resource_requirements": [{"resource_type": "machine", "candidates": ["M1"]}]},
+                    {"id": "A2", "duration": {"process_ms": 500}, "predecessors": ["A1"]}
+                ]
+            }],
+            "resources": [{"id": "M1", "resource_type": "Primary"}]
+        }"#;
+        let mut value: Value = serde_json::from_str(json).unwrap();
+        assert!(serde_json::from_value::<ScheduleRequest>(value.clone()).is_err());
+        assert_eq!(migrate(&mut value, DocumentKind::Problem).unwrap(), 0);
+
+        let request = ScheduleRequest::from_versioned_json(json).unwrap();
+        let task = &request.tasks[0];
+        assert_eq!(task.name, "T1");
+        assert_eq!(task.priority, 1);
+        assert_eq!(task.activities[1].task_id, "T1");
+        assert_eq!(task.activities[1].sequence, 1);
+        assert_eq!(task.activities[0].resource_requirements[0].quantity, 1);
+        assert_eq!(request.resources[0].efficiency, 1.0);
+    }
+
+    #[test]
+    fn test_migrate_v0_schedule() {
+        let mut schedule = Schedule::new();
+        schedule.add_assignment(Assignment::new("A1", "T1", "M1", 0, 1500));
+        let mut value = serde_json::to_value(&schedule).unwrap();
+        let fields = value.as_object_mut().unwrap();
+        fields.remove("makespan_ms");
+        fields.remove("violations");
+        fields["assignments"][0]
+            .as_object_mut()
+            .unwrap()
+            .remove("setup_ms");
+
+        let loaded = Schedule::from_versioned_json(&value.to_string()).unwrap();
+        assert_eq!(loaded.makespan_ms, 1500);
+        assert_eq!(loaded.assignments[0].setup_ms, 0);
+    }
+
+    #[test]
+    fn test_newer_version_rejected() {
+        let json = format!(
+            r#"{{"format_version": {}, "assignments": []}}"#,
+            FORMAT_VERSION + 1
+        );
+        assert!(matches!(
+            Schedule::from_versioned_json(&json),
+            Err(FormatError::UnsupportedVersion(_))
+        ));
+        assert!(matches!(
+            Schedule::from_versioned_json(r#"{"format_version": "1"}"#),
+            Err(FormatError::InvalidDocument(_))
+        ));
+    }
+}
@@ -3,8 +3,12 @@
 //! Dual-vector representation:
 //! - OSV (Operation Sequence Vector): Activity execution order
 //! - MAV (Machine Assignment Vector): Resource assignment
+//!
+//! Genes are integer indices into the activities table (task index,
+//! candidate index), so a chromosome holds two flat integer vectors.
+//! Task and resource IDs are only looked up when encoding or decoding.
 
-use crate::models::{Resource, ResourceType};
+use crate::models::{Assignment, Resource, ResourceType, Schedule};
 use rand::prelude::*;
 use std::collections::HashMap;
 
@@ -12,15 +16,13 @@ use std::collections::HashMap;
 #[derive(Debug, Clone, PartialEq)]
 pub struct Chromosome {
     /// Operation Sequence Vector - activity execution order
-    /// Represented as task ID permutation, k-th occurrence = k-th activity
-    pub osv: Vec<String>,
+    /// Represented as task index permutation (tasks numbered in order of
+    /// first appearance in the activities), k-th occurrence = k-th activity
+    pub osv: Vec<u32>,
 
-    /// Machine Assignment Vector - resource for each activity
+    /// Machine Assignment Vector - candidate index for each activity
     /// Index: fixed order of all activities (Task1-A1, Task1-A2, ..., TaskN-AM)
-    pub mav: Vec<String>,
-
-    /// Activity index mapping: (task_id, sequence) -> mav_index
-    pub activity_index: HashMap<(String, i32), usize>,
+    pub mav: Vec<u32>,
 
     /// Fitness (lower is better)
     pub fitness: f64,
@@ -36,16 +38,56 @@ pub struct ActivityInfo {
     pub process_time_ms: i64,
 }
 
+/// Activities table grouped by task
+pub(crate) struct TaskLayout<'a> {
+    /// Task IDs by task index
+    pub ids: Vec<&'a str>,
+    /// Task index of each activity
+    pub task_of: Vec<u32>,
+    /// Activity indices of each task in sequence order
+    pub activities: Vec<Vec<usize>>,
+}
+
+impl<'a> TaskLayout<'a> {
+    pub fn new(activities: &'a [ActivityInfo]) -> Self {
+        let mut index: HashMap<&str, u32> = HashMap::new();
+        let mut layout = Self {
+            ids: Vec::new(),
+            task_of: Vec::with_capacity(activities.len()),
+            activities: Vec::new(),
+        };
+        for (idx, act) in activities.iter().enumerate() {
+            let task = *index.entry(act.task_id.as_str()).or_insert_with(|| {
+                layout.ids.push(act.task_id.as_str());
+                layout.activities.push(Vec::new());
+                layout.ids.len() as u32 - 1
+            });
+            layout.task_of.push(task);
+            layout.activities[task as usize].push(idx);
+        }
+        for list in &mut layout.activities {
+            list.sort_by_key(|&idx| activities[idx].sequence);
+        }
+        layout
+    }
+
+    fn task_index(&self, task_id: &str) -> Option<u32> {
+        self.ids
+            .iter()
+            .position(|id| *id == task_id)
+            .map(|t| t as u32)
+    }
+}
+
 impl Chromosome {
     /// Create random chromosome
     pub fn random(activities: &[ActivityInfo], rng: &mut impl Rng) -> Self {
-        let (osv, activity_index) = Self::create_random_osv(activities, rng);
+        let osv = Self::create_random_osv(activities, rng);
         let mav = Self::create_random_mav(activities, rng);
 
         Self {
             osv,
             mav,
-            activity_index,
             fitness: f64::INFINITY,
         }
     }
@@ -56,13 +98,12 @@ impl Chromosome {
         resources: &[Resource],
         rng: &mut impl Rng,
     ) -> Self {
-        let (osv, activity_index) = Self::create_random_osv(activities, rng);
+        let osv = Self::create_random_osv(activities, rng);
         let mav = Self::create_load_balanced_mav(activities, resources);
 
         Self {
             osv,
             mav,
-            activity_index,
             fitness: f64::INFINITY,
         }
     }
@@ -73,148 +114,264 @@ impl Chromosome {
         process_times: &std::collections::HashMap<(String, String), i64>,
         rng: &mut impl Rng,
     ) -> Self {
-        let (osv, activity_index) = Self::create_random_osv(activities, rng);
+        let osv = Self::create_random_osv(activities, rng);
         let mav = Self::create_shortest_time_mav(activities, process_times);
 
         Self {
             osv,
             mav,
-            activity_index,
             fitness: f64::INFINITY,
         }
     }
 
+    /// Encode a task ID sequence and one resource ID per activity
+    ///
+    /// Returns `None` for an unknown task, a resource that is not a
+    /// candidate of its activity, or vectors of the wrong length.
+    pub fn encode(
+        activities: &[ActivityInfo],
+        sequence: &[&str],
+        resources: &[&str],
+    ) -> Option<Self> {
+        if sequence.len() != activities.len() || resources.len() != activities.len() {
+            return None;
+        }
+        let layout = TaskLayout::new(activities);
+        let osv = sequence
+            .iter()
+            .map(|task_id| layout.task_index(task_id))
+            .collect::<Option<Vec<u32>>>()?;
+        let mav = activities
+            .iter()
+            .zip(resources)
+            .map(|(act, resource_id)| {
+                if act.candidates.is_empty() {
+                    Some(0)
+                } else {
+                    act.candidates
+                        .iter()
+                        .position(|c| c == resource_id)
+                        .map(|k| k as u32)
+                }
+            })
+            .collect::<Option<Vec<u32>>>()?;
+
+        Some(Self {
+            osv,
+            mav,
+            fitness: f64::INFINITY,
+        })
+    }
+
     /// Create MAV - shortest processing time
     fn create_shortest_time_mav(
         activities: &[ActivityInfo],
         process_times: &std::collections::HashMap<(String, String), i64>,
-    ) -> Vec<String> {
+    ) -> Vec<u32> {
         activities
             .iter()
             .map(|act| {
-                if act.candidates.is_empty() {
-                    return "NONE".to_string();
-                }
-
                 // Select resource with shortest processing time
-                act.candidates
-                    .iter()
-                    .min_by_key(|c| {
+                (0..act.candidates.len())
+                    .min_by_key(|&k| {
                         process_times
-                            .get(&(act.activity_id.clone(), (*c).clone()))
+                            .get(&(act.activity_id.clone(), act.candidates[k].clone()))
                             .unwrap_or(&i64::MAX)
                     })
-                    .cloned()
-                    .unwrap_or_else(|| act.candidates[0].clone())
+                    .unwrap_or(0) as u32
             })
             .collect()
     }
 
     /// Create OSV - random order
-    fn create_random_osv(
-        activities: &[ActivityInfo],
-        rng: &mut impl Rng,
-    ) -> (Vec<String>, HashMap<(String, i32), usize>) {
-        let mut task_activity_counts: HashMap<String, i32> = HashMap::new();
-        let mut activity_index: HashMap<(String, i32), usize> = HashMap::new();
-
-        for (idx, act) in activities.iter().enumerate() {
-            *task_activity_counts.entry(act.task_id.clone()).or_insert(0) += 1;
-            activity_index.insert((act.task_id.clone(), act.sequence), idx);
-        }
-
-        // Build OSV: each task ID appears once per activity
-        let mut osv: Vec<String> = Vec::new();
-        for (task_id, count) in &task_activity_counts {
-            for _ in 0..*count {
-                osv.push(task_id.clone());
-            }
-        }
+    fn create_random_osv(activities: &[ActivityInfo], rng: &mut impl Rng) -> Vec<u32> {
+        // Build OSV: each task index appears once per activity (in activity
+        // order, so the shuffle alone decides and a fixed seed reproduces it)
+        let mut osv = TaskLayout::new(activities).task_of;
 
         osv.shuffle(rng);
-        (osv, activity_index)
+        osv
     }
 
     /// Create MAV - random assignment
-    fn create_random_mav(activities: &[ActivityInfo], rng: &mut impl Rng) -> Vec<String> {
+    pub(crate) fn create_random_mav(activities: &[ActivityInfo], rng: &mut impl Rng) -> Vec<u32> {
         activities
             .iter()
             .map(|act| {
                 if act.candidates.is_empty() {
-                    "NONE".to_string()
+                    0
                 } else {
-                    act.candidates.choose(rng).unwrap().clone()
+                    rng.gen_range(0..act.candidates.len()) as u32
                 }
             })
             .collect()
     }
 
     /// Create MAV - load balanced
-    fn create_load_balanced_mav(
-        activities: &[ActivityInfo],
-        resources: &[Resource],
-    ) -> Vec<String> {
-        let mut resource_load: HashMap<String, i64> = HashMap::new();
+    fn create_load_balanced_mav(activities: &[ActivityInfo], resources: &[Resource]) -> Vec<u32> {
+        let mut resource_load: HashMap<&str, i64> = HashMap::new();
 
         // Initialize primary resources
         for res in resources {
             if res.resource_type == ResourceType::Primary {
-                resource_load.insert(res.id.clone(), 0);
+                resource_load.insert(res.id.as_str(), 0);
             }
         }
 
         activities
             .iter()
             .map(|act| {
-                if act.candidates.is_empty() {
-                    return "NONE".to_string();
-                }
-
                 // Select resource with lowest load
-                let best = act
-                    .candidates
-                    .iter()
-                    .filter(|c| resource_load.contains_key(*c))
-                    .min_by_key(|c| resource_load.get(*c).unwrap_or(&i64::MAX))
-                    .cloned()
-                    .unwrap_or_else(|| act.candidates[0].clone());
+                let best = (0..act.candidates.len())
+                    .filter(|&k| resource_load.contains_key(act.candidates[k].as_str()))
+                    .min_by_key(|&k| resource_load[act.candidates[k].as_str()])
+                    .unwrap_or(0);
 
-                if let Some(load) = resource_load.get_mut(&best) {
+                if let Some(load) = act
+                    .candidates
+                    .get(best)
+                    .and_then(|c| resource_load.get_mut(c.as_str()))
+                {
                     *load += act.process_time_ms;
                 }
 
-                best
+                best as u32
+            })
+            .collect()
+    }
+
+    /// Activity indices in OSV order
+    ///
+    /// The k-th occurrence of a task is its k-th activity by sequence;
+    /// surplus occurrences and unknown task indices are skipped.
+    pub fn activity_order(&self, activities: &[ActivityInfo]) -> Vec<usize> {
+        let layout = TaskLayout::new(activities);
+        let mut next = vec![0usize; layout.activities.len()];
+        self.osv
+            .iter()
+            .filter_map(|&task| {
+                let list = layout.activities.get(task as usize)?;
+                let k = next[task as usize];
+                next[task as usize] += 1;
+                list.get(k).copied()
             })
             .collect()
     }
 
     /// Decode OSV to (task_id, sequence) pairs
-    pub fn decode_osv(&self) -> Vec<(String, i32)> {
-        let mut task_counters: HashMap<String, i32> = HashMap::new();
+    pub fn decode_osv(&self, activities: &[ActivityInfo]) -> Vec<(String, i32)> {
+        self.activity_order(activities)
+            .into_iter()
+            .map(|idx| (activities[idx].task_id.clone(), activities[idx].sequence))
+            .collect()
+    }
 
+    /// OSV as task IDs
+    pub fn task_sequence<'a>(&self, activities: &'a [ActivityInfo]) -> Vec<&'a str> {
+        let layout = TaskLayout::new(activities);
         self.osv
             .iter()
-            .map(|task_id| {
-                let seq = task_counters.entry(task_id.clone()).or_insert(0);
-                *seq += 1;
-                (task_id.clone(), *seq)
-            })
+            .filter_map(|&task| layout.ids.get(task as usize).copied())
             .collect()
     }
 
+    /// Decode into a semi-active schedule
+    ///
+    /// Activities are placed in OSV order, each as early as its task and
+    /// assigned resource allow, taking `duration_ms` of its info.
+    pub fn decode(
+        &self,
+        activities: &[ActivityInfo],
+        start_time_ms: i64,
+        duration_ms: impl Fn(&ActivityInfo) -> i64,
+    ) -> Schedule {
+        let mut schedule = Schedule::new();
+        let mut task_ready: HashMap<&str, i64> = HashMap::new();
+        let mut resource_ready: HashMap<&str, i64> = HashMap::new();
+
+        for idx in self.activity_order(activities) {
+            let act = &activities[idx];
+            let Some(resource_id) = self
+                .mav
+                .get(idx)
+                .and_then(|&k| act.candidates.get(k as usize))
+            else {
+                continue;
+            };
+            let start = task_ready
+                .get(act.task_id.as_str())
+                .copied()
+                .unwrap_or(start_time_ms)
+                .max(
+                    resource_ready
+                        .get(resource_id.as_str())
+                        .copied()
+                        .unwrap_or(start_time_ms),
+                );
+            let end = start + duration_ms(act);
+            schedule.add_assignment(Assignment::new(
+                &act.activity_id,
+                &act.task_id,
+                resource_id,
+                start,
+                end,
+            ));
+            task_ready.insert(&act.task_id, end);
+            resource_ready.insert(resource_id, end);
+        }
+        schedule
+    }
+
     /// Get assigned resource for activity
-    pub fn get_assigned_resource(&self, task_id: &str, sequence: i32) -> Option<&String> {
-        self.activity_index
-            .get(&(task_id.to_string(), sequence))
-            .and_then(|idx| self.mav.get(*idx))
+    pub fn get_assigned_resource<'a>(
+        &self,
+        activities: &'a [ActivityInfo],
+        task_id: &str,
+        sequence: i32,
+    ) -> Option<&'a String> {
+        let idx = activities
+            .iter()
+            .position(|a| a.task_id == task_id && a.sequence == sequence)?;
+        activities[idx].candidates.get(*self.mav.get(idx)? as usize)
     }
 
-    /// Set resource for activity
-    pub fn set_resource(&mut self, task_id: &str, sequence: i32, resource_id: String) {
-        if let Some(idx) = self.activity_index.get(&(task_id.to_string(), sequence)) {
-            if *idx < self.mav.len() {
-                self.mav[*idx] = resource_id;
-            }
+    /// Share of OSV positions holding a different task (0.0 to 1.0)
+    pub fn osv_distance(&self, other: &Chromosome) -> f64 {
+        let len = self.osv.len().max(other.osv.len());
+        if len == 0 {
+            return 0.0;
+        }
+        let same = self
+            .osv
+            .iter()
+            .zip(&other.osv)
+            .filter(|(a, b)| a == b)
+            .count();
+        (len - same) as f64 / len as f64
+    }
+
+    /// Set resource for activity (ignored unless it is a candidate)
+    pub fn set_resource(
+        &mut self,
+        activities: &[ActivityInfo],
+        task_id: &str,
+        sequence: i32,
+        resource_id: &str,
+    ) {
+        let Some(idx) = activities
+            .iter()
+            .position(|a| a.task_id == task_id && a.sequence == sequence)
+        else {
+            return;
+        };
+        if let (Some(k), Some(gene)) = (
+            activities[idx]
+                .candidates
+                .iter()
+                .position(|c| c == resource_id),
+            self.mav.get_mut(idx),
+        ) {
+            *gene = k as u32;
         }
     }
 
@@ -225,23 +382,25 @@ impl Chromosome {
         }
 
         // Check task counts
-        let mut task_counts: HashMap<String, i32> = HashMap::new();
-        for task_id in &self.osv {
-            *task_counts.entry(task_id.clone()).or_insert(0) += 1;
-        }
-
-        let mut expected: HashMap<String, i32> = HashMap::new();
-        for act in activities {
-            *expected.entry(act.task_id.clone()).or_insert(0) += 1;
+        let layout = TaskLayout::new(activities);
+        let mut task_counts = vec![0usize; layout.activities.len()];
+        for &task in &self.osv {
+            match task_counts.get_mut(task as usize) {
+                Some(count) => *count += 1,
+                None => return false,
+            }
         }
-
-        if task_counts != expected {
+        if task_counts
+            .iter()
+            .zip(&layout.activities)
+            .any(|(count, list)| *count != list.len())
+        {
             return false;
         }
 
         // Check resource candidates
         for (idx, act) in activities.iter().enumerate() {
-            if !act.candidates.is_empty() && !act.candidates.contains(&self.mav[idx]) {
+            if !act.candidates.is_empty() && self.mav[idx] as usize >= act.candidates.len() {
                 return false;
             }
         }
@@ -298,7 +457,7 @@ mod tests {
         let mut rng = rand::thread_rng();
 
         let chromosome = Chromosome::random(&activities, &mut rng);
-        let decoded = chromosome.decode_osv();
+        let decoded = chromosome.decode_osv(&activities);
 
         assert_eq!(decoded.len(), 3);
 
@@ -309,6 +468,41 @@ mod tests {
         assert_eq!(t2_count, 1);
     }
 
+    #[test]
+    fn test_decode_schedule() {
+        let activities = create_test_activities();
+
+        let chromosome =
+            Chromosome::encode(&activities, &["T1", "T2", "T1"], &["R1", "R3", "R1"]).unwrap();
+        assert_eq!(chromosome.osv, vec![0, 1, 0]);
+        assert_eq!(chromosome.mav, vec![0, 1, 0]);
+        assert_eq!(
+            chromosome.task_sequence(&activities),
+            vec!["T1", "T2", "T1"]
+        );
+        assert_eq!(
+            chromosome.get_assigned_resource(&activities, "T1", 2),
+            Some(&"R3".to_string())
+        );
+        // R3 is not a candidate of T1-A1
+        assert!(
+            Chromosome::encode(&activities, &["T1", "T2", "T1"], &["R3", "R3", "R1"]).is_none()
+        );
+
+        let schedule = chromosome.decode(&activities, 0, |a| a.process_time_ms);
+
+        // T2-A1 waits for R1, T1-A2 only for its predecessor
+        assert_eq!(
+            schedule.assignment_for_activity("T2-A1").unwrap().start_ms,
+            30000
+        );
+        assert_eq!(
+            schedule.assignment_for_activity("T1-A2").unwrap().start_ms,
+            30000
+        );
+        assert_eq!(schedule.makespan_ms, 75000);
+    }
+
     #[test]
     fn test_validity() {
         let activities = create_test_activities();
@@ -318,7 +512,85 @@ mod tests {
         assert!(chromosome.is_valid(&activities));
 
         // Invalid resource
-        chromosome.mav[0] = "INVALID".to_string();
+        chromosome.mav[0] = 2;
         assert!(!chromosome.is_valid(&activities));
+
+        // Setting by ID only accepts candidates
+        chromosome.set_resource(&activities, "T1", 1, "R3");
+        assert_eq!(chromosome.mav[0], 2);
+        chromosome.set_resource(&activities, "T1", 1, "R2");
+        assert!(chromosome.is_valid(&activities));
+    }
+
+    #[test]
+    fn test_encode_round_trip() {
+        let activities = create_test_activities();
+        let sequence = ["T2", "T1", "T1"];
+        let resources = ["R2", "R3", "R3"];
+
+        let chromosome = Chromosome::encode(&activities, &sequence, &resources).unwrap();
+        assert_eq!(chromosome.task_sequence(&activities), sequence);
+
+        let schedule = chromosome.decode(&activities, 0, |a| a.process_time_ms);
+        assert_eq!(schedule.assignment_count(), activities.len());
+        for (act, resource_id) in activities.iter().zip(resources) {
+            let assignment = schedule.assignment_for_activity(&act.activity_id).unwrap();
+            assert_eq!(assignment.task_id, act.task_id);
+            assert_eq!(assignment.resource_id, resource_id);
+        }
+    }
+
+    #[test]
+    fn test_operators_keep_permutation() {
+        use crate::ga::operators::{CrossoverType, GeneticOperators, MutationType};
+
+        let activities = create_test_activities();
+        let mut rng = StdRng::seed_from_u64(7);
+        for crossover_type in [CrossoverType::POX, CrossoverType::LOX, CrossoverType::JOX] {
+            for mutation_type in [
+                MutationType::Swap,
+                MutationType::Insert,
+                MutationType::Invert,
+            ] {
+                let operators = GeneticOperators {
+                    crossover_type,
+                    mutation_type,
+                };
+                for _ in 0..20 {
+                    let p1 = Chromosome::random(&activities, &mut rng);
+                    let p2 = Chromosome::random(&activities, &mut rng);
+                    let (mut c1, mut c2) = operators.crossover(&p1, &p2, &activities, &mut rng);
+                    operators.mutate(&mut c1, &activities, &mut rng);
+                    operators.mutate(&mut c2, &activities, &mut rng);
+                    for child in [&c1, &c2] {
+                        let mut osv = child.osv.clone();
+                        osv.sort_unstable();
+                        assert_eq!(osv, vec![0, 0, 1]);
+                        for (gene, act) in child.mav.iter().zip(&activities) {
+                            assert!((*gene as usize) < act.candidates.len());
+                        }
+                        assert!(child.is_valid(&activities));
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_resource_accessors() {
+        let activities = create_test_activities();
+        let mut chromosome = Chromosome::random(&activities, &mut StdRng::seed_from_u64(1));
+
+        for act in &activities {
+            for candidate in &act.candidates {
+                chromosome.set_resource(&activities, &act.task_id, act.sequence, candidate);
+                assert_eq!(
+                    chromosome.get_assigned_resource(&activities, &act.task_id, act.sequence),
+                    Some(candidate)
+                );
+            }
+        }
+        assert_eq!(chromosome.get_assigned_resource(&activities, "T9", 1), None);
+        assert!(chromosome.is_valid(&activities));
     }
 }
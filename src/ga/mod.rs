@@ -5,7 +5,11 @@
 mod chromosome;
 mod operators;
 mod population;
+mod scheduler;
+mod stochastic;
 
 pub use chromosome::*;
 pub use operators::*;
 pub use population::*;
+pub use scheduler::*;
+pub use stochastic::*;
@@ -2,7 +2,7 @@
 //!
 //! Domain-agnostic genetic operators for scheduling
 
-use super::chromosome::{ActivityInfo, Chromosome};
+use super::chromosome::{ActivityInfo, Chromosome, TaskLayout};
 use rand::prelude::*;
 
 /// Genetic operators configuration
@@ -95,7 +95,7 @@ impl GeneticOperators {
         if !chromosome.mav.is_empty() && !activities.is_empty() {
             let idx = rng.gen_range(0..chromosome.mav.len().min(activities.len()));
             if !activities[idx].candidates.is_empty() {
-                chromosome.mav[idx] = activities[idx].candidates.choose(rng).unwrap().clone();
+                chromosome.mav[idx] = rng.gen_range(0..activities[idx].candidates.len()) as u32;
             }
         }
 
@@ -110,60 +110,46 @@ impl GeneticOperators {
         activities: &[ActivityInfo],
         rng: &mut impl Rng,
     ) -> (Chromosome, Chromosome) {
-        let task_ids: Vec<String> = activities
-            .iter()
-            .map(|a| a.task_id.clone())
-            .collect::<std::collections::HashSet<_>>()
-            .into_iter()
-            .collect();
-
-        if task_ids.is_empty() {
+        let task_count = TaskLayout::new(activities).ids.len();
+        if task_count == 0 {
             return (p1.clone(), p2.clone());
         }
 
         // Random task set
-        let set_size = rng.gen_range(1..=task_ids.len().max(1));
-        let selected: std::collections::HashSet<String> =
-            task_ids.choose_multiple(rng, set_size).cloned().collect();
-
-        let child1_osv = self.pox_build_child(&p1.osv, &p2.osv, &selected);
-        let child2_osv = self.pox_build_child(&p2.osv, &p1.osv, &selected);
-
-        let mut child1 = Chromosome::random(activities, rng);
-        let mut child2 = Chromosome::random(activities, rng);
-
-        child1.osv = child1_osv;
-        child2.osv = child2_osv;
-
-        // Inherit MAV from parents
-        for i in 0..child1.mav.len().min(p1.mav.len()) {
-            child1.mav[i] = p1.mav[i].clone();
-        }
-        for i in 0..child2.mav.len().min(p2.mav.len()) {
-            child2.mav[i] = p2.mav[i].clone();
+        let set_size = rng.gen_range(1..=task_count);
+        let mut selected = vec![false; task_count];
+        for task in rand::seq::index::sample(rng, task_count, set_size) {
+            selected[task] = true;
         }
 
+        // Children inherit the MAV of their first parent
+        let child1 = Chromosome {
+            osv: self.pox_build_child(&p1.osv, &p2.osv, &selected),
+            mav: p1.mav.clone(),
+            fitness: f64::INFINITY,
+        };
+        let child2 = Chromosome {
+            osv: self.pox_build_child(&p2.osv, &p1.osv, &selected),
+            mav: p2.mav.clone(),
+            fitness: f64::INFINITY,
+        };
+
         (child1, child2)
     }
 
-    fn pox_build_child(
-        &self,
-        p1: &[String],
-        p2: &[String],
-        selected: &std::collections::HashSet<String>,
-    ) -> Vec<String> {
-        let mut child = vec![String::new(); p1.len()];
-        let mut p2_iter = p2.iter().filter(|t| !selected.contains(*t)).peekable();
-
-        for (i, task) in p1.iter().enumerate() {
-            if selected.contains(task) {
-                child[i] = task.clone();
-            } else if let Some(t) = p2_iter.next() {
-                child[i] = t.clone();
-            }
-        }
-
-        child
+    fn pox_build_child(&self, p1: &[u32], p2: &[u32], selected: &[bool]) -> Vec<u32> {
+        let is_selected = |task: u32| selected.get(task as usize).copied().unwrap_or(false);
+        let mut p2_iter = p2.iter().filter(|&&t| !is_selected(t));
+
+        p1.iter()
+            .map(|&task| {
+                if is_selected(task) {
+                    task
+                } else {
+                    p2_iter.next().copied().unwrap_or(task)
+                }
+            })
+            .collect()
     }
 
     /// LOX crossover
@@ -187,32 +173,27 @@ impl GeneticOperators {
             (points[1], points[0])
         };
 
-        let child1_osv = self.lox_build_child(&p1.osv, &p2.osv, start, end);
-        let child2_osv = self.lox_build_child(&p2.osv, &p1.osv, start, end);
-
-        let mut child1 = Chromosome::random(activities, rng);
-        let mut child2 = Chromosome::random(activities, rng);
-
-        child1.osv = child1_osv;
-        child2.osv = child2_osv;
+        let child1 = Chromosome {
+            osv: self.lox_build_child(&p1.osv, &p2.osv, start, end),
+            mav: Chromosome::create_random_mav(activities, rng),
+            fitness: f64::INFINITY,
+        };
+        let child2 = Chromosome {
+            osv: self.lox_build_child(&p2.osv, &p1.osv, start, end),
+            mav: Chromosome::create_random_mav(activities, rng),
+            fitness: f64::INFINITY,
+        };
 
         (child1, child2)
     }
 
-    fn lox_build_child(
-        &self,
-        p1: &[String],
-        p2: &[String],
-        start: usize,
-        end: usize,
-    ) -> Vec<String> {
-        let mut child = vec![String::new(); p1.len()];
-        let segment: Vec<String> = p1[start..=end].to_vec();
+    fn lox_build_child(&self, p1: &[u32], p2: &[u32], start: usize, end: usize) -> Vec<u32> {
+        const UNSET: u32 = u32::MAX;
+        let mut child = vec![UNSET; p1.len()];
+        let segment = &p1[start..=end];
 
         // Copy segment
-        for (i, item) in segment.iter().enumerate() {
-            child[start + i] = item.clone();
-        }
+        child[start..=end].copy_from_slice(segment);
 
         // Fill rest from p2
         let mut child_idx = (end + 1) % p1.len();
@@ -223,9 +204,9 @@ impl GeneticOperators {
             if (!segment.contains(item)
                 || segment.iter().filter(|&x| x == item).count()
                     < p2.iter().filter(|&x| x == item).count())
-                && child[child_idx].is_empty()
+                && child[child_idx] == UNSET
             {
-                child[child_idx] = item.clone();
+                child[child_idx] = *item;
                 child_idx = (child_idx + 1) % p1.len();
             }
         }
@@ -246,7 +227,7 @@ impl GeneticOperators {
     }
 
     /// Swap mutation
-    fn swap_mutation(&self, osv: &mut Vec<String>, rng: &mut impl Rng) {
+    fn swap_mutation(&self, osv: &mut [u32], rng: &mut impl Rng) {
         if osv.len() < 2 {
             return;
         }
@@ -256,7 +237,7 @@ impl GeneticOperators {
     }
 
     /// Insert mutation
-    fn insert_mutation(&self, osv: &mut Vec<String>, rng: &mut impl Rng) {
+    fn insert_mutation(&self, osv: &mut Vec<u32>, rng: &mut impl Rng) {
         if osv.len() < 2 {
             return;
         }
@@ -267,7 +248,7 @@ impl GeneticOperators {
     }
 
     /// Invert mutation
-    fn invert_mutation(&self, osv: &mut Vec<String>, rng: &mut impl Rng) {
+    fn invert_mutation(&self, osv: &mut [u32], rng: &mut impl Rng) {
         if osv.len() < 2 {
             return;
         }
@@ -5,6 +5,7 @@
 use crate::ga::chromosome::{ActivityInfo, Chromosome};
 use crate::ga::operators::{tournament_selection, GeneticOperators};
 use crate::models::Resource;
+use crate::telemetry;
 use rand::prelude::*;
 
 /// Population manager
@@ -41,6 +42,8 @@ pub struct GaParams {
     pub convergence_threshold: f64,
     /// Time limit in milliseconds (None = no limit)
     pub time_limit_ms: Option<i64>,
+    /// Fitness sharing niche radius on OSV distance (None = off)
+    pub sharing_radius: Option<f64>,
 }
 
 impl Default for GaParams {
@@ -53,6 +56,7 @@ impl Default for GaParams {
             convergence_generations: 50,
             convergence_threshold: 0.001,
             time_limit_ms: None,
+            sharing_radius: None,
         }
     }
 }
@@ -68,6 +72,7 @@ impl GaParams {
             convergence_generations: 20,
             convergence_threshold: 0.005,
             time_limit_ms: Some(10_000),
+            sharing_radius: None,
         }
     }
 
@@ -81,6 +86,7 @@ impl GaParams {
             convergence_generations: 30,
             convergence_threshold: 0.002,
             time_limit_ms: Some(30_000),
+            sharing_radius: None,
         }
     }
 
@@ -94,9 +100,20 @@ impl GaParams {
             convergence_generations: 50,
             convergence_threshold: 0.001,
             time_limit_ms: Some(60_000),
+            sharing_radius: None,
         }
     }
 
+    /// Enable fitness sharing
+    ///
+    /// Parents are selected on fitness multiplied by their niche count,
+    /// the number of individuals within `radius` OSV distance weighted
+    /// by closeness, so crowded sequences lose tournaments.
+    pub fn with_fitness_sharing(mut self, radius: f64) -> Self {
+        self.sharing_radius = Some(radius);
+        self
+    }
+
     /// Auto-select based on operation count
     pub fn auto_select(operation_count: usize) -> Self {
         if operation_count < 50 {
@@ -191,10 +208,19 @@ impl Population {
         }
 
         // Generate rest through selection, crossover, mutation
+        let shared = self.params.sharing_radius.map(|r| self.shared_fitness(r));
         while new_population.len() < self.params.population_size {
             // Selection
-            let parent1 = tournament_selection(&self.individuals, self.params.tournament_size, rng);
-            let parent2 = tournament_selection(&self.individuals, self.params.tournament_size, rng);
+            let (parent1, parent2) = match &shared {
+                Some(scores) => (
+                    &self.individuals[shared_tournament(scores, self.params.tournament_size, rng)],
+                    &self.individuals[shared_tournament(scores, self.params.tournament_size, rng)],
+                ),
+                None => (
+                    tournament_selection(&self.individuals, self.params.tournament_size, rng),
+                    tournament_selection(&self.individuals, self.params.tournament_size, rng),
+                ),
+            };
 
             // Crossover
             let (mut child1, mut child2) =
@@ -215,6 +241,39 @@ impl Population {
 
         self.individuals = new_population;
         self.generation += 1;
+        telemetry::ga_generation(self.best.as_ref().map(|b| b.fitness));
+    }
+
+    /// Fitness scaled by niche count (lower is better)
+    fn shared_fitness(&self, radius: f64) -> Vec<f64> {
+        self.individuals
+            .iter()
+            .map(|a| {
+                let niche: f64 = self
+                    .individuals
+                    .iter()
+                    .map(|b| a.osv_distance(b))
+                    .filter(|&d| d < radius)
+                    .map(|d| 1.0 - d / radius)
+                    .sum();
+                a.fitness * niche.max(1.0)
+            })
+            .collect()
+    }
+
+    /// Mean pairwise OSV distance (0.0 = all identical)
+    pub fn diversity(&self) -> f64 {
+        let n = self.individuals.len();
+        if n < 2 {
+            return 0.0;
+        }
+        let mut total = 0.0;
+        for (i, a) in self.individuals.iter().enumerate() {
+            for b in &self.individuals[i + 1..] {
+                total += a.osv_distance(b);
+            }
+        }
+        total / (n * (n - 1) / 2) as f64
     }
 
     /// Check if converged
@@ -262,6 +321,7 @@ impl Population {
             worst_fitness: fitnesses.iter().cloned().fold(f64::NEG_INFINITY, f64::max),
             mean_fitness: mean,
             std_dev,
+            diversity: self.diversity(),
         }
     }
 
@@ -279,6 +339,20 @@ pub struct PopulationStats {
     pub worst_fitness: f64,
     pub mean_fitness: f64,
     pub std_dev: f64,
+    /// Mean pairwise OSV distance (0.0 to 1.0)
+    pub diversity: f64,
+}
+
+/// Tournament on precomputed scores (lower is better)
+fn shared_tournament(scores: &[f64], tournament_size: usize, rng: &mut impl Rng) -> usize {
+    let mut best = rng.gen_range(0..scores.len());
+    for _ in 1..tournament_size {
+        let idx = rng.gen_range(0..scores.len());
+        if scores[idx] < scores[best] {
+            best = idx;
+        }
+    }
+    best
 }
 
 /// Build process times map
@@ -487,4 +561,62 @@ mod tests {
             .iter()
             .any(|c| c.osv == best_before.osv));
     }
+
+    #[test]
+    fn test_fitness_sharing_diversity() {
+        let activities: Vec<ActivityInfo> = (0..6)
+            .flat_map(|t| {
+                (1..=3).map(move |seq| ActivityInfo {
+                    task_id: format!("T{}", t),
+                    activity_id: format!("T{}-A{}", t, seq),
+                    sequence: seq,
+                    candidates: vec!["R1".to_string(), "R2".to_string()],
+                    process_time_ms: 1000 * (1 + (t + seq as usize) as i64 % 3),
+                })
+            })
+            .collect();
+        let resources = create_test_resources();
+
+        let run = |params: GaParams| {
+            let mut rng = StdRng::seed_from_u64(11);
+            let mut population = Population::new(
+                &activities,
+                &resources,
+                params,
+                GeneticOperators::default(),
+                &mut rng,
+            );
+            for _ in 0..40 {
+                for individual in &mut population.individuals {
+                    individual.fitness = individual
+                        .decode(&activities, 0, |a| a.process_time_ms)
+                        .makespan_ms as f64;
+                }
+                population.evolve(&activities, &mut rng);
+            }
+            population.statistics().diversity
+        };
+        let params = GaParams {
+            population_size: 20,
+            ..Default::default()
+        };
+
+        let elitist = run(params.clone());
+        let shared = run(params.with_fitness_sharing(0.5));
+        assert!(shared > elitist, "{} <= {}", shared, elitist);
+        assert!(shared <= 1.0);
+
+        // Identical individuals have no diversity
+        let mut rng = StdRng::seed_from_u64(1);
+        let mut population = Population::new(
+            &activities,
+            &resources,
+            GaParams::default(),
+            GeneticOperators::default(),
+            &mut rng,
+        );
+        let first = population.individuals[0].clone();
+        population.individuals = vec![first.clone(), first];
+        assert_eq!(population.diversity(), 0.0);
+    }
 }
@@ -0,0 +1,569 @@
+//! GA Scheduler - End-to-end genetic algorithm scheduling
+//!
+//! Runs the population loop (evaluate, evolve, stop on convergence,
+//! generation or time limit) and decodes the best chromosome.
+
+use crate::ga::chromosome::{ActivityInfo, Chromosome};
+use crate::ga::operators::GeneticOperators;
+use crate::ga::population::{GaParams, Population};
+use crate::models::{Resource, Schedule, Task, TransitionMatrixCollection};
+use crate::parallel::{stream_seed, Parallelism};
+use crate::scheduler::{
+    relative_gap, total_transition_ms, CancellationToken, CostModel, Incumbent, MakespanBound,
+    TerminationReason,
+};
+use crate::telemetry::Stopwatch;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+/// Genetic algorithm scheduler minimizing makespan
+///
+/// Fitness is the makespan of the semi-active schedule decoded from each
+/// chromosome with nominal process times, plus optional weighted
+/// changeover and cost terms (see `with_changeover_penalty` and
+/// `with_cost_objective`). The random generator is
+/// seeded, so runs are reproducible, also with parallel evaluation and
+/// multi-start (unless a time limit cuts a run short).
+#[derive(Debug, Clone)]
+pub struct GaScheduler {
+    /// GA parameters
+    pub params: GaParams,
+    /// Genetic operators
+    pub operators: GeneticOperators,
+    /// Random seed
+    pub seed: u64,
+    /// Pool fitness evaluation and restarts run on
+    pub parallelism: Parallelism,
+    /// Setup matrices for the changeover term
+    pub transitions: TransitionMatrixCollection,
+    /// Weight of total transition time in the fitness (0 = off)
+    pub changeover_weight: f64,
+    /// Cost function for the cost term
+    pub cost_model: Option<CostModel>,
+    /// Makespan milliseconds one unit of cost is worth in the fitness
+    pub cost_weight: f64,
+}
+
+impl Default for GaScheduler {
+    fn default() -> Self {
+        Self::new(GaParams::fast())
+    }
+}
+
+impl GaScheduler {
+    /// Create GA scheduler
+    pub fn new(params: GaParams) -> Self {
+        Self {
+            params,
+            operators: GeneticOperators::default(),
+            seed: 42,
+            parallelism: Parallelism::sequential(),
+            transitions: TransitionMatrixCollection::default(),
+            changeover_weight: 0.0,
+            cost_model: None,
+            cost_weight: 0.0,
+        }
+    }
+
+    /// Set genetic operators
+    pub fn with_operators(mut self, operators: GeneticOperators) -> Self {
+        self.operators = operators;
+        self
+    }
+
+    /// Set random seed
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Evaluate fitness and run restarts in parallel
+    ///
+    /// Decoding is deterministic and all random draws stay on the
+    /// calling thread, so results match the sequential run.
+    pub fn with_parallel(self, parallel: bool) -> Self {
+        self.with_parallelism(Parallelism::from_flag(parallel))
+    }
+
+    /// Set the pool fitness evaluation and restarts run on
+    pub fn with_parallelism(mut self, parallelism: Parallelism) -> Self {
+        self.parallelism = parallelism;
+        self
+    }
+
+    /// Penalize changeovers
+    ///
+    /// Adds `weight` times the total transition time between consecutive
+    /// task categories on each resource (`total_transition_ms`) to the
+    /// makespan, steering the search towards campaigns.
+    pub fn with_changeover_penalty(
+        mut self,
+        transitions: TransitionMatrixCollection,
+        weight: f64,
+    ) -> Self {
+        self.transitions = transitions;
+        self.changeover_weight = weight;
+        self
+    }
+
+    /// Optimize cost as well as makespan
+    ///
+    /// Adds `weight` times the schedule's `CostModel::total_cost` to the
+    /// fitness, so `weight` is the makespan (ms) one unit of cost is worth.
+    /// A large weight prefers cheap resources even when faster ones would
+    /// finish earlier.
+    pub fn with_cost_objective(mut self, cost_model: CostModel, weight: f64) -> Self {
+        self.cost_model = Some(cost_model);
+        self.cost_weight = weight;
+        self
+    }
+
+    /// Schedule tasks on resources
+    pub fn schedule(&self, tasks: &[Task], resources: &[Resource], start_time_ms: i64) -> Schedule {
+        self.schedule_cancellable(tasks, resources, start_time_ms, &CancellationToken::new())
+    }
+
+    /// Schedule tasks on resources, stopping early on cancellation
+    ///
+    /// The token is checked once per generation; a cancelled run returns
+    /// the best schedule found so far.
+    pub fn schedule_cancellable(
+        &self,
+        tasks: &[Task],
+        resources: &[Resource],
+        start_time_ms: i64,
+        cancel: &CancellationToken,
+    ) -> Schedule {
+        self.schedule_streaming(tasks, resources, start_time_ms, cancel, |_| {})
+    }
+
+    /// Schedule tasks on resources, reporting each improved schedule
+    ///
+    /// `on_incumbent` is called with the initial best schedule and then
+    /// whenever a generation improves on it (objective = makespan ms, plus
+    /// any changeover and cost terms).
+    pub fn schedule_streaming(
+        &self,
+        tasks: &[Task],
+        resources: &[Resource],
+        start_time_ms: i64,
+        cancel: &CancellationToken,
+        on_incumbent: impl FnMut(Incumbent<Schedule>),
+    ) -> Schedule {
+        self.solve_streaming(tasks, resources, start_time_ms, cancel, on_incumbent)
+            .schedule
+    }
+
+    /// Schedule tasks on resources with bound, gap and termination reason
+    pub fn solve(&self, tasks: &[Task], resources: &[Resource], start_time_ms: i64) -> GaResult {
+        self.solve_streaming(
+            tasks,
+            resources,
+            start_time_ms,
+            &CancellationToken::new(),
+            |_| {},
+        )
+    }
+
+    /// Best of `starts` independent runs
+    ///
+    /// Run `k` is seeded from the scheduler seed and `k`; with equal
+    /// makespans the lowest run wins, so the result does not depend on
+    /// which run finishes first.
+    pub fn solve_multi_start(
+        &self,
+        tasks: &[Task],
+        resources: &[Resource],
+        start_time_ms: i64,
+        starts: usize,
+    ) -> GaResult {
+        let runs = self.parallelism.map_indexed(starts.max(1), |k| {
+            self.clone()
+                .with_seed(stream_seed(self.seed, k as u64))
+                .solve(tasks, resources, start_time_ms)
+        });
+        runs.into_iter()
+            .reduce(|best, run| {
+                if run.incumbent < best.incumbent {
+                    run
+                } else {
+                    best
+                }
+            })
+            .expect("at least one start")
+    }
+
+    /// Like `schedule_streaming`, returning the full search result
+    ///
+    /// The run also stops once the incumbent reaches the makespan lower
+    /// bound of the GA's relaxed problem (nominal process times, task
+    /// chains in sequence order).
+    pub fn solve_streaming(
+        &self,
+        tasks: &[Task],
+        resources: &[Resource],
+        start_time_ms: i64,
+        cancel: &CancellationToken,
+        mut on_incumbent: impl FnMut(Incumbent<Schedule>),
+    ) -> GaResult {
+        let activities = activity_infos(tasks);
+        if activities.is_empty() {
+            return GaResult {
+                schedule: Schedule::new(),
+                incumbent: 0.0,
+                best_bound: 0.0,
+                gap: Some(0.0),
+                generations: 0,
+                termination: TerminationReason::Completed,
+            };
+        }
+        let best_bound = nominal_bound(tasks, resources) as f64;
+        let mut rng = StdRng::seed_from_u64(self.seed);
+        let mut population = Population::new(
+            &activities,
+            resources,
+            self.params.clone(),
+            self.operators.clone(),
+            &mut rng,
+        );
+        let evaluate = |individual: &mut Chromosome| {
+            let schedule = individual.decode(&activities, start_time_ms, |a| a.process_time_ms);
+            let mut fitness = (schedule.makespan_ms - start_time_ms) as f64;
+            if self.changeover_weight > 0.0 {
+                fitness += self.changeover_weight
+                    * total_transition_ms(&schedule, tasks, &self.transitions) as f64;
+            }
+            if let Some(cost_model) = self.cost_model.as_ref().filter(|_| self.cost_weight > 0.0) {
+                fitness += self.cost_weight * cost_model.total_cost(&schedule, tasks, resources);
+            }
+            individual.fitness = fitness;
+        };
+        let fitness = |population: &mut Population| {
+            self.parallelism
+                .for_each_mut(&mut population.individuals, evaluate);
+        };
+
+        let clock = Stopwatch::start();
+        let mut incumbent = f64::INFINITY;
+        let mut report = |population: &Population| {
+            let best = population
+                .individuals
+                .iter()
+                .min_by(|a, b| a.fitness.total_cmp(&b.fitness));
+            if let Some(best) = best.filter(|c| c.fitness < incumbent) {
+                incumbent = best.fitness;
+                on_incumbent(Incumbent {
+                    solution: best.decode(&activities, start_time_ms, |a| a.process_time_ms),
+                    objective: best.fitness,
+                    iteration: population.generation,
+                    elapsed_ms: clock.elapsed_ms(),
+                });
+            }
+            incumbent
+        };
+        fitness(&mut population);
+        let mut best_fitness = report(&population);
+        let termination = loop {
+            if best_fitness <= best_bound {
+                break TerminationReason::ProvenOptimal;
+            } else if cancel.is_cancelled() {
+                break TerminationReason::Cancelled;
+            } else if population.generation >= self.params.max_generations {
+                break TerminationReason::IterationLimit;
+            } else if population.is_converged() {
+                break TerminationReason::Converged;
+            } else if self
+                .params
+                .time_limit_ms
+                .is_some_and(|limit| clock.elapsed_ms() >= limit)
+            {
+                break TerminationReason::TimeLimit;
+            }
+            population.evolve(&activities, &mut rng);
+            fitness(&mut population);
+            best_fitness = report(&population);
+        };
+        // Account for the last generation's offspring
+        let best = population
+            .individuals
+            .iter()
+            .chain(population.best.as_ref())
+            .min_by(|a, b| a.fitness.total_cmp(&b.fitness));
+
+        let (schedule, incumbent) = best.map_or_else(
+            || (Schedule::new(), f64::INFINITY),
+            |c| {
+                (
+                    c.decode(&activities, start_time_ms, |a| a.process_time_ms),
+                    c.fitness,
+                )
+            },
+        );
+        GaResult {
+            schedule,
+            incumbent,
+            best_bound,
+            gap: relative_gap(incumbent, best_bound),
+            generations: population.generation,
+            termination,
+        }
+    }
+}
+
+/// Outcome of a GA run
+#[derive(Debug, Clone)]
+pub struct GaResult {
+    /// Best schedule found
+    pub schedule: Schedule,
+    /// Fitness of the best schedule (makespan from the start in ms, plus
+    /// any changeover and cost terms)
+    pub incumbent: f64,
+    /// Makespan lower bound of the relaxed problem (ms)
+    pub best_bound: f64,
+    /// Relative gap between incumbent and bound
+    pub gap: Option<f64>,
+    /// Generations evolved
+    pub generations: usize,
+    /// Why the run stopped
+    pub termination: TerminationReason,
+}
+
+/// Makespan lower bound under the GA's decoding
+///
+/// The decoder ignores releases, explicit predecessors, efficiencies and
+/// capacities, so the bound is taken on the same simplification.
+fn nominal_bound(tasks: &[Task], resources: &[Resource]) -> i64 {
+    let tasks: Vec<Task> = tasks
+        .iter()
+        .map(|t| {
+            let mut task = Task::new(&t.id);
+            task.activities = t
+                .activities
+                .iter()
+                .filter(|a| !a.candidate_resources().is_empty())
+                .map(|a| {
+                    let mut a = a.clone();
+                    a.predecessors.clear();
+                    a
+                })
+                .collect();
+            task
+        })
+        .collect();
+    let resources: Vec<Resource> = resources
+        .iter()
+        .map(|r| {
+            let mut r = r.clone();
+            r.efficiency = 1.0;
+            r.capacity = 1;
+            r
+        })
+        .collect();
+    MakespanBound::calculate(&tasks, &resources, 0).lower_bound_ms()
+}
+
+/// Chromosome view of all activities of the tasks
+///
+/// Activities without candidate resources are left out.
+pub fn activity_infos(tasks: &[Task]) -> Vec<ActivityInfo> {
+    tasks
+        .iter()
+        .flat_map(|t| t.activities.iter())
+        .filter_map(|a| {
+            let candidates = a.candidate_resources();
+            (!candidates.is_empty()).then(|| ActivityInfo {
+                task_id: a.task_id.clone(),
+                activity_id: a.id.clone(),
+                sequence: a.sequence,
+                candidates,
+                process_time_ms: a.duration.process_ms,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Activity, ActivityDuration};
+
+    fn job(id: &str, ms: &[i64]) -> Task {
+        let mut task = Task::new(id);
+        for (i, &ms) in ms.iter().enumerate() {
+            task = task.with_activity(
+                Activity::new(&format!("{}-A{}", id, i + 1), id, i as i32 + 1)
+                    .with_duration(ActivityDuration::fixed(ms))
+                    .with_resources("machine", vec!["M1".into(), "M2".into()]),
+            );
+        }
+        task
+    }
+
+    #[test]
+    fn test_ga_schedule() {
+        let tasks = vec![
+            job("J1", &[3000, 1000]),
+            job("J2", &[2000, 2000]),
+            job("J3", &[1000, 3000]),
+        ];
+        let resources = vec![Resource::primary("M1"), Resource::primary("M2")];
+        let params = GaParams {
+            population_size: 20,
+            max_generations: 30,
+            ..GaParams::fast()
+        };
+
+        let schedule = GaScheduler::new(params.clone()).schedule(&tasks, &resources, 0);
+
+        assert_eq!(schedule.assignment_count(), 6);
+        // 12 s of work on two machines
+        assert!(schedule.makespan_ms >= 6000);
+        assert!(schedule.makespan_ms <= 8000);
+        for task in &tasks {
+            let mut end = 0;
+            for activity in &task.activities {
+                let a = schedule.assignment_for_activity(&activity.id).unwrap();
+                assert!(a.start_ms >= end);
+                end = a.end_ms;
+            }
+        }
+
+        // Same seed, same schedule
+        let again = GaScheduler::new(params).schedule(&tasks, &resources, 0);
+        assert_eq!(again.makespan_ms, schedule.makespan_ms);
+    }
+
+    #[test]
+    fn test_parallel_determinism() {
+        let tasks: Vec<Task> = (0..8)
+            .map(|i| job(&format!("J{}", i), &[1000 + 300 * i, 2000 - 200 * i, 700]))
+            .collect();
+        let resources = vec![Resource::primary("M1"), Resource::primary("M2")];
+        let ga = GaScheduler::new(GaParams {
+            population_size: 16,
+            max_generations: 25,
+            time_limit_ms: None,
+            ..GaParams::fast()
+        });
+
+        let sequential = ga.solve(&tasks, &resources, 0);
+        let parallel = ga.clone().with_parallel(true).solve(&tasks, &resources, 0);
+        assert_eq!(parallel.incumbent, sequential.incumbent);
+        assert_eq!(parallel.generations, sequential.generations);
+        crate::assert_schedule_eq!(parallel.schedule, sequential.schedule);
+
+        let sequential = ga.solve_multi_start(&tasks, &resources, 0, 4);
+        let parallel = ga
+            .with_parallelism(Parallelism::threads(3))
+            .solve_multi_start(&tasks, &resources, 0, 4);
+        assert_eq!(parallel.incumbent, sequential.incumbent);
+        crate::assert_schedule_eq!(parallel.schedule, sequential.schedule);
+    }
+
+    #[test]
+    fn test_ga_result() {
+        let tasks = vec![
+            job("J1", &[3000, 1000]),
+            job("J2", &[2000, 2000]),
+            job("J3", &[1000, 3000]),
+        ];
+        let resources = vec![Resource::primary("M1"), Resource::primary("M2")];
+        let params = GaParams {
+            population_size: 20,
+            max_generations: 30,
+            ..GaParams::fast()
+        };
+
+        let result = GaScheduler::new(params).solve(&tasks, &resources, 0);
+
+        // 12 s of work on two machines
+        assert_eq!(result.best_bound, 6000.0);
+        assert_eq!(result.incumbent, result.schedule.makespan_ms as f64);
+        assert_eq!(result.gap, relative_gap(result.incumbent, 6000.0));
+        // A perfectly balanced schedule ends the run early
+        assert_eq!(result.termination, TerminationReason::ProvenOptimal);
+        assert_eq!(result.gap, Some(0.0));
+        assert!(result.generations < 30);
+        assert!(!result.termination.can_improve());
+    }
+
+    #[test]
+    fn test_changeover_penalty() {
+        use crate::models::TransitionMatrix;
+
+        // Alternating colors on one machine
+        let tasks: Vec<Task> = (0..4)
+            .map(|i| {
+                Task::new(&format!("J{}", i))
+                    .with_category(if i % 2 == 0 { "red" } else { "blue" })
+                    .with_activity(
+                        Activity::new(&format!("J{}-A1", i), &format!("J{}", i), 1)
+                            .with_duration(ActivityDuration::fixed(1000))
+                            .with_resources("machine", vec!["M1".into()]),
+                    )
+            })
+            .collect();
+        let resources = vec![Resource::primary("M1")];
+        let mut matrix = TransitionMatrix::new("colors", "M1").with_default(500);
+        matrix.set_transition("red", "red", 0);
+        matrix.set_transition("blue", "blue", 0);
+        let mut transitions = TransitionMatrixCollection::new();
+        transitions.add(matrix);
+        let params = GaParams {
+            population_size: 20,
+            max_generations: 30,
+            ..GaParams::fast()
+        };
+
+        let result = GaScheduler::new(params)
+            .with_changeover_penalty(transitions.clone(), 1.0)
+            .solve(&tasks, &resources, 0);
+
+        // One color switch is the minimum
+        assert_eq!(
+            total_transition_ms(&result.schedule, &tasks, &transitions),
+            500
+        );
+        assert_eq!(result.incumbent, 4000.0 + 500.0);
+    }
+
+    #[test]
+    fn test_cost_objective() {
+        const HOUR: i64 = 3_600_000;
+
+        // Splitting the jobs finishes in one hour; the budget machine
+        // alone takes two but costs far less
+        let tasks: Vec<Task> = (0..2)
+            .map(|i| {
+                Task::new(&format!("J{}", i)).with_activity(
+                    Activity::new(&format!("J{}-A1", i), &format!("J{}", i), 1)
+                        .with_duration(ActivityDuration::fixed(HOUR))
+                        .with_resources("machine", vec!["Premium".into(), "Budget".into()]),
+                )
+            })
+            .collect();
+        let resources = vec![
+            Resource::primary("Premium").with_cost(100.0),
+            Resource::primary("Budget").with_cost(10.0),
+        ];
+        let params = GaParams {
+            population_size: 20,
+            max_generations: 30,
+            ..GaParams::fast()
+        };
+
+        let fastest = GaScheduler::new(params.clone()).solve(&tasks, &resources, 0);
+        assert_eq!(fastest.schedule.makespan_ms, HOUR);
+
+        let cheapest = GaScheduler::new(params)
+            .with_cost_objective(CostModel::new(), 100_000.0)
+            .solve(&tasks, &resources, 0);
+        assert_eq!(cheapest.schedule.makespan_ms, 2 * HOUR);
+        assert!(cheapest
+            .schedule
+            .assignments
+            .iter()
+            .all(|a| a.resource_id == "Budget"));
+        assert_eq!(cheapest.incumbent, (2 * HOUR) as f64 + 100_000.0 * 20.0);
+    }
+}
@@ -0,0 +1,277 @@
+//! Stochastic - Sample average approximation for the GA
+//!
+//! Evaluates chromosomes against a fixed set of sampled duration
+//! scenarios and uses the average objective as fitness, so evolution
+//! optimizes expected rather than nominal performance.
+
+use crate::ga::chromosome::{ActivityInfo, Chromosome};
+use crate::ga::population::Population;
+use crate::models::{Schedule, Task};
+use crate::parallel::{stream_rng, Parallelism};
+use std::collections::HashMap;
+
+/// Objective averaged over scenarios
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StochasticObjective {
+    /// Expected makespan
+    #[default]
+    Makespan,
+    /// Expected total tardiness
+    Tardiness,
+    /// Expected total earliness (completion before the deadline)
+    Earliness,
+    /// Expected total earliness plus tardiness (just-in-time)
+    EarlinessTardiness,
+}
+
+/// Sample average approximation fitness evaluator
+///
+/// Scenarios are drawn once from each activity's `duration_distribution`
+/// (activities without one keep their nominal process time) and shared
+/// by all chromosomes, so fitness differences reflect the schedules
+/// rather than sampling noise.
+#[derive(Debug, Clone)]
+pub struct SaaEvaluator {
+    /// Sampled process time per activity, one map per scenario
+    scenarios: Vec<HashMap<String, i64>>,
+    /// Task deadlines (epoch ms)
+    deadlines: HashMap<String, i64>,
+    /// Objective to average
+    objective: StochasticObjective,
+    /// Schedule start time
+    start_time_ms: i64,
+    /// Pool populations are evaluated on
+    parallelism: Parallelism,
+}
+
+impl SaaEvaluator {
+    /// Sample `samples` duration scenarios for the tasks
+    pub fn new(tasks: &[Task], samples: usize, seed: u64) -> Self {
+        let scenarios = (0..samples.max(1))
+            .map(|k| {
+                let mut rng = stream_rng(seed, k as u64);
+                tasks
+                    .iter()
+                    .flat_map(|t| t.activities.iter())
+                    .map(|a| {
+                        let duration = match &a.duration_distribution {
+                            Some(distribution) => distribution.sample(&mut rng),
+                            None => a.duration.process_ms,
+                        };
+                        (a.id.clone(), duration)
+                    })
+                    .collect()
+            })
+            .collect();
+        let deadlines = tasks
+            .iter()
+            .filter_map(|t| t.due_date().map(|d| (t.id.clone(), d.timestamp_millis())))
+            .collect();
+
+        Self {
+            scenarios,
+            deadlines,
+            objective: StochasticObjective::default(),
+            start_time_ms: 0,
+            parallelism: Parallelism::sequential(),
+        }
+    }
+
+    /// Set objective
+    pub fn with_objective(mut self, objective: StochasticObjective) -> Self {
+        self.objective = objective;
+        self
+    }
+
+    /// Set schedule start time
+    pub fn with_start_time(mut self, start_time_ms: i64) -> Self {
+        self.start_time_ms = start_time_ms;
+        self
+    }
+
+    /// Evaluate populations in parallel (fitness does not depend on threads)
+    pub fn with_parallel(self, parallel: bool) -> Self {
+        self.with_parallelism(Parallelism::from_flag(parallel))
+    }
+
+    /// Set the pool populations are evaluated on
+    pub fn with_parallelism(mut self, parallelism: Parallelism) -> Self {
+        self.parallelism = parallelism;
+        self
+    }
+
+    /// Number of scenarios
+    pub fn scenario_count(&self) -> usize {
+        self.scenarios.len()
+    }
+
+    /// Average objective of a chromosome over all scenarios
+    pub fn evaluate(&self, chromosome: &Chromosome, activities: &[ActivityInfo]) -> f64 {
+        let total: f64 = self
+            .scenarios
+            .iter()
+            .map(|scenario| {
+                let schedule = chromosome.decode(activities, self.start_time_ms, |a| {
+                    scenario
+                        .get(&a.activity_id)
+                        .copied()
+                        .unwrap_or(a.process_time_ms)
+                });
+                self.objective_value(&schedule)
+            })
+            .sum();
+        total / self.scenarios.len() as f64
+    }
+
+    /// Objective of a chromosome with nominal process times
+    pub fn evaluate_nominal(&self, chromosome: &Chromosome, activities: &[ActivityInfo]) -> f64 {
+        let schedule = chromosome.decode(activities, self.start_time_ms, |a| a.process_time_ms);
+        self.objective_value(&schedule)
+    }
+
+    /// Set the fitness of every individual to its average objective
+    pub fn evaluate_population(&self, population: &mut Population, activities: &[ActivityInfo]) {
+        self.parallelism
+            .for_each_mut(&mut population.individuals, |individual| {
+                individual.fitness = self.evaluate(individual, activities)
+            });
+    }
+
+    fn objective_value(&self, schedule: &Schedule) -> f64 {
+        match self.objective {
+            StochasticObjective::Makespan => (schedule.makespan_ms - self.start_time_ms) as f64,
+            StochasticObjective::Tardiness => self.lateness_sum(schedule, |l| l.max(0)),
+            StochasticObjective::Earliness => self.lateness_sum(schedule, |l| (-l).max(0)),
+            StochasticObjective::EarlinessTardiness => self.lateness_sum(schedule, i64::abs),
+        }
+    }
+
+    /// Sum of `term(completion - deadline)` over tasks with a deadline
+    fn lateness_sum(&self, schedule: &Schedule, term: impl Fn(i64) -> i64) -> f64 {
+        self.deadlines
+            .iter()
+            .filter_map(|(task, &deadline)| {
+                schedule
+                    .task_completion_time(task)
+                    .map(|end| term(end - deadline))
+            })
+            .sum::<i64>() as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ga::operators::GeneticOperators;
+    use crate::ga::population::GaParams;
+    use crate::models::{Activity, ActivityDuration, DurationDistribution, Resource};
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn at(ms: i64) -> chrono::DateTime<chrono::Utc> {
+        chrono::DateTime::from_timestamp_millis(ms).unwrap()
+    }
+
+    /// A: nominally 1000ms but usually longer; B: exactly 1000ms, due early
+    fn tasks() -> Vec<Task> {
+        vec![
+            Task::new("A").with_deadline(at(4000)).with_activity(
+                Activity::new("A1", "A", 1)
+                    .with_duration(ActivityDuration::fixed(1000))
+                    .with_duration_distribution(DurationDistribution::Uniform {
+                        min_ms: 500,
+                        max_ms: 3500,
+                    }),
+            ),
+            Task::new("B").with_deadline(at(2000)).with_activity(
+                Activity::new("B1", "B", 1).with_duration(ActivityDuration::fixed(1000)),
+            ),
+        ]
+    }
+
+    fn infos() -> Vec<ActivityInfo> {
+        tasks()
+            .iter()
+            .flat_map(|t| t.activities.iter())
+            .map(|a| ActivityInfo {
+                task_id: a.task_id.clone(),
+                activity_id: a.id.clone(),
+                sequence: a.sequence,
+                candidates: vec!["M1".to_string()],
+                process_time_ms: a.duration.process_ms,
+            })
+            .collect()
+    }
+
+    fn ordered(first: &str, second: &str) -> Chromosome {
+        Chromosome::encode(&infos(), &[first, second], &["M1", "M1"]).unwrap()
+    }
+
+    #[test]
+    fn test_expected_vs_nominal() {
+        let evaluator =
+            SaaEvaluator::new(&tasks(), 500, 42).with_objective(StochasticObjective::Tardiness);
+        let activities = infos();
+
+        // Nominally both orders are on time
+        assert_eq!(
+            evaluator.evaluate_nominal(&ordered("A", "B"), &activities),
+            0.0
+        );
+        assert_eq!(
+            evaluator.evaluate_nominal(&ordered("B", "A"), &activities),
+            0.0
+        );
+
+        // In expectation, running uncertain A first makes B late
+        let a_first = evaluator.evaluate(&ordered("A", "B"), &activities);
+        let b_first = evaluator.evaluate(&ordered("B", "A"), &activities);
+        assert!(a_first > 500.0);
+        assert!(b_first < 100.0);
+
+        let makespan = SaaEvaluator::new(&tasks(), 500, 42);
+        let expected = makespan.evaluate(&ordered("A", "B"), &activities);
+        assert!((expected - 3000.0).abs() < 100.0);
+
+        // Nominally A finishes 3000ms early and B right on time
+        let earliness =
+            SaaEvaluator::new(&tasks(), 10, 42).with_objective(StochasticObjective::Earliness);
+        assert_eq!(
+            earliness.evaluate_nominal(&ordered("A", "B"), &activities),
+            3000.0
+        );
+        // Expected earliness plus tardiness counts both directions
+        let jit = SaaEvaluator::new(&tasks(), 500, 42)
+            .with_objective(StochasticObjective::EarlinessTardiness);
+        assert!(jit.evaluate(&ordered("A", "B"), &activities) > a_first);
+    }
+
+    #[test]
+    fn test_ga_with_saa_fitness() {
+        let activities = infos();
+        let resources = vec![Resource::primary("M1")];
+        let evaluator =
+            SaaEvaluator::new(&tasks(), 100, 7).with_objective(StochasticObjective::Tardiness);
+        let mut rng = StdRng::seed_from_u64(3);
+        let params = GaParams {
+            population_size: 10,
+            ..GaParams::default()
+        };
+        let mut population = Population::new(
+            &activities,
+            &resources,
+            params,
+            GeneticOperators::default(),
+            &mut rng,
+        );
+
+        for _ in 0..5 {
+            evaluator.evaluate_population(&mut population, &activities);
+            population.evolve(&activities, &mut rng);
+        }
+
+        let best = population.get_best().unwrap();
+        assert_eq!(best.task_sequence(&activities)[0], "B");
+        assert_eq!(evaluator.scenario_count(), 100);
+    }
+}
@@ -0,0 +1,595 @@
+//! Golden - Regression tests against stored schedules
+//!
+//! Runs a named scheduler with a fixed seed on an instance file and
+//! compares the schedule and its KPIs with a stored golden file, so
+//! behavioral changes show up when the crate (or a model) is upgraded:
+//!
+//! ```no_run
+//! use u_ras::golden::{GoldenScheduler, GoldenTest, Tolerance};
+//!
+//! GoldenTest::new("tests/data/week12.json", "tests/golden/week12.ga.json")
+//!     .with_scheduler(GoldenScheduler::Ga)
+//!     .with_seed(7)
+//!     .with_tolerance(Tolerance::default().with_time_ms(1000))
+//!     .assert_matches();
+//! ```
+//!
+//! Instances are `ScheduleRequest` JSON. Golden files are written with
+//! `U_RAS_UPDATE_GOLDEN=1` (or `with_update(true)`) and compared on every
+//! other run.
+
+use crate::ga::{GaParams, GaScheduler};
+use crate::models::{Assignment, Schedule};
+use crate::scheduler::{HeftScheduler, ScheduleKpi, ScheduleRequest, SimpleScheduler};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Environment variable that switches golden tests to (re)writing files
+pub const UPDATE_ENV: &str = "U_RAS_UPDATE_GOLDEN";
+
+/// Golden test error
+#[derive(Debug)]
+pub enum GoldenError {
+    /// File could not be read or written
+    Io(PathBuf, std::io::Error),
+    /// File is not valid JSON of the expected shape
+    Json(PathBuf, serde_json::Error),
+    /// Scheduler name not recognized
+    UnknownScheduler(String),
+    /// Golden file does not exist yet
+    Missing(PathBuf),
+    /// Output differs from the golden file
+    Mismatch(PathBuf, Vec<String>),
+}
+
+impl std::fmt::Display for GoldenError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GoldenError::Io(path, e) => write!(f, "{}: {}", path.display(), e),
+            GoldenError::Json(path, e) => write!(f, "{}: invalid JSON: {}", path.display(), e),
+            GoldenError::UnknownScheduler(name) => write!(f, "unknown scheduler '{}'", name),
+            GoldenError::Missing(path) => write!(
+                f,
+                "golden file {} missing (run with {}=1 to create it)",
+                path.display(),
+                UPDATE_ENV
+            ),
+            GoldenError::Mismatch(path, lines) => write!(
+                f,
+                "output differs from {} (- golden, + actual):\n  {}",
+                path.display(),
+                lines.join("\n  ")
+            ),
+        }
+    }
+}
+
+impl std::error::Error for GoldenError {}
+
+/// Scheduler a golden test runs
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GoldenScheduler {
+    /// `SimpleScheduler` (priority greedy, with transition matrices)
+    #[default]
+    Simple,
+    /// `HeftScheduler` without communication delays
+    Heft,
+    /// `GaScheduler` with the fast preset and no time limit
+    Ga,
+}
+
+impl GoldenScheduler {
+    /// Name used in golden files ("simple", "heft", "ga")
+    pub fn name(&self) -> &'static str {
+        match self {
+            GoldenScheduler::Simple => "simple",
+            GoldenScheduler::Heft => "heft",
+            GoldenScheduler::Ga => "ga",
+        }
+    }
+
+    /// Schedule a request; the seed only matters for randomized schedulers
+    ///
+    /// The GA runs without a time limit so the result does not depend
+    /// on machine speed.
+    pub fn run(&self, request: &ScheduleRequest, seed: u64) -> Schedule {
+        match self {
+            GoldenScheduler::Simple => SimpleScheduler::new().schedule_request(request),
+            GoldenScheduler::Heft => HeftScheduler::new().schedule(
+                &request.eligible_tasks(),
+                &request.resources,
+                request.start_time_ms,
+            ),
+            GoldenScheduler::Ga => GaScheduler::new(GaParams {
+                time_limit_ms: None,
+                ..GaParams::fast()
+            })
+            .with_seed(seed)
+            .schedule(
+                &request.eligible_tasks(),
+                &request.resources,
+                request.start_time_ms,
+            ),
+        }
+    }
+}
+
+impl std::str::FromStr for GoldenScheduler {
+    type Err = GoldenError;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        match name.to_ascii_lowercase().as_str() {
+            "simple" | "greedy" => Ok(GoldenScheduler::Simple),
+            "heft" => Ok(GoldenScheduler::Heft),
+            "ga" | "genetic" => Ok(GoldenScheduler::Ga),
+            _ => Err(GoldenError::UnknownScheduler(name.to_string())),
+        }
+    }
+}
+
+/// Allowed deviation from a golden file
+///
+/// The default is exact: same assignments and violations, KPIs equal up
+/// to floating-point noise.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Tolerance {
+    /// Allowed shift of makespan, starts and ends (ms)
+    pub time_ms: i64,
+    /// Allowed absolute KPI difference
+    pub kpi_absolute: f64,
+    /// Allowed KPI difference relative to the larger value
+    pub kpi_relative: f64,
+    /// KPIs (top-level field names) left out of the comparison
+    pub ignored_kpis: Vec<String>,
+}
+
+impl Default for Tolerance {
+    fn default() -> Self {
+        Self {
+            time_ms: 0,
+            kpi_absolute: 1e-9,
+            kpi_relative: 0.0,
+            ignored_kpis: Vec::new(),
+        }
+    }
+}
+
+impl Tolerance {
+    /// Set allowed time shift
+    pub fn with_time_ms(mut self, time_ms: i64) -> Self {
+        self.time_ms = time_ms.max(0);
+        self
+    }
+
+    /// Set allowed absolute KPI difference
+    pub fn with_kpi_absolute(mut self, absolute: f64) -> Self {
+        self.kpi_absolute = absolute.max(0.0);
+        self
+    }
+
+    /// Set allowed relative KPI difference (0.01 = 1%)
+    pub fn with_kpi_relative(mut self, relative: f64) -> Self {
+        self.kpi_relative = relative.max(0.0);
+        self
+    }
+
+    /// Leave a KPI out of the comparison
+    pub fn ignore_kpi(mut self, name: &str) -> Self {
+        self.ignored_kpis.push(name.to_string());
+        self
+    }
+
+    fn numbers_match(&self, expected: f64, actual: f64) -> bool {
+        (expected - actual).abs()
+            <= self.kpi_absolute + self.kpi_relative * expected.abs().max(actual.abs())
+    }
+}
+
+/// Stored output of one scheduler run
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Golden {
+    /// Scheduler name
+    pub scheduler: String,
+    /// Random seed
+    pub seed: u64,
+    /// Schedule (normalized)
+    pub schedule: Schedule,
+    /// KPIs as JSON, so files stay readable and comparable field by field
+    pub kpi: Value,
+}
+
+impl Golden {
+    /// Capture a schedule and its KPIs
+    pub fn capture(
+        scheduler: GoldenScheduler,
+        seed: u64,
+        request: &ScheduleRequest,
+        schedule: &Schedule,
+    ) -> Self {
+        let kpi = ScheduleKpi::calculate(schedule, &request.tasks);
+        Self {
+            scheduler: scheduler.name().to_string(),
+            seed,
+            schedule: schedule.normalized(),
+            kpi: serde_json::to_value(kpi).expect("KPIs serialize"),
+        }
+    }
+
+    /// Read a golden file
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, GoldenError> {
+        read_json(path.as_ref())
+    }
+
+    /// Write a golden file (sorted keys, canonical assignment order)
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), GoldenError> {
+        let path = path.as_ref();
+        let mut value =
+            serde_json::to_value(self).map_err(|e| GoldenError::Json(path.to_path_buf(), e))?;
+        value["schedule"] = serde_json::from_str(&self.schedule.to_normalized_json())
+            .map_err(|e| GoldenError::Json(path.to_path_buf(), e))?;
+        let text = serde_json::to_string_pretty(&value)
+            .map_err(|e| GoldenError::Json(path.to_path_buf(), e))?;
+        if let Some(dir) = path.parent().filter(|d| !d.as_os_str().is_empty()) {
+            std::fs::create_dir_all(dir).map_err(|e| GoldenError::Io(dir.to_path_buf(), e))?;
+        }
+        std::fs::write(path, text + "\n").map_err(|e| GoldenError::Io(path.to_path_buf(), e))
+    }
+
+    /// Differences from `actual` beyond `tolerance`, one line each
+    pub fn compare(&self, actual: &Golden, tolerance: &Tolerance) -> Vec<String> {
+        let mut lines = Vec::new();
+        if self.scheduler != actual.scheduler || self.seed != actual.seed {
+            lines.push(format!(
+                "run {} seed {} != {} seed {}",
+                self.scheduler, self.seed, actual.scheduler, actual.seed
+            ));
+        }
+        compare_schedules(&self.schedule, &actual.schedule, tolerance, &mut lines);
+        if let (Value::Object(expected), Value::Object(actual)) = (&self.kpi, &actual.kpi) {
+            for (name, value) in expected {
+                if tolerance.ignored_kpis.contains(name) {
+                    continue;
+                }
+                compare_values(
+                    &format!("kpi {}", name),
+                    value,
+                    actual.get(name).unwrap_or(&Value::Null),
+                    tolerance,
+                    &mut lines,
+                );
+            }
+        } else if self.kpi != actual.kpi {
+            lines.push("kpi shape differs".to_string());
+        }
+        lines
+    }
+}
+
+/// Golden-file check of one instance and scheduler
+#[derive(Debug, Clone)]
+pub struct GoldenTest {
+    /// Instance file (`ScheduleRequest` JSON)
+    pub instance: PathBuf,
+    /// Golden file
+    pub golden: PathBuf,
+    /// Scheduler to run
+    pub scheduler: GoldenScheduler,
+    /// Random seed
+    pub seed: u64,
+    /// Allowed deviation
+    pub tolerance: Tolerance,
+    /// Write the golden file instead of comparing
+    pub update: bool,
+}
+
+/// Result of a passing golden test
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GoldenOutcome {
+    /// Output matched the golden file
+    Matched,
+    /// Golden file was (re)written
+    Updated,
+}
+
+impl GoldenTest {
+    /// Create test of the simple scheduler with seed 42
+    ///
+    /// Update mode is on if `U_RAS_UPDATE_GOLDEN` is set to anything but
+    /// "" or "0".
+    pub fn new(instance: impl Into<PathBuf>, golden: impl Into<PathBuf>) -> Self {
+        Self {
+            instance: instance.into(),
+            golden: golden.into(),
+            scheduler: GoldenScheduler::default(),
+            seed: 42,
+            tolerance: Tolerance::default(),
+            update: std::env::var(UPDATE_ENV).is_ok_and(|v| !v.is_empty() && v != "0"),
+        }
+    }
+
+    /// Set scheduler
+    pub fn with_scheduler(mut self, scheduler: GoldenScheduler) -> Self {
+        self.scheduler = scheduler;
+        self
+    }
+
+    /// Set random seed
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Set tolerance
+    pub fn with_tolerance(mut self, tolerance: Tolerance) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Set update mode
+    pub fn with_update(mut self, update: bool) -> Self {
+        self.update = update;
+        self
+    }
+
+    /// Run the scheduler and compare with (or write) the golden file
+    pub fn run(&self) -> Result<GoldenOutcome, GoldenError> {
+        let request = load_instance(&self.instance)?;
+        let schedule = self.scheduler.run(&request, self.seed);
+        let actual = Golden::capture(self.scheduler, self.seed, &request, &schedule);
+
+        if self.update {
+            actual.save(&self.golden)?;
+            return Ok(GoldenOutcome::Updated);
+        }
+        if !self.golden.exists() {
+            return Err(GoldenError::Missing(self.golden.clone()));
+        }
+        let lines = Golden::load(&self.golden)?.compare(&actual, &self.tolerance);
+        if lines.is_empty() {
+            Ok(GoldenOutcome::Matched)
+        } else {
+            Err(GoldenError::Mismatch(self.golden.clone(), lines))
+        }
+    }
+
+    /// Run and panic with the differences on failure
+    pub fn assert_matches(&self) {
+        if let Err(e) = self.run() {
+            panic!("{}", e);
+        }
+    }
+}
+
+/// Read an instance file (`ScheduleRequest` JSON)
+pub fn load_instance(path: impl AsRef<Path>) -> Result<ScheduleRequest, GoldenError> {
+    read_json(path.as_ref())
+}
+
+fn read_json<T: serde::de::DeserializeOwned>(path: &Path) -> Result<T, GoldenError> {
+    let text = std::fs::read_to_string(path).map_err(|e| GoldenError::Io(path.to_path_buf(), e))?;
+    serde_json::from_str(&text).map_err(|e| GoldenError::Json(path.to_path_buf(), e))
+}
+
+fn compare_schedules(
+    expected: &Schedule,
+    actual: &Schedule,
+    tolerance: &Tolerance,
+    lines: &mut Vec<String>,
+) {
+    if (expected.makespan_ms - actual.makespan_ms).abs() > tolerance.time_ms {
+        lines.push(format!(
+            "makespan {} ms != {} ms",
+            expected.makespan_ms, actual.makespan_ms
+        ));
+    }
+
+    // Pair the k-th segment of each activity (split activities have several)
+    let by_activity = |schedule: &'_ Schedule| {
+        let mut map: BTreeMap<String, Vec<Assignment>> = BTreeMap::new();
+        for a in schedule.canonical_assignments() {
+            map.entry(a.activity_id.clone())
+                .or_default()
+                .push(a.clone());
+        }
+        map
+    };
+    let (expected_map, mut actual_map) = (by_activity(expected), by_activity(actual));
+    for (activity, segments) in expected_map {
+        let others = actual_map.remove(&activity).unwrap_or_default();
+        for k in 0..segments.len().max(others.len()) {
+            match (segments.get(k), others.get(k)) {
+                (Some(e), Some(a)) => {
+                    if e.resource_id != a.resource_id
+                        || (e.start_ms - a.start_ms).abs() > tolerance.time_ms
+                        || (e.end_ms - a.end_ms).abs() > tolerance.time_ms
+                    {
+                        lines.push(format!("- {}", e));
+                        lines.push(format!("+ {}", a));
+                    }
+                }
+                (Some(e), None) => lines.push(format!("- {}", e)),
+                (None, Some(a)) => lines.push(format!("+ {}", a)),
+                (None, None) => {}
+            }
+        }
+    }
+    for a in actual_map.into_values().flatten() {
+        lines.push(format!("+ {}", a));
+    }
+
+    // Violation messages quote times, so only kind and entity must match
+    let kinds = |schedule: &Schedule| {
+        let mut kinds: Vec<String> = schedule
+            .violations
+            .iter()
+            .map(|v| format!("violation {}: {:?}", v.entity_id, v.violation_type))
+            .collect();
+        kinds.sort();
+        kinds
+    };
+    let (expected_kinds, mut actual_kinds) = (kinds(expected), kinds(actual));
+    for kind in expected_kinds {
+        match actual_kinds.iter().position(|k| *k == kind) {
+            Some(i) => {
+                actual_kinds.remove(i);
+            }
+            None => lines.push(format!("- {}", kind)),
+        }
+    }
+    lines.extend(actual_kinds.into_iter().map(|k| format!("+ {}", k)));
+}
+
+fn compare_values(
+    path: &str,
+    expected: &Value,
+    actual: &Value,
+    tolerance: &Tolerance,
+    lines: &mut Vec<String>,
+) {
+    match (expected, actual) {
+        (Value::Number(e), Value::Number(a)) => {
+            let (e, a) = (e.as_f64().unwrap_or(0.0), a.as_f64().unwrap_or(0.0));
+            if !tolerance.numbers_match(e, a) {
+                lines.push(format!("{}: {} != {}", path, e, a));
+            }
+        }
+        (Value::Object(e), Value::Object(a)) => {
+            for key in e.keys().chain(a.keys().filter(|k| !e.contains_key(*k))) {
+                compare_values(
+                    &format!("{}.{}", path, key),
+                    e.get(key).unwrap_or(&Value::Null),
+                    a.get(key).unwrap_or(&Value::Null),
+                    tolerance,
+                    lines,
+                );
+            }
+        }
+        (Value::Array(e), Value::Array(a)) if e.len() == a.len() => {
+            for (k, (e, a)) in e.iter().zip(a).enumerate() {
+                compare_values(&format!("{}[{}]", path, k), e, a, tolerance, lines);
+            }
+        }
+        _ if expected != actual => lines.push(format!("{}: {} != {}", path, expected, actual)),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Activity, ActivityDuration, Resource, Task};
+
+    fn request() -> ScheduleRequest {
+        let step = |id: &str, task: &str, seq: i32, ms: i64, resources: &[&str]| {
+            Activity::new(id, task, seq)
+                .with_duration(ActivityDuration::fixed(ms))
+                .with_resources("machine", resources.iter().map(|r| r.to_string()).collect())
+        };
+        ScheduleRequest::new(
+            vec![
+                Task::new("T1")
+                    .with_activity(step("A1", "T1", 1, 3000, &["M1", "M2"]))
+                    .with_activity(step("A2", "T1", 2, 2000, &["M2"])),
+                Task::new("T2")
+                    .with_deadline(chrono::DateTime::from_timestamp_millis(4000).unwrap())
+                    .with_activity(step("B1", "T2", 1, 2000, &["M1"]))
+                    .with_activity(step("B2", "T2", 2, 1000, &["M2"])),
+            ],
+            vec![Resource::primary("M1"), Resource::primary("M2")],
+        )
+    }
+
+    fn scratch(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("u-ras-golden-{}-{}", std::process::id(), name));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let instance = dir.join("instance.json");
+        std::fs::write(&instance, serde_json::to_string(&request()).unwrap()).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_golden_roundtrip() {
+        let dir = scratch("roundtrip");
+        for scheduler in [
+            GoldenScheduler::Simple,
+            GoldenScheduler::Heft,
+            GoldenScheduler::Ga,
+        ] {
+            let golden = dir.join(format!("golden/{}.json", scheduler.name()));
+            let test = GoldenTest::new(dir.join("instance.json"), &golden)
+                .with_scheduler(scheduler)
+                .with_seed(7)
+                .with_update(false);
+
+            assert!(matches!(test.run(), Err(GoldenError::Missing(_))));
+            assert_eq!(
+                test.clone().with_update(true).run().unwrap(),
+                GoldenOutcome::Updated
+            );
+            assert_eq!(test.run().unwrap(), GoldenOutcome::Matched);
+            test.assert_matches();
+
+            // Written files are stable byte for byte
+            let text = std::fs::read_to_string(&golden).unwrap();
+            test.clone().with_update(true).run().unwrap();
+            assert_eq!(std::fs::read_to_string(&golden).unwrap(), text);
+        }
+        assert_eq!(
+            "GA".parse::<GoldenScheduler>().unwrap(),
+            GoldenScheduler::Ga
+        );
+        assert!("tabu".parse::<GoldenScheduler>().is_err());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_mismatch_and_tolerance() {
+        let request = request();
+        let schedule = GoldenScheduler::Simple.run(&request, 0);
+        let golden = Golden::capture(GoldenScheduler::Simple, 0, &request, &schedule);
+
+        // Shift one assignment by 500 ms
+        let mut shifted = schedule.clone();
+        let a = shifted
+            .assignments
+            .iter_mut()
+            .max_by_key(|a| a.end_ms)
+            .unwrap();
+        a.start_ms += 500;
+        a.end_ms += 500;
+        shifted.makespan_ms += 500;
+        let actual = Golden::capture(GoldenScheduler::Simple, 0, &request, &shifted);
+
+        let lines = golden.compare(&actual, &Tolerance::default());
+        assert!(lines[0].starts_with("makespan"));
+        assert!(lines.iter().any(|l| l.starts_with("- ")));
+        assert!(lines.iter().any(|l| l.starts_with("+ ")));
+        assert!(lines.iter().any(|l| l.starts_with("kpi makespan_ms")));
+
+        let loose = Tolerance::default()
+            .with_time_ms(500)
+            .with_kpi_relative(0.2)
+            .with_kpi_absolute(0.5);
+        assert_eq!(golden.compare(&actual, &loose), Vec::<String>::new());
+        let lines = golden.compare(&actual, &Tolerance::default().with_time_ms(500));
+        assert!(!lines.is_empty());
+        assert!(lines.iter().all(|l| l.starts_with("kpi")));
+
+        // Mismatches name the golden file
+        let dir = scratch("mismatch");
+        let path = dir.join("golden.json");
+        actual.save(&path).unwrap();
+        let err = GoldenTest::new(dir.join("instance.json"), &path)
+            .with_update(false)
+            .run()
+            .unwrap_err();
+        assert!(matches!(err, GoldenError::Mismatch(_, _)));
+        assert!(err.to_string().contains("golden.json"));
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,400 @@
+//! Graph - Activity precedence graph
+//!
+//! One precedence graph shared by validation, deadline propagation and
+//! the schedulers: topological ordering, cycle reporting, transitive
+//! reduction and reachability queries.
+//!
+//! Edges come from `Activity::predecessors` (across tasks too); a task
+//! whose activities declare no predecessors is a chain in sequence order.
+
+use crate::models::{Activity, Task};
+use std::collections::{HashMap, VecDeque};
+
+/// Directed precedence graph over activity IDs
+///
+/// Nodes are addressed by index (insertion order); an edge `a -> b`
+/// means `a` must finish before `b` starts.
+#[derive(Debug, Clone, Default)]
+pub struct PrecedenceGraph {
+    /// Node IDs
+    nodes: Vec<String>,
+    /// Node index by ID
+    index: HashMap<String, usize>,
+    /// Predecessor indices
+    predecessors: Vec<Vec<usize>>,
+    /// Successor indices
+    successors: Vec<Vec<usize>>,
+}
+
+/// Result of a topological sort
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TopologicalOrder {
+    /// Acyclic part in topological order (ties in insertion order)
+    pub order: Vec<usize>,
+    /// Nodes on or behind a cycle
+    pub cyclic: Vec<usize>,
+}
+
+impl TopologicalOrder {
+    /// Whether the whole graph was ordered
+    pub fn is_complete(&self) -> bool {
+        self.cyclic.is_empty()
+    }
+}
+
+impl PrecedenceGraph {
+    /// Create empty graph
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Precedence graph of all activities of `tasks`
+    ///
+    /// Predecessors naming unknown activities are ignored.
+    pub fn from_tasks(tasks: &[Task]) -> Self {
+        let mut graph = Self::new();
+        for activity in tasks.iter().flat_map(|t| t.activities.iter()) {
+            graph.add_node(&activity.id);
+        }
+        for task in tasks {
+            if task.activities.iter().any(|a| !a.predecessors.is_empty()) {
+                for a in &task.activities {
+                    for p in &a.predecessors {
+                        if graph.contains(p) {
+                            graph.add_edge(p, &a.id);
+                        }
+                    }
+                }
+            } else {
+                let mut chain: Vec<&Activity> = task.activities.iter().collect();
+                chain.sort_by_key(|a| a.sequence);
+                for pair in chain.windows(2) {
+                    graph.add_edge(&pair[0].id, &pair[1].id);
+                }
+            }
+        }
+        graph
+    }
+
+    /// Add node (returns the index of an existing node with this ID)
+    pub fn add_node(&mut self, id: &str) -> usize {
+        if let Some(&i) = self.index.get(id) {
+            return i;
+        }
+        let i = self.nodes.len();
+        self.nodes.push(id.to_string());
+        self.index.insert(id.to_string(), i);
+        self.predecessors.push(Vec::new());
+        self.successors.push(Vec::new());
+        i
+    }
+
+    /// Add edge `before -> after` (missing nodes are added, duplicates ignored)
+    pub fn add_edge(&mut self, before: &str, after: &str) {
+        let b = self.add_node(before);
+        let a = self.add_node(after);
+        if !self.successors[b].contains(&a) {
+            self.successors[b].push(a);
+            self.predecessors[a].push(b);
+        }
+    }
+
+    /// Number of nodes
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Whether the graph has no nodes
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Number of edges
+    pub fn edge_count(&self) -> usize {
+        self.successors.iter().map(Vec::len).sum()
+    }
+
+    /// Whether a node with this ID exists
+    pub fn contains(&self, id: &str) -> bool {
+        self.index.contains_key(id)
+    }
+
+    /// Index of a node
+    pub fn index_of(&self, id: &str) -> Option<usize> {
+        self.index.get(id).copied()
+    }
+
+    /// ID of a node
+    pub fn id(&self, i: usize) -> &str {
+        &self.nodes[i]
+    }
+
+    /// Direct predecessors of a node
+    pub fn predecessors(&self, i: usize) -> &[usize] {
+        &self.predecessors[i]
+    }
+
+    /// Direct successors of a node
+    pub fn successors(&self, i: usize) -> &[usize] {
+        &self.successors[i]
+    }
+
+    /// All edges as `(before, after)` ID pairs
+    pub fn edges(&self) -> Vec<(&str, &str)> {
+        self.successors
+            .iter()
+            .enumerate()
+            .flat_map(|(b, succ)| succ.iter().map(move |&a| (self.id(b), self.id(a))))
+            .collect()
+    }
+
+    /// Graph with every edge reversed
+    pub fn reversed(&self) -> Self {
+        Self {
+            nodes: self.nodes.clone(),
+            index: self.index.clone(),
+            predecessors: self.successors.clone(),
+            successors: self.predecessors.clone(),
+        }
+    }
+
+    /// Topological order (Kahn's algorithm)
+    ///
+    /// Nodes that cannot be ordered (on a cycle or reachable from one)
+    /// are reported in `cyclic` instead.
+    pub fn topological_order(&self) -> TopologicalOrder {
+        let mut in_degree: Vec<usize> = self.predecessors.iter().map(Vec::len).collect();
+        let mut queue: VecDeque<usize> = (0..self.len()).filter(|&i| in_degree[i] == 0).collect();
+        let mut order = Vec::with_capacity(self.len());
+        while let Some(i) = queue.pop_front() {
+            order.push(i);
+            for &j in &self.successors[i] {
+                in_degree[j] -= 1;
+                if in_degree[j] == 0 {
+                    queue.push_back(j);
+                }
+            }
+        }
+        TopologicalOrder {
+            order,
+            cyclic: (0..self.len()).filter(|&i| in_degree[i] > 0).collect(),
+        }
+    }
+
+    /// Whether the graph has no cycle
+    pub fn is_acyclic(&self) -> bool {
+        self.topological_order().is_complete()
+    }
+
+    /// Precedence cycles (strongly connected components with an edge)
+    ///
+    /// Each cycle lists its node IDs in insertion order; cycles are sorted
+    /// by their first node.
+    pub fn cycles(&self) -> Vec<Vec<String>> {
+        let mut cycles: Vec<Vec<usize>> = self
+            .strongly_connected()
+            .into_iter()
+            .filter(|c| c.len() > 1 || self.successors[c[0]].contains(&c[0]))
+            .map(|mut c| {
+                c.sort_unstable();
+                c
+            })
+            .collect();
+        cycles.sort();
+        cycles
+            .into_iter()
+            .map(|c| c.into_iter().map(|i| self.nodes[i].clone()).collect())
+            .collect()
+    }
+
+    /// Whether `to` is reachable from `from` over one or more edges
+    pub fn reaches(&self, from: &str, to: &str) -> bool {
+        match (self.index_of(from), self.index_of(to)) {
+            (Some(f), Some(t)) => self.reachable(f)[t],
+            _ => false,
+        }
+    }
+
+    /// IDs of all nodes reachable from `id` (transitive successors)
+    pub fn descendants(&self, id: &str) -> Vec<&str> {
+        self.collect(self.index_of(id).map(|i| self.reachable(i)))
+    }
+
+    /// IDs of all nodes `id` is reachable from (transitive predecessors)
+    pub fn ancestors(&self, id: &str) -> Vec<&str> {
+        self.collect(self.index_of(id).map(|i| self.reversed().reachable(i)))
+    }
+
+    /// Graph without edges implied by longer paths
+    ///
+    /// An edge `a -> b` is dropped if `b` is also reachable from another
+    /// successor of `a`. On an acyclic graph the result is the unique
+    /// transitive reduction; edges on a cycle are kept.
+    pub fn transitive_reduction(&self) -> Self {
+        let mut reduced = self.clone();
+        for b in 0..self.len() {
+            let successors = &self.successors[b];
+            let reach: Vec<Vec<bool>> = successors.iter().map(|&s| self.reachable(s)).collect();
+            let kept: Vec<usize> = successors
+                .iter()
+                .enumerate()
+                .filter(|&(k, &a)| {
+                    let implied = successors
+                        .iter()
+                        .enumerate()
+                        .any(|(j, &other)| j != k && other != b && reach[j][a]);
+                    !implied || reach[k][b]
+                })
+                .map(|(_, &a)| a)
+                .collect();
+            for &a in successors {
+                if !kept.contains(&a) {
+                    reduced.predecessors[a].retain(|&p| p != b);
+                }
+            }
+            reduced.successors[b] = kept;
+        }
+        reduced
+    }
+
+    /// Nodes reachable from `from` over one or more edges
+    fn reachable(&self, from: usize) -> Vec<bool> {
+        let mut seen = vec![false; self.len()];
+        let mut stack: Vec<usize> = self.successors[from].clone();
+        while let Some(i) = stack.pop() {
+            if !seen[i] {
+                seen[i] = true;
+                stack.extend(&self.successors[i]);
+            }
+        }
+        seen
+    }
+
+    fn collect(&self, seen: Option<Vec<bool>>) -> Vec<&str> {
+        seen.map_or_else(Vec::new, |seen| {
+            (0..self.len())
+                .filter(|&i| seen[i])
+                .map(|i| self.id(i))
+                .collect()
+        })
+    }
+
+    /// Strongly connected components (Tarjan, iterative)
+    fn strongly_connected(&self) -> Vec<Vec<usize>> {
+        let n = self.len();
+        let mut index = vec![usize::MAX; n];
+        let mut low = vec![0; n];
+        let mut on_stack = vec![false; n];
+        let mut stack = Vec::new();
+        let mut components = Vec::new();
+        let mut counter = 0;
+
+        for root in 0..n {
+            if index[root] != usize::MAX {
+                continue;
+            }
+            // (node, next successor position)
+            let mut call: Vec<(usize, usize)> = vec![(root, 0)];
+            index[root] = counter;
+            low[root] = counter;
+            counter += 1;
+            stack.push(root);
+            on_stack[root] = true;
+
+            while let Some(&mut (v, ref mut next)) = call.last_mut() {
+                if let Some(&w) = self.successors[v].get(*next) {
+                    *next += 1;
+                    if index[w] == usize::MAX {
+                        index[w] = counter;
+                        low[w] = counter;
+                        counter += 1;
+                        stack.push(w);
+                        on_stack[w] = true;
+                        call.push((w, 0));
+                    } else if on_stack[w] {
+                        low[v] = low[v].min(index[w]);
+                    }
+                    continue;
+                }
+                call.pop();
+                if let Some(&(parent, _)) = call.last() {
+                    low[parent] = low[parent].min(low[v]);
+                }
+                if low[v] == index[v] {
+                    let mut component = Vec::new();
+                    while let Some(w) = stack.pop() {
+                        on_stack[w] = false;
+                        component.push(w);
+                        if w == v {
+                            break;
+                        }
+                    }
+                    components.push(component);
+                }
+            }
+        }
+        components
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn graph(edges: &[(&str, &str)]) -> PrecedenceGraph {
+        let mut graph = PrecedenceGraph::new();
+        for (b, a) in edges {
+            graph.add_edge(b, a);
+        }
+        graph
+    }
+
+    #[test]
+    fn test_from_tasks() {
+        let tasks = vec![
+            Task::new("T1")
+                .with_activity(Activity::new("A2", "T1", 2))
+                .with_activity(Activity::new("A1", "T1", 1)),
+            Task::new("T2")
+                .with_activity(Activity::new("B1", "T2", 1).with_predecessor("A2"))
+                .with_activity(Activity::new("B2", "T2", 2).with_predecessor("X")),
+        ];
+        let graph = PrecedenceGraph::from_tasks(&tasks);
+
+        assert_eq!(graph.len(), 4);
+        assert_eq!(graph.edges(), vec![("A2", "B1"), ("A1", "A2")]);
+        let order: Vec<&str> = graph
+            .topological_order()
+            .order
+            .into_iter()
+            .map(|i| graph.id(i))
+            .collect();
+        assert_eq!(order, vec!["A1", "B2", "A2", "B1"]);
+        assert!(graph.reaches("A1", "B1"));
+        assert!(!graph.reaches("B1", "A1"));
+        assert_eq!(graph.ancestors("B1"), vec!["A2", "A1"]);
+    }
+
+    #[test]
+    fn test_cycles() {
+        let graph = graph(&[("A", "B"), ("B", "C"), ("C", "A"), ("C", "D"), ("E", "E")]);
+
+        let order = graph.topological_order();
+        assert!(order.order.is_empty());
+        assert_eq!(order.cyclic.len(), 5);
+        assert!(!graph.is_acyclic());
+        assert_eq!(graph.cycles(), vec![vec!["A", "B", "C"], vec!["E"]]);
+        assert!(graph.reaches("A", "A"));
+        assert!(!graph.reaches("D", "D"));
+    }
+
+    #[test]
+    fn test_transitive_reduction() {
+        let graph = graph(&[("A", "B"), ("B", "C"), ("A", "C"), ("A", "D"), ("C", "D")]);
+
+        let reduced = graph.transitive_reduction();
+        assert_eq!(reduced.edges(), vec![("A", "B"), ("B", "C"), ("C", "D")]);
+        assert_eq!(reduced.predecessors(reduced.index_of("D").unwrap()), &[2]);
+        assert_eq!(reduced.descendants("A"), graph.descendants("A"));
+    }
+}
@@ -50,12 +50,29 @@
 //! assert!(schedule.makespan_ms > 0);
 //! ```
 
+pub mod compare;
 pub mod cp;
 pub mod dispatching;
+pub mod format;
 pub mod ga;
+pub mod golden;
+pub mod graph;
 pub mod models;
+pub mod parallel;
+#[cfg(feature = "protobuf")]
+pub mod proto;
+pub mod psplib;
+pub mod report;
 pub mod scheduler;
+#[cfg(feature = "schema")]
+pub mod schema;
+pub mod simulation;
+#[cfg(feature = "sqlite")]
+pub mod storage;
+pub mod telemetry;
 pub mod validation;
+#[cfg(feature = "wasm")]
+pub mod wasm;
 
 pub use cp::*;
 pub use dispatching::*;
@@ -2,6 +2,8 @@
 //!
 //! Domain-agnostic representation of work steps
 
+use super::time_constraints::DurationDistribution;
+use super::time_unit::TimeUnit;
 use serde::{Deserialize, Serialize};
 
 /// Activity - A step within a task requiring resources
@@ -13,6 +15,7 @@ use serde::{Deserialize, Serialize};
 /// - Education: Lecture, Lab Session
 /// - Cloud: Task Stage, Computation Step
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Activity {
     /// Unique identifier
     pub id: String,
@@ -30,12 +33,38 @@ pub struct Activity {
     pub splittable: bool,
     /// Minimum split size if splittable (ms)
     pub min_split_ms: i64,
+    /// Energy demand while running (kW)
+    #[serde(default)]
+    pub energy_kw: f64,
+    /// Demand of shared quantities while running (power, headcount, bandwidth)
+    #[serde(default)]
+    pub demands: std::collections::HashMap<String, f64>,
+    /// Usage per capacity dimension of a multi-dimensional resource (CPU
+    /// cores, memory GB), packed against `Resource::dimension_capacity`
+    #[serde(default)]
+    pub dimension_demands: std::collections::HashMap<String, f64>,
+    /// Units of renewable resource pools held while running (RCPSP)
+    #[serde(default)]
+    pub pool_demands: std::collections::HashMap<String, u32>,
+    /// Soft preference weight per resource (preferred machine, instructor)
+    #[serde(default)]
+    pub preferred_resources: std::collections::HashMap<String, f64>,
+    /// Location where the activity takes place (defaults to the resource's)
+    #[serde(default)]
+    pub location: Option<String>,
+    /// Duration/allocation trade-off when more units can be assigned
+    #[serde(default)]
+    pub malleable: Option<Malleability>,
+    /// Uncertainty of the process time (planning uses `duration`)
+    #[serde(default)]
+    pub duration_distribution: Option<DurationDistribution>,
     /// Custom attributes
     pub attributes: std::collections::HashMap<String, String>,
 }
 
 /// Duration specification for activity
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct ActivityDuration {
     /// Setup/preparation time (ms)
     pub setup_ms: i64,
@@ -64,6 +93,11 @@ impl ActivityDuration {
         }
     }
 
+    /// Create fixed duration in a given unit
+    pub fn fixed_in(amount: f64, unit: TimeUnit) -> Self {
+        Self::fixed(unit.to_ms(amount))
+    }
+
     /// Total duration
     pub fn total_ms(&self) -> i64 {
         self.setup_ms + self.process_ms + self.teardown_ms
@@ -76,8 +110,46 @@ impl Default for ActivityDuration {
     }
 }
 
+/// Allocation range of a malleable activity
+///
+/// With `k` units the work runs `1 + (k - 1) * efficiency` times as fast:
+/// efficiency 1.0 is linear speedup, 0.0 means extra units do not help.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Malleability {
+    /// Minimum units allocated
+    pub min_units: u32,
+    /// Maximum units allocated
+    pub max_units: u32,
+    /// Parallel efficiency of each additional unit (0.0 to 1.0)
+    pub efficiency: f64,
+}
+
+impl Malleability {
+    /// Create allocation range
+    pub fn new(min_units: u32, max_units: u32, efficiency: f64) -> Self {
+        let min_units = min_units.max(1);
+        Self {
+            min_units,
+            max_units: max_units.max(min_units),
+            efficiency: efficiency.clamp(0.0, 1.0),
+        }
+    }
+
+    /// Speedup with `units` allocated
+    pub fn speedup(&self, units: u32) -> f64 {
+        1.0 + (units.max(1) - 1) as f64 * self.efficiency
+    }
+
+    /// Duration of `process_ms` of work with `units` allocated
+    pub fn duration_ms(&self, process_ms: i64, units: u32) -> i64 {
+        (process_ms as f64 / self.speedup(units)).ceil() as i64
+    }
+}
+
 /// Resource requirement for an activity
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct ResourceRequirement {
     /// Resource type/category
     pub resource_type: String,
@@ -137,54 +209,240 @@ impl Activity {
             predecessors: Vec::new(),
             splittable: false,
             min_split_ms: 0,
+            energy_kw: 0.0,
+            demands: std::collections::HashMap::new(),
+            dimension_demands: std::collections::HashMap::new(),
+            pool_demands: std::collections::HashMap::new(),
+            preferred_resources: std::collections::HashMap::new(),
+            location: None,
+            malleable: None,
+            duration_distribution: None,
             attributes: std::collections::HashMap::new(),
         }
     }
 
     /// Set duration
     pub fn with_duration(mut self, duration: ActivityDuration) -> Self {
-        self.duration = duration;
+        self.set_duration(duration);
         self
     }
 
+    /// Set duration
+    pub fn set_duration(&mut self, duration: ActivityDuration) {
+        self.duration = duration;
+    }
+
     /// Set duration with components
     pub fn with_time(mut self, setup_ms: i64, process_ms: i64, teardown_ms: i64) -> Self {
-        self.duration = ActivityDuration::new(setup_ms, process_ms, teardown_ms);
+        self.set_time(setup_ms, process_ms, teardown_ms);
         self
     }
 
+    /// Set duration with components
+    pub fn set_time(&mut self, setup_ms: i64, process_ms: i64, teardown_ms: i64) {
+        self.duration = ActivityDuration::new(setup_ms, process_ms, teardown_ms);
+    }
+
     /// Add resource requirement
     pub fn with_requirement(mut self, requirement: ResourceRequirement) -> Self {
-        self.resource_requirements.push(requirement);
+        self.add_requirement(requirement);
         self
     }
 
+    /// Add resource requirement
+    pub fn add_requirement(&mut self, requirement: ResourceRequirement) {
+        self.resource_requirements.push(requirement);
+    }
+
     /// Add simple resource candidates (shorthand)
     pub fn with_resources(mut self, resource_type: &str, candidates: Vec<String>) -> Self {
+        self.add_resources(resource_type, candidates);
+        self
+    }
+
+    /// Add simple resource candidates (shorthand)
+    pub fn add_resources(&mut self, resource_type: &str, candidates: Vec<String>) {
         self.resource_requirements
             .push(ResourceRequirement::new(resource_type).with_candidates(candidates));
+    }
+
+    /// Add gang requirement: `count` distinct candidates for the whole duration
+    pub fn with_gang(mut self, resource_type: &str, candidates: Vec<String>, count: i32) -> Self {
+        self.add_gang(resource_type, candidates, count);
+        self
+    }
+
+    /// Add gang requirement: `count` distinct candidates for the whole duration
+    pub fn add_gang(&mut self, resource_type: &str, candidates: Vec<String>, count: i32) {
+        self.resource_requirements.push(
+            ResourceRequirement::new(resource_type)
+                .with_candidates(candidates)
+                .with_quantity(count),
+        );
+    }
+
+    /// Allow `min_units..=max_units` of the first requirement's candidates
+    pub fn with_malleability(mut self, min_units: u32, max_units: u32, efficiency: f64) -> Self {
+        self.set_malleability(min_units, max_units, efficiency);
+        self
+    }
+
+    /// Allow `min_units..=max_units` of the first requirement's candidates
+    pub fn set_malleability(&mut self, min_units: u32, max_units: u32, efficiency: f64) {
+        self.malleable = Some(Malleability::new(min_units, max_units, efficiency));
+    }
+
+    /// Set process time distribution
+    pub fn with_duration_distribution(mut self, distribution: DurationDistribution) -> Self {
+        self.set_duration_distribution(distribution);
         self
     }
 
+    /// Set process time distribution
+    pub fn set_duration_distribution(&mut self, distribution: DurationDistribution) {
+        self.duration_distribution = Some(distribution);
+    }
+
+    /// Check if the activity needs several resources simultaneously
+    pub fn is_gang(&self) -> bool {
+        self.resource_requirements.iter().any(|r| r.quantity > 1)
+    }
+
+    /// Number of resources occupied at once
+    pub fn gang_size(&self) -> usize {
+        self.resource_requirements
+            .iter()
+            .map(|r| r.quantity.max(1) as usize)
+            .sum()
+    }
+
     /// Add predecessor
     pub fn with_predecessor(mut self, activity_id: &str) -> Self {
-        self.predecessors.push(activity_id.to_string());
+        self.add_predecessor(activity_id);
         self
     }
 
+    /// Add predecessor
+    pub fn add_predecessor(&mut self, activity_id: &str) {
+        self.predecessors.push(activity_id.to_string());
+    }
+
     /// Enable splitting
     pub fn with_splitting(mut self, min_split_ms: i64) -> Self {
+        self.set_splitting(min_split_ms);
+        self
+    }
+
+    /// Enable splitting
+    pub fn set_splitting(&mut self, min_split_ms: i64) {
         self.splittable = true;
         self.min_split_ms = min_split_ms;
+    }
+
+    /// Set energy demand (kW)
+    pub fn with_energy_demand(mut self, energy_kw: f64) -> Self {
+        self.set_energy_demand(energy_kw);
+        self
+    }
+
+    /// Set energy demand (kW)
+    pub fn set_energy_demand(&mut self, energy_kw: f64) {
+        self.energy_kw = energy_kw;
+    }
+
+    /// Set demand of a shared quantity
+    pub fn with_demand(mut self, quantity: &str, amount: f64) -> Self {
+        self.set_demand(quantity, amount);
+        self
+    }
+
+    /// Set demand of a shared quantity
+    pub fn set_demand(&mut self, quantity: &str, amount: f64) {
+        self.demands.insert(quantity.to_string(), amount);
+    }
+
+    /// Demand of a shared quantity (0.0 if not declared)
+    pub fn demand(&self, quantity: &str) -> f64 {
+        self.demands.get(quantity).copied().unwrap_or(0.0)
+    }
+
+    /// Set usage in a capacity dimension of the assigned resource
+    pub fn with_dimension_demand(mut self, dimension: &str, amount: f64) -> Self {
+        self.set_dimension_demand(dimension, amount);
+        self
+    }
+
+    /// Set usage in a capacity dimension of the assigned resource
+    pub fn set_dimension_demand(&mut self, dimension: &str, amount: f64) {
+        self.dimension_demands.insert(dimension.to_string(), amount);
+    }
+
+    /// Usage in a capacity dimension (0.0 if not declared)
+    pub fn dimension_demand(&self, dimension: &str) -> f64 {
+        self.dimension_demands
+            .get(dimension)
+            .copied()
+            .unwrap_or(0.0)
+    }
+
+    /// Hold units of a renewable resource pool while running
+    pub fn with_pool_demand(mut self, pool_id: &str, units: u32) -> Self {
+        self.set_pool_demand(pool_id, units);
         self
     }
 
+    /// Hold units of a renewable resource pool while running
+    pub fn set_pool_demand(&mut self, pool_id: &str, units: u32) {
+        self.pool_demands.insert(pool_id.to_string(), units);
+    }
+
+    /// Units of a renewable pool held (0 if not declared)
+    pub fn pool_demand(&self, pool_id: &str) -> u32 {
+        self.pool_demands.get(pool_id).copied().unwrap_or(0)
+    }
+
+    /// Prefer a resource with weight (0.0 to 1.0)
+    pub fn with_preferred_resource(mut self, resource_id: &str, weight: f64) -> Self {
+        self.set_preferred_resource(resource_id, weight);
+        self
+    }
+
+    /// Prefer a resource with weight (0.0 to 1.0)
+    pub fn set_preferred_resource(&mut self, resource_id: &str, weight: f64) {
+        self.preferred_resources
+            .insert(resource_id.to_string(), weight.clamp(0.0, 1.0));
+    }
+
+    /// Preference weight for a resource (0.0 if not preferred)
+    pub fn resource_preference(&self, resource_id: &str) -> f64 {
+        self.preferred_resources
+            .get(resource_id)
+            .copied()
+            .unwrap_or(0.0)
+    }
+
+    /// Set location
+    pub fn with_location(mut self, location: &str) -> Self {
+        self.set_location(location);
+        self
+    }
+
+    /// Set location
+    pub fn set_location(&mut self, location: &str) {
+        self.location = Some(location.to_string());
+    }
+
     /// Add custom attribute
     pub fn with_attribute(mut self, key: &str, value: &str) -> Self {
-        self.attributes.insert(key.to_string(), value.to_string());
+        self.set_attribute(key, value);
         self
     }
 
+    /// Add custom attribute
+    pub fn set_attribute(&mut self, key: &str, value: &str) {
+        self.attributes.insert(key.to_string(), value.to_string());
+    }
+
     /// Get first candidate resource IDs
     pub fn candidate_resources(&self) -> Vec<String> {
         self.resource_requirements
@@ -216,6 +474,27 @@ mod tests {
 
         let fixed = ActivityDuration::fixed(3000);
         assert_eq!(fixed.total_ms(), 3000);
+
+        let lift = Activity::new("L1", "T1", 1)
+            .with_gang("crane", vec!["C1".into(), "C2".into(), "C3".into()], 2)
+            .with_resources("operator", vec!["O1".into()]);
+        assert!(lift.is_gang());
+        assert_eq!(lift.gang_size(), 3);
+    }
+
+    #[test]
+    fn test_malleability() {
+        // 4 workers at 1/3 efficiency finish in half the time
+        let m = Malleability::new(1, 4, 1.0 / 3.0);
+        assert_eq!(m.duration_ms(8000, 1), 8000);
+        assert_eq!(m.duration_ms(8000, 4), 4000);
+
+        let linear = Malleability::new(0, 0, 1.0);
+        assert_eq!((linear.min_units, linear.max_units), (1, 1));
+        assert_eq!(linear.duration_ms(9000, 3), 3000);
+
+        let minutes = ActivityDuration::fixed_in(15.0, TimeUnit::Minutes);
+        assert_eq!(minutes.total_ms(), 900_000);
     }
 
     #[test]
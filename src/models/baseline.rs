@@ -0,0 +1,244 @@
+//! Baseline - An existing plan as the starting state of a replan
+//!
+//! Splits a previously produced schedule (or an external plan given as
+//! assignments) at a point in time: finished work is dropped, work in
+//! progress is frozen and work not yet started becomes a hint for the
+//! next scheduling run. Reported progress of started activities
+//! (`ActivityProgress`) anchors them to their actual start and resource
+//! with the remaining duration recomputed.
+
+use super::schedule::{Assignment, Schedule};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// State of a planned activity at the baseline time
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BaselineState {
+    /// All of its work ended by the baseline time
+    Completed,
+    /// Started before the baseline time and not yet finished
+    InProgress,
+    /// Not started yet
+    Pending,
+}
+
+/// Existing plan split at a point in time
+///
+/// Activities are classified as a whole: an activity with several
+/// assignments (split or gang work) is in progress as soon as any of
+/// them started, and completed only when all of them ended.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Baseline {
+    /// Baseline time (epoch ms)
+    pub as_of_ms: i64,
+    /// Finished assignments (dropped from the replan)
+    pub completed: Vec<Assignment>,
+    /// In-progress assignments (kept as they are)
+    pub frozen: Vec<Assignment>,
+    /// Not yet started assignments (previous resource is preferred)
+    pub hints: Vec<Assignment>,
+    /// Reported execution state (overrides the plan for its activities)
+    #[serde(default)]
+    pub progress: Vec<ActivityProgress>,
+}
+
+/// Reported execution state of a started activity
+///
+/// A started activity cannot be moved or reassigned: it stays on its
+/// resource from its actual start and ends once the remaining share of
+/// its processing time has run from the baseline time.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActivityProgress {
+    /// Activity ID
+    pub activity_id: String,
+    /// Resource the activity runs on
+    pub resource_id: String,
+    /// Actual start (epoch ms)
+    pub actual_start_ms: i64,
+    /// Completed share (0.0 to 1.0)
+    pub progress: f64,
+}
+
+impl ActivityProgress {
+    /// Create progress report
+    pub fn new(activity_id: &str, resource_id: &str, actual_start_ms: i64, progress: f64) -> Self {
+        Self {
+            activity_id: activity_id.to_string(),
+            resource_id: resource_id.to_string(),
+            actual_start_ms,
+            progress: progress.clamp(0.0, 1.0),
+        }
+    }
+
+    /// Whether the activity is finished
+    pub fn is_complete(&self) -> bool {
+        self.progress >= 1.0
+    }
+
+    /// Remaining time of an activity taking `duration_ms` in total
+    pub fn remaining_ms(&self, duration_ms: i64) -> i64 {
+        (duration_ms as f64 * (1.0 - self.progress)).ceil() as i64
+    }
+
+    /// Assignment from the actual start to the projected end
+    pub fn anchor(&self, task_id: &str, duration_ms: i64, as_of_ms: i64) -> Assignment {
+        let end_ms = as_of_ms.max(self.actual_start_ms) + self.remaining_ms(duration_ms);
+        Assignment::new(
+            &self.activity_id,
+            task_id,
+            &self.resource_id,
+            self.actual_start_ms,
+            end_ms,
+        )
+    }
+}
+
+impl Baseline {
+    /// Empty baseline at `as_of_ms` (e.g., for progress reports only)
+    pub fn new(as_of_ms: i64) -> Self {
+        Self {
+            as_of_ms,
+            ..Self::default()
+        }
+    }
+
+    /// Add reported progress of a started activity
+    pub fn with_progress(mut self, progress: ActivityProgress) -> Self {
+        self.progress
+            .retain(|p| p.activity_id != progress.activity_id);
+        self.progress.push(progress);
+        self
+    }
+
+    /// Reported progress of an activity
+    pub fn progress_of(&self, activity_id: &str) -> Option<&ActivityProgress> {
+        self.progress.iter().find(|p| p.activity_id == activity_id)
+    }
+
+    /// Baseline of a schedule at `as_of_ms`
+    pub fn from_schedule(schedule: &Schedule, as_of_ms: i64) -> Self {
+        Self::from_assignments(schedule.assignments.iter().cloned(), as_of_ms)
+    }
+
+    /// Baseline of an external plan at `as_of_ms`
+    pub fn from_assignments(
+        assignments: impl IntoIterator<Item = Assignment>,
+        as_of_ms: i64,
+    ) -> Self {
+        let mut by_activity: Vec<(String, Vec<Assignment>)> = Vec::new();
+        let mut index: HashMap<String, usize> = HashMap::new();
+        for assignment in assignments {
+            let slot = *index
+                .entry(assignment.activity_id.clone())
+                .or_insert_with(|| {
+                    by_activity.push((assignment.activity_id.clone(), Vec::new()));
+                    by_activity.len() - 1
+                });
+            by_activity[slot].1.push(assignment);
+        }
+
+        let mut baseline = Self::new(as_of_ms);
+        for (_, assignments) in by_activity {
+            let target = match classify(&assignments, as_of_ms) {
+                BaselineState::Completed => &mut baseline.completed,
+                BaselineState::InProgress => &mut baseline.frozen,
+                BaselineState::Pending => &mut baseline.hints,
+            };
+            target.extend(assignments);
+        }
+        baseline
+    }
+
+    /// State of an activity (None if it is not in the plan)
+    pub fn state(&self, activity_id: &str) -> Option<BaselineState> {
+        if let Some(progress) = self.progress_of(activity_id) {
+            return Some(if progress.is_complete() {
+                BaselineState::Completed
+            } else {
+                BaselineState::InProgress
+            });
+        }
+        let has = |list: &[Assignment]| list.iter().any(|a| a.activity_id == activity_id);
+        if has(&self.frozen) {
+            Some(BaselineState::InProgress)
+        } else if has(&self.completed) {
+            Some(BaselineState::Completed)
+        } else if has(&self.hints) {
+            Some(BaselineState::Pending)
+        } else {
+            None
+        }
+    }
+
+    /// End of an in-progress activity in the plan
+    pub fn frozen_end(&self, activity_id: &str) -> Option<i64> {
+        self.frozen
+            .iter()
+            .filter(|a| a.activity_id == activity_id)
+            .map(|a| a.end_ms)
+            .max()
+    }
+
+    /// Previously planned resource of a pending activity
+    pub fn hint(&self, activity_id: &str) -> Option<&str> {
+        self.pending(activity_id).map(|a| a.resource_id.as_str())
+    }
+
+    /// Planned (first) assignment of a pending activity
+    pub fn pending(&self, activity_id: &str) -> Option<&Assignment> {
+        self.hints.iter().find(|a| a.activity_id == activity_id)
+    }
+}
+
+fn classify(assignments: &[Assignment], as_of_ms: i64) -> BaselineState {
+    if assignments.iter().all(|a| a.end_ms <= as_of_ms) {
+        BaselineState::Completed
+    } else if assignments.iter().any(|a| a.start_ms < as_of_ms) {
+        BaselineState::InProgress
+    } else {
+        BaselineState::Pending
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_at_time() {
+        let plan = vec![
+            Assignment::new("A1", "T1", "M1", 0, 1000),
+            Assignment::new("A2", "T1", "M1", 1000, 3000),
+            Assignment::new("A3", "T1", "M2", 3000, 4000),
+            // Split activity: first part done, second part ahead
+            Assignment::new("B1", "T2", "M2", 0, 500),
+            Assignment::new("B1", "T2", "M2", 2500, 3000),
+        ];
+
+        let baseline = Baseline::from_assignments(plan, 2000);
+
+        assert_eq!(baseline.state("A1"), Some(BaselineState::Completed));
+        assert_eq!(baseline.state("A2"), Some(BaselineState::InProgress));
+        assert_eq!(baseline.state("A3"), Some(BaselineState::Pending));
+        assert_eq!(baseline.state("B1"), Some(BaselineState::InProgress));
+        assert_eq!(baseline.state("X"), None);
+        assert_eq!(baseline.frozen.len(), 3);
+        assert_eq!(baseline.frozen_end("B1"), Some(3000));
+        assert_eq!(baseline.hint("A3"), Some("M2"));
+
+        // Reported progress overrides the plan
+        let baseline = baseline.with_progress(ActivityProgress::new("A2", "M2", 1200, 1.0));
+        assert_eq!(baseline.state("A2"), Some(BaselineState::Completed));
+    }
+
+    #[test]
+    fn test_progress_anchor() {
+        let progress = ActivityProgress::new("A1", "M1", 1000, 0.6);
+
+        // 40% of 5 s left at 4000
+        let anchor = progress.anchor("T1", 5000, 4000);
+        assert_eq!((anchor.start_ms, anchor.end_ms), (1000, 6000));
+        assert_eq!(anchor.resource_id, "M1");
+        assert!(!progress.is_complete());
+    }
+}
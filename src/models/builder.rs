@@ -0,0 +1,428 @@
+//! Builders - Validated construction of tasks and resources
+//!
+//! `TaskBuilder` and `ResourceBuilder` take the same `with_*` calls as
+//! `Task` and `Resource` but finish with `build()`, which rejects
+//! malformed input (empty IDs, negative durations, non-positive
+//! efficiency, duplicate activity sequences) at construction time
+//! instead of during scheduling. The plain `with_*` chains stay
+//! available for quick prototyping.
+
+use super::activity::Activity;
+use super::calendar::Calendar;
+use super::labor::WorkingTimeRules;
+use super::resource::{Resource, ResourceType};
+use super::task::{PriorityClass, Task};
+use chrono::{DateTime, Utc};
+use std::collections::HashSet;
+
+/// Why a builder rejected its input
+#[derive(Debug, Clone, PartialEq)]
+pub enum BuildError {
+    /// An ID is empty (kind of entity: task, activity, resource)
+    EmptyId(&'static str),
+    /// An activity has a negative setup, process or teardown time
+    NegativeDuration(String),
+    /// Two activities of a task share a sequence number (task, sequence)
+    DuplicateSequence(String, i32),
+    /// Two activities of a task share an ID
+    DuplicateActivity(String),
+    /// An activity names another task as its parent (activity, its task ID)
+    ForeignActivity(String, String),
+    /// A task's release time is after its deadline
+    ReleaseAfterDeadline(String),
+    /// A task's target date is after its hard deadline
+    TargetAfterDeadline(String),
+    /// A resource's efficiency is not a positive number (resource, value)
+    InvalidEfficiency(String, f64),
+    /// A resource's capacity is below one (resource, value)
+    InvalidCapacity(String, i32),
+    /// A resource's revocation probability is outside 0.0 to 1.0
+    InvalidProbability(String, f64),
+}
+
+impl std::fmt::Display for BuildError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BuildError::EmptyId(kind) => write!(f, "{} ID is empty", kind),
+            BuildError::NegativeDuration(id) => {
+                write!(f, "activity {} has a negative duration", id)
+            }
+            BuildError::DuplicateSequence(task, sequence) => {
+                write!(
+                    f,
+                    "task {} has two activities with sequence {}",
+                    task, sequence
+                )
+            }
+            BuildError::DuplicateActivity(id) => write!(f, "duplicate activity ID {}", id),
+            BuildError::ForeignActivity(id, task) => {
+                write!(f, "activity {} belongs to task {}", id, task)
+            }
+            BuildError::ReleaseAfterDeadline(id) => {
+                write!(f, "task {} is released after its deadline", id)
+            }
+            BuildError::TargetAfterDeadline(id) => {
+                write!(f, "task {} has a target date after its deadline", id)
+            }
+            BuildError::InvalidEfficiency(id, value) => {
+                write!(f, "resource {} has efficiency {} (must be > 0)", id, value)
+            }
+            BuildError::InvalidCapacity(id, value) => {
+                write!(f, "resource {} has capacity {} (must be >= 1)", id, value)
+            }
+            BuildError::InvalidProbability(id, value) => write!(
+                f,
+                "resource {} has revocation probability {} (must be in 0..=1)",
+                id, value
+            ),
+        }
+    }
+}
+
+impl std::error::Error for BuildError {}
+
+/// Validated builder for `Task`
+#[derive(Debug, Clone)]
+pub struct TaskBuilder {
+    task: Task,
+}
+
+impl TaskBuilder {
+    /// Start a task with ID
+    pub fn new(id: &str) -> Self {
+        Self {
+            task: Task::new(id),
+        }
+    }
+
+    /// Set task name
+    pub fn with_name(mut self, name: &str) -> Self {
+        self.task = self.task.with_name(name);
+        self
+    }
+
+    /// Set category
+    pub fn with_category(mut self, category: &str) -> Self {
+        self.task = self.task.with_category(category);
+        self
+    }
+
+    /// Set priority
+    pub fn with_priority(mut self, priority: i32) -> Self {
+        self.task = self.task.with_priority(priority);
+        self
+    }
+
+    /// Set strict priority class
+    pub fn with_priority_class(mut self, class: PriorityClass) -> Self {
+        self.task = self.task.with_priority_class(class);
+        self
+    }
+
+    /// Set weight
+    pub fn with_weight(mut self, weight: f64) -> Self {
+        self.task = self.task.with_weight(weight);
+        self
+    }
+
+    /// Set deadline
+    pub fn with_deadline(mut self, deadline: DateTime<Utc>) -> Self {
+        self.task = self.task.with_deadline(deadline);
+        self
+    }
+
+    /// Set target completion date
+    pub fn with_target_date(mut self, target: DateTime<Utc>) -> Self {
+        self.task = self.task.with_target_date(target);
+        self
+    }
+
+    /// Set release time
+    pub fn with_release_time(mut self, release_time: DateTime<Utc>) -> Self {
+        self.task = self.task.with_release_time(release_time);
+        self
+    }
+
+    /// Add activity
+    pub fn with_activity(mut self, activity: Activity) -> Self {
+        self.task = self.task.with_activity(activity);
+        self
+    }
+
+    /// Add custom attribute
+    pub fn with_attribute(mut self, key: &str, value: &str) -> Self {
+        self.task = self.task.with_attribute(key, value);
+        self
+    }
+
+    /// Set load in a dimension (weight, volume)
+    pub fn with_load(mut self, dimension: &str, amount: f64) -> Self {
+        self.task = self.task.with_load(dimension, amount);
+        self
+    }
+
+    /// Validate and return the task
+    pub fn build(self) -> Result<Task, BuildError> {
+        let task = self.task;
+        if task.id.is_empty() {
+            return Err(BuildError::EmptyId("task"));
+        }
+        if let (Some(release), Some(deadline)) = (task.release_time, task.deadline) {
+            if release > deadline {
+                return Err(BuildError::ReleaseAfterDeadline(task.id));
+            }
+        }
+        if let (Some(target), Some(deadline)) = (task.target_date, task.deadline) {
+            if target > deadline {
+                return Err(BuildError::TargetAfterDeadline(task.id));
+            }
+        }
+
+        let mut ids = HashSet::new();
+        let mut sequences = HashSet::new();
+        for activity in &task.activities {
+            if activity.id.is_empty() {
+                return Err(BuildError::EmptyId("activity"));
+            }
+            if activity.task_id != task.id {
+                return Err(BuildError::ForeignActivity(
+                    activity.id.clone(),
+                    activity.task_id.clone(),
+                ));
+            }
+            let d = &activity.duration;
+            if d.setup_ms < 0 || d.process_ms < 0 || d.teardown_ms < 0 {
+                return Err(BuildError::NegativeDuration(activity.id.clone()));
+            }
+            if !ids.insert(activity.id.as_str()) {
+                return Err(BuildError::DuplicateActivity(activity.id.clone()));
+            }
+            if !sequences.insert(activity.sequence) {
+                return Err(BuildError::DuplicateSequence(
+                    task.id.clone(),
+                    activity.sequence,
+                ));
+            }
+        }
+        Ok(task)
+    }
+}
+
+/// Validated builder for `Resource`
+#[derive(Debug, Clone)]
+pub struct ResourceBuilder {
+    resource: Resource,
+}
+
+impl ResourceBuilder {
+    /// Start a resource with ID and type
+    pub fn new(id: &str, resource_type: ResourceType) -> Self {
+        Self {
+            resource: Resource::new(id, resource_type),
+        }
+    }
+
+    /// Set name
+    pub fn with_name(mut self, name: &str) -> Self {
+        self.resource = self.resource.with_name(name);
+        self
+    }
+
+    /// Set capacity
+    pub fn with_capacity(mut self, capacity: i32) -> Self {
+        self.resource = self.resource.with_capacity(capacity);
+        self
+    }
+
+    /// Set efficiency
+    pub fn with_efficiency(mut self, efficiency: f64) -> Self {
+        self.resource = self.resource.with_efficiency(efficiency);
+        self
+    }
+
+    /// Set calendar
+    pub fn with_calendar(mut self, calendar: Calendar) -> Self {
+        self.resource = self.resource.with_calendar(calendar);
+        self
+    }
+
+    /// Add skill
+    pub fn with_skill(mut self, name: &str, level: f64) -> Self {
+        self.resource = self.resource.with_skill(name, level);
+        self
+    }
+
+    /// Set cost
+    pub fn with_cost(mut self, cost_per_hour: f64) -> Self {
+        self.resource = self.resource.with_cost(cost_per_hour);
+        self
+    }
+
+    /// Set working-time rules
+    pub fn with_working_time_rules(mut self, rules: WorkingTimeRules) -> Self {
+        self.resource = self.resource.with_working_time_rules(rules);
+        self
+    }
+
+    /// Add preferred shift with weight (0.0 to 1.0)
+    pub fn with_preferred_shift(mut self, start_ms: i64, end_ms: i64, weight: f64) -> Self {
+        self.resource = self.resource.with_preferred_shift(start_ms, end_ms, weight);
+        self
+    }
+
+    /// Set location
+    pub fn with_location(mut self, location: &str) -> Self {
+        self.resource = self.resource.with_location(location);
+        self
+    }
+
+    /// Set carrying capacity in a load dimension (weight, volume)
+    pub fn with_load_capacity(mut self, dimension: &str, capacity: f64) -> Self {
+        self.resource = self.resource.with_load_capacity(dimension, capacity);
+        self
+    }
+
+    /// Set shared capacity in a dimension (CPU cores, memory GB)
+    pub fn with_dimension_capacity(mut self, dimension: &str, capacity: f64) -> Self {
+        self.resource = self.resource.with_dimension_capacity(dimension, capacity);
+        self
+    }
+
+    /// Set probability of being revoked during the horizon
+    ///
+    /// Unlike `Resource::with_revocation_risk`, out-of-range values are
+    /// rejected by `build` rather than clamped.
+    pub fn with_revocation_risk(mut self, probability: f64) -> Self {
+        self.resource.revocation_probability = probability;
+        self
+    }
+
+    /// Require one of `crew` to be present during setups
+    pub fn with_setup_crew(mut self, crew: Vec<String>) -> Self {
+        self.resource = self.resource.with_setup_crew(crew);
+        self
+    }
+
+    /// Add attribute
+    pub fn with_attribute(mut self, key: &str, value: &str) -> Self {
+        self.resource = self.resource.with_attribute(key, value);
+        self
+    }
+
+    /// Validate and return the resource
+    pub fn build(self) -> Result<Resource, BuildError> {
+        let resource = self.resource;
+        if resource.id.is_empty() {
+            return Err(BuildError::EmptyId("resource"));
+        }
+        if !(resource.efficiency > 0.0 && resource.efficiency.is_finite()) {
+            return Err(BuildError::InvalidEfficiency(
+                resource.id,
+                resource.efficiency,
+            ));
+        }
+        if resource.capacity < 1 {
+            return Err(BuildError::InvalidCapacity(resource.id, resource.capacity));
+        }
+        if !(0.0..=1.0).contains(&resource.revocation_probability) {
+            return Err(BuildError::InvalidProbability(
+                resource.id,
+                resource.revocation_probability,
+            ));
+        }
+        Ok(resource)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ActivityDuration;
+
+    fn activity(id: &str, task_id: &str, sequence: i32, process_ms: i64) -> Activity {
+        Activity::new(id, task_id, sequence).with_duration(ActivityDuration::fixed(process_ms))
+    }
+
+    #[test]
+    fn test_task_builder() {
+        let task = TaskBuilder::new("T1")
+            .with_priority(3)
+            .with_activity(activity("A1", "T1", 1, 1000))
+            .with_activity(activity("A2", "T1", 2, 2000))
+            .build()
+            .unwrap();
+        assert_eq!(task.priority, 3);
+        assert_eq!(task.total_duration_ms(), 3000);
+
+        let build = |builder: TaskBuilder| builder.build().unwrap_err();
+        assert_eq!(build(TaskBuilder::new("")), BuildError::EmptyId("task"));
+        assert_eq!(
+            build(TaskBuilder::new("T1").with_activity(activity("A1", "T1", 1, -5))),
+            BuildError::NegativeDuration("A1".into())
+        );
+        assert_eq!(
+            build(
+                TaskBuilder::new("T1")
+                    .with_activity(activity("A1", "T1", 1, 1000))
+                    .with_activity(activity("A2", "T1", 1, 1000))
+            ),
+            BuildError::DuplicateSequence("T1".into(), 1)
+        );
+        assert_eq!(
+            build(TaskBuilder::new("T1").with_activity(activity("A1", "T2", 1, 1000))),
+            BuildError::ForeignActivity("A1".into(), "T2".into())
+        );
+        let at = |ms| DateTime::from_timestamp_millis(ms).unwrap();
+        assert_eq!(
+            build(
+                TaskBuilder::new("T1")
+                    .with_release_time(at(2000))
+                    .with_deadline(at(1000))
+            ),
+            BuildError::ReleaseAfterDeadline("T1".into())
+        );
+        assert_eq!(
+            build(
+                TaskBuilder::new("T1")
+                    .with_target_date(at(2000))
+                    .with_deadline(at(1000))
+            ),
+            BuildError::TargetAfterDeadline("T1".into())
+        );
+    }
+
+    #[test]
+    fn test_resource_builder() {
+        let resource = ResourceBuilder::new("M1", ResourceType::Primary)
+            .with_efficiency(1.5)
+            .with_capacity(2)
+            .build()
+            .unwrap();
+        assert_eq!(resource.efficiency, 1.5);
+
+        let build = |builder: ResourceBuilder| builder.build().unwrap_err();
+        let machine = || ResourceBuilder::new("M1", ResourceType::Primary);
+        assert_eq!(
+            build(ResourceBuilder::new("", ResourceType::Human)),
+            BuildError::EmptyId("resource")
+        );
+        assert_eq!(
+            build(machine().with_efficiency(0.0)),
+            BuildError::InvalidEfficiency("M1".into(), 0.0)
+        );
+        assert!(matches!(
+            build(machine().with_efficiency(f64::NAN)),
+            BuildError::InvalidEfficiency(..)
+        ));
+        assert_eq!(
+            build(machine().with_capacity(0)),
+            BuildError::InvalidCapacity("M1".into(), 0)
+        );
+        let error = build(machine().with_revocation_risk(1.5));
+        assert_eq!(error, BuildError::InvalidProbability("M1".into(), 1.5));
+        assert_eq!(
+            error.to_string(),
+            "resource M1 has revocation probability 1.5 (must be in 0..=1)"
+        );
+    }
+}
@@ -4,8 +4,12 @@
 
 use serde::{Deserialize, Serialize};
 
+/// Milliseconds per day
+const MS_PER_DAY: i64 = 24 * 3600 * 1000;
+
 /// Calendar - Defines availability over time
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Calendar {
     /// Calendar identifier
     pub id: String,
@@ -13,10 +17,58 @@ pub struct Calendar {
     pub time_windows: Vec<TimeWindow>,
     /// Blocked periods (holidays, maintenance)
     pub blocked_periods: Vec<TimeWindow>,
+    /// Named exceptions overriding windows and blocked periods
+    #[serde(default)]
+    pub exceptions: Vec<CalendarException>,
+}
+
+/// Kind of calendar exception
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum ExceptionKind {
+    /// Public holiday (closed)
+    Holiday,
+    /// One-off closure (closed)
+    Closure,
+    /// Ad-hoc extra opening (open)
+    ExtraOpening,
+}
+
+/// Named override of the recurring calendar pattern
+///
+/// Closures and holidays take precedence over extra openings when they
+/// overlap.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct CalendarException {
+    /// Name (e.g., "New Year", "Stocktake")
+    pub name: String,
+    /// Kind of exception
+    pub kind: ExceptionKind,
+    /// Affected period
+    pub window: TimeWindow,
+}
+
+impl CalendarException {
+    /// Create new exception
+    pub fn new(name: &str, kind: ExceptionKind, start_ms: i64, end_ms: i64) -> Self {
+        Self {
+            name: name.to_string(),
+            kind,
+            window: TimeWindow::new(start_ms, end_ms),
+        }
+    }
+
+    /// Whether the period is open for work
+    pub fn is_open(&self) -> bool {
+        self.kind == ExceptionKind::ExtraOpening
+    }
 }
 
 /// Time window - A period of availability
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct TimeWindow {
     /// Start time (epoch ms)
     pub start_ms: i64,
@@ -53,6 +105,7 @@ impl Calendar {
             id: id.to_string(),
             time_windows: Vec::new(),
             blocked_periods: Vec::new(),
+            exceptions: Vec::new(),
         }
     }
 
@@ -62,23 +115,120 @@ impl Calendar {
             id: id.to_string(),
             time_windows: vec![TimeWindow::new(0, i64::MAX)],
             blocked_periods: Vec::new(),
+            exceptions: Vec::new(),
         }
     }
 
     /// Add time window
     pub fn with_window(mut self, start_ms: i64, end_ms: i64) -> Self {
-        self.time_windows.push(TimeWindow::new(start_ms, end_ms));
+        self.add_window(start_ms, end_ms);
         self
     }
 
+    /// Add time window
+    pub fn add_window(&mut self, start_ms: i64, end_ms: i64) {
+        self.time_windows.push(TimeWindow::new(start_ms, end_ms));
+    }
+
     /// Add blocked period
     pub fn with_blocked(mut self, start_ms: i64, end_ms: i64) -> Self {
+        self.add_blocked(start_ms, end_ms);
+        self
+    }
+
+    /// Add blocked period
+    pub fn add_blocked(&mut self, start_ms: i64, end_ms: i64) {
         self.blocked_periods.push(TimeWindow::new(start_ms, end_ms));
+    }
+
+    /// Add exception
+    pub fn with_exception(mut self, exception: CalendarException) -> Self {
+        self.add_exception(exception);
         self
     }
 
+    /// Add exception
+    pub fn add_exception(&mut self, exception: CalendarException) {
+        self.exceptions.push(exception);
+    }
+
+    /// Add public holiday
+    pub fn with_holiday(mut self, name: &str, start_ms: i64, end_ms: i64) -> Self {
+        self.add_holiday(name, start_ms, end_ms);
+        self
+    }
+
+    /// Add public holiday
+    pub fn add_holiday(&mut self, name: &str, start_ms: i64, end_ms: i64) {
+        self.add_exception(CalendarException::new(
+            name,
+            ExceptionKind::Holiday,
+            start_ms,
+            end_ms,
+        ));
+    }
+
+    /// Add one-off closure
+    pub fn with_closure(mut self, name: &str, start_ms: i64, end_ms: i64) -> Self {
+        self.add_closure(name, start_ms, end_ms);
+        self
+    }
+
+    /// Add one-off closure
+    pub fn add_closure(&mut self, name: &str, start_ms: i64, end_ms: i64) {
+        self.add_exception(CalendarException::new(
+            name,
+            ExceptionKind::Closure,
+            start_ms,
+            end_ms,
+        ));
+    }
+
+    /// Add ad-hoc extra opening
+    pub fn with_extra_opening(mut self, name: &str, start_ms: i64, end_ms: i64) -> Self {
+        self.add_extra_opening(name, start_ms, end_ms);
+        self
+    }
+
+    /// Add ad-hoc extra opening
+    pub fn add_extra_opening(&mut self, name: &str, start_ms: i64, end_ms: i64) {
+        self.add_exception(CalendarException::new(
+            name,
+            ExceptionKind::ExtraOpening,
+            start_ms,
+            end_ms,
+        ));
+    }
+
+    /// Exception in effect at time (closures before openings)
+    pub fn exception_at(&self, timestamp_ms: i64) -> Option<&CalendarException> {
+        let mut active = self
+            .exceptions
+            .iter()
+            .filter(|e| e.window.contains(timestamp_ms));
+        let first = active.next()?;
+        if first.is_open() {
+            active.find(|e| !e.is_open()).or(Some(first))
+        } else {
+            Some(first)
+        }
+    }
+
+    /// Holidays overlapping an interval
+    pub fn holidays_between(&self, start_ms: i64, end_ms: i64) -> Vec<&CalendarException> {
+        let interval = TimeWindow::new(start_ms, end_ms);
+        self.exceptions
+            .iter()
+            .filter(|e| e.kind == ExceptionKind::Holiday && e.window.overlaps(&interval))
+            .collect()
+    }
+
     /// Check if time is working time
     pub fn is_working_time(&self, timestamp_ms: i64) -> bool {
+        if let Some(exception) = self.exception_at(timestamp_ms) {
+            return exception.is_open();
+        }
+
         // Check if in any time window
         let in_window = self.time_windows.iter().any(|w| w.contains(timestamp_ms));
         // Check if not blocked
@@ -96,29 +246,217 @@ impl Calendar {
             return from_ms;
         }
 
-        // Find next window start
-        self.time_windows
+        // Next point where availability can change
+        let mut changes: Vec<i64> = self
+            .time_windows
             .iter()
-            .filter(|w| w.end_ms > from_ms)
-            .map(|w| w.start_ms.max(from_ms))
-            .min()
+            .map(|w| w.start_ms)
+            .chain(self.blocked_periods.iter().map(|b| b.end_ms))
+            .chain(self.exceptions.iter().map(|e| {
+                if e.is_open() {
+                    e.window.start_ms
+                } else {
+                    e.window.end_ms
+                }
+            }))
+            .filter(|&t| t > from_ms)
+            .collect();
+        changes.sort_unstable();
+        changes
+            .into_iter()
+            .find(|&t| self.is_working_time(t))
             .unwrap_or(from_ms)
     }
 
+    /// Latest time at or before `to_ms` that is working time or ends a
+    /// working stretch (latest feasible end for backward scheduling)
+    pub fn previous_available_time(&self, to_ms: i64) -> i64 {
+        if self.is_open(to_ms - 1) {
+            return to_ms;
+        }
+        self.change_points()
+            .into_iter()
+            .rev()
+            .filter(|&t| t < to_ms)
+            .find(|&t| self.is_open(t - 1))
+            .unwrap_or(to_ms)
+    }
+
+    /// Time at which `duration_ms` of working time starting at `start_ms`
+    /// is complete
+    ///
+    /// Non-working periods stretch the elapsed time; `None` if the
+    /// calendar runs out of working time.
+    pub fn add_working_duration(&self, start_ms: i64, duration_ms: i64) -> Option<i64> {
+        if duration_ms <= 0 {
+            return Some(start_ms);
+        }
+        let mut remaining = duration_ms;
+        let mut t = start_ms;
+        for p in self.change_points().into_iter().filter(|&p| p > start_ms) {
+            if self.is_open(t) {
+                if t + remaining <= p {
+                    return Some(t + remaining);
+                }
+                remaining -= p - t;
+            }
+            t = p;
+        }
+        // Availability is constant after the last change
+        self.is_open(t).then_some(t + remaining)
+    }
+
+    /// Time at which work of `duration_ms` must start to complete at
+    /// `end_ms` (backward counterpart of `add_working_duration`)
+    pub fn subtract_working_duration(&self, end_ms: i64, duration_ms: i64) -> Option<i64> {
+        if duration_ms <= 0 {
+            return Some(end_ms);
+        }
+        let mut remaining = duration_ms;
+        let mut t = end_ms;
+        for p in self
+            .change_points()
+            .into_iter()
+            .rev()
+            .filter(|&p| p < end_ms)
+        {
+            if self.is_open(p) {
+                if t - remaining >= p {
+                    return Some(t - remaining);
+                }
+                remaining -= t - p;
+            }
+            t = p;
+        }
+        self.is_open(t - 1).then_some(t - remaining)
+    }
+
+    /// Check if the whole interval is working time
+    pub fn is_available_between(&self, start_ms: i64, end_ms: i64) -> bool {
+        self.working_duration_between(start_ms, end_ms) >= end_ms - start_ms
+    }
+
     /// Calculate available time between two points
+    ///
+    /// Counts time windows (or the whole interval if there are none)
+    /// adjusted by exceptions; blocked periods are not deducted.
     pub fn available_time_between(&self, start_ms: i64, end_ms: i64) -> i64 {
-        if self.time_windows.is_empty() {
-            return end_ms - start_ms;
+        self.covered_time(start_ms, end_ms, |t| {
+            self.exception_at(t)
+                .map_or_else(|| self.in_pattern(t), |e| e.is_open())
+        })
+    }
+
+    /// Working time between two points, net of blocked periods and closures
+    pub fn working_duration_between(&self, start_ms: i64, end_ms: i64) -> i64 {
+        self.covered_time(start_ms, end_ms, |t| self.is_open(t))
+    }
+
+    /// Number of days (UTC, epoch-aligned) in an interval with any working time
+    pub fn working_days_between(&self, start_ms: i64, end_ms: i64) -> usize {
+        if end_ms <= start_ms {
+            return 0;
+        }
+        (start_ms.div_euclid(MS_PER_DAY)..=(end_ms - 1).div_euclid(MS_PER_DAY))
+            .filter(|day| {
+                let day_start = (day * MS_PER_DAY).max(start_ms);
+                let day_end = ((day + 1) * MS_PER_DAY).min(end_ms);
+                self.working_duration_between(day_start, day_end) > 0
+            })
+            .count()
+    }
+
+    /// Calendar open only where both calendars are open
+    ///
+    /// Blocked periods and exceptions are resolved into plain windows, so
+    /// the result answers availability queries as cheaply as a single
+    /// calendar (holiday names are not kept). Chain calls to combine
+    /// resource, site and contract calendars.
+    pub fn intersect(&self, other: &Calendar) -> Calendar {
+        let mut bounds = self.change_points();
+        bounds.extend(other.change_points());
+        bounds.push(i64::MIN);
+        bounds.push(i64::MAX);
+        bounds.sort_unstable();
+        bounds.dedup();
+
+        let mut windows: Vec<TimeWindow> = Vec::new();
+        for s in bounds.windows(2) {
+            if !(self.is_open(s[0]) && other.is_open(s[0])) {
+                continue;
+            }
+            match windows.last_mut() {
+                Some(last) if last.end_ms == s[0] => last.end_ms = s[1],
+                _ => windows.push(TimeWindow::new(s[0], s[1])),
+            }
+        }
+        if windows.is_empty() {
+            // An empty window: never open (no windows would mean always)
+            windows.push(TimeWindow::new(0, 0));
         }
 
-        self.time_windows
+        Calendar {
+            id: format!("{}&{}", self.id, other.id),
+            time_windows: windows,
+            blocked_periods: Vec::new(),
+            exceptions: Vec::new(),
+        }
+    }
+
+    /// Open for work, net of blocked periods and exceptions (no windows
+    /// means always open)
+    fn is_open(&self, timestamp_ms: i64) -> bool {
+        self.exception_at(timestamp_ms).map_or_else(
+            || {
+                self.in_pattern(timestamp_ms)
+                    && !self
+                        .blocked_periods
+                        .iter()
+                        .any(|b| b.contains(timestamp_ms))
+            },
+            |e| e.is_open(),
+        )
+    }
+
+    /// Sorted times at which availability may change
+    fn change_points(&self) -> Vec<i64> {
+        let mut points: Vec<i64> = self
+            .time_windows
             .iter()
-            .filter(|w| w.overlaps(&TimeWindow::new(start_ms, end_ms)))
-            .map(|w| {
-                let overlap_start = w.start_ms.max(start_ms);
-                let overlap_end = w.end_ms.min(end_ms);
-                overlap_end - overlap_start
-            })
+            .chain(&self.blocked_periods)
+            .chain(self.exceptions.iter().map(|e| &e.window))
+            .flat_map(|w| [w.start_ms, w.end_ms])
+            .collect();
+        points.sort_unstable();
+        points.dedup();
+        points
+    }
+
+    /// Inside the recurring pattern (no windows means always)
+    fn in_pattern(&self, timestamp_ms: i64) -> bool {
+        self.time_windows.is_empty() || self.time_windows.iter().any(|w| w.contains(timestamp_ms))
+    }
+
+    /// Length of the parts of an interval where `open` holds
+    ///
+    /// Availability only changes at window boundaries, so it is sampled
+    /// once per elementary segment.
+    fn covered_time(&self, start_ms: i64, end_ms: i64, open: impl Fn(i64) -> bool) -> i64 {
+        if end_ms <= start_ms {
+            return 0;
+        }
+        let mut points: Vec<i64> = self
+            .change_points()
+            .into_iter()
+            .filter(|&t| t > start_ms && t < end_ms)
+            .collect();
+        points.insert(0, start_ms);
+        points.push(end_ms);
+
+        points
+            .windows(2)
+            .filter(|s| open(s[0]))
+            .map(|s| s[1] - s[0])
             .sum()
     }
 }
@@ -155,4 +493,118 @@ mod tests {
         assert!(!calendar.is_working_time(7000));
         assert!(calendar.is_working_time(15000));
     }
+
+    #[test]
+    fn test_exceptions() {
+        const HOUR: i64 = 3600 * 1000;
+        const DAY: i64 = 24 * HOUR;
+        // Weekday shifts 9-17 for days 0..5
+        let mut calendar = Calendar::new("office");
+        for d in 0..5 {
+            calendar = calendar.with_window(d * DAY + 9 * HOUR, d * DAY + 17 * HOUR);
+        }
+        let calendar = calendar
+            .with_holiday("Founders Day", 2 * DAY, 3 * DAY)
+            .with_extra_opening("Saturday sale", 5 * DAY + 10 * HOUR, 5 * DAY + 14 * HOUR)
+            .with_closure("Inventory", 4 * DAY + 12 * HOUR, 4 * DAY + 13 * HOUR);
+
+        assert!(!calendar.is_working_time(2 * DAY + 10 * HOUR));
+        assert_eq!(
+            calendar.exception_at(2 * DAY + 10 * HOUR).unwrap().name,
+            "Founders Day"
+        );
+        assert!(calendar.is_working_time(5 * DAY + 11 * HOUR));
+        assert!(!calendar.is_working_time(4 * DAY + 12 * HOUR));
+
+        // Skips the holiday to the next morning
+        assert_eq!(
+            calendar.next_available_time(DAY + 17 * HOUR),
+            3 * DAY + 9 * HOUR
+        );
+        assert_eq!(
+            calendar.next_available_time(4 * DAY + 12 * HOUR),
+            4 * DAY + 13 * HOUR
+        );
+
+        // 4 regular days + the extra Saturday, holiday excluded
+        assert_eq!(calendar.working_days_between(0, 7 * DAY), 5);
+        assert_eq!(calendar.holidays_between(0, 7 * DAY).len(), 1);
+        assert_eq!(
+            calendar.working_duration_between(4 * DAY, 6 * DAY),
+            7 * HOUR + 4 * HOUR
+        );
+        assert!(!calendar.is_available_between(4 * DAY + 11 * HOUR, 4 * DAY + 14 * HOUR));
+    }
+
+    #[test]
+    fn test_working_time_arithmetic() {
+        const HOUR: i64 = 3600 * 1000;
+        let calendar = Calendar::new("shifts")
+            .with_window(0, 8 * HOUR)
+            .with_window(9 * HOUR, 17 * HOUR)
+            .with_blocked(12 * HOUR, 13 * HOUR);
+
+        // Work stretches over the 8-9 gap and the 12-13 lunch block
+        assert_eq!(calendar.add_working_duration(0, 10 * HOUR), Some(11 * HOUR));
+        assert_eq!(
+            calendar.add_working_duration(6 * HOUR, 6 * HOUR),
+            Some(14 * HOUR)
+        );
+        assert_eq!(calendar.add_working_duration(0, 20 * HOUR), None);
+
+        assert_eq!(
+            calendar.subtract_working_duration(14 * HOUR, 6 * HOUR),
+            Some(6 * HOUR)
+        );
+        assert_eq!(calendar.subtract_working_duration(HOUR, 2 * HOUR), None);
+        assert_eq!(
+            calendar.working_duration_between(6 * HOUR, 14 * HOUR),
+            6 * HOUR
+        );
+
+        assert_eq!(
+            calendar.previous_available_time(12 * HOUR + 1800 * 1000),
+            12 * HOUR
+        );
+        assert_eq!(calendar.previous_available_time(8 * HOUR + 1), 8 * HOUR);
+        assert_eq!(calendar.previous_available_time(10 * HOUR), 10 * HOUR);
+    }
+
+    #[test]
+    fn test_intersect() {
+        const HOUR: i64 = 3600 * 1000;
+        let resource = Calendar::new("machine").with_blocked(10 * HOUR, 11 * HOUR);
+        let site = Calendar::new("site")
+            .with_window(6 * HOUR, 18 * HOUR)
+            .with_holiday("Founders", 14 * HOUR, 15 * HOUR);
+        let contract = Calendar::new("contract").with_window(8 * HOUR, 16 * HOUR);
+
+        let both = resource.intersect(&site).intersect(&contract);
+        assert_eq!(both.id, "machine&site&contract");
+        let spans: Vec<(i64, i64)> = both
+            .time_windows
+            .iter()
+            .map(|w| (w.start_ms, w.end_ms))
+            .collect();
+        assert_eq!(
+            spans,
+            vec![
+                (8 * HOUR, 10 * HOUR),
+                (11 * HOUR, 14 * HOUR),
+                (15 * HOUR, 16 * HOUR)
+            ]
+        );
+        assert_eq!(both.next_available_time(0), 8 * HOUR);
+        assert_eq!(
+            both.add_working_duration(9 * HOUR, 2 * HOUR),
+            Some(12 * HOUR)
+        );
+        assert_eq!(both.working_duration_between(0, 24 * HOUR), 6 * HOUR);
+
+        // Disjoint calendars are never open
+        let night = Calendar::new("night").with_window(20 * HOUR, 22 * HOUR);
+        let never = contract.intersect(&night);
+        assert!(!never.is_working_time(21 * HOUR));
+        assert_eq!(never.working_duration_between(0, 24 * HOUR), 0);
+    }
 }
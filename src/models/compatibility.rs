@@ -0,0 +1,680 @@
+//! Compatibility - Attribute-matching eligibility rules
+//!
+//! Declarative rules over task, activity and resource attributes decide
+//! which candidates may run an activity, e.g.
+//!
+//! ```text
+//! resource.attr("temp_class") == task.attr("temp_class")
+//! resource.attr("max_weight_kg") >= task.attr("weight_kg") && !resource.attr("maintenance")
+//! ```
+//!
+//! Grammar: comparisons (`==`, `!=`, `<`, `<=`, `>`, `>=`) of attribute
+//! references (`resource.attr("k")`, `task.attr("k")`,
+//! `activity.attr("k")`) and literals (`"text"`, `42`, `0.5`), combined
+//! with `!`, `&&`, `||` and parentheses. A bare reference tests that the
+//! attribute is set. Values compare numerically when both sides are
+//! numbers, as text otherwise; a comparison with a missing attribute is
+//! false.
+//!
+//! Rules are compiled into the activities' candidate lists
+//! ([`Compatibility::apply`]), which every scheduler already honors.
+
+use super::activity::{Activity, ResourceRequirement};
+use super::resource::Resource;
+use super::task::Task;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fmt;
+
+/// Value in a rule: attribute reference or literal
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Operand {
+    /// Attribute of the candidate resource
+    Resource(String),
+    /// Attribute of the task
+    Task(String),
+    /// Attribute of the activity
+    Activity(String),
+    /// Text literal
+    Text(String),
+    /// Number literal
+    Number(f64),
+}
+
+/// Comparison operator
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CompareOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+/// Eligibility rule expression
+///
+/// Serialized as its source text.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(try_from = "String", into = "String")]
+pub enum AttrExpr {
+    /// Attribute is set
+    Present(Operand),
+    /// Comparison of two values
+    Compare(Operand, CompareOp, Operand),
+    /// Negation
+    Not(Box<AttrExpr>),
+    /// All hold
+    And(Vec<AttrExpr>),
+    /// Any holds
+    Or(Vec<AttrExpr>),
+}
+
+/// Rule text that could not be parsed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuleParseError {
+    /// Byte offset of the problem
+    pub position: usize,
+    /// What was wrong
+    pub message: String,
+}
+
+impl fmt::Display for RuleParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at offset {}", self.message, self.position)
+    }
+}
+
+impl std::error::Error for RuleParseError {}
+
+impl Operand {
+    fn resolve<'a>(
+        &'a self,
+        task: &'a Task,
+        activity: &'a Activity,
+        resource: &'a Resource,
+    ) -> Option<Value<'a>> {
+        let text = |map: &'a HashMap<String, String>, key: &str| {
+            map.get(key).map(|v| Value::Text(v.as_str()))
+        };
+        match self {
+            Operand::Resource(key) => text(&resource.attributes, key),
+            Operand::Task(key) => text(&task.attributes, key),
+            Operand::Activity(key) => text(&activity.attributes, key),
+            Operand::Text(value) => Some(Value::Text(value)),
+            Operand::Number(value) => Some(Value::Number(*value)),
+        }
+    }
+}
+
+/// Resolved operand
+enum Value<'a> {
+    Text(&'a str),
+    Number(f64),
+}
+
+impl Value<'_> {
+    fn number(&self) -> Option<f64> {
+        match self {
+            Value::Text(s) => s.trim().parse().ok(),
+            Value::Number(n) => Some(*n),
+        }
+    }
+
+    fn compare(&self, other: &Value<'_>) -> Option<Ordering> {
+        match (self.number(), other.number()) {
+            (Some(a), Some(b)) => a.partial_cmp(&b),
+            _ => Some(self.text().cmp(&other.text())),
+        }
+    }
+
+    fn text(&self) -> String {
+        match self {
+            Value::Text(s) => s.to_string(),
+            Value::Number(n) => n.to_string(),
+        }
+    }
+}
+
+impl CompareOp {
+    fn holds(&self, ordering: Ordering) -> bool {
+        match self {
+            CompareOp::Eq => ordering == Ordering::Equal,
+            CompareOp::Ne => ordering != Ordering::Equal,
+            CompareOp::Lt => ordering == Ordering::Less,
+            CompareOp::Le => ordering != Ordering::Greater,
+            CompareOp::Gt => ordering == Ordering::Greater,
+            CompareOp::Ge => ordering != Ordering::Less,
+        }
+    }
+
+    fn symbol(&self) -> &'static str {
+        match self {
+            CompareOp::Eq => "==",
+            CompareOp::Ne => "!=",
+            CompareOp::Lt => "<",
+            CompareOp::Le => "<=",
+            CompareOp::Gt => ">",
+            CompareOp::Ge => ">=",
+        }
+    }
+}
+
+impl AttrExpr {
+    /// Parse rule text
+    pub fn parse(source: &str) -> Result<Self, RuleParseError> {
+        let mut parser = Parser {
+            tokens: tokenize(source)?,
+            pos: 0,
+            end: source.len(),
+        };
+        let expr = parser.or()?;
+        match parser.tokens.get(parser.pos) {
+            None => Ok(expr),
+            Some((at, _)) => Err(RuleParseError {
+                position: *at,
+                message: "unexpected trailing input".to_string(),
+            }),
+        }
+    }
+
+    /// Whether the rule allows `resource` to run `activity` of `task`
+    pub fn eval(&self, task: &Task, activity: &Activity, resource: &Resource) -> bool {
+        match self {
+            AttrExpr::Present(operand) => operand.resolve(task, activity, resource).is_some(),
+            AttrExpr::Compare(left, op, right) => {
+                match (
+                    left.resolve(task, activity, resource),
+                    right.resolve(task, activity, resource),
+                ) {
+                    (Some(l), Some(r)) => l.compare(&r).is_some_and(|o| op.holds(o)),
+                    _ => false,
+                }
+            }
+            AttrExpr::Not(inner) => !inner.eval(task, activity, resource),
+            AttrExpr::And(items) => items.iter().all(|e| e.eval(task, activity, resource)),
+            AttrExpr::Or(items) => items.iter().any(|e| e.eval(task, activity, resource)),
+        }
+    }
+}
+
+impl std::str::FromStr for AttrExpr {
+    type Err = RuleParseError;
+
+    fn from_str(source: &str) -> Result<Self, Self::Err> {
+        Self::parse(source)
+    }
+}
+
+impl TryFrom<String> for AttrExpr {
+    type Error = RuleParseError;
+
+    fn try_from(source: String) -> Result<Self, Self::Error> {
+        Self::parse(&source)
+    }
+}
+
+impl From<AttrExpr> for String {
+    fn from(expr: AttrExpr) -> Self {
+        expr.to_string()
+    }
+}
+
+impl fmt::Display for Operand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let quote = |s: &str| format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""));
+        match self {
+            Operand::Resource(key) => write!(f, "resource.attr({})", quote(key)),
+            Operand::Task(key) => write!(f, "task.attr({})", quote(key)),
+            Operand::Activity(key) => write!(f, "activity.attr({})", quote(key)),
+            Operand::Text(value) => write!(f, "{}", quote(value)),
+            Operand::Number(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+impl fmt::Display for AttrExpr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Parenthesize `||` inside `&&` and anything compound under `!`
+        let join = |f: &mut fmt::Formatter<'_>, items: &[AttrExpr], sep: &str, wrap_or: bool| {
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    write!(f, " {} ", sep)?;
+                }
+                if wrap_or && matches!(item, AttrExpr::Or(_)) {
+                    write!(f, "({})", item)?;
+                } else {
+                    write!(f, "{}", item)?;
+                }
+            }
+            Ok(())
+        };
+        match self {
+            AttrExpr::Present(operand) => write!(f, "{}", operand),
+            AttrExpr::Compare(l, op, r) => write!(f, "{} {} {}", l, op.symbol(), r),
+            AttrExpr::Not(inner) => match inner.as_ref() {
+                AttrExpr::Present(_) | AttrExpr::Not(_) => write!(f, "!{}", inner),
+                _ => write!(f, "!({})", inner),
+            },
+            AttrExpr::And(items) => join(f, items, "&&", true),
+            AttrExpr::Or(items) => join(f, items, "||", false),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Text(String),
+    Number(f64),
+    Op(CompareOp),
+    And,
+    Or,
+    Not,
+    Dot,
+    Open,
+    Close,
+}
+
+fn tokenize(source: &str) -> Result<Vec<(usize, Token)>, RuleParseError> {
+    let error = |position: usize, message: &str| RuleParseError {
+        position,
+        message: message.to_string(),
+    };
+    let chars: Vec<(usize, char)> = source.char_indices().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let (at, c) = chars[i];
+        let next = chars.get(i + 1).map(|&(_, c)| c);
+        let (token, width) = match (c, next) {
+            (c, _) if c.is_whitespace() => {
+                i += 1;
+                continue;
+            }
+            ('=', Some('=')) => (Token::Op(CompareOp::Eq), 2),
+            ('!', Some('=')) => (Token::Op(CompareOp::Ne), 2),
+            ('<', Some('=')) => (Token::Op(CompareOp::Le), 2),
+            ('>', Some('=')) => (Token::Op(CompareOp::Ge), 2),
+            ('<', _) => (Token::Op(CompareOp::Lt), 1),
+            ('>', _) => (Token::Op(CompareOp::Gt), 1),
+            ('&', Some('&')) => (Token::And, 2),
+            ('|', Some('|')) => (Token::Or, 2),
+            ('!', _) => (Token::Not, 1),
+            ('.', _) => (Token::Dot, 1),
+            ('(', _) => (Token::Open, 1),
+            (')', _) => (Token::Close, 1),
+            ('"' | '\'', _) => {
+                let mut text = String::new();
+                let mut j = i + 1;
+                loop {
+                    match chars.get(j) {
+                        None => return Err(error(at, "unterminated string")),
+                        Some(&(_, q)) if q == c => break,
+                        Some(&(_, '\\')) => {
+                            j += 1;
+                            match chars.get(j) {
+                                Some(&(_, escaped)) => text.push(escaped),
+                                None => return Err(error(at, "unterminated string")),
+                            }
+                        }
+                        Some(&(_, ch)) => text.push(ch),
+                    }
+                    j += 1;
+                }
+                (Token::Text(text), j + 1 - i)
+            }
+            (c, _) if c.is_ascii_digit() || c == '-' => {
+                let mut j = i + 1;
+                while chars
+                    .get(j)
+                    .is_some_and(|&(_, d)| d.is_ascii_digit() || d == '.')
+                {
+                    j += 1;
+                }
+                let end = chars.get(j).map_or(source.len(), |&(p, _)| p);
+                let number = source[at..end]
+                    .parse()
+                    .map_err(|_| error(at, "invalid number"))?;
+                (Token::Number(number), j - i)
+            }
+            (c, _) if c.is_alphabetic() || c == '_' => {
+                let mut j = i + 1;
+                while chars
+                    .get(j)
+                    .is_some_and(|&(_, d)| d.is_alphanumeric() || d == '_')
+                {
+                    j += 1;
+                }
+                let end = chars.get(j).map_or(source.len(), |&(p, _)| p);
+                (Token::Ident(source[at..end].to_string()), j - i)
+            }
+            _ => return Err(error(at, &format!("unexpected character '{}'", c))),
+        };
+        tokens.push((at, token));
+        i += width;
+    }
+    Ok(tokens)
+}
+
+/// Recursive-descent parser: or := and ("||" and)*, and := unary ("&&"
+/// unary)*, unary := "!" unary | "(" or ")" | operand (op operand)?
+struct Parser {
+    tokens: Vec<(usize, Token)>,
+    pos: usize,
+    end: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos).map(|(_, t)| t)
+    }
+
+    fn error(&self, message: &str) -> RuleParseError {
+        RuleParseError {
+            position: self.tokens.get(self.pos).map_or(self.end, |(at, _)| *at),
+            message: message.to_string(),
+        }
+    }
+
+    fn expect(&mut self, token: Token, what: &str) -> Result<(), RuleParseError> {
+        if self.peek() == Some(&token) {
+            self.pos += 1;
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected {}", what)))
+        }
+    }
+
+    fn or(&mut self) -> Result<AttrExpr, RuleParseError> {
+        let mut items = vec![self.and()?];
+        while self.peek() == Some(&Token::Or) {
+            self.pos += 1;
+            items.push(self.and()?);
+        }
+        Ok(if items.len() == 1 {
+            items.remove(0)
+        } else {
+            AttrExpr::Or(items)
+        })
+    }
+
+    fn and(&mut self) -> Result<AttrExpr, RuleParseError> {
+        let mut items = vec![self.unary()?];
+        while self.peek() == Some(&Token::And) {
+            self.pos += 1;
+            items.push(self.unary()?);
+        }
+        Ok(if items.len() == 1 {
+            items.remove(0)
+        } else {
+            AttrExpr::And(items)
+        })
+    }
+
+    fn unary(&mut self) -> Result<AttrExpr, RuleParseError> {
+        match self.peek() {
+            Some(Token::Not) => {
+                self.pos += 1;
+                Ok(AttrExpr::Not(Box::new(self.unary()?)))
+            }
+            Some(Token::Open) => {
+                self.pos += 1;
+                let inner = self.or()?;
+                self.expect(Token::Close, "')'")?;
+                Ok(inner)
+            }
+            _ => {
+                let left = self.operand()?;
+                if let Some(Token::Op(op)) = self.peek() {
+                    let op = *op;
+                    self.pos += 1;
+                    Ok(AttrExpr::Compare(left, op, self.operand()?))
+                } else if matches!(left, Operand::Text(_) | Operand::Number(_)) {
+                    Err(self.error("expected comparison after literal"))
+                } else {
+                    Ok(AttrExpr::Present(left))
+                }
+            }
+        }
+    }
+
+    fn operand(&mut self) -> Result<Operand, RuleParseError> {
+        let token = self.peek().cloned();
+        match token {
+            Some(Token::Text(text)) => {
+                self.pos += 1;
+                Ok(Operand::Text(text))
+            }
+            Some(Token::Number(number)) => {
+                self.pos += 1;
+                Ok(Operand::Number(number))
+            }
+            Some(Token::Ident(scope)) => {
+                let make: fn(String) -> Operand = match scope.as_str() {
+                    "resource" => Operand::Resource,
+                    "task" => Operand::Task,
+                    "activity" => Operand::Activity,
+                    _ => return Err(self.error("expected resource, task or activity")),
+                };
+                self.pos += 1;
+                self.expect(Token::Dot, "'.'")?;
+                self.expect(Token::Ident("attr".to_string()), "attr")?;
+                self.expect(Token::Open, "'('")?;
+                let key = match self.peek().cloned() {
+                    Some(Token::Text(key)) => key,
+                    _ => return Err(self.error("expected attribute name")),
+                };
+                self.pos += 1;
+                self.expect(Token::Close, "')'")?;
+                Ok(make(key))
+            }
+            _ => Err(self.error("expected attribute or literal")),
+        }
+    }
+}
+
+/// Set of eligibility rules a resource must all satisfy
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Compatibility {
+    /// Rules (all must hold)
+    pub rules: Vec<AttrExpr>,
+}
+
+impl Compatibility {
+    /// Create empty rule set (everything is compatible)
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parse a rule set from rule texts
+    pub fn parse<S: AsRef<str>>(rules: &[S]) -> Result<Self, RuleParseError> {
+        Ok(Self {
+            rules: rules
+                .iter()
+                .map(|r| AttrExpr::parse(r.as_ref()))
+                .collect::<Result<_, _>>()?,
+        })
+    }
+
+    /// Add rule
+    pub fn with_rule(mut self, rule: AttrExpr) -> Self {
+        self.add_rule(rule);
+        self
+    }
+
+    /// Add rule
+    pub fn add_rule(&mut self, rule: AttrExpr) {
+        self.rules.push(rule);
+    }
+
+    /// Whether there are no rules
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Whether `resource` may run `activity` of `task`
+    pub fn allows(&self, task: &Task, activity: &Activity, resource: &Resource) -> bool {
+        self.rules.iter().all(|r| r.eval(task, activity, resource))
+    }
+
+    /// Candidates of a requirement that satisfy the rules
+    ///
+    /// Candidates missing from `resources` are kept; input validation
+    /// reports them.
+    pub fn eligible_candidates(
+        &self,
+        task: &Task,
+        activity: &Activity,
+        requirement: &ResourceRequirement,
+        resources: &[Resource],
+    ) -> Vec<String> {
+        requirement
+            .candidates
+            .iter()
+            .filter(|c| {
+                resources
+                    .iter()
+                    .find(|r| &r.id == *c)
+                    .is_none_or(|r| self.allows(task, activity, r))
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Tasks with candidate lists narrowed to compatible resources
+    pub fn apply(&self, tasks: &[Task], resources: &[Resource]) -> Vec<Task> {
+        let mut compiled = tasks.to_vec();
+        if self.is_empty() {
+            return compiled;
+        }
+        for (task, original) in compiled.iter_mut().zip(tasks) {
+            for (activity, source) in task.activities.iter_mut().zip(&original.activities) {
+                for requirement in &mut activity.resource_requirements {
+                    requirement.candidates =
+                        self.eligible_candidates(original, source, requirement, resources);
+                }
+            }
+        }
+        compiled
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn oven(id: &str, class: &str, max_kg: &str) -> Resource {
+        Resource::primary(id)
+            .with_attribute("temp_class", class)
+            .with_attribute("max_weight_kg", max_kg)
+    }
+
+    fn job(class: &str, kg: &str) -> Task {
+        Task::new("T1")
+            .with_attribute("temp_class", class)
+            .with_attribute("weight_kg", kg)
+            .with_activity(
+                Activity::new("A1", "T1", 1)
+                    .with_resources("oven", vec!["O1".into(), "O2".into(), "O3".into()]),
+            )
+    }
+
+    #[test]
+    fn test_parse_and_display() {
+        let rule = AttrExpr::parse(
+            r#"resource.attr("temp_class") == task.attr("temp_class") && (resource.attr("max_weight_kg") >= task.attr("weight_kg") || !activity.attr('heavy'))"#,
+        )
+        .unwrap();
+        let AttrExpr::And(items) = &rule else {
+            panic!("expected conjunction, got {:?}", rule);
+        };
+        assert_eq!(
+            items[0],
+            AttrExpr::Compare(
+                Operand::Resource("temp_class".into()),
+                CompareOp::Eq,
+                Operand::Task("temp_class".into())
+            )
+        );
+        assert!(matches!(items[1], AttrExpr::Or(_)));
+        // Display parses back to the same rule
+        assert_eq!(AttrExpr::parse(&rule.to_string()).unwrap(), rule);
+        assert_eq!(
+            AttrExpr::parse("!(task.attr(\"a\") < -1.5)")
+                .unwrap()
+                .to_string(),
+            "!(task.attr(\"a\") < -1.5)"
+        );
+
+        let err = AttrExpr::parse("resource.attr(\"x\") == ").unwrap_err();
+        assert_eq!(err.position, 22);
+        assert!(AttrExpr::parse("machine.attr(\"x\")").is_err());
+        assert!(AttrExpr::parse("\"open").is_err());
+        assert!(AttrExpr::parse("5").is_err());
+        assert!(AttrExpr::parse("task.attr(\"a\") task.attr(\"b\")").is_err());
+    }
+
+    #[test]
+    fn test_eval_numeric_and_text() {
+        let task = job("hot", "120");
+        let activity = &task.activities[0];
+        let rules = Compatibility::parse(&[
+            r#"resource.attr("temp_class") == task.attr("temp_class")"#,
+            r#"resource.attr("max_weight_kg") >= task.attr("weight_kg")"#,
+        ])
+        .unwrap();
+
+        assert!(rules.allows(&task, activity, &oven("O1", "hot", "150")));
+        assert!(!rules.allows(&task, activity, &oven("O2", "cold", "150")));
+        // Numeric, not lexicographic: "90" < "120"
+        assert!(!rules.allows(&task, activity, &oven("O3", "hot", "90")));
+        // Missing attributes fail comparisons but can be tested for
+        assert!(!rules.allows(&task, activity, &Resource::primary("O4")));
+        let optional =
+            AttrExpr::parse(r#"!resource.attr("zone") || resource.attr("zone") == "A""#).unwrap();
+        assert!(optional.eval(&task, activity, &Resource::primary("O4")));
+        assert!(!optional.eval(
+            &task,
+            activity,
+            &oven("O5", "hot", "1").with_attribute("zone", "B")
+        ));
+        assert!(Compatibility::new().allows(&task, activity, &Resource::primary("O4")));
+    }
+
+    #[test]
+    fn test_apply_narrows_candidates() {
+        let tasks = vec![job("hot", "120")];
+        let resources = vec![
+            oven("O1", "hot", "150"),
+            oven("O2", "cold", "150"),
+            oven("O3", "hot", "200"),
+        ];
+        let rules = Compatibility::new().with_rule(
+            r#"resource.attr("temp_class") == task.attr("temp_class")"#
+                .parse()
+                .unwrap(),
+        );
+
+        let compiled = rules.apply(&tasks, &resources);
+        assert_eq!(
+            compiled[0].activities[0].candidate_resources(),
+            vec!["O1", "O3"]
+        );
+        assert_eq!(tasks[0].activities[0].candidate_resources().len(), 3);
+
+        // Rules serialize as their text
+        let json = serde_json::to_string(&rules).unwrap();
+        assert_eq!(
+            json,
+            r#"{"rules":["resource.attr(\"temp_class\") == task.attr(\"temp_class\")"]}"#
+        );
+        assert_eq!(serde_json::from_str::<Compatibility>(&json).unwrap(), rules);
+        assert!(serde_json::from_str::<Compatibility>(r#"{"rules":["task.attr("]}"#).is_err());
+    }
+}
@@ -6,6 +6,7 @@ use serde::{Deserialize, Serialize};
 
 /// Constraint types for scheduling
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum Constraint {
     /// Activity A must finish before Activity B starts
     Precedence {
@@ -37,6 +38,82 @@ pub enum Constraint {
     },
     /// Synchronization - activities must start together
     Synchronize { activity_ids: Vec<String> },
+    /// Total simultaneous demand of a quantity across all resources
+    /// (power, staffed headcount, bandwidth) must stay below a cap
+    PeakLoad { quantity: String, max_load: f64 },
+    /// Assigned hours across a group of resources may differ by at most
+    /// `max_deviation_ms` between the most and least loaded member
+    Fairness {
+        resource_ids: Vec<String>,
+        max_deviation_ms: i64,
+    },
+    /// Pickup must precede delivery on the same resource (vehicle)
+    PickupDelivery { pickup: String, delivery: String },
+    /// Start of `to` must coincide with the end of `from` within a
+    /// tolerance (patient transfer, cross-docking)
+    Handoff {
+        from: String,
+        to: String,
+        tolerance_ms: i64,
+    },
+    /// `after` must start within `max_delay_ms` of `before` ending
+    /// (bake within 30 min of coating, anesthesia soon after prep)
+    MaxDelay {
+        before: String,
+        after: String,
+        max_delay_ms: i64,
+    },
+    /// `after` may start before `before` ends (transfer batches, lapped
+    /// construction work) but never finishes before it
+    Overlap {
+        before: String,
+        after: String,
+        allowance: OverlapAllowance,
+    },
+    /// Activities of a named group never run at the same time, whatever
+    /// resources they use (shared utility, safety zone, license seat)
+    MutualExclusion {
+        group: String,
+        activity_ids: Vec<String>,
+    },
+}
+
+/// How far a successor may overlap its predecessor
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum OverlapAllowance {
+    /// Successor may start once this share of the predecessor is done (0-1)
+    Fraction(f64),
+    /// Successor may start this long before the predecessor ends (ms)
+    LeadMs(i64),
+}
+
+impl OverlapAllowance {
+    /// Time the successor may start before a predecessor of
+    /// `predecessor_ms` length ends
+    pub fn lead_ms(&self, predecessor_ms: i64) -> i64 {
+        let predecessor_ms = predecessor_ms.max(0);
+        match self {
+            OverlapAllowance::Fraction(f) => {
+                let done = (f.clamp(0.0, 1.0) * predecessor_ms as f64).ceil() as i64;
+                predecessor_ms - done
+            }
+            OverlapAllowance::LeadMs(lead) => (*lead).clamp(0, predecessor_ms),
+        }
+    }
+
+    /// Earliest start of a successor lasting `successor_ms` behind a
+    /// predecessor running [`predecessor_start`, `predecessor_end`)
+    pub fn earliest_start(
+        &self,
+        predecessor_start: i64,
+        predecessor_end: i64,
+        successor_ms: i64,
+    ) -> i64 {
+        let lead = self.lead_ms(predecessor_end - predecessor_start);
+        (predecessor_end - lead).max(predecessor_end - successor_ms)
+    }
 }
 
 impl Constraint {
@@ -83,6 +160,65 @@ impl Constraint {
         }
     }
 
+    /// Create peak-load constraint
+    pub fn peak_load(quantity: &str, max_load: f64) -> Self {
+        Constraint::PeakLoad {
+            quantity: quantity.to_string(),
+            max_load,
+        }
+    }
+
+    /// Create workload-fairness constraint
+    pub fn fairness(resource_ids: Vec<String>, max_deviation_ms: i64) -> Self {
+        Constraint::Fairness {
+            resource_ids,
+            max_deviation_ms,
+        }
+    }
+
+    /// Create pickup-delivery pairing
+    pub fn pickup_delivery(pickup: &str, delivery: &str) -> Self {
+        Constraint::PickupDelivery {
+            pickup: pickup.to_string(),
+            delivery: delivery.to_string(),
+        }
+    }
+
+    /// Create handoff synchronization
+    pub fn handoff(from: &str, to: &str, tolerance_ms: i64) -> Self {
+        Constraint::Handoff {
+            from: from.to_string(),
+            to: to.to_string(),
+            tolerance_ms,
+        }
+    }
+
+    /// Create maximum time lag between an activity and its successor
+    pub fn max_delay(before: &str, after: &str, max_delay_ms: i64) -> Self {
+        Constraint::MaxDelay {
+            before: before.to_string(),
+            after: after.to_string(),
+            max_delay_ms,
+        }
+    }
+
+    /// Create overlap allowance between consecutive activities
+    pub fn overlap(before: &str, after: &str, allowance: OverlapAllowance) -> Self {
+        Constraint::Overlap {
+            before: before.to_string(),
+            after: after.to_string(),
+            allowance,
+        }
+    }
+
+    /// Create mutual-exclusion group
+    pub fn mutual_exclusion(group: &str, activity_ids: Vec<String>) -> Self {
+        Constraint::MutualExclusion {
+            group: group.to_string(),
+            activity_ids,
+        }
+    }
+
     /// Create transition cost
     pub fn transition_cost(from: &str, to: &str, cost_ms: i64) -> Self {
         Constraint::TransitionCost {
@@ -95,6 +231,7 @@ impl Constraint {
 
 /// Transition matrix for sequence-dependent setup times
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct TransitionMatrix {
     /// Matrix name
     pub name: String,
@@ -140,6 +277,7 @@ impl TransitionMatrix {
 
 /// Collection of transition matrices
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct TransitionMatrixCollection {
     pub matrices: Vec<TransitionMatrix>,
 }
@@ -191,6 +329,21 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_overlap_allowance() {
+        // Successor may start at 60% of a 1000 ms predecessor
+        let fraction = OverlapAllowance::Fraction(0.6);
+        assert_eq!(fraction.lead_ms(1000), 400);
+        assert_eq!(fraction.earliest_start(0, 1000, 1000), 600);
+        // A short successor still may not finish before its predecessor
+        assert_eq!(fraction.earliest_start(0, 1000, 100), 900);
+
+        let lead = OverlapAllowance::LeadMs(5000);
+        assert_eq!(lead.lead_ms(1000), 1000);
+        assert_eq!(lead.earliest_start(0, 1000, 2000), 0);
+        assert_eq!(OverlapAllowance::Fraction(1.0).lead_ms(1000), 0);
+    }
+
     #[test]
     fn test_transition_matrix() {
         let mut matrix = TransitionMatrix::new("setup", "M1").with_default(1000);
@@ -0,0 +1,101 @@
+//! Disruption - Unplanned events affecting an existing schedule
+//!
+//! Describes real-world events (breakdowns, outages) that invalidate
+//! parts of a published plan and must be repaired.
+
+use serde::{Deserialize, Serialize};
+
+/// Unplanned resource outage
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResourceOutage {
+    /// Affected resource ID
+    pub resource_id: String,
+    /// Outage start (epoch ms)
+    pub start_ms: i64,
+    /// Outage duration (ms)
+    pub duration_ms: i64,
+}
+
+impl ResourceOutage {
+    /// Create new outage
+    pub fn new(resource_id: &str, start_ms: i64, duration_ms: i64) -> Self {
+        Self {
+            resource_id: resource_id.to_string(),
+            start_ms,
+            duration_ms,
+        }
+    }
+
+    /// Outage end (epoch ms)
+    pub fn end_ms(&self) -> i64 {
+        self.start_ms + self.duration_ms
+    }
+
+    /// Check if the outage overlaps an interval
+    pub fn overlaps(&self, start_ms: i64, end_ms: i64) -> bool {
+        start_ms < self.end_ms() && end_ms > self.start_ms
+    }
+}
+
+/// Disruption event types
+///
+/// Domain mappings:
+/// - Manufacturing: Machine breakdown, unplanned maintenance, operator sick leave
+/// - Healthcare: Equipment failure, room closure, nurse absence
+/// - Logistics: Vehicle breakdown
+/// - Cloud: Node failure
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum Disruption {
+    /// Resource breaks down and cannot process work during the outage
+    Breakdown(ResourceOutage),
+    /// Human resource is absent (sickness, leave) for a shift
+    Absence(ResourceOutage),
+}
+
+impl Disruption {
+    /// Create breakdown disruption
+    pub fn breakdown(resource_id: &str, start_ms: i64, duration_ms: i64) -> Self {
+        Disruption::Breakdown(ResourceOutage::new(resource_id, start_ms, duration_ms))
+    }
+
+    /// Create absence disruption
+    pub fn absence(resource_id: &str, start_ms: i64, duration_ms: i64) -> Self {
+        Disruption::Absence(ResourceOutage::new(resource_id, start_ms, duration_ms))
+    }
+
+    /// Underlying outage window
+    pub fn outage(&self) -> &ResourceOutage {
+        match self {
+            Disruption::Breakdown(outage) | Disruption::Absence(outage) => outage,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_outage_window() {
+        let outage = ResourceOutage::new("M1", 1000, 500);
+
+        assert_eq!(outage.end_ms(), 1500);
+        assert!(outage.overlaps(0, 1200));
+        assert!(outage.overlaps(1400, 2000));
+        assert!(!outage.overlaps(0, 1000));
+        assert!(!outage.overlaps(1500, 2000));
+    }
+
+    #[test]
+    fn test_breakdown() {
+        let disruption = Disruption::breakdown("M1", 1000, 500);
+        assert_eq!(disruption.outage().resource_id, "M1");
+    }
+
+    #[test]
+    fn test_absence() {
+        let disruption = Disruption::absence("W1", 0, 8 * 3600 * 1000);
+        assert!(matches!(disruption, Disruption::Absence(_)));
+        assert_eq!(disruption.outage().end_ms(), 8 * 3600 * 1000);
+    }
+}
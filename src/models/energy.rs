@@ -0,0 +1,211 @@
+//! Energy - Time-of-use tariffs and energy cost
+//!
+//! Prices energy consumption over time so schedules can be evaluated
+//! (and shifted) by cost rather than duration alone.
+
+use super::schedule::Schedule;
+use super::task::Task;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Milliseconds per hour
+const MS_PER_HOUR: f64 = 3_600_000.0;
+
+/// Price period within a tariff
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TariffPeriod {
+    /// Period start (epoch ms)
+    pub start_ms: i64,
+    /// Period end (epoch ms)
+    pub end_ms: i64,
+    /// Price per kWh during the period
+    pub price_per_kwh: f64,
+}
+
+/// Time-of-use energy tariff
+///
+/// Periods should not overlap; time outside all periods is billed at
+/// the default price.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EnergyTariff {
+    /// Priced periods
+    pub periods: Vec<TariffPeriod>,
+    /// Price per kWh outside any period
+    pub default_price_per_kwh: f64,
+}
+
+impl EnergyTariff {
+    /// Create tariff with a flat default price
+    pub fn new(default_price_per_kwh: f64) -> Self {
+        Self {
+            periods: Vec::new(),
+            default_price_per_kwh,
+        }
+    }
+
+    /// Add price period
+    pub fn with_period(mut self, start_ms: i64, end_ms: i64, price_per_kwh: f64) -> Self {
+        self.periods.push(TariffPeriod {
+            start_ms,
+            end_ms,
+            price_per_kwh,
+        });
+        self
+    }
+
+    /// Add a period repeating every day for `days` days from `origin_ms`
+    ///
+    /// Offsets are relative to the start of each day.
+    pub fn with_daily_period(
+        mut self,
+        origin_ms: i64,
+        start_offset_ms: i64,
+        end_offset_ms: i64,
+        price_per_kwh: f64,
+        days: i64,
+    ) -> Self {
+        const DAY_MS: i64 = 24 * 3_600_000;
+        for day in 0..days {
+            let base = origin_ms + day * DAY_MS;
+            self.periods.push(TariffPeriod {
+                start_ms: base + start_offset_ms,
+                end_ms: base + end_offset_ms,
+                price_per_kwh,
+            });
+        }
+        self
+    }
+
+    /// Price per kWh at a point in time
+    pub fn price_at(&self, timestamp_ms: i64) -> f64 {
+        self.periods
+            .iter()
+            .find(|p| timestamp_ms >= p.start_ms && timestamp_ms < p.end_ms)
+            .map(|p| p.price_per_kwh)
+            .unwrap_or(self.default_price_per_kwh)
+    }
+
+    /// Cost of drawing constant power over an interval
+    pub fn cost(&self, start_ms: i64, end_ms: i64, power_kw: f64) -> f64 {
+        if end_ms <= start_ms || power_kw <= 0.0 {
+            return 0.0;
+        }
+
+        let mut covered_ms = 0i64;
+        let mut cost = 0.0;
+        for period in &self.periods {
+            let overlap_start = period.start_ms.max(start_ms);
+            let overlap_end = period.end_ms.min(end_ms);
+            if overlap_end > overlap_start {
+                let ms = overlap_end - overlap_start;
+                covered_ms += ms;
+                cost += power_kw * (ms as f64 / MS_PER_HOUR) * period.price_per_kwh;
+            }
+        }
+
+        let uncovered_ms = (end_ms - start_ms) - covered_ms;
+        cost + power_kw * (uncovered_ms.max(0) as f64 / MS_PER_HOUR) * self.default_price_per_kwh
+    }
+
+    /// Cheapest start in `[earliest_ms, latest_ms]` for a job of given
+    /// duration and power. Ties resolve to the earliest start.
+    pub fn cheapest_start(
+        &self,
+        earliest_ms: i64,
+        latest_ms: i64,
+        duration_ms: i64,
+        power_kw: f64,
+    ) -> i64 {
+        if latest_ms <= earliest_ms || power_kw <= 0.0 {
+            return earliest_ms;
+        }
+
+        // Optimal starts align with a period boundary (start or end)
+        let mut candidates = vec![earliest_ms, latest_ms];
+        for period in &self.periods {
+            for boundary in [period.start_ms, period.end_ms] {
+                for start in [boundary, boundary - duration_ms] {
+                    if start > earliest_ms && start < latest_ms {
+                        candidates.push(start);
+                    }
+                }
+            }
+        }
+        candidates.sort();
+
+        let mut best = earliest_ms;
+        let mut best_cost = self.cost(earliest_ms, earliest_ms + duration_ms, power_kw);
+        for start in candidates {
+            let cost = self.cost(start, start + duration_ms, power_kw);
+            if cost < best_cost - 1e-9 {
+                best = start;
+                best_cost = cost;
+            }
+        }
+        best
+    }
+
+    /// Total energy cost of a schedule
+    ///
+    /// Each assignment draws its activity's `energy_kw` for its full duration.
+    pub fn schedule_cost(&self, schedule: &Schedule, tasks: &[Task]) -> f64 {
+        let demands: HashMap<&str, f64> = tasks
+            .iter()
+            .flat_map(|t| t.activities.iter())
+            .map(|a| (a.id.as_str(), a.energy_kw))
+            .collect();
+
+        schedule
+            .assignments
+            .iter()
+            .map(|a| {
+                let power = demands.get(a.activity_id.as_str()).copied().unwrap_or(0.0);
+                self.cost(a.start_ms, a.end_ms, power)
+            })
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HOUR: i64 = 3_600_000;
+
+    #[test]
+    fn test_cost_across_periods() {
+        let tariff = EnergyTariff::new(0.10).with_period(0, 2 * HOUR, 0.30);
+
+        // 1h peak + 1h off-peak at 10 kW
+        let cost = tariff.cost(HOUR, 3 * HOUR, 10.0);
+        assert!((cost - (3.0 + 1.0)).abs() < 1e-9);
+        assert_eq!(tariff.price_at(HOUR), 0.30);
+        assert_eq!(tariff.price_at(5 * HOUR), 0.10);
+    }
+
+    #[test]
+    fn test_cheapest_start() {
+        let tariff = EnergyTariff::new(0.10).with_period(0, 4 * HOUR, 0.50);
+
+        // Waiting until the peak ends is cheapest
+        let start = tariff.cheapest_start(0, 10 * HOUR, HOUR, 5.0);
+        assert_eq!(start, 4 * HOUR);
+
+        // Limited delay: best we can do is minimize peak overlap
+        let start = tariff.cheapest_start(0, 3 * HOUR + HOUR / 2, HOUR, 5.0);
+        assert_eq!(start, 3 * HOUR + HOUR / 2);
+
+        // No cheaper option within reach: stay at the earliest start
+        let start = tariff.cheapest_start(0, 2 * HOUR, HOUR, 5.0);
+        assert_eq!(start, 0);
+    }
+
+    #[test]
+    fn test_daily_period() {
+        let tariff = EnergyTariff::new(0.1).with_daily_period(0, 8 * HOUR, 20 * HOUR, 0.4, 2);
+
+        assert_eq!(tariff.periods.len(), 2);
+        assert_eq!(tariff.price_at(24 * HOUR + 9 * HOUR), 0.4);
+        assert_eq!(tariff.price_at(24 * HOUR + 21 * HOUR), 0.1);
+    }
+}
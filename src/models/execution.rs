@@ -0,0 +1,130 @@
+//! Execution - Actual execution of a plan
+//!
+//! Records when and where activities actually ran, for comparison with
+//! the planned schedule (see `ScheduleAdherence`).
+
+use super::baseline::ActivityProgress;
+use serde::{Deserialize, Serialize};
+
+/// Actual execution of one activity
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExecutionRecord {
+    /// Activity ID
+    pub activity_id: String,
+    /// Task ID
+    pub task_id: String,
+    /// Resource the activity actually ran on
+    pub resource_id: String,
+    /// Actual start (epoch ms)
+    pub actual_start_ms: i64,
+    /// Actual end (None while running)
+    pub actual_end_ms: Option<i64>,
+}
+
+impl ExecutionRecord {
+    /// Create record of a started activity
+    pub fn new(activity_id: &str, task_id: &str, resource_id: &str, actual_start_ms: i64) -> Self {
+        Self {
+            activity_id: activity_id.to_string(),
+            task_id: task_id.to_string(),
+            resource_id: resource_id.to_string(),
+            actual_start_ms,
+            actual_end_ms: None,
+        }
+    }
+
+    /// Set actual end
+    pub fn with_end(mut self, actual_end_ms: i64) -> Self {
+        self.actual_end_ms = Some(actual_end_ms);
+        self
+    }
+
+    /// Whether the activity has finished
+    pub fn is_finished(&self) -> bool {
+        self.actual_end_ms.is_some()
+    }
+
+    /// Actual duration of a finished activity
+    pub fn duration_ms(&self) -> Option<i64> {
+        self.actual_end_ms.map(|end| end - self.actual_start_ms)
+    }
+}
+
+/// Log of actual activity executions
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ExecutionLog {
+    /// Records (one per activity)
+    pub records: Vec<ExecutionRecord>,
+}
+
+impl ExecutionLog {
+    /// Create empty log
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add record (replaces an earlier record of the same activity)
+    pub fn with_record(mut self, record: ExecutionRecord) -> Self {
+        self.record(record);
+        self
+    }
+
+    /// Add record (replaces an earlier record of the same activity)
+    pub fn record(&mut self, record: ExecutionRecord) {
+        self.records.retain(|r| r.activity_id != record.activity_id);
+        self.records.push(record);
+    }
+
+    /// Record the end of a started activity (false if it was not started)
+    pub fn finish(&mut self, activity_id: &str, actual_end_ms: i64) -> bool {
+        match self
+            .records
+            .iter_mut()
+            .find(|r| r.activity_id == activity_id)
+        {
+            Some(record) => {
+                record.actual_end_ms = Some(actual_end_ms);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Record of an activity
+    pub fn get(&self, activity_id: &str) -> Option<&ExecutionRecord> {
+        self.records.iter().find(|r| r.activity_id == activity_id)
+    }
+
+    /// Activities still running
+    pub fn running(&self) -> impl Iterator<Item = &ExecutionRecord> {
+        self.records.iter().filter(|r| !r.is_finished())
+    }
+
+    /// Progress reports of finished activities (for a replan baseline)
+    pub fn completed_progress(&self) -> Vec<ActivityProgress> {
+        self.records
+            .iter()
+            .filter(|r| r.is_finished())
+            .map(|r| ActivityProgress::new(&r.activity_id, &r.resource_id, r.actual_start_ms, 1.0))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_execution_log() {
+        let mut log = ExecutionLog::new()
+            .with_record(ExecutionRecord::new("A1", "T1", "M1", 100).with_end(1300))
+            .with_record(ExecutionRecord::new("A2", "T1", "M2", 1300));
+
+        assert_eq!(log.get("A1").unwrap().duration_ms(), Some(1200));
+        assert_eq!(log.running().count(), 1);
+        assert!(log.finish("A2", 2000));
+        assert!(!log.finish("A3", 2000));
+        assert_eq!(log.running().count(), 0);
+        assert_eq!(log.completed_progress().len(), 2);
+    }
+}
@@ -0,0 +1,301 @@
+//! Labor - Working-time rules for human resources
+//!
+//! Limits how much and how often a person may be assigned work
+//! (daily/weekly hours, rest between shifts, consecutive working days).
+
+use super::schedule::{Assignment, Violation};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Milliseconds per day
+const DAY_MS: i64 = 24 * 3_600_000;
+
+/// Search limit for `earliest_start`
+const MAX_SEARCH_STEPS: usize = 1000;
+
+/// Working-time rules (all limits optional)
+///
+/// Days are counted in UTC from the epoch; weeks are consecutive
+/// 7-day blocks from the epoch.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct WorkingTimeRules {
+    /// Maximum assigned time per day (ms)
+    pub max_daily_ms: Option<i64>,
+    /// Maximum assigned time per week (ms)
+    pub max_weekly_ms: Option<i64>,
+    /// Minimum rest between two assignments (ms)
+    pub min_rest_ms: Option<i64>,
+    /// Maximum consecutive days with any assigned work
+    pub max_consecutive_days: Option<u32>,
+}
+
+impl WorkingTimeRules {
+    /// Create rules without limits
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set maximum daily working time
+    pub fn with_max_daily(mut self, max_ms: i64) -> Self {
+        self.max_daily_ms = Some(max_ms);
+        self
+    }
+
+    /// Set maximum weekly working time
+    pub fn with_max_weekly(mut self, max_ms: i64) -> Self {
+        self.max_weekly_ms = Some(max_ms);
+        self
+    }
+
+    /// Set minimum rest between assignments
+    pub fn with_min_rest(mut self, rest_ms: i64) -> Self {
+        self.min_rest_ms = Some(rest_ms);
+        self
+    }
+
+    /// Set maximum consecutive working days
+    pub fn with_max_consecutive_days(mut self, days: u32) -> Self {
+        self.max_consecutive_days = Some(days);
+        self
+    }
+
+    /// Check rule violations for one resource's assignments
+    pub fn violations(&self, resource_id: &str, assignments: &[&Assignment]) -> Vec<Violation> {
+        let intervals: Vec<(i64, i64)> =
+            assignments.iter().map(|a| (a.start_ms, a.end_ms)).collect();
+        self.check(&intervals)
+            .into_iter()
+            .map(|message| Violation::working_time(resource_id, &message))
+            .collect()
+    }
+
+    /// Check whether adding `[start_ms, end_ms)` keeps all rules satisfied
+    pub fn allows(&self, assignments: &[&Assignment], start_ms: i64, end_ms: i64) -> bool {
+        self.first_conflict(assignments, start_ms, end_ms).is_none()
+    }
+
+    /// Earliest start at or after `from_ms` where a job of `duration_ms`
+    /// satisfies all rules, or `None` if none is found within the search limit
+    pub fn earliest_start(
+        &self,
+        assignments: &[&Assignment],
+        from_ms: i64,
+        duration_ms: i64,
+    ) -> Option<i64> {
+        let mut start = from_ms;
+        for _ in 0..MAX_SEARCH_STEPS {
+            match self.first_conflict(assignments, start, start + duration_ms) {
+                None => return Some(start),
+                Some(next) => start = next.max(start + 1),
+            }
+        }
+        None
+    }
+
+    /// Earliest retry point if the candidate interval breaks a rule
+    fn first_conflict(
+        &self,
+        assignments: &[&Assignment],
+        start_ms: i64,
+        end_ms: i64,
+    ) -> Option<i64> {
+        let rest = self.min_rest_ms.unwrap_or(0);
+        for a in assignments {
+            // Too close to (or overlapping) an existing assignment
+            if start_ms < a.end_ms + rest && end_ms + rest > a.start_ms {
+                return Some(a.end_ms + rest);
+            }
+        }
+
+        let mut intervals: Vec<(i64, i64)> =
+            assignments.iter().map(|a| (a.start_ms, a.end_ms)).collect();
+        intervals.push((start_ms, end_ms));
+        let start_day = start_ms.div_euclid(DAY_MS);
+
+        if let Some(max) = self.max_daily_ms {
+            let over = day_loads(&intervals)
+                .into_iter()
+                .find(|&(day, load)| day >= start_day && load > max);
+            if let Some((day, _)) = over {
+                return Some((day + 1) * DAY_MS);
+            }
+        }
+
+        if let Some(max) = self.max_weekly_ms {
+            let over = week_loads(&intervals)
+                .into_iter()
+                .find(|&(week, load)| week >= start_day.div_euclid(7) && load > max);
+            if let Some((week, _)) = over {
+                return Some((week + 1) * 7 * DAY_MS);
+            }
+        }
+
+        if let Some(max) = self.max_consecutive_days {
+            let end_day = (end_ms - 1).div_euclid(DAY_MS);
+            let run = longest_run(&intervals, start_day, end_day);
+            if run > max as i64 {
+                return Some((start_day + 1) * DAY_MS);
+            }
+        }
+
+        None
+    }
+
+    /// Rule violation messages for a set of intervals
+    fn check(&self, intervals: &[(i64, i64)]) -> Vec<String> {
+        let mut messages = Vec::new();
+
+        if let Some(rest) = self.min_rest_ms {
+            let mut sorted = intervals.to_vec();
+            sorted.sort();
+            for pair in sorted.windows(2) {
+                let gap = pair[1].0 - pair[0].1;
+                if gap < rest {
+                    messages.push(format!(
+                        "Rest of {} ms at {} ms is below minimum {} ms",
+                        gap, pair[0].1, rest
+                    ));
+                }
+            }
+        }
+
+        if let Some(max) = self.max_daily_ms {
+            for (day, load) in day_loads(intervals) {
+                if load > max {
+                    messages.push(format!(
+                        "Day {} has {} ms of work (max {} ms)",
+                        day, load, max
+                    ));
+                }
+            }
+        }
+
+        if let Some(max) = self.max_weekly_ms {
+            for (week, load) in week_loads(intervals) {
+                if load > max {
+                    messages.push(format!(
+                        "Week {} has {} ms of work (max {} ms)",
+                        week, load, max
+                    ));
+                }
+            }
+        }
+
+        if let Some(max) = self.max_consecutive_days {
+            let days: Vec<i64> = day_loads(intervals).into_keys().collect();
+            let mut run = 0;
+            for (i, day) in days.iter().enumerate() {
+                run = if i > 0 && days[i - 1] + 1 == *day {
+                    run + 1
+                } else {
+                    1
+                };
+                if run == max as i64 + 1 {
+                    messages.push(format!(
+                        "More than {} consecutive working days ending on day {}",
+                        max, day
+                    ));
+                }
+            }
+        }
+
+        messages
+    }
+}
+
+/// Worked time per day index, splitting intervals at midnight
+fn day_loads(intervals: &[(i64, i64)]) -> BTreeMap<i64, i64> {
+    let mut loads = BTreeMap::new();
+    for &(start, end) in intervals {
+        let mut t = start;
+        while t < end {
+            let day = t.div_euclid(DAY_MS);
+            let boundary = ((day + 1) * DAY_MS).min(end);
+            *loads.entry(day).or_insert(0) += boundary - t;
+            t = boundary;
+        }
+    }
+    loads
+}
+
+/// Worked time per 7-day block
+fn week_loads(intervals: &[(i64, i64)]) -> BTreeMap<i64, i64> {
+    let mut loads = BTreeMap::new();
+    for (day, load) in day_loads(intervals) {
+        *loads.entry(day.div_euclid(7)).or_insert(0) += load;
+    }
+    loads
+}
+
+/// Length of the run of consecutive worked days containing `[first, last]`
+fn longest_run(intervals: &[(i64, i64)], first: i64, last: i64) -> i64 {
+    let days = day_loads(intervals);
+    let mut lo = first;
+    while days.contains_key(&(lo - 1)) {
+        lo -= 1;
+    }
+    let mut hi = last;
+    while days.contains_key(&(hi + 1)) {
+        hi += 1;
+    }
+    hi - lo + 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::schedule::ViolationType;
+
+    const HOUR: i64 = 3_600_000;
+
+    #[test]
+    fn test_min_rest() {
+        let rules = WorkingTimeRules::new().with_min_rest(11 * HOUR);
+        let shift = Assignment::new("A1", "T1", "N1", 6 * HOUR, 14 * HOUR);
+
+        assert!(!rules.allows(&[&shift], 20 * HOUR, 28 * HOUR));
+        assert_eq!(
+            rules.earliest_start(&[&shift], 20 * HOUR, 8 * HOUR),
+            Some(25 * HOUR)
+        );
+    }
+
+    #[test]
+    fn test_max_daily() {
+        let rules = WorkingTimeRules::new().with_max_daily(8 * HOUR);
+        let morning = Assignment::new("A1", "T1", "N1", 6 * HOUR, 12 * HOUR);
+
+        // 6h + 4h would exceed 8h today: pushed to tomorrow
+        assert_eq!(
+            rules.earliest_start(&[&morning], 12 * HOUR, 4 * HOUR),
+            Some(24 * HOUR)
+        );
+        assert_eq!(rules.violations("N1", &[&morning]).len(), 0);
+    }
+
+    #[test]
+    fn test_max_consecutive_days() {
+        let rules = WorkingTimeRules::new().with_max_consecutive_days(2);
+        let day0 = Assignment::new("A1", "T1", "N1", 8 * HOUR, 16 * HOUR);
+        let day1 = Assignment::new("A2", "T2", "N1", 32 * HOUR, 40 * HOUR);
+        let day2 = Assignment::new("A3", "T3", "N1", 56 * HOUR, 64 * HOUR);
+
+        assert_eq!(rules.violations("N1", &[&day0, &day1, &day2]).len(), 1);
+        assert_eq!(
+            rules.earliest_start(&[&day0, &day1], 56 * HOUR, 8 * HOUR),
+            Some(72 * HOUR)
+        );
+    }
+
+    #[test]
+    fn test_max_weekly() {
+        let rules = WorkingTimeRules::new().with_max_weekly(10 * HOUR);
+        let a = Assignment::new("A1", "T1", "N1", 0, 8 * HOUR);
+        let b = Assignment::new("A2", "T2", "N1", 24 * HOUR, 28 * HOUR);
+
+        let violations = rules.violations("N1", &[&a, &b]);
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].violation_type, ViolationType::WorkingTime);
+    }
+}
@@ -0,0 +1,474 @@
+//! Local Calendar - Wall-clock working hours in a time zone
+//!
+//! [`Calendar`] windows are raw epoch intervals, so a fixed "09:00-17:00"
+//! pattern drifts by an hour across daylight saving changes.
+//! [`LocalCalendar`] keeps recurring shifts in local time and expands them
+//! into correct UTC windows over a horizon.
+//!
+//! Shifts can also be written as compact recurrence specs, either
+//! `"Mon-Fri 08:00-12:00,13:00-17:00; Sat 08:00-12:00"` or an RRULE subset
+//! such as `"RRULE:FREQ=WEEKLY;BYDAY=MO,WE;BYHOUR=22;DURATION=PT8H"`.
+
+use super::calendar::Calendar;
+use chrono::{
+    Datelike, Duration, LocalResult, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Weekday,
+};
+use std::fmt;
+
+/// Recurring shift in local time
+#[derive(Debug, Clone, PartialEq)]
+pub struct LocalShift {
+    /// Days on which the shift starts
+    pub days: Vec<Weekday>,
+    /// Local start time
+    pub start: NaiveTime,
+    /// Local end time (at or before `start`: ends the next day)
+    pub end: NaiveTime,
+}
+
+/// Recurrence spec that could not be parsed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecurrenceParseError {
+    /// Offending part of the spec
+    pub token: String,
+    /// What was wrong
+    pub message: String,
+}
+
+impl fmt::Display for RecurrenceParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} in '{}'", self.message, self.token)
+    }
+}
+
+impl std::error::Error for RecurrenceParseError {}
+
+impl LocalShift {
+    /// Parse a recurrence spec into shifts
+    ///
+    /// Each line is either an RRULE (`FREQ=DAILY|WEEKLY` with optional
+    /// `BYDAY`, `BYHOUR`, `BYMINUTE` and a required `DURATION=PT#H#M`) or
+    /// `;`-separated groups of days (`Mon-Fri`, `Sat,Sun`, `Daily`) and
+    /// `HH:MM-HH:MM` ranges. Ranges ending at or before their start run
+    /// overnight; `24:00` ends at midnight.
+    pub fn parse_spec(spec: &str) -> Result<Vec<LocalShift>, RecurrenceParseError> {
+        let mut shifts = Vec::new();
+        for line in spec.lines().map(str::trim).filter(|l| !l.is_empty()) {
+            let upper = line.to_ascii_uppercase();
+            if upper.starts_with("RRULE:") || upper.starts_with("FREQ=") {
+                shifts.extend(parse_rrule(line)?);
+                continue;
+            }
+            for group in line.split(';').map(str::trim).filter(|g| !g.is_empty()) {
+                let (days, ranges) = group
+                    .split_once(char::is_whitespace)
+                    .ok_or_else(|| error(group, "expected days followed by time ranges"))?;
+                let days = parse_days(days)?;
+                for range in ranges.split(',').map(str::trim) {
+                    let (start, end) = range
+                        .split_once('-')
+                        .ok_or_else(|| error(range, "expected HH:MM-HH:MM"))?;
+                    shifts.push(LocalShift {
+                        days: days.clone(),
+                        start: parse_time(start)?,
+                        end: parse_time(end)?,
+                    });
+                }
+            }
+        }
+        Ok(shifts)
+    }
+}
+
+fn error(token: &str, message: &str) -> RecurrenceParseError {
+    RecurrenceParseError {
+        token: token.to_string(),
+        message: message.to_string(),
+    }
+}
+
+/// `Mon-Fri,Sun`, `Daily` or `*`
+fn parse_days(text: &str) -> Result<Vec<Weekday>, RecurrenceParseError> {
+    let day = |s: &str| {
+        s.trim()
+            .parse::<Weekday>()
+            .map_err(|_| error(s, "unknown weekday"))
+    };
+    if text.eq_ignore_ascii_case("daily") || text == "*" {
+        return Ok(all_days());
+    }
+    let mut days = Vec::new();
+    for item in text.split(',') {
+        match item.split_once('-') {
+            Some((from, to)) => {
+                let (mut d, to) = (day(from)?, day(to)?);
+                days.push(d);
+                while d != to {
+                    d = d.succ();
+                    days.push(d);
+                }
+            }
+            None => days.push(day(item)?),
+        }
+    }
+    Ok(days)
+}
+
+fn all_days() -> Vec<Weekday> {
+    (0..7).filter_map(|n| Weekday::try_from(n).ok()).collect()
+}
+
+/// `HH:MM`, with `24:00` as midnight
+fn parse_time(text: &str) -> Result<NaiveTime, RecurrenceParseError> {
+    let text = text.trim();
+    if text == "24:00" {
+        return Ok(NaiveTime::MIN);
+    }
+    NaiveTime::parse_from_str(text, "%H:%M").map_err(|_| error(text, "expected HH:MM"))
+}
+
+/// RRULE subset describing shift starts, plus the shift `DURATION`
+fn parse_rrule(line: &str) -> Result<Vec<LocalShift>, RecurrenceParseError> {
+    let body = line
+        .get(..6)
+        .filter(|p| p.eq_ignore_ascii_case("RRULE:"))
+        .map_or(line, |_| &line[6..]);
+    let (mut weekly, mut days, mut hours, mut minute, mut duration) =
+        (None, None, vec![0], 0, None);
+    for part in body.split(';').map(str::trim).filter(|p| !p.is_empty()) {
+        let (key, value) = part
+            .split_once('=')
+            .ok_or_else(|| error(part, "expected KEY=VALUE"))?;
+        let number = |v: &str, max: u32| {
+            v.parse::<u32>()
+                .ok()
+                .filter(|&n| n < max)
+                .ok_or_else(|| error(part, "invalid number"))
+        };
+        match key.to_ascii_uppercase().as_str() {
+            "FREQ" => {
+                weekly = Some(match value.to_ascii_uppercase().as_str() {
+                    "DAILY" => false,
+                    "WEEKLY" => true,
+                    _ => return Err(error(part, "only DAILY and WEEKLY are supported")),
+                })
+            }
+            "INTERVAL" if value == "1" => {}
+            "BYDAY" => {
+                days = Some(
+                    value
+                        .split(',')
+                        .map(|d| rrule_day(d).ok_or_else(|| error(d, "unknown weekday")))
+                        .collect::<Result<Vec<_>, _>>()?,
+                )
+            }
+            "BYHOUR" => {
+                hours = value
+                    .split(',')
+                    .map(|h| number(h, 24))
+                    .collect::<Result<_, _>>()?
+            }
+            "BYMINUTE" => minute = number(value, 60)?,
+            "DURATION" => {
+                duration =
+                    Some(parse_duration(value).ok_or_else(|| error(part, "expected PT#H#M"))?)
+            }
+            _ => return Err(error(part, "unsupported RRULE part")),
+        }
+    }
+    let weekly = weekly.ok_or_else(|| error(line, "missing FREQ"))?;
+    let duration = duration
+        .filter(|d| *d > Duration::zero() && *d <= Duration::days(1))
+        .ok_or_else(|| error(line, "missing DURATION of up to a day"))?;
+    let days = match days {
+        Some(days) => days,
+        None if weekly => return Err(error(line, "weekly rules need BYDAY")),
+        None => all_days(),
+    };
+    Ok(hours
+        .into_iter()
+        .filter_map(|h| NaiveTime::from_hms_opt(h, minute, 0))
+        .map(|start| LocalShift {
+            days: days.clone(),
+            start,
+            end: start + duration,
+        })
+        .collect())
+}
+
+/// Two-letter RRULE weekday (`MO`..`SU`)
+fn rrule_day(code: &str) -> Option<Weekday> {
+    let codes = ["MO", "TU", "WE", "TH", "FR", "SA", "SU"];
+    let index = codes
+        .iter()
+        .position(|c| c.eq_ignore_ascii_case(code.trim()))?;
+    Weekday::try_from(index as u8).ok()
+}
+
+/// ISO 8601 `PT#H#M` duration
+fn parse_duration(text: &str) -> Option<Duration> {
+    let rest = text.strip_prefix("PT")?;
+    let (mut total, mut number) = (Duration::zero(), String::new());
+    for c in rest.chars() {
+        match c {
+            '0'..='9' => number.push(c),
+            'H' | 'M' => {
+                let n: i64 = number.parse().ok()?;
+                number.clear();
+                let part = if c == 'H' {
+                    Duration::try_hours(n)?
+                } else {
+                    Duration::try_minutes(n)?
+                };
+                total = total.checked_add(&part)?;
+            }
+            _ => return None,
+        }
+    }
+    number.is_empty().then_some(total)
+}
+
+/// Weekly working pattern in a time zone
+///
+/// Works with any chrono [`TimeZone`]; with the `timezone` feature,
+/// [`LocalCalendar::in_zone`] accepts IANA names such as
+/// `"Europe/Berlin"`.
+#[derive(Debug, Clone)]
+pub struct LocalCalendar<Tz: TimeZone> {
+    /// Calendar identifier
+    pub id: String,
+    /// Time zone the shifts are written in
+    pub tz: Tz,
+    /// Recurring shifts
+    pub shifts: Vec<LocalShift>,
+    /// Whole local days closed (holidays)
+    pub closed_days: Vec<NaiveDate>,
+}
+
+impl<Tz: TimeZone> LocalCalendar<Tz> {
+    /// Create empty calendar in a time zone
+    pub fn new(id: &str, tz: Tz) -> Self {
+        Self {
+            id: id.to_string(),
+            tz,
+            shifts: Vec::new(),
+            closed_days: Vec::new(),
+        }
+    }
+
+    /// Add a shift on the given weekdays
+    pub fn with_shift(mut self, days: &[Weekday], start: NaiveTime, end: NaiveTime) -> Self {
+        self.add_shift(days, start, end);
+        self
+    }
+
+    /// Add a shift on the given weekdays
+    pub fn add_shift(&mut self, days: &[Weekday], start: NaiveTime, end: NaiveTime) {
+        self.shifts.push(LocalShift {
+            days: days.to_vec(),
+            start,
+            end,
+        });
+    }
+
+    /// Add shifts from a recurrence spec (see [`LocalShift::parse_spec`])
+    pub fn with_spec(mut self, spec: &str) -> Result<Self, RecurrenceParseError> {
+        self.shifts.extend(LocalShift::parse_spec(spec)?);
+        Ok(self)
+    }
+
+    /// Close a whole local day
+    pub fn with_closed_day(mut self, date: NaiveDate) -> Self {
+        self.add_closed_day(date);
+        self
+    }
+
+    /// Close a whole local day
+    pub fn add_closed_day(&mut self, date: NaiveDate) {
+        self.closed_days.push(date);
+    }
+
+    /// Expand into UTC windows covering `[from_ms, to_ms)`
+    ///
+    /// Only the requested horizon is expanded; call again for a later one.
+    /// Local times skipped by a clock change resolve to the instant the
+    /// clocks jump to; repeated local times resolve to their first
+    /// occurrence. Closed days become holidays.
+    pub fn to_calendar(&self, from_ms: i64, to_ms: i64) -> Calendar {
+        let mut calendar = Calendar::new(&self.id);
+        let (Some(first), Some(last)) = (self.local_date(from_ms), self.local_date(to_ms)) else {
+            return calendar;
+        };
+
+        // A day early for overnight shifts reaching into the horizon
+        let mut date = first - Duration::days(1);
+        while date <= last {
+            for shift in self
+                .shifts
+                .iter()
+                .filter(|s| s.days.contains(&date.weekday()))
+            {
+                let end_date = if shift.end > shift.start {
+                    date
+                } else {
+                    date + Duration::days(1)
+                };
+                let (Some(start), Some(end)) = (
+                    self.to_utc_ms(date.and_time(shift.start)),
+                    self.to_utc_ms(end_date.and_time(shift.end)),
+                ) else {
+                    continue;
+                };
+                let (start, end) = (start.max(from_ms), end.min(to_ms));
+                if start < end {
+                    calendar.add_window(start, end);
+                }
+            }
+            date += Duration::days(1);
+        }
+
+        for day in &self.closed_days {
+            if let (Some(start), Some(end)) = (
+                self.to_utc_ms(day.and_time(NaiveTime::MIN)),
+                self.to_utc_ms((*day + Duration::days(1)).and_time(NaiveTime::MIN)),
+            ) {
+                calendar.add_holiday(&day.to_string(), start, end);
+            }
+        }
+        calendar
+    }
+
+    /// Local calendar date at an instant
+    fn local_date(&self, timestamp_ms: i64) -> Option<NaiveDate> {
+        self.tz
+            .timestamp_millis_opt(timestamp_ms)
+            .single()
+            .map(|t| t.date_naive())
+    }
+
+    /// Instant of a local time (see `to_calendar` for gaps and overlaps)
+    fn to_utc_ms(&self, local: NaiveDateTime) -> Option<i64> {
+        // Clock changes skip at most a few hours
+        (0..=24 * 60).find_map(|minutes| {
+            match self
+                .tz
+                .from_local_datetime(&(local + Duration::minutes(minutes)))
+            {
+                LocalResult::Single(t) | LocalResult::Ambiguous(t, _) => Some(t.timestamp_millis()),
+                LocalResult::None => None,
+            }
+        })
+    }
+}
+
+#[cfg(feature = "timezone")]
+impl LocalCalendar<chrono_tz::Tz> {
+    /// Create empty calendar in an IANA time zone (`None` if unknown)
+    pub fn in_zone(id: &str, zone: &str) -> Option<Self> {
+        zone.parse().ok().map(|tz| Self::new(id, tz))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::FixedOffset;
+
+    const HOUR: i64 = 3600 * 1000;
+
+    fn time(h: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(h, 0, 0).unwrap()
+    }
+
+    fn utc_ms(y: i32, m: u32, d: u32, h: u32) -> i64 {
+        chrono::Utc
+            .with_ymd_and_hms(y, m, d, h, 0, 0)
+            .unwrap()
+            .timestamp_millis()
+    }
+
+    #[test]
+    fn test_fixed_offset() {
+        let seoul = FixedOffset::east_opt(9 * 3600).unwrap();
+        let calendar = LocalCalendar::new("office", seoul)
+            .with_shift(&[Weekday::Mon, Weekday::Tue], time(9), time(17))
+            .with_closed_day(NaiveDate::from_ymd_opt(2024, 1, 2).unwrap())
+            // 2024-01-01 is a Monday
+            .to_calendar(utc_ms(2024, 1, 1, 0), utc_ms(2024, 1, 8, 0));
+
+        assert_eq!(calendar.time_windows.len(), 2);
+        assert_eq!(calendar.time_windows[0].start_ms, utc_ms(2024, 1, 1, 0));
+        assert_eq!(calendar.time_windows[0].end_ms, utc_ms(2024, 1, 1, 8));
+        assert!(calendar.is_working_time(utc_ms(2024, 1, 1, 3)));
+        // Tuesday is closed
+        assert!(!calendar.is_working_time(utc_ms(2024, 1, 2, 3)));
+        assert_eq!(
+            calendar.working_duration_between(utc_ms(2024, 1, 1, 0), utc_ms(2024, 1, 8, 0)),
+            8 * HOUR
+        );
+    }
+
+    #[test]
+    fn test_parse_spec() {
+        let week = |calendar: &LocalCalendar<chrono::Utc>| {
+            // 2024-01-01 is a Monday
+            calendar
+                .to_calendar(utc_ms(2024, 1, 1, 0), utc_ms(2024, 1, 8, 0))
+                .working_duration_between(utc_ms(2024, 1, 1, 0), utc_ms(2024, 1, 8, 0))
+        };
+        let compact = LocalCalendar::new("plant", chrono::Utc)
+            .with_spec("Mon-Fri 08:00-12:00,13:00-17:00; Sat 08:00-12:00")
+            .unwrap();
+        assert_eq!(compact.shifts.len(), 3);
+        assert_eq!(compact.shifts[0].days.len(), 5);
+        assert_eq!(week(&compact), 44 * HOUR);
+
+        let rrule = LocalCalendar::new("night", chrono::Utc)
+            .with_spec("RRULE:FREQ=WEEKLY;BYDAY=MO,WE;BYHOUR=22;BYMINUTE=30;DURATION=PT8H")
+            .unwrap();
+        assert_eq!(
+            rrule.shifts[0].end,
+            NaiveTime::from_hms_opt(6, 30, 0).unwrap()
+        );
+        assert_eq!(week(&rrule), 16 * HOUR);
+
+        let wrapped = LocalShift::parse_spec("Sat-Mon 20:00-24:00").unwrap();
+        assert_eq!(
+            wrapped[0].days,
+            vec![Weekday::Sat, Weekday::Sun, Weekday::Mon]
+        );
+        assert_eq!(wrapped[0].end, NaiveTime::MIN);
+
+        let err = LocalShift::parse_spec("Mon-Fri 8-17").unwrap_err();
+        assert_eq!(err.token, "8");
+        assert!(LocalShift::parse_spec("Funday 08:00-17:00").is_err());
+        assert!(LocalShift::parse_spec("FREQ=MONTHLY;DURATION=PT1H").is_err());
+        assert!(LocalShift::parse_spec("FREQ=DAILY;BYHOUR=8").is_err());
+        // Out-of-range durations are rejected instead of overflowing
+        assert!(LocalShift::parse_spec("RRULE:FREQ=DAILY;DURATION=PT9999999999999999H").is_err());
+    }
+
+    #[cfg(feature = "timezone")]
+    #[test]
+    fn test_daylight_saving() {
+        use Weekday::*;
+        // Berlin moves from UTC+1 to UTC+2 on 2024-03-31 at 02:00
+        let calendar = LocalCalendar::in_zone("berlin", "Europe/Berlin")
+            .unwrap()
+            .with_shift(&[Fri, Mon], time(9), time(17))
+            .with_shift(&[Sat], time(22), time(6))
+            .to_calendar(utc_ms(2024, 3, 29, 0), utc_ms(2024, 4, 2, 0));
+
+        // 09:00 local is 08:00 UTC before the change, 07:00 after
+        assert!(calendar.is_working_time(utc_ms(2024, 3, 29, 8)));
+        assert!(!calendar.is_working_time(utc_ms(2024, 4, 1, 15)));
+        assert!(calendar.is_working_time(utc_ms(2024, 4, 1, 7)));
+        // The night shift loses the skipped hour
+        assert_eq!(
+            calendar.working_duration_between(utc_ms(2024, 3, 30, 12), utc_ms(2024, 3, 31, 12)),
+            7 * HOUR
+        );
+
+        assert!(LocalCalendar::in_zone("x", "Mars/Olympus").is_none());
+    }
+}
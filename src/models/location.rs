@@ -0,0 +1,95 @@
+//! Location - Travel times between places
+//!
+//! Activities and resources may be tied to a location; moving between
+//! locations takes time that schedulers must leave free.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Travel time matrix between locations
+///
+/// Domain mappings:
+/// - Logistics: Depot/customer driving times
+/// - Field service: Technician travel between sites
+/// - Manufacturing: Transfer time between work centers
+/// - Healthcare: Patient transport between wards
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TravelTimeMatrix {
+    /// Travel times: from -> to -> time_ms
+    pub times: HashMap<String, HashMap<String, i64>>,
+    /// Travel time for unlisted pairs of different locations
+    pub default_ms: i64,
+}
+
+impl TravelTimeMatrix {
+    /// Create empty matrix
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set directed travel time
+    pub fn set_time(&mut self, from: &str, to: &str, time_ms: i64) {
+        self.times
+            .entry(from.to_string())
+            .or_default()
+            .insert(to.to_string(), time_ms);
+    }
+
+    /// Add directed travel time
+    pub fn with_travel(mut self, from: &str, to: &str, time_ms: i64) -> Self {
+        self.set_time(from, to, time_ms);
+        self
+    }
+
+    /// Add travel time in both directions
+    pub fn with_symmetric(mut self, a: &str, b: &str, time_ms: i64) -> Self {
+        self.set_time(a, b, time_ms);
+        self.set_time(b, a, time_ms);
+        self
+    }
+
+    /// Set default travel time
+    pub fn with_default(mut self, default_ms: i64) -> Self {
+        self.default_ms = default_ms;
+        self
+    }
+
+    /// Travel time between locations (0 for the same location)
+    pub fn travel_time(&self, from: &str, to: &str) -> i64 {
+        if from == to {
+            return 0;
+        }
+        self.times
+            .get(from)
+            .and_then(|row| row.get(to))
+            .copied()
+            .unwrap_or(self.default_ms)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_travel_time() {
+        let matrix = TravelTimeMatrix::new()
+            .with_symmetric("depot", "site-a", 1800)
+            .with_travel("site-a", "site-b", 600)
+            .with_default(3600);
+
+        assert_eq!(matrix.travel_time("depot", "site-a"), 1800);
+        assert_eq!(matrix.travel_time("site-a", "depot"), 1800);
+        assert_eq!(matrix.travel_time("site-a", "site-b"), 600);
+        assert_eq!(matrix.travel_time("site-b", "site-a"), 3600);
+        assert_eq!(matrix.travel_time("site-b", "site-b"), 0);
+    }
+
+    #[test]
+    fn test_serialization() {
+        let matrix = TravelTimeMatrix::new().with_travel("A", "B", 100);
+        let json = serde_json::to_string(&matrix).unwrap();
+        let back: TravelTimeMatrix = serde_json::from_str(&json).unwrap();
+        assert_eq!(back.travel_time("A", "B"), 100);
+    }
+}
@@ -3,17 +3,49 @@
 //! Domain-agnostic abstractions for resource allocation and scheduling
 
 pub mod activity;
+pub mod baseline;
+pub mod builder;
 pub mod calendar;
+pub mod compatibility;
 pub mod constraint;
+pub mod disruption;
+pub mod energy;
+pub mod execution;
+pub mod labor;
+pub mod local_calendar;
+pub mod location;
+pub mod pool;
+pub mod preference;
+pub mod renewable;
 pub mod resource;
 pub mod schedule;
+pub mod slot;
 pub mod task;
 pub mod time_constraints;
+pub mod time_unit;
+pub mod trace;
+pub mod transition_loader;
 
 pub use activity::*;
+pub use baseline::*;
+pub use builder::*;
 pub use calendar::*;
+pub use compatibility::*;
 pub use constraint::*;
+pub use disruption::*;
+pub use energy::*;
+pub use execution::*;
+pub use labor::*;
+pub use local_calendar::*;
+pub use location::*;
+pub use pool::*;
+pub use preference::*;
+pub use renewable::*;
 pub use resource::*;
 pub use schedule::*;
+pub use slot::*;
 pub use task::*;
 pub use time_constraints::*;
+pub use time_unit::*;
+pub use trace::*;
+pub use transition_loader::*;
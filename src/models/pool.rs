@@ -0,0 +1,108 @@
+//! Pool - Elastic resource pools
+//!
+//! Resources that can be provisioned on demand (cloud VMs, temporary
+//! staff): each instance takes time to start and costs money while held.
+
+use super::resource::Resource;
+use serde::{Deserialize, Serialize};
+
+/// Pool of identical resources provisioned on demand
+///
+/// Activities reference the pool id among their candidates; schedulers
+/// replace it with the instances they decide to provision.
+///
+/// Domain mappings:
+/// - Cloud: Autoscaling VM group
+/// - Workforce: Temporary staff agency
+/// - Logistics: Rental trucks
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResourcePool {
+    /// Pool id (used as candidate in activities)
+    pub id: String,
+    /// Template every instance is cloned from
+    pub template: Resource,
+    /// Maximum instances that may be provisioned
+    pub max_instances: usize,
+    /// Delay from request until an instance is usable (ms)
+    pub startup_ms: i64,
+    /// Cost per hour an instance is held
+    pub cost_per_hour: f64,
+}
+
+impl ResourcePool {
+    /// Create pool from a template resource
+    pub fn new(id: &str, template: Resource) -> Self {
+        Self {
+            id: id.to_string(),
+            template,
+            max_instances: 1,
+            startup_ms: 0,
+            cost_per_hour: 0.0,
+        }
+    }
+
+    /// Set maximum instances
+    pub fn with_max_instances(mut self, max: usize) -> Self {
+        self.max_instances = max;
+        self
+    }
+
+    /// Set startup delay
+    pub fn with_startup(mut self, startup_ms: i64) -> Self {
+        self.startup_ms = startup_ms;
+        self
+    }
+
+    /// Set instance cost per hour
+    pub fn with_cost(mut self, cost_per_hour: f64) -> Self {
+        self.cost_per_hour = cost_per_hour;
+        self
+    }
+
+    /// Resource id of the `index`-th instance (1-based)
+    pub fn instance_id(&self, index: usize) -> String {
+        format!("{}-{}", self.id, index)
+    }
+
+    /// Create the `index`-th instance (1-based)
+    pub fn instance(&self, index: usize) -> Resource {
+        let mut resource = self.template.clone();
+        resource.id = self.instance_id(index);
+        resource.cost_per_hour = Some(self.cost_per_hour);
+        resource
+    }
+}
+
+/// Decision to provision one pool instance
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProvisioningDecision {
+    /// Pool the instance comes from
+    pub pool_id: String,
+    /// Resource id of the instance
+    pub resource_id: String,
+    /// When the instance is requested (epoch ms)
+    pub requested_at_ms: i64,
+    /// When the instance is usable (epoch ms)
+    pub ready_at_ms: i64,
+    /// When the instance is released after its last assignment (epoch ms)
+    pub released_at_ms: i64,
+    /// Cost of holding the instance
+    pub cost: f64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_instances() {
+        let pool = ResourcePool::new("vm", Resource::primary("template").with_skill("gpu", 1.0))
+            .with_max_instances(3)
+            .with_cost(2.5);
+
+        let instance = pool.instance(2);
+        assert_eq!(instance.id, "vm-2");
+        assert!(instance.has_skill("gpu"));
+        assert_eq!(instance.cost_per_hour, Some(2.5));
+    }
+}
@@ -0,0 +1,145 @@
+//! Preference - Soft assignment preferences
+//!
+//! Preferred resources (on activities) and preferred shifts (on resources)
+//! are soft goals: schedulers try to honor them and reports measure how
+//! well they were met.
+
+use super::activity::Activity;
+use super::resource::Resource;
+use super::schedule::Schedule;
+use super::task::Task;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Preferred working period of a resource
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ShiftPreference {
+    /// Period start (epoch ms)
+    pub start_ms: i64,
+    /// Period end (epoch ms)
+    pub end_ms: i64,
+    /// Preference weight (0.0 to 1.0)
+    pub weight: f64,
+}
+
+impl ShiftPreference {
+    /// Create shift preference
+    pub fn new(start_ms: i64, end_ms: i64, weight: f64) -> Self {
+        Self {
+            start_ms,
+            end_ms,
+            weight: weight.clamp(0.0, 1.0),
+        }
+    }
+
+    /// Check if an interval lies entirely within the shift
+    pub fn contains(&self, start_ms: i64, end_ms: i64) -> bool {
+        start_ms >= self.start_ms && end_ms <= self.end_ms
+    }
+}
+
+/// Preference satisfaction of a schedule
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PreferenceReport {
+    /// Weight of preferences honored
+    pub satisfied_weight: f64,
+    /// Weight achievable if every preference were honored
+    pub total_weight: f64,
+}
+
+impl PreferenceReport {
+    /// Evaluate preference satisfaction
+    ///
+    /// Each assignment counts its best achievable activity-resource
+    /// preference and, if its resource declares shifts, its best shift
+    /// preference.
+    pub fn evaluate(schedule: &Schedule, tasks: &[Task], resources: &[Resource]) -> Self {
+        let activities: HashMap<&str, &Activity> = tasks
+            .iter()
+            .flat_map(|t| t.activities.iter())
+            .map(|a| (a.id.as_str(), a))
+            .collect();
+        let resource_map: HashMap<&str, &Resource> =
+            resources.iter().map(|r| (r.id.as_str(), r)).collect();
+
+        let mut report = Self::default();
+        for assignment in &schedule.assignments {
+            if let Some(activity) = activities.get(assignment.activity_id.as_str()) {
+                let best = activity
+                    .preferred_resources
+                    .values()
+                    .copied()
+                    .fold(0.0, f64::max);
+                report.total_weight += best;
+                report.satisfied_weight += activity.resource_preference(&assignment.resource_id);
+            }
+
+            if let Some(resource) = resource_map.get(assignment.resource_id.as_str()) {
+                let best = resource
+                    .preferred_shifts
+                    .iter()
+                    .map(|s| s.weight)
+                    .fold(0.0, f64::max);
+                report.total_weight += best;
+                report.satisfied_weight +=
+                    resource.shift_preference(assignment.start_ms, assignment.end_ms);
+            }
+        }
+        report
+    }
+
+    /// Satisfaction rate (0.0 to 1.0; 1.0 when nothing is preferred)
+    pub fn satisfaction_rate(&self) -> f64 {
+        if self.total_weight <= 0.0 {
+            1.0
+        } else {
+            self.satisfied_weight / self.total_weight
+        }
+    }
+
+    /// Weight of preferences not honored
+    pub fn unsatisfied_weight(&self) -> f64 {
+        (self.total_weight - self.satisfied_weight).max(0.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Assignment;
+
+    #[test]
+    fn test_resource_preference() {
+        let tasks = vec![Task::new("T1")
+            .with_activity(Activity::new("A1", "T1", 1).with_preferred_resource("M1", 1.0))
+            .with_activity(Activity::new("A2", "T1", 2).with_preferred_resource("M1", 0.5))];
+        let resources = vec![Resource::primary("M1"), Resource::primary("M2")];
+
+        let mut schedule = Schedule::new();
+        schedule.add_assignment(Assignment::new("A1", "T1", "M1", 0, 1000));
+        schedule.add_assignment(Assignment::new("A2", "T1", "M2", 1000, 2000));
+
+        let report = PreferenceReport::evaluate(&schedule, &tasks, &resources);
+        assert!((report.satisfaction_rate() - 1.0 / 1.5).abs() < 1e-9);
+        assert!((report.unsatisfied_weight() - 0.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_shift_preference() {
+        let resources = vec![Resource::human("I1").with_preferred_shift(0, 4000, 1.0)];
+
+        let mut schedule = Schedule::new();
+        schedule.add_assignment(Assignment::new("A1", "T1", "I1", 1000, 2000));
+        schedule.add_assignment(Assignment::new("A2", "T2", "I1", 3000, 5000));
+
+        let report = PreferenceReport::evaluate(&schedule, &[], &resources);
+        assert_eq!(report.satisfaction_rate(), 0.5);
+    }
+
+    #[test]
+    fn test_no_preferences() {
+        let report = PreferenceReport::evaluate(&Schedule::new(), &[], &[]);
+        assert_eq!(report.satisfaction_rate(), 1.0);
+    }
+}
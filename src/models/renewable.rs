@@ -0,0 +1,34 @@
+//! Renewable - Renewable resource pools (RCPSP)
+//!
+//! Capacity shared by units rather than by named instances: activities
+//! hold some units while they run and release them when they finish.
+
+use serde::{Deserialize, Serialize};
+
+/// Renewable resource pool with integer capacity
+///
+/// Activities demand units through `Activity::with_pool_demand`; at no
+/// time may the units held exceed the capacity.
+///
+/// Domain mappings:
+/// - Construction: Crew of workers, crane fleet
+/// - Manufacturing: Operators on a line
+/// - Project management: Engineers of a department
+/// - Cloud: CPU cores of a cluster
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RenewablePool {
+    /// Pool id (referenced by activity demands)
+    pub id: String,
+    /// Units available at any time
+    pub capacity: u32,
+}
+
+impl RenewablePool {
+    /// Create pool
+    pub fn new(id: &str, capacity: u32) -> Self {
+        Self {
+            id: id.to_string(),
+            capacity,
+        }
+    }
+}
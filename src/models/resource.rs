@@ -3,6 +3,8 @@
 //! Domain-agnostic representation of resources
 
 use super::calendar::Calendar;
+use super::labor::WorkingTimeRules;
+use super::preference::ShiftPreference;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -15,6 +17,7 @@ use std::collections::HashMap;
 /// - Education: Classroom, Instructor, Lab Equipment
 /// - Cloud: VM, CPU Core, Memory, Storage
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Resource {
     /// Unique identifier
     pub id: String,
@@ -26,6 +29,16 @@ pub struct Resource {
     pub capacity: i32,
     /// Efficiency factor (1.0 = normal)
     pub efficiency: f64,
+    /// Periods overriding `efficiency` (night shifts, ramp-up after
+    /// maintenance); later entries win where periods overlap
+    #[serde(default)]
+    pub efficiency_periods: Vec<EfficiencyPeriod>,
+    /// Processing speeds up with repetitions of a task category
+    #[serde(default)]
+    pub learning_curve: Option<LearningCurve>,
+    /// Wear limit before replacement or service (tool life)
+    #[serde(default)]
+    pub usage_budget: Option<UsageBudget>,
     /// Availability calendar
     pub calendar: Option<Calendar>,
     /// Skills/capabilities
@@ -34,10 +47,154 @@ pub struct Resource {
     pub cost_per_hour: Option<f64>,
     /// Custom attributes
     pub attributes: HashMap<String, String>,
+    /// Working-time rules (human resources)
+    #[serde(default)]
+    pub working_time: Option<WorkingTimeRules>,
+    /// Preferred working periods (soft)
+    #[serde(default)]
+    pub preferred_shifts: Vec<ShiftPreference>,
+    /// Home/current location (start point for mobile resources)
+    #[serde(default)]
+    pub location: Option<String>,
+    /// Carrying capacity per load dimension (vehicles)
+    #[serde(default)]
+    pub load_capacity: HashMap<String, f64>,
+    /// Shared capacity per dimension (CPU, memory, disk); activities run
+    /// concurrently while their summed demands fit
+    #[serde(default)]
+    pub dimension_capacity: HashMap<String, f64>,
+    /// Probability the resource is revoked during the horizon (spot
+    /// instances, on-call rooms)
+    #[serde(default)]
+    pub revocation_probability: f64,
+    /// Resources one of which must attend every setup (setters,
+    /// technicians)
+    #[serde(default)]
+    pub setup_crew: Vec<String>,
+}
+
+/// Efficiency in effect over a time window
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct EfficiencyPeriod {
+    /// Start time (epoch ms)
+    pub start_ms: i64,
+    /// End time (epoch ms, exclusive)
+    pub end_ms: i64,
+    /// Efficiency factor during the period
+    pub efficiency: f64,
+}
+
+/// Wright learning curve
+///
+/// Each doubling of the repetitions of a task category a resource has
+/// already done multiplies its processing time by `rate`: at 0.9 the
+/// 2nd run takes 90%, the 4th 81%.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct LearningCurve {
+    /// Learning rate in (0, 1] (1.0 = no learning)
+    pub rate: f64,
+    /// Lowest factor reached (plateau)
+    #[serde(default)]
+    pub min_factor: f64,
+}
+
+impl LearningCurve {
+    /// Create curve with a learning rate (e.g. 0.9)
+    pub fn new(rate: f64) -> Self {
+        Self {
+            rate: rate.clamp(f64::MIN_POSITIVE, 1.0),
+            min_factor: 0.0,
+        }
+    }
+
+    /// Set plateau below which time no longer falls
+    pub fn with_min_factor(mut self, min_factor: f64) -> Self {
+        self.min_factor = min_factor.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Time factor for the next run after `done` repetitions
+    pub fn factor(&self, done: u32) -> f64 {
+        (done as f64 + 1.0)
+            .powf(self.rate.log2())
+            .max(self.min_factor)
+    }
+}
+
+/// Usage a wearing resource (tool, die, fixture) allows before it must be
+/// replaced or serviced
+///
+/// Either limit triggers a replacement, which takes the resource out of
+/// service for `replacement_ms` and resets both counters.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct UsageBudget {
+    /// Processing time per tool life (ms)
+    #[serde(default)]
+    pub max_runtime_ms: Option<i64>,
+    /// Activities per tool life
+    #[serde(default)]
+    pub max_activities: Option<u32>,
+    /// Replacement/service downtime (ms)
+    pub replacement_ms: i64,
+    /// Processing time already used at the start of the horizon (ms)
+    #[serde(default)]
+    pub used_ms: i64,
+    /// Activities already run at the start of the horizon
+    #[serde(default)]
+    pub used_activities: u32,
+}
+
+impl UsageBudget {
+    /// Create budget with replacement downtime and no limits yet
+    pub fn new(replacement_ms: i64) -> Self {
+        Self {
+            max_runtime_ms: None,
+            max_activities: None,
+            replacement_ms: replacement_ms.max(0),
+            used_ms: 0,
+            used_activities: 0,
+        }
+    }
+
+    /// Set processing time per tool life
+    pub fn with_max_runtime(mut self, runtime_ms: i64) -> Self {
+        self.max_runtime_ms = Some(runtime_ms);
+        self
+    }
+
+    /// Set activities per tool life
+    pub fn with_max_activities(mut self, activities: u32) -> Self {
+        self.max_activities = Some(activities);
+        self
+    }
+
+    /// Set usage carried into the horizon
+    pub fn with_used(mut self, used_ms: i64, used_activities: u32) -> Self {
+        self.used_ms = used_ms;
+        self.used_activities = used_activities;
+        self
+    }
+
+    /// Whether running `run_ms` more after `used_ms` and `runs` activities
+    /// requires a replacement first
+    ///
+    /// A single run longer than the tool life still goes on a fresh tool.
+    pub fn needs_replacement(&self, used_ms: i64, runs: u32, run_ms: i64) -> bool {
+        if runs == 0 && used_ms == 0 {
+            return false;
+        }
+        self.max_runtime_ms
+            .is_some_and(|max| used_ms + run_ms > max)
+            || self.max_activities.is_some_and(|max| runs >= max)
+    }
 }
 
 /// Resource type classification
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum ResourceType {
     /// Primary processing resource (machine, room, vehicle)
     Primary,
@@ -53,6 +210,7 @@ pub enum ResourceType {
 
 /// Skill/capability of a resource
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Skill {
     /// Skill name
     pub name: String,
@@ -78,10 +236,20 @@ impl Resource {
             resource_type,
             capacity: 1,
             efficiency: 1.0,
+            efficiency_periods: Vec::new(),
+            learning_curve: None,
+            usage_budget: None,
             calendar: None,
             skills: Vec::new(),
             cost_per_hour: None,
             attributes: HashMap::new(),
+            working_time: None,
+            preferred_shifts: Vec::new(),
+            location: None,
+            load_capacity: HashMap::new(),
+            dimension_capacity: HashMap::new(),
+            revocation_probability: 0.0,
+            setup_crew: Vec::new(),
         }
     }
 
@@ -102,46 +270,245 @@ impl Resource {
 
     /// Set name
     pub fn with_name(mut self, name: &str) -> Self {
-        self.name = name.to_string();
+        self.set_name(name);
         self
     }
 
+    /// Set name
+    pub fn set_name(&mut self, name: &str) {
+        self.name = name.to_string();
+    }
+
     /// Set capacity
     pub fn with_capacity(mut self, capacity: i32) -> Self {
-        self.capacity = capacity;
+        self.set_capacity(capacity);
         self
     }
 
+    /// Set capacity
+    pub fn set_capacity(&mut self, capacity: i32) {
+        self.capacity = capacity;
+    }
+
     /// Set efficiency
     pub fn with_efficiency(mut self, efficiency: f64) -> Self {
+        self.set_efficiency(efficiency);
+        self
+    }
+
+    /// Set efficiency
+    pub fn set_efficiency(&mut self, efficiency: f64) {
         self.efficiency = efficiency;
+    }
+
+    /// Add a period with a different efficiency
+    pub fn with_efficiency_period(mut self, start_ms: i64, end_ms: i64, efficiency: f64) -> Self {
+        self.add_efficiency_period(start_ms, end_ms, efficiency);
+        self
+    }
+
+    /// Add a period with a different efficiency
+    pub fn add_efficiency_period(&mut self, start_ms: i64, end_ms: i64, efficiency: f64) {
+        self.efficiency_periods.push(EfficiencyPeriod {
+            start_ms,
+            end_ms,
+            efficiency,
+        });
+    }
+
+    /// Set learning curve
+    pub fn with_learning_curve(mut self, curve: LearningCurve) -> Self {
+        self.set_learning_curve(curve);
         self
     }
 
+    /// Set learning curve
+    pub fn set_learning_curve(&mut self, curve: LearningCurve) {
+        self.learning_curve = Some(curve);
+    }
+
+    /// Set usage budget (tool life)
+    pub fn with_usage_budget(mut self, budget: UsageBudget) -> Self {
+        self.set_usage_budget(budget);
+        self
+    }
+
+    /// Set usage budget (tool life)
+    pub fn set_usage_budget(&mut self, budget: UsageBudget) {
+        self.usage_budget = Some(budget);
+    }
+
     /// Set calendar
     pub fn with_calendar(mut self, calendar: Calendar) -> Self {
+        self.set_calendar(calendar);
+        self
+    }
+
+    /// Set calendar
+    pub fn set_calendar(&mut self, calendar: Calendar) {
         self.calendar = Some(calendar);
+    }
+
+    /// Restrict availability by another calendar (site, contract)
+    pub fn with_calendar_constraint(mut self, calendar: &Calendar) -> Self {
+        self.add_calendar_constraint(calendar);
         self
     }
 
+    /// Restrict availability by another calendar; the resource is then
+    /// available only where its calendar and `calendar` both are
+    pub fn add_calendar_constraint(&mut self, calendar: &Calendar) {
+        self.calendar = Some(match &self.calendar {
+            Some(own) => own.intersect(calendar),
+            None => calendar.clone(),
+        });
+    }
+
     /// Add skill
     pub fn with_skill(mut self, name: &str, level: f64) -> Self {
-        self.skills.push(Skill::new(name, level));
+        self.add_skill(name, level);
         self
     }
 
+    /// Add skill
+    pub fn add_skill(&mut self, name: &str, level: f64) {
+        self.skills.push(Skill::new(name, level));
+    }
+
     /// Set cost
     pub fn with_cost(mut self, cost_per_hour: f64) -> Self {
+        self.set_cost(cost_per_hour);
+        self
+    }
+
+    /// Set cost
+    pub fn set_cost(&mut self, cost_per_hour: f64) {
         self.cost_per_hour = Some(cost_per_hour);
+    }
+
+    /// Set working-time rules
+    pub fn with_working_time_rules(mut self, rules: WorkingTimeRules) -> Self {
+        self.set_working_time_rules(rules);
+        self
+    }
+
+    /// Set working-time rules
+    pub fn set_working_time_rules(&mut self, rules: WorkingTimeRules) {
+        self.working_time = Some(rules);
+    }
+
+    /// Add preferred shift with weight (0.0 to 1.0)
+    pub fn with_preferred_shift(mut self, start_ms: i64, end_ms: i64, weight: f64) -> Self {
+        self.add_preferred_shift(start_ms, end_ms, weight);
+        self
+    }
+
+    /// Add preferred shift with weight (0.0 to 1.0)
+    pub fn add_preferred_shift(&mut self, start_ms: i64, end_ms: i64, weight: f64) {
+        self.preferred_shifts
+            .push(ShiftPreference::new(start_ms, end_ms, weight));
+    }
+
+    /// Preference weight of the best preferred shift containing an interval
+    pub fn shift_preference(&self, start_ms: i64, end_ms: i64) -> f64 {
+        self.preferred_shifts
+            .iter()
+            .filter(|s| s.contains(start_ms, end_ms))
+            .map(|s| s.weight)
+            .fold(0.0, f64::max)
+    }
+
+    /// Set location
+    pub fn with_location(mut self, location: &str) -> Self {
+        self.set_location(location);
+        self
+    }
+
+    /// Set location
+    pub fn set_location(&mut self, location: &str) {
+        self.location = Some(location.to_string());
+    }
+
+    /// Set carrying capacity in a load dimension (weight, volume)
+    pub fn with_load_capacity(mut self, dimension: &str, capacity: f64) -> Self {
+        self.set_load_capacity(dimension, capacity);
+        self
+    }
+
+    /// Set carrying capacity in a load dimension (weight, volume)
+    pub fn set_load_capacity(&mut self, dimension: &str, capacity: f64) {
+        self.load_capacity.insert(dimension.to_string(), capacity);
+    }
+
+    /// Check if a load fits within every declared capacity dimension
+    pub fn can_carry(&self, loads: &HashMap<String, f64>) -> bool {
+        self.load_capacity
+            .iter()
+            .all(|(dim, cap)| loads.get(dim).copied().unwrap_or(0.0) <= *cap + 1e-9)
+    }
+
+    /// Set shared capacity in a dimension (CPU cores, memory GB)
+    pub fn with_dimension_capacity(mut self, dimension: &str, capacity: f64) -> Self {
+        self.set_dimension_capacity(dimension, capacity);
         self
     }
 
+    /// Set shared capacity in a dimension (CPU cores, memory GB)
+    pub fn set_dimension_capacity(&mut self, dimension: &str, capacity: f64) {
+        self.dimension_capacity
+            .insert(dimension.to_string(), capacity);
+    }
+
+    /// Check if the resource is packed by dimension rather than used exclusively
+    pub fn is_multi_dimensional(&self) -> bool {
+        !self.dimension_capacity.is_empty()
+    }
+
+    /// Check if demands fit an otherwise idle resource in every dimension
+    pub fn can_host(&self, demands: &HashMap<String, f64>) -> bool {
+        self.dimension_capacity
+            .iter()
+            .all(|(dim, cap)| demands.get(dim).copied().unwrap_or(0.0) <= *cap + 1e-9)
+    }
+
+    /// Set probability of being revoked during the horizon
+    pub fn with_revocation_risk(mut self, probability: f64) -> Self {
+        self.set_revocation_risk(probability);
+        self
+    }
+
+    /// Set probability of being revoked during the horizon
+    pub fn set_revocation_risk(&mut self, probability: f64) {
+        self.revocation_probability = probability.clamp(0.0, 1.0);
+    }
+
+    /// Check if the resource may be revoked
+    pub fn is_preemptible(&self) -> bool {
+        self.revocation_probability > 0.0
+    }
+
+    /// Require one of `crew` to be present during setups
+    pub fn with_setup_crew(mut self, crew: Vec<String>) -> Self {
+        self.set_setup_crew(crew);
+        self
+    }
+
+    /// Require one of `crew` to be present during setups
+    pub fn set_setup_crew(&mut self, crew: Vec<String>) {
+        self.setup_crew = crew;
+    }
+
     /// Add attribute
     pub fn with_attribute(mut self, key: &str, value: &str) -> Self {
-        self.attributes.insert(key.to_string(), value.to_string());
+        self.set_attribute(key, value);
         self
     }
 
+    /// Add attribute
+    pub fn set_attribute(&mut self, key: &str, value: &str) {
+        self.attributes.insert(key.to_string(), value.to_string());
+    }
+
     /// Check if resource has skill
     pub fn has_skill(&self, skill_name: &str) -> bool {
         self.skills.iter().any(|s| s.name == skill_name)
@@ -156,6 +523,76 @@ impl Resource {
             .unwrap_or(0.0)
     }
 
+    /// Time this resource needs for `process_ms` of nominal work
+    pub fn processing_time(&self, process_ms: i64) -> i64 {
+        if self.efficiency > 0.0 {
+            (process_ms as f64 / self.efficiency).round() as i64
+        } else {
+            process_ms
+        }
+    }
+
+    /// Nominal work left of `process_ms` after `done` repetitions of the
+    /// same task category (before efficiency scaling)
+    pub fn learned_work(&self, process_ms: i64, done: u32) -> i64 {
+        match &self.learning_curve {
+            Some(curve) => (process_ms as f64 * curve.factor(done)).round() as i64,
+            None => process_ms,
+        }
+    }
+
+    /// Efficiency in effect at a time
+    pub fn efficiency_at(&self, timestamp_ms: i64) -> f64 {
+        self.efficiency_periods
+            .iter()
+            .rev()
+            .find(|p| timestamp_ms >= p.start_ms && timestamp_ms < p.end_ms)
+            .map_or(self.efficiency, |p| p.efficiency)
+    }
+
+    /// End of work starting at `start_ms`: `setup_ms` as is, then
+    /// `process_ms` of nominal work at the efficiency in effect as it
+    /// runs, inside `calendar` working time if given
+    ///
+    /// `None` if the calendar runs out of working time. Without
+    /// efficiency periods this is `start_ms + setup_ms +
+    /// processing_time(process_ms)` stretched over the calendar.
+    pub fn finish_time(
+        &self,
+        start_ms: i64,
+        setup_ms: i64,
+        process_ms: i64,
+        calendar: Option<&Calendar>,
+    ) -> Option<i64> {
+        let advance = |from: i64, ms: i64| match calendar {
+            Some(calendar) => calendar.add_working_duration(from, ms),
+            None => Some(from + ms),
+        };
+        let mut t = advance(start_ms, setup_ms)?;
+        let mut work = process_ms as f64;
+        loop {
+            let efficiency = self.efficiency_at(t);
+            let rate = if efficiency > 0.0 { efficiency } else { 1.0 };
+            let end = advance(t, (work / rate).round() as i64)?;
+            // Next point where the efficiency may change
+            let change = self
+                .efficiency_periods
+                .iter()
+                .flat_map(|p| [p.start_ms, p.end_ms])
+                .filter(|&b| b > t)
+                .min();
+            match change {
+                Some(change) if end > change => {
+                    let worked =
+                        calendar.map_or(change - t, |c| c.working_duration_between(t, change));
+                    work -= worked as f64 * rate;
+                    t = change;
+                }
+                _ => return Some(end),
+            }
+        }
+    }
+
     /// Check if available at time
     pub fn is_available_at(&self, timestamp_ms: i64) -> bool {
         match &self.calendar {
@@ -163,6 +600,14 @@ impl Resource {
             None => true,
         }
     }
+
+    /// Check if available for a whole interval
+    pub fn is_available_between(&self, start_ms: i64, end_ms: i64) -> bool {
+        match &self.calendar {
+            Some(cal) => cal.is_available_between(start_ms, end_ms),
+            None => true,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -181,6 +626,87 @@ mod tests {
         assert_eq!(resource.capacity, 2);
     }
 
+    #[test]
+    fn test_in_place_setters() {
+        let mut resources = vec![Resource::primary("M1"), Resource::primary("M2")];
+        for resource in &mut resources {
+            resource.set_efficiency(1.25);
+            resource.add_skill("welding", 0.8);
+            resource.set_calendar(Calendar::new("day").with_window(0, 8 * 3_600_000));
+        }
+        if let Some(calendar) = resources[1].calendar.as_mut() {
+            calendar.add_holiday("Founders day", 0, 3_600_000);
+        }
+
+        assert!(resources.iter().all(|r| r.efficiency == 1.25));
+        assert!(resources[0].has_skill("welding"));
+        assert!(resources[0].is_available_at(1000));
+        assert!(!resources[1].is_available_at(1000));
+
+        // A site calendar further restricts the resource's own
+        let site = Calendar::new("site").with_window(4 * 3_600_000, 24 * 3_600_000);
+        resources[0].add_calendar_constraint(&site);
+        assert!(!resources[0].is_available_at(1000));
+        assert!(resources[0].is_available_between(4 * 3_600_000, 8 * 3_600_000));
+        assert!(!resources[0].is_available_at(9 * 3_600_000));
+    }
+
+    #[test]
+    fn test_efficiency_periods() {
+        const HOUR: i64 = 3_600_000;
+        let resource = Resource::primary("M1")
+            .with_efficiency_period(2 * HOUR, 4 * HOUR, 0.5)
+            .with_efficiency_period(3 * HOUR, 4 * HOUR, 2.0);
+        assert_eq!(resource.efficiency_at(HOUR), 1.0);
+        assert_eq!(resource.efficiency_at(2 * HOUR), 0.5);
+        assert_eq!(resource.efficiency_at(3 * HOUR), 2.0);
+
+        // 1h setup, then 1h at 1.0, 0.5h at 0.5, 2h at 2.0, 0.5h at 1.0
+        assert_eq!(
+            resource.finish_time(0, HOUR, 4 * HOUR, None),
+            Some(4 * HOUR + HOUR / 2)
+        );
+
+        // A break from 2h to 3h skips the slow hour
+        let calendar = Calendar::new("shift")
+            .with_window(0, 2 * HOUR)
+            .with_window(3 * HOUR, 24 * HOUR);
+        assert_eq!(
+            resource.finish_time(HOUR, 0, 4 * HOUR, Some(&calendar)),
+            Some(5 * HOUR)
+        );
+        assert_eq!(resource.finish_time(0, 0, 48 * HOUR, Some(&calendar)), None);
+    }
+
+    #[test]
+    fn test_learning_curve() {
+        let curve = LearningCurve::new(0.9);
+        assert_eq!(curve.factor(0), 1.0);
+        assert!((curve.factor(1) - 0.9).abs() < 1e-9);
+        assert!((curve.factor(3) - 0.81).abs() < 1e-9);
+        assert_eq!(curve.clone().with_min_factor(0.85).factor(3), 0.85);
+
+        let resource = Resource::human("W1").with_learning_curve(curve);
+        assert_eq!(resource.learned_work(1000, 0), 1000);
+        assert_eq!(resource.learned_work(1000, 3), 810);
+        assert_eq!(Resource::human("W2").learned_work(1000, 3), 1000);
+    }
+
+    #[test]
+    fn test_usage_budget() {
+        let budget = UsageBudget::new(600_000)
+            .with_max_runtime(3_600_000)
+            .with_max_activities(10);
+        assert!(!budget.needs_replacement(0, 0, 5_000_000));
+        assert!(!budget.needs_replacement(3_000_000, 4, 600_000));
+        assert!(budget.needs_replacement(3_000_000, 4, 600_001));
+        assert!(budget.needs_replacement(0, 10, 1000));
+
+        let tool = Resource::new("D1", ResourceType::Secondary)
+            .with_usage_budget(budget.with_used(3_000_000, 4));
+        assert_eq!(tool.usage_budget.unwrap().used_ms, 3_000_000);
+    }
+
     #[test]
     fn test_resource_skills() {
         let resource = Resource::human("W1")
@@ -193,6 +719,20 @@ mod tests {
         assert_eq!(resource.skill_level("unknown"), 0.0);
     }
 
+    #[test]
+    fn test_dimension_capacity() {
+        let host = Resource::primary("H1")
+            .with_dimension_capacity("cpu", 8.0)
+            .with_dimension_capacity("mem", 32.0);
+        let demands: HashMap<String, f64> = [("cpu".to_string(), 4.0), ("mem".to_string(), 64.0)]
+            .into_iter()
+            .collect();
+
+        assert!(host.is_multi_dimensional());
+        assert!(!host.can_host(&demands));
+        assert!(host.can_host(&HashMap::new()));
+    }
+
     #[test]
     fn test_resource_types() {
         let primary = Resource::primary("P1");
@@ -2,22 +2,211 @@
 //!
 //! Represents resource allocations and timing decisions
 
+use super::time_unit::TimeUnit;
+use super::trace::DecisionTrace;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::sync::{PoisonError, RwLock};
 
 /// Schedule - The result of a scheduling operation
+///
+/// Two schedules are equal (and hash alike) when they have the same
+/// makespan and the same assignments and violations in any order;
+/// diagnostics (planned confidence, explanations, trace) and downtime are
+/// ignored.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Schedule {
     /// Activity assignments
+    ///
+    /// Prefer `add_assignment`, which keeps the lookup index current.
+    /// Lookups rebuild the index when assignments are added or removed
+    /// directly; after changing IDs or times of existing assignments in
+    /// place, call `reindex`.
     pub assignments: Vec<Assignment>,
     /// Total completion time (makespan)
     pub makespan_ms: i64,
     /// Constraint violations (if any)
     pub violations: Vec<Violation>,
+    /// Confidence level covered by each activity's planned duration
+    /// (activities planned from a duration distribution)
+    #[serde(default)]
+    pub planned_confidence: HashMap<String, f64>,
+    /// Why each activity was placed where it is (if recorded by the
+    /// scheduler)
+    #[serde(default)]
+    pub explanations: HashMap<String, PlacementExplanation>,
+    /// Decision log of the run (if traced by the scheduler)
+    #[serde(default)]
+    pub trace: Option<DecisionTrace>,
+    /// Resource time the scheduler reserved for non-activity work (tool
+    /// replacement)
+    #[serde(default)]
+    pub downtime: Vec<Downtime>,
+    /// Activity, task and resource lookup index
+    #[serde(skip)]
+    index: ScheduleIndex,
+}
+
+/// Lookup tables over `Schedule::assignments`
+///
+/// Built on first lookup and extended by `add_assignment`; clones start
+/// without one.
+#[derive(Debug, Default)]
+struct ScheduleIndex(RwLock<IndexTables>);
+
+impl Clone for ScheduleIndex {
+    fn clone(&self) -> Self {
+        Self::default()
+    }
+}
+
+#[derive(Debug, Default)]
+struct IndexTables {
+    /// Assignments covered (stale when the vector's length differs)
+    len: usize,
+    by_activity: HashMap<String, Vec<usize>>,
+    by_task: HashMap<String, Vec<usize>>,
+    /// Sorted by start time, then insertion order
+    by_resource: HashMap<String, Vec<usize>>,
+}
+
+impl IndexTables {
+    fn build(assignments: &[Assignment]) -> Self {
+        let mut tables = Self::default();
+        for idx in 0..assignments.len() {
+            tables.insert(assignments, idx);
+        }
+        tables
+    }
+
+    fn insert(&mut self, assignments: &[Assignment], idx: usize) {
+        let a = &assignments[idx];
+        self.by_activity
+            .entry(a.activity_id.clone())
+            .or_default()
+            .push(idx);
+        self.by_task.entry(a.task_id.clone()).or_default().push(idx);
+        let on_resource = self.by_resource.entry(a.resource_id.clone()).or_default();
+        let at = on_resource.partition_point(|&j| (assignments[j].start_ms, j) < (a.start_ms, idx));
+        on_resource.insert(at, idx);
+        self.len = idx + 1;
+    }
+}
+
+/// What determined an activity's start time
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum StartDriver {
+    /// Nothing held it back: it starts with the schedule
+    ScheduleStart,
+    /// The previous activity of its task
+    Predecessor,
+    /// An overlap allowance with its predecessor
+    Overlap,
+    /// Its pickup (pickup-delivery pair)
+    Pickup,
+    /// A handoff synchronization
+    Handoff,
+    /// A maximum time lag behind its predecessor
+    MaxDelay,
+    /// The opening of its time window
+    TimeWindow,
+    /// The chosen resource becoming free
+    ResourceAvailable,
+    /// Travel, setup crew, working-time rules, slots, calendars or energy
+    /// shifting moved it later
+    Adjusted,
+}
+
+/// Outcome of one candidate resource for an activity
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct CandidateOption {
+    /// Candidate resource
+    pub resource_id: String,
+    /// Earliest start on it (None if infeasible)
+    pub start_ms: Option<i64>,
+    /// Earliest finish on it (None if infeasible)
+    pub finish_ms: Option<i64>,
+}
+
+/// Reasons behind an activity's placement
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct PlacementExplanation {
+    /// Activity ID
+    pub activity_id: String,
+    /// Chosen resource
+    pub resource_id: String,
+    /// Planned start
+    pub start_ms: i64,
+    /// Earliest start allowed by its task and constraints
+    pub ready_ms: i64,
+    /// What determined the start
+    pub driver: StartDriver,
+    /// Setup/transition time incurred
+    pub setup_ms: i64,
+    /// All candidates considered (including the chosen one)
+    pub candidates: Vec<CandidateOption>,
+}
+
+impl PlacementExplanation {
+    /// Human-readable explanation
+    pub fn summary(&self) -> String {
+        let cause = match self.driver {
+            StartDriver::ScheduleStart => "nothing held it back".to_string(),
+            StartDriver::Predecessor => "its predecessor finished then".to_string(),
+            StartDriver::Overlap => "its overlap allowance permits it then".to_string(),
+            StartDriver::Pickup => "its pickup finished then".to_string(),
+            StartDriver::Handoff => "it receives a handoff then".to_string(),
+            StartDriver::MaxDelay => "it must closely follow its predecessor".to_string(),
+            StartDriver::TimeWindow => "its time window opens then".to_string(),
+            StartDriver::ResourceAvailable => format!("{} was busy until then", self.resource_id),
+            StartDriver::Adjusted => format!(
+                "travel, working-time rules, slots, calendars or energy prices moved it from {} ms",
+                self.ready_ms
+            ),
+        };
+        let mut text = format!(
+            "{} starts at {} ms on {} because {}.",
+            self.activity_id, self.start_ms, self.resource_id, cause
+        );
+
+        let chosen = self
+            .candidates
+            .iter()
+            .find(|c| c.resource_id == self.resource_id)
+            .and_then(|c| c.finish_ms);
+        let rivals: Vec<&CandidateOption> = self
+            .candidates
+            .iter()
+            .filter(|c| c.resource_id != self.resource_id)
+            .collect();
+        if let Some(chosen) = chosen.filter(|_| !rivals.is_empty()) {
+            let faster = rivals
+                .iter()
+                .any(|c| c.finish_ms.is_some_and(|f| f < chosen));
+            text.push_str(if faster {
+                " It won on preference, reliability or workload despite a faster rival."
+            } else {
+                " It finishes no later than any other candidate."
+            });
+        }
+        for rival in rivals.iter().filter(|c| c.finish_ms.is_none()) {
+            text.push_str(&format!(" {} was infeasible.", rival.resource_id));
+        }
+        if self.setup_ms > 0 {
+            text.push_str(&format!(" A {} ms setup precedes it.", self.setup_ms));
+        }
+        text
+    }
 }
 
 /// Assignment - Allocation of an activity to a resource
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Assignment {
     /// Activity ID
     pub activity_id: String,
@@ -33,8 +222,50 @@ pub struct Assignment {
     pub setup_ms: i64,
 }
 
+/// Period a resource is taken out of service by the scheduler
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Downtime {
+    /// Resource out of service
+    pub resource_id: String,
+    /// Start time (epoch ms)
+    pub start_ms: i64,
+    /// End time (epoch ms)
+    pub end_ms: i64,
+    /// Why (e.g. "tool replacement")
+    pub reason: String,
+}
+
+/// Load of a resource in one time bucket
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LoadBucket {
+    /// Bucket start (epoch ms)
+    pub start_ms: i64,
+    /// Bucket end (epoch ms, exclusive)
+    pub end_ms: i64,
+    /// Processing time in the bucket (ms)
+    pub busy_ms: i64,
+    /// Setup time in the bucket (ms)
+    pub setup_ms: i64,
+    /// Time neither processing nor in setup (ms)
+    pub idle_ms: i64,
+}
+
+impl LoadBucket {
+    /// Share of the bucket processing or in setup (0.0 - 1.0, more if
+    /// assignments overlap on a multi-capacity resource)
+    pub fn utilization(&self) -> f64 {
+        let length = self.end_ms - self.start_ms;
+        if length <= 0 {
+            return 0.0;
+        }
+        (self.busy_ms + self.setup_ms) as f64 / length as f64
+    }
+}
+
 /// Constraint violation
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Violation {
     /// Violation type
     pub violation_type: ViolationType,
@@ -47,7 +278,8 @@ pub struct Violation {
 }
 
 /// Types of violations
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum ViolationType {
     /// Deadline missed
     DeadlineMiss,
@@ -59,6 +291,10 @@ pub enum ViolationType {
     ResourceUnavailable,
     /// Skill mismatch
     SkillMismatch,
+    /// Working-time rule broken (hours, rest, consecutive days)
+    WorkingTime,
+    /// Activity outside its time window
+    TimeWindow,
     /// Custom violation
     Custom(String),
 }
@@ -97,6 +333,25 @@ impl Assignment {
     pub fn process_ms(&self) -> i64 {
         self.end_ms - self.start_ms - self.setup_ms
     }
+
+    /// Duration in a given unit
+    pub fn duration_in(&self, unit: TimeUnit) -> f64 {
+        unit.from_ms(self.duration_ms())
+    }
+}
+
+impl std::fmt::Display for Assignment {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} ({}) on {} {}..{} ms",
+            self.activity_id, self.task_id, self.resource_id, self.start_ms, self.end_ms
+        )?;
+        if self.setup_ms > 0 {
+            write!(f, " (setup {} ms)", self.setup_ms)?;
+        }
+        Ok(())
+    }
 }
 
 impl Schedule {
@@ -106,15 +361,77 @@ impl Schedule {
             assignments: Vec::new(),
             makespan_ms: 0,
             violations: Vec::new(),
+            planned_confidence: HashMap::new(),
+            explanations: HashMap::new(),
+            trace: None,
+            downtime: Vec::new(),
+            index: ScheduleIndex::default(),
         }
     }
 
+    /// Reasons behind an activity's placement (if recorded)
+    pub fn explain(&self, activity_id: &str) -> Option<&PlacementExplanation> {
+        self.explanations.get(activity_id)
+    }
+
+    /// Makespan in a given unit
+    pub fn makespan_in(&self, unit: TimeUnit) -> f64 {
+        unit.from_ms(self.makespan_ms)
+    }
+
     /// Add assignment
     pub fn add_assignment(&mut self, assignment: Assignment) {
         if assignment.end_ms > self.makespan_ms {
             self.makespan_ms = assignment.end_ms;
         }
+        let idx = self.assignments.len();
         self.assignments.push(assignment);
+        let tables = self
+            .index
+            .0
+            .get_mut()
+            .unwrap_or_else(PoisonError::into_inner);
+        if tables.len == idx {
+            tables.insert(&self.assignments, idx);
+        }
+    }
+
+    /// Rebuild the lookup index after editing assignments in place
+    pub fn reindex(&mut self) {
+        *self
+            .index
+            .0
+            .get_mut()
+            .unwrap_or_else(PoisonError::into_inner) = IndexTables::build(&self.assignments);
+    }
+
+    /// Assignments listed under `key` in one of the index tables
+    ///
+    /// Falls back to a scan if the index no longer matches `matches`.
+    fn lookup(
+        &self,
+        table: impl Fn(&IndexTables) -> Option<&Vec<usize>>,
+        matches: impl Fn(&Assignment) -> bool,
+    ) -> Vec<&Assignment> {
+        let indexed = |tables: &IndexTables| {
+            table(tables).map_or(Some(Vec::new()), |idxs| {
+                idxs.iter()
+                    .map(|&i| self.assignments.get(i).filter(|a| matches(a)))
+                    .collect::<Option<Vec<_>>>()
+            })
+        };
+        let tables = self.index.0.read().unwrap_or_else(PoisonError::into_inner);
+        let found = if tables.len == self.assignments.len() {
+            indexed(&tables)
+        } else {
+            drop(tables);
+            let mut tables = self.index.0.write().unwrap_or_else(PoisonError::into_inner);
+            if tables.len != self.assignments.len() {
+                *tables = IndexTables::build(&self.assignments);
+            }
+            indexed(&tables)
+        };
+        found.unwrap_or_else(|| self.assignments.iter().filter(|a| matches(a)).collect())
     }
 
     /// Add violation
@@ -129,25 +446,51 @@ impl Schedule {
 
     /// Get assignment for activity
     pub fn assignment_for_activity(&self, activity_id: &str) -> Option<&Assignment> {
-        self.assignments
-            .iter()
-            .find(|a| a.activity_id == activity_id)
+        self.assignments_for_activity(activity_id).first().copied()
+    }
+
+    /// Get all assignments of an activity (several for gang activities)
+    pub fn assignments_for_activity(&self, activity_id: &str) -> Vec<&Assignment> {
+        self.lookup(
+            |t| t.by_activity.get(activity_id),
+            |a| a.activity_id == activity_id,
+        )
     }
 
     /// Get assignments for task
     pub fn assignments_for_task(&self, task_id: &str) -> Vec<&Assignment> {
-        self.assignments
-            .iter()
-            .filter(|a| a.task_id == task_id)
-            .collect()
+        self.lookup(|t| t.by_task.get(task_id), |a| a.task_id == task_id)
     }
 
-    /// Get assignments for resource
+    /// Get assignments for resource, by start time
     pub fn assignments_for_resource(&self, resource_id: &str) -> Vec<&Assignment> {
-        self.assignments
+        let mut on_resource = self.lookup(
+            |t| t.by_resource.get(resource_id),
+            |a| a.resource_id == resource_id,
+        );
+        // Already in order unless times were edited in place
+        on_resource.sort_by_key(|a| a.start_ms);
+        on_resource
+    }
+
+    /// Total assigned time on a resource (ms)
+    pub fn resource_workload_ms(&self, resource_id: &str) -> i64 {
+        self.assignments_for_resource(resource_id)
             .iter()
-            .filter(|a| a.resource_id == resource_id)
-            .collect()
+            .map(|a| a.duration_ms())
+            .sum()
+    }
+
+    /// Spread between the most and least loaded resources in a group (ms)
+    pub fn workload_deviation_ms(&self, resource_ids: &[String]) -> i64 {
+        let loads: Vec<i64> = resource_ids
+            .iter()
+            .map(|id| self.resource_workload_ms(id))
+            .collect();
+        match (loads.iter().max(), loads.iter().min()) {
+            (Some(max), Some(min)) => max - min,
+            _ => 0,
+        }
     }
 
     /// Calculate resource utilization
@@ -156,15 +499,67 @@ impl Schedule {
             return 0.0;
         }
 
-        let busy_time: i64 = self
-            .assignments_for_resource(resource_id)
-            .iter()
-            .map(|a| a.duration_ms())
-            .sum();
+        let busy_time = self.resource_workload_ms(resource_id);
 
         busy_time as f64 / horizon_ms as f64
     }
 
+    /// Time-bucketed load of a resource for charting
+    ///
+    /// Buckets of `bucket_ms` cover the schedule horizon, from the earliest
+    /// start of any assignment to the makespan, so the timelines of all
+    /// resources line up; the last bucket may be shorter. Setup time is
+    /// the leading `setup_ms` of each assignment.
+    pub fn utilization_timeline(&self, resource_id: &str, bucket_ms: i64) -> Vec<LoadBucket> {
+        let Some(horizon_start) = self.assignments.iter().map(|a| a.start_ms).min() else {
+            return Vec::new();
+        };
+        if bucket_ms <= 0 || self.makespan_ms <= horizon_start {
+            return Vec::new();
+        }
+
+        let mut buckets: Vec<LoadBucket> = (horizon_start..self.makespan_ms)
+            .step_by(bucket_ms as usize)
+            .map(|start_ms| LoadBucket {
+                start_ms,
+                end_ms: (start_ms + bucket_ms).min(self.makespan_ms),
+                busy_ms: 0,
+                setup_ms: 0,
+                idle_ms: 0,
+            })
+            .collect();
+
+        // Overlap of [from, to) with each bucket it touches
+        let mut spread = |from: i64, to: i64, setup: bool| {
+            if to <= from {
+                return;
+            }
+            let first = ((from - horizon_start) / bucket_ms) as usize;
+            for bucket in buckets.iter_mut().skip(first) {
+                if bucket.start_ms >= to {
+                    break;
+                }
+                let overlap = to.min(bucket.end_ms) - from.max(bucket.start_ms);
+                if setup {
+                    bucket.setup_ms += overlap;
+                } else {
+                    bucket.busy_ms += overlap;
+                }
+            }
+        };
+        for a in self.assignments_for_resource(resource_id) {
+            let setup_end = (a.start_ms + a.setup_ms.max(0)).min(a.end_ms);
+            spread(a.start_ms, setup_end, true);
+            spread(setup_end, a.end_ms, false);
+        }
+
+        for bucket in &mut buckets {
+            let length = bucket.end_ms - bucket.start_ms;
+            bucket.idle_ms = (length - bucket.busy_ms - bucket.setup_ms).max(0);
+        }
+        buckets
+    }
+
     /// Calculate all resource utilizations
     pub fn all_utilizations(&self) -> HashMap<String, f64> {
         let mut utilizations = HashMap::new();
@@ -195,6 +590,102 @@ impl Schedule {
     pub fn assignment_count(&self) -> usize {
         self.assignments.len()
     }
+
+    /// Assignments in canonical order (start, then activity, resource, ...)
+    pub fn canonical_assignments(&self) -> Vec<&Assignment> {
+        let mut sorted: Vec<&Assignment> = self.assignments.iter().collect();
+        sorted.sort_by(|a, b| (a.start_ms, a).cmp(&(b.start_ms, b)));
+        sorted
+    }
+
+    fn sorted_violations(&self) -> Vec<&Violation> {
+        let mut sorted: Vec<&Violation> = self.violations.iter().collect();
+        sorted.sort();
+        sorted
+    }
+
+    /// Copy with assignments and violations in canonical order
+    pub fn normalized(&self) -> Schedule {
+        let mut schedule = self.clone();
+        schedule.assignments = self.canonical_assignments().into_iter().cloned().collect();
+        schedule.violations.sort();
+        schedule.reindex();
+        schedule
+    }
+
+    /// Pretty JSON of the normalized schedule with sorted object keys
+    ///
+    /// Stable across runs and refactors that only reorder the output,
+    /// for snapshot tests.
+    pub fn to_normalized_json(&self) -> String {
+        fn sort_keys(value: serde_json::Value) -> serde_json::Value {
+            match value {
+                serde_json::Value::Object(map) => {
+                    let mut entries: Vec<_> = map.into_iter().collect();
+                    entries.sort_by(|a, b| a.0.cmp(&b.0));
+                    serde_json::Value::Object(
+                        entries
+                            .into_iter()
+                            .map(|(k, v)| (k, sort_keys(v)))
+                            .collect(),
+                    )
+                }
+                serde_json::Value::Array(items) => {
+                    serde_json::Value::Array(items.into_iter().map(sort_keys).collect())
+                }
+                other => other,
+            }
+        }
+        let value = serde_json::to_value(self.normalized()).expect("schedule serializes");
+        serde_json::to_string_pretty(&sort_keys(value)).expect("value serializes")
+    }
+
+    /// Differences from `other`, one line each (empty if equal)
+    ///
+    /// Lists the makespan change, then assignments and violations only
+    /// in this schedule (`-`) or only in `other` (`+`).
+    pub fn diff(&self, other: &Schedule) -> Vec<String> {
+        fn only_in<'a, T: Ord>(left: &'a [T], right: &[T]) -> Vec<&'a T> {
+            let mut right: Vec<&T> = right.iter().collect();
+            right.sort();
+            let mut left: Vec<&T> = left.iter().collect();
+            left.sort();
+            let mut missing = Vec::new();
+            let mut j = 0;
+            for item in left {
+                while j < right.len() && right[j] < item {
+                    j += 1;
+                }
+                if j < right.len() && right[j] == item {
+                    j += 1;
+                } else {
+                    missing.push(item);
+                }
+            }
+            missing
+        }
+
+        let mut lines = Vec::new();
+        if self.makespan_ms != other.makespan_ms {
+            lines.push(format!(
+                "makespan {} ms != {} ms",
+                self.makespan_ms, other.makespan_ms
+            ));
+        }
+        for a in only_in(&self.assignments, &other.assignments) {
+            lines.push(format!("- {}", a));
+        }
+        for a in only_in(&other.assignments, &self.assignments) {
+            lines.push(format!("+ {}", a));
+        }
+        for v in only_in(&self.violations, &other.violations) {
+            lines.push(format!("- violation {}: {}", v.entity_id, v.message));
+        }
+        for v in only_in(&other.violations, &self.violations) {
+            lines.push(format!("+ violation {}: {}", v.entity_id, v.message));
+        }
+        lines
+    }
 }
 
 impl Default for Schedule {
@@ -203,6 +694,43 @@ impl Default for Schedule {
     }
 }
 
+impl PartialEq for Schedule {
+    fn eq(&self, other: &Self) -> bool {
+        self.makespan_ms == other.makespan_ms
+            && self.assignments.len() == other.assignments.len()
+            && self.canonical_assignments() == other.canonical_assignments()
+            && self.sorted_violations() == other.sorted_violations()
+    }
+}
+
+impl Eq for Schedule {}
+
+impl std::hash::Hash for Schedule {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.makespan_ms.hash(state);
+        self.canonical_assignments().hash(state);
+        self.sorted_violations().hash(state);
+    }
+}
+
+/// Assert that two schedules are equal, listing their differences
+///
+/// Compares with `Schedule`'s order-insensitive equality; on failure the
+/// panic message shows `Schedule::diff` of left against right.
+#[macro_export]
+macro_rules! assert_schedule_eq {
+    ($left:expr, $right:expr $(,)?) => {{
+        let (left, right): (&$crate::models::Schedule, &$crate::models::Schedule) =
+            (&$left, &$right);
+        if left != right {
+            panic!(
+                "schedules differ (- left, + right):\n  {}",
+                left.diff(right).join("\n  ")
+            );
+        }
+    }};
+}
+
 impl Violation {
     /// Create deadline miss violation
     pub fn deadline_miss(task_id: &str, message: &str) -> Self {
@@ -223,6 +751,46 @@ impl Violation {
             severity: 90,
         }
     }
+
+    /// Create resource unavailable violation
+    pub fn resource_unavailable(entity_id: &str, message: &str) -> Self {
+        Self {
+            violation_type: ViolationType::ResourceUnavailable,
+            entity_id: entity_id.to_string(),
+            message: message.to_string(),
+            severity: 85,
+        }
+    }
+
+    /// Create precedence violation
+    pub fn precedence_violation(activity_id: &str, message: &str) -> Self {
+        Self {
+            violation_type: ViolationType::PrecedenceViolation,
+            entity_id: activity_id.to_string(),
+            message: message.to_string(),
+            severity: 85,
+        }
+    }
+
+    /// Create time window violation
+    pub fn time_window(activity_id: &str, message: &str) -> Self {
+        Self {
+            violation_type: ViolationType::TimeWindow,
+            entity_id: activity_id.to_string(),
+            message: message.to_string(),
+            severity: 70,
+        }
+    }
+
+    /// Create working-time rule violation
+    pub fn working_time(resource_id: &str, message: &str) -> Self {
+        Self {
+            violation_type: ViolationType::WorkingTime,
+            entity_id: resource_id.to_string(),
+            message: message.to_string(),
+            severity: 75,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -252,6 +820,133 @@ mod tests {
         assert_eq!(schedule.resource_utilization("R1", 20000), 0.5);
     }
 
+    #[test]
+    fn test_utilization_timeline() {
+        let mut schedule = Schedule::new();
+        schedule.add_assignment(Assignment::new("A1", "T1", "R1", 1000, 4000).with_setup(1500));
+        schedule.add_assignment(Assignment::new("A2", "T2", "R1", 6000, 7000));
+        schedule.add_assignment(Assignment::new("B1", "T3", "R2", 1000, 8500));
+
+        let timeline = schedule.utilization_timeline("R1", 2000);
+        let loads: Vec<(i64, i64, i64, i64)> = timeline
+            .iter()
+            .map(|b| (b.start_ms, b.setup_ms, b.busy_ms, b.idle_ms))
+            .collect();
+        assert_eq!(
+            loads,
+            vec![
+                (1000, 1500, 500, 0),
+                (3000, 0, 1000, 1000),
+                (5000, 0, 1000, 1000),
+                (7000, 0, 0, 1500),
+            ]
+        );
+        assert_eq!(timeline[3].end_ms, 8500);
+        assert_eq!(timeline[1].utilization(), 0.5);
+        assert!(schedule
+            .utilization_timeline("R9", 2000)
+            .iter()
+            .all(|b| b.busy_ms == 0));
+        assert!(schedule.utilization_timeline("R1", 0).is_empty());
+    }
+
+    #[test]
+    fn test_indexed_lookups() {
+        let mut schedule = Schedule::new();
+        schedule.add_assignment(Assignment::new("A2", "T1", "R1", 5000, 8000));
+        schedule.add_assignment(Assignment::new("A1", "T1", "R1", 0, 5000));
+        schedule.add_assignment(Assignment::new("B1", "T2", "R2", 0, 4000));
+
+        assert_eq!(
+            schedule.assignment_for_activity("B1").unwrap().task_id,
+            "T2"
+        );
+        assert!(schedule.assignment_for_activity("C1").is_none());
+        assert_eq!(schedule.assignments_for_task("T1").len(), 2);
+        let ids = |list: Vec<&Assignment>| {
+            list.iter()
+                .map(|a| a.activity_id.clone())
+                .collect::<Vec<_>>()
+        };
+        // Resource lookups come in start order
+        assert_eq!(
+            ids(schedule.assignments_for_resource("R1")),
+            vec!["A1", "A2"]
+        );
+
+        // Direct edits: pushes are picked up, in-place edits after reindex
+        schedule
+            .assignments
+            .push(Assignment::new("C1", "T3", "R2", 4000, 6000));
+        assert_eq!(schedule.task_completion_time("T3"), Some(6000));
+        schedule.assignments[2].resource_id = "R1".to_string();
+        schedule.reindex();
+        assert_eq!(
+            ids(schedule.assignments_for_resource("R1")),
+            vec!["A1", "B1", "A2"]
+        );
+        assert_eq!(ids(schedule.assignments_for_resource("R2")), vec!["C1"]);
+        // Clones rebuild their own index
+        assert_eq!(
+            ids(schedule.clone().assignments_for_task("T1")),
+            vec!["A2", "A1"]
+        );
+    }
+
+    #[test]
+    fn test_schedule_equality() {
+        use std::collections::hash_map::DefaultHasher;
+        use std::hash::{Hash, Hasher};
+
+        let a1 = Assignment::new("A1", "T1", "R1", 0, 5000);
+        let a2 = Assignment::new("A2", "T2", "R2", 0, 3000).with_setup(500);
+        let mut left = Schedule::new();
+        left.add_assignment(a1.clone());
+        left.add_assignment(a2.clone());
+        let mut right = Schedule::new();
+        right.add_assignment(a2);
+        right.add_assignment(a1);
+        assert_eq!(left.to_normalized_json(), right.to_normalized_json());
+        assert!(left
+            .to_normalized_json()
+            .contains("\"activity_id\": \"A1\""));
+        right.planned_confidence.insert("A1".to_string(), 0.9);
+
+        // Order and diagnostics do not matter
+        assert_eq!(left, right);
+        crate::assert_schedule_eq!(left, right);
+        let hash = |s: &Schedule| {
+            let mut hasher = DefaultHasher::new();
+            s.hash(&mut hasher);
+            hasher.finish()
+        };
+        assert_eq!(hash(&left), hash(&right));
+        assert_eq!(
+            left.normalized().assignments,
+            right.normalized().assignments
+        );
+
+        right.assignments[0].start_ms = 1000;
+        right.add_violation(Violation::deadline_miss("T2", "Late"));
+        assert_ne!(left, right);
+        assert_eq!(
+            left.diff(&right),
+            vec![
+                "- A2 (T2) on R2 0..3000 ms (setup 500 ms)",
+                "+ A2 (T2) on R2 1000..3000 ms (setup 500 ms)",
+                "+ violation T2: Late",
+            ]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "schedules differ (- left, + right):\n  makespan 5000 ms != 0 ms")]
+    fn test_assert_schedule_eq_reports_diff() {
+        let mut left = Schedule::new();
+        left.add_assignment(Assignment::new("A1", "T1", "R1", 0, 5000));
+        crate::assert_schedule_eq!(left, Schedule::new());
+    }
+
     #[test]
     fn test_violations() {
         let mut schedule = Schedule::new();
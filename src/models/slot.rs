@@ -0,0 +1,208 @@
+//! Slot - Discretized time grid
+//!
+//! Divides the horizon into fixed slots (15 min, 1 hour) or explicit
+//! periods (class periods) so activities start and end on slot boundaries.
+
+use super::schedule::Schedule;
+use super::time_unit::TimeUnit;
+use serde::{Deserialize, Serialize};
+
+/// Slot grid for appointment-style scheduling
+///
+/// Domain mappings:
+/// - Healthcare: 15-minute clinic appointment slots
+/// - Education: Class periods with breaks in between
+/// - Services: Hourly booking slots
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SlotGrid {
+    /// Start of the first fixed slot (epoch ms)
+    pub origin_ms: i64,
+    /// Fixed slot length (ms)
+    pub slot_ms: i64,
+    /// Explicit slots `[start, end)` in time order; replace the fixed grid if set
+    #[serde(default)]
+    pub periods: Vec<(i64, i64)>,
+}
+
+/// One slot occupied by an assignment
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SlotBooking {
+    /// Slot index in the grid
+    pub slot: usize,
+    /// Slot start (epoch ms)
+    pub start_ms: i64,
+    /// Slot end (epoch ms)
+    pub end_ms: i64,
+    /// Booked resource
+    pub resource_id: String,
+    /// Booked activity
+    pub activity_id: String,
+}
+
+impl SlotGrid {
+    /// Create grid of equal slots starting at `origin_ms`
+    pub fn fixed(origin_ms: i64, slot_ms: i64) -> Self {
+        Self {
+            origin_ms,
+            slot_ms: slot_ms.max(1),
+            periods: Vec::new(),
+        }
+    }
+
+    /// Create grid from explicit periods
+    pub fn periods(mut periods: Vec<(i64, i64)>) -> Self {
+        periods.sort();
+        Self {
+            origin_ms: periods.first().map_or(0, |p| p.0),
+            slot_ms: 1,
+            periods,
+        }
+    }
+
+    /// Fixed slot length as a time unit
+    pub fn unit(&self) -> TimeUnit {
+        TimeUnit::Slots {
+            slot_ms: self.slot_ms,
+        }
+    }
+
+    /// Slot `index` as `[start, end)`
+    pub fn slot(&self, index: usize) -> Option<(i64, i64)> {
+        if self.periods.is_empty() {
+            let start = self.origin_ms + index as i64 * self.slot_ms;
+            Some((start, start + self.slot_ms))
+        } else {
+            self.periods.get(index).copied()
+        }
+    }
+
+    /// Index of the slot containing `time_ms`
+    pub fn slot_index(&self, time_ms: i64) -> Option<usize> {
+        if self.periods.is_empty() {
+            (time_ms >= self.origin_ms)
+                .then(|| ((time_ms - self.origin_ms) / self.slot_ms) as usize)
+        } else {
+            self.periods
+                .iter()
+                .position(|&(start, end)| start <= time_ms && time_ms < end)
+        }
+    }
+
+    /// Earliest slot start at or after `time_ms`
+    pub fn next_start(&self, time_ms: i64) -> Option<i64> {
+        if self.periods.is_empty() {
+            let offset = (time_ms - self.origin_ms).max(0);
+            let slots = (offset + self.slot_ms - 1) / self.slot_ms;
+            Some(self.origin_ms + slots * self.slot_ms)
+        } else {
+            self.periods
+                .iter()
+                .map(|p| p.0)
+                .find(|&start| start >= time_ms)
+        }
+    }
+
+    /// Earliest slot end at or after `time_ms`
+    pub fn next_end(&self, time_ms: i64) -> Option<i64> {
+        if self.periods.is_empty() {
+            self.next_start(time_ms.max(self.origin_ms + 1))
+        } else {
+            self.periods.iter().map(|p| p.1).find(|&end| end >= time_ms)
+        }
+    }
+
+    /// Snap a job of `duration_ms` ready at `ready_ms` onto the grid
+    ///
+    /// Starts at the next slot start and occupies whole slots until the
+    /// work is done.
+    pub fn snap(&self, ready_ms: i64, duration_ms: i64) -> Option<(i64, i64)> {
+        let start = self.next_start(ready_ms)?;
+        let end = self.next_end(start + duration_ms.max(1))?;
+        Some((start, end))
+    }
+
+    /// Check if an interval starts and ends on slot boundaries
+    pub fn is_aligned(&self, start_ms: i64, end_ms: i64) -> bool {
+        self.next_start(start_ms) == Some(start_ms) && self.next_end(end_ms) == Some(end_ms)
+    }
+
+    /// Slots overlapped by `[start_ms, end_ms)`
+    pub fn slots_between(&self, start_ms: i64, end_ms: i64) -> Vec<(usize, i64, i64)> {
+        let first = if self.periods.is_empty() {
+            self.slot_index(start_ms.max(self.origin_ms)).unwrap_or(0)
+        } else {
+            0
+        };
+        (first..)
+            .map_while(|i| self.slot(i).map(|(s, e)| (i, s, e)))
+            .take_while(|&(_, s, _)| s < end_ms)
+            .filter(|&(_, _, e)| e > start_ms)
+            .collect()
+    }
+
+    /// Export slot occupancy of a schedule, one entry per booked slot
+    pub fn bookings(&self, schedule: &Schedule) -> Vec<SlotBooking> {
+        let mut bookings: Vec<SlotBooking> = schedule
+            .assignments
+            .iter()
+            .flat_map(|a| {
+                self.slots_between(a.start_ms, a.end_ms).into_iter().map(
+                    move |(slot, start_ms, end_ms)| SlotBooking {
+                        slot,
+                        start_ms,
+                        end_ms,
+                        resource_id: a.resource_id.clone(),
+                        activity_id: a.activity_id.clone(),
+                    },
+                )
+            })
+            .collect();
+        bookings.sort_by(|a, b| {
+            (a.slot, &a.resource_id, &a.activity_id).cmp(&(b.slot, &b.resource_id, &b.activity_id))
+        });
+        bookings
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Assignment;
+
+    const MIN: i64 = 60_000;
+
+    #[test]
+    fn test_fixed_grid() {
+        let grid = SlotGrid::fixed(0, 15 * MIN);
+
+        assert_eq!(grid.next_start(MIN), Some(15 * MIN));
+        assert_eq!(grid.next_start(15 * MIN), Some(15 * MIN));
+        assert_eq!(grid.snap(MIN, 20 * MIN), Some((15 * MIN, 45 * MIN)));
+        assert_eq!(grid.slot_index(44 * MIN), Some(2));
+        assert!(grid.is_aligned(15 * MIN, 45 * MIN));
+        assert!(!grid.is_aligned(15 * MIN, 40 * MIN));
+    }
+
+    #[test]
+    fn test_periods() {
+        // Two class periods with a break in between
+        let grid = SlotGrid::periods(vec![(60 * MIN, 110 * MIN), (0, 50 * MIN)]);
+
+        assert_eq!(grid.snap(10 * MIN, 45 * MIN), Some((60 * MIN, 110 * MIN)));
+        assert_eq!(grid.snap(0, 30 * MIN), Some((0, 50 * MIN)));
+        assert_eq!(grid.snap(70 * MIN, 10 * MIN), None);
+        assert_eq!(grid.slot_index(55 * MIN), None);
+    }
+
+    #[test]
+    fn test_bookings() {
+        let grid = SlotGrid::fixed(0, 30 * MIN);
+        let mut schedule = Schedule::new();
+        schedule.add_assignment(Assignment::new("A1", "T1", "Dr1", 30 * MIN, 90 * MIN));
+
+        let bookings = grid.bookings(&schedule);
+        assert_eq!(bookings.len(), 2);
+        assert_eq!(bookings[0].slot, 1);
+        assert_eq!(bookings[1].start_ms, 60 * MIN);
+    }
+}
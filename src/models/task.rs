@@ -6,6 +6,28 @@ use super::activity::Activity;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
+/// Strict priority class (higher = more important)
+///
+/// Unlike the scalar `priority`, classes are absolute tiers: schedulers
+/// place every task of a higher class before any task of a lower one, so
+/// lower-class work never delays higher-class work. Dispatching rules and
+/// priorities order tasks within a class.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(transparent)]
+pub struct PriorityClass(pub i32);
+
+impl PriorityClass {
+    /// Routine work (the default class)
+    pub const ROUTINE: Self = Self(0);
+    /// Urgent work, ahead of routine work
+    pub const URGENT: Self = Self(1);
+    /// Emergencies, ahead of everything else
+    pub const EMERGENCY: Self = Self(2);
+}
+
 /// Task - Abstract schedulable work unit
 ///
 /// Domain mappings:
@@ -15,6 +37,7 @@ use serde::{Deserialize, Serialize};
 /// - Education: Course, Exam Session
 /// - Cloud: Job, Workflow
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Task {
     /// Unique identifier
     pub id: String,
@@ -24,14 +47,28 @@ pub struct Task {
     pub category: String,
     /// Priority (higher = more important)
     pub priority: i32,
-    /// Deadline for completion
+    /// Weight in weighted objectives (weighted tardiness, WSPT, ATC);
+    /// if not set, KPIs and costs use 1.0 and WSPT/ATC derive it from
+    /// `priority`
+    #[serde(default)]
+    pub weight: Option<f64>,
+    /// Strict priority class, above `priority`
+    #[serde(default)]
+    pub priority_class: PriorityClass,
+    /// Deadline for completion (hard: finishing later is infeasible)
     pub deadline: Option<DateTime<Utc>>,
+    /// Target completion date (soft: tardiness is measured against it)
+    #[serde(default)]
+    pub target_date: Option<DateTime<Utc>>,
     /// Earliest start time
     pub release_time: Option<DateTime<Utc>>,
     /// Activities that comprise this task
     pub activities: Vec<Activity>,
     /// Custom attributes for domain-specific data
     pub attributes: std::collections::HashMap<String, String>,
+    /// Load carried on a vehicle resource (weight, volume, pallets)
+    #[serde(default)]
+    pub loads: std::collections::HashMap<String, f64>,
 }
 
 impl Task {
@@ -42,55 +79,162 @@ impl Task {
             name: id.to_string(),
             category: String::new(),
             priority: 1,
+            weight: None,
+            priority_class: PriorityClass::ROUTINE,
             deadline: None,
+            target_date: None,
             release_time: None,
             activities: Vec::new(),
             attributes: std::collections::HashMap::new(),
+            loads: std::collections::HashMap::new(),
         }
     }
 
     /// Set task name
     pub fn with_name(mut self, name: &str) -> Self {
-        self.name = name.to_string();
+        self.set_name(name);
         self
     }
 
+    /// Set task name
+    pub fn set_name(&mut self, name: &str) {
+        self.name = name.to_string();
+    }
+
     /// Set category
     pub fn with_category(mut self, category: &str) -> Self {
-        self.category = category.to_string();
+        self.set_category(category);
         self
     }
 
+    /// Set category
+    pub fn set_category(&mut self, category: &str) {
+        self.category = category.to_string();
+    }
+
     /// Set priority
     pub fn with_priority(mut self, priority: i32) -> Self {
+        self.set_priority(priority);
+        self
+    }
+
+    /// Set priority
+    pub fn set_priority(&mut self, priority: i32) {
         self.priority = priority;
+    }
+
+    /// Set strict priority class
+    pub fn with_priority_class(mut self, class: PriorityClass) -> Self {
+        self.set_priority_class(class);
+        self
+    }
+
+    /// Set strict priority class
+    pub fn set_priority_class(&mut self, class: PriorityClass) {
+        self.priority_class = class;
+    }
+
+    /// Set weight (clamped to >= 0)
+    pub fn with_weight(mut self, weight: f64) -> Self {
+        self.set_weight(weight);
         self
     }
 
+    /// Set weight (clamped to >= 0)
+    pub fn set_weight(&mut self, weight: f64) {
+        self.weight = Some(weight.max(0.0));
+    }
+
+    /// Weight in weighted objectives (1.0 if not set)
+    pub fn objective_weight(&self) -> f64 {
+        self.weight.unwrap_or(1.0)
+    }
+
+    /// Weight for weighted dispatching rules (WSPT, ATC)
+    ///
+    /// Falls back to `1000 / (priority + 1)` if not set.
+    pub fn dispatch_weight(&self) -> f64 {
+        self.weight
+            .unwrap_or_else(|| 1000.0 / (self.priority as f64 + 1.0))
+    }
+
     /// Set deadline
     pub fn with_deadline(mut self, deadline: DateTime<Utc>) -> Self {
+        self.set_deadline(deadline);
+        self
+    }
+
+    /// Set deadline
+    pub fn set_deadline(&mut self, deadline: DateTime<Utc>) {
         self.deadline = Some(deadline);
+    }
+
+    /// Set target completion date
+    pub fn with_target_date(mut self, target: DateTime<Utc>) -> Self {
+        self.set_target_date(target);
         self
     }
 
+    /// Set target completion date
+    pub fn set_target_date(&mut self, target: DateTime<Utc>) {
+        self.target_date = Some(target);
+    }
+
+    /// Date tardiness and earliness are measured against: the target
+    /// date if set, else the deadline
+    pub fn due_date(&self) -> Option<DateTime<Utc>> {
+        self.target_date.or(self.deadline)
+    }
+
     /// Set release time
     pub fn with_release_time(mut self, release_time: DateTime<Utc>) -> Self {
-        self.release_time = Some(release_time);
+        self.set_release_time(release_time);
         self
     }
 
+    /// Set release time
+    pub fn set_release_time(&mut self, release_time: DateTime<Utc>) {
+        self.release_time = Some(release_time);
+    }
+
     /// Add activity
     pub fn with_activity(mut self, activity: Activity) -> Self {
-        self.activities.push(activity);
+        self.add_activity(activity);
         self
     }
 
+    /// Add activity
+    pub fn add_activity(&mut self, activity: Activity) {
+        self.activities.push(activity);
+    }
+
     /// Add custom attribute
     pub fn with_attribute(mut self, key: &str, value: &str) -> Self {
+        self.set_attribute(key, value);
+        self
+    }
+
+    /// Add custom attribute
+    pub fn set_attribute(&mut self, key: &str, value: &str) {
         self.attributes.insert(key.to_string(), value.to_string());
+    }
+
+    /// Set load in a dimension (weight, volume)
+    pub fn with_load(mut self, dimension: &str, amount: f64) -> Self {
+        self.set_load(dimension, amount);
         self
     }
 
+    /// Set load in a dimension (weight, volume)
+    pub fn set_load(&mut self, dimension: &str, amount: f64) {
+        self.loads.insert(dimension.to_string(), amount);
+    }
+
+    /// Load in a dimension (0.0 if not declared)
+    pub fn load(&self, dimension: &str) -> f64 {
+        self.loads.get(dimension).copied().unwrap_or(0.0)
+    }
+
     /// Get total estimated duration
     pub fn total_duration_ms(&self) -> i64 {
         self.activities.iter().map(|a| a.duration.process_ms).sum()
@@ -123,6 +267,13 @@ mod tests {
         assert_eq!(task.id, "T1");
         assert_eq!(task.name, "Test Task");
         assert_eq!(task.priority, 5);
+        assert_eq!(task.objective_weight(), 1.0);
+        assert_eq!(task.dispatch_weight(), 1000.0 / 6.0);
+        assert_eq!(task.priority_class, PriorityClass::ROUTINE);
+        let task = task.with_weight(-2.0);
+        assert_eq!(task.objective_weight(), 0.0);
+        assert_eq!(task.dispatch_weight(), 0.0);
+        assert!(PriorityClass::EMERGENCY > PriorityClass::URGENT);
     }
 
     #[test]
@@ -138,4 +289,20 @@ mod tests {
         assert_eq!(task.activities.len(), 2);
         assert_eq!(task.total_duration_ms(), 8000);
     }
+
+    #[test]
+    fn test_in_place_setters() {
+        let mut tasks = vec![Task::new("T1"), Task::new("T2")];
+        for task in &mut tasks {
+            task.set_priority(5);
+            task.add_activity(Activity::new(&format!("{}-A1", task.id), &task.id, 1));
+        }
+        let activity = &mut tasks[1].activities[0];
+        activity.set_time(100, 1000, 0);
+        activity.add_predecessor("T1-A1");
+
+        assert!(tasks.iter().all(|t| t.priority == 5 && t.has_activities()));
+        assert_eq!(tasks[1].total_duration_ms(), 1000);
+        assert_eq!(tasks[1].activities[0].predecessors, vec!["T1-A1"]);
+    }
 }
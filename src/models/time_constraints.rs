@@ -13,20 +13,15 @@ use serde::{Deserialize, Serialize};
 // ================================
 
 /// Time window constraint type
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
 pub enum TimeWindowType {
     /// Must be satisfied (schedule invalid if violated)
     Hard,
     /// Should be satisfied (penalty if violated)
+    #[default]
     Soft,
 }
 
-impl Default for TimeWindowType {
-    fn default() -> Self {
-        TimeWindowType::Soft
-    }
-}
-
 /// Time window constraint for activities
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TimeWindow {
@@ -42,6 +37,10 @@ pub struct TimeWindow {
     pub window_type: TimeWindowType,
     /// Penalty per millisecond of violation (for soft constraints)
     pub penalty_per_ms: f64,
+    /// Penalty per millisecond too early, if it differs from
+    /// `penalty_per_ms`
+    #[serde(default)]
+    pub earliness_penalty_per_ms: Option<f64>,
 }
 
 impl TimeWindow {
@@ -54,6 +53,7 @@ impl TimeWindow {
             latest_end_ms: None,
             window_type: TimeWindowType::Soft,
             penalty_per_ms: 1.0,
+            earliness_penalty_per_ms: None,
         }
     }
 
@@ -66,6 +66,7 @@ impl TimeWindow {
             latest_end_ms: Some(end_ms),
             window_type: TimeWindowType::Soft,
             penalty_per_ms: 1.0,
+            earliness_penalty_per_ms: None,
         }
     }
 
@@ -78,6 +79,7 @@ impl TimeWindow {
             latest_end_ms: Some(deadline_ms),
             window_type: TimeWindowType::Hard,
             penalty_per_ms: 0.0,
+            earliness_penalty_per_ms: None,
         }
     }
 
@@ -90,6 +92,7 @@ impl TimeWindow {
             latest_end_ms: None,
             window_type: TimeWindowType::Hard,
             penalty_per_ms: 0.0,
+            earliness_penalty_per_ms: None,
         }
     }
 
@@ -97,6 +100,7 @@ impl TimeWindow {
     pub fn hard(mut self) -> Self {
         self.window_type = TimeWindowType::Hard;
         self.penalty_per_ms = 0.0;
+        self.earliness_penalty_per_ms = None;
         self
     }
 
@@ -107,6 +111,21 @@ impl TimeWindow {
         self
     }
 
+    /// Set separate penalty per millisecond too early
+    ///
+    /// Weighs earliness (storage, perishables, waiting) against tardiness;
+    /// 0.0 makes early completion free.
+    pub fn with_earliness_penalty(mut self, penalty_per_ms: f64) -> Self {
+        self.earliness_penalty_per_ms = Some(penalty_per_ms);
+        self
+    }
+
+    /// Set earliest end
+    pub fn with_earliest_end(mut self, ms: i64) -> Self {
+        self.earliest_end_ms = Some(ms);
+        self
+    }
+
     /// Set earliest start
     pub fn with_earliest_start(mut self, ms: i64) -> Self {
         self.earliest_start_ms = Some(ms);
@@ -166,7 +185,9 @@ impl TimeWindow {
             } else {
                 ViolationSeverity::Minor
             },
-            penalty: (total_early_ms + total_late_ms) as f64 * self.penalty_per_ms,
+            penalty: total_early_ms as f64
+                * self.earliness_penalty_per_ms.unwrap_or(self.penalty_per_ms)
+                + total_late_ms as f64 * self.penalty_per_ms,
         })
     }
 }
@@ -316,7 +337,8 @@ impl ConstraintViolation {
 ///
 /// Mean = (O + 4M + P) / 6
 /// StdDev = (P - O) / 6
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct PertEstimate {
     /// Optimistic duration (ms)
     pub optimistic_ms: i64,
@@ -405,22 +427,13 @@ impl PertEstimate {
     }
 }
 
-impl Default for PertEstimate {
-    fn default() -> Self {
-        Self {
-            optimistic_ms: 0,
-            most_likely_ms: 0,
-            pessimistic_ms: 0,
-        }
-    }
-}
-
 // ================================
 // Probabilistic Scheduling Support
 // ================================
 
 /// Duration distribution for probabilistic scheduling
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum DurationDistribution {
     /// Fixed duration (deterministic)
     Fixed(i64),
@@ -485,6 +498,48 @@ impl DurationDistribution {
         }
     }
 
+    /// Draw a random duration (never negative)
+    ///
+    /// PERT uses the same normal approximation as `duration_at_confidence`,
+    /// truncated to the optimistic/pessimistic range.
+    pub fn sample<R: rand::Rng + ?Sized>(&self, rng: &mut R) -> i64 {
+        let duration = match self {
+            DurationDistribution::Fixed(d) => *d as f64,
+            DurationDistribution::Pert(p) => (p.mean_ms() + p.std_dev_ms() * standard_normal_sample(rng))
+                .clamp(p.optimistic_ms as f64, p.pessimistic_ms.max(p.optimistic_ms) as f64),
+            DurationDistribution::Uniform { min_ms, max_ms } => {
+                if max_ms > min_ms {
+                    rng.gen_range(*min_ms..=*max_ms) as f64
+                } else {
+                    *min_ms as f64
+                }
+            }
+            DurationDistribution::Triangular {
+                min_ms,
+                mode_ms,
+                max_ms,
+            } => {
+                let range = (*max_ms - *min_ms) as f64;
+                if range <= 0.0 {
+                    *min_ms as f64
+                } else {
+                    // Inverse CDF
+                    let u: f64 = rng.gen();
+                    let fc = (*mode_ms - *min_ms) as f64 / range;
+                    if u < fc {
+                        *min_ms as f64 + (u * range * (*mode_ms - *min_ms) as f64).sqrt()
+                    } else {
+                        *max_ms as f64 - ((1.0 - u) * range * (*max_ms - *mode_ms) as f64).sqrt()
+                    }
+                }
+            }
+            DurationDistribution::LogNormal { mu, sigma } => {
+                (mu + sigma * standard_normal_sample(rng)).exp()
+            }
+        };
+        duration.max(0.0).round() as i64
+    }
+
     /// Create from PERT estimates
     pub fn from_pert(optimistic: i64, most_likely: i64, pessimistic: i64) -> Self {
         DurationDistribution::Pert(PertEstimate::new(optimistic, most_likely, pessimistic))
@@ -524,6 +579,13 @@ fn confidence_to_z(confidence: f64) -> f64 {
     t - (c0 + c1 * t + c2 * t * t) / (1.0 + d1 * t + d2 * t * t + d3 * t * t * t)
 }
 
+/// Standard normal sample (Box-Muller)
+fn standard_normal_sample<R: rand::Rng + ?Sized>(rng: &mut R) -> f64 {
+    let u1: f64 = rng.gen::<f64>().max(f64::MIN_POSITIVE);
+    let u2: f64 = rng.gen();
+    (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos()
+}
+
 /// Standard normal CDF approximation
 fn standard_normal_cdf(x: f64) -> f64 {
     // Approximation using error function
@@ -576,6 +638,23 @@ mod tests {
         assert!((vs.penalty - 2000.0).abs() < 0.01); // 1000ms * 2.0
     }
 
+    #[test]
+    fn test_earliness_penalty() {
+        // Due at 5000: finishing before 4000 is too early
+        let window = TimeWindow::new()
+            .with_earliest_end(4000)
+            .with_due_date(5000)
+            .soft(2.0)
+            .with_earliness_penalty(0.5);
+
+        let early = window.check_violation(0, 3000).unwrap();
+        assert!(early.is_early() && !early.is_tardy());
+        assert!((early.penalty - 500.0).abs() < 0.01); // 1000ms * 0.5
+        let late = window.check_violation(0, 6000).unwrap();
+        assert!((late.penalty - 2000.0).abs() < 0.01);
+        assert!(window.check_violation(0, 4500).is_none());
+    }
+
     #[test]
     fn test_pert_calculation() {
         // Classic PERT example: O=4, M=6, P=14 (in hours, but same math applies)
@@ -632,6 +711,26 @@ mod tests {
         assert!((triangular.expected_duration_ms() - 5000.0).abs() < 0.01);
     }
 
+    #[test]
+    fn test_duration_distribution_sampling() {
+        use rand::SeedableRng;
+        let mut rng = rand::rngs::StdRng::seed_from_u64(7);
+
+        let triangular = DurationDistribution::Triangular {
+            min_ms: 3000,
+            mode_ms: 5000,
+            max_ms: 7000,
+        };
+        let samples: Vec<i64> = (0..2000).map(|_| triangular.sample(&mut rng)).collect();
+        assert!(samples.iter().all(|&d| (3000..=7000).contains(&d)));
+        let mean = samples.iter().sum::<i64>() as f64 / samples.len() as f64;
+        assert!((mean - 5000.0).abs() < 100.0);
+
+        let pert = DurationDistribution::from_pert(6000, 10000, 14000);
+        assert!((0..200).all(|_| (6000..=14000).contains(&pert.sample(&mut rng))));
+        assert_eq!(DurationDistribution::Fixed(42).sample(&mut rng), 42);
+    }
+
     #[test]
     fn test_constraint_violation_creation() {
         let tw_v = ConstraintViolation::time_window("OP-001", 5000, ViolationSeverity::Minor, 500.0);
@@ -0,0 +1,102 @@
+//! Time Unit - Unit conversion at the API boundary
+//!
+//! All times are stored as i64 milliseconds internally; `TimeUnit`
+//! converts user-facing amounts (minutes, hours, slots) to and from that
+//! canonical form.
+
+use serde::{Deserialize, Serialize};
+
+/// Milliseconds per second
+const MS_PER_SECOND: i64 = 1_000;
+/// Milliseconds per minute
+const MS_PER_MINUTE: i64 = 60 * MS_PER_SECOND;
+/// Milliseconds per hour
+const MS_PER_HOUR: i64 = 60 * MS_PER_MINUTE;
+/// Milliseconds per day
+const MS_PER_DAY: i64 = 24 * MS_PER_HOUR;
+
+/// Unit of time
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TimeUnit {
+    /// Milliseconds (canonical)
+    #[default]
+    Milliseconds,
+    /// Seconds
+    Seconds,
+    /// Minutes
+    Minutes,
+    /// Hours
+    Hours,
+    /// Days (24 hours)
+    Days,
+    /// Fixed-length slots (e.g., 15-minute appointments)
+    Slots { slot_ms: i64 },
+}
+
+impl TimeUnit {
+    /// Length of one unit (ms)
+    pub fn ms_per_unit(&self) -> i64 {
+        match self {
+            TimeUnit::Milliseconds => 1,
+            TimeUnit::Seconds => MS_PER_SECOND,
+            TimeUnit::Minutes => MS_PER_MINUTE,
+            TimeUnit::Hours => MS_PER_HOUR,
+            TimeUnit::Days => MS_PER_DAY,
+            TimeUnit::Slots { slot_ms } => (*slot_ms).max(1),
+        }
+    }
+
+    /// Convert an amount in this unit to milliseconds (rounded)
+    pub fn to_ms(&self, amount: f64) -> i64 {
+        (amount * self.ms_per_unit() as f64).round() as i64
+    }
+
+    /// Convert milliseconds to an amount in this unit
+    pub fn from_ms(&self, ms: i64) -> f64 {
+        ms as f64 / self.ms_per_unit() as f64
+    }
+
+    /// Convert an amount in this unit to another unit
+    pub fn convert(&self, amount: f64, to: TimeUnit) -> f64 {
+        to.from_ms(self.to_ms(amount))
+    }
+
+    /// Parse a unit symbol (`ms`, `s`, `min`, `h`, `d`)
+    pub fn from_symbol(symbol: &str) -> Option<Self> {
+        match symbol.trim().to_ascii_lowercase().as_str() {
+            "ms" | "millis" | "milliseconds" => Some(TimeUnit::Milliseconds),
+            "s" | "sec" | "seconds" => Some(TimeUnit::Seconds),
+            "m" | "min" | "minutes" => Some(TimeUnit::Minutes),
+            "h" | "hr" | "hours" => Some(TimeUnit::Hours),
+            "d" | "days" => Some(TimeUnit::Days),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_conversion() {
+        assert_eq!(TimeUnit::Minutes.to_ms(15.0), 900_000);
+        assert_eq!(TimeUnit::Hours.from_ms(5_400_000), 1.5);
+        assert_eq!(TimeUnit::Hours.convert(2.0, TimeUnit::Minutes), 120.0);
+
+        let slot = TimeUnit::Slots { slot_ms: 900_000 };
+        assert_eq!(slot.to_ms(4.0), 3_600_000);
+        assert_eq!(TimeUnit::Hours.convert(1.0, slot), 4.0);
+    }
+
+    #[test]
+    fn test_symbols_and_serde() {
+        assert_eq!(TimeUnit::from_symbol("min"), Some(TimeUnit::Minutes));
+        assert_eq!(TimeUnit::from_symbol("H"), Some(TimeUnit::Hours));
+        assert_eq!(TimeUnit::from_symbol("fortnight"), None);
+
+        let json = serde_json::to_string(&TimeUnit::Minutes).unwrap();
+        assert_eq!(json, "\"minutes\"");
+    }
+}
@@ -0,0 +1,174 @@
+//! Trace - Audit log of scheduler decisions
+//!
+//! Records, in the order they were made, which candidates the scheduler
+//! considered for each activity, how they scored, which one it chose and
+//! why the others lost.
+
+use serde::{Deserialize, Serialize};
+
+/// Why a candidate was or was not chosen
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum CandidateOutcome {
+    /// Selected for the activity
+    Chosen,
+    /// Feasible, but another candidate scored better
+    Outscored,
+    /// Same score as the chosen one; lost on workload balance, packing
+    /// fit or candidate order
+    TieBreak,
+    /// Would start after the activity's latest start (deadline pruning)
+    PastLatestStart,
+    /// Not among the resources given to the scheduler
+    UnknownResource,
+    /// Cannot carry the task's load or host the activity's demands
+    InsufficientCapacity,
+    /// Setup needed but the resource's setup crew is unknown
+    NoSetupCrew,
+    /// Working-time rules leave no room for it
+    WorkingTimeRules,
+    /// No slot left on the slot grid
+    NoSlot,
+    /// The resource calendar has no working time left for it
+    CalendarExhausted,
+}
+
+/// One candidate considered for an activity
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct CandidateRecord {
+    /// Candidate resource
+    pub resource_id: String,
+    /// Earliest start on it (None if infeasible)
+    pub start_ms: Option<i64>,
+    /// Earliest finish on it (None if infeasible)
+    pub finish_ms: Option<i64>,
+    /// Selection score, lower is better: finish adjusted by preferences
+    /// and reliability (None if infeasible)
+    pub score: Option<i64>,
+    /// Verdict
+    pub outcome: CandidateOutcome,
+}
+
+/// Placement decision for one activity
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Decision {
+    /// Position in the decision sequence (0-based)
+    pub sequence: usize,
+    /// Task ID
+    pub task_id: String,
+    /// Activity ID
+    pub activity_id: String,
+    /// Earliest start allowed by its task and constraints
+    pub ready_ms: i64,
+    /// Every candidate considered
+    pub candidates: Vec<CandidateRecord>,
+    /// Resources the activity was assigned to (empty if unplaced)
+    pub chosen: Vec<String>,
+    /// Final start (None if unplaced)
+    pub start_ms: Option<i64>,
+    /// Final end (None if unplaced)
+    pub end_ms: Option<i64>,
+}
+
+impl Decision {
+    /// Candidates that lost, with their outcome
+    pub fn rejected(&self) -> impl Iterator<Item = &CandidateRecord> {
+        self.candidates
+            .iter()
+            .filter(|c| c.outcome != CandidateOutcome::Chosen)
+    }
+}
+
+/// Ordered log of a scheduler run
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct DecisionTrace {
+    /// Decisions in the order they were made
+    pub decisions: Vec<Decision>,
+}
+
+impl DecisionTrace {
+    /// Create empty trace
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a decision, numbering it
+    pub fn record(&mut self, mut decision: Decision) {
+        decision.sequence = self.decisions.len();
+        self.decisions.push(decision);
+    }
+
+    /// Decision for an activity
+    pub fn for_activity(&self, activity_id: &str) -> Option<&Decision> {
+        self.decisions.iter().find(|d| d.activity_id == activity_id)
+    }
+
+    /// Activities that could not be placed
+    pub fn unplaced(&self) -> Vec<&str> {
+        self.decisions
+            .iter()
+            .filter(|d| d.chosen.is_empty())
+            .map(|d| d.activity_id.as_str())
+            .collect()
+    }
+
+    /// Serialize as pretty-printed JSON
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decision(activity: &str, chosen: &[&str]) -> Decision {
+        Decision {
+            sequence: 99,
+            task_id: "T1".into(),
+            activity_id: activity.into(),
+            ready_ms: 0,
+            candidates: vec![
+                CandidateRecord {
+                    resource_id: "M1".into(),
+                    start_ms: Some(0),
+                    finish_ms: Some(1000),
+                    score: Some(1000),
+                    outcome: CandidateOutcome::Chosen,
+                },
+                CandidateRecord {
+                    resource_id: "M2".into(),
+                    start_ms: None,
+                    finish_ms: None,
+                    score: None,
+                    outcome: CandidateOutcome::NoSlot,
+                },
+            ],
+            chosen: chosen.iter().map(|s| s.to_string()).collect(),
+            start_ms: None,
+            end_ms: None,
+        }
+    }
+
+    #[test]
+    fn test_record_and_serialize() {
+        let mut trace = DecisionTrace::new();
+        trace.record(decision("A1", &["M1"]));
+        trace.record(decision("A2", &[]));
+
+        assert_eq!(trace.for_activity("A2").unwrap().sequence, 1);
+        assert_eq!(trace.unplaced(), vec!["A2"]);
+        let rejected: Vec<_> = trace.decisions[0].rejected().collect();
+        assert_eq!(rejected.len(), 1);
+        assert_eq!(rejected[0].resource_id, "M2");
+
+        let json = trace.to_json();
+        assert!(json.contains("\"no_slot\""));
+        let back: DecisionTrace = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, trace);
+    }
+}
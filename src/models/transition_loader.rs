@@ -0,0 +1,246 @@
+//! Transition loader - Setup matrices from CSV and JSON
+//!
+//! Large setup matrices are maintained in spreadsheets; these loaders
+//! read them into a `TransitionMatrixCollection`. Both formats list
+//! (from, to, resource, minutes) entries; see `validate_transitions` for
+//! symmetry and default checks after loading.
+
+use super::constraint::{TransitionMatrix, TransitionMatrixCollection};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Error loading a transition matrix file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransitionLoadError {
+    /// Where the problem is (`line 3`, `transitions[2]`, ...)
+    pub location: String,
+    /// What was wrong
+    pub message: String,
+}
+
+impl fmt::Display for TransitionLoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.location, self.message)
+    }
+}
+
+impl std::error::Error for TransitionLoadError {}
+
+/// JSON document layout
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct TransitionFile {
+    /// Default minutes per resource
+    #[serde(default)]
+    defaults: BTreeMap<String, f64>,
+    #[serde(default)]
+    transitions: Vec<TransitionEntry>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct TransitionEntry {
+    from: String,
+    to: String,
+    resource: String,
+    minutes: f64,
+}
+
+impl TransitionMatrixCollection {
+    /// Load matrices from CSV rows `from,to,resource,minutes`
+    ///
+    /// A header row, blank lines and `#` comments are skipped; fields may
+    /// be double-quoted. A `*,*` row sets the resource's default for
+    /// pairs not listed. Minutes may be fractional. Negative or
+    /// conflicting duplicate entries are rejected.
+    pub fn from_csv(text: &str) -> Result<Self, TransitionLoadError> {
+        let mut builder = Builder::default();
+        for (n, line) in text.lines().enumerate() {
+            let location = format!("line {}", n + 1);
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let fields: Vec<&str> = line
+                .split(',')
+                .map(|f| f.trim().trim_matches('"').trim())
+                .collect();
+            if builder.is_empty() && fields[0].eq_ignore_ascii_case("from") {
+                continue;
+            }
+            let [from, to, resource, minutes] = fields[..] else {
+                return Err(load_error(
+                    &location,
+                    &format!("expected 4 fields, found {}", fields.len()),
+                ));
+            };
+            let minutes: f64 = minutes
+                .parse()
+                .map_err(|_| load_error(&location, &format!("invalid minutes '{}'", minutes)))?;
+            builder.add(&location, from, to, resource, minutes)?;
+        }
+        Ok(builder.build())
+    }
+
+    /// Load matrices from JSON
+    ///
+    /// The document holds `transitions` (objects with `from`, `to`,
+    /// `resource` and `minutes`) and optional per-resource `defaults` in
+    /// minutes, e.g. `{"defaults": {"M1": 10}, "transitions": [...]}`.
+    pub fn from_json(text: &str) -> Result<Self, TransitionLoadError> {
+        let file: TransitionFile = serde_json::from_str(text)
+            .map_err(|e| load_error(&format!("line {}", e.line()), &e.to_string()))?;
+        let mut builder = Builder::default();
+        for (resource, minutes) in &file.defaults {
+            builder.add(
+                &format!("defaults.{}", resource),
+                "*",
+                "*",
+                resource,
+                *minutes,
+            )?;
+        }
+        for (i, entry) in file.transitions.iter().enumerate() {
+            builder.add(
+                &format!("transitions[{}]", i),
+                &entry.from,
+                &entry.to,
+                &entry.resource,
+                entry.minutes,
+            )?;
+        }
+        Ok(builder.build())
+    }
+}
+
+/// Entries per resource, checked as they are added
+#[derive(Default)]
+struct Builder {
+    /// resource -> (from, to) -> ms; ("*", "*") is the default
+    entries: BTreeMap<String, BTreeMap<(String, String), i64>>,
+}
+
+impl Builder {
+    fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    fn add(
+        &mut self,
+        location: &str,
+        from: &str,
+        to: &str,
+        resource: &str,
+        minutes: f64,
+    ) -> Result<(), TransitionLoadError> {
+        if resource.is_empty() || resource == "*" {
+            return Err(load_error(location, "missing resource"));
+        }
+        if (from == "*") != (to == "*") {
+            return Err(load_error(
+                location,
+                "'*' is only allowed in both from and to (resource default)",
+            ));
+        }
+        if !minutes.is_finite() || minutes < 0.0 {
+            return Err(load_error(
+                location,
+                &format!("minutes must be non-negative, got {}", minutes),
+            ));
+        }
+        let ms = (minutes * 60_000.0).round() as i64;
+        let key = (from.to_string(), to.to_string());
+        let matrix = self.entries.entry(resource.to_string()).or_default();
+        match matrix.get(&key) {
+            Some(&existing) if existing != ms => Err(load_error(
+                location,
+                &format!(
+                    "conflicting entry {} -> {} on {} ({} ms, earlier {} ms)",
+                    from, to, resource, ms, existing
+                ),
+            )),
+            _ => {
+                matrix.insert(key, ms);
+                Ok(())
+            }
+        }
+    }
+
+    fn build(self) -> TransitionMatrixCollection {
+        let mut collection = TransitionMatrixCollection::new();
+        for (resource, entries) in self.entries {
+            let mut matrix = TransitionMatrix::new(&resource, &resource);
+            for ((from, to), ms) in entries {
+                if from == "*" {
+                    matrix.default_ms = ms;
+                } else {
+                    matrix.set_transition(&from, &to, ms);
+                }
+            }
+            collection.add(matrix);
+        }
+        collection
+    }
+}
+
+fn load_error(location: &str, message: &str) -> TransitionLoadError {
+    TransitionLoadError {
+        location: location.to_string(),
+        message: message.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_csv() {
+        let csv = "\
+from,to,resource,minutes
+# paint line
+red,blue,M1,15
+blue,red,M1,10
+\"*\",\"*\",M1,5
+red,blue,M2,0.5
+red,blue,M1,15
+";
+        let matrices = TransitionMatrixCollection::from_csv(csv).unwrap();
+
+        assert_eq!(matrices.matrices.len(), 2);
+        assert_eq!(matrices.get_transition_time("M1", "red", "blue"), 900_000);
+        assert_eq!(matrices.get_transition_time("M1", "blue", "red"), 600_000);
+        assert_eq!(matrices.get_transition_time("M1", "red", "green"), 300_000);
+        assert_eq!(matrices.get_transition_time("M2", "red", "blue"), 30_000);
+
+        let err =
+            TransitionMatrixCollection::from_csv("red,blue,M1,15\nred,blue,M1,20").unwrap_err();
+        assert_eq!(err.location, "line 2");
+        assert!(TransitionMatrixCollection::from_csv("red,blue,M1").is_err());
+        assert!(TransitionMatrixCollection::from_csv("red,blue,M1,-1").is_err());
+        assert!(TransitionMatrixCollection::from_csv("red,*,M1,1").is_err());
+    }
+
+    #[test]
+    fn test_from_json() {
+        let json = r#"{
+            "defaults": {"M1": 5},
+            "transitions": [
+                {"from": "red", "to": "blue", "resource": "M1", "minutes": 15},
+                {"from": "blue", "to": "red", "resource": "M1", "minutes": 10}
+            ]
+        }"#;
+        let matrices = TransitionMatrixCollection::from_json(json).unwrap();
+
+        assert_eq!(matrices.get_transition_time("M1", "red", "blue"), 900_000);
+        assert_eq!(matrices.get_transition_time("M1", "red", "red"), 300_000);
+
+        let err = TransitionMatrixCollection::from_json(
+            r#"{"transitions": [{"from": "a", "to": "b", "resource": "", "minutes": 1}]}"#,
+        )
+        .unwrap_err();
+        assert_eq!(err.location, "transitions[0]");
+        assert!(TransitionMatrixCollection::from_json(r#"{"matrix": []}"#).is_err());
+    }
+}
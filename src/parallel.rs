@@ -0,0 +1,177 @@
+//! Parallel - Reproducible parallel execution
+//!
+//! Parallel components (GA evaluation and multi-start, Monte Carlo
+//! simulation, scenario sampling) give the same result for a fixed seed
+//! whether they run on one thread or many:
+//!
+//! - Every independent unit of work (sample, scenario, restart) draws
+//!   from its own random stream, derived from the seed and the unit's
+//!   index with [`stream_rng`], never from a generator shared across
+//!   threads.
+//! - Results are collected in index order and reduced sequentially, so
+//!   floating-point sums and tie-breaks do not depend on which thread
+//!   finished first.
+//!
+//! Where the work runs is set with one [`Parallelism`] handle (calling
+//! thread, rayon's global pool, or a dedicated or caller-owned pool),
+//! which the components share instead of spawning threads of their own.
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use rayon::prelude::*;
+use rayon::{ThreadPool, ThreadPoolBuilder};
+use std::sync::Arc;
+
+/// Thread pool parallel components run on
+///
+/// Cloning shares the pool, so one handle can be passed to the GA, the
+/// Monte Carlo simulators and the CP solver. Results do not depend on
+/// the choice.
+#[derive(Debug, Clone, Default)]
+pub struct Parallelism {
+    pool: Pool,
+}
+
+#[derive(Debug, Clone, Default)]
+enum Pool {
+    #[default]
+    Sequential,
+    Global,
+    Dedicated(Arc<ThreadPool>),
+}
+
+impl Parallelism {
+    /// Run on the calling thread (default)
+    pub fn sequential() -> Self {
+        Self::default()
+    }
+
+    /// Run on rayon's global pool (or the rayon pool the caller runs in)
+    pub fn global() -> Self {
+        Self { pool: Pool::Global }
+    }
+
+    /// Run on a dedicated pool of `workers` threads
+    ///
+    /// One worker runs sequentially and zero uses the global pool, as
+    /// does a pool whose threads cannot be spawned.
+    pub fn threads(workers: usize) -> Self {
+        match workers {
+            0 => Self::global(),
+            1 => Self::sequential(),
+            _ => ThreadPoolBuilder::new()
+                .num_threads(workers)
+                .thread_name(|i| format!("u-ras-worker-{}", i))
+                .build()
+                .map_or_else(|_| Self::global(), |pool| Self::pool(Arc::new(pool))),
+        }
+    }
+
+    /// Run on a caller-owned rayon pool
+    pub fn pool(pool: Arc<ThreadPool>) -> Self {
+        Self {
+            pool: Pool::Dedicated(pool),
+        }
+    }
+
+    /// Shortcut: global pool if `parallel`, else sequential
+    pub fn from_flag(parallel: bool) -> Self {
+        if parallel {
+            Self::global()
+        } else {
+            Self::sequential()
+        }
+    }
+
+    /// Whether work is spread over threads
+    pub fn is_parallel(&self) -> bool {
+        self.workers() > 1
+    }
+
+    /// Number of worker threads
+    pub fn workers(&self) -> usize {
+        match &self.pool {
+            Pool::Sequential => 1,
+            Pool::Global => rayon::current_num_threads(),
+            Pool::Dedicated(pool) => pool.current_num_threads(),
+        }
+    }
+
+    /// Run `op` inside the pool, so rayon work it starts uses the pool
+    pub fn install<R: Send>(&self, op: impl FnOnce() -> R + Send) -> R {
+        match &self.pool {
+            Pool::Dedicated(pool) => pool.install(op),
+            _ => op(),
+        }
+    }
+
+    /// Map `0..count` in index order
+    pub(crate) fn map_indexed<T: Send>(
+        &self,
+        count: usize,
+        f: impl Fn(usize) -> T + Sync + Send,
+    ) -> Vec<T> {
+        match &self.pool {
+            Pool::Sequential => (0..count).map(f).collect(),
+            _ => self.install(|| (0..count).into_par_iter().map(f).collect()),
+        }
+    }
+
+    /// Apply `f` to every item
+    pub(crate) fn for_each_mut<T: Send>(&self, items: &mut [T], f: impl Fn(&mut T) + Sync + Send) {
+        match &self.pool {
+            Pool::Sequential => items.iter_mut().for_each(f),
+            _ => self.install(|| items.par_iter_mut().for_each(f)),
+        }
+    }
+}
+
+/// Seed of stream `stream` derived from `seed` (SplitMix64 finalizer)
+pub fn stream_seed(seed: u64, stream: u64) -> u64 {
+    let mut z = seed.wrapping_add(stream.wrapping_add(1).wrapping_mul(0x9E37_79B9_7F4A_7C15));
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+/// Independent random generator of stream `stream`
+pub fn stream_rng(seed: u64, stream: u64) -> StdRng {
+    StdRng::seed_from_u64(stream_seed(seed, stream))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::Rng;
+
+    #[test]
+    fn test_streams_are_independent_and_ordered() {
+        assert_eq!(stream_seed(42, 3), stream_seed(42, 3));
+        assert_ne!(stream_seed(42, 3), stream_seed(42, 4));
+        assert_ne!(stream_seed(42, 0), stream_seed(43, 0));
+
+        let draw = |k: usize| stream_rng(7, k as u64).gen::<u64>();
+        let sequential = Parallelism::sequential().map_indexed(64, draw);
+        assert_eq!(Parallelism::global().map_indexed(64, draw), sequential);
+        assert_eq!(Parallelism::threads(3).map_indexed(64, draw), sequential);
+    }
+
+    #[test]
+    fn test_dedicated_pool() {
+        let sequential = Parallelism::default();
+        assert!(!sequential.is_parallel());
+        assert_eq!(Parallelism::threads(1).workers(), 1);
+
+        let pool = Parallelism::threads(3);
+        assert_eq!(pool.workers(), 3);
+        assert!(pool.is_parallel());
+        // Work started inside the pool runs on its threads
+        assert_eq!(pool.install(rayon::current_num_threads), 3);
+        let shared = pool.clone();
+        assert_eq!(shared.install(rayon::current_num_threads), 3);
+
+        let mut items = vec![1, 2, 3];
+        pool.for_each_mut(&mut items, |x| *x *= 2);
+        assert_eq!(items, vec![2, 4, 6]);
+    }
+}
@@ -0,0 +1,617 @@
+//! Proto - Protobuf wire format
+//!
+//! Message types for `proto/u_ras.proto` (package `u_ras.v1`, feature
+//! `protobuf`) and conversions to and from the models, so problems and
+//! schedules can travel over gRPC. The messages are written with
+//! `prost` derives, so no `protoc` is needed to build; services in other
+//! languages generate their stubs from the `.proto` file.
+//!
+//! Fields without a wire equivalent (calendars, working-time rules, shift
+//! preferences, malleability, duration distributions, explanations,
+//! traces) are dropped on encode and take their defaults on decode.
+
+use crate::models::schedule::{Violation, ViolationType};
+use crate::models::{
+    Activity, ActivityDuration, Assignment, PriorityClass, Resource, ResourceRequirement,
+    ResourceType, Schedule, Skill, Task, TransitionMatrix, TransitionMatrixCollection,
+};
+use crate::scheduler::ScheduleRequest;
+use chrono::DateTime;
+use prost::Message;
+
+pub use prost::DecodeError;
+
+/// Generated-style message types of `u_ras.v1`
+pub mod pb {
+    use std::collections::HashMap;
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct SchedulingProblem {
+        #[prost(message, repeated, tag = "1")]
+        pub tasks: Vec<Task>,
+        #[prost(message, repeated, tag = "2")]
+        pub resources: Vec<Resource>,
+        #[prost(int64, tag = "3")]
+        pub start_time_ms: i64,
+        #[prost(message, repeated, tag = "4")]
+        pub transition_matrices: Vec<TransitionMatrix>,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct Task {
+        #[prost(string, tag = "1")]
+        pub id: String,
+        #[prost(string, tag = "2")]
+        pub name: String,
+        #[prost(string, tag = "3")]
+        pub category: String,
+        #[prost(int32, tag = "4")]
+        pub priority: i32,
+        #[prost(int64, optional, tag = "5")]
+        pub deadline_ms: Option<i64>,
+        #[prost(int64, optional, tag = "6")]
+        pub release_time_ms: Option<i64>,
+        #[prost(message, repeated, tag = "7")]
+        pub activities: Vec<Activity>,
+        #[prost(map = "string, string", tag = "8")]
+        pub attributes: HashMap<String, String>,
+        #[prost(map = "string, double", tag = "9")]
+        pub loads: HashMap<String, f64>,
+        #[prost(double, optional, tag = "10")]
+        pub weight: Option<f64>,
+        #[prost(int32, tag = "11")]
+        pub priority_class: i32,
+        #[prost(int64, optional, tag = "12")]
+        pub target_date_ms: Option<i64>,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct Activity {
+        #[prost(string, tag = "1")]
+        pub id: String,
+        #[prost(string, tag = "2")]
+        pub task_id: String,
+        #[prost(int32, tag = "3")]
+        pub sequence: i32,
+        #[prost(int64, tag = "4")]
+        pub setup_ms: i64,
+        #[prost(int64, tag = "5")]
+        pub process_ms: i64,
+        #[prost(int64, tag = "6")]
+        pub teardown_ms: i64,
+        #[prost(message, repeated, tag = "7")]
+        pub requirements: Vec<ResourceRequirement>,
+        #[prost(string, repeated, tag = "8")]
+        pub predecessors: Vec<String>,
+        #[prost(bool, tag = "9")]
+        pub splittable: bool,
+        #[prost(int64, tag = "10")]
+        pub min_split_ms: i64,
+        #[prost(double, tag = "11")]
+        pub energy_kw: f64,
+        #[prost(map = "string, double", tag = "12")]
+        pub demands: HashMap<String, f64>,
+        #[prost(map = "string, double", tag = "13")]
+        pub preferred_resources: HashMap<String, f64>,
+        #[prost(string, optional, tag = "14")]
+        pub location: Option<String>,
+        #[prost(map = "string, string", tag = "15")]
+        pub attributes: HashMap<String, String>,
+        #[prost(map = "string, double", tag = "16")]
+        pub dimension_demands: HashMap<String, f64>,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct ResourceRequirement {
+        #[prost(string, tag = "1")]
+        pub resource_type: String,
+        #[prost(int32, tag = "2")]
+        pub quantity: i32,
+        #[prost(string, repeated, tag = "3")]
+        pub candidates: Vec<String>,
+        #[prost(string, repeated, tag = "4")]
+        pub required_skills: Vec<String>,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct Resource {
+        #[prost(string, tag = "1")]
+        pub id: String,
+        #[prost(string, tag = "2")]
+        pub name: String,
+        #[prost(string, tag = "3")]
+        pub resource_type: String,
+        #[prost(int32, tag = "4")]
+        pub capacity: i32,
+        #[prost(double, tag = "5")]
+        pub efficiency: f64,
+        #[prost(message, repeated, tag = "6")]
+        pub skills: Vec<Skill>,
+        #[prost(double, optional, tag = "7")]
+        pub cost_per_hour: Option<f64>,
+        #[prost(map = "string, string", tag = "8")]
+        pub attributes: HashMap<String, String>,
+        #[prost(string, optional, tag = "9")]
+        pub location: Option<String>,
+        #[prost(map = "string, double", tag = "10")]
+        pub load_capacity: HashMap<String, f64>,
+        #[prost(map = "string, double", tag = "11")]
+        pub dimension_capacity: HashMap<String, f64>,
+        #[prost(double, tag = "12")]
+        pub revocation_probability: f64,
+        #[prost(string, repeated, tag = "13")]
+        pub setup_crew: Vec<String>,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct Skill {
+        #[prost(string, tag = "1")]
+        pub name: String,
+        #[prost(double, tag = "2")]
+        pub level: f64,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct TransitionMatrix {
+        #[prost(string, tag = "1")]
+        pub name: String,
+        #[prost(string, tag = "2")]
+        pub resource_id: String,
+        #[prost(message, repeated, tag = "3")]
+        pub transitions: Vec<Transition>,
+        #[prost(int64, tag = "4")]
+        pub default_ms: i64,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct Transition {
+        #[prost(string, tag = "1")]
+        pub from_category: String,
+        #[prost(string, tag = "2")]
+        pub to_category: String,
+        #[prost(int64, tag = "3")]
+        pub time_ms: i64,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct Schedule {
+        #[prost(message, repeated, tag = "1")]
+        pub assignments: Vec<Assignment>,
+        #[prost(int64, tag = "2")]
+        pub makespan_ms: i64,
+        #[prost(message, repeated, tag = "3")]
+        pub violations: Vec<Violation>,
+        #[prost(map = "string, double", tag = "4")]
+        pub planned_confidence: HashMap<String, f64>,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct Assignment {
+        #[prost(string, tag = "1")]
+        pub activity_id: String,
+        #[prost(string, tag = "2")]
+        pub task_id: String,
+        #[prost(string, tag = "3")]
+        pub resource_id: String,
+        #[prost(int64, tag = "4")]
+        pub start_ms: i64,
+        #[prost(int64, tag = "5")]
+        pub end_ms: i64,
+        #[prost(int64, tag = "6")]
+        pub setup_ms: i64,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct Violation {
+        #[prost(string, tag = "1")]
+        pub violation_type: String,
+        #[prost(string, tag = "2")]
+        pub entity_id: String,
+        #[prost(string, tag = "3")]
+        pub message: String,
+        #[prost(int32, tag = "4")]
+        pub severity: i32,
+    }
+}
+
+/// Encode a scheduling problem
+pub fn encode_problem(request: &ScheduleRequest) -> Vec<u8> {
+    pb::SchedulingProblem::from(request).encode_to_vec()
+}
+
+/// Decode a scheduling problem
+pub fn decode_problem(bytes: &[u8]) -> Result<ScheduleRequest, DecodeError> {
+    pb::SchedulingProblem::decode(bytes).map(ScheduleRequest::from)
+}
+
+/// Encode a schedule
+pub fn encode_schedule(schedule: &Schedule) -> Vec<u8> {
+    pb::Schedule::from(schedule).encode_to_vec()
+}
+
+/// Decode a schedule
+pub fn decode_schedule(bytes: &[u8]) -> Result<Schedule, DecodeError> {
+    pb::Schedule::decode(bytes).map(Schedule::from)
+}
+
+impl From<&ScheduleRequest> for pb::SchedulingProblem {
+    fn from(request: &ScheduleRequest) -> Self {
+        Self {
+            tasks: request.tasks.iter().map(pb::Task::from).collect(),
+            resources: request.resources.iter().map(pb::Resource::from).collect(),
+            start_time_ms: request.start_time_ms,
+            transition_matrices: request
+                .transition_matrices
+                .matrices
+                .iter()
+                .map(pb::TransitionMatrix::from)
+                .collect(),
+        }
+    }
+}
+
+impl From<pb::SchedulingProblem> for ScheduleRequest {
+    fn from(problem: pb::SchedulingProblem) -> Self {
+        let mut matrices = TransitionMatrixCollection::new();
+        for matrix in problem.transition_matrices {
+            matrices.add(matrix.into());
+        }
+        ScheduleRequest::new(
+            problem.tasks.into_iter().map(Task::from).collect(),
+            problem.resources.into_iter().map(Resource::from).collect(),
+        )
+        .with_start_time(problem.start_time_ms)
+        .with_transition_matrices(matrices)
+    }
+}
+
+impl From<&Task> for pb::Task {
+    fn from(task: &Task) -> Self {
+        Self {
+            id: task.id.clone(),
+            name: task.name.clone(),
+            category: task.category.clone(),
+            priority: task.priority,
+            deadline_ms: task.deadline.map(|d| d.timestamp_millis()),
+            release_time_ms: task.release_time.map(|r| r.timestamp_millis()),
+            activities: task.activities.iter().map(pb::Activity::from).collect(),
+            attributes: task.attributes.clone(),
+            loads: task.loads.clone(),
+            weight: task.weight,
+            priority_class: task.priority_class.0,
+            target_date_ms: task.target_date.map(|d| d.timestamp_millis()),
+        }
+    }
+}
+
+impl From<pb::Task> for Task {
+    fn from(task: pb::Task) -> Self {
+        Task {
+            name: task.name,
+            category: task.category,
+            priority: task.priority,
+            deadline: task.deadline_ms.and_then(DateTime::from_timestamp_millis),
+            release_time: task
+                .release_time_ms
+                .and_then(DateTime::from_timestamp_millis),
+            activities: task.activities.into_iter().map(Activity::from).collect(),
+            attributes: task.attributes,
+            loads: task.loads,
+            weight: task.weight,
+            priority_class: PriorityClass(task.priority_class),
+            target_date: task
+                .target_date_ms
+                .and_then(DateTime::from_timestamp_millis),
+            ..Task::new(&task.id)
+        }
+    }
+}
+
+impl From<&Activity> for pb::Activity {
+    fn from(activity: &Activity) -> Self {
+        Self {
+            id: activity.id.clone(),
+            task_id: activity.task_id.clone(),
+            sequence: activity.sequence,
+            setup_ms: activity.duration.setup_ms,
+            process_ms: activity.duration.process_ms,
+            teardown_ms: activity.duration.teardown_ms,
+            requirements: activity
+                .resource_requirements
+                .iter()
+                .map(|r| pb::ResourceRequirement {
+                    resource_type: r.resource_type.clone(),
+                    quantity: r.quantity,
+                    candidates: r.candidates.clone(),
+                    required_skills: r.required_skills.clone(),
+                })
+                .collect(),
+            predecessors: activity.predecessors.clone(),
+            splittable: activity.splittable,
+            min_split_ms: activity.min_split_ms,
+            energy_kw: activity.energy_kw,
+            demands: activity.demands.clone(),
+            dimension_demands: activity.dimension_demands.clone(),
+            preferred_resources: activity.preferred_resources.clone(),
+            location: activity.location.clone(),
+            attributes: activity.attributes.clone(),
+        }
+    }
+}
+
+impl From<pb::Activity> for Activity {
+    fn from(activity: pb::Activity) -> Self {
+        Activity {
+            duration: ActivityDuration::new(
+                activity.setup_ms,
+                activity.process_ms,
+                activity.teardown_ms,
+            ),
+            resource_requirements: activity
+                .requirements
+                .into_iter()
+                .map(|r| ResourceRequirement {
+                    resource_type: r.resource_type,
+                    quantity: r.quantity,
+                    candidates: r.candidates,
+                    required_skills: r.required_skills,
+                })
+                .collect(),
+            predecessors: activity.predecessors,
+            splittable: activity.splittable,
+            min_split_ms: activity.min_split_ms,
+            energy_kw: activity.energy_kw,
+            demands: activity.demands,
+            dimension_demands: activity.dimension_demands,
+            preferred_resources: activity.preferred_resources,
+            location: activity.location,
+            attributes: activity.attributes,
+            ..Activity::new(&activity.id, &activity.task_id, activity.sequence)
+        }
+    }
+}
+
+impl From<&Resource> for pb::Resource {
+    fn from(resource: &Resource) -> Self {
+        Self {
+            id: resource.id.clone(),
+            name: resource.name.clone(),
+            resource_type: match &resource.resource_type {
+                ResourceType::Custom(name) => name.clone(),
+                known => format!("{:?}", known),
+            },
+            capacity: resource.capacity,
+            efficiency: resource.efficiency,
+            skills: resource
+                .skills
+                .iter()
+                .map(|s| pb::Skill {
+                    name: s.name.clone(),
+                    level: s.level,
+                })
+                .collect(),
+            cost_per_hour: resource.cost_per_hour,
+            attributes: resource.attributes.clone(),
+            location: resource.location.clone(),
+            load_capacity: resource.load_capacity.clone(),
+            dimension_capacity: resource.dimension_capacity.clone(),
+            revocation_probability: resource.revocation_probability,
+            setup_crew: resource.setup_crew.clone(),
+        }
+    }
+}
+
+impl From<pb::Resource> for Resource {
+    fn from(resource: pb::Resource) -> Self {
+        let resource_type = match resource.resource_type.as_str() {
+            "Primary" => ResourceType::Primary,
+            "Secondary" => ResourceType::Secondary,
+            "Human" => ResourceType::Human,
+            "Consumable" => ResourceType::Consumable,
+            _ => ResourceType::Custom(resource.resource_type.clone()),
+        };
+        Resource {
+            name: resource.name,
+            capacity: resource.capacity,
+            efficiency: resource.efficiency,
+            skills: resource
+                .skills
+                .into_iter()
+                .map(|s| Skill::new(&s.name, s.level))
+                .collect(),
+            cost_per_hour: resource.cost_per_hour,
+            attributes: resource.attributes,
+            location: resource.location,
+            load_capacity: resource.load_capacity,
+            dimension_capacity: resource.dimension_capacity,
+            revocation_probability: resource.revocation_probability,
+            setup_crew: resource.setup_crew,
+            ..Resource::new(&resource.id, resource_type)
+        }
+    }
+}
+
+impl From<&TransitionMatrix> for pb::TransitionMatrix {
+    fn from(matrix: &TransitionMatrix) -> Self {
+        let mut transitions: Vec<pb::Transition> = matrix
+            .transitions
+            .iter()
+            .map(|((from, to), &time_ms)| pb::Transition {
+                from_category: from.clone(),
+                to_category: to.clone(),
+                time_ms,
+            })
+            .collect();
+        // Deterministic bytes for equal matrices
+        transitions.sort_by(|a, b| {
+            (&a.from_category, &a.to_category).cmp(&(&b.from_category, &b.to_category))
+        });
+        Self {
+            name: matrix.name.clone(),
+            resource_id: matrix.resource_id.clone(),
+            transitions,
+            default_ms: matrix.default_ms,
+        }
+    }
+}
+
+impl From<pb::TransitionMatrix> for TransitionMatrix {
+    fn from(matrix: pb::TransitionMatrix) -> Self {
+        let mut converted = TransitionMatrix::new(&matrix.name, &matrix.resource_id);
+        converted.default_ms = matrix.default_ms;
+        for t in matrix.transitions {
+            converted.set_transition(&t.from_category, &t.to_category, t.time_ms);
+        }
+        converted
+    }
+}
+
+impl From<&Schedule> for pb::Schedule {
+    fn from(schedule: &Schedule) -> Self {
+        Self {
+            assignments: schedule
+                .assignments
+                .iter()
+                .map(|a| pb::Assignment {
+                    activity_id: a.activity_id.clone(),
+                    task_id: a.task_id.clone(),
+                    resource_id: a.resource_id.clone(),
+                    start_ms: a.start_ms,
+                    end_ms: a.end_ms,
+                    setup_ms: a.setup_ms,
+                })
+                .collect(),
+            makespan_ms: schedule.makespan_ms,
+            violations: schedule
+                .violations
+                .iter()
+                .map(|v| pb::Violation {
+                    violation_type: match &v.violation_type {
+                        ViolationType::Custom(name) => name.clone(),
+                        known => format!("{:?}", known),
+                    },
+                    entity_id: v.entity_id.clone(),
+                    message: v.message.clone(),
+                    severity: v.severity,
+                })
+                .collect(),
+            planned_confidence: schedule.planned_confidence.clone(),
+        }
+    }
+}
+
+impl From<pb::Schedule> for Schedule {
+    fn from(schedule: pb::Schedule) -> Self {
+        let mut converted = Schedule::new();
+        for a in schedule.assignments {
+            converted.add_assignment(Assignment {
+                activity_id: a.activity_id,
+                task_id: a.task_id,
+                resource_id: a.resource_id,
+                start_ms: a.start_ms,
+                end_ms: a.end_ms,
+                setup_ms: a.setup_ms,
+            });
+        }
+        converted.makespan_ms = schedule.makespan_ms;
+        converted.violations = schedule
+            .violations
+            .into_iter()
+            .map(|v| Violation {
+                violation_type: match v.violation_type.as_str() {
+                    "DeadlineMiss" => ViolationType::DeadlineMiss,
+                    "CapacityExceeded" => ViolationType::CapacityExceeded,
+                    "PrecedenceViolation" => ViolationType::PrecedenceViolation,
+                    "ResourceUnavailable" => ViolationType::ResourceUnavailable,
+                    "SkillMismatch" => ViolationType::SkillMismatch,
+                    "WorkingTime" => ViolationType::WorkingTime,
+                    "TimeWindow" => ViolationType::TimeWindow,
+                    _ => ViolationType::Custom(v.violation_type.clone()),
+                },
+                entity_id: v.entity_id,
+                message: v.message,
+                severity: v.severity,
+            })
+            .collect();
+        converted.planned_confidence = schedule.planned_confidence;
+        converted
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scheduler::SimpleScheduler;
+    use chrono::{TimeZone, Utc};
+
+    fn request() -> ScheduleRequest {
+        let task = Task::new("T1")
+            .with_priority(3)
+            .with_category("red")
+            .with_deadline(Utc.timestamp_millis_opt(9000).unwrap())
+            .with_activity(
+                Activity::new("T1-A1", "T1", 1)
+                    .with_duration(ActivityDuration::new(100, 2000, 50))
+                    .with_resources("machine", vec!["M1".into(), "M2".into()]),
+            );
+        let mut matrix = TransitionMatrix::new("paint", "M1");
+        matrix.set_transition("red", "blue", 500);
+        let mut matrices = TransitionMatrixCollection::new();
+        matrices.add(matrix);
+        ScheduleRequest::new(
+            vec![task],
+            vec![
+                Resource::primary("M1").with_efficiency(2.0),
+                Resource::new("M2", ResourceType::Custom("robot".into())),
+            ],
+        )
+        .with_start_time(1000)
+        .with_transition_matrices(matrices)
+    }
+
+    #[test]
+    fn test_problem_roundtrip() {
+        let bytes = encode_problem(&request());
+        let decoded = decode_problem(&bytes).unwrap();
+
+        let task = &decoded.tasks[0];
+        assert_eq!(task.priority, 3);
+        assert_eq!(task.category, "red");
+        assert_eq!(task.deadline.unwrap().timestamp_millis(), 9000);
+        assert_eq!(task.activities[0].duration.teardown_ms, 50);
+        assert_eq!(
+            task.activities[0].candidate_resources(),
+            vec!["M1".to_string(), "M2".to_string()]
+        );
+        assert_eq!(decoded.resources[0].efficiency, 2.0);
+        assert_eq!(
+            decoded.resources[1].resource_type,
+            ResourceType::Custom("robot".into())
+        );
+        assert_eq!(decoded.start_time_ms, 1000);
+        assert_eq!(
+            decoded.transition_matrices.matrices[0].transitions[&("red".into(), "blue".into())],
+            500
+        );
+        // Re-encoding is stable
+        assert_eq!(encode_problem(&decoded), bytes);
+    }
+
+    #[test]
+    fn test_schedule_roundtrip() {
+        let request = request();
+        let mut schedule =
+            SimpleScheduler::new().schedule(&request.tasks, &request.resources, 1000);
+        schedule.add_violation(Violation::deadline_miss("T1", "late"));
+
+        let decoded = decode_schedule(&encode_schedule(&schedule)).unwrap();
+
+        assert_eq!(decoded.assignments.len(), schedule.assignments.len());
+        assert_eq!(decoded.makespan_ms, schedule.makespan_ms);
+        assert_eq!(
+            decoded.violations[0].violation_type,
+            ViolationType::DeadlineMiss
+        );
+        assert!(decode_schedule(&[0xff, 0xff]).is_err());
+    }
+}
@@ -0,0 +1,450 @@
+//! PSPLIB - Project scheduling benchmark instances
+//!
+//! Reads single-mode (`.sm`) and multi-mode (`.mm`) files of the PSPLIB
+//! instance library into the RCPSP model (tasks with pool demands and
+//! `RenewablePool`s), so `RcpspScheduler` can be checked against the
+//! published optimal and best-known makespans:
+//!
+//! ```no_run
+//! use u_ras::psplib::PsplibInstance;
+//! use u_ras::scheduler::RcpspScheduler;
+//!
+//! let text = std::fs::read_to_string("j30/j301_1.sm").unwrap();
+//! let instance = PsplibInstance::parse(&text).unwrap();
+//! let schedule = RcpspScheduler::new().schedule(&instance.to_tasks(1), &instance.pools(), 0);
+//! println!("makespan {} periods", schedule.makespan_ms);
+//! ```
+//!
+//! Renewable resources become pools `R1`, `R2`, ...; jobs become
+//! activities `J1`, `J2`, ... (including the dummy source and sink) of a
+//! single task `P`. Nonrenewable resources are parsed but not enforced by
+//! the RCPSP mode; doubly constrained resources are skipped.
+
+use crate::models::{Activity, ActivityDuration, RenewablePool, Task};
+use std::fmt;
+
+/// PSPLIB parse error
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PsplibParseError {
+    /// Line number (1-based, 0 for the file as a whole)
+    pub line: usize,
+    /// What was wrong
+    pub message: String,
+}
+
+impl fmt::Display for PsplibParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for PsplibParseError {}
+
+/// One execution mode of a job
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PsplibMode {
+    /// Duration (periods)
+    pub duration: i64,
+    /// Units of each renewable resource
+    pub renewable: Vec<u32>,
+    /// Units of each nonrenewable resource
+    pub nonrenewable: Vec<u32>,
+}
+
+/// One job of the project
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PsplibJob {
+    /// Job number (1-based)
+    pub number: usize,
+    /// Successor job numbers
+    pub successors: Vec<usize>,
+    /// Modes (mode `k` at index `k - 1`)
+    pub modes: Vec<PsplibMode>,
+}
+
+/// Parsed PSPLIB instance
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PsplibInstance {
+    /// Scheduling horizon (periods)
+    pub horizon: i64,
+    /// Project release date (periods)
+    pub release_date: i64,
+    /// Project due date (periods)
+    pub due_date: i64,
+    /// Jobs in file order
+    pub jobs: Vec<PsplibJob>,
+    /// Availability of each renewable resource
+    pub renewable: Vec<u32>,
+    /// Availability of each nonrenewable resource
+    pub nonrenewable: Vec<u32>,
+}
+
+/// File section being read
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Section {
+    Header,
+    Project,
+    Precedence,
+    Requests,
+    Availability,
+}
+
+impl PsplibInstance {
+    /// Parse an `.sm` or `.mm` file
+    pub fn parse(text: &str) -> Result<Self, PsplibParseError> {
+        let mut instance = Self::default();
+        let (mut renewable, mut nonrenewable, mut doubly) = (0, 0, 0);
+        let mut section = Section::Header;
+        let mut last_job: Option<usize> = None;
+
+        for (n, raw) in text.lines().enumerate() {
+            let line_no = n + 1;
+            let line = raw.trim();
+            let err = |message: &str| PsplibParseError {
+                line: line_no,
+                message: message.to_string(),
+            };
+            if line.is_empty()
+                || line.starts_with('*')
+                || (line.starts_with('-') && line.ends_with('-'))
+            {
+                continue;
+            }
+            let upper = line.to_ascii_uppercase();
+            if upper.starts_with("PROJECT INFORMATION") {
+                section = Section::Project;
+                continue;
+            } else if upper.starts_with("PRECEDENCE RELATIONS") {
+                section = Section::Precedence;
+                continue;
+            } else if upper.starts_with("REQUESTS/DURATIONS") {
+                section = Section::Requests;
+                continue;
+            } else if upper.starts_with("RESOURCEAVAILABILITIES") {
+                section = Section::Availability;
+                continue;
+            }
+
+            match section {
+                Section::Header => {
+                    let Some((key, value)) = line.split_once(':') else {
+                        continue;
+                    };
+                    let key = key.trim().to_ascii_lowercase();
+                    let number = || -> Result<i64, PsplibParseError> {
+                        value
+                            .split_whitespace()
+                            .next()
+                            .and_then(|v| v.parse().ok())
+                            .ok_or_else(|| err(&format!("expected a number after '{}'", key)))
+                    };
+                    if key == "horizon" {
+                        instance.horizon = number()?;
+                    } else if key == "- renewable" {
+                        renewable = number()? as usize;
+                    } else if key == "- nonrenewable" {
+                        nonrenewable = number()? as usize;
+                    } else if key == "- doubly constrained" {
+                        doubly = number()? as usize;
+                    }
+                }
+                Section::Project => {
+                    // pronr. #jobs rel.date duedate tardcost MPM-Time
+                    if let Ok(values) = numbers(line) {
+                        if values.len() >= 4 {
+                            instance.release_date = values[2];
+                            instance.due_date = values[3];
+                        }
+                    }
+                }
+                Section::Precedence => {
+                    let Ok(values) = numbers(line) else {
+                        continue; // column header
+                    };
+                    let [number, _, count, ref successors @ ..] = values[..] else {
+                        return Err(err("expected job number, mode count and successor count"));
+                    };
+                    if successors.len() != count as usize {
+                        return Err(err(&format!(
+                            "job {} lists {} successors, expected {}",
+                            number,
+                            successors.len(),
+                            count
+                        )));
+                    }
+                    instance.jobs.push(PsplibJob {
+                        number: number as usize,
+                        successors: successors.iter().map(|&s| s as usize).collect(),
+                        modes: Vec::new(),
+                    });
+                }
+                Section::Requests => {
+                    let Ok(values) = numbers(line) else {
+                        continue; // column header
+                    };
+                    let columns = renewable + nonrenewable + doubly;
+                    let (job, mode) = if values.len() == columns + 3 {
+                        let job = instance
+                            .jobs
+                            .iter()
+                            .position(|j| j.number == values[0] as usize)
+                            .ok_or_else(|| err(&format!("unknown job {}", values[0])))?;
+                        last_job = Some(job);
+                        (job, &values[1..])
+                    } else if values.len() == columns + 2 {
+                        // Further mode of the previous job
+                        (
+                            last_job.ok_or_else(|| err("mode row without a job"))?,
+                            &values[..],
+                        )
+                    } else {
+                        return Err(err(&format!(
+                            "expected {} or {} values, found {}",
+                            columns + 3,
+                            columns + 2,
+                            values.len()
+                        )));
+                    };
+                    let modes = &mut instance.jobs[job].modes;
+                    if mode[0] as usize != modes.len() + 1 {
+                        return Err(err(&format!("expected mode {}", modes.len() + 1)));
+                    }
+                    let units =
+                        |range: std::ops::Range<usize>| -> Result<Vec<u32>, PsplibParseError> {
+                            mode[2 + range.start..2 + range.end]
+                                .iter()
+                                .map(|&u| {
+                                    u32::try_from(u).map_err(|_| err("negative resource request"))
+                                })
+                                .collect()
+                        };
+                    modes.push(PsplibMode {
+                        duration: mode[1],
+                        renewable: units(0..renewable)?,
+                        nonrenewable: units(renewable..renewable + nonrenewable)?,
+                    });
+                }
+                Section::Availability => {
+                    let Ok(values) = numbers(line) else {
+                        continue; // column header
+                    };
+                    if values.len() < renewable + nonrenewable {
+                        return Err(err(&format!(
+                            "expected {} availabilities, found {}",
+                            renewable + nonrenewable,
+                            values.len()
+                        )));
+                    }
+                    let units = |v: &[i64]| -> Result<Vec<u32>, PsplibParseError> {
+                        v.iter()
+                            .map(|&u| u32::try_from(u).map_err(|_| err("negative availability")))
+                            .collect()
+                    };
+                    instance.renewable = units(&values[..renewable])?;
+                    instance.nonrenewable = units(&values[renewable..renewable + nonrenewable])?;
+                }
+            }
+        }
+
+        let whole = |message: String| PsplibParseError { line: 0, message };
+        if instance.jobs.is_empty() {
+            return Err(whole("no precedence relations found".to_string()));
+        }
+        if let Some(job) = instance.jobs.iter().find(|j| j.modes.is_empty()) {
+            return Err(whole(format!(
+                "job {} has no requests/durations",
+                job.number
+            )));
+        }
+        if instance.renewable.len() != renewable {
+            return Err(whole("no resource availabilities found".to_string()));
+        }
+        for job in &instance.jobs {
+            if let Some(s) = job
+                .successors
+                .iter()
+                .find(|&&s| !instance.jobs.iter().any(|j| j.number == s))
+            {
+                return Err(whole(format!(
+                    "job {} has unknown successor {}",
+                    job.number, s
+                )));
+            }
+        }
+        Ok(instance)
+    }
+
+    /// Renewable resources as pools `R1`, `R2`, ...
+    pub fn pools(&self) -> Vec<RenewablePool> {
+        self.renewable
+            .iter()
+            .enumerate()
+            .map(|(k, &capacity)| RenewablePool::new(&format!("R{}", k + 1), capacity))
+            .collect()
+    }
+
+    /// Project as a task, every job in its first mode
+    ///
+    /// One period lasts `period_ms`.
+    pub fn to_tasks(&self, period_ms: i64) -> Vec<Task> {
+        self.to_tasks_with_modes(&vec![1; self.jobs.len()], period_ms)
+    }
+
+    /// Project as a task with a chosen mode per job
+    ///
+    /// `modes[i]` is the 1-based mode of the `i`-th job; missing or
+    /// out-of-range entries fall back to mode 1.
+    pub fn to_tasks_with_modes(&self, modes: &[usize], period_ms: i64) -> Vec<Task> {
+        let mut predecessors: Vec<Vec<usize>> = vec![Vec::new(); self.jobs.len()];
+        for job in &self.jobs {
+            for &s in &job.successors {
+                if let Some(k) = self.jobs.iter().position(|j| j.number == s) {
+                    predecessors[k].push(job.number);
+                }
+            }
+        }
+
+        let mut task = Task::new("P");
+        for (i, job) in self.jobs.iter().enumerate() {
+            let mode = modes
+                .get(i)
+                .and_then(|&m| job.modes.get(m.wrapping_sub(1)))
+                .unwrap_or(&job.modes[0]);
+            let mut activity = Activity::new(&format!("J{}", job.number), "P", job.number as i32)
+                .with_duration(ActivityDuration::fixed(mode.duration * period_ms));
+            for (k, &units) in mode.renewable.iter().enumerate() {
+                if units > 0 {
+                    activity.set_pool_demand(&format!("R{}", k + 1), units);
+                }
+            }
+            for p in &predecessors[i] {
+                activity = activity.with_predecessor(&format!("J{}", p));
+            }
+            task.add_activity(activity);
+        }
+        vec![task]
+    }
+}
+
+/// Whitespace-separated integers of a line
+fn numbers(line: &str) -> Result<Vec<i64>, std::num::ParseIntError> {
+    line.split_whitespace().map(str::parse).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scheduler::RcpspScheduler;
+    use crate::validation::validate_pool_capacity;
+
+    const SINGLE_MODE: &str = "\
+************************************************************************
+file with basedata            : tiny.bas
+initial value random generator: 1
+************************************************************************
+projects                      :  1
+jobs (incl. supersource/sink ):  5
+horizon                       :  20
+RESOURCES
+  - renewable                 :  1   R
+  - nonrenewable              :  0   N
+  - doubly constrained        :  0   D
+************************************************************************
+PROJECT INFORMATION:
+pronr.  #jobs rel.date duedate tardcost  MPM-Time
+    1      3      0        8        2        5
+************************************************************************
+PRECEDENCE RELATIONS:
+jobnr.    #modes  #successors   successors
+   1        1          2           2   3
+   2        1          1           4
+   3        1          1           5
+   4        1          1           5
+   5        1          0
+************************************************************************
+REQUESTS/DURATIONS:
+jobnr. mode duration  R 1
+------------------------------------------------------------------------
+  1      1     0       0
+  2      1     3       2
+  3      1     2       2
+  4      1     2       1
+  5      1     0       0
+************************************************************************
+RESOURCEAVAILABILITIES:
+  R 1
+    3
+************************************************************************
+";
+
+    #[test]
+    fn test_single_mode() {
+        let instance = PsplibInstance::parse(SINGLE_MODE).unwrap();
+        assert_eq!(instance.horizon, 20);
+        assert_eq!(instance.due_date, 8);
+        assert_eq!(instance.jobs.len(), 5);
+        assert_eq!(instance.jobs[0].successors, vec![2, 3]);
+        assert_eq!(instance.renewable, vec![3]);
+
+        let tasks = instance.to_tasks(1000);
+        let pools = instance.pools();
+        let schedule = RcpspScheduler::new().schedule(&tasks, &pools, 0);
+        // Jobs 2 and 3 (2 units each) cannot overlap on 3 units
+        assert_eq!(schedule.makespan_ms, 5000);
+        assert!(validate_pool_capacity(&schedule, &tasks, &pools).is_valid);
+    }
+
+    #[test]
+    fn test_multi_mode() {
+        let text = "\
+jobs (incl. supersource/sink ):  3
+horizon                       :  10
+  - renewable                 :  1   R
+  - nonrenewable              :  1   N
+  - doubly constrained        :  0   D
+PRECEDENCE RELATIONS:
+jobnr.    #modes  #successors   successors
+   1        1          1           2
+   2        2          1           3
+   3        1          0
+REQUESTS/DURATIONS:
+jobnr. mode duration  R 1  N 1
+------------------------------------------------------------------------
+  1      1     0       0    0
+  2      1     3       2    4
+         2     5       1    2
+  3      1     0       0    0
+RESOURCEAVAILABILITIES:
+  R 1  N 1
+    3    5
+";
+        let instance = PsplibInstance::parse(text).unwrap();
+        assert_eq!(instance.jobs[1].modes.len(), 2);
+        assert_eq!(
+            instance.jobs[1].modes[1],
+            PsplibMode {
+                duration: 5,
+                renewable: vec![1],
+                nonrenewable: vec![2],
+            }
+        );
+        assert_eq!(instance.nonrenewable, vec![5]);
+
+        let tasks = instance.to_tasks_with_modes(&[1, 2, 1], 1);
+        let j2 = &tasks[0].activities[1];
+        assert_eq!(j2.duration.process_ms, 5);
+        assert_eq!(j2.pool_demand("R1"), 1);
+        assert_eq!(j2.predecessors, vec!["J1".to_string()]);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let truncated = SINGLE_MODE.replace("  5      1     0       0\n", "");
+        let err = PsplibInstance::parse(&truncated).unwrap_err();
+        assert_eq!(err.message, "job 5 has no requests/durations");
+
+        let bad = SINGLE_MODE.replace("   2        1          1           4", "   2  1  2  4");
+        let err = PsplibInstance::parse(&bad).unwrap_err();
+        assert_eq!(err.line, 20);
+    }
+}
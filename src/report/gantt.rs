@@ -0,0 +1,327 @@
+//! Gantt - Renderer-agnostic Gantt chart model
+//!
+//! [`GanttModel`] lays a schedule out as rows (one per resource) of bars
+//! with dependency arrows and violation markers. Every reference is an
+//! index into the model, so the serialized JSON can be drawn without the
+//! problem definition.
+
+use crate::models::schedule::ViolationType;
+use crate::models::{Resource, Schedule, Task};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Gantt chart row (a resource)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GanttRow {
+    /// Resource ID
+    pub id: String,
+    /// Display label (resource name, else ID)
+    pub label: String,
+}
+
+/// Gantt chart bar (an assignment)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GanttBar {
+    /// Row index
+    pub row: usize,
+    /// Activity ID
+    pub activity_id: String,
+    /// Task ID
+    pub task_id: String,
+    /// Start (epoch ms)
+    pub start_ms: i64,
+    /// End (epoch ms)
+    pub end_ms: i64,
+    /// Leading setup time (ms)
+    pub setup_ms: i64,
+    /// Color group: task category, else task ID
+    pub category: String,
+    /// Display labels: activity ID, then task name (if known)
+    pub labels: Vec<String>,
+}
+
+/// Why one bar depends on another
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DependencyKind {
+    /// Consecutive activities of a task
+    Sequence,
+    /// Explicit activity predecessor
+    Precedence,
+}
+
+/// Dependency arrow between bars
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GanttDependency {
+    /// Bar index of the predecessor
+    pub from: usize,
+    /// Bar index of the successor
+    pub to: usize,
+    /// Dependency kind
+    pub kind: DependencyKind,
+}
+
+/// Violation marker
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ViolationMarker {
+    /// Violation type
+    pub violation_type: ViolationType,
+    /// Related entity ID
+    pub entity_id: String,
+    /// Description
+    pub message: String,
+    /// Severity (0-100)
+    pub severity: i32,
+    /// Row of the resource or activity, if any
+    pub row: Option<usize>,
+    /// Where to place the marker: activity start or task completion (epoch ms)
+    pub time_ms: Option<i64>,
+}
+
+/// Gantt chart of a schedule
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GanttModel {
+    /// Earliest bar start (epoch ms)
+    pub start_ms: i64,
+    /// Latest bar end (epoch ms)
+    pub end_ms: i64,
+    /// Rows
+    pub rows: Vec<GanttRow>,
+    /// Bars by row, then start
+    pub bars: Vec<GanttBar>,
+    /// Dependency arrows
+    pub dependencies: Vec<GanttDependency>,
+    /// Violation markers
+    pub markers: Vec<ViolationMarker>,
+}
+
+impl GanttModel {
+    /// Chart from the schedule alone: rows by resource ID, no dependencies
+    pub fn from_schedule(schedule: &Schedule) -> Self {
+        Self::build(schedule, &[], &[])
+    }
+
+    /// Chart with rows in resource order and labels, categories and
+    /// dependencies from the tasks
+    ///
+    /// Resources used by the schedule but not listed get rows after the
+    /// listed ones, by ID.
+    pub fn build(schedule: &Schedule, tasks: &[Task], resources: &[Resource]) -> Self {
+        let mut rows: Vec<GanttRow> = resources
+            .iter()
+            .map(|r| GanttRow {
+                id: r.id.clone(),
+                label: if r.name.is_empty() {
+                    r.id.clone()
+                } else {
+                    r.name.clone()
+                },
+            })
+            .collect();
+        let mut unlisted: Vec<&str> = schedule
+            .assignments
+            .iter()
+            .map(|a| a.resource_id.as_str())
+            .filter(|id| !resources.iter().any(|r| r.id == *id))
+            .collect();
+        unlisted.sort_unstable();
+        unlisted.dedup();
+        rows.extend(unlisted.into_iter().map(|id| GanttRow {
+            id: id.to_string(),
+            label: id.to_string(),
+        }));
+        let row_of: HashMap<&str, usize> = rows
+            .iter()
+            .enumerate()
+            .map(|(i, r)| (r.id.as_str(), i))
+            .collect();
+
+        let task_of: HashMap<&str, &Task> = tasks.iter().map(|t| (t.id.as_str(), t)).collect();
+        let mut bars: Vec<GanttBar> = schedule
+            .assignments
+            .iter()
+            .map(|a| {
+                let task = task_of.get(a.task_id.as_str());
+                let mut labels = vec![a.activity_id.clone()];
+                labels.extend(task.filter(|t| !t.name.is_empty()).map(|t| t.name.clone()));
+                GanttBar {
+                    row: row_of[a.resource_id.as_str()],
+                    activity_id: a.activity_id.clone(),
+                    task_id: a.task_id.clone(),
+                    start_ms: a.start_ms,
+                    end_ms: a.end_ms,
+                    setup_ms: a.setup_ms,
+                    category: task
+                        .filter(|t| !t.category.is_empty())
+                        .map_or_else(|| a.task_id.clone(), |t| t.category.clone()),
+                    labels,
+                }
+            })
+            .collect();
+        bars.sort_by(|a, b| {
+            (a.row, a.start_ms, &a.activity_id).cmp(&(b.row, b.start_ms, &b.activity_id))
+        });
+
+        let dependencies = Self::dependencies(&bars, tasks);
+        let markers = schedule
+            .violations
+            .iter()
+            .map(|v| {
+                let entity = v.entity_id.as_str();
+                let activity = bars.iter().find(|b| b.activity_id == entity);
+                let (row, time_ms) = if let Some(&row) = row_of.get(entity) {
+                    (Some(row), None)
+                } else if let Some(bar) = activity {
+                    (Some(bar.row), Some(bar.start_ms))
+                } else {
+                    let end = bars
+                        .iter()
+                        .filter(|b| b.task_id == entity)
+                        .map(|b| b.end_ms)
+                        .max();
+                    (None, end)
+                };
+                ViolationMarker {
+                    violation_type: v.violation_type.clone(),
+                    entity_id: v.entity_id.clone(),
+                    message: v.message.clone(),
+                    severity: v.severity,
+                    row,
+                    time_ms,
+                }
+            })
+            .collect();
+
+        Self {
+            start_ms: bars.iter().map(|b| b.start_ms).min().unwrap_or(0),
+            end_ms: bars.iter().map(|b| b.end_ms).max().unwrap_or(0),
+            rows,
+            bars,
+            dependencies,
+            markers,
+        }
+    }
+
+    /// Arrows from the last part of each predecessor to the first part of
+    /// its successor (split activities have several bars)
+    fn dependencies(bars: &[GanttBar], tasks: &[Task]) -> Vec<GanttDependency> {
+        let mut first: HashMap<&str, usize> = HashMap::new();
+        let mut last: HashMap<&str, usize> = HashMap::new();
+        for (i, bar) in bars.iter().enumerate() {
+            let id = bar.activity_id.as_str();
+            if first
+                .get(id)
+                .is_none_or(|&f| bar.start_ms < bars[f].start_ms)
+            {
+                first.insert(id, i);
+            }
+            if last.get(id).is_none_or(|&l| bar.end_ms > bars[l].end_ms) {
+                last.insert(id, i);
+            }
+        }
+
+        let mut dependencies = Vec::new();
+        let mut link = |pred: &str, succ: &str, kind: DependencyKind| {
+            if let (Some(&from), Some(&to)) = (last.get(pred), first.get(succ)) {
+                dependencies.push(GanttDependency { from, to, kind });
+            }
+        };
+        for task in tasks {
+            let mut sequence: Vec<_> = task.activities.iter().collect();
+            sequence.sort_by_key(|a| a.sequence);
+            for pair in sequence.windows(2) {
+                link(&pair[0].id, &pair[1].id, DependencyKind::Sequence);
+            }
+            for activity in &task.activities {
+                for pred in &activity.predecessors {
+                    link(pred, &activity.id, DependencyKind::Precedence);
+                }
+            }
+        }
+        dependencies
+    }
+
+    /// Bars of a row, by start
+    pub fn bars_in_row(&self, row: usize) -> Vec<&GanttBar> {
+        self.bars.iter().filter(|b| b.row == row).collect()
+    }
+
+    /// Distinct bar categories, in first-seen order
+    pub fn categories(&self) -> Vec<&str> {
+        let mut seen: Vec<&str> = Vec::new();
+        for bar in &self.bars {
+            if !seen.contains(&bar.category.as_str()) {
+                seen.push(&bar.category);
+            }
+        }
+        seen
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Activity, Assignment, Violation};
+
+    #[test]
+    fn test_gantt_model() {
+        let task = Task::new("T1")
+            .with_name("Order 1")
+            .with_category("urgent")
+            .with_activity(Activity::new("A1", "T1", 1))
+            .with_activity(Activity::new("A2", "T1", 2));
+        let other =
+            Task::new("T2").with_activity(Activity::new("B1", "T2", 1).with_predecessor("A1"));
+
+        let mut schedule = Schedule::new();
+        schedule.add_assignment(Assignment::new("A2", "T1", "M2", 3000, 5000));
+        schedule.add_assignment(Assignment::new("A1", "T1", "M1", 0, 3000).with_setup(500));
+        schedule.add_assignment(Assignment::new("B1", "T2", "X9", 3000, 4000));
+        schedule.add_violation(Violation::deadline_miss("T1", "late"));
+        schedule.add_violation(Violation::capacity_exceeded("M2", "overload"));
+
+        let resources = vec![
+            Resource::primary("M2").with_name("Lathe"),
+            Resource::primary("M1"),
+        ];
+        let gantt = GanttModel::build(&schedule, &[task, other], &resources);
+
+        let rows: Vec<(&str, &str)> = gantt
+            .rows
+            .iter()
+            .map(|r| (r.id.as_str(), r.label.as_str()))
+            .collect();
+        assert_eq!(rows, vec![("M2", "Lathe"), ("M1", "M1"), ("X9", "X9")]);
+        assert_eq!((gantt.start_ms, gantt.end_ms), (0, 5000));
+        assert_eq!(gantt.bars[1].activity_id, "A1");
+        assert_eq!(gantt.bars[1].setup_ms, 500);
+        assert_eq!(gantt.bars[1].labels, vec!["A1", "Order 1"]);
+        assert_eq!(gantt.categories(), vec!["urgent", "T2"]);
+        assert_eq!(
+            gantt.dependencies,
+            vec![
+                GanttDependency {
+                    from: 1,
+                    to: 0,
+                    kind: DependencyKind::Sequence
+                },
+                GanttDependency {
+                    from: 1,
+                    to: 2,
+                    kind: DependencyKind::Precedence
+                },
+            ]
+        );
+        assert_eq!(gantt.markers[0].time_ms, Some(5000));
+        assert_eq!(gantt.markers[1].row, Some(0));
+
+        // Schedule-only charts sort rows by ID and have no dependencies
+        let plain = GanttModel::from_schedule(&schedule);
+        assert_eq!(plain.rows[0].id, "M1");
+        assert!(plain.dependencies.is_empty());
+        assert_eq!(plain.bars_in_row(1)[0].activity_id, "A2");
+
+        let json = serde_json::to_string(&gantt).unwrap();
+        assert_eq!(serde_json::from_str::<GanttModel>(&json).unwrap(), gantt);
+    }
+}
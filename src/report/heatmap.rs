@@ -0,0 +1,266 @@
+//! Heatmap - Where and when a plan is stressed
+//!
+//! [`ViolationHeatmap`] bins problems of a schedule into resource rows and
+//! time buckets: late task completions, capacity conflicts (more
+//! overlapping assignments than the resource's capacity) and the
+//! severity of violations that can be placed in time (soft-penalty
+//! concentration). Rows match the [`GanttModel`] rows, so the grid lines
+//! up with the Gantt view of the HTML report.
+
+use super::gantt::GanttModel;
+use crate::models::{Resource, Schedule, Task};
+use serde::{Deserialize, Serialize};
+use std::fmt::Write as _;
+
+/// Problems in one resource row and time bucket
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct HeatCell {
+    /// Tasks completing late here (last assignment ends in the bucket)
+    pub late_tasks: usize,
+    /// Tardiness of those tasks (ms)
+    pub tardiness_ms: i64,
+    /// Time over capacity (ms)
+    pub conflict_ms: i64,
+    /// Summed severity of violations placed here
+    pub penalty: f64,
+}
+
+impl HeatCell {
+    /// Whether nothing is wrong here
+    pub fn is_clear(&self) -> bool {
+        self.late_tasks == 0 && self.conflict_ms == 0 && self.penalty == 0.0
+    }
+}
+
+/// Violation density per resource per time bucket
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ViolationHeatmap {
+    /// Start of the first bucket (epoch ms)
+    pub start_ms: i64,
+    /// Bucket width (ms)
+    pub bucket_ms: i64,
+    /// Resource ID per row
+    pub rows: Vec<String>,
+    /// Cells by row, then bucket
+    pub cells: Vec<Vec<HeatCell>>,
+    /// Violations without a row or time, not in any cell
+    pub unplaced: usize,
+}
+
+impl ViolationHeatmap {
+    /// Analyze a schedule in buckets of `bucket_ms` over its horizon
+    ///
+    /// Resources without a listed capacity count as capacity 1.
+    pub fn analyze(
+        schedule: &Schedule,
+        tasks: &[Task],
+        resources: &[Resource],
+        bucket_ms: i64,
+    ) -> Self {
+        let gantt = GanttModel::build(schedule, tasks, resources);
+        let bucket_ms = bucket_ms.max(1);
+        let span = gantt.end_ms - gantt.start_ms;
+        let buckets = ((span + bucket_ms - 1) / bucket_ms).max(1) as usize;
+        let mut heatmap = Self {
+            start_ms: gantt.start_ms,
+            bucket_ms,
+            rows: gantt.rows.iter().map(|r| r.id.clone()).collect(),
+            cells: vec![vec![HeatCell::default(); buckets]; gantt.rows.len()],
+            unplaced: 0,
+        };
+
+        // Late completions, at the bucket of the task's last assignment
+        for task in tasks {
+            let Some(deadline) = task.due_date().map(|d| d.timestamp_millis()) else {
+                continue;
+            };
+            let last = gantt
+                .bars
+                .iter()
+                .filter(|b| b.task_id == task.id)
+                .max_by_key(|b| b.end_ms);
+            if let Some(bar) = last.filter(|b| b.end_ms > deadline) {
+                let cell = heatmap.cell_at(bar.row, bar.end_ms - 1);
+                cell.late_tasks += 1;
+                cell.tardiness_ms += bar.end_ms - deadline;
+            }
+        }
+
+        // Capacity conflicts, by sweeping each row's bar starts and ends
+        for (row, id) in gantt.rows.iter().map(|r| &r.id).enumerate() {
+            let capacity = resources
+                .iter()
+                .find(|r| r.id == *id)
+                .map_or(1, |r| r.capacity.max(1)) as i64;
+            let mut events: Vec<(i64, i64)> = gantt
+                .bars_in_row(row)
+                .iter()
+                .flat_map(|b| [(b.start_ms, 1), (b.end_ms, -1)])
+                .collect();
+            // Ends before starts at the same time
+            events.sort_unstable();
+            let mut load = 0;
+            for pair in events.windows(2) {
+                load += pair[0].1;
+                if load > capacity {
+                    heatmap.spread_conflict(row, pair[0].0, pair[1].0);
+                }
+            }
+        }
+
+        // Violation severity where the Gantt marker sits; task-level
+        // markers use the row of the task's last assignment
+        for marker in &gantt.markers {
+            let row = marker.row.or_else(|| {
+                gantt
+                    .bars
+                    .iter()
+                    .filter(|b| b.task_id == marker.entity_id)
+                    .max_by_key(|b| b.end_ms)
+                    .map(|b| b.row)
+            });
+            match (row, marker.time_ms) {
+                (Some(row), Some(t)) => {
+                    heatmap.cell_at(row, t).penalty += marker.severity as f64;
+                }
+                _ => heatmap.unplaced += 1,
+            }
+        }
+        heatmap
+    }
+
+    /// Number of time buckets
+    pub fn bucket_count(&self) -> usize {
+        self.cells.first().map_or(0, |r| r.len())
+    }
+
+    /// Start of a bucket (epoch ms)
+    pub fn bucket_start(&self, bucket: usize) -> i64 {
+        self.start_ms + bucket as i64 * self.bucket_ms
+    }
+
+    /// Cell of a resource and bucket
+    pub fn cell(&self, resource_id: &str, bucket: usize) -> Option<&HeatCell> {
+        let row = self.rows.iter().position(|r| r == resource_id)?;
+        self.cells[row].get(bucket)
+    }
+
+    /// Stress of a cell (0.0 - 1.0): mean of its late tasks, conflict
+    /// time and penalty, each relative to the largest cell
+    pub fn intensity(&self, row: usize, bucket: usize) -> f64 {
+        let all = self.cells.iter().flatten();
+        let max_late = all.clone().map(|c| c.late_tasks).max().unwrap_or(0) as f64;
+        let max_conflict = all.clone().map(|c| c.conflict_ms).max().unwrap_or(0) as f64;
+        let max_penalty = all.map(|c| c.penalty).fold(0.0, f64::max);
+        let cell = &self.cells[row][bucket];
+        let share = |v: f64, max: f64| if max > 0.0 { v / max } else { 0.0 };
+        (share(cell.late_tasks as f64, max_late)
+            + share(cell.conflict_ms as f64, max_conflict)
+            + share(cell.penalty, max_penalty))
+            / 3.0
+    }
+
+    /// The `n` most stressed cells as (resource ID, bucket, intensity)
+    pub fn hotspots(&self, n: usize) -> Vec<(&str, usize, f64)> {
+        let mut spots: Vec<(&str, usize, f64)> = self
+            .cells
+            .iter()
+            .enumerate()
+            .flat_map(|(row, cells)| {
+                cells
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, c)| !c.is_clear())
+                    .map(move |(bucket, _)| (row, bucket))
+            })
+            .map(|(row, bucket)| (self.rows[row].as_str(), bucket, self.intensity(row, bucket)))
+            .collect();
+        spots.sort_by(|a, b| b.2.total_cmp(&a.2).then(a.1.cmp(&b.1)));
+        spots.truncate(n);
+        spots
+    }
+
+    /// Non-empty cells as CSV
+    /// (`resource,bucket_start_ms,late_tasks,tardiness_ms,conflict_ms,penalty`)
+    pub fn to_csv(&self) -> String {
+        let mut csv =
+            String::from("resource,bucket_start_ms,late_tasks,tardiness_ms,conflict_ms,penalty\n");
+        for (row, cells) in self.cells.iter().enumerate() {
+            for (bucket, cell) in cells.iter().enumerate().filter(|(_, c)| !c.is_clear()) {
+                let _ = writeln!(
+                    csv,
+                    "{},{},{},{},{},{}",
+                    self.rows[row],
+                    self.bucket_start(bucket),
+                    cell.late_tasks,
+                    cell.tardiness_ms,
+                    cell.conflict_ms,
+                    cell.penalty
+                );
+            }
+        }
+        csv
+    }
+
+    fn bucket_of(&self, t: i64) -> usize {
+        let last = self.bucket_count().saturating_sub(1);
+        ((t - self.start_ms).max(0) / self.bucket_ms).min(last as i64) as usize
+    }
+
+    fn cell_at(&mut self, row: usize, t: i64) -> &mut HeatCell {
+        let bucket = self.bucket_of(t);
+        &mut self.cells[row][bucket]
+    }
+
+    fn spread_conflict(&mut self, row: usize, from: i64, to: i64) {
+        let mut t = from;
+        while t < to {
+            let bucket = self.bucket_of(t);
+            let end = to.min(self.bucket_start(bucket + 1));
+            self.cells[row][bucket].conflict_ms += end - t;
+            t = end;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Activity, Assignment, Violation};
+    use chrono::DateTime;
+
+    #[test]
+    fn test_heatmap() {
+        let mut late = Task::new("T1").with_activity(Activity::new("A1", "T1", 1));
+        late.deadline = DateTime::from_timestamp_millis(2000);
+        let tasks = vec![late, Task::new("T2"), Task::new("T3")];
+        let resources = vec![Resource::primary("M1"), Resource::primary("M2")];
+
+        let mut schedule = Schedule::new();
+        schedule.add_assignment(Assignment::new("A1", "T1", "M1", 0, 3500));
+        schedule.add_assignment(Assignment::new("B1", "T2", "M2", 0, 2500));
+        schedule.add_assignment(Assignment::new("C1", "T3", "M2", 1500, 4000));
+        schedule.add_violation(Violation::deadline_miss("T1", "late"));
+        schedule.add_violation(Violation::capacity_exceeded("M2", "overlap"));
+
+        let heatmap = ViolationHeatmap::analyze(&schedule, &tasks, &resources, 1000);
+        assert_eq!(heatmap.bucket_count(), 4);
+        assert_eq!(heatmap.rows, vec!["M1", "M2"]);
+
+        let cell = heatmap.cell("M1", 3).unwrap();
+        assert_eq!((cell.late_tasks, cell.tardiness_ms), (1, 1500));
+        // The deadline miss is marked at T1's completion
+        assert_eq!(cell.penalty, 80.0);
+        // M2 runs B1 and C1 together from 1500 to 2500
+        let conflicts: Vec<i64> = (0..4)
+            .map(|b| heatmap.cell("M2", b).unwrap().conflict_ms)
+            .collect();
+        assert_eq!(conflicts, vec![0, 500, 500, 0]);
+        // Resource-level violations have no time
+        assert_eq!(heatmap.unplaced, 1);
+
+        assert_eq!(heatmap.hotspots(1), vec![("M1", 3, 2.0 / 3.0)]);
+        assert_eq!(heatmap.hotspots(10).len(), 3);
+        assert_eq!(heatmap.to_csv().lines().nth(1), Some("M1,3000,1,1500,0,80"));
+    }
+}
@@ -0,0 +1,487 @@
+//! HTML - Self-contained schedule report
+//!
+//! [`HtmlReport`] renders a solver run as one HTML file with inline CSS
+//! and SVG (no scripts or external assets): KPIs, a Gantt view,
+//! utilization charts per resource, a stress heatmap, the violations
+//! list and the schedule table. Planners can open it in any browser or attach it to an email.
+
+use super::gantt::GanttModel;
+use super::heatmap::ViolationHeatmap;
+use crate::models::{Resource, Schedule, Task};
+use crate::scheduler::ScheduleKpi;
+use chrono::DateTime;
+use std::fmt::Write as _;
+use std::path::Path;
+
+/// Bar colors by category (cycled)
+const PALETTE: [&str; 10] = [
+    "#4e79a7", "#f28e2b", "#59a14f", "#b07aa1", "#76b7b2", "#edc948", "#ff9da7", "#9c755f",
+    "#bab0ac", "#e15759",
+];
+/// Width of the row label column (px)
+const LABEL_WIDTH: f64 = 140.0;
+/// Width of the time area (px)
+const CHART_WIDTH: f64 = 860.0;
+/// Height of a Gantt row (px)
+const ROW_HEIGHT: f64 = 26.0;
+/// Height of a utilization chart (px)
+const LOAD_HEIGHT: f64 = 40.0;
+/// Utilization buckets when no bucket size is set
+const DEFAULT_BUCKETS: i64 = 48;
+
+/// HTML report generator
+#[derive(Debug, Clone)]
+pub struct HtmlReport {
+    /// Page title
+    pub title: String,
+    /// Utilization bucket size (ms); `None` splits the horizon in 48
+    pub bucket_ms: Option<i64>,
+    /// KPIs to show; `None` calculates them from the schedule and tasks
+    pub kpi: Option<ScheduleKpi>,
+}
+
+impl Default for HtmlReport {
+    fn default() -> Self {
+        Self::new("Schedule Report")
+    }
+}
+
+impl HtmlReport {
+    /// Create report with a title
+    pub fn new(title: &str) -> Self {
+        Self {
+            title: title.to_string(),
+            bucket_ms: None,
+            kpi: None,
+        }
+    }
+
+    /// Set utilization bucket size
+    pub fn with_bucket_ms(mut self, bucket_ms: i64) -> Self {
+        self.bucket_ms = Some(bucket_ms);
+        self
+    }
+
+    /// Set KPIs (e.g. priced with a cost model)
+    pub fn with_kpi(mut self, kpi: ScheduleKpi) -> Self {
+        self.kpi = Some(kpi);
+        self
+    }
+
+    /// Write the report to a file
+    pub fn write(
+        &self,
+        path: impl AsRef<Path>,
+        schedule: &Schedule,
+        tasks: &[Task],
+        resources: &[Resource],
+    ) -> std::io::Result<()> {
+        std::fs::write(path, self.render(schedule, tasks, resources))
+    }
+
+    /// Render the report as an HTML document
+    pub fn render(&self, schedule: &Schedule, tasks: &[Task], resources: &[Resource]) -> String {
+        let gantt = GanttModel::build(schedule, tasks, resources);
+        let kpi = self
+            .kpi
+            .clone()
+            .unwrap_or_else(|| ScheduleKpi::calculate(schedule, tasks));
+
+        let mut html = String::new();
+        let _ = write!(
+            html,
+            "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+             <title>{title}</title>\n<style>{STYLE}</style>\n</head>\n<body>\n<h1>{title}</h1>\n\
+             <p class=\"meta\">{} assignments on {} resources, {} to {}</p>\n",
+            schedule.assignment_count(),
+            gantt.rows.len(),
+            format_time(gantt.start_ms),
+            format_time(gantt.end_ms),
+            title = escape(&self.title),
+        );
+        self.kpi_section(&mut html, &kpi);
+        self.gantt_section(&mut html, &gantt);
+        self.utilization_section(&mut html, schedule, &gantt);
+        let heatmap =
+            ViolationHeatmap::analyze(schedule, tasks, resources, self.bucket_size(&gantt));
+        self.stress_section(&mut html, &heatmap);
+        self.violation_section(&mut html, &gantt);
+        self.table_section(&mut html, &gantt);
+        html.push_str("</body>\n</html>\n");
+        html
+    }
+
+    fn kpi_section(&self, html: &mut String, kpi: &ScheduleKpi) {
+        let mut rows = vec![
+            ("Makespan", format_duration(kpi.makespan_ms)),
+            ("On-time rate", format_percent(kpi.on_time_rate)),
+            ("Late tasks", kpi.late_tasks.to_string()),
+            ("Total tardiness", format_duration(kpi.total_tardiness_ms)),
+            ("Max tardiness", format_duration(kpi.max_tardiness_ms)),
+            ("Total earliness", format_duration(kpi.total_earliness_ms)),
+            (
+                "Average flow time",
+                format_duration(kpi.avg_flow_time_ms as i64),
+            ),
+            ("Average utilization", format_percent(kpi.avg_utilization)),
+        ];
+        if kpi.total_cost != 0.0 {
+            rows.push(("Total cost", format!("{:.2}", kpi.total_cost)));
+        }
+        if kpi.energy_cost != 0.0 {
+            rows.push(("Energy cost", format!("{:.2}", kpi.energy_cost)));
+        }
+        if kpi.makespan_lower_bound_ms > 0 {
+            rows.push(("Optimality gap", format_percent(kpi.optimality_gap)));
+        }
+        if kpi.setup_count > 0 {
+            rows.push(("Setups", kpi.setup_count.to_string()));
+            rows.push(("Setup / processing", format_percent(kpi.setup_ratio)));
+        }
+
+        html.push_str("<h2>KPIs</h2>\n<div class=\"kpis\">\n");
+        for (name, value) in rows {
+            let _ = writeln!(
+                html,
+                "<div class=\"kpi\"><span>{}</span><b>{}</b></div>",
+                name, value
+            );
+        }
+        html.push_str("</div>\n");
+    }
+
+    fn gantt_section(&self, html: &mut String, gantt: &GanttModel) {
+        html.push_str("<h2>Gantt</h2>\n");
+        if gantt.bars.is_empty() {
+            html.push_str("<p>No assignments.</p>\n");
+            return;
+        }
+        let axis = 20.0;
+        let height = axis + gantt.rows.len() as f64 * ROW_HEIGHT;
+        let x = |t: i64| x_of(t, gantt.start_ms, gantt.end_ms);
+        let categories = gantt.categories();
+
+        let _ = writeln!(
+            html,
+            "<svg class=\"gantt\" width=\"{}\" height=\"{}\" xmlns=\"http://www.w3.org/2000/svg\">",
+            LABEL_WIDTH + CHART_WIDTH,
+            height
+        );
+        for tick in 0..=4 {
+            let t = gantt.start_ms + (gantt.end_ms - gantt.start_ms) * tick / 4;
+            let _ = writeln!(
+                html,
+                "<line class=\"grid\" x1=\"{x:.1}\" y1=\"{axis}\" x2=\"{x:.1}\" y2=\"{height}\"/>\
+                 <text class=\"tick\" x=\"{x:.1}\" y=\"12\">{}</text>",
+                escape(&format_time(t)),
+                x = x(t),
+            );
+        }
+        for (i, row) in gantt.rows.iter().enumerate() {
+            let y = axis + i as f64 * ROW_HEIGHT;
+            let _ = writeln!(
+                html,
+                "<text class=\"row\" x=\"4\" y=\"{:.1}\">{}</text>",
+                y + ROW_HEIGHT * 0.65,
+                escape(&row.label)
+            );
+        }
+        for bar in &gantt.bars {
+            let y = axis + bar.row as f64 * ROW_HEIGHT + 3.0;
+            let (x0, x1) = (x(bar.start_ms), x(bar.end_ms));
+            let color = categories
+                .iter()
+                .position(|c| *c == bar.category)
+                .map_or(PALETTE[0], |c| PALETTE[c % PALETTE.len()]);
+            let _ = write!(
+                html,
+                "<g><title>{} ({}): {} - {}</title>\
+                 <rect x=\"{x0:.1}\" y=\"{y:.1}\" width=\"{:.1}\" height=\"{:.1}\" fill=\"{color}\"/>",
+                escape(&bar.labels.join(" / ")),
+                escape(&bar.category),
+                escape(&format_time(bar.start_ms)),
+                escape(&format_time(bar.end_ms)),
+                (x1 - x0).max(1.0),
+                ROW_HEIGHT - 6.0,
+            );
+            if bar.setup_ms > 0 {
+                let _ = write!(
+                    html,
+                    "<rect class=\"setup\" x=\"{x0:.1}\" y=\"{y:.1}\" width=\"{:.1}\" height=\"{:.1}\"/>",
+                    x(bar.start_ms + bar.setup_ms) - x0,
+                    ROW_HEIGHT - 6.0,
+                );
+            }
+            html.push_str("</g>\n");
+        }
+        for dep in &gantt.dependencies {
+            let (from, to) = (&gantt.bars[dep.from], &gantt.bars[dep.to]);
+            let _ = writeln!(
+                html,
+                "<line class=\"dep\" x1=\"{:.1}\" y1=\"{:.1}\" x2=\"{:.1}\" y2=\"{:.1}\"/>",
+                x(from.end_ms),
+                axis + (from.row as f64 + 0.5) * ROW_HEIGHT,
+                x(to.start_ms),
+                axis + (to.row as f64 + 0.5) * ROW_HEIGHT,
+            );
+        }
+        for marker in &gantt.markers {
+            let (Some(row), Some(t)) = (marker.row, marker.time_ms) else {
+                continue;
+            };
+            let (mx, my) = (x(t), axis + row as f64 * ROW_HEIGHT + 2.0);
+            let _ = writeln!(
+                html,
+                "<g><title>{}</title><path class=\"marker\" d=\"M{:.1} {:.1} l5 -8 h-10 z\"/></g>",
+                escape(&marker.message),
+                mx,
+                my + 8.0,
+            );
+        }
+        html.push_str("</svg>\n");
+    }
+
+    fn utilization_section(&self, html: &mut String, schedule: &Schedule, gantt: &GanttModel) {
+        html.push_str("<h2>Utilization</h2>\n");
+        let span = gantt.end_ms - gantt.start_ms;
+        if span <= 0 {
+            html.push_str("<p>No assignments.</p>\n");
+            return;
+        }
+        let bucket_ms = self.bucket_size(gantt);
+        let x = |t: i64| x_of(t, gantt.start_ms, gantt.end_ms);
+
+        html.push_str("<table class=\"load\">\n");
+        for row in &gantt.rows {
+            let timeline = schedule.utilization_timeline(&row.id, bucket_ms);
+            let busy: i64 = timeline.iter().map(|b| b.busy_ms + b.setup_ms).sum();
+            let _ = write!(
+                html,
+                "<tr><th>{}</th><td>{}</td><td><svg width=\"{}\" height=\"{}\" xmlns=\"http://www.w3.org/2000/svg\">",
+                escape(&row.label),
+                format_percent(busy as f64 / span as f64),
+                CHART_WIDTH,
+                LOAD_HEIGHT
+            );
+            for bucket in &timeline {
+                let length = (bucket.end_ms - bucket.start_ms) as f64;
+                let (x0, width) = (
+                    x(bucket.start_ms) - LABEL_WIDTH,
+                    x(bucket.end_ms) - x(bucket.start_ms),
+                );
+                let busy_h = (bucket.busy_ms as f64 / length).min(1.0) * LOAD_HEIGHT;
+                let setup_h = (bucket.setup_ms as f64 / length).min(1.0) * LOAD_HEIGHT;
+                let _ = write!(
+                    html,
+                    "<g><title>{}: {} busy</title>\
+                     <rect class=\"busy\" x=\"{x0:.1}\" y=\"{:.1}\" width=\"{width:.1}\" height=\"{busy_h:.1}\"/>\
+                     <rect class=\"setup\" x=\"{x0:.1}\" y=\"{:.1}\" width=\"{width:.1}\" height=\"{setup_h:.1}\"/></g>",
+                    escape(&format_time(bucket.start_ms)),
+                    format_percent(bucket.utilization()),
+                    LOAD_HEIGHT - busy_h,
+                    (LOAD_HEIGHT - busy_h - setup_h).max(0.0),
+                );
+            }
+            html.push_str("</svg></td></tr>\n");
+        }
+        html.push_str("</table>\n");
+    }
+
+    fn stress_section(&self, html: &mut String, heatmap: &ViolationHeatmap) {
+        html.push_str("<h2>Stress</h2>\n");
+        if heatmap.cells.iter().flatten().all(|c| c.is_clear()) {
+            html.push_str("<p>No late tasks, capacity conflicts or placed violations.</p>\n");
+            return;
+        }
+        html.push_str("<table class=\"heat\">\n");
+        for (row, cells) in heatmap.cells.iter().enumerate() {
+            let _ = write!(html, "<tr><th>{}</th>", escape(&heatmap.rows[row]));
+            for (bucket, cell) in cells.iter().enumerate() {
+                let _ = write!(
+                    html,
+                    "<td style=\"background:rgba(214,39,40,{:.2})\" title=\"{}: {} late, {} over capacity, penalty {}\"></td>",
+                    heatmap.intensity(row, bucket),
+                    escape(&format_time(heatmap.bucket_start(bucket))),
+                    cell.late_tasks,
+                    format_duration(cell.conflict_ms),
+                    cell.penalty
+                );
+            }
+            html.push_str("</tr>\n");
+        }
+        html.push_str("</table>\n");
+    }
+
+    /// Bucket size of the utilization and stress charts
+    fn bucket_size(&self, gantt: &GanttModel) -> i64 {
+        let span = gantt.end_ms - gantt.start_ms;
+        self.bucket_ms
+            .filter(|&b| b > 0)
+            .unwrap_or_else(|| ((span + DEFAULT_BUCKETS - 1) / DEFAULT_BUCKETS).max(1))
+    }
+
+    fn violation_section(&self, html: &mut String, gantt: &GanttModel) {
+        let _ = writeln!(html, "<h2>Violations ({})</h2>", gantt.markers.len());
+        if gantt.markers.is_empty() {
+            html.push_str("<p>None.</p>\n");
+            return;
+        }
+        html.push_str(
+            "<table>\n<tr><th>Type</th><th>Entity</th><th>Severity</th><th>Message</th></tr>\n",
+        );
+        let mut markers: Vec<_> = gantt.markers.iter().collect();
+        markers.sort_by_key(|m| std::cmp::Reverse(m.severity));
+        for marker in markers {
+            let _ = writeln!(
+                html,
+                "<tr class=\"violation\"><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+                escape(&format!("{:?}", marker.violation_type)),
+                escape(&marker.entity_id),
+                marker.severity,
+                escape(&marker.message)
+            );
+        }
+        html.push_str("</table>\n");
+    }
+
+    fn table_section(&self, html: &mut String, gantt: &GanttModel) {
+        html.push_str(
+            "<h2>Schedule</h2>\n<table>\n<tr><th>Activity</th><th>Task</th><th>Resource</th>\
+             <th>Start</th><th>End</th><th>Duration</th><th>Setup</th></tr>\n",
+        );
+        let mut bars: Vec<_> = gantt.bars.iter().collect();
+        bars.sort_by(|a, b| (a.start_ms, &a.activity_id).cmp(&(b.start_ms, &b.activity_id)));
+        for bar in bars {
+            let _ = writeln!(
+                html,
+                "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+                escape(&bar.activity_id),
+                escape(&bar.task_id),
+                escape(&gantt.rows[bar.row].label),
+                escape(&format_time(bar.start_ms)),
+                escape(&format_time(bar.end_ms)),
+                format_duration(bar.end_ms - bar.start_ms),
+                format_duration(bar.setup_ms),
+            );
+        }
+        html.push_str("</table>\n");
+    }
+}
+
+const STYLE: &str = "body{font-family:system-ui,sans-serif;margin:24px;color:#222}\
+h2{margin-top:32px;border-bottom:1px solid #ddd}.meta{color:#666}\
+.kpis{display:flex;flex-wrap:wrap;gap:12px}\
+.kpi{border:1px solid #ddd;border-radius:6px;padding:8px 12px;min-width:140px}\
+.kpi span{display:block;color:#666;font-size:12px}.kpi b{font-size:18px}\
+table{border-collapse:collapse;font-size:13px}th,td{border:1px solid #ddd;padding:3px 8px;text-align:left}\
+.load th{width:128px}.load td{padding:2px 6px}svg text{font-size:11px}\
+.grid{stroke:#eee}.tick{fill:#666}.row{fill:#222}\
+.setup{fill:#000;fill-opacity:.35}.busy{fill:#4e79a7}\
+.dep{stroke:#888;stroke-dasharray:3 2}.marker{fill:#d62728}\
+.violation td{background:#fff2f2}\
+.heat td{width:12px;height:14px;padding:0}";
+
+/// X coordinate of a time on the chart
+fn x_of(t: i64, start_ms: i64, end_ms: i64) -> f64 {
+    let span = (end_ms - start_ms).max(1) as f64;
+    LABEL_WIDTH + (t - start_ms) as f64 / span * CHART_WIDTH
+}
+
+/// Escape text for HTML content and attributes
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// Epoch times as UTC date-time; times within a year of 0 as offsets
+fn format_time(ms: i64) -> String {
+    const YEAR_MS: i64 = 365 * 86_400_000;
+    if ms.abs() < YEAR_MS {
+        return format!("+{}", format_duration(ms));
+    }
+    DateTime::from_timestamp_millis(ms)
+        .map(|t| t.format("%Y-%m-%d %H:%M").to_string())
+        .unwrap_or_else(|| ms.to_string())
+}
+
+/// Duration as [d] hh:mm:ss
+fn format_duration(ms: i64) -> String {
+    let sign = if ms < 0 { "-" } else { "" };
+    let s = ms.abs() / 1000;
+    let (d, h, m, s) = (s / 86_400, s / 3600 % 24, s / 60 % 60, s % 60);
+    if d > 0 {
+        format!("{}{}d {:02}:{:02}:{:02}", sign, d, h, m, s)
+    } else {
+        format!("{}{:02}:{:02}:{:02}", sign, h, m, s)
+    }
+}
+
+fn format_percent(value: f64) -> String {
+    format!("{:.1}%", value * 100.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Activity, Assignment, Violation};
+
+    #[test]
+    fn test_render() {
+        let tasks = vec![Task::new("T1")
+            .with_name("Order <1>")
+            .with_activity(Activity::new("A1", "T1", 1))
+            .with_activity(Activity::new("A2", "T1", 2))];
+        let resources = vec![Resource::primary("M1"), Resource::primary("M2")];
+        let mut schedule = Schedule::new();
+        schedule.add_assignment(Assignment::new("A1", "T1", "M1", 0, 60_000).with_setup(10_000));
+        schedule.add_assignment(Assignment::new("A2", "T1", "M2", 60_000, 90_000));
+        schedule.add_violation(Violation::capacity_exceeded("M2", "too much & more"));
+
+        let html = HtmlReport::new("Week 12")
+            .with_bucket_ms(30_000)
+            .render(&schedule, &tasks, &resources);
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<title>Week 12</title>"));
+        for section in [
+            "KPIs",
+            "Gantt",
+            "Utilization",
+            "Stress",
+            "Violations (1)",
+            "Schedule",
+        ] {
+            assert!(
+                html.contains(&format!("<h2>{}</h2>", section)),
+                "{}",
+                section
+            );
+        }
+        assert!(html.contains("A1 / Order &lt;1&gt;"));
+        assert!(html.contains("too much &amp; more"));
+        assert!(html.contains("<td>+00:01:00</td><td>+00:01:30</td><td>00:00:30</td>"));
+        assert!(html.contains("class=\"dep\""));
+        assert!(!html.contains("<script"));
+
+        assert_eq!(format_time(1_700_000_000_000), "2023-11-14 22:13");
+        assert_eq!(format_duration(90_061_000), "1d 01:01:01");
+
+        let path = std::env::temp_dir().join("u_ras_report_test.html");
+        HtmlReport::default()
+            .write(&path, &schedule, &tasks, &resources)
+            .unwrap();
+        assert!(std::fs::read_to_string(&path)
+            .unwrap()
+            .contains("Schedule Report"));
+        let _ = std::fs::remove_file(path);
+    }
+}
@@ -0,0 +1,16 @@
+//! Report - Presentation data derived from schedules
+//!
+//! Renderer-agnostic models that exporters consume and front ends can
+//! serialize for their own chart libraries:
+//!
+//! - [`GanttModel`] - Rows, bars, dependency arrows and violation markers
+//! - [`ViolationHeatmap`] - Violation density per resource and time bucket
+//! - [`HtmlReport`] - Single-file HTML report of a solver run
+
+mod gantt;
+mod heatmap;
+mod html;
+
+pub use gantt::*;
+pub use heatmap::*;
+pub use html::*;
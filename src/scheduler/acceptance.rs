@@ -0,0 +1,309 @@
+//! Acceptance - Order acceptance and capacity feasibility
+//!
+//! Answers whether a prospective task can be promised a deadline without
+//! making committed work late (and which resources stand in the way), and
+//! quotes the earliest completion a new order can get in the current plan
+//! (capable-to-promise).
+
+use super::heft::earliest_gap;
+use super::simple::SimpleScheduler;
+use crate::models::{Assignment, Resource, Schedule, Task};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// Outcome of an acceptance check
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AcceptanceDecision {
+    /// The task meets its requested deadline and no committed deadline
+    /// is newly missed
+    pub accepted: bool,
+    /// Planned completion of the prospective task (None if it could not
+    /// be fully scheduled)
+    pub completion_ms: Option<i64>,
+    /// Committed tasks that would become late
+    pub newly_late_tasks: Vec<String>,
+    /// Resources the prospective task queued for, or shares with newly
+    /// late tasks (empty when accepted)
+    pub blocking_resources: Vec<String>,
+}
+
+/// Order acceptance check
+///
+/// Plans the committed tasks with and without the prospective one using
+/// the underlying scheduler. Committed tasks already late without the
+/// new task do not count against it.
+#[derive(Clone, Default)]
+pub struct OrderAcceptance {
+    /// Underlying scheduler
+    scheduler: SimpleScheduler,
+}
+
+impl OrderAcceptance {
+    /// Create check with the default scheduler
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set underlying scheduler
+    pub fn with_scheduler(mut self, scheduler: SimpleScheduler) -> Self {
+        self.scheduler = scheduler;
+        self
+    }
+
+    /// Check whether `candidate` can be accepted with `deadline`
+    pub fn check(
+        &self,
+        committed: &[Task],
+        candidate: &Task,
+        deadline: DateTime<Utc>,
+        resources: &[Resource],
+        start_time_ms: i64,
+    ) -> AcceptanceDecision {
+        let baseline = self.scheduler.schedule(committed, resources, start_time_ms);
+        let late_before = late_tasks(&baseline, committed);
+
+        let mut candidate = candidate.clone();
+        candidate.deadline = Some(deadline);
+        let mut tasks = committed.to_vec();
+        tasks.push(candidate.clone());
+        let schedule = self.scheduler.schedule(&tasks, resources, start_time_ms);
+
+        let newly_late_tasks: Vec<String> = late_tasks(&schedule, committed)
+            .into_iter()
+            .filter(|t| !late_before.contains(t))
+            .collect();
+        let complete = candidate
+            .activities
+            .iter()
+            .all(|a| schedule.assignment_for_activity(&a.id).is_some());
+        let completion_ms = schedule
+            .task_completion_time(&candidate.id)
+            .filter(|_| complete);
+        let on_time = completion_ms.is_some_and(|c| c <= deadline.timestamp_millis());
+        let accepted = on_time && newly_late_tasks.is_empty();
+
+        let mut blocking: Vec<String> = Vec::new();
+        if !accepted {
+            let release = candidate
+                .release_time
+                .map_or(start_time_ms, |r| r.timestamp_millis().max(start_time_ms));
+            let mut ready = release;
+            for activity in &candidate.activities {
+                let assigned = schedule.assignments_for_activity(&activity.id);
+                if assigned.is_empty() {
+                    blocking.extend(activity.candidate_resources());
+                    continue;
+                }
+                for a in &assigned {
+                    // Queued behind other work on this resource
+                    if a.start_ms > ready {
+                        blocking.push(a.resource_id.clone());
+                    }
+                    // Pushing a committed task past its deadline
+                    let shared = newly_late_tasks.iter().any(|t| {
+                        schedule
+                            .assignments_for_task(t)
+                            .iter()
+                            .any(|o| o.resource_id == a.resource_id)
+                    });
+                    if shared {
+                        blocking.push(a.resource_id.clone());
+                    }
+                }
+                ready = assigned.iter().map(|a| a.end_ms).max().unwrap_or(ready);
+            }
+            blocking.sort();
+            blocking.dedup();
+        }
+
+        AcceptanceDecision {
+            accepted,
+            completion_ms,
+            newly_late_tasks,
+            blocking_resources: blocking,
+        }
+    }
+}
+
+/// Capable-to-promise quote for a new task
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompletionQuote {
+    /// Earliest achievable completion (epoch ms)
+    pub completion_ms: i64,
+    /// Tentative placement of the task's activities (not committed)
+    pub assignments: Vec<Assignment>,
+}
+
+/// Earliest completion of `task` inserted into the current plan
+///
+/// Existing assignments stay where they are; the task's activities run in
+/// order, each in the earliest idle gap of the candidate finishing first.
+/// The task starts at its release time, else at the start of the plan.
+/// `None` if some activity has no known candidate resource.
+pub fn quote_completion(
+    task: &Task,
+    schedule: &Schedule,
+    resources: &[Resource],
+) -> Option<CompletionQuote> {
+    let plan_start = schedule
+        .assignments
+        .iter()
+        .map(|a| a.start_ms)
+        .min()
+        .unwrap_or(0);
+    let mut ready = task
+        .release_time
+        .map_or(plan_start, |r| r.timestamp_millis());
+    let mut assignments = Vec::new();
+
+    for activity in &task.activities {
+        let mut best: Option<(&str, i64, i64)> = None;
+        for candidate in activity.candidate_resources() {
+            let Some(resource) = resources.iter().find(|r| r.id == candidate) else {
+                continue;
+            };
+            let mut busy: Vec<(i64, i64)> = schedule
+                .assignments_for_resource(&resource.id)
+                .iter()
+                .map(|a| (a.start_ms, a.end_ms))
+                .collect();
+            busy.sort_unstable();
+            let duration = resource.processing_time(activity.duration.process_ms);
+            let start = earliest_gap(&busy, ready, duration);
+            if best.is_none_or(|(_, _, end)| start + duration < end) {
+                best = Some((resource.id.as_str(), start, start + duration));
+            }
+        }
+        let (resource_id, start, end) = best?;
+        assignments.push(Assignment::new(
+            &activity.id,
+            &task.id,
+            resource_id,
+            start,
+            end,
+        ));
+        ready = end;
+    }
+
+    Some(CompletionQuote {
+        completion_ms: ready,
+        assignments,
+    })
+}
+
+/// Tasks with a deadline that finish after it or are not fully scheduled
+fn late_tasks(schedule: &Schedule, tasks: &[Task]) -> HashSet<String> {
+    tasks
+        .iter()
+        .filter(|t| {
+            let Some(deadline) = t.deadline else {
+                return false;
+            };
+            let complete = t
+                .activities
+                .iter()
+                .all(|a| schedule.assignment_for_activity(&a.id).is_some());
+            !complete
+                || schedule
+                    .task_completion_time(&t.id)
+                    .is_some_and(|c| c > deadline.timestamp_millis())
+        })
+        .map(|t| t.id.clone())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Activity, ActivityDuration};
+    use chrono::TimeZone;
+
+    fn job(id: &str, priority: i32, ms: i64, deadline_ms: i64) -> Task {
+        Task::new(id)
+            .with_priority(priority)
+            .with_deadline(Utc.timestamp_millis_opt(deadline_ms).unwrap())
+            .with_activity(
+                Activity::new(&format!("{}-A1", id), id, 1)
+                    .with_duration(ActivityDuration::fixed(ms))
+                    .with_resources("machine", vec!["M1".into()]),
+            )
+    }
+
+    fn at(ms: i64) -> DateTime<Utc> {
+        Utc.timestamp_millis_opt(ms).unwrap()
+    }
+
+    #[test]
+    fn test_quote_completion() {
+        let mut plan = Schedule::new();
+        plan.add_assignment(Assignment::new("C1-A1", "C1", "M1", 0, 2000));
+        plan.add_assignment(Assignment::new("C2-A1", "C2", "M1", 3000, 6000));
+        let resources = vec![Resource::primary("M1")];
+
+        // Fits the idle gap between the committed jobs
+        let quote = quote_completion(&job("New", 1, 1000, 0), &plan, &resources).unwrap();
+        assert_eq!(quote.completion_ms, 3000);
+        assert_eq!(quote.assignments[0].start_ms, 2000);
+        // The plan itself is untouched
+        assert_eq!(plan.assignment_count(), 2);
+
+        // Too long for the gap: queued after the last job
+        let quote = quote_completion(&job("Big", 1, 1500, 0), &plan, &resources).unwrap();
+        assert_eq!(quote.completion_ms, 7500);
+
+        let orphan = Task::new("X").with_activity(
+            Activity::new("X-A1", "X", 1).with_resources("machine", vec!["Nowhere".into()]),
+        );
+        assert!(quote_completion(&orphan, &plan, &resources).is_none());
+    }
+
+    #[test]
+    fn test_accept_when_capacity_left() {
+        let committed = vec![job("C1", 5, 2000, 3000)];
+        let resources = vec![Resource::primary("M1")];
+
+        let decision = OrderAcceptance::new().check(
+            &committed,
+            &job("New", 1, 1000, 0),
+            at(5000),
+            &resources,
+            0,
+        );
+
+        assert!(decision.accepted);
+        assert_eq!(decision.completion_ms, Some(3000));
+        assert!(decision.blocking_resources.is_empty());
+    }
+
+    #[test]
+    fn test_reject_with_blocking_resource() {
+        let committed = vec![job("C1", 5, 2000, 3000)];
+        let resources = vec![Resource::primary("M1")];
+
+        // Queued behind C1, the new order cannot finish by 2500
+        let decision = OrderAcceptance::new().check(
+            &committed,
+            &job("New", 1, 1000, 0),
+            at(2500),
+            &resources,
+            0,
+        );
+        assert!(!decision.accepted);
+        assert!(decision.newly_late_tasks.is_empty());
+        assert_eq!(decision.blocking_resources, vec!["M1"]);
+
+        // An urgent order jumps the queue but makes C1 late
+        let decision = OrderAcceptance::new().check(
+            &committed,
+            &job("Rush", 9, 2000, 0),
+            at(2500),
+            &resources,
+            0,
+        );
+        assert!(!decision.accepted);
+        assert_eq!(decision.completion_ms, Some(2000));
+        assert_eq!(decision.newly_late_tasks, vec!["C1"]);
+        assert_eq!(decision.blocking_resources, vec!["M1"]);
+    }
+}
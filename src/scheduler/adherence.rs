@@ -0,0 +1,183 @@
+//! Adherence - Planned versus actual execution
+//!
+//! Compares a schedule with its execution log: start deviations, resource
+//! changes and actual/planned duration ratios per resource. The ratios
+//! calibrate resource efficiencies for the next plan.
+
+use crate::models::{ExecutionLog, Resource, Schedule};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Planned versus actual execution of one activity
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActivityAdherence {
+    /// Activity ID
+    pub activity_id: String,
+    /// Planned resource
+    pub planned_resource: String,
+    /// Actual resource
+    pub actual_resource: String,
+    /// Actual minus planned start (ms, positive = late)
+    pub start_deviation_ms: i64,
+    /// Planned duration (ms)
+    pub planned_duration_ms: i64,
+    /// Actual duration (None while running)
+    pub actual_duration_ms: Option<i64>,
+}
+
+impl ActivityAdherence {
+    /// Actual / planned duration (None while running or if nothing was planned)
+    pub fn duration_ratio(&self) -> Option<f64> {
+        self.actual_duration_ms
+            .filter(|_| self.planned_duration_ms > 0)
+            .map(|actual| actual as f64 / self.planned_duration_ms as f64)
+    }
+
+    /// Whether the activity ran on its planned resource
+    pub fn on_planned_resource(&self) -> bool {
+        self.planned_resource == self.actual_resource
+    }
+}
+
+/// Schedule adherence report
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ScheduleAdherence {
+    /// Planned activities that were executed
+    pub activities: Vec<ActivityAdherence>,
+    /// Share of executed activities started within the tolerance (0.0 to 1.0)
+    pub start_adherence: f64,
+    /// Share of executed activities on their planned resource (0.0 to 1.0)
+    pub resource_adherence: f64,
+    /// Mean absolute start deviation (ms)
+    pub mean_start_deviation_ms: f64,
+    /// Mean actual / planned duration per actual resource (finished work)
+    pub duration_ratio_by_resource: HashMap<String, f64>,
+    /// Executed activities missing from the plan
+    pub unplanned: Vec<String>,
+}
+
+impl ScheduleAdherence {
+    /// Compare a schedule with its execution log
+    ///
+    /// Activities starting within `tolerance_ms` of their planned start
+    /// count as adhering. Activities with several assignments are planned
+    /// from their first start to their last end.
+    pub fn calculate(schedule: &Schedule, log: &ExecutionLog, tolerance_ms: i64) -> Self {
+        let mut activities = Vec::new();
+        let mut unplanned = Vec::new();
+        for record in &log.records {
+            let planned = schedule.assignments_for_activity(&record.activity_id);
+            let (Some(start), Some(end)) = (
+                planned.iter().map(|a| a.start_ms).min(),
+                planned.iter().map(|a| a.end_ms).max(),
+            ) else {
+                unplanned.push(record.activity_id.clone());
+                continue;
+            };
+            activities.push(ActivityAdherence {
+                activity_id: record.activity_id.clone(),
+                planned_resource: planned[0].resource_id.clone(),
+                actual_resource: record.resource_id.clone(),
+                start_deviation_ms: record.actual_start_ms - start,
+                planned_duration_ms: end - start,
+                actual_duration_ms: record.duration_ms(),
+            });
+        }
+
+        let count = activities.len().max(1) as f64;
+        let on_time = activities
+            .iter()
+            .filter(|a| a.start_deviation_ms.abs() <= tolerance_ms)
+            .count();
+        let on_resource = activities
+            .iter()
+            .filter(|a| a.on_planned_resource())
+            .count();
+        let deviation: i64 = activities.iter().map(|a| a.start_deviation_ms.abs()).sum();
+
+        let mut ratios: HashMap<String, (f64, usize)> = HashMap::new();
+        for activity in &activities {
+            if let Some(ratio) = activity.duration_ratio() {
+                let entry = ratios.entry(activity.actual_resource.clone()).or_default();
+                entry.0 += ratio;
+                entry.1 += 1;
+            }
+        }
+
+        Self {
+            start_adherence: if activities.is_empty() {
+                1.0
+            } else {
+                on_time as f64 / count
+            },
+            resource_adherence: if activities.is_empty() {
+                1.0
+            } else {
+                on_resource as f64 / count
+            },
+            mean_start_deviation_ms: deviation as f64 / count,
+            duration_ratio_by_resource: ratios
+                .into_iter()
+                .map(|(id, (sum, n))| (id, sum / n as f64))
+                .collect(),
+            activities,
+            unplanned,
+        }
+    }
+
+    /// Resources with efficiencies calibrated to the observed durations
+    ///
+    /// A resource whose work took `r` times as long as planned gets its
+    /// efficiency divided by `r`. Resources without finished work are
+    /// returned unchanged.
+    pub fn calibrated_resources(&self, resources: &[Resource]) -> Vec<Resource> {
+        resources
+            .iter()
+            .map(|resource| {
+                let mut resource = resource.clone();
+                if let Some(&ratio) = self.duration_ratio_by_resource.get(&resource.id) {
+                    if ratio > 0.0 && resource.efficiency > 0.0 {
+                        resource.efficiency /= ratio;
+                    }
+                }
+                resource
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Assignment, ExecutionRecord};
+
+    #[test]
+    fn test_adherence() {
+        let mut schedule = Schedule::new();
+        schedule.add_assignment(Assignment::new("A1", "T1", "M1", 0, 1000));
+        schedule.add_assignment(Assignment::new("A2", "T1", "M1", 1000, 2000));
+        schedule.add_assignment(Assignment::new("A3", "T2", "M2", 0, 1000));
+        let log = ExecutionLog::new()
+            .with_record(ExecutionRecord::new("A1", "T1", "M1", 100).with_end(1600))
+            .with_record(ExecutionRecord::new("A2", "T1", "M1", 1600).with_end(3100))
+            .with_record(ExecutionRecord::new("A3", "T2", "M1", 3100))
+            .with_record(ExecutionRecord::new("X", "T3", "M2", 0));
+
+        let report = ScheduleAdherence::calculate(&schedule, &log, 200);
+
+        assert_eq!(report.activities.len(), 3);
+        assert_eq!(report.unplanned, vec!["X"]);
+        // Only A1 started within 200 ms
+        assert!((report.start_adherence - 1.0 / 3.0).abs() < 1e-9);
+        assert!((report.resource_adherence - 2.0 / 3.0).abs() < 1e-9);
+        assert!((report.mean_start_deviation_ms - 3800.0 / 3.0).abs() < 1e-9);
+        // M1 work took 1.5x as long; A3 is still running
+        assert_eq!(report.duration_ratio_by_resource.len(), 1);
+        assert!((report.duration_ratio_by_resource["M1"] - 1.5).abs() < 1e-9);
+
+        let resources = vec![Resource::primary("M1"), Resource::primary("M2")];
+        let calibrated = report.calibrated_resources(&resources);
+        assert!((calibrated[0].efficiency - 1.0 / 1.5).abs() < 1e-9);
+        assert_eq!(calibrated[1].efficiency, 1.0);
+    }
+}
@@ -0,0 +1,368 @@
+//! Background - Non-blocking solving
+//!
+//! Runs a solver on a background thread and hands back a future, so
+//! async services (tokio, async-std, ...) can await long solves without
+//! blocking a request handler. The future is runtime-agnostic: it is
+//! woken from the solver thread when the result is ready.
+//!
+//! `solve_streaming` additionally yields every improved incumbent while
+//! the solver runs, so a UI can show the best-so-far plan live.
+
+use std::future::Future;
+use std::panic::{self, AssertUnwindSafe};
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+use std::thread;
+
+/// Cooperative cancellation flag shared with a running solver
+///
+/// Cancellable solvers (e.g. `GaScheduler::schedule_cancellable`) stop
+/// at their next check and return their best result so far.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    /// Create token (not cancelled)
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Request cancellation
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    /// Whether cancellation was requested
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// Why a background solve produced no result
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SolveError {
+    /// Cancelled before the solver started
+    Cancelled,
+    /// The solver panicked (panic message)
+    Panicked(String),
+}
+
+impl std::fmt::Display for SolveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SolveError::Cancelled => write!(f, "solve cancelled"),
+            SolveError::Panicked(message) => write!(f, "solver panicked: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for SolveError {}
+
+/// Improved solution reported during a solver run
+#[derive(Debug, Clone)]
+pub struct Incumbent<T> {
+    /// Best solution so far
+    pub solution: T,
+    /// Objective value (lower is better)
+    pub objective: f64,
+    /// Solver iteration that found it (GA generation, ...)
+    pub iteration: usize,
+    /// Time since the run started (ms)
+    pub elapsed_ms: i64,
+}
+
+/// Sending side of an incumbent stream
+///
+/// Sends never block or fail; incumbents are dropped once the receiver
+/// is gone.
+#[derive(Debug)]
+pub struct IncumbentSender<T>(Sender<Incumbent<T>>);
+
+impl<T> Clone for IncumbentSender<T> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<T> IncumbentSender<T> {
+    /// Report an incumbent
+    pub fn send(&self, incumbent: Incumbent<T>) {
+        let _ = self.0.send(incumbent);
+    }
+}
+
+/// Result slot filled by the solver thread
+struct Slot<T> {
+    result: Option<Result<T, SolveError>>,
+    waker: Option<Waker>,
+}
+
+/// Future of a background solve
+///
+/// Dropping it cancels the solve's token; the result is then discarded.
+pub struct SolveHandle<T> {
+    slot: Arc<Mutex<Slot<T>>>,
+    token: CancellationToken,
+}
+
+impl<T> SolveHandle<T> {
+    /// Token of this solve
+    pub fn token(&self) -> &CancellationToken {
+        &self.token
+    }
+
+    /// Request cancellation without dropping the future
+    pub fn cancel(&self) {
+        self.token.cancel();
+    }
+
+    /// Whether the result is ready
+    pub fn is_finished(&self) -> bool {
+        self.slot.lock().is_ok_and(|slot| slot.result.is_some())
+    }
+}
+
+impl<T> Future for SolveHandle<T> {
+    type Output = Result<T, SolveError>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut slot = match self.slot.lock() {
+            Ok(slot) => slot,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        match slot.result.take() {
+            Some(result) => Poll::Ready(result),
+            None => {
+                slot.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+impl<T> Drop for SolveHandle<T> {
+    fn drop(&mut self) {
+        self.token.cancel();
+    }
+}
+
+/// Run `solve` on a background thread
+///
+/// The solver gets the handle's cancellation token; awaiting the handle
+/// yields its result, or an error if it panicked.
+///
+/// ```no_run
+/// # async fn run(tasks: Vec<u_ras::models::Task>, resources: Vec<u_ras::models::Resource>) {
+/// use u_ras::scheduler::{solve_async, SimpleScheduler};
+///
+/// let scheduler = SimpleScheduler::new();
+/// let schedule = solve_async(move |_| scheduler.schedule(&tasks, &resources, 0))
+///     .await
+///     .unwrap();
+/// # }
+/// ```
+pub fn solve_async<T, F>(solve: F) -> SolveHandle<T>
+where
+    F: FnOnce(&CancellationToken) -> T + Send + 'static,
+    T: Send + 'static,
+{
+    let token = CancellationToken::new();
+    let slot = Arc::new(Mutex::new(Slot {
+        result: None,
+        waker: None,
+    }));
+
+    let (thread_token, thread_slot) = (token.clone(), Arc::clone(&slot));
+    let spawned = thread::Builder::new()
+        .name("u-ras-solve".to_string())
+        .spawn(move || {
+            let result = if thread_token.is_cancelled() {
+                Err(SolveError::Cancelled)
+            } else {
+                panic::catch_unwind(AssertUnwindSafe(|| solve(&thread_token)))
+                    .map_err(|payload| SolveError::Panicked(panic_message(payload.as_ref())))
+            };
+            fill(&thread_slot, result);
+        });
+    if let Err(e) = spawned {
+        fill(&slot, Err(SolveError::Panicked(e.to_string())));
+    }
+
+    SolveHandle { slot, token }
+}
+
+/// Run `solve` on a background thread, streaming its incumbents
+///
+/// Like [`solve_async`], but the solver also gets an [`IncumbentSender`];
+/// everything it sends arrives on the returned receiver. The stream ends
+/// when the solver returns. Cancel the handle to stop once an incumbent
+/// is good enough.
+///
+/// ```no_run
+/// # use u_ras::models::{Resource, Task};
+/// # fn run(tasks: Vec<Task>, resources: Vec<Resource>) {
+/// use u_ras::ga::GaScheduler;
+/// use u_ras::scheduler::solve_streaming;
+///
+/// let ga = GaScheduler::default();
+/// let (handle, incumbents) = solve_streaming(move |token, sender| {
+///     ga.schedule_streaming(&tasks, &resources, 0, token, |i| sender.send(i))
+/// });
+/// for incumbent in incumbents {
+///     if incumbent.objective <= 8.0 * 3_600_000.0 {
+///         handle.cancel();
+///     }
+/// }
+/// # }
+/// ```
+pub fn solve_streaming<T, I, F>(solve: F) -> (SolveHandle<T>, Receiver<Incumbent<I>>)
+where
+    F: FnOnce(&CancellationToken, &IncumbentSender<I>) -> T + Send + 'static,
+    T: Send + 'static,
+    I: Send + 'static,
+{
+    let (sender, receiver) = mpsc::channel();
+    let sender = IncumbentSender(sender);
+    let handle = solve_async(move |token| solve(token, &sender));
+    (handle, receiver)
+}
+
+/// Store the result and wake the awaiting task
+fn fill<T>(slot: &Mutex<Slot<T>>, result: Result<T, SolveError>) {
+    let waker = {
+        let mut slot = match slot.lock() {
+            Ok(slot) => slot,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        slot.result = Some(result);
+        slot.waker.take()
+    };
+    if let Some(waker) = waker {
+        waker.wake();
+    }
+}
+
+fn panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|s| s.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ga::{GaParams, GaScheduler};
+    use crate::models::{Activity, ActivityDuration, Resource, Task};
+    use crate::scheduler::SimpleScheduler;
+    use std::task::Wake;
+    use std::time::Duration;
+
+    /// Minimal executor: park the thread until woken
+    fn block_on<F: Future>(future: F) -> F::Output {
+        struct Unpark(thread::Thread);
+        impl Wake for Unpark {
+            fn wake(self: Arc<Self>) {
+                self.0.unpark();
+            }
+        }
+        let waker = Waker::from(Arc::new(Unpark(thread::current())));
+        let mut cx = Context::from_waker(&waker);
+        let mut future = std::pin::pin!(future);
+        loop {
+            if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+                return output;
+            }
+            thread::park();
+        }
+    }
+
+    fn tasks(n: usize) -> Vec<Task> {
+        (0..n)
+            .map(|i| {
+                let id = format!("T{}", i);
+                Task::new(&id).with_activity(
+                    Activity::new(&format!("{}-A1", id), &id, 1)
+                        .with_duration(ActivityDuration::fixed(1000))
+                        .with_resources("machine", vec!["M1".into(), "M2".into()]),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_solve_async() {
+        let (tasks, resources) = (tasks(4), vec![Resource::primary("M1")]);
+        let scheduler = SimpleScheduler::new();
+
+        let schedule = block_on(solve_async(move |_| {
+            scheduler.schedule(&tasks, &resources, 0)
+        }))
+        .unwrap();
+        assert_eq!(schedule.makespan_ms, 4000);
+
+        let failed = block_on(solve_async(|_| -> i64 { panic!("boom") }));
+        assert_eq!(failed, Err(SolveError::Panicked("boom".into())));
+    }
+
+    #[test]
+    fn test_cancel_running_ga() {
+        // An odd job count never reaches the 15.5 s load bound, so only
+        // the cancellation ends the run
+        let (tasks, resources) = (
+            tasks(31),
+            vec![Resource::primary("M1"), Resource::primary("M2")],
+        );
+        let params = GaParams {
+            population_size: 20,
+            max_generations: usize::MAX,
+            convergence_generations: usize::MAX,
+            time_limit_ms: None,
+            ..GaParams::default()
+        };
+        let ga = GaScheduler::new(params);
+
+        let handle =
+            solve_async(move |token| ga.schedule_cancellable(&tasks, &resources, 0, token));
+        thread::sleep(Duration::from_millis(50));
+        assert!(!handle.is_finished());
+        handle.cancel();
+
+        // Best schedule found before the cancellation
+        let schedule = block_on(handle).unwrap();
+        assert_eq!(schedule.assignment_count(), 31);
+    }
+
+    #[test]
+    fn test_solve_streaming() {
+        let (tasks, resources) = (
+            tasks(12),
+            vec![Resource::primary("M1"), Resource::primary("M2")],
+        );
+        let ga = GaScheduler::new(GaParams {
+            population_size: 10,
+            max_generations: 20,
+            ..GaParams::fast()
+        });
+
+        let (handle, incumbents) = solve_streaming(move |token, sender| {
+            ga.schedule_streaming(&tasks, &resources, 0, token, |i| sender.send(i))
+        });
+        let incumbents: Vec<_> = incumbents.into_iter().collect();
+        let schedule = block_on(handle).unwrap();
+
+        assert!(!incumbents.is_empty());
+        // Strictly improving, ending with the returned schedule
+        for pair in incumbents.windows(2) {
+            assert!(pair[1].objective < pair[0].objective);
+            assert!(pair[1].iteration > pair[0].iteration);
+        }
+        let last = incumbents.last().unwrap();
+        assert_eq!(last.solution.makespan_ms, schedule.makespan_ms);
+        assert_eq!(last.objective, schedule.makespan_ms as f64);
+    }
+}
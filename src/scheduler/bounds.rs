@@ -0,0 +1,427 @@
+//! Bounds - Makespan lower bounds
+//!
+//! Relaxations no schedule can beat: the longest precedence chain, the
+//! total work a resource group must absorb, and energetic reasoning over
+//! time windows. Comparing a heuristic schedule with the best bound gives
+//! its optimality gap ("makespan 412 min, lower bound 390 min, gap 5.6%").
+
+use crate::graph::PrecedenceGraph;
+use crate::models::{Activity, Resource, Task};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+
+/// Lower bounds on the makespan of a problem
+///
+/// All bounds are lengths measured from the scheduling start. Durations
+/// are the fastest candidate's processing time; setups are left out as
+/// they depend on the sequence.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MakespanBound {
+    /// Scheduling start (epoch ms)
+    pub start_ms: i64,
+    /// Longest release + precedence chain (ms)
+    pub critical_path_ms: i64,
+    /// Heaviest resource group load with its head and tail (ms)
+    pub resource_load_ms: i64,
+    /// Energetic reasoning on resource groups (ms, at least the other bounds)
+    pub energetic_ms: i64,
+}
+
+/// Makespan of a schedule compared with a lower bound
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct OptimalityGap {
+    /// Schedule length from the start (ms)
+    pub makespan_ms: i64,
+    /// Lower bound on the length (ms)
+    pub lower_bound_ms: i64,
+    /// (makespan - bound) / bound (0.0 = proven optimal)
+    pub gap: f64,
+}
+
+/// Why a search stopped
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TerminationReason {
+    /// The algorithm ran to its natural end
+    #[default]
+    Completed,
+    /// The incumbent reached the lower bound
+    ProvenOptimal,
+    /// The search stopped improving (e.g., GA population converged)
+    Converged,
+    /// Iteration, generation or node limit reached
+    IterationLimit,
+    /// Time limit reached
+    TimeLimit,
+    /// Cancelled by the caller
+    Cancelled,
+    /// No solution (infeasible or invalid model)
+    NoSolution,
+}
+
+impl TerminationReason {
+    /// Whether more search could still improve the incumbent
+    pub fn can_improve(&self) -> bool {
+        matches!(
+            self,
+            Self::Converged | Self::IterationLimit | Self::TimeLimit | Self::Cancelled
+        )
+    }
+}
+
+/// Relative gap between an incumbent and a lower bound
+///
+/// `(incumbent - bound) / bound`, 0.0 once the incumbent reaches the bound
+/// and None if the bound is not positive.
+pub fn relative_gap(incumbent: f64, bound: f64) -> Option<f64> {
+    if incumbent <= bound {
+        Some(0.0)
+    } else if bound > 0.0 {
+        Some((incumbent - bound) / bound)
+    } else {
+        None
+    }
+}
+
+impl fmt::Display for OptimalityGap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "makespan = {} ms, lower bound = {} ms, gap {:.1}%",
+            self.makespan_ms,
+            self.lower_bound_ms,
+            self.gap * 100.0
+        )
+    }
+}
+
+impl MakespanBound {
+    /// Lower bounds of scheduling `tasks` on `resources` from `start_ms`
+    ///
+    /// Activities on a precedence cycle count with their own duration
+    /// only. Energetic reasoning checks O(n²) windows per candidate
+    /// makespan and resource group.
+    pub fn calculate(tasks: &[Task], resources: &[Resource], start_ms: i64) -> Self {
+        let problem = Relaxation::build(tasks, resources, start_ms);
+        let critical_path_ms = problem
+            .jobs
+            .iter()
+            .map(|j| j.head + j.duration + j.tail)
+            .max()
+            .unwrap_or(0);
+        let resource_load_ms = problem
+            .groups
+            .iter()
+            .map(|g| problem.load_bound(g))
+            .max()
+            .unwrap_or(0);
+
+        let mut energetic_ms = critical_path_ms.max(resource_load_ms);
+        for group in &problem.groups {
+            energetic_ms = energetic_ms.max(problem.energetic_bound(group, energetic_ms));
+        }
+
+        Self {
+            start_ms,
+            critical_path_ms,
+            resource_load_ms,
+            energetic_ms,
+        }
+    }
+
+    /// Best (largest) lower bound on the schedule length
+    pub fn lower_bound_ms(&self) -> i64 {
+        self.critical_path_ms
+            .max(self.resource_load_ms)
+            .max(self.energetic_ms)
+    }
+
+    /// Gap of a makespan (epoch ms, as in `Schedule::makespan_ms`)
+    pub fn gap(&self, makespan_ms: i64) -> OptimalityGap {
+        let length = makespan_ms - self.start_ms;
+        let bound = self.lower_bound_ms();
+        OptimalityGap {
+            makespan_ms: length,
+            lower_bound_ms: bound,
+            gap: relative_gap(length as f64, bound as f64).unwrap_or(0.0),
+        }
+    }
+}
+
+/// Activity in the relaxed problem
+struct Job {
+    /// Earliest start after releases and predecessors
+    head: i64,
+    /// Fastest processing time
+    duration: i64,
+    /// Minimum work after the activity ends
+    tail: i64,
+}
+
+/// Activities that must share a set of resources
+struct Group {
+    /// Total capacity of the resources
+    capacity: i64,
+    /// Member jobs
+    jobs: Vec<usize>,
+}
+
+/// Precedence and resource relaxation of a problem
+struct Relaxation {
+    jobs: Vec<Job>,
+    groups: Vec<Group>,
+}
+
+impl Relaxation {
+    fn build(tasks: &[Task], resources: &[Resource], start_ms: i64) -> Self {
+        let resource_map: HashMap<&str, &Resource> =
+            resources.iter().map(|r| (r.id.as_str(), r)).collect();
+        let graph = PrecedenceGraph::from_tasks(tasks);
+
+        let mut jobs: Vec<Job> = Vec::with_capacity(graph.len());
+        let mut activities: Vec<&Activity> = Vec::with_capacity(graph.len());
+        for task in tasks {
+            let release = task
+                .release_time
+                .map_or(0, |t| (t.timestamp_millis() - start_ms).max(0));
+            for a in &task.activities {
+                if graph.index_of(&a.id) == Some(jobs.len()) {
+                    jobs.push(Job {
+                        head: release,
+                        duration: fastest_ms(a, &resource_map),
+                        tail: 0,
+                    });
+                    activities.push(a);
+                }
+            }
+        }
+
+        let order = graph.topological_order().order;
+        for &i in &order {
+            for &p in graph.predecessors(i) {
+                jobs[i].head = jobs[i].head.max(jobs[p].head + jobs[p].duration);
+            }
+        }
+        for &i in order.iter().rev() {
+            for &s in graph.successors(i) {
+                jobs[i].tail = jobs[i].tail.max(jobs[s].duration + jobs[s].tail);
+            }
+        }
+
+        // An activity with candidates S must run on one resource of S
+        let mut sets: BTreeMap<Vec<String>, Vec<usize>> = BTreeMap::new();
+        for (i, a) in activities.iter().enumerate() {
+            let mut set = a.candidate_resources();
+            set.sort_unstable();
+            set.dedup();
+            if !set.is_empty() {
+                sets.entry(set).or_default().push(i);
+            }
+        }
+        let groups = sets
+            .keys()
+            .map(|set| Group {
+                capacity: set
+                    .iter()
+                    .map(|r| {
+                        resource_map
+                            .get(r.as_str())
+                            .map_or(1, |r| r.capacity.max(1) as i64)
+                    })
+                    .sum(),
+                // Every activity confined to a subset is confined to the set
+                jobs: sets
+                    .iter()
+                    .filter(|(other, _)| other.iter().all(|r| set.contains(r)))
+                    .flat_map(|(_, jobs)| jobs.iter().copied())
+                    .collect(),
+            })
+            .collect();
+
+        Self { jobs, groups }
+    }
+
+    /// Smallest head + work / capacity + smallest tail of a group
+    fn load_bound(&self, group: &Group) -> i64 {
+        let jobs = group.jobs.iter().map(|&i| &self.jobs[i]);
+        let work: i64 = jobs.clone().map(|j| j.duration).sum();
+        let head = jobs.clone().map(|j| j.head).min().unwrap_or(0);
+        let tail = jobs.map(|j| j.tail).min().unwrap_or(0);
+        head + div_ceil(work, group.capacity) + tail
+    }
+
+    /// Smallest makespan from `from` passing the energetic check of a group
+    fn energetic_bound(&self, group: &Group, from: i64) -> i64 {
+        if self.feasible(group, from) {
+            return from;
+        }
+        let work: i64 = group.jobs.iter().map(|&i| self.jobs[i].duration).sum();
+        let (mut lo, mut hi) = (from, from + work.max(1));
+        if !self.feasible(group, hi) {
+            return hi;
+        }
+        // Invariant: lo infeasible, hi feasible
+        while hi - lo > 1 {
+            let mid = lo + (hi - lo) / 2;
+            if self.feasible(group, mid) {
+                hi = mid;
+            } else {
+                lo = mid;
+            }
+        }
+        hi
+    }
+
+    /// Whether the work forced into every window fits the group capacity
+    fn feasible(&self, group: &Group, makespan: i64) -> bool {
+        let windows: Vec<(i64, i64, i64)> = group
+            .jobs
+            .iter()
+            .map(|&i| {
+                let job = &self.jobs[i];
+                (job.head, makespan - job.tail, job.duration)
+            })
+            .collect();
+        if windows.iter().any(|&(est, lct, p)| lct - est < p) {
+            return false;
+        }
+
+        let mut starts: Vec<i64> = windows
+            .iter()
+            .flat_map(|&(est, lct, p)| [est, est + p, lct - p])
+            .collect();
+        let mut ends: Vec<i64> = windows
+            .iter()
+            .flat_map(|&(est, lct, p)| [lct, lct - p, est + p])
+            .collect();
+        starts.sort_unstable();
+        starts.dedup();
+        ends.sort_unstable();
+        ends.dedup();
+
+        for &t1 in &starts {
+            for &t2 in ends.iter().filter(|&&t2| t2 > t1) {
+                let required: i64 = windows
+                    .iter()
+                    .map(|&(est, lct, p)| {
+                        // Left-shifted or right-shifted share inside [t1, t2]
+                        (t2 - t1)
+                            .min(p)
+                            .min(est + p - t1)
+                            .min(t2 - (lct - p))
+                            .max(0)
+                    })
+                    .sum();
+                if required > group.capacity * (t2 - t1) {
+                    return false;
+                }
+            }
+        }
+        true
+    }
+}
+
+/// Processing time on the fastest known candidate
+fn fastest_ms(activity: &Activity, resources: &HashMap<&str, &Resource>) -> i64 {
+    activity
+        .candidate_resources()
+        .iter()
+        .filter_map(|c| resources.get(c.as_str()))
+        .map(|r| r.processing_time(activity.duration.process_ms))
+        .min()
+        .unwrap_or(activity.duration.process_ms)
+}
+
+fn div_ceil(a: i64, b: i64) -> i64 {
+    (a + b - 1) / b.max(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ActivityDuration;
+    use crate::scheduler::{ScheduleKpi, SimpleScheduler};
+
+    fn step(id: &str, task: &str, seq: i32, ms: i64, resources: &[&str]) -> Activity {
+        Activity::new(id, task, seq)
+            .with_duration(ActivityDuration::fixed(ms))
+            .with_resources("machine", resources.iter().map(|r| r.to_string()).collect())
+    }
+
+    #[test]
+    fn test_critical_path_and_load() {
+        let tasks = vec![
+            Task::new("T1")
+                .with_activity(step("A1", "T1", 1, 3000, &["M1"]))
+                .with_activity(step("A2", "T1", 2, 2000, &["M2"])),
+            Task::new("T2")
+                .with_activity(step("B1", "T2", 1, 2000, &["M1"]))
+                .with_activity(step("B2", "T2", 2, 1000, &["M2"])),
+        ];
+        let resources = vec![Resource::primary("M1"), Resource::primary("M2")];
+
+        let bound = MakespanBound::calculate(&tasks, &resources, 0);
+        assert_eq!(bound.critical_path_ms, 5000);
+        // M1 carries 5 s; the M2 work after it needs at least 1 s more
+        assert_eq!(bound.resource_load_ms, 6000);
+        assert_eq!(bound.energetic_ms, 6000);
+
+        let schedule = SimpleScheduler::new().schedule(&tasks, &resources, 0);
+        let gap = bound.gap(schedule.makespan_ms);
+        assert!(gap.makespan_ms >= 6000);
+        assert!((gap.gap - (gap.makespan_ms - 6000) as f64 / 6000.0).abs() < 1e-9);
+        let kpi = ScheduleKpi::calculate(&schedule, &tasks).with_lower_bound(&bound);
+        assert_eq!(kpi.makespan_lower_bound_ms, 6000);
+        assert_eq!(kpi.optimality_gap, gap.gap);
+        assert_eq!(
+            MakespanBound::calculate(&tasks, &resources, 0)
+                .gap(6600)
+                .to_string(),
+            "makespan = 6600 ms, lower bound = 6000 ms, gap 10.0%"
+        );
+    }
+
+    #[test]
+    fn test_energetic_reasoning() {
+        // Two jobs need M1 for 2 s and then 5 s on their own machine; a
+        // third needs M1 for 1 s. Neither chain nor load exceeds 7 s, but
+        // both long tails cannot start by 2 s, so 9 s is the optimum
+        let tail_job = |k: usize| {
+            let id = format!("T{}", k);
+            Task::new(&id)
+                .with_activity(step(&format!("{}-1", id), &id, 1, 2000, &["M1"]))
+                .with_activity(step(
+                    &format!("{}-2", id),
+                    &id,
+                    2,
+                    5000,
+                    &[&format!("P{}", k)],
+                ))
+        };
+        let mut tasks = vec![
+            tail_job(1),
+            tail_job(2),
+            Task::new("T3").with_activity(step("T3-1", "T3", 1, 1000, &["M1"])),
+        ];
+        let resources: Vec<Resource> = ["M1", "P1", "P2"]
+            .iter()
+            .map(|r| Resource::primary(r))
+            .collect();
+
+        let bound = MakespanBound::calculate(&tasks, &resources, 0);
+        assert_eq!(bound.critical_path_ms, 7000);
+        assert_eq!(bound.resource_load_ms, 7000);
+        assert_eq!(bound.energetic_ms, 9000);
+        assert_eq!(bound.lower_bound_ms(), 9000);
+
+        // Releases are measured from the start
+        tasks.push(
+            Task::new("T4")
+                .with_release_time(chrono::DateTime::from_timestamp_millis(19000).unwrap())
+                .with_activity(step("T4-1", "T4", 1, 2000, &["M1"])),
+        );
+        let bound = MakespanBound::calculate(&tasks, &resources, 10000);
+        assert_eq!(bound.critical_path_ms, 11000);
+        assert_eq!(bound.lower_bound_ms(), 11000);
+    }
+}
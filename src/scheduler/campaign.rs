@@ -0,0 +1,301 @@
+//! Campaign - Changeover-minimizing resequencing
+//!
+//! Groups same-category work on each resource into campaigns (runs of one
+//! product family, recipe or color) so sequence-dependent setups are paid
+//! once per campaign instead of at every category switch.
+
+use super::repair::predecessor_map;
+use crate::models::{Schedule, Task, TransitionMatrixCollection};
+use std::collections::HashMap;
+
+/// Result of campaign resequencing
+#[derive(Debug, Clone)]
+pub struct CampaignResult {
+    /// Resequenced schedule (the input if nothing improved)
+    pub schedule: Schedule,
+    /// Total transition time before (ms)
+    pub transition_before_ms: i64,
+    /// Total transition time after (ms)
+    pub transition_after_ms: i64,
+}
+
+/// Campaign sequencing post-processor
+///
+/// On every resource with a transition matrix, assignments are grouped by
+/// task category; campaigns follow each other by cheapest next transition,
+/// starting with the category that ran first, and keep their original
+/// order inside. The schedule is then re-timed as early as the new
+/// sequences, precedence (task sequence and explicit predecessors) and
+/// task release times allow, with setups taken from the matrices.
+/// Calendars and other constraints are not re-checked. The new schedule
+/// is kept only if it lowers total transition time; if the new sequences
+/// conflict with precedence, the input is returned.
+#[derive(Debug, Clone, Default)]
+pub struct CampaignSequencer {
+    transitions: TransitionMatrixCollection,
+}
+
+impl CampaignSequencer {
+    /// Create sequencer for the given setup matrices
+    pub fn new(transitions: TransitionMatrixCollection) -> Self {
+        Self { transitions }
+    }
+
+    /// Resequence a schedule into campaigns
+    pub fn resequence(&self, schedule: &Schedule, tasks: &[Task]) -> CampaignResult {
+        let before = total_transition_ms(schedule, tasks, &self.transitions);
+        let unchanged = || CampaignResult {
+            schedule: schedule.clone(),
+            transition_before_ms: before,
+            transition_after_ms: before,
+        };
+        let category: HashMap<&str, &str> = tasks
+            .iter()
+            .map(|t| (t.id.as_str(), t.category.as_str()))
+            .collect();
+        let category_of = |i: usize| {
+            category
+                .get(schedule.assignments[i].task_id.as_str())
+                .copied()
+                .unwrap_or("")
+        };
+
+        // Assignment indices per resource, in the new order
+        let mut sequences: Vec<(&str, Vec<usize>)> = Vec::new();
+        for (i, a) in schedule.assignments.iter().enumerate() {
+            match sequences.iter_mut().find(|(r, _)| *r == a.resource_id) {
+                Some((_, seq)) => seq.push(i),
+                None => sequences.push((a.resource_id.as_str(), vec![i])),
+            }
+        }
+        for (resource, seq) in &mut sequences {
+            seq.sort_by_key(|&i| {
+                let a = &schedule.assignments[i];
+                (a.start_ms, a.end_ms)
+            });
+            if self.transitions.get_for_resource(resource).is_none() {
+                continue;
+            }
+            let mut campaigns: Vec<(&str, Vec<usize>)> = Vec::new();
+            for &i in seq.iter() {
+                match campaigns.iter_mut().find(|(c, _)| *c == category_of(i)) {
+                    Some((_, members)) => members.push(i),
+                    None => campaigns.push((category_of(i), vec![i])),
+                }
+            }
+            let mut ordered = Vec::with_capacity(seq.len());
+            let mut current = campaigns.remove(0);
+            loop {
+                let from = current.0;
+                ordered.extend(current.1);
+                let Some(next) = (0..campaigns.len()).min_by_key(|&k| {
+                    self.transitions
+                        .get_transition_time(resource, from, campaigns[k].0)
+                }) else {
+                    break;
+                };
+                current = campaigns.remove(next);
+            }
+            *seq = ordered;
+        }
+
+        // Re-time: place the next assignment of any resource whose
+        // predecessors are all placed
+        let predecessors = predecessor_map(tasks);
+        let release: HashMap<&str, i64> = tasks
+            .iter()
+            .filter_map(|t| Some((t.id.as_str(), t.release_time?.timestamp_millis())))
+            .collect();
+        let floor = schedule
+            .assignments
+            .iter()
+            .map(|a| a.start_ms)
+            .min()
+            .unwrap_or(0);
+        let mut pieces_left: HashMap<&str, usize> = HashMap::new();
+        for a in &schedule.assignments {
+            *pieces_left.entry(a.activity_id.as_str()).or_insert(0) += 1;
+        }
+        let mut finished: HashMap<&str, i64> = HashMap::new();
+        let mut assignments = schedule.assignments.clone();
+        let mut cursor = vec![0; sequences.len()];
+        let mut free = vec![floor; sequences.len()];
+        let mut placed = 0;
+        while placed < assignments.len() {
+            let mut progressed = false;
+            for (r, (resource, seq)) in sequences.iter().enumerate() {
+                while let Some(&i) = seq.get(cursor[r]) {
+                    let original = &schedule.assignments[i];
+                    let preds = predecessors.get(&original.activity_id);
+                    let ready = preds.into_iter().flatten().try_fold(floor, |t, p| {
+                        match (pieces_left.get(p.as_str()), finished.get(p.as_str())) {
+                            (Some(&left), _) if left > 0 => None,
+                            (_, Some(&end)) => Some(t.max(end)),
+                            _ => Some(t),
+                        }
+                    });
+                    let Some(ready) = ready else {
+                        break;
+                    };
+                    let setup = match seq[..cursor[r]].last() {
+                        Some(&prev) => self.transitions.get_transition_time(
+                            resource,
+                            category_of(prev),
+                            category_of(i),
+                        ),
+                        None => original.setup_ms,
+                    };
+                    let start = ready.max(free[r]).max(
+                        release
+                            .get(original.task_id.as_str())
+                            .copied()
+                            .unwrap_or(floor),
+                    );
+                    let a = &mut assignments[i];
+                    let processing = a.duration_ms() - a.setup_ms;
+                    a.start_ms = start;
+                    a.setup_ms = setup;
+                    a.end_ms = start + setup + processing;
+                    free[r] = a.end_ms;
+                    let end = finished.entry(original.activity_id.as_str()).or_insert(0);
+                    *end = (*end).max(a.end_ms);
+                    if let Some(left) = pieces_left.get_mut(original.activity_id.as_str()) {
+                        *left -= 1;
+                    }
+                    cursor[r] += 1;
+                    placed += 1;
+                    progressed = true;
+                }
+            }
+            if !progressed {
+                // The new sequences contradict precedence
+                return unchanged();
+            }
+        }
+
+        let mut resequenced = schedule.clone();
+        resequenced.makespan_ms = 0;
+        resequenced.assignments.clear();
+        resequenced.explanations.clear();
+        for a in assignments {
+            resequenced.add_assignment(a);
+        }
+        resequenced.reindex();
+        let after = total_transition_ms(&resequenced, tasks, &self.transitions);
+        if after >= before {
+            return unchanged();
+        }
+        CampaignResult {
+            schedule: resequenced,
+            transition_before_ms: before,
+            transition_after_ms: after,
+        }
+    }
+}
+
+/// Total sequence-dependent transition time of a schedule (ms)
+///
+/// Sums the matrix time between the categories of consecutive
+/// assignments on each resource, whatever setups the assignments record.
+/// Usable as an objective term by any search.
+pub fn total_transition_ms(
+    schedule: &Schedule,
+    tasks: &[Task],
+    transitions: &TransitionMatrixCollection,
+) -> i64 {
+    let category: HashMap<&str, &str> = tasks
+        .iter()
+        .map(|t| (t.id.as_str(), t.category.as_str()))
+        .collect();
+    let mut by_resource: HashMap<&str, Vec<(i64, i64, &str)>> = HashMap::new();
+    for a in &schedule.assignments {
+        by_resource
+            .entry(a.resource_id.as_str())
+            .or_default()
+            .push((
+                a.start_ms,
+                a.end_ms,
+                category.get(a.task_id.as_str()).copied().unwrap_or(""),
+            ));
+    }
+    by_resource
+        .into_iter()
+        .filter(|(resource, _)| transitions.get_for_resource(resource).is_some())
+        .map(|(resource, mut runs)| {
+            runs.sort_unstable();
+            runs.windows(2)
+                .map(|w| transitions.get_transition_time(resource, w[0].2, w[1].2))
+                .sum::<i64>()
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Activity, Assignment, TransitionMatrix};
+
+    fn task(id: &str, category: &str) -> Task {
+        Task::new(id)
+            .with_category(category)
+            .with_activity(Activity::new(&format!("{}-A1", id), id, 1))
+    }
+
+    fn colors() -> TransitionMatrixCollection {
+        let mut matrix = TransitionMatrix::new("colors", "M1").with_default(500);
+        matrix.set_transition("red", "red", 0);
+        matrix.set_transition("blue", "blue", 0);
+        let mut transitions = TransitionMatrixCollection::new();
+        transitions.add(matrix);
+        transitions
+    }
+
+    #[test]
+    fn test_campaigns() {
+        let tasks = vec![
+            task("T1", "red"),
+            task("T2", "blue"),
+            task("T3", "red"),
+            task("T4", "blue"),
+        ];
+        let mut schedule = Schedule::new();
+        let mut start = 0;
+        for (k, t) in tasks.iter().enumerate() {
+            let setup = if k == 0 { 0 } else { 500 };
+            let end = start + setup + 1000;
+            schedule.add_assignment(
+                Assignment::new(&t.activities[0].id, &t.id, "M1", start, end).with_setup(setup),
+            );
+            start = end;
+        }
+
+        let result = CampaignSequencer::new(colors()).resequence(&schedule, &tasks);
+        assert_eq!(result.transition_before_ms, 1500);
+        assert_eq!(result.transition_after_ms, 500);
+        let order: Vec<&str> = {
+            let mut a: Vec<_> = result.schedule.assignments.iter().collect();
+            a.sort_by_key(|a| a.start_ms);
+            a.iter().map(|a| a.task_id.as_str()).collect()
+        };
+        assert_eq!(order, vec!["T1", "T3", "T2", "T4"]);
+        // Two setups saved
+        assert_eq!(result.schedule.makespan_ms, 4500);
+        let t2 = result.schedule.assignment_for_activity("T2-A1").unwrap();
+        assert_eq!((t2.start_ms, t2.setup_ms, t2.end_ms), (2000, 500, 3500));
+    }
+
+    #[test]
+    fn test_precedence_conflict_keeps_input() {
+        // Campaigns would run T3 before T2, which it must follow
+        let mut tasks = vec![task("T1", "blue"), task("T2", "red"), task("T3", "blue")];
+        tasks[2].activities[0].predecessors = vec!["T2-A1".into()];
+        let mut schedule = Schedule::new();
+        schedule.add_assignment(Assignment::new("T1-A1", "T1", "M1", 0, 1000));
+        schedule.add_assignment(Assignment::new("T2-A1", "T2", "M1", 1500, 2500).with_setup(500));
+        schedule.add_assignment(Assignment::new("T3-A1", "T3", "M1", 3000, 4000).with_setup(500));
+
+        let result = CampaignSequencer::new(colors()).resequence(&schedule, &tasks);
+        assert_eq!(result.schedule, schedule);
+        assert_eq!(result.transition_after_ms, result.transition_before_ms);
+    }
+}
@@ -0,0 +1,605 @@
+//! Compaction - Left-shift post-processing
+//!
+//! Pulls activities earlier into feasible gaps to remove avoidable idle
+//! time left by heuristic solves. Precedence (task sequence, explicit
+//! predecessors and constraints), task and resource release times, time
+//! windows, maximum delays, mutual exclusion, resource capacity, downtime
+//! and calendars are respected; no assignment ever moves later or to
+//! another resource, and the assignments of an activity move together.
+
+use super::repair::predecessor_map;
+use crate::models::{Assignment, Calendar, Constraint, Resource, Schedule, Task};
+use std::collections::HashMap;
+
+/// Result of a compaction
+#[derive(Debug, Clone)]
+pub struct CompactionResult {
+    /// Compacted schedule
+    pub schedule: Schedule,
+    /// Activities that start earlier
+    pub moved_activities: Vec<String>,
+    /// Makespan reduction (ms)
+    pub makespan_saved_ms: i64,
+}
+
+/// Left-shift compaction pass
+///
+/// Activities are visited by start time, repeatedly until nothing
+/// moves, and each starts at its earliest feasible time with all of its
+/// assignments (gang members, split pieces) shifted by the same amount.
+/// With gap filling an activity may jump into an idle gap before other
+/// work on its resources; resources with setup times keep their sequence
+/// (setups may depend on it), so their assignments only close up.
+#[derive(Debug, Clone)]
+pub struct ScheduleCompactor {
+    gap_filling: bool,
+    constraints: Vec<Constraint>,
+    release_times: HashMap<String, i64>,
+}
+
+impl Default for ScheduleCompactor {
+    fn default() -> Self {
+        Self {
+            gap_filling: true,
+            constraints: Vec::new(),
+            release_times: HashMap::new(),
+        }
+    }
+}
+
+impl ScheduleCompactor {
+    /// Create compactor (gap filling on)
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Allow assignments to jump into gaps before other work
+    pub fn with_gap_filling(mut self, enabled: bool) -> Self {
+        self.gap_filling = enabled;
+        self
+    }
+
+    /// Respect constraints the schedule was built with (precedence, pickup
+    /// and delivery, time windows, handoffs, maximum delays, mutual
+    /// exclusion)
+    pub fn with_constraints(mut self, constraints: Vec<Constraint>) -> Self {
+        self.constraints = constraints;
+        self
+    }
+
+    /// Keep a resource unused before `time_ms` (e.g., startup delay)
+    pub fn with_release_time(mut self, resource_id: &str, time_ms: i64) -> Self {
+        self.release_times.insert(resource_id.to_string(), time_ms);
+        self
+    }
+
+    /// Compact a schedule
+    ///
+    /// Nothing moves before the schedule's earliest start. Resources not
+    /// in `resources` count as capacity 1 without a calendar.
+    pub fn compact(
+        &self,
+        schedule: &Schedule,
+        tasks: &[Task],
+        resources: &[Resource],
+    ) -> CompactionResult {
+        let layout = Layout::new(self, schedule, tasks, resources);
+        let mut assignments = schedule.assignments.clone();
+
+        for _ in 0..=layout.of_activity.len() {
+            let mut order: Vec<&str> = layout.of_activity.keys().copied().collect();
+            order.sort_by_key(|&id| (layout.span(&assignments, id), id));
+
+            let mut changed = false;
+            for id in order {
+                let from = layout.earliest(&assignments, id);
+                if let Some(start) = layout.earliest_fit(&assignments, id, from) {
+                    let delta = start - layout.span(&assignments, id).0;
+                    for &i in &layout.of_activity[id] {
+                        let a = &mut assignments[i];
+                        a.start_ms += delta;
+                        a.end_ms += delta;
+                    }
+                    changed = true;
+                }
+            }
+            if !changed {
+                break;
+            }
+        }
+
+        let mut compacted = schedule.clone();
+        compacted.makespan_ms = 0;
+        compacted.assignments.clear();
+        let mut moved_activities = Vec::new();
+        for (before, after) in schedule.assignments.iter().zip(assignments) {
+            if after.start_ms != before.start_ms {
+                if !moved_activities.contains(&after.activity_id) {
+                    moved_activities.push(after.activity_id.clone());
+                }
+                // The recorded placement reasons no longer apply
+                compacted.explanations.remove(&after.activity_id);
+            }
+            compacted.add_assignment(after);
+        }
+        compacted.reindex();
+
+        CompactionResult {
+            makespan_saved_ms: schedule.makespan_ms - compacted.makespan_ms,
+            schedule: compacted,
+            moved_activities,
+        }
+    }
+}
+
+/// Compact a schedule with the default compactor
+pub fn compact(schedule: &Schedule, tasks: &[Task], resources: &[Resource]) -> Schedule {
+    ScheduleCompactor::new()
+        .compact(schedule, tasks, resources)
+        .schedule
+}
+
+/// Lookup tables of one compaction; assignments are referred to by index
+struct Layout<'s> {
+    /// Schedule start
+    floor: i64,
+    /// Predecessors per activity, with the lag after their end
+    predecessors: HashMap<String, Vec<(String, i64)>>,
+    /// Successors an activity must end close to, with the allowed gap
+    successors: HashMap<&'s str, Vec<(&'s str, i64)>>,
+    window_start: HashMap<&'s str, i64>,
+    /// Other members of each activity's mutual-exclusion groups
+    excluded: HashMap<&'s str, Vec<&'s str>>,
+    task_release: HashMap<&'s str, i64>,
+    resource_release: &'s HashMap<String, i64>,
+    resource_of: HashMap<&'s str, &'s Resource>,
+    downtime: HashMap<&'s str, Vec<(i64, i64)>>,
+    of_activity: HashMap<&'s str, Vec<usize>>,
+    on_resource: HashMap<&'s str, Vec<usize>>,
+    /// Resources whose sequence may change
+    reorder: HashMap<&'s str, bool>,
+}
+
+impl<'s> Layout<'s> {
+    fn new(
+        compactor: &'s ScheduleCompactor,
+        schedule: &'s Schedule,
+        tasks: &'s [Task],
+        resources: &'s [Resource],
+    ) -> Self {
+        let mut of_activity: HashMap<&str, Vec<usize>> = HashMap::new();
+        let mut on_resource: HashMap<&str, Vec<usize>> = HashMap::new();
+        for (i, a) in schedule.assignments.iter().enumerate() {
+            of_activity.entry(&a.activity_id).or_default().push(i);
+            on_resource.entry(&a.resource_id).or_default().push(i);
+        }
+        let reorder = on_resource
+            .iter()
+            .map(|(&r, on)| {
+                let has_setup = on.iter().any(|&i| schedule.assignments[i].setup_ms > 0);
+                (r, compactor.gap_filling && !has_setup)
+            })
+            .collect();
+        let mut downtime: HashMap<&str, Vec<(i64, i64)>> = HashMap::new();
+        for d in &schedule.downtime {
+            downtime
+                .entry(&d.resource_id)
+                .or_default()
+                .push((d.start_ms, d.end_ms));
+        }
+
+        let mut predecessors: HashMap<String, Vec<(String, i64)>> = predecessor_map(tasks)
+            .into_iter()
+            .map(|(id, preds)| (id, preds.into_iter().map(|p| (p, 0)).collect()))
+            .collect();
+        let mut precede = |before: &str, after: &str, lag: i64| {
+            predecessors
+                .entry(after.to_string())
+                .or_default()
+                .push((before.to_string(), lag));
+        };
+        let mut successors: HashMap<&str, Vec<(&str, i64)>> = HashMap::new();
+        let mut window_start = HashMap::new();
+        let mut groups: HashMap<&str, Vec<&str>> = HashMap::new();
+        for constraint in &compactor.constraints {
+            match constraint {
+                Constraint::Precedence {
+                    before,
+                    after,
+                    min_delay_ms,
+                } => precede(before, after, *min_delay_ms),
+                Constraint::PickupDelivery { pickup, delivery } => precede(pickup, delivery, 0),
+                Constraint::TimeWindow {
+                    activity_id,
+                    start_ms,
+                    ..
+                } => {
+                    window_start.insert(activity_id.as_str(), *start_ms);
+                }
+                Constraint::Handoff {
+                    from,
+                    to,
+                    tolerance_ms,
+                } => {
+                    precede(from, to, -tolerance_ms);
+                    successors
+                        .entry(from.as_str())
+                        .or_default()
+                        .push((to.as_str(), *tolerance_ms));
+                }
+                Constraint::MaxDelay {
+                    before,
+                    after,
+                    max_delay_ms,
+                } => {
+                    precede(before, after, 0);
+                    successors
+                        .entry(before.as_str())
+                        .or_default()
+                        .push((after.as_str(), *max_delay_ms));
+                }
+                Constraint::MutualExclusion {
+                    group,
+                    activity_ids,
+                } => {
+                    groups
+                        .entry(group.as_str())
+                        .or_default()
+                        .extend(activity_ids.iter().map(String::as_str));
+                }
+                _ => {}
+            }
+        }
+        let mut excluded: HashMap<&str, Vec<&str>> = HashMap::new();
+        for members in groups.values() {
+            for &id in members {
+                excluded
+                    .entry(id)
+                    .or_default()
+                    .extend(members.iter().filter(|&&m| m != id));
+            }
+        }
+
+        Self {
+            floor: schedule
+                .assignments
+                .iter()
+                .map(|a| a.start_ms)
+                .min()
+                .unwrap_or(0),
+            predecessors,
+            successors,
+            window_start,
+            excluded,
+            task_release: tasks
+                .iter()
+                .filter_map(|t| Some((t.id.as_str(), t.release_time?.timestamp_millis())))
+                .collect(),
+            resource_release: &compactor.release_times,
+            resource_of: resources.iter().map(|r| (r.id.as_str(), r)).collect(),
+            downtime,
+            of_activity,
+            on_resource,
+            reorder,
+        }
+    }
+
+    /// Start and end of a scheduled activity over all of its assignments
+    fn span(&self, assignments: &[Assignment], id: &str) -> (i64, i64) {
+        let on = &self.of_activity[id];
+        let start = on.iter().map(|&i| assignments[i].start_ms).min();
+        let end = on.iter().map(|&i| assignments[i].end_ms).max();
+        (start.unwrap_or(0), end.unwrap_or(0))
+    }
+
+    /// Earliest start of an activity allowed by precedence, release times,
+    /// its time window, successors it must stay close to and, on resources
+    /// keeping their sequence, the work before it
+    fn earliest(&self, assignments: &[Assignment], id: &str) -> i64 {
+        let on = &self.of_activity[id];
+        let (start, end) = self.span(assignments, id);
+        let task_id = assignments[on[0]].task_id.as_str();
+        let mut earliest = self.floor;
+        if let Some(&release) = self.task_release.get(task_id) {
+            earliest = earliest.max(release);
+        }
+        if let Some(&window_start) = self.window_start.get(id) {
+            earliest = earliest.max(window_start);
+        }
+        for (pred, lag) in self.predecessors.get(id).into_iter().flatten() {
+            if self.of_activity.contains_key(pred.as_str()) {
+                earliest = earliest.max(self.span(assignments, pred).1 + lag);
+            }
+        }
+        for &(succ, gap) in self.successors.get(id).into_iter().flatten() {
+            if self.of_activity.contains_key(succ) {
+                earliest = earliest.max(self.span(assignments, succ).0 - gap - (end - start));
+            }
+        }
+        for &i in on {
+            let a = &assignments[i];
+            let offset = a.start_ms - start;
+            if let Some(&release) = self.resource_release.get(&a.resource_id) {
+                earliest = earliest.max(release - offset);
+            }
+            if self.reorder[a.resource_id.as_str()] {
+                continue;
+            }
+            let capacity = self.capacity(&a.resource_id);
+            for &j in &self.on_resource[a.resource_id.as_str()] {
+                let o = &assignments[j];
+                if o.activity_id != id && (o.start_ms, o.end_ms) <= (a.start_ms, a.end_ms) {
+                    let after = if capacity == 1 { o.end_ms } else { o.start_ms };
+                    earliest = earliest.max(after - offset);
+                }
+            }
+        }
+        earliest
+    }
+
+    fn capacity(&self, resource_id: &str) -> usize {
+        self.resource_of
+            .get(resource_id)
+            .map_or(1, |r| r.capacity.max(1)) as usize
+    }
+
+    /// Earliest start in `[from, current start)` where every assignment of
+    /// the activity fits and no other member of its exclusion groups runs
+    ///
+    /// Candidate starts advance to the next point where feasibility can
+    /// change.
+    fn earliest_fit(&self, assignments: &[Assignment], id: &str, from: i64) -> Option<i64> {
+        let (start, end) = self.span(assignments, id);
+        let mut t = from;
+        'search: while t < start {
+            for &i in &self.of_activity[id] {
+                let a = &assignments[i];
+                let offset = a.start_ms - start;
+                let shifted = (t + offset, t + offset + a.duration_ms());
+                if let Some(next) = self.conflict(assignments, a, shifted) {
+                    t = next.saturating_sub(offset);
+                    continue 'search;
+                }
+            }
+            let next = self
+                .excluded
+                .get(id)
+                .into_iter()
+                .flatten()
+                .filter(|&&other| other != id && self.of_activity.contains_key(other))
+                .map(|other| self.span(assignments, other))
+                .filter(|&(s, e)| s < t + (end - start) && t < e)
+                .map(|(_, e)| e)
+                .max();
+            match next {
+                Some(next) => t = next,
+                None => return Some(t),
+            }
+        }
+        None
+    }
+
+    /// Next start worth trying if an assignment cannot run in
+    /// `[from, to)` on its resource (`i64::MAX` if none), `None` if it can
+    fn conflict(
+        &self,
+        assignments: &[Assignment],
+        a: &Assignment,
+        (from, to): (i64, i64),
+    ) -> Option<i64> {
+        let resource_id = a.resource_id.as_str();
+        let calendar = self
+            .resource_of
+            .get(resource_id)
+            .and_then(|r| r.calendar.as_ref());
+        if let Some(calendar) = calendar {
+            let open = calendar.next_available_time(from);
+            if !calendar.is_working_time(open) {
+                // No working time left
+                return Some(i64::MAX);
+            }
+            if open > from {
+                return Some(open);
+            }
+            if let Some(stop) = first_break(calendar, from, to) {
+                return Some(stop);
+            }
+        }
+        let down = self
+            .downtime
+            .get(resource_id)
+            .into_iter()
+            .flatten()
+            .filter(|&&(s, e)| s < to && from < e)
+            .map(|&(_, e)| e)
+            .max();
+        if down.is_some() {
+            return down;
+        }
+        let others: Vec<&Assignment> = self.on_resource[resource_id]
+            .iter()
+            .map(|&j| &assignments[j])
+            .filter(|o| o.activity_id != a.activity_id && o.start_ms < to && from < o.end_ms)
+            .collect();
+        let overlapping: Vec<&&Assignment> = others.iter().collect();
+        if peak_usage(&overlapping, from, to) >= self.capacity(resource_id) {
+            return others.iter().map(|o| o.end_ms).filter(|&e| e > from).min();
+        }
+        None
+    }
+}
+
+/// Most assignments running at once within `[from, to)`
+pub(crate) fn peak_usage(overlapping: &[&&Assignment], from: i64, to: i64) -> usize {
+    let mut events: Vec<(i64, i32)> = overlapping
+        .iter()
+        .flat_map(|o| [(o.start_ms.max(from), 1), (o.end_ms.min(to), -1)])
+        .collect();
+    // Ends before starts at the same time
+    events.sort_unstable();
+    let mut usage = 0;
+    let mut peak = 0;
+    for (_, delta) in events {
+        usage += delta;
+        peak = peak.max(usage);
+    }
+    peak as usize
+}
+
+/// Start of the first non-working stretch in `[from, to)`, if any
+fn first_break(calendar: &Calendar, from: i64, to: i64) -> Option<i64> {
+    if calendar.is_available_between(from, to) {
+        return None;
+    }
+    // Longest fully working prefix
+    let (mut lo, mut hi) = (from, to);
+    while hi - lo > 1 {
+        let mid = lo + (hi - lo) / 2;
+        if calendar.is_available_between(from, mid) {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+    }
+    Some(lo.max(from + 1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Activity, Calendar};
+    use chrono::DateTime;
+
+    fn chain(id: &str, n: usize) -> Task {
+        (1..=n).fold(Task::new(id), |t, k| {
+            t.with_activity(Activity::new(&format!("{}{}", id, k), id, k as i32))
+        })
+    }
+
+    #[test]
+    fn test_left_shift_and_gap_filling() {
+        let tasks = vec![
+            chain("A", 2),
+            Task::new("B").with_activity(Activity::new("B1", "B", 1).with_predecessor("A2")),
+            chain("C", 1),
+        ];
+        let resources = vec![Resource::primary("M1"), Resource::primary("M2")];
+        let mut schedule = Schedule::new();
+        schedule.add_assignment(Assignment::new("A1", "A", "M1", 0, 1000));
+        schedule.add_assignment(Assignment::new("A2", "A", "M2", 2000, 4000));
+        schedule.add_assignment(Assignment::new("B1", "B", "M1", 4000, 5000));
+        schedule.add_assignment(Assignment::new("C1", "C", "M1", 6000, 7000));
+
+        // B1 waits for A2; C1 jumps into M1's gap before it
+        let result = ScheduleCompactor::new().compact(&schedule, &tasks, &resources);
+        let start = |s: &Schedule, id: &str| s.assignment_for_activity(id).unwrap().start_ms;
+        let starts: Vec<i64> = ["A1", "A2", "B1", "C1"]
+            .iter()
+            .map(|id| start(&result.schedule, id))
+            .collect();
+        assert_eq!(starts, vec![0, 1000, 3000, 1000]);
+        assert_eq!(result.makespan_saved_ms, 3000);
+        assert_eq!(result.moved_activities, vec!["A2", "B1", "C1"]);
+
+        // Without gap filling the resource order is kept
+        let ordered = ScheduleCompactor::new()
+            .with_gap_filling(false)
+            .compact(&schedule, &tasks, &resources);
+        assert_eq!(start(&ordered.schedule, "C1"), 4000);
+        assert_eq!(ordered.makespan_saved_ms, 2000);
+    }
+
+    #[test]
+    fn test_release_calendar_and_setups() {
+        let mut late = chain("R", 1);
+        late.release_time = DateTime::from_timestamp_millis(1500);
+        let tasks = vec![late, chain("S", 1), chain("T", 1)];
+        let calendar = Calendar::new("shift")
+            .with_window(0, 10_000)
+            .with_blocked(2000, 3000);
+        let resources = vec![
+            Resource::primary("M1").with_calendar(calendar),
+            Resource::primary("M2"),
+        ];
+        let mut schedule = Schedule::new();
+        // R cannot start before its release, and 1500..2500 hits the break
+        schedule.add_assignment(Assignment::new("R1", "R", "M1", 6000, 7000));
+        schedule.add_assignment(Assignment::new("S1", "S", "M2", 0, 1000).with_setup(500));
+        // The setup resource keeps its order
+        schedule.add_assignment(Assignment::new("T1", "T", "M2", 4000, 4500));
+
+        let compacted = compact(&schedule, &tasks, &resources);
+        let r1 = compacted.assignment_for_activity("R1").unwrap();
+        assert_eq!((r1.start_ms, r1.end_ms), (3000, 4000));
+        assert_eq!(
+            compacted.assignment_for_activity("T1").unwrap().start_ms,
+            1000
+        );
+        assert_eq!(
+            compacted.assignment_for_activity("S1").unwrap().setup_ms,
+            500
+        );
+        assert_eq!(compacted.makespan_ms, 4000);
+    }
+
+    #[test]
+    fn test_gang_moves_together() {
+        let tasks = vec![chain("B", 1), chain("G", 1)];
+        let resources = vec![Resource::primary("C1"), Resource::primary("C2")];
+        let mut schedule = Schedule::new();
+        schedule.add_assignment(Assignment::new("B1", "B", "C2", 0, 1000));
+        schedule.add_assignment(Assignment::new("G1", "G", "C1", 2000, 3000));
+        schedule.add_assignment(Assignment::new("G1", "G", "C2", 2000, 3000));
+
+        // C1 is free from 0, but the gang waits for C2
+        let compacted = compact(&schedule, &tasks, &resources);
+        let lift = compacted.assignments_for_activity("G1");
+        assert_eq!(lift.len(), 2);
+        assert!(lift.iter().all(|a| (a.start_ms, a.end_ms) == (1000, 2000)));
+    }
+
+    #[test]
+    fn test_constraints_downtime_and_release() {
+        let tasks: Vec<Task> = ["X", "W", "E", "P", "Q", "D", "R"]
+            .iter()
+            .map(|id| chain(id, 1))
+            .collect();
+        let resources: Vec<Resource> = (1..=7)
+            .map(|i| Resource::primary(&format!("M{}", i)))
+            .collect();
+        let mut schedule = Schedule::new();
+        schedule.add_assignment(Assignment::new("X1", "X", "M1", 0, 2000));
+        schedule.add_assignment(Assignment::new("W1", "W", "M2", 5000, 6000));
+        schedule.add_assignment(Assignment::new("E1", "E", "M3", 3000, 4000));
+        schedule.add_assignment(Assignment::new("P1", "P", "M4", 1000, 2000));
+        schedule.add_assignment(Assignment::new("Q1", "Q", "M5", 2500, 3500));
+        schedule.add_assignment(Assignment::new("D1", "D", "M6", 3000, 4000));
+        schedule.add_assignment(Assignment::new("R1", "R", "M7", 3000, 4000));
+        schedule.downtime.push(crate::models::Downtime {
+            resource_id: "M6".to_string(),
+            start_ms: 0,
+            end_ms: 2500,
+            reason: "maintenance".to_string(),
+        });
+        let constraints = vec![
+            Constraint::time_window("W1", 5000, 9000),
+            Constraint::mutual_exclusion("zone", vec!["X1".into(), "E1".into()]),
+            Constraint::max_delay("P1", "Q1", 500),
+        ];
+
+        let compacted = ScheduleCompactor::new()
+            .with_constraints(constraints)
+            .with_release_time("M7", 1500)
+            .compact(&schedule, &tasks, &resources)
+            .schedule;
+        let at = |id: &str| {
+            let a = compacted.assignment_for_activity(id).unwrap();
+            (a.start_ms, a.end_ms)
+        };
+        assert_eq!(at("W1"), (5000, 6000));
+        assert_eq!(at("E1"), (2000, 3000));
+        assert!(at("Q1").0 - at("P1").1 <= 500);
+        assert_eq!(at("D1"), (2500, 3500));
+        assert_eq!(at("R1"), (1500, 2500));
+    }
+}
@@ -0,0 +1,332 @@
+//! Cost - Monetary objective for schedules
+//!
+//! Combines resource rates, setup, overtime, tardiness and earliness into a single
+//! configurable cost, usable as GA fitness (`GaScheduler::with_cost_objective`),
+//! as a CP objective (`CpModel::minimize_cost`) or to compare solver outputs.
+
+use crate::models::{PreferenceReport, Resource, ResourceType, Schedule, Task};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Milliseconds per hour
+const MS_PER_HOUR: f64 = 3_600_000.0;
+
+/// Cost function configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CostModel {
+    /// Charge resource `cost_per_hour` for all assigned time
+    pub include_resource_rates: bool,
+    /// Cost per hour of setup/transition time
+    pub setup_cost_per_hour: f64,
+    /// Premium on top of the resource rate for time outside its calendar
+    /// (0.5 = time-and-a-half)
+    pub overtime_premium: f64,
+    /// Penalty per hour a task completes after its deadline (times the
+    /// task weight)
+    pub tardiness_cost_per_hour: f64,
+    /// Penalty per hour a task completes before its deadline (storage,
+    /// perishables, waiting; times the task weight)
+    #[serde(default)]
+    pub earliness_cost_per_hour: f64,
+    /// Penalty per hour of workload spread across human resources
+    #[serde(default)]
+    pub fairness_cost_per_hour: f64,
+    /// Penalty per unit of unsatisfied preference weight
+    #[serde(default)]
+    pub preference_penalty: f64,
+}
+
+/// Cost breakdown of a schedule
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CostBreakdown {
+    /// Resource time cost
+    pub resource_cost: f64,
+    /// Setup cost
+    pub setup_cost: f64,
+    /// Overtime premium
+    pub overtime_cost: f64,
+    /// Tardiness penalties
+    pub tardiness_cost: f64,
+    /// Earliness penalties
+    #[serde(default)]
+    pub earliness_cost: f64,
+    /// Workload fairness penalty
+    pub fairness_cost: f64,
+    /// Unmet preference penalty
+    pub preference_cost: f64,
+    /// Sum of all components
+    pub total: f64,
+}
+
+impl Default for CostModel {
+    fn default() -> Self {
+        Self {
+            include_resource_rates: true,
+            setup_cost_per_hour: 0.0,
+            overtime_premium: 0.5,
+            tardiness_cost_per_hour: 0.0,
+            earliness_cost_per_hour: 0.0,
+            fairness_cost_per_hour: 0.0,
+            preference_penalty: 0.0,
+        }
+    }
+}
+
+impl CostModel {
+    /// Create cost model charging resource rates only
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set setup cost per hour
+    pub fn with_setup_cost(mut self, cost_per_hour: f64) -> Self {
+        self.setup_cost_per_hour = cost_per_hour;
+        self
+    }
+
+    /// Set overtime premium
+    pub fn with_overtime_premium(mut self, premium: f64) -> Self {
+        self.overtime_premium = premium;
+        self
+    }
+
+    /// Set tardiness penalty per hour
+    pub fn with_tardiness_cost(mut self, cost_per_hour: f64) -> Self {
+        self.tardiness_cost_per_hour = cost_per_hour;
+        self
+    }
+
+    /// Set earliness penalty per hour
+    pub fn with_earliness_cost(mut self, cost_per_hour: f64) -> Self {
+        self.earliness_cost_per_hour = cost_per_hour;
+        self
+    }
+
+    /// Set fairness penalty per hour of workload spread
+    ///
+    /// Penalizes the gap between the most and least loaded human
+    /// resources, discouraging plans that overload one person.
+    pub fn with_fairness_penalty(mut self, cost_per_hour: f64) -> Self {
+        self.fairness_cost_per_hour = cost_per_hour;
+        self
+    }
+
+    /// Set penalty per unit of unsatisfied preference weight
+    pub fn with_preference_penalty(mut self, penalty: f64) -> Self {
+        self.preference_penalty = penalty;
+        self
+    }
+
+    /// Toggle resource rate charges
+    pub fn with_resource_rates(mut self, include: bool) -> Self {
+        self.include_resource_rates = include;
+        self
+    }
+
+    /// Evaluate schedule cost with breakdown
+    pub fn evaluate(
+        &self,
+        schedule: &Schedule,
+        tasks: &[Task],
+        resources: &[Resource],
+    ) -> CostBreakdown {
+        let resource_map: HashMap<&str, &Resource> =
+            resources.iter().map(|r| (r.id.as_str(), r)).collect();
+        let mut breakdown = CostBreakdown::default();
+
+        for assignment in &schedule.assignments {
+            let rate = resource_map
+                .get(assignment.resource_id.as_str())
+                .and_then(|r| r.cost_per_hour)
+                .unwrap_or(0.0);
+            let hours = assignment.duration_ms() as f64 / MS_PER_HOUR;
+
+            if self.include_resource_rates {
+                breakdown.resource_cost += rate * hours;
+            }
+            breakdown.setup_cost +=
+                self.setup_cost_per_hour * assignment.setup_ms as f64 / MS_PER_HOUR;
+
+            let calendar = resource_map
+                .get(assignment.resource_id.as_str())
+                .and_then(|r| r.calendar.as_ref());
+            if let Some(calendar) = calendar {
+                let regular =
+                    calendar.available_time_between(assignment.start_ms, assignment.end_ms);
+                let overtime_ms = (assignment.duration_ms() - regular).max(0);
+                breakdown.overtime_cost +=
+                    rate * self.overtime_premium * overtime_ms as f64 / MS_PER_HOUR;
+            }
+        }
+
+        for task in tasks {
+            if let (Some(deadline), Some(completion)) =
+                (task.due_date(), schedule.task_completion_time(&task.id))
+            {
+                let lateness_ms = completion - deadline.timestamp_millis();
+                let weight = task.objective_weight();
+                breakdown.tardiness_cost +=
+                    weight * self.tardiness_cost_per_hour * lateness_ms.max(0) as f64 / MS_PER_HOUR;
+                breakdown.earliness_cost +=
+                    weight * self.earliness_cost_per_hour * (-lateness_ms).max(0) as f64
+                        / MS_PER_HOUR;
+            }
+        }
+
+        if self.fairness_cost_per_hour > 0.0 {
+            let staff: Vec<String> = resources
+                .iter()
+                .filter(|r| r.resource_type == ResourceType::Human)
+                .map(|r| r.id.clone())
+                .collect();
+            let spread_ms = schedule.workload_deviation_ms(&staff);
+            breakdown.fairness_cost = self.fairness_cost_per_hour * spread_ms as f64 / MS_PER_HOUR;
+        }
+
+        if self.preference_penalty > 0.0 {
+            let report = PreferenceReport::evaluate(schedule, tasks, resources);
+            breakdown.preference_cost = self.preference_penalty * report.unsatisfied_weight();
+        }
+
+        breakdown.total = breakdown.resource_cost
+            + breakdown.setup_cost
+            + breakdown.overtime_cost
+            + breakdown.tardiness_cost
+            + breakdown.earliness_cost
+            + breakdown.fairness_cost
+            + breakdown.preference_cost;
+        breakdown
+    }
+
+    /// Total schedule cost (lower is better, usable as GA fitness)
+    pub fn total_cost(&self, schedule: &Schedule, tasks: &[Task], resources: &[Resource]) -> f64 {
+        self.evaluate(schedule, tasks, resources).total
+    }
+
+    /// Rate cost of holding a resource for `duration_ms`
+    ///
+    /// The part of the cost known before placement (no overtime or
+    /// deadline terms), used to price CP alternatives.
+    pub fn resource_time_cost(&self, resource: &Resource, duration_ms: i64) -> f64 {
+        if self.include_resource_rates {
+            resource.cost_per_hour.unwrap_or(0.0) * duration_ms as f64 / MS_PER_HOUR
+        } else {
+            0.0
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Activity, Assignment, Calendar};
+
+    const HOUR: i64 = 3_600_000;
+
+    #[test]
+    fn test_resource_and_setup_cost() {
+        let mut schedule = Schedule::new();
+        schedule.add_assignment(Assignment::new("A1", "T1", "R1", 0, 2 * HOUR).with_setup(HOUR));
+
+        let resources = vec![Resource::primary("R1").with_cost(50.0)];
+        let tasks = vec![Task::new("T1").with_activity(Activity::new("A1", "T1", 1))];
+
+        let cost = CostModel::new()
+            .with_setup_cost(20.0)
+            .evaluate(&schedule, &tasks, &resources);
+
+        assert!((cost.resource_cost - 100.0).abs() < 1e-9);
+        assert!((cost.setup_cost - 20.0).abs() < 1e-9);
+        assert!((cost.total - 120.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_overtime_and_tardiness() {
+        let mut schedule = Schedule::new();
+        schedule.add_assignment(Assignment::new("A1", "T1", "W1", 6 * HOUR, 10 * HOUR));
+
+        // Regular hours 0-8h: 2h overtime
+        let resources = vec![Resource::human("W1")
+            .with_cost(30.0)
+            .with_calendar(Calendar::new("day").with_window(0, 8 * HOUR))];
+        let deadline = chrono::DateTime::from_timestamp_millis(9 * HOUR).unwrap();
+        let tasks = vec![Task::new("T1")
+            .with_deadline(deadline)
+            .with_activity(Activity::new("A1", "T1", 1))];
+
+        let cost = CostModel::new()
+            .with_overtime_premium(0.5)
+            .with_tardiness_cost(100.0)
+            .evaluate(&schedule, &tasks, &resources);
+
+        assert!((cost.resource_cost - 120.0).abs() < 1e-9);
+        assert!((cost.overtime_cost - 30.0).abs() < 1e-9);
+        assert!((cost.tardiness_cost - 100.0).abs() < 1e-9);
+        assert_eq!(cost.earliness_cost, 0.0);
+    }
+
+    #[test]
+    fn test_earliness_cost() {
+        let mut schedule = Schedule::new();
+        schedule.add_assignment(Assignment::new("A1", "T1", "R1", 0, HOUR));
+        let deadline = chrono::DateTime::from_timestamp_millis(4 * HOUR).unwrap();
+        let tasks = vec![Task::new("T1")
+            .with_deadline(deadline)
+            .with_weight(0.5)
+            .with_activity(Activity::new("A1", "T1", 1))];
+
+        let cost = CostModel::new()
+            .with_tardiness_cost(100.0)
+            .with_earliness_cost(10.0)
+            .evaluate(&schedule, &tasks, &[]);
+
+        assert_eq!(cost.tardiness_cost, 0.0);
+        assert!((cost.earliness_cost - 15.0).abs() < 1e-9);
+        assert!((cost.total - 15.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_weighted_tardiness_cost() {
+        let mut schedule = Schedule::new();
+        schedule.add_assignment(Assignment::new("A1", "T1", "R1", 0, 2 * HOUR));
+        schedule.add_assignment(Assignment::new("A2", "T2", "R1", 0, 2 * HOUR));
+        schedule.add_assignment(Assignment::new("A3", "T3", "R1", 0, 2 * HOUR));
+
+        // All one hour late
+        let deadline = chrono::DateTime::from_timestamp_millis(HOUR).unwrap();
+        let task = |id: &str, activity: &str| {
+            Task::new(id)
+                .with_deadline(deadline)
+                .with_activity(Activity::new(activity, id, 1))
+        };
+        let tasks = vec![
+            task("T1", "A1").with_weight(2.0),
+            task("T2", "A2").with_priority(10),
+            task("T3", "A3").with_weight(0.0),
+        ];
+
+        let cost = CostModel::new()
+            .with_tardiness_cost(100.0)
+            .evaluate(&schedule, &tasks, &[]);
+
+        // 2.0 * 100 + 1.0 * 100 (unset weight) + 0
+        assert!((cost.tardiness_cost - 300.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_fairness_penalty() {
+        let mut schedule = Schedule::new();
+        schedule.add_assignment(Assignment::new("A1", "T1", "N1", 0, 3 * HOUR));
+        schedule.add_assignment(Assignment::new("A2", "T2", "N2", 0, HOUR));
+
+        let resources = vec![Resource::human("N1"), Resource::human("N2")];
+
+        let cost =
+            CostModel::new()
+                .with_fairness_penalty(10.0)
+                .evaluate(&schedule, &[], &resources);
+
+        assert!((cost.fairness_cost - 20.0).abs() < 1e-9);
+        assert!((cost.total - 20.0).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,129 @@
+//! Deadline - Back-propagation of task deadlines
+//!
+//! Pushes task deadlines backwards through the precedence graph to the
+//! latest time each activity may start and finish without making its
+//! task (or a dependent task) late.
+
+use crate::graph::PrecedenceGraph;
+use crate::models::{Activity, Resource, Task};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Latest times of an activity that still meet all downstream deadlines
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LatestTimes {
+    /// Latest start (epoch ms)
+    pub latest_start_ms: i64,
+    /// Latest finish (epoch ms)
+    pub latest_finish_ms: i64,
+}
+
+/// Latest start and finish of every activity bounded by a deadline
+///
+/// Edges come from `Activity::predecessors` (across tasks too); a task
+/// whose activities declare no predecessors is a chain in sequence
+/// order. Durations are the fastest candidate's processing time, so the
+/// bounds are necessary, not sufficient: an activity starting later is
+/// certainly late. Activities with no deadline downstream, or on or
+/// ahead of a precedence cycle, are left out.
+pub fn propagate_deadlines(tasks: &[Task], resources: &[Resource]) -> HashMap<String, LatestTimes> {
+    let resource_map: HashMap<&str, &Resource> =
+        resources.iter().map(|r| (r.id.as_str(), r)).collect();
+    let activities: HashMap<&str, (&Activity, Option<i64>)> = tasks
+        .iter()
+        .flat_map(|t| {
+            let deadline = t.deadline.map(|d| d.timestamp_millis());
+            t.activities
+                .iter()
+                .map(move |a| (a.id.as_str(), (a, deadline)))
+        })
+        .collect();
+
+    // Sinks first: every successor is settled before its predecessors
+    let graph = PrecedenceGraph::from_tasks(tasks);
+    let mut latest: HashMap<String, LatestTimes> = HashMap::new();
+    for i in graph.reversed().topological_order().order {
+        let (activity, deadline) = activities[graph.id(i)];
+        let finish = graph
+            .successors(i)
+            .iter()
+            .filter_map(|&j| latest.get(graph.id(j)))
+            .map(|times| times.latest_start_ms)
+            .fold(deadline, |f, s| Some(f.map_or(s, |f| f.min(s))));
+        if let Some(latest_finish_ms) = finish {
+            latest.insert(
+                activity.id.clone(),
+                LatestTimes {
+                    latest_start_ms: latest_finish_ms - fastest_ms(activity, &resource_map),
+                    latest_finish_ms,
+                },
+            );
+        }
+    }
+    latest
+}
+
+/// Processing time on the fastest known candidate
+fn fastest_ms(activity: &Activity, resources: &HashMap<&str, &Resource>) -> i64 {
+    activity
+        .candidate_resources()
+        .iter()
+        .filter_map(|c| resources.get(c.as_str()))
+        .map(|r| r.processing_time(activity.duration.process_ms))
+        .min()
+        .unwrap_or(activity.duration.process_ms)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ActivityDuration;
+    use chrono::{TimeZone, Utc};
+
+    fn step(id: &str, task: &str, seq: i32, ms: i64) -> Activity {
+        Activity::new(id, task, seq)
+            .with_duration(ActivityDuration::fixed(ms))
+            .with_resources("machine", vec!["Slow".into(), "Fast".into()])
+    }
+
+    #[test]
+    fn test_chain_latest_times() {
+        let tasks = vec![
+            Task::new("T1")
+                .with_deadline(Utc.timestamp_millis_opt(10_000).unwrap())
+                .with_activity(step("T1-A1", "T1", 1, 2000))
+                .with_activity(step("T1-A2", "T1", 2, 4000)),
+            Task::new("T2").with_activity(step("T2-A1", "T2", 1, 1000)),
+        ];
+        let resources = vec![
+            Resource::primary("Slow"),
+            Resource::primary("Fast").with_efficiency(2.0),
+        ];
+
+        let latest = propagate_deadlines(&tasks, &resources);
+
+        // Fastest candidate halves the durations
+        assert_eq!(latest["T1-A2"].latest_finish_ms, 10_000);
+        assert_eq!(latest["T1-A2"].latest_start_ms, 8000);
+        assert_eq!(latest["T1-A1"].latest_finish_ms, 8000);
+        assert_eq!(latest["T1-A1"].latest_start_ms, 7000);
+        assert!(!latest.contains_key("T2-A1"));
+    }
+
+    #[test]
+    fn test_cross_task_deadline() {
+        // Part feeds an assembly due at 5000
+        let tasks = vec![
+            Task::new("Part").with_activity(step("Part-A1", "Part", 1, 1000)),
+            Task::new("Asm")
+                .with_deadline(Utc.timestamp_millis_opt(5000).unwrap())
+                .with_activity(step("Asm-A1", "Asm", 1, 3000).with_predecessor("Part-A1")),
+        ];
+
+        let latest = propagate_deadlines(&tasks, &[]);
+
+        assert_eq!(latest["Asm-A1"].latest_start_ms, 2000);
+        assert_eq!(latest["Part-A1"].latest_finish_ms, 2000);
+        assert_eq!(latest["Part-A1"].latest_start_ms, 1000);
+    }
+}
@@ -0,0 +1,289 @@
+//! Elastic - Scheduling with on-demand resource pools
+//!
+//! Decides how many instances of each elastic pool to provision,
+//! trading instance cost against makespan.
+
+use super::cost::CostModel;
+use super::simple::SimpleScheduler;
+use crate::models::{ProvisioningDecision, Resource, ResourcePool, Schedule, Task};
+
+/// Milliseconds per hour
+const MS_PER_HOUR: f64 = 3_600_000.0;
+
+/// Elastic scheduling result
+#[derive(Debug, Clone)]
+pub struct ElasticResult {
+    /// Schedule over fixed resources and provisioned instances
+    pub schedule: Schedule,
+    /// Instances to provision
+    pub provisioned: Vec<ProvisioningDecision>,
+    /// Activities left unscheduled
+    pub unscheduled: usize,
+    /// Cost of holding provisioned instances
+    pub instance_cost: f64,
+    /// Instance cost + schedule cost + makespan cost
+    pub total_cost: f64,
+}
+
+/// Scheduler that provisions pool instances on demand
+///
+/// Starts without instances and greedily adds one instance at a time
+/// to the pool whose extra instance lowers the objective most, first
+/// minimizing unscheduled activities, then total cost. Instances are
+/// requested just in time for their first assignment and released
+/// after their last.
+#[derive(Clone)]
+pub struct ElasticScheduler {
+    /// Elastic pools
+    pools: Vec<ResourcePool>,
+    /// Cost per hour of makespan (value of finishing early)
+    makespan_cost_per_hour: f64,
+    /// Cost of the schedule on fixed resources
+    cost_model: CostModel,
+    /// Underlying scheduler
+    scheduler: SimpleScheduler,
+}
+
+impl ElasticScheduler {
+    /// Create elastic scheduler without pools
+    pub fn new() -> Self {
+        Self {
+            pools: Vec::new(),
+            makespan_cost_per_hour: 0.0,
+            cost_model: CostModel::new(),
+            scheduler: SimpleScheduler::new(),
+        }
+    }
+
+    /// Add elastic pool
+    pub fn with_pool(mut self, pool: ResourcePool) -> Self {
+        self.pools.push(pool);
+        self
+    }
+
+    /// Set cost per hour of makespan
+    pub fn with_makespan_cost(mut self, cost_per_hour: f64) -> Self {
+        self.makespan_cost_per_hour = cost_per_hour;
+        self
+    }
+
+    /// Set cost model for fixed resources
+    pub fn with_cost_model(mut self, cost_model: CostModel) -> Self {
+        self.cost_model = cost_model;
+        self
+    }
+
+    /// Set underlying scheduler
+    pub fn with_scheduler(mut self, scheduler: SimpleScheduler) -> Self {
+        self.scheduler = scheduler;
+        self
+    }
+
+    /// Schedule tasks, provisioning pool instances as needed
+    pub fn schedule(
+        &self,
+        tasks: &[Task],
+        resources: &[Resource],
+        start_time_ms: i64,
+    ) -> ElasticResult {
+        let mut counts = vec![0; self.pools.len()];
+        let mut best = self.evaluate(&counts, tasks, resources, start_time_ms);
+
+        loop {
+            let mut improvement: Option<(usize, ElasticResult)> = None;
+            for i in 0..self.pools.len() {
+                if counts[i] >= self.pools[i].max_instances {
+                    continue;
+                }
+                counts[i] += 1;
+                let result = self.evaluate(&counts, tasks, resources, start_time_ms);
+                counts[i] -= 1;
+
+                let incumbent = improvement.as_ref().map_or(&best, |(_, r)| r);
+                if is_better(&result, incumbent) {
+                    improvement = Some((i, result));
+                }
+            }
+
+            match improvement {
+                Some((i, result)) => {
+                    counts[i] += 1;
+                    best = result;
+                }
+                None => break,
+            }
+        }
+
+        best
+    }
+
+    /// Schedule with a fixed number of instances per pool
+    fn evaluate(
+        &self,
+        counts: &[usize],
+        tasks: &[Task],
+        resources: &[Resource],
+        start_time_ms: i64,
+    ) -> ElasticResult {
+        let mut all_resources = resources.to_vec();
+        let mut scheduler = self.scheduler.clone();
+        for (pool, &count) in self.pools.iter().zip(counts) {
+            for k in 1..=count {
+                let instance = pool.instance(k);
+                scheduler =
+                    scheduler.with_release_time(&instance.id, start_time_ms + pool.startup_ms);
+                all_resources.push(instance);
+            }
+        }
+
+        // Pool ids among candidates stand for all provisioned instances
+        let expanded: Vec<Task> = tasks
+            .iter()
+            .map(|task| {
+                let mut task = task.clone();
+                for activity in &mut task.activities {
+                    for requirement in &mut activity.resource_requirements {
+                        requirement.candidates = requirement
+                            .candidates
+                            .iter()
+                            .flat_map(|c| match self.pools.iter().position(|p| &p.id == c) {
+                                Some(i) => (1..=counts[i])
+                                    .map(|k| self.pools[i].instance_id(k))
+                                    .collect(),
+                                None => vec![c.clone()],
+                            })
+                            .collect();
+                    }
+                }
+                task
+            })
+            .collect();
+
+        let schedule = scheduler.schedule(&expanded, &all_resources, start_time_ms);
+
+        let mut provisioned = Vec::new();
+        for (pool, &count) in self.pools.iter().zip(counts) {
+            for k in 1..=count {
+                let id = pool.instance_id(k);
+                let booked = schedule.assignments_for_resource(&id);
+                let (Some(first), Some(last)) = (
+                    booked.iter().map(|a| a.start_ms).min(),
+                    booked.iter().map(|a| a.end_ms).max(),
+                ) else {
+                    continue;
+                };
+                let requested_at_ms = start_time_ms.max(first - pool.startup_ms);
+                provisioned.push(ProvisioningDecision {
+                    pool_id: pool.id.clone(),
+                    resource_id: id,
+                    requested_at_ms,
+                    ready_at_ms: requested_at_ms + pool.startup_ms,
+                    released_at_ms: last,
+                    cost: pool.cost_per_hour * (last - requested_at_ms) as f64 / MS_PER_HOUR,
+                });
+            }
+        }
+
+        let unscheduled = tasks
+            .iter()
+            .flat_map(|t| t.activities.iter())
+            .filter(|a| schedule.assignment_for_activity(&a.id).is_none())
+            .count();
+        let instance_cost: f64 = provisioned.iter().map(|p| p.cost).sum();
+        let makespan_hours = (schedule.makespan_ms - start_time_ms).max(0) as f64 / MS_PER_HOUR;
+        let total_cost = instance_cost
+            + self.cost_model.total_cost(&schedule, tasks, resources)
+            + self.makespan_cost_per_hour * makespan_hours;
+
+        ElasticResult {
+            schedule,
+            provisioned,
+            unscheduled,
+            instance_cost,
+            total_cost,
+        }
+    }
+}
+
+impl Default for ElasticScheduler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Fewer unscheduled activities first, then lower total cost
+fn is_better(a: &ElasticResult, b: &ElasticResult) -> bool {
+    a.unscheduled < b.unscheduled
+        || (a.unscheduled == b.unscheduled && a.total_cost < b.total_cost - 1e-9)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Activity, ActivityDuration};
+
+    const HOUR: i64 = 3_600_000;
+
+    fn jobs(count: usize, candidates: Vec<String>) -> Vec<Task> {
+        (1..=count)
+            .map(|i| {
+                let id = format!("J{}", i);
+                Task::new(&id).with_activity(
+                    Activity::new(&format!("{}-A1", id), &id, 1)
+                        .with_duration(ActivityDuration::fixed(HOUR))
+                        .with_resources("worker", candidates.clone()),
+                )
+            })
+            .collect()
+    }
+
+    fn vm_pool() -> ResourcePool {
+        ResourcePool::new("vm", Resource::primary("vm"))
+            .with_max_instances(3)
+            .with_startup(HOUR / 2)
+            .with_cost(1.0)
+    }
+
+    #[test]
+    fn test_scale_out_when_makespan_is_expensive() {
+        let tasks = jobs(4, vec!["M1".into(), "vm".into()]);
+        let resources = vec![Resource::primary("M1")];
+
+        let result = ElasticScheduler::new()
+            .with_pool(vm_pool())
+            .with_makespan_cost(10.0)
+            .schedule(&tasks, &resources, 0);
+
+        assert_eq!(result.provisioned.len(), 3);
+        assert_eq!(result.provisioned[0].ready_at_ms, HOUR / 2);
+        assert_eq!(result.schedule.makespan_ms, 3 * HOUR / 2);
+        assert!((result.instance_cost - 4.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_no_scale_out_when_instances_cost_more() {
+        let tasks = jobs(4, vec!["M1".into(), "vm".into()]);
+        let resources = vec![Resource::primary("M1")];
+
+        let result = ElasticScheduler::new()
+            .with_pool(vm_pool())
+            .with_makespan_cost(1.0)
+            .schedule(&tasks, &resources, 0);
+
+        assert!(result.provisioned.is_empty());
+        assert_eq!(result.schedule.makespan_ms, 4 * HOUR);
+    }
+
+    #[test]
+    fn test_pool_only_work_is_provisioned() {
+        let tasks = jobs(2, vec!["vm".into()]);
+
+        let result = ElasticScheduler::new()
+            .with_pool(vm_pool())
+            .schedule(&tasks, &[], 0);
+
+        assert_eq!(result.unscheduled, 0);
+        assert_eq!(result.provisioned.len(), 1);
+        assert_eq!(result.provisioned[0].released_at_ms, 5 * HOUR / 2);
+    }
+}
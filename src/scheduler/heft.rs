@@ -0,0 +1,299 @@
+//! HEFT - Heterogeneous Earliest Finish Time list scheduling
+//!
+//! Standard baseline for DAG workflows on heterogeneous resources:
+//! activities are prioritized by upward rank and placed on the resource
+//! giving the earliest finish, inserting into idle gaps when possible.
+
+use crate::graph::{PrecedenceGraph, TopologicalOrder};
+use crate::models::schedule::Violation;
+use crate::models::{Activity, Assignment, Resource, Schedule, Task};
+use std::collections::HashMap;
+
+/// HEFT workflow scheduler
+///
+/// DAG edges come from `Activity::predecessors`; a task whose activities
+/// declare no predecessors is treated as a chain in sequence order.
+/// Execution time on a resource is the process time divided by the
+/// resource efficiency. A fixed communication delay applies when
+/// dependent activities run on different resources.
+#[derive(Debug, Clone, Default)]
+pub struct HeftScheduler {
+    /// Data transfer delay between activities on different resources (ms)
+    pub communication_ms: i64,
+}
+
+impl HeftScheduler {
+    /// Create HEFT scheduler without communication delays
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set communication delay between different resources
+    pub fn with_communication(mut self, delay_ms: i64) -> Self {
+        self.communication_ms = delay_ms;
+        self
+    }
+
+    /// Upward rank of every activity in the DAG
+    ///
+    /// Average execution time plus the longest rank-weighted path to an
+    /// exit activity. Activities on a cycle get no rank.
+    pub fn upward_ranks(&self, tasks: &[Task], resources: &[Resource]) -> HashMap<String, f64> {
+        let dag = Dag::build(tasks);
+        let resource_map: HashMap<&str, &Resource> =
+            resources.iter().map(|r| (r.id.as_str(), r)).collect();
+
+        let mut ranks: HashMap<String, f64> = HashMap::new();
+        for &i in dag.order.iter().rev() {
+            let activity = dag.activities[i];
+            let succ = dag
+                .graph
+                .successors(i)
+                .iter()
+                .filter_map(|&j| ranks.get(&dag.activities[j].id))
+                .map(|r| self.communication_ms as f64 + r)
+                .fold(0.0, f64::max);
+            ranks.insert(
+                activity.id.clone(),
+                average_exec_ms(activity, &resource_map) + succ,
+            );
+        }
+        ranks
+    }
+
+    /// Schedule a workflow
+    pub fn schedule(&self, tasks: &[Task], resources: &[Resource], start_time_ms: i64) -> Schedule {
+        let dag = Dag::build(tasks);
+        let resource_map: HashMap<&str, &Resource> =
+            resources.iter().map(|r| (r.id.as_str(), r)).collect();
+        let ranks = self.upward_ranks(tasks, resources);
+        let mut schedule = Schedule::new();
+
+        // Rank order; topological position breaks ties so edges are respected
+        let position: HashMap<usize, usize> =
+            dag.order.iter().enumerate().map(|(p, &i)| (i, p)).collect();
+        let mut order = dag.order.clone();
+        order.sort_by(|&a, &b| {
+            let ra = ranks[&dag.activities[a].id];
+            let rb = ranks[&dag.activities[b].id];
+            rb.partial_cmp(&ra)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then(position[&a].cmp(&position[&b]))
+        });
+
+        let task_release: HashMap<&str, i64> = tasks
+            .iter()
+            .map(|t| {
+                let release = t
+                    .release_time
+                    .map_or(start_time_ms, |r| r.timestamp_millis().max(start_time_ms));
+                (t.id.as_str(), release)
+            })
+            .collect();
+        let mut busy: HashMap<&str, Vec<(i64, i64)>> = HashMap::new();
+        let mut placed: HashMap<usize, (String, i64)> = HashMap::new();
+
+        for i in order {
+            let activity = dag.activities[i];
+            let release = task_release
+                .get(activity.task_id.as_str())
+                .copied()
+                .unwrap_or(start_time_ms);
+
+            let mut best: Option<(&str, i64, i64)> = None;
+            for candidate in activity.candidate_resources() {
+                let Some(resource) = resource_map.get(candidate.as_str()) else {
+                    continue;
+                };
+                let ready = dag
+                    .graph
+                    .predecessors(i)
+                    .iter()
+                    .filter_map(|p| placed.get(p))
+                    .map(|(r, end)| {
+                        if *r == resource.id {
+                            *end
+                        } else {
+                            end + self.communication_ms
+                        }
+                    })
+                    .fold(release, i64::max);
+                let duration = exec_ms(activity, resource);
+                let slots = busy.get(resource.id.as_str()).map_or(&[][..], |v| &v[..]);
+                let start = earliest_gap(slots, ready, duration);
+                if best.is_none_or(|(_, _, end)| start + duration < end) {
+                    best = Some((resource.id.as_str(), start, start + duration));
+                }
+            }
+
+            match best {
+                Some((resource_id, start, end)) => {
+                    let slots = busy.entry(resource_id).or_default();
+                    let at = slots.partition_point(|s| s.0 < start);
+                    slots.insert(at, (start, end));
+                    placed.insert(i, (resource_id.to_string(), end));
+                    schedule.add_assignment(Assignment::new(
+                        &activity.id,
+                        &activity.task_id,
+                        resource_id,
+                        start,
+                        end,
+                    ));
+                }
+                None => schedule.add_violation(Violation::resource_unavailable(
+                    &activity.id,
+                    &format!("No candidate resource for activity {}", activity.id),
+                )),
+            }
+        }
+
+        for &i in &dag.cyclic {
+            schedule.add_violation(Violation::precedence_violation(
+                &dag.activities[i].id,
+                &format!("Activity {} is on a precedence cycle", dag.activities[i].id),
+            ));
+        }
+
+        schedule
+    }
+}
+
+/// Activity precedence graph
+struct Dag<'a> {
+    /// Activities by graph node index
+    activities: Vec<&'a Activity>,
+    /// Precedence edges
+    graph: PrecedenceGraph,
+    /// Topological order (acyclic part)
+    order: Vec<usize>,
+    /// Activities on or behind a cycle
+    cyclic: Vec<usize>,
+}
+
+impl<'a> Dag<'a> {
+    fn build(tasks: &'a [Task]) -> Self {
+        let graph = PrecedenceGraph::from_tasks(tasks);
+        let mut activities: Vec<&Activity> = Vec::with_capacity(graph.len());
+        for a in tasks.iter().flat_map(|t| t.activities.iter()) {
+            if graph.index_of(&a.id) == Some(activities.len()) {
+                activities.push(a);
+            }
+        }
+        let TopologicalOrder { order, cyclic } = graph.topological_order();
+
+        Self {
+            activities,
+            graph,
+            order,
+            cyclic,
+        }
+    }
+}
+
+/// Execution time on a resource
+fn exec_ms(activity: &Activity, resource: &Resource) -> i64 {
+    resource.processing_time(activity.duration.process_ms)
+}
+
+/// Average execution time over known candidate resources
+fn average_exec_ms(activity: &Activity, resources: &HashMap<&str, &Resource>) -> f64 {
+    let times: Vec<i64> = activity
+        .candidate_resources()
+        .iter()
+        .filter_map(|c| resources.get(c.as_str()))
+        .map(|r| exec_ms(activity, r))
+        .collect();
+    if times.is_empty() {
+        activity.duration.process_ms as f64
+    } else {
+        times.iter().sum::<i64>() as f64 / times.len() as f64
+    }
+}
+
+/// Earliest start at or after `ready` of an idle gap of `duration` between
+/// sorted busy intervals
+pub(super) fn earliest_gap(busy: &[(i64, i64)], ready: i64, duration: i64) -> i64 {
+    let mut start = ready;
+    for &(s, e) in busy {
+        if start + duration <= s {
+            return start;
+        }
+        start = start.max(e);
+    }
+    start
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ActivityDuration;
+
+    fn node(id: &str, ms: i64, preds: &[&str]) -> Activity {
+        let mut a = Activity::new(id, "WF", 1)
+            .with_duration(ActivityDuration::fixed(ms))
+            .with_resources("cpu", vec!["Fast".into(), "Slow".into()]);
+        for p in preds {
+            a = a.with_predecessor(p);
+        }
+        a
+    }
+
+    fn workflow() -> Vec<Task> {
+        // Fork-join: A -> (B, C) -> D
+        vec![Task::new("WF")
+            .with_activity(node("A", 1000, &[]))
+            .with_activity(node("B", 4000, &["A"]))
+            .with_activity(node("C", 2000, &["A"]))
+            .with_activity(node("D", 1000, &["B", "C"]))]
+    }
+
+    fn resources() -> Vec<Resource> {
+        vec![
+            Resource::primary("Fast").with_efficiency(2.0),
+            Resource::primary("Slow"),
+        ]
+    }
+
+    #[test]
+    fn test_upward_ranks() {
+        let ranks = HeftScheduler::new().upward_ranks(&workflow(), &resources());
+
+        // Average exec = 0.75 * process time
+        assert_eq!(ranks["D"], 750.0);
+        assert_eq!(ranks["B"], 3750.0);
+        assert_eq!(ranks["A"], 4500.0);
+        assert!(ranks["B"] > ranks["C"]);
+    }
+
+    #[test]
+    fn test_fork_join_schedule() {
+        let schedule = HeftScheduler::new().schedule(&workflow(), &resources(), 0);
+
+        let a = |id: &str| schedule.assignment_for_activity(id).unwrap();
+        assert_eq!(a("A").resource_id, "Fast");
+        assert_eq!(a("B").resource_id, "Fast");
+        // C runs in parallel on Slow
+        assert_eq!(a("C").resource_id, "Slow");
+        assert_eq!(a("C").start_ms, 500);
+        assert!(a("D").start_ms >= a("B").end_ms.max(a("C").end_ms));
+        assert_eq!(schedule.makespan_ms, 3000);
+        assert!(schedule.violations.is_empty());
+    }
+
+    #[test]
+    fn test_communication_and_cycle() {
+        let schedule =
+            HeftScheduler::new()
+                .with_communication(10_000)
+                .schedule(&workflow(), &resources(), 0);
+        // Transfers are too expensive: everything stays on Fast
+        assert!(schedule.assignments.iter().all(|a| a.resource_id == "Fast"));
+
+        let cyclic = vec![Task::new("WF")
+            .with_activity(node("X", 1000, &["Y"]))
+            .with_activity(node("Y", 1000, &["X"]))];
+        let schedule = HeftScheduler::new().schedule(&cyclic, &resources(), 0);
+        assert_eq!(schedule.assignment_count(), 0);
+        assert_eq!(schedule.violations.len(), 2);
+    }
+}
@@ -0,0 +1,313 @@
+//! JIT - Just-in-time right-shifting toward due dates
+//!
+//! Forward schedulers start everything as early as possible, so tasks
+//! with distant deadlines finish early and their work waits as inventory.
+//! [`JustInTime`] deliberately inserts idle time: each task's last
+//! activity moves toward the task's due date and earlier activities move up
+//! to their successors, reducing earliness without adding tardiness.
+
+use super::compaction::peak_usage;
+use super::repair::predecessor_map;
+use crate::models::{Assignment, Calendar, Resource, Schedule, Task};
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+
+/// Earliness-reducing post-processing pass
+///
+/// Assignments are visited latest-ending first, repeatedly until nothing
+/// moves, and each moves to its latest start that keeps precedence,
+/// resource capacity and calendars, and finishes by the task's due date
+/// (target date, else deadline).
+/// Nothing moves earlier or to another resource; late tasks and tasks
+/// without a due date keep their completion time. Resources with setup
+/// times keep their sequence (setups may depend on it).
+#[derive(Debug, Clone, Default)]
+pub struct JustInTime {
+    /// Keep this much time before each deadline (ms)
+    buffer_ms: i64,
+    /// Assignments starting before this time stay (already started)
+    frozen_before_ms: Option<i64>,
+}
+
+impl JustInTime {
+    /// Create pass finishing tasks right at their deadlines
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set safety buffer kept before each deadline
+    pub fn with_buffer_ms(mut self, buffer_ms: i64) -> Self {
+        self.buffer_ms = buffer_ms.max(0);
+        self
+    }
+
+    /// Keep assignments starting before `time_ms` in place
+    pub fn with_frozen_before(mut self, time_ms: i64) -> Self {
+        self.frozen_before_ms = Some(time_ms);
+        self
+    }
+
+    /// Shift a schedule toward its due dates
+    ///
+    /// Resources not in `resources` count as capacity 1 without a
+    /// calendar.
+    pub fn apply(&self, schedule: &Schedule, tasks: &[Task], resources: &[Resource]) -> Schedule {
+        let mut successors: HashMap<&str, Vec<&str>> = HashMap::new();
+        let predecessors = predecessor_map(tasks);
+        for (activity, preds) in &predecessors {
+            for pred in preds {
+                successors
+                    .entry(pred.as_str())
+                    .or_default()
+                    .push(activity.as_str());
+            }
+        }
+        let due: HashMap<&str, i64> = tasks
+            .iter()
+            .filter_map(|t| {
+                // Aim at the target date, never past the hard deadline
+                let due = t
+                    .due_date()?
+                    .min(t.deadline.unwrap_or(DateTime::<Utc>::MAX_UTC));
+                Some((t.id.as_str(), due.timestamp_millis() - self.buffer_ms))
+            })
+            .collect();
+        let resource_of: HashMap<&str, &Resource> =
+            resources.iter().map(|r| (r.id.as_str(), r)).collect();
+
+        let mut assignments = schedule.assignments.clone();
+        let keep_order: HashMap<String, bool> = assignments
+            .iter()
+            .map(|a| {
+                let has_setup = assignments
+                    .iter()
+                    .any(|o| o.resource_id == a.resource_id && o.setup_ms > 0);
+                (a.resource_id.clone(), has_setup)
+            })
+            .collect();
+
+        for _ in 0..=assignments.len() {
+            let mut order: Vec<usize> = (0..assignments.len()).collect();
+            order.sort_by(|&i, &j| {
+                let (a, b) = (&assignments[i], &assignments[j]);
+                (b.end_ms, b.start_ms, &b.activity_id).cmp(&(a.end_ms, a.start_ms, &a.activity_id))
+            });
+
+            let mut changed = false;
+            for idx in order {
+                let a = &assignments[idx];
+                if self.frozen_before_ms.is_some_and(|t| a.start_ms < t) {
+                    continue;
+                }
+                let succ = successors.get(a.activity_id.as_str());
+
+                // Terminal activities aim at the due date; late ones and
+                // those without one stay
+                let mut latest_end = if succ.is_some_and(|s| !s.is_empty()) {
+                    i64::MAX
+                } else {
+                    due.get(a.task_id.as_str())
+                        .copied()
+                        .unwrap_or(a.end_ms)
+                        .max(a.end_ms)
+                };
+                for s in succ.into_iter().flatten() {
+                    for o in assignments.iter().filter(|o| o.activity_id == *s) {
+                        latest_end = latest_end.min(o.start_ms);
+                    }
+                }
+                // Pieces of a split activity keep their order
+                for (j, p) in assignments.iter().enumerate() {
+                    if j != idx && p.activity_id == a.activity_id && p.start_ms > a.start_ms {
+                        latest_end = latest_end.min(p.start_ms);
+                    }
+                }
+                if latest_end == i64::MAX {
+                    continue;
+                }
+
+                let resource = resource_of.get(a.resource_id.as_str());
+                let capacity = resource.map_or(1, |r| r.capacity.max(1)) as usize;
+                let calendar = resource.and_then(|r| r.calendar.as_ref());
+                let others: Vec<&Assignment> = assignments
+                    .iter()
+                    .enumerate()
+                    .filter(|&(j, o)| j != idx && o.resource_id == a.resource_id)
+                    .map(|(_, o)| o)
+                    .collect();
+                if keep_order[&a.resource_id] {
+                    for o in others
+                        .iter()
+                        .filter(|o| (o.start_ms, o.end_ms) >= (a.start_ms, a.end_ms))
+                    {
+                        let limit = if capacity == 1 {
+                            o.start_ms
+                        } else {
+                            o.start_ms + a.duration_ms()
+                        };
+                        latest_end = latest_end.min(limit);
+                    }
+                }
+
+                if let Some(start) = latest_fit(a, latest_end, &others, capacity, calendar) {
+                    let a = &mut assignments[idx];
+                    a.end_ms = start + a.duration_ms();
+                    a.start_ms = start;
+                    changed = true;
+                }
+            }
+            if !changed {
+                break;
+            }
+        }
+
+        let mut shifted = schedule.clone();
+        shifted.makespan_ms = 0;
+        shifted.assignments.clear();
+        for (before, after) in schedule.assignments.iter().zip(assignments) {
+            if after.start_ms != before.start_ms {
+                // The recorded placement reasons no longer apply
+                shifted.explanations.remove(&after.activity_id);
+            }
+            shifted.add_assignment(after);
+        }
+        shifted.reindex();
+        shifted
+    }
+}
+
+/// Latest start in `(a.start_ms, to - duration]` where `a` fits, if any
+///
+/// Candidate ends move back before calendar breaks and to the starts of
+/// overlapping work, the only points where feasibility can change.
+fn latest_fit(
+    a: &Assignment,
+    to: i64,
+    others: &[&Assignment],
+    capacity: usize,
+    calendar: Option<&Calendar>,
+) -> Option<i64> {
+    let duration = a.duration_ms();
+    let mut end = to;
+    loop {
+        if let Some(calendar) = calendar {
+            end = calendar.previous_available_time(end);
+            if !calendar.is_working_time(end - 1) {
+                // No working time left
+                return None;
+            }
+        }
+        let start = end - duration;
+        if start <= a.start_ms {
+            return None;
+        }
+        if let Some(stop) = calendar.and_then(|c| last_break(c, start, end)) {
+            end = stop;
+            continue;
+        }
+        let overlapping: Vec<&&Assignment> = others
+            .iter()
+            .filter(|o| o.start_ms < end && start < o.end_ms)
+            .collect();
+        if peak_usage(&overlapping, start, end) >= capacity {
+            end = overlapping
+                .iter()
+                .map(|o| o.start_ms)
+                .filter(|&s| s < end)
+                .max()?;
+            continue;
+        }
+        return Some(start);
+    }
+}
+
+/// A time inside the last non-working stretch in `[from, to)`, if any
+fn last_break(calendar: &Calendar, from: i64, to: i64) -> Option<i64> {
+    if calendar.is_available_between(from, to) {
+        return None;
+    }
+    // Longest fully working suffix
+    let (mut lo, mut hi) = (from, to);
+    while hi - lo > 1 {
+        let mid = lo + (hi - lo) / 2;
+        if calendar.is_available_between(mid, to) {
+            hi = mid;
+        } else {
+            lo = mid;
+        }
+    }
+    Some(lo)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Activity, Calendar};
+    use crate::scheduler::ScheduleKpi;
+    use chrono::DateTime;
+
+    fn task(id: &str, n: usize, deadline_ms: Option<i64>) -> Task {
+        let mut task = (1..=n).fold(Task::new(id), |t, k| {
+            t.with_activity(Activity::new(&format!("{}{}", id, k), id, k as i32))
+        });
+        task.deadline = deadline_ms.and_then(DateTime::from_timestamp_millis);
+        task
+    }
+
+    #[test]
+    fn test_shift_toward_deadlines() {
+        let tasks = vec![
+            task("A", 2, Some(10_000)),
+            task("B", 1, Some(2500)),
+            task("C", 1, None),
+        ];
+        let resources = vec![Resource::primary("M1"), Resource::primary("M2")];
+        let mut schedule = Schedule::new();
+        schedule.add_assignment(Assignment::new("A1", "A", "M1", 0, 1000));
+        schedule.add_assignment(Assignment::new("A2", "A", "M2", 1000, 3000));
+        // Late: stays
+        schedule.add_assignment(Assignment::new("B1", "B", "M1", 1000, 3000));
+        // No deadline: stays, and blocks M2 for A2 until 9000
+        schedule.add_assignment(Assignment::new("C1", "C", "M2", 9000, 9500));
+
+        let shifted = JustInTime::new().apply(&schedule, &tasks, &resources);
+        let span = |id: &str| {
+            let a = shifted.assignment_for_activity(id).unwrap();
+            (a.start_ms, a.end_ms)
+        };
+        assert_eq!(span("A2"), (7000, 9000));
+        assert_eq!(span("A1"), (6000, 7000));
+        assert_eq!(span("B1"), (1000, 3000));
+        assert_eq!(span("C1"), (9000, 9500));
+
+        let before = ScheduleKpi::calculate(&schedule, &tasks);
+        let after = ScheduleKpi::calculate(&shifted, &tasks);
+        assert_eq!(
+            (before.total_earliness_ms, after.total_earliness_ms),
+            (7000, 1000)
+        );
+        assert_eq!(after.total_tardiness_ms, before.total_tardiness_ms);
+
+        // A buffer keeps completions ahead of the deadline
+        let buffered = JustInTime::new()
+            .with_buffer_ms(2000)
+            .apply(&schedule, &tasks, &resources);
+        assert_eq!(buffered.assignment_for_activity("A2").unwrap().end_ms, 8000);
+    }
+
+    #[test]
+    fn test_calendar_break() {
+        let tasks = vec![task("A", 1, Some(5000))];
+        let calendar = Calendar::new("shift")
+            .with_window(0, 10_000)
+            .with_blocked(4000, 4500);
+        let resources = vec![Resource::primary("M1").with_calendar(calendar)];
+        let mut schedule = Schedule::new();
+        schedule.add_assignment(Assignment::new("A1", "A", "M1", 0, 1000));
+
+        // 4000..5000 would cross the break
+        let shifted = JustInTime::new().apply(&schedule, &tasks, &resources);
+        let a1 = shifted.assignment_for_activity("A1").unwrap();
+        assert_eq!((a1.start_ms, a1.end_ms), (3000, 4000));
+    }
+}
@@ -2,7 +2,10 @@
 //!
 //! Metrics for evaluating schedule quality
 
-use crate::models::{Schedule, Task};
+use super::bounds::MakespanBound;
+use super::cost::CostModel;
+use super::robustness::RobustnessAnalyzer;
+use crate::models::{EnergyTariff, PreferenceReport, Resource, Schedule, Task};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -15,6 +18,15 @@ pub struct ScheduleKpi {
     pub total_tardiness_ms: i64,
     /// Maximum tardiness
     pub max_tardiness_ms: i64,
+    /// Total tardiness weighted by task weight
+    #[serde(default)]
+    pub weighted_tardiness_ms: f64,
+    /// Total earliness (deadline - completion of tasks finishing early)
+    #[serde(default)]
+    pub total_earliness_ms: i64,
+    /// Maximum earliness
+    #[serde(default)]
+    pub max_earliness_ms: i64,
     /// On-time delivery rate (0.0 to 1.0)
     pub on_time_rate: f64,
     /// Average resource utilization (0.0 to 1.0)
@@ -23,6 +35,133 @@ pub struct ScheduleKpi {
     pub utilization_by_resource: HashMap<String, f64>,
     /// Average flow time (time from release to completion)
     pub avg_flow_time_ms: f64,
+    /// Total energy cost (0 unless priced with a tariff)
+    #[serde(default)]
+    pub energy_cost: f64,
+    /// Total monetary cost (0 unless priced with a cost model)
+    #[serde(default)]
+    pub total_cost: f64,
+    /// Preference satisfaction rate (0.0 to 1.0)
+    #[serde(default = "default_preference_satisfaction")]
+    pub preference_satisfaction: f64,
+    /// Number of tasks finishing after their due date (target date, else
+    /// deadline)
+    #[serde(default)]
+    pub late_tasks: usize,
+    /// Number of tasks finishing after their hard deadline (infeasible)
+    #[serde(default)]
+    pub deadline_misses: usize,
+    /// Average tardiness of late tasks only
+    #[serde(default)]
+    pub conditional_tardiness_ms: f64,
+    /// Lateness distribution over tasks with deadlines
+    #[serde(default)]
+    pub lateness: LatenessStats,
+    /// Lateness distribution by task category
+    #[serde(default)]
+    pub lateness_by_category: HashMap<String, LatenessStats>,
+    /// Total free slack (0 unless analyzed for robustness)
+    #[serde(default)]
+    pub total_free_slack_ms: i64,
+    /// Smallest gap on the critical chain
+    #[serde(default)]
+    pub min_critical_slack_ms: i64,
+    /// Expected makespan delay under duration distributions
+    #[serde(default)]
+    pub expected_delay_ms: f64,
+    /// Makespan lower bound (0 unless bounded)
+    #[serde(default)]
+    pub makespan_lower_bound_ms: i64,
+    /// Relative gap to the lower bound (0 unless bounded)
+    #[serde(default)]
+    pub optimality_gap: f64,
+    /// Number of setups performed (assignments with setup time)
+    #[serde(default)]
+    pub setup_count: usize,
+    /// Total setup time
+    #[serde(default)]
+    pub total_setup_ms: i64,
+    /// Setup time / processing time (0 without processing)
+    #[serde(default)]
+    pub setup_ratio: f64,
+    /// Setups performed by resource
+    #[serde(default)]
+    pub setups_by_resource: HashMap<String, usize>,
+}
+
+/// Distribution of lateness (completion - deadline, negative when early)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct LatenessStats {
+    /// Tasks with a deadline
+    pub count: usize,
+    /// Tasks finishing after their deadline
+    pub late_count: usize,
+    /// Median lateness
+    pub p50_ms: i64,
+    /// 90th percentile lateness
+    pub p90_ms: i64,
+    /// Maximum lateness
+    pub max_ms: i64,
+    /// Average tardiness of late tasks
+    pub conditional_tardiness_ms: f64,
+    /// Tasks finishing before their deadline
+    #[serde(default)]
+    pub early_count: usize,
+    /// Average earliness of early tasks
+    #[serde(default)]
+    pub conditional_earliness_ms: f64,
+}
+
+impl LatenessStats {
+    /// Compute statistics from lateness values
+    pub fn from_lateness(values: &[i64]) -> Self {
+        if values.is_empty() {
+            return Self::default();
+        }
+        let mut sorted = values.to_vec();
+        sorted.sort_unstable();
+        let late: Vec<i64> = sorted.iter().copied().filter(|&l| l > 0).collect();
+        let early: Vec<i64> = sorted.iter().copied().filter(|&l| l < 0).collect();
+
+        Self {
+            count: sorted.len(),
+            late_count: late.len(),
+            p50_ms: percentile(&sorted, 0.5),
+            p90_ms: percentile(&sorted, 0.9),
+            max_ms: sorted[sorted.len() - 1],
+            conditional_tardiness_ms: if late.is_empty() {
+                0.0
+            } else {
+                late.iter().sum::<i64>() as f64 / late.len() as f64
+            },
+            early_count: early.len(),
+            conditional_earliness_ms: if early.is_empty() {
+                0.0
+            } else {
+                -early.iter().sum::<i64>() as f64 / early.len() as f64
+            },
+        }
+    }
+
+    /// Lateness at percentile `p` (0.0 to 1.0, nearest rank)
+    pub fn percentile(values: &[i64], p: f64) -> i64 {
+        let mut sorted = values.to_vec();
+        sorted.sort_unstable();
+        percentile(&sorted, p)
+    }
+}
+
+/// Nearest-rank percentile of sorted values
+fn percentile(sorted: &[i64], p: f64) -> i64 {
+    if sorted.is_empty() {
+        return 0;
+    }
+    let rank = (p.clamp(0.0, 1.0) * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+fn default_preference_satisfaction() -> f64 {
+    1.0
 }
 
 impl ScheduleKpi {
@@ -30,26 +169,47 @@ impl ScheduleKpi {
     pub fn calculate(schedule: &Schedule, tasks: &[Task]) -> Self {
         let mut total_tardiness = 0i64;
         let mut max_tardiness = 0i64;
+        let mut weighted_tardiness = 0.0;
+        let mut total_earliness = 0i64;
+        let mut max_earliness = 0i64;
         let mut on_time_count = 0;
+        let mut deadline_misses = 0;
         let mut total_flow_time = 0i64;
         let mut task_count = 0;
+        let mut lateness: Vec<i64> = Vec::new();
+        let mut lateness_by_category: HashMap<&str, Vec<i64>> = HashMap::new();
 
         for task in tasks {
             if let Some(completion) = schedule.task_completion_time(&task.id) {
                 task_count += 1;
+                if task
+                    .deadline
+                    .is_some_and(|d| completion > d.timestamp_millis())
+                {
+                    deadline_misses += 1;
+                }
 
-                // Calculate tardiness
-                if let Some(deadline) = &task.deadline {
-                    let deadline_ms = deadline.timestamp_millis();
-                    if completion > deadline_ms {
-                        let tardiness = completion - deadline_ms;
+                // Calculate tardiness against the due date (target, else deadline)
+                if let Some(due) = task.due_date() {
+                    let due_ms = due.timestamp_millis();
+                    lateness.push(completion - due_ms);
+                    lateness_by_category
+                        .entry(task.category.as_str())
+                        .or_default()
+                        .push(completion - due_ms);
+                    if completion > due_ms {
+                        let tardiness = completion - due_ms;
                         total_tardiness += tardiness;
+                        weighted_tardiness += task.objective_weight() * tardiness as f64;
                         max_tardiness = max_tardiness.max(tardiness);
                     } else {
+                        let earliness = due_ms - completion;
+                        total_earliness += earliness;
+                        max_earliness = max_earliness.max(earliness);
                         on_time_count += 1;
                     }
                 } else {
-                    on_time_count += 1; // No deadline = on time
+                    on_time_count += 1; // No due date = on time
                 }
 
                 // Calculate flow time
@@ -78,17 +238,117 @@ impl ScheduleKpi {
             0.0
         };
 
+        let lateness = LatenessStats::from_lateness(&lateness);
+
+        // Setups and changeovers
+        let mut setups_by_resource: HashMap<String, usize> = HashMap::new();
+        let mut total_setup = 0i64;
+        let mut total_process = 0i64;
+        for a in &schedule.assignments {
+            total_process += a.process_ms();
+            if a.setup_ms > 0 {
+                total_setup += a.setup_ms;
+                *setups_by_resource.entry(a.resource_id.clone()).or_insert(0) += 1;
+            }
+        }
+
         Self {
             makespan_ms: schedule.makespan_ms,
             total_tardiness_ms: total_tardiness,
             max_tardiness_ms: max_tardiness,
+            weighted_tardiness_ms: weighted_tardiness,
+            total_earliness_ms: total_earliness,
+            max_earliness_ms: max_earliness,
             on_time_rate,
             avg_utilization,
             utilization_by_resource,
             avg_flow_time_ms: avg_flow_time,
+            energy_cost: 0.0,
+            total_cost: 0.0,
+            preference_satisfaction: 1.0,
+            late_tasks: lateness.late_count,
+            deadline_misses,
+            conditional_tardiness_ms: lateness.conditional_tardiness_ms,
+            lateness,
+            lateness_by_category: lateness_by_category
+                .into_iter()
+                .map(|(category, values)| {
+                    (category.to_string(), LatenessStats::from_lateness(&values))
+                })
+                .collect(),
+            total_free_slack_ms: 0,
+            min_critical_slack_ms: 0,
+            expected_delay_ms: 0.0,
+            makespan_lower_bound_ms: 0,
+            optimality_gap: 0.0,
+            setup_count: setups_by_resource.values().sum(),
+            total_setup_ms: total_setup,
+            setup_ratio: if total_process > 0 {
+                total_setup as f64 / total_process as f64
+            } else {
+                0.0
+            },
+            setups_by_resource,
         }
     }
 
+    /// Price the schedule's energy consumption under a tariff
+    pub fn with_energy_cost(
+        mut self,
+        schedule: &Schedule,
+        tasks: &[Task],
+        tariff: &EnergyTariff,
+    ) -> Self {
+        self.energy_cost = tariff.schedule_cost(schedule, tasks);
+        self
+    }
+
+    /// Price the schedule under a cost model
+    pub fn with_cost(
+        mut self,
+        schedule: &Schedule,
+        tasks: &[Task],
+        resources: &[Resource],
+        cost_model: &CostModel,
+    ) -> Self {
+        self.total_cost = cost_model.total_cost(schedule, tasks, resources);
+        self
+    }
+
+    /// Measure how well soft preferences were honored
+    pub fn with_preferences(
+        mut self,
+        schedule: &Schedule,
+        tasks: &[Task],
+        resources: &[Resource],
+    ) -> Self {
+        self.preference_satisfaction =
+            PreferenceReport::evaluate(schedule, tasks, resources).satisfaction_rate();
+        self
+    }
+
+    /// Measure slack and expected delay
+    pub fn with_robustness(
+        mut self,
+        schedule: &Schedule,
+        tasks: &[Task],
+        analyzer: &RobustnessAnalyzer,
+    ) -> Self {
+        let report = analyzer.evaluate(schedule, tasks);
+        self.total_free_slack_ms = report.total_free_slack_ms;
+        self.min_critical_slack_ms = report.min_critical_slack_ms;
+        self.expected_delay_ms = report.expected_delay_ms;
+        self
+    }
+
+    /// Compare the makespan with a lower bound
+    pub fn with_lower_bound(mut self, bound: &MakespanBound) -> Self {
+        let gap = bound.gap(self.makespan_ms);
+        self.makespan_lower_bound_ms = gap.lower_bound_ms;
+        self.optimality_gap = gap.gap;
+        self
+    }
+
     /// Check if schedule meets quality thresholds
     pub fn meets_thresholds(&self, max_tardiness: i64, min_utilization: f64) -> bool {
         self.max_tardiness_ms <= max_tardiness && self.avg_utilization >= min_utilization
@@ -101,10 +361,30 @@ impl Default for ScheduleKpi {
             makespan_ms: 0,
             total_tardiness_ms: 0,
             max_tardiness_ms: 0,
+            weighted_tardiness_ms: 0.0,
+            total_earliness_ms: 0,
+            max_earliness_ms: 0,
             on_time_rate: 1.0,
             avg_utilization: 0.0,
             utilization_by_resource: HashMap::new(),
             avg_flow_time_ms: 0.0,
+            energy_cost: 0.0,
+            total_cost: 0.0,
+            preference_satisfaction: 1.0,
+            late_tasks: 0,
+            deadline_misses: 0,
+            conditional_tardiness_ms: 0.0,
+            lateness: LatenessStats::default(),
+            lateness_by_category: HashMap::new(),
+            total_free_slack_ms: 0,
+            min_critical_slack_ms: 0,
+            expected_delay_ms: 0.0,
+            makespan_lower_bound_ms: 0,
+            optimality_gap: 0.0,
+            setup_count: 0,
+            total_setup_ms: 0,
+            setup_ratio: 0.0,
+            setups_by_resource: HashMap::new(),
         }
     }
 }
@@ -144,11 +424,193 @@ mod tests {
         let deadline = chrono::DateTime::from_timestamp_millis(5000).unwrap();
         let task = Task::new("T1")
             .with_deadline(deadline)
+            .with_weight(2.5)
             .with_activity(Activity::new("A1", "T1", 1));
 
         let kpi = ScheduleKpi::calculate(&schedule, &[task]);
 
         assert_eq!(kpi.total_tardiness_ms, 5000);
+        assert_eq!(kpi.weighted_tardiness_ms, 12500.0);
         assert_eq!(kpi.on_time_rate, 0.0);
+        assert_eq!(kpi.deadline_misses, 1);
+    }
+
+    #[test]
+    fn test_weighted_tardiness() {
+        let mut schedule = Schedule::new();
+        schedule.add_assignment(Assignment::new("A1", "T1", "R1", 0, 2000));
+        schedule.add_assignment(Assignment::new("A2", "T2", "R1", 2000, 4000));
+        schedule.add_assignment(Assignment::new("A3", "T3", "R1", 4000, 6000));
+
+        // All due at 1000ms: 1000, 3000 and 5000ms late
+        let deadline = chrono::DateTime::from_timestamp_millis(1000).unwrap();
+        let tasks = vec![
+            Task::new("T1")
+                .with_deadline(deadline)
+                .with_weight(3.0)
+                .with_activity(Activity::new("A1", "T1", 1)),
+            // Unset weight counts as 1.0, whatever the priority
+            Task::new("T2")
+                .with_deadline(deadline)
+                .with_priority(10)
+                .with_activity(Activity::new("A2", "T2", 1)),
+            Task::new("T3")
+                .with_deadline(deadline)
+                .with_weight(0.0)
+                .with_activity(Activity::new("A3", "T3", 1)),
+        ];
+
+        let kpi = ScheduleKpi::calculate(&schedule, &tasks);
+
+        assert_eq!(kpi.total_tardiness_ms, 9000);
+        assert_eq!(kpi.weighted_tardiness_ms, 3.0 * 1000.0 + 3000.0);
+    }
+
+    #[test]
+    fn test_setup_kpis() {
+        let mut schedule = Schedule::new();
+        schedule.add_assignment(Assignment::new("A1", "T1", "R1", 0, 4000));
+        schedule.add_assignment(Assignment::new("A2", "T2", "R1", 4000, 9000).with_setup(1000));
+        schedule.add_assignment(Assignment::new("A3", "T3", "R2", 0, 3000).with_setup(1000));
+
+        let kpi = ScheduleKpi::calculate(&schedule, &[]);
+
+        assert_eq!(kpi.setup_count, 2);
+        assert_eq!(kpi.total_setup_ms, 2000);
+        // 2 s of setup against 10 s of processing
+        assert_eq!(kpi.setup_ratio, 0.2);
+        assert_eq!(kpi.setups_by_resource["R1"], 1);
+        assert_eq!(kpi.setups_by_resource["R2"], 1);
+    }
+
+    #[test]
+    fn test_target_date() {
+        let at = |ms: i64| chrono::DateTime::from_timestamp_millis(ms).unwrap();
+        let mut schedule = Schedule::new();
+        schedule.add_assignment(Assignment::new("A1", "T1", "R1", 0, 4000));
+
+        // Misses the target by 1000ms, meets the hard deadline
+        let task = Task::new("T1")
+            .with_target_date(at(3000))
+            .with_deadline(at(5000))
+            .with_activity(Activity::new("A1", "T1", 1));
+
+        let kpi = ScheduleKpi::calculate(&schedule, &[task]);
+
+        assert_eq!(kpi.late_tasks, 1);
+        assert_eq!(kpi.total_tardiness_ms, 1000);
+        assert_eq!(kpi.deadline_misses, 0);
+    }
+
+    #[test]
+    fn test_lateness_distribution() {
+        let at = |ms: i64| chrono::DateTime::from_timestamp_millis(ms).unwrap();
+        let mut schedule = Schedule::new();
+        let mut tasks = Vec::new();
+        // Rush orders: 1000ms early, on time, 2000ms and 6000ms late
+        for (i, (end, category)) in [
+            (4000, "rush"),
+            (5000, "rush"),
+            (7000, "rush"),
+            (11000, "rush"),
+            (9000, "bulk"),
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            let id = format!("T{}", i);
+            schedule.add_assignment(Assignment::new(&format!("{}-A", id), &id, "R1", 0, end));
+            tasks.push(
+                Task::new(&id)
+                    .with_category(category)
+                    .with_deadline(at(5000))
+                    .with_activity(Activity::new(&format!("{}-A", id), &id, 1)),
+            );
+        }
+
+        let kpi = ScheduleKpi::calculate(&schedule, &tasks);
+
+        assert_eq!(kpi.late_tasks, 3);
+        assert_eq!(kpi.conditional_tardiness_ms, 4000.0);
+        assert_eq!(kpi.lateness.p50_ms, 2000);
+        assert_eq!(kpi.lateness.max_ms, 6000);
+        assert_eq!((kpi.total_earliness_ms, kpi.max_earliness_ms), (1000, 1000));
+        assert_eq!(kpi.lateness.early_count, 1);
+        assert_eq!(kpi.lateness.conditional_earliness_ms, 1000.0);
+
+        let rush = &kpi.lateness_by_category["rush"];
+        assert_eq!(rush.count, 4);
+        assert_eq!(rush.p50_ms, 0);
+        assert_eq!(rush.p90_ms, 6000);
+        assert_eq!(kpi.lateness_by_category["bulk"].late_count, 1);
+        assert_eq!(LatenessStats::percentile(&[5, 1, 3], 0.9), 5);
+    }
+
+    #[test]
+    fn test_energy_cost() {
+        let mut schedule = Schedule::new();
+        schedule.add_assignment(Assignment::new("A1", "T1", "R1", 0, 3_600_000));
+
+        let tasks =
+            vec![Task::new("T1")
+                .with_activity(Activity::new("A1", "T1", 1).with_energy_demand(10.0))];
+        let tariff = EnergyTariff::new(0.2);
+
+        let kpi =
+            ScheduleKpi::calculate(&schedule, &tasks).with_energy_cost(&schedule, &tasks, &tariff);
+
+        assert!((kpi.energy_cost - 2.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_total_cost() {
+        let mut schedule = Schedule::new();
+        schedule.add_assignment(Assignment::new("A1", "T1", "R1", 0, 7_200_000));
+
+        let tasks = vec![Task::new("T1").with_activity(Activity::new("A1", "T1", 1))];
+        let resources = vec![Resource::primary("R1").with_cost(40.0)];
+
+        let kpi = ScheduleKpi::calculate(&schedule, &tasks).with_cost(
+            &schedule,
+            &tasks,
+            &resources,
+            &CostModel::new(),
+        );
+
+        assert!((kpi.total_cost - 80.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_preference_satisfaction() {
+        let mut schedule = Schedule::new();
+        schedule.add_assignment(Assignment::new("A1", "T1", "R2", 0, 1000));
+
+        let tasks = vec![Task::new("T1")
+            .with_activity(Activity::new("A1", "T1", 1).with_preferred_resource("R1", 1.0))];
+
+        let kpi =
+            ScheduleKpi::calculate(&schedule, &tasks).with_preferences(&schedule, &tasks, &[]);
+        assert_eq!(kpi.preference_satisfaction, 0.0);
+        assert_eq!(ScheduleKpi::default().preference_satisfaction, 1.0);
+    }
+
+    #[test]
+    fn test_robustness() {
+        let mut schedule = Schedule::new();
+        schedule.add_assignment(Assignment::new("A1", "T1", "R1", 0, 1000));
+        schedule.add_assignment(Assignment::new("A2", "T2", "R1", 1500, 2000));
+
+        let tasks = vec![
+            Task::new("T1").with_activity(Activity::new("A1", "T1", 1)),
+            Task::new("T2").with_activity(Activity::new("A2", "T2", 1)),
+        ];
+
+        let kpi = ScheduleKpi::calculate(&schedule, &tasks).with_robustness(
+            &schedule,
+            &tasks,
+            &RobustnessAnalyzer::default(),
+        );
+        assert_eq!(kpi.total_free_slack_ms, 500);
+        assert_eq!(kpi.min_critical_slack_ms, 500);
     }
 }
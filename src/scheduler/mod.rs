@@ -2,8 +2,48 @@
 //!
 //! Provides various scheduling strategies for resource allocation
 
+mod acceptance;
+mod adherence;
+mod background;
+mod bounds;
+mod campaign;
+mod compaction;
+mod cost;
+mod deadline;
+mod elastic;
+mod heft;
+mod jit;
 mod kpi;
+mod rcpsp;
+mod repair;
+mod revocation;
+mod robustness;
+mod roster;
+mod scenario;
 mod simple;
+mod stability;
+mod strict;
+mod transition_estimate;
 
+pub use acceptance::*;
+pub use adherence::*;
+pub use background::*;
+pub use bounds::*;
+pub use campaign::*;
+pub use compaction::*;
+pub use cost::*;
+pub use deadline::*;
+pub use elastic::*;
+pub use heft::*;
+pub use jit::*;
 pub use kpi::*;
+pub use rcpsp::*;
+pub use repair::*;
+pub use revocation::*;
+pub use robustness::*;
+pub use roster::*;
+pub use scenario::*;
 pub use simple::*;
+pub use stability::*;
+pub use strict::*;
+pub use transition_estimate::*;
@@ -0,0 +1,447 @@
+//! RCPSP - Resource-constrained project scheduling
+//!
+//! Activities hold integer units of renewable resource pools instead of
+//! occupying named resources; the units in use may never exceed a pool's
+//! capacity. Schedules are built by the classic schedule generation
+//! schemes (serial and parallel SGS) driven by a priority rule.
+
+use crate::graph::{PrecedenceGraph, TopologicalOrder};
+use crate::models::schedule::Violation;
+use crate::models::{Activity, Assignment, RenewablePool, Schedule, Task};
+use std::collections::HashMap;
+
+/// Schedule generation scheme
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SgsScheme {
+    /// Take activities in priority order, each at its earliest
+    /// precedence- and resource-feasible start (active schedules)
+    #[default]
+    Serial,
+    /// Advance time; at each decision point start every eligible activity
+    /// that fits, in priority order (non-delay schedules)
+    Parallel,
+}
+
+/// Priority rule for choosing among eligible activities
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RcpspPriority {
+    /// Smallest latest finish time of the resource-free critical path
+    #[default]
+    LatestFinish,
+    /// Most transitive successors
+    MostSuccessors,
+    /// Shortest processing time
+    ShortestDuration,
+}
+
+/// RCPSP list scheduler
+///
+/// Precedence comes from the task structure (explicit predecessors, else
+/// sequence chains) and task release times are honored. Durations are
+/// nominal process times. Every activity gets one assignment per pool it
+/// holds (resource ID = pool ID), all with the same times; an activity
+/// holding no pool gets one assignment with an empty resource ID.
+/// Activities demanding more than a pool's capacity, or an unknown pool,
+/// are left out with a capacity violation.
+#[derive(Debug, Clone, Default)]
+pub struct RcpspScheduler {
+    /// Schedule generation scheme
+    pub scheme: SgsScheme,
+    /// Priority rule
+    pub priority: RcpspPriority,
+}
+
+impl RcpspScheduler {
+    /// Create serial-SGS scheduler with latest-finish priority
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set schedule generation scheme
+    pub fn with_scheme(mut self, scheme: SgsScheme) -> Self {
+        self.scheme = scheme;
+        self
+    }
+
+    /// Set priority rule
+    pub fn with_priority(mut self, priority: RcpspPriority) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    /// Schedule a project
+    pub fn schedule(
+        &self,
+        tasks: &[Task],
+        pools: &[RenewablePool],
+        start_time_ms: i64,
+    ) -> Schedule {
+        let graph = PrecedenceGraph::from_tasks(tasks);
+        let mut activities: Vec<&Activity> = Vec::with_capacity(graph.len());
+        for a in tasks.iter().flat_map(|t| t.activities.iter()) {
+            if graph.index_of(&a.id) == Some(activities.len()) {
+                activities.push(a);
+            }
+        }
+        let TopologicalOrder { order, cyclic } = graph.topological_order();
+        let capacity: HashMap<&str, u32> =
+            pools.iter().map(|p| (p.id.as_str(), p.capacity)).collect();
+        let release: HashMap<&str, i64> = tasks
+            .iter()
+            .map(|t| {
+                let release = t
+                    .release_time
+                    .map_or(start_time_ms, |r| r.timestamp_millis().max(start_time_ms));
+                (t.id.as_str(), release)
+            })
+            .collect();
+
+        let mut schedule = Schedule::new();
+        // Activities that can never fit are dropped up front
+        let mut skipped = vec![false; activities.len()];
+        for (i, activity) in activities.iter().enumerate() {
+            let mut demands: Vec<(&String, &u32)> = activity.pool_demands.iter().collect();
+            demands.sort();
+            for (pool, &units) in demands {
+                let available = capacity.get(pool.as_str()).copied().unwrap_or(0);
+                if units > available {
+                    skipped[i] = true;
+                    schedule.add_violation(Violation::capacity_exceeded(
+                        pool,
+                        &format!(
+                            "Activity {} demands {} units of pool {} (capacity {})",
+                            activity.id, units, pool, available
+                        ),
+                    ));
+                }
+            }
+        }
+
+        let position: HashMap<usize, usize> =
+            order.iter().enumerate().map(|(p, &i)| (i, p)).collect();
+        let priority = self.priorities(&graph, &activities, &order);
+        let better = |a: usize, b: usize| (priority[a], position[&a]) < (priority[b], position[&b]);
+
+        let mut profile = Profile::default();
+        let mut placed: Vec<Option<(i64, i64)>> = vec![None; activities.len()];
+        let mut pending: Vec<usize> = vec![0; activities.len()];
+        for &i in &order {
+            pending[i] = graph.predecessors(i).len();
+        }
+        let mut eligible: Vec<usize> = order.iter().copied().filter(|&i| pending[i] == 0).collect();
+        let ready = |i: usize, placed: &[Option<(i64, i64)>]| {
+            graph
+                .predecessors(i)
+                .iter()
+                .filter_map(|&p| placed[p].map(|(_, end)| end))
+                .fold(release[activities[i].task_id.as_str()], i64::max)
+        };
+        let finish = |i: usize, eligible: &mut Vec<usize>, pending: &mut Vec<usize>| {
+            for &j in graph.successors(i) {
+                pending[j] -= 1;
+                if pending[j] == 0 {
+                    eligible.push(j);
+                }
+            }
+        };
+
+        let mut now = start_time_ms;
+        while !eligible.is_empty() {
+            match self.scheme {
+                SgsScheme::Serial => {
+                    let k = (0..eligible.len())
+                        .reduce(|a, b| {
+                            if better(eligible[b], eligible[a]) {
+                                b
+                            } else {
+                                a
+                            }
+                        })
+                        .expect("eligible is not empty");
+                    let i = eligible.swap_remove(k);
+                    if !skipped[i] {
+                        let activity = activities[i];
+                        let duration = activity.duration.process_ms;
+                        let start = profile.earliest_fit(
+                            ready(i, &placed),
+                            duration,
+                            &activity.pool_demands,
+                            &capacity,
+                        );
+                        profile.book(start, start + duration, &activity.pool_demands);
+                        placed[i] = Some((start, start + duration));
+                    }
+                    finish(i, &mut eligible, &mut pending);
+                }
+                SgsScheme::Parallel => {
+                    eligible.sort_by(|&a, &b| {
+                        (priority[a], position[&a]).cmp(&(priority[b], position[&b]))
+                    });
+                    let mut started = Vec::new();
+                    for (k, &i) in eligible.iter().enumerate() {
+                        let activity = activities[i];
+                        let duration = activity.duration.process_ms;
+                        if skipped[i] {
+                            started.push(k);
+                        } else if ready(i, &placed) <= now
+                            && profile.fits(now, duration, &activity.pool_demands, &capacity)
+                        {
+                            profile.book(now, now + duration, &activity.pool_demands);
+                            placed[i] = Some((now, now + duration));
+                            started.push(k);
+                        }
+                    }
+                    for &k in started.iter().rev() {
+                        let i = eligible.remove(k);
+                        finish(i, &mut eligible, &mut pending);
+                    }
+                    if started.is_empty() {
+                        // Next decision point: a release or a finish
+                        now = eligible
+                            .iter()
+                            .map(|&i| ready(i, &placed))
+                            .chain(placed.iter().flatten().map(|&(_, end)| end))
+                            .filter(|&t| t > now)
+                            .min()
+                            .expect("an activity is running or released later");
+                    }
+                }
+            }
+        }
+
+        for (i, activity) in activities.iter().enumerate() {
+            let Some((start, end)) = placed[i] else {
+                continue;
+            };
+            let mut pools: Vec<&str> = activity
+                .pool_demands
+                .iter()
+                .filter(|(_, &units)| units > 0)
+                .map(|(pool, _)| pool.as_str())
+                .collect();
+            pools.sort_unstable();
+            if pools.is_empty() {
+                pools.push("");
+            }
+            for pool in pools {
+                schedule.add_assignment(Assignment::new(
+                    &activity.id,
+                    &activity.task_id,
+                    pool,
+                    start,
+                    end,
+                ));
+            }
+        }
+        for &i in &cyclic {
+            schedule.add_violation(Violation::precedence_violation(
+                &activities[i].id,
+                &format!("Activity {} is on a precedence cycle", activities[i].id),
+            ));
+        }
+
+        schedule
+    }
+
+    /// Priority value per activity (lower goes first)
+    fn priorities(
+        &self,
+        graph: &PrecedenceGraph,
+        activities: &[&Activity],
+        order: &[usize],
+    ) -> Vec<i64> {
+        let duration = |i: usize| activities[i].duration.process_ms;
+        match self.priority {
+            RcpspPriority::LatestFinish => {
+                // Forward pass for the critical path length, then backward
+                let mut early_finish = vec![0; activities.len()];
+                for &i in order {
+                    let start = graph
+                        .predecessors(i)
+                        .iter()
+                        .map(|&p| early_finish[p])
+                        .max()
+                        .unwrap_or(0);
+                    early_finish[i] = start + duration(i);
+                }
+                let horizon = early_finish.iter().copied().max().unwrap_or(0);
+                let mut latest_finish = vec![horizon; activities.len()];
+                for &i in order.iter().rev() {
+                    for &j in graph.successors(i) {
+                        latest_finish[i] = latest_finish[i].min(latest_finish[j] - duration(j));
+                    }
+                }
+                latest_finish
+            }
+            RcpspPriority::MostSuccessors => (0..activities.len())
+                .map(|i| -(graph.descendants(graph.id(i)).len() as i64))
+                .collect(),
+            RcpspPriority::ShortestDuration => (0..activities.len()).map(duration).collect(),
+        }
+    }
+}
+
+/// Units in use per pool over time
+#[derive(Default)]
+struct Profile {
+    /// Booked (start, end, pool, units)
+    bookings: Vec<(i64, i64, String, u32)>,
+}
+
+impl Profile {
+    /// Peak units of `pool` in use during `[start, end)`
+    fn peak(&self, pool: &str, start: i64, end: i64) -> u32 {
+        // The load only rises where a booking starts
+        let bookings = || {
+            self.bookings
+                .iter()
+                .filter(|b| b.2 == pool && b.0 < end && start < b.1)
+        };
+        bookings()
+            .map(|b| b.0.max(start))
+            .map(|t| {
+                bookings()
+                    .filter(|b| b.0 <= t && t < b.1)
+                    .map(|b| b.3)
+                    .sum()
+            })
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Whether the demands fit during `[start, start + duration)`
+    fn fits(
+        &self,
+        start: i64,
+        duration: i64,
+        demands: &HashMap<String, u32>,
+        capacity: &HashMap<&str, u32>,
+    ) -> bool {
+        duration <= 0
+            || demands.iter().all(|(pool, &units)| {
+                units == 0
+                    || self.peak(pool, start, start + duration) + units
+                        <= capacity.get(pool.as_str()).copied().unwrap_or(0)
+            })
+    }
+
+    /// Earliest start at or after `ready` where the demands fit
+    ///
+    /// Usage only drops when a booking ends, so those are the candidates.
+    fn earliest_fit(
+        &self,
+        ready: i64,
+        duration: i64,
+        demands: &HashMap<String, u32>,
+        capacity: &HashMap<&str, u32>,
+    ) -> i64 {
+        let mut candidates: Vec<i64> = self
+            .bookings
+            .iter()
+            .map(|b| b.1)
+            .filter(|&end| end > ready)
+            .collect();
+        candidates.push(ready);
+        candidates.sort_unstable();
+        candidates
+            .into_iter()
+            .find(|&t| self.fits(t, duration, demands, capacity))
+            .expect("after the last booking every demand within capacity fits")
+    }
+
+    fn book(&mut self, start: i64, end: i64, demands: &HashMap<String, u32>) {
+        for (pool, &units) in demands {
+            if units > 0 && end > start {
+                self.bookings.push((start, end, pool.clone(), units));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ActivityDuration;
+    use crate::validation::validate_pool_capacity;
+
+    fn job(id: &str, ms: i64, crew: u32, preds: &[&str]) -> Activity {
+        let mut a = Activity::new(id, "P", 1)
+            .with_duration(ActivityDuration::fixed(ms))
+            .with_pool_demand("crew", crew);
+        for p in preds {
+            a = a.with_predecessor(p);
+        }
+        a
+    }
+
+    fn project() -> Vec<Task> {
+        // A -> (B, C, D) -> E, crew of 4
+        vec![Task::new("P")
+            .with_activity(job("A", 1000, 2, &[]))
+            .with_activity(job("B", 3000, 3, &["A"]))
+            .with_activity(job("C", 2000, 2, &["A"]))
+            .with_activity(job("D", 1000, 1, &["A"]))
+            .with_activity(job("E", 1000, 4, &["B", "C", "D"]))]
+    }
+
+    #[test]
+    fn test_serial_sgs() {
+        let tasks = project();
+        let pools = vec![RenewablePool::new("crew", 4)];
+        let schedule = RcpspScheduler::new().schedule(&tasks, &pools, 0);
+
+        let a = |id: &str| schedule.assignment_for_activity(id).unwrap();
+        assert_eq!(a("A").resource_id, "crew");
+        // B (3) and C (2) cannot overlap; D (1) runs alongside B
+        assert_eq!((a("B").start_ms, a("D").start_ms), (1000, 1000));
+        assert_eq!(a("C").start_ms, 4000);
+        assert_eq!(a("E").start_ms, 6000);
+        assert_eq!(schedule.makespan_ms, 7000);
+        assert!(validate_pool_capacity(&schedule, &tasks, &pools).is_valid);
+    }
+
+    #[test]
+    fn test_parallel_sgs() {
+        let tasks = project();
+        let pools = vec![RenewablePool::new("crew", 4)];
+        for priority in [
+            RcpspPriority::LatestFinish,
+            RcpspPriority::MostSuccessors,
+            RcpspPriority::ShortestDuration,
+        ] {
+            let schedule = RcpspScheduler::new()
+                .with_scheme(SgsScheme::Parallel)
+                .with_priority(priority)
+                .schedule(&tasks, &pools, 0);
+            assert_eq!(schedule.assignment_count(), 5);
+            assert!(validate_pool_capacity(&schedule, &tasks, &pools).is_valid);
+            let e = schedule.assignment_for_activity("E").unwrap();
+            assert!(e.start_ms >= 5000);
+        }
+
+        // Shortest first: C and D at 1000, B waits for C
+        let schedule = RcpspScheduler::new()
+            .with_scheme(SgsScheme::Parallel)
+            .with_priority(RcpspPriority::ShortestDuration)
+            .schedule(&tasks, &pools, 0);
+        let a = |id: &str| schedule.assignment_for_activity(id).unwrap();
+        assert_eq!((a("C").start_ms, a("D").start_ms), (1000, 1000));
+        assert_eq!(a("B").start_ms, 3000);
+    }
+
+    #[test]
+    fn test_over_capacity() {
+        let tasks = vec![Task::new("P")
+            .with_activity(job("A", 1000, 5, &[]))
+            .with_activity(Activity::new("B", "P", 2).with_duration(ActivityDuration::fixed(500)))];
+        let schedule = RcpspScheduler::new().schedule(&tasks, &[RenewablePool::new("crew", 4)], 0);
+
+        assert!(schedule.assignment_for_activity("A").is_none());
+        assert_eq!(schedule.violations.len(), 1);
+        // B holds no pool
+        assert_eq!(
+            schedule.assignment_for_activity("B").unwrap().resource_id,
+            ""
+        );
+    }
+}
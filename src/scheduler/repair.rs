@@ -0,0 +1,741 @@
+//! Repair - Reactive schedule repair after disruptions
+//!
+//! Takes a published schedule and a disruption event and produces
+//! a minimally changed schedule that respects the disruption.
+
+use crate::models::schedule::Violation;
+use crate::models::{Assignment, Disruption, Resource, ResourceOutage, Schedule, Task};
+use std::collections::{HashMap, HashSet};
+
+/// How assignments hit by a disruption are repaired
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RepairStrategy {
+    /// Keep resources, push affected work (and its successors) later
+    #[default]
+    RightShift,
+    /// Move affected work to an alternative candidate resource when that
+    /// finishes earlier than waiting; fall back to right-shifting
+    Reassign,
+}
+
+/// Result of a repair
+#[derive(Debug, Clone)]
+pub struct RepairResult {
+    /// Repaired schedule
+    pub schedule: Schedule,
+    /// Activities whose start time or resource changed
+    pub affected_activities: Vec<String>,
+    /// Tasks that miss their deadline after repair but met it before
+    pub newly_late_tasks: Vec<String>,
+    /// Activities that could not be placed and were dropped, followed by
+    /// the successors dropped with them
+    pub unassigned_activities: Vec<String>,
+}
+
+/// Disruption repair routine
+#[derive(Debug, Clone, Default)]
+pub struct DisruptionRepair {
+    strategy: RepairStrategy,
+}
+
+impl DisruptionRepair {
+    /// Create repair routine (right-shift by default)
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set repair strategy
+    pub fn with_strategy(mut self, strategy: RepairStrategy) -> Self {
+        self.strategy = strategy;
+        self
+    }
+
+    /// Repair a schedule after a disruption
+    pub fn repair(
+        &self,
+        schedule: &Schedule,
+        disruption: &Disruption,
+        tasks: &[Task],
+        resources: &[Resource],
+    ) -> RepairResult {
+        let outage = disruption.outage();
+        let mut assignments = schedule.assignments.clone();
+
+        if let Disruption::Absence(_) = disruption {
+            let (mut unassigned, stranded) =
+                substitute_absent(&mut assignments, outage, tasks, resources);
+            let mut result = build_result(schedule, assignments, tasks);
+            for activity_id in &unassigned {
+                result
+                    .schedule
+                    .add_violation(Violation::resource_unavailable(
+                        activity_id,
+                        &format!(
+                            "No skill-compatible substitute for {} on activity {}",
+                            outage.resource_id, activity_id
+                        ),
+                    ));
+            }
+            for activity_id in &stranded {
+                result
+                    .schedule
+                    .add_violation(Violation::precedence_violation(
+                        activity_id,
+                        &format!(
+                            "Activity {} depends on work left unassigned by the absence of {}",
+                            activity_id, outage.resource_id
+                        ),
+                    ));
+            }
+            unassigned.extend(stranded);
+            result.unassigned_activities = unassigned;
+            return result;
+        }
+
+        if self.strategy == RepairStrategy::Reassign {
+            reassign_affected(&mut assignments, outage, tasks, resources);
+        }
+
+        let predecessors = predecessor_map(tasks);
+        cascade(
+            &mut assignments,
+            std::slice::from_ref(outage),
+            &predecessors,
+        );
+
+        build_result(schedule, assignments, tasks)
+    }
+}
+
+/// Repair a schedule after a delay of all work from `from_ms`
+///
+/// Work in progress at `from_ms` finishes `delay_ms` later and work
+/// starting at or after it shifts by `delay_ms`; sequence and resource
+/// choices are kept. Tasks pushed past their deadline are reported in
+/// `newly_late_tasks`.
+pub fn right_shift(
+    schedule: &Schedule,
+    tasks: &[Task],
+    from_ms: i64,
+    delay_ms: i64,
+) -> RepairResult {
+    delay(schedule, tasks, None, from_ms, delay_ms)
+}
+
+/// Repair a schedule after a delay of one resource's work from `from_ms`
+///
+/// Like [`right_shift`] on that resource; work elsewhere is pushed later
+/// only where precedences cascade the delay.
+pub fn right_shift_resource(
+    schedule: &Schedule,
+    tasks: &[Task],
+    resource_id: &str,
+    from_ms: i64,
+    delay_ms: i64,
+) -> RepairResult {
+    delay(schedule, tasks, Some(resource_id), from_ms, delay_ms)
+}
+
+fn delay(
+    schedule: &Schedule,
+    tasks: &[Task],
+    resource_id: Option<&str>,
+    from_ms: i64,
+    delay_ms: i64,
+) -> RepairResult {
+    let mut assignments = schedule.assignments.clone();
+    if delay_ms > 0 {
+        for a in &mut assignments {
+            if resource_id.is_some_and(|r| r != a.resource_id) {
+                continue;
+            }
+            if a.start_ms >= from_ms {
+                a.start_ms += delay_ms;
+                a.end_ms += delay_ms;
+            } else if a.end_ms > from_ms {
+                // Interrupted, resumes after the delay
+                a.end_ms += delay_ms;
+            }
+        }
+        cascade(&mut assignments, &[], &predecessor_map(tasks));
+    }
+    build_result(schedule, assignments, tasks)
+}
+
+/// Build predecessor map (activity_id -> predecessor activity IDs)
+///
+/// Activities within a task are chained by sequence number, plus any
+/// explicitly declared predecessors.
+pub(crate) fn predecessor_map(tasks: &[Task]) -> HashMap<String, Vec<String>> {
+    let mut map: HashMap<String, Vec<String>> = HashMap::new();
+
+    for task in tasks {
+        let mut ordered: Vec<_> = task.activities.iter().collect();
+        ordered.sort_by_key(|a| a.sequence);
+
+        for (i, activity) in ordered.iter().enumerate() {
+            let preds = map.entry(activity.id.clone()).or_default();
+            if i > 0 {
+                preds.push(ordered[i - 1].id.clone());
+            }
+            for pred in &activity.predecessors {
+                if !preds.contains(pred) {
+                    preds.push(pred.clone());
+                }
+            }
+        }
+    }
+
+    map
+}
+
+/// Push assignments later until resource sequencing, precedence and
+/// blocked windows are all respected. Never moves anything earlier.
+pub(crate) fn cascade(
+    assignments: &mut [Assignment],
+    blocked: &[ResourceOutage],
+    predecessors: &HashMap<String, Vec<String>>,
+) {
+    // Each pass only moves assignments later, so this converges
+    for _ in 0..=assignments.len() {
+        let mut order: Vec<usize> = (0..assignments.len()).collect();
+        order.sort_by_key(|&i| (assignments[i].start_ms, assignments[i].end_ms));
+
+        let mut resource_free: HashMap<String, i64> = HashMap::new();
+        let mut ends: HashMap<String, i64> = assignments
+            .iter()
+            .map(|a| (a.activity_id.clone(), a.end_ms))
+            .collect();
+        let mut changed = false;
+
+        for idx in order {
+            let a = &assignments[idx];
+            let duration = a.duration_ms();
+
+            let mut start = a.start_ms;
+            if let Some(&free) = resource_free.get(&a.resource_id) {
+                start = start.max(free);
+            }
+            if let Some(preds) = predecessors.get(&a.activity_id) {
+                for pred in preds {
+                    if let Some(&end) = ends.get(pred) {
+                        start = start.max(end);
+                    }
+                }
+            }
+            start = skip_blocked(&a.resource_id, start, duration, blocked);
+
+            let a = &mut assignments[idx];
+            if start != a.start_ms {
+                a.start_ms = start;
+                a.end_ms = start + duration;
+                changed = true;
+            }
+            resource_free.insert(a.resource_id.clone(), a.end_ms);
+            ends.insert(a.activity_id.clone(), a.end_ms);
+        }
+
+        if !changed {
+            break;
+        }
+    }
+}
+
+/// Earliest start >= `start` that avoids all blocked windows of a resource
+fn skip_blocked(
+    resource_id: &str,
+    mut start: i64,
+    duration: i64,
+    blocked: &[ResourceOutage],
+) -> i64 {
+    loop {
+        let hit = blocked
+            .iter()
+            .filter(|o| o.resource_id == resource_id)
+            .find(|o| o.overlaps(start, start + duration.max(1)));
+        match hit {
+            Some(outage) => start = outage.end_ms(),
+            None => return start,
+        }
+    }
+}
+
+/// Earliest gap on a resource of given length at or after `from`
+pub(crate) fn find_gap(
+    assignments: &[Assignment],
+    resource_id: &str,
+    from: i64,
+    duration: i64,
+) -> i64 {
+    let mut busy: Vec<(i64, i64)> = assignments
+        .iter()
+        .filter(|a| a.resource_id == resource_id)
+        .map(|a| (a.start_ms, a.end_ms))
+        .collect();
+    busy.sort();
+
+    let mut start = from;
+    for (busy_start, busy_end) in busy {
+        if busy_end <= start {
+            continue;
+        }
+        if busy_start >= start + duration {
+            break;
+        }
+        start = busy_end;
+    }
+    start
+}
+
+/// Move assignments hit by the outage to alternative candidates
+fn reassign_affected(
+    assignments: &mut [Assignment],
+    outage: &ResourceOutage,
+    tasks: &[Task],
+    resources: &[Resource],
+) {
+    let candidates: HashMap<&str, Vec<String>> = tasks
+        .iter()
+        .flat_map(|t| t.activities.iter())
+        .map(|a| (a.id.as_str(), a.candidate_resources()))
+        .collect();
+
+    let mut hit: Vec<usize> = (0..assignments.len())
+        .filter(|&i| {
+            let a = &assignments[i];
+            a.resource_id == outage.resource_id && outage.overlaps(a.start_ms, a.end_ms)
+        })
+        .collect();
+    hit.sort_by_key(|&i| assignments[i].start_ms);
+
+    for idx in hit {
+        let duration = assignments[idx].duration_ms();
+        let from = assignments[idx].start_ms;
+
+        let best = candidates
+            .get(assignments[idx].activity_id.as_str())
+            .into_iter()
+            .flatten()
+            .filter(|c| **c != outage.resource_id)
+            .filter(|c| resources.iter().any(|r| &r.id == *c))
+            .map(|c| (find_gap(assignments, c, from, duration), c))
+            .filter(|(start, c)| {
+                resources
+                    .iter()
+                    .find(|r| &r.id == *c)
+                    .map(|r| r.is_available_at(*start))
+                    .unwrap_or(false)
+            })
+            .min_by_key(|(start, _)| *start);
+
+        if let Some((start, resource_id)) = best {
+            // Only worth moving if it beats waiting for the repair
+            if start < outage.end_ms() {
+                let a = &mut assignments[idx];
+                a.resource_id = resource_id.clone();
+                a.start_ms = start;
+                a.end_ms = start + duration;
+            }
+        }
+    }
+}
+
+/// Hand activities of an absent resource to skill-compatible substitutes
+///
+/// Substitutes keep the original time slot. A substitute qualifies if it is
+/// a listed candidate of the requirement, or if it has the same resource type
+/// and all skills the requirement asks for. Among qualified and free
+/// substitutes, the most skilled one wins. Returns the activities that could
+/// not be covered and, separately, the scheduled activities that depend on
+/// them (directly or transitively); both are removed from the assignment
+/// list, since successors would otherwise keep times that assume the
+/// dropped work was done.
+fn substitute_absent(
+    assignments: &mut Vec<Assignment>,
+    outage: &ResourceOutage,
+    tasks: &[Task],
+    resources: &[Resource],
+) -> (Vec<String>, Vec<String>) {
+    let activities: HashMap<&str, &crate::models::Activity> = tasks
+        .iter()
+        .flat_map(|t| t.activities.iter())
+        .map(|a| (a.id.as_str(), a))
+        .collect();
+    let absent_type = resources
+        .iter()
+        .find(|r| r.id == outage.resource_id)
+        .map(|r| r.resource_type.clone());
+
+    let mut hit: Vec<usize> = (0..assignments.len())
+        .filter(|&i| {
+            let a = &assignments[i];
+            a.resource_id == outage.resource_id && outage.overlaps(a.start_ms, a.end_ms)
+        })
+        .collect();
+    hit.sort_by_key(|&i| assignments[i].start_ms);
+
+    let mut unassigned = Vec::new();
+    for idx in hit {
+        let (start, end) = (assignments[idx].start_ms, assignments[idx].end_ms);
+        let requirement = activities
+            .get(assignments[idx].activity_id.as_str())
+            .and_then(|act| {
+                act.resource_requirements
+                    .iter()
+                    .find(|req| req.candidates.contains(&outage.resource_id))
+                    .or_else(|| act.resource_requirements.first())
+            });
+
+        let substitute = resources
+            .iter()
+            .filter(|r| r.id != outage.resource_id)
+            .filter(|r| match requirement {
+                Some(req) => {
+                    req.candidates.contains(&r.id)
+                        || (Some(&r.resource_type) == absent_type.as_ref()
+                            && !req.required_skills.is_empty()
+                            && req.required_skills.iter().all(|s| r.has_skill(s)))
+                }
+                None => false,
+            })
+            .filter(|r| r.is_available_at(start))
+            .filter(|r| {
+                !assignments
+                    .iter()
+                    .any(|a| a.resource_id == r.id && a.start_ms < end && a.end_ms > start)
+            })
+            .filter(|r| {
+                r.working_time.as_ref().is_none_or(|rules| {
+                    let booked: Vec<&Assignment> = assignments
+                        .iter()
+                        .filter(|a| a.resource_id == r.id)
+                        .collect();
+                    rules.allows(&booked, start, end)
+                })
+            })
+            .max_by(|a, b| {
+                let level = |r: &Resource| -> f64 {
+                    requirement
+                        .map(|req| req.required_skills.iter().map(|s| r.skill_level(s)).sum())
+                        .unwrap_or(0.0)
+                };
+                level(a)
+                    .partial_cmp(&level(b))
+                    .unwrap_or(std::cmp::Ordering::Equal)
+                    .then_with(|| b.id.cmp(&a.id))
+            });
+
+        match substitute {
+            Some(r) => assignments[idx].resource_id = r.id.clone(),
+            None => unassigned.push(assignments[idx].activity_id.clone()),
+        }
+    }
+
+    let mut successors: HashMap<String, Vec<String>> = HashMap::new();
+    for (activity, preds) in predecessor_map(tasks) {
+        for pred in preds {
+            successors.entry(pred).or_default().push(activity.clone());
+        }
+    }
+    let mut dropped: HashSet<String> = unassigned.iter().cloned().collect();
+    let mut stack = unassigned.clone();
+    let mut stranded = Vec::new();
+    while let Some(id) = stack.pop() {
+        for next in successors.get(&id).into_iter().flatten() {
+            if dropped.insert(next.clone()) {
+                if assignments.iter().any(|a| &a.activity_id == next) {
+                    stranded.push(next.clone());
+                }
+                stack.push(next.clone());
+            }
+        }
+    }
+    stranded.sort_by_key(|id| {
+        assignments
+            .iter()
+            .find(|a| &a.activity_id == id)
+            .map(|a| a.start_ms)
+    });
+
+    assignments.retain(|a| !dropped.contains(&a.activity_id));
+    (unassigned, stranded)
+}
+
+/// Assemble repaired schedule and impact report
+pub(crate) fn build_result(
+    original: &Schedule,
+    assignments: Vec<Assignment>,
+    tasks: &[Task],
+) -> RepairResult {
+    let mut schedule = Schedule::new();
+    schedule.violations = original.violations.clone();
+
+    let mut affected = Vec::new();
+    for assignment in assignments {
+        if let Some(before) = original.assignment_for_activity(&assignment.activity_id) {
+            if before.start_ms != assignment.start_ms
+                || before.resource_id != assignment.resource_id
+            {
+                affected.push(assignment.activity_id.clone());
+            }
+        }
+        schedule.add_assignment(assignment);
+    }
+
+    let late_before: HashSet<&str> = late_tasks(original, tasks).into_iter().collect();
+    let mut newly_late = Vec::new();
+    for task_id in late_tasks(&schedule, tasks) {
+        if !late_before.contains(task_id) {
+            newly_late.push(task_id.to_string());
+        }
+    }
+
+    for task_id in &newly_late {
+        schedule.add_violation(Violation::deadline_miss(
+            task_id,
+            &format!("Task {} misses its deadline after repair", task_id),
+        ));
+    }
+
+    RepairResult {
+        schedule,
+        affected_activities: affected,
+        newly_late_tasks: newly_late,
+        unassigned_activities: Vec::new(),
+    }
+}
+
+/// Task IDs completing after their deadline
+fn late_tasks<'a>(schedule: &Schedule, tasks: &'a [Task]) -> Vec<&'a str> {
+    tasks
+        .iter()
+        .filter(
+            |task| match (task.deadline, schedule.task_completion_time(&task.id)) {
+                (Some(deadline), Some(completion)) => completion > deadline.timestamp_millis(),
+                _ => false,
+            },
+        )
+        .map(|task| task.id.as_str())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Activity, ActivityDuration, ResourceRequirement};
+    use crate::scheduler::SimpleScheduler;
+
+    fn create_scenario() -> (Vec<Task>, Vec<Resource>) {
+        let deadline = chrono::DateTime::from_timestamp_millis(12_000).unwrap();
+        let tasks = vec![Task::new("T1")
+            .with_priority(5)
+            .with_deadline(deadline)
+            .with_activity(
+                Activity::new("T1-A1", "T1", 1)
+                    .with_duration(ActivityDuration::fixed(5000))
+                    .with_resources("machine", vec!["M1".into(), "M2".into()]),
+            )
+            .with_activity(
+                Activity::new("T1-A2", "T1", 2)
+                    .with_duration(ActivityDuration::fixed(5000))
+                    .with_resources("machine", vec!["M1".into()]),
+            )];
+        let resources = vec![Resource::primary("M1"), Resource::primary("M2")];
+        (tasks, resources)
+    }
+
+    #[test]
+    fn test_right_shift_after_breakdown() {
+        let (tasks, resources) = create_scenario();
+        let schedule = SimpleScheduler::new().schedule(&tasks, &resources, 0);
+        assert_eq!(
+            schedule
+                .assignment_for_activity("T1-A1")
+                .unwrap()
+                .resource_id,
+            "M1"
+        );
+
+        let disruption = Disruption::breakdown("M1", 2000, 3000);
+        let result = DisruptionRepair::new().repair(&schedule, &disruption, &tasks, &resources);
+
+        let a1 = result.schedule.assignment_for_activity("T1-A1").unwrap();
+        let a2 = result.schedule.assignment_for_activity("T1-A2").unwrap();
+        assert_eq!(a1.start_ms, 5000);
+        assert_eq!(a2.start_ms, 10_000);
+        assert_eq!(result.schedule.makespan_ms, 15_000);
+        assert_eq!(result.affected_activities.len(), 2);
+        assert_eq!(result.newly_late_tasks, vec!["T1"]);
+        assert!(!result.schedule.is_valid());
+    }
+
+    #[test]
+    fn test_reassign_after_breakdown() {
+        let (tasks, resources) = create_scenario();
+        let schedule = SimpleScheduler::new().schedule(&tasks, &resources, 0);
+
+        let disruption = Disruption::breakdown("M1", 2000, 3000);
+        let result = DisruptionRepair::new()
+            .with_strategy(RepairStrategy::Reassign)
+            .repair(&schedule, &disruption, &tasks, &resources);
+
+        let a1 = result.schedule.assignment_for_activity("T1-A1").unwrap();
+        assert_eq!(a1.resource_id, "M2");
+        assert_eq!(a1.start_ms, 0);
+        assert!(result.newly_late_tasks.is_empty());
+    }
+
+    fn create_staff_scenario() -> (Vec<Task>, Vec<Resource>) {
+        let tasks = vec![
+            Task::new("T1").with_activity(
+                Activity::new("T1-A1", "T1", 1)
+                    .with_duration(ActivityDuration::fixed(4000))
+                    .with_requirement(
+                        ResourceRequirement::new("nurse")
+                            .with_candidate("W1")
+                            .with_skill("icu"),
+                    ),
+            ),
+            Task::new("T2").with_activity(
+                Activity::new("T2-A1", "T2", 1)
+                    .with_duration(ActivityDuration::fixed(4000))
+                    .with_requirement(
+                        ResourceRequirement::new("nurse")
+                            .with_candidate("W1")
+                            .with_skill("surgery"),
+                    ),
+            ),
+        ];
+        let resources = vec![
+            Resource::human("W1")
+                .with_skill("icu", 0.9)
+                .with_skill("surgery", 0.9),
+            Resource::human("W2").with_skill("icu", 0.5),
+            Resource::human("W3").with_skill("icu", 0.8),
+        ];
+        (tasks, resources)
+    }
+
+    #[test]
+    fn test_absence_substitution() {
+        let (tasks, resources) = create_staff_scenario();
+        let schedule = SimpleScheduler::new().schedule(&tasks, &resources, 0);
+
+        let disruption = Disruption::absence("W1", 0, 24 * 3600 * 1000);
+        let result = DisruptionRepair::new().repair(&schedule, &disruption, &tasks, &resources);
+
+        // ICU work goes to the more skilled substitute
+        let a1 = result.schedule.assignment_for_activity("T1-A1").unwrap();
+        assert_eq!(a1.resource_id, "W3");
+
+        // Nobody else can do surgery
+        assert_eq!(result.unassigned_activities, vec!["T2-A1"]);
+        assert!(result.schedule.assignment_for_activity("T2-A1").is_none());
+        assert_eq!(result.schedule.violations.len(), 1);
+        assert_eq!(
+            result.schedule.violations[0].violation_type,
+            crate::models::schedule::ViolationType::ResourceUnavailable
+        );
+    }
+
+    #[test]
+    fn test_absence_drops_successors() {
+        // T1-A2 loses its only nurse; T1-A3 must not keep a slot that
+        // assumes T1-A2 was done
+        let tasks = vec![Task::new("T1")
+            .with_activity(
+                Activity::new("T1-A1", "T1", 1)
+                    .with_duration(ActivityDuration::fixed(1000))
+                    .with_resources("machine", vec!["M1".into()]),
+            )
+            .with_activity(
+                Activity::new("T1-A2", "T1", 2)
+                    .with_duration(ActivityDuration::fixed(1000))
+                    .with_requirement(
+                        ResourceRequirement::new("nurse")
+                            .with_candidate("W1")
+                            .with_skill("surgery"),
+                    ),
+            )
+            .with_activity(
+                Activity::new("T1-A3", "T1", 3)
+                    .with_duration(ActivityDuration::fixed(1000))
+                    .with_resources("machine", vec!["M1".into()]),
+            )];
+        let resources = vec![
+            Resource::primary("M1"),
+            Resource::human("W1").with_skill("surgery", 0.9),
+            Resource::human("W2").with_skill("icu", 0.5),
+        ];
+        let schedule = SimpleScheduler::new().schedule(&tasks, &resources, 0);
+
+        let disruption = Disruption::absence("W1", 0, 24 * 3600 * 1000);
+        let result = DisruptionRepair::new().repair(&schedule, &disruption, &tasks, &resources);
+
+        assert_eq!(result.unassigned_activities, vec!["T1-A2", "T1-A3"]);
+        assert!(result.schedule.assignment_for_activity("T1-A1").is_some());
+        assert!(result.schedule.assignment_for_activity("T1-A3").is_none());
+        assert_eq!(result.schedule.violations.len(), 2);
+        assert_eq!(
+            result.schedule.violations[1].violation_type,
+            crate::models::schedule::ViolationType::PrecedenceViolation
+        );
+    }
+
+    #[test]
+    fn test_right_shift_after_delay() {
+        let deadline = |ms| chrono::DateTime::from_timestamp_millis(ms).unwrap();
+        let tasks = vec![
+            Task::new("T1")
+                .with_deadline(deadline(6000))
+                .with_activity(Activity::new("A1", "T1", 1))
+                .with_activity(Activity::new("A2", "T1", 2)),
+            Task::new("T2")
+                .with_deadline(deadline(10_000))
+                .with_activity(Activity::new("B1", "T2", 1)),
+            Task::new("T3")
+                .with_deadline(deadline(2500))
+                .with_activity(Activity::new("C1", "T3", 1)),
+        ];
+        let mut schedule = Schedule::new();
+        schedule.add_assignment(Assignment::new("A1", "T1", "M1", 0, 3000));
+        schedule.add_assignment(Assignment::new("A2", "T1", "M2", 3000, 5000));
+        schedule.add_assignment(Assignment::new("B1", "T2", "M1", 3000, 4000));
+        schedule.add_assignment(Assignment::new("C1", "T3", "M2", 0, 2000));
+
+        // M1 stalls for 2 s at 1000: A1 overruns, B1 and (through
+        // precedence) A2 follow, C1 on M2 is untouched
+        let result = right_shift_resource(&schedule, &tasks, "M1", 1000, 2000);
+        let span = |id: &str| {
+            let a = result.schedule.assignment_for_activity(id).unwrap();
+            (a.start_ms, a.end_ms)
+        };
+        assert_eq!(span("A1"), (0, 5000));
+        assert_eq!(span("B1"), (5000, 6000));
+        assert_eq!(span("A2"), (5000, 7000));
+        assert_eq!(span("C1"), (0, 2000));
+        assert_eq!(result.affected_activities, vec!["A2", "B1"]);
+        assert_eq!(result.newly_late_tasks, vec!["T1"]);
+
+        // Everything stalls: C1 overruns its deadline too
+        let result = right_shift(&schedule, &tasks, 1000, 2000);
+        assert_eq!(result.schedule.makespan_ms, 7000);
+        assert_eq!(result.newly_late_tasks, vec!["T1", "T3"]);
+        assert_eq!(result.schedule.violations.len(), 2);
+
+        let unchanged = right_shift(&schedule, &tasks, 1000, 0);
+        assert!(unchanged.affected_activities.is_empty());
+    }
+
+    #[test]
+    fn test_unaffected_schedule() {
+        let (tasks, resources) = create_scenario();
+        let schedule = SimpleScheduler::new().schedule(&tasks, &resources, 0);
+
+        let disruption = Disruption::breakdown("M2", 0, 100_000);
+        let result = DisruptionRepair::new().repair(&schedule, &disruption, &tasks, &resources);
+
+        assert!(result.affected_activities.is_empty());
+        assert_eq!(result.schedule.makespan_ms, schedule.makespan_ms);
+    }
+}
@@ -0,0 +1,194 @@
+//! Revocation - Monte Carlo risk of preemptible resources
+//!
+//! Samples revocations of preemptible resources (spot instances, on-call
+//! rooms) and measures how often the schedule survives.
+
+use crate::models::{Resource, Schedule};
+use crate::parallel::{stream_rng, Parallelism};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+/// Revocation risk of a schedule
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RevocationReport {
+    /// Number of sampled scenarios
+    pub samples: usize,
+    /// Share of scenarios where no task loses work
+    pub completion_probability: f64,
+    /// Average number of interrupted activities per scenario
+    pub expected_lost_activities: f64,
+    /// Share of scenarios each task survives
+    pub task_survival: HashMap<String, f64>,
+}
+
+/// Monte Carlo evaluator for resource revocation
+///
+/// In each scenario every preemptible resource is revoked with its
+/// `revocation_probability`, at a uniformly random time within the
+/// schedule horizon. Activities on a revoked resource that have not
+/// finished by then are lost, and their tasks fail.
+#[derive(Debug, Clone)]
+pub struct RevocationSimulator {
+    /// Number of scenarios
+    pub samples: usize,
+    /// Random seed (reproducible results)
+    pub seed: u64,
+    /// Pool scenarios are sampled on (same results as sequential)
+    pub parallelism: Parallelism,
+}
+
+impl RevocationSimulator {
+    /// Create simulator
+    pub fn new(samples: usize) -> Self {
+        Self {
+            samples,
+            seed: 42,
+            parallelism: Parallelism::sequential(),
+        }
+    }
+
+    /// Set random seed
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Sample scenarios in parallel
+    pub fn with_parallel(self, parallel: bool) -> Self {
+        self.with_parallelism(Parallelism::from_flag(parallel))
+    }
+
+    /// Set the pool scenarios are sampled on
+    pub fn with_parallelism(mut self, parallelism: Parallelism) -> Self {
+        self.parallelism = parallelism;
+        self
+    }
+
+    /// Evaluate revocation risk of a schedule
+    pub fn evaluate(&self, schedule: &Schedule, resources: &[Resource]) -> RevocationReport {
+        let horizon_start = schedule
+            .assignments
+            .iter()
+            .map(|a| a.start_ms)
+            .min()
+            .unwrap_or(0);
+        let horizon_end = schedule.makespan_ms.max(horizon_start + 1);
+        let preemptible: Vec<&Resource> = resources.iter().filter(|r| r.is_preemptible()).collect();
+        let task_ids: HashSet<&str> = schedule
+            .assignments
+            .iter()
+            .map(|a| a.task_id.as_str())
+            .collect();
+
+        // One random stream per scenario: (lost activities, failed tasks)
+        let scenarios = self.parallelism.map_indexed(self.samples, |k| {
+            let mut rng = stream_rng(self.seed, k as u64);
+            let mut revoked: HashMap<&str, i64> = HashMap::new();
+            for r in &preemptible {
+                // The field is public and deserializable, so it may be out of range
+                if rng.gen_bool(r.revocation_probability.clamp(0.0, 1.0)) {
+                    revoked.insert(r.id.as_str(), rng.gen_range(horizon_start..horizon_end));
+                }
+            }
+
+            let mut lost = 0;
+            let mut failed: HashSet<&str> = HashSet::new();
+            for a in &schedule.assignments {
+                if revoked
+                    .get(a.resource_id.as_str())
+                    .is_some_and(|&at| a.end_ms > at)
+                {
+                    lost += 1;
+                    failed.insert(a.task_id.as_str());
+                }
+            }
+            (lost, failed)
+        });
+
+        let mut survived: HashMap<&str, usize> = task_ids.iter().map(|&t| (t, 0)).collect();
+        let mut complete = 0;
+        let mut lost_total = 0;
+        for (lost, failed) in scenarios {
+            lost_total += lost;
+            if failed.is_empty() {
+                complete += 1;
+            }
+            for (task, count) in survived.iter_mut() {
+                if !failed.contains(task) {
+                    *count += 1;
+                }
+            }
+        }
+
+        let samples = self.samples.max(1) as f64;
+        RevocationReport {
+            samples: self.samples,
+            completion_probability: complete as f64 / samples,
+            expected_lost_activities: lost_total as f64 / samples,
+            task_survival: survived
+                .into_iter()
+                .map(|(task, count)| (task.to_string(), count as f64 / samples))
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Assignment;
+
+    fn schedule() -> Schedule {
+        let mut schedule = Schedule::new();
+        schedule.add_assignment(Assignment::new("A1", "T1", "Spot", 0, 1000));
+        schedule.add_assignment(Assignment::new("A2", "T2", "OnDemand", 0, 1000));
+        schedule
+    }
+
+    #[test]
+    fn test_reliable_resources_always_complete() {
+        let resources = vec![Resource::primary("Spot"), Resource::primary("OnDemand")];
+
+        let report = RevocationSimulator::new(100).evaluate(&schedule(), &resources);
+
+        assert_eq!(report.completion_probability, 1.0);
+        assert_eq!(report.expected_lost_activities, 0.0);
+    }
+
+    #[test]
+    fn test_spot_revocation() {
+        let resources = vec![
+            Resource::primary("Spot").with_revocation_risk(0.5),
+            Resource::primary("OnDemand"),
+        ];
+
+        let report = RevocationSimulator::new(2000).evaluate(&schedule(), &resources);
+
+        assert!((report.completion_probability - 0.5).abs() < 0.05);
+        assert_eq!(report.task_survival["T2"], 1.0);
+        assert!(report.task_survival["T1"] < 0.6);
+
+        let parallel = RevocationSimulator::new(2000)
+            .with_parallelism(Parallelism::threads(2))
+            .evaluate(&schedule(), &resources);
+        assert_eq!(
+            parallel.completion_probability,
+            report.completion_probability
+        );
+        assert_eq!(parallel.task_survival, report.task_survival);
+    }
+
+    #[test]
+    fn test_out_of_range_probability() {
+        let mut spot = Resource::primary("Spot");
+        spot.revocation_probability = 1.5;
+        let mut nan = Resource::primary("OnDemand");
+        nan.revocation_probability = f64::NAN;
+
+        let report = RevocationSimulator::new(100).evaluate(&schedule(), &[spot, nan]);
+
+        assert_eq!(report.task_survival["T1"], 0.0);
+        assert_eq!(report.task_survival["T2"], 1.0);
+    }
+}
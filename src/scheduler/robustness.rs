@@ -0,0 +1,432 @@
+//! Robustness - Slack and expected delay of a schedule
+//!
+//! Measures how much a schedule can absorb duration deviations: free
+//! slack per assignment, the tightest link of the critical chain, and the
+//! expected makespan delay when durations follow their declared
+//! `DurationDistribution`.
+
+use crate::models::{Activity, Schedule, Task};
+use crate::parallel::{stream_rng, Parallelism};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Robustness of a schedule
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RobustnessReport {
+    /// Sum of free slack over all scheduled activities
+    pub total_free_slack_ms: i64,
+    /// Smallest gap between consecutive activities of the critical chain
+    pub min_critical_slack_ms: i64,
+    /// Activities on the chain ending at the makespan (in order)
+    pub critical_chain: Vec<String>,
+    /// Expected makespan when durations vary
+    pub expected_makespan_ms: f64,
+    /// Expected makespan increase over the plan
+    pub expected_delay_ms: f64,
+}
+
+/// Timing freedom of one scheduled activity
+///
+/// Earliest and latest starts keep the planned sequence on every
+/// resource and in every task; the latest start still finishes by the
+/// makespan.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ActivitySlack {
+    /// Activity id
+    pub activity_id: String,
+    /// Planned start
+    pub start_ms: i64,
+    /// Earliest start if everything before it moved left
+    pub earliest_start_ms: i64,
+    /// Latest start without delaying the makespan
+    pub latest_start_ms: i64,
+    /// Delay absorbable without delaying the makespan
+    pub total_slack_ms: i64,
+    /// Delay absorbable without moving any other activity
+    pub free_slack_ms: i64,
+    /// No total slack
+    pub is_critical: bool,
+}
+
+/// Slack of every scheduled activity, in planned start order
+///
+/// Earliest starts are bounded below by the task release time, else the
+/// first planned start.
+pub fn activity_slack(schedule: &Schedule, tasks: &[Task]) -> Vec<ActivitySlack> {
+    let plan = Plan::build(schedule, tasks);
+    let horizon_start = plan.nodes.iter().map(|n| n.start_ms).min().unwrap_or(0);
+    let release: HashMap<&str, i64> = tasks
+        .iter()
+        .filter_map(|t| {
+            t.release_time
+                .map(|r| (t.id.as_str(), r.timestamp_millis()))
+        })
+        .collect();
+    let n = plan.nodes.len();
+
+    let mut earliest_end = vec![0i64; n];
+    let mut earliest = vec![0i64; n];
+    for (i, node) in plan.nodes.iter().enumerate() {
+        let floor = release.get(node.task_id).copied().unwrap_or(horizon_start);
+        earliest[i] = plan.predecessors[i]
+            .iter()
+            .map(|&p| earliest_end[p])
+            .fold(floor, i64::max);
+        earliest_end[i] = earliest[i] + node.end_ms - node.start_ms;
+    }
+
+    let mut latest = vec![0i64; n];
+    let mut latest_finish = vec![schedule.makespan_ms; n];
+    for i in (0..n).rev() {
+        let node = &plan.nodes[i];
+        latest[i] = latest_finish[i] - (node.end_ms - node.start_ms);
+        for &p in &plan.predecessors[i] {
+            latest_finish[p] = latest_finish[p].min(latest[i]);
+        }
+    }
+
+    let free = plan.free_slack(schedule.makespan_ms);
+    plan.nodes
+        .iter()
+        .enumerate()
+        .map(|(i, node)| ActivitySlack {
+            activity_id: node.activity_id.to_string(),
+            start_ms: node.start_ms,
+            earliest_start_ms: earliest[i],
+            latest_start_ms: latest[i],
+            total_slack_ms: (latest[i] - node.start_ms).max(0),
+            free_slack_ms: free[i],
+            is_critical: latest[i] <= node.start_ms,
+        })
+        .collect()
+}
+
+/// Robustness evaluator
+///
+/// Free slack is how long an activity can overrun without delaying the
+/// next activity on its resources or in its task (or the makespan for
+/// the last ones). Expected delay replays the schedule in sampled
+/// scenarios, keeping the planned order and never starting early.
+#[derive(Debug, Clone)]
+pub struct RobustnessAnalyzer {
+    /// Number of sampled scenarios
+    pub samples: usize,
+    /// Random seed (reproducible results)
+    pub seed: u64,
+    /// Pool scenarios are replayed on (same results as sequential)
+    pub parallelism: Parallelism,
+}
+
+impl RobustnessAnalyzer {
+    /// Create analyzer
+    pub fn new(samples: usize) -> Self {
+        Self {
+            samples,
+            seed: 42,
+            parallelism: Parallelism::sequential(),
+        }
+    }
+
+    /// Set random seed
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Replay scenarios in parallel
+    pub fn with_parallel(self, parallel: bool) -> Self {
+        self.with_parallelism(Parallelism::from_flag(parallel))
+    }
+
+    /// Set the pool scenarios are replayed on
+    pub fn with_parallelism(mut self, parallelism: Parallelism) -> Self {
+        self.parallelism = parallelism;
+        self
+    }
+
+    /// Evaluate a schedule
+    pub fn evaluate(&self, schedule: &Schedule, tasks: &[Task]) -> RobustnessReport {
+        let plan = Plan::build(schedule, tasks);
+        if plan.nodes.is_empty() {
+            return RobustnessReport::default();
+        }
+
+        let total_free_slack_ms = plan.free_slack(schedule.makespan_ms).iter().sum();
+
+        // Walk back from the last activity along the latest-ending predecessor
+        let mut chain = Vec::new();
+        let mut min_gap: Option<i64> = None;
+        let mut current = (0..plan.nodes.len()).max_by_key(|&i| (plan.nodes[i].end_ms, i));
+        while let Some(i) = current {
+            chain.push(plan.nodes[i].activity_id.to_string());
+            current = plan.predecessors[i]
+                .iter()
+                .copied()
+                .max_by_key(|&p| (plan.nodes[p].end_ms, p));
+            if let Some(p) = current {
+                let gap = plan.nodes[i].start_ms - plan.nodes[p].end_ms;
+                min_gap = Some(min_gap.map_or(gap, |g| g.min(gap)));
+            }
+        }
+        chain.reverse();
+
+        // One random stream per scenario, summed in scenario order
+        let makespans = self.parallelism.map_indexed(self.samples, |k| {
+            let mut rng = stream_rng(self.seed, k as u64);
+            plan.replay(|activity, planned_ms| match activity {
+                Some(a) => match &a.duration_distribution {
+                    Some(distribution) if a.duration.process_ms > 0 => {
+                        let ratio =
+                            distribution.sample(&mut rng) as f64 / a.duration.process_ms as f64;
+                        (planned_ms as f64 * ratio).round() as i64
+                    }
+                    _ => planned_ms,
+                },
+                None => planned_ms,
+            })
+        });
+        let total_makespan: f64 = makespans.iter().map(|&m| m as f64).sum();
+        let expected_makespan_ms = if self.samples > 0 {
+            total_makespan / self.samples as f64
+        } else {
+            schedule.makespan_ms as f64
+        };
+
+        RobustnessReport {
+            total_free_slack_ms,
+            min_critical_slack_ms: min_gap.unwrap_or(0).max(0),
+            critical_chain: chain,
+            expected_makespan_ms,
+            expected_delay_ms: (expected_makespan_ms - schedule.makespan_ms as f64).max(0.0),
+        }
+    }
+}
+
+impl Default for RobustnessAnalyzer {
+    fn default() -> Self {
+        Self::new(200)
+    }
+}
+
+/// Scheduled activity (all its assignments)
+struct Node<'a> {
+    activity_id: &'a str,
+    task_id: &'a str,
+    activity: Option<&'a Activity>,
+    resources: Vec<&'a str>,
+    start_ms: i64,
+    end_ms: i64,
+    setup_ms: i64,
+}
+
+/// Scheduled activities with their resource and task predecessors
+struct Plan<'a> {
+    /// Nodes in planned start order
+    nodes: Vec<Node<'a>>,
+    /// Predecessor indices
+    predecessors: Vec<Vec<usize>>,
+}
+
+impl<'a> Plan<'a> {
+    fn build(schedule: &'a Schedule, tasks: &'a [Task]) -> Self {
+        let activities: HashMap<&str, &Activity> = tasks
+            .iter()
+            .flat_map(|t| t.activities.iter())
+            .map(|a| (a.id.as_str(), a))
+            .collect();
+
+        let mut nodes: Vec<Node> = Vec::new();
+        let mut index: HashMap<&str, usize> = HashMap::new();
+        let mut task_of: Vec<&str> = Vec::new();
+        for a in &schedule.assignments {
+            match index.get(a.activity_id.as_str()) {
+                Some(&i) => {
+                    let node = &mut nodes[i];
+                    node.resources.push(&a.resource_id);
+                    node.start_ms = node.start_ms.min(a.start_ms);
+                    node.end_ms = node.end_ms.max(a.end_ms);
+                }
+                None => {
+                    index.insert(&a.activity_id, nodes.len());
+                    task_of.push(&a.task_id);
+                    nodes.push(Node {
+                        activity_id: &a.activity_id,
+                        task_id: &a.task_id,
+                        activity: activities.get(a.activity_id.as_str()).copied(),
+                        resources: vec![&a.resource_id],
+                        start_ms: a.start_ms,
+                        end_ms: a.end_ms,
+                        setup_ms: a.setup_ms,
+                    });
+                }
+            }
+        }
+
+        let mut order: Vec<usize> = (0..nodes.len()).collect();
+        order.sort_by_key(|&i| (nodes[i].start_ms, nodes[i].end_ms, i));
+        let mut position = vec![0; nodes.len()];
+        for (p, &i) in order.iter().enumerate() {
+            position[i] = p;
+        }
+
+        let mut predecessors: Vec<Vec<usize>> = vec![Vec::new(); nodes.len()];
+        let mut last_on_resource: HashMap<&str, usize> = HashMap::new();
+        let mut last_of_task: HashMap<&str, usize> = HashMap::new();
+        for &i in &order {
+            let explicit = nodes[i].activity.filter(|a| !a.predecessors.is_empty());
+            match explicit {
+                Some(a) => predecessors[i].extend(
+                    a.predecessors
+                        .iter()
+                        .filter_map(|p| index.get(p.as_str()).copied())
+                        .filter(|&p| position[p] < position[i]),
+                ),
+                None => predecessors[i].extend(last_of_task.get(task_of[i]).copied()),
+            }
+            for r in &nodes[i].resources {
+                if let Some(&p) = last_on_resource.get(r) {
+                    predecessors[i].push(p);
+                }
+                last_on_resource.insert(r, i);
+            }
+            predecessors[i].sort_unstable();
+            predecessors[i].dedup();
+            last_of_task.insert(task_of[i], i);
+        }
+
+        let mut sorted: Vec<Option<Node>> = nodes.into_iter().map(Some).collect();
+        let nodes: Vec<Node> = order.iter().map(|&i| sorted[i].take().unwrap()).collect();
+        let predecessors = order
+            .iter()
+            .map(|&i| predecessors[i].iter().map(|&p| position[p]).collect())
+            .collect();
+
+        Self {
+            nodes,
+            predecessors,
+        }
+    }
+
+    /// Free slack up to the earliest successor (or the makespan)
+    fn free_slack(&self, makespan_ms: i64) -> Vec<i64> {
+        let mut earliest_next = vec![makespan_ms; self.nodes.len()];
+        for (j, preds) in self.predecessors.iter().enumerate() {
+            for &i in preds {
+                earliest_next[i] = earliest_next[i].min(self.nodes[j].start_ms);
+            }
+        }
+        self.nodes
+            .iter()
+            .zip(&earliest_next)
+            .map(|(n, &next)| (next - n.end_ms).max(0))
+            .collect()
+    }
+
+    /// Makespan when each activity takes `duration(activity, planned)`
+    ///
+    /// Setup time is kept as planned; only the remainder is scaled.
+    fn replay(&self, mut duration: impl FnMut(Option<&Activity>, i64) -> i64) -> i64 {
+        let mut end = vec![0i64; self.nodes.len()];
+        for (i, node) in self.nodes.iter().enumerate() {
+            let start = self.predecessors[i]
+                .iter()
+                .map(|&p| end[p])
+                .fold(node.start_ms, i64::max);
+            let work = node.end_ms - node.start_ms - node.setup_ms;
+            end[i] = start + node.setup_ms + duration(node.activity, work);
+        }
+        end.into_iter().max().unwrap_or(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{ActivityDuration, Assignment, DurationDistribution};
+
+    fn step(id: &str, task: &str, seq: i32, ms: i64) -> Activity {
+        Activity::new(id, task, seq).with_duration(ActivityDuration::fixed(ms))
+    }
+
+    #[test]
+    fn test_slack_and_critical_chain() {
+        let tasks = vec![
+            Task::new("T1")
+                .with_activity(step("A1", "T1", 1, 1000))
+                .with_activity(step("A2", "T1", 2, 1000)),
+            Task::new("T2").with_activity(step("B1", "T2", 1, 1000)),
+        ];
+        let mut schedule = Schedule::new();
+        schedule.add_assignment(Assignment::new("A1", "T1", "M1", 0, 1000));
+        schedule.add_assignment(Assignment::new("A2", "T1", "M2", 1500, 2500));
+        schedule.add_assignment(Assignment::new("B1", "T2", "M1", 1000, 1800));
+
+        let report = RobustnessAnalyzer::new(10).evaluate(&schedule, &tasks);
+
+        // B1 follows A1 on M1 without a gap; only B1 may overrun (until
+        // the makespan)
+        assert_eq!(report.total_free_slack_ms, 700);
+        assert_eq!(report.critical_chain, vec!["A1", "A2"]);
+        assert_eq!(report.min_critical_slack_ms, 500);
+        // Deterministic durations: no delay expected
+        assert_eq!(report.expected_delay_ms, 0.0);
+    }
+
+    #[test]
+    fn test_activity_slack() {
+        let tasks = vec![
+            Task::new("T1")
+                .with_activity(step("A1", "T1", 1, 1000))
+                .with_activity(step("A2", "T1", 2, 1000)),
+            Task::new("T2").with_activity(step("B1", "T2", 1, 1000)),
+        ];
+        let mut schedule = Schedule::new();
+        schedule.add_assignment(Assignment::new("A1", "T1", "M1", 0, 1000));
+        schedule.add_assignment(Assignment::new("A2", "T1", "M2", 1500, 2500));
+        schedule.add_assignment(Assignment::new("B1", "T2", "M1", 1000, 1800));
+
+        let slack = activity_slack(&schedule, &tasks);
+        let of = |id: &str| slack.iter().find(|s| s.activity_id == id).unwrap();
+
+        // A2 could start right after A1, or wait until the makespan allows
+        assert_eq!(of("A2").earliest_start_ms, 1000);
+        assert_eq!(of("A2").latest_start_ms, 1500);
+        assert!(of("A2").is_critical);
+        // A1 can slip 500 ms before it pushes A2
+        assert_eq!(of("A1").total_slack_ms, 500);
+        assert_eq!(of("A1").free_slack_ms, 0);
+        assert!(!of("A1").is_critical);
+        assert_eq!(of("B1").total_slack_ms, 700);
+        assert_eq!(of("B1").free_slack_ms, 700);
+    }
+
+    #[test]
+    fn test_expected_delay() {
+        let uncertain = DurationDistribution::Uniform {
+            min_ms: 1000,
+            max_ms: 3000,
+        };
+        let tasks = vec![Task::new("T1")
+            .with_activity(step("A1", "T1", 1, 1000).with_duration_distribution(uncertain))
+            .with_activity(step("A2", "T1", 2, 1000))];
+        let mut tight = Schedule::new();
+        tight.add_assignment(Assignment::new("A1", "T1", "M1", 0, 1000));
+        tight.add_assignment(Assignment::new("A2", "T1", "M1", 1000, 2000));
+
+        let report = RobustnessAnalyzer::new(2000).evaluate(&tight, &tasks);
+        assert!((report.expected_delay_ms - 1000.0).abs() < 50.0);
+
+        // Same seed, same estimate on any number of threads
+        let parallel = RobustnessAnalyzer::new(2000)
+            .with_parallel(true)
+            .evaluate(&tight, &tasks);
+        assert_eq!(parallel.expected_makespan_ms, report.expected_makespan_ms);
+
+        // A buffer before A2 absorbs most of the overrun
+        let mut buffered = Schedule::new();
+        buffered.add_assignment(Assignment::new("A1", "T1", "M1", 0, 1000));
+        buffered.add_assignment(Assignment::new("A2", "T1", "M1", 2000, 3000));
+        let report = RobustnessAnalyzer::new(2000).evaluate(&buffered, &tasks);
+        assert!(report.expected_delay_ms < 300.0);
+    }
+}
@@ -0,0 +1,300 @@
+//! Roster - Shift rostering
+//!
+//! Staffs fixed shift slots with people: each slot needs a number of
+//! staff with given skills; staff bring skills, availability calendars,
+//! working-time rules and shift preferences.
+
+use crate::models::schedule::Violation;
+use crate::models::{Assignment, PreferenceReport, Resource, Schedule};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Shift slot requiring coverage
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShiftSlot {
+    /// Unique identifier
+    pub id: String,
+    /// Shift start (epoch ms)
+    pub start_ms: i64,
+    /// Shift end (epoch ms)
+    pub end_ms: i64,
+    /// Number of staff needed
+    pub required_staff: u32,
+    /// Skills every assigned person must have
+    pub required_skills: Vec<String>,
+}
+
+impl ShiftSlot {
+    /// Create slot needing one person
+    pub fn new(id: &str, start_ms: i64, end_ms: i64) -> Self {
+        Self {
+            id: id.to_string(),
+            start_ms,
+            end_ms,
+            required_staff: 1,
+            required_skills: Vec::new(),
+        }
+    }
+
+    /// Set number of staff needed
+    pub fn with_staff(mut self, count: u32) -> Self {
+        self.required_staff = count;
+        self
+    }
+
+    /// Add required skill
+    pub fn with_skill(mut self, skill: &str) -> Self {
+        self.required_skills.push(skill.to_string());
+        self
+    }
+
+    /// Shift duration (ms)
+    pub fn duration_ms(&self) -> i64 {
+        self.end_ms - self.start_ms
+    }
+}
+
+/// Roster coverage metrics
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RosterKpi {
+    /// Positions requested across all slots
+    pub required_positions: u32,
+    /// Positions filled
+    pub filled_positions: u32,
+    /// Filled / required (1.0 when nothing is required)
+    pub coverage_rate: f64,
+    /// Slots with at least one open position
+    pub understaffed_slots: Vec<String>,
+    /// Number of violations in the roster
+    pub violation_count: usize,
+    /// Spread between most and least loaded staff (ms)
+    pub workload_deviation_ms: i64,
+    /// Shift preference satisfaction (0.0 to 1.0)
+    pub preference_satisfaction: f64,
+}
+
+/// Rostering result
+#[derive(Debug, Clone)]
+pub struct RosterResult {
+    /// Roster as a schedule (one assignment per filled position)
+    pub schedule: Schedule,
+    /// Coverage metrics
+    pub kpi: RosterKpi,
+}
+
+/// Greedy shift rostering
+///
+/// Slots are filled in start order, hardest-to-staff first on ties.
+/// Each position goes to an eligible person (skills, calendar, no
+/// overlap, working-time rules) preferring higher shift preference,
+/// then lower workload so far.
+#[derive(Debug, Clone, Default)]
+pub struct RosterScheduler {
+    /// Ignore workload when choosing among eligible staff
+    pub ignore_fairness: bool,
+}
+
+impl RosterScheduler {
+    /// Create roster scheduler
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Disable workload balancing between eligible staff
+    pub fn without_fairness(mut self) -> Self {
+        self.ignore_fairness = true;
+        self
+    }
+
+    /// Build a roster
+    pub fn roster(&self, slots: &[ShiftSlot], staff: &[Resource]) -> RosterResult {
+        let mut schedule = Schedule::new();
+        let mut workload: HashMap<&str, i64> = HashMap::new();
+
+        let eligible_count = |slot: &ShiftSlot| {
+            staff
+                .iter()
+                .filter(|s| slot.required_skills.iter().all(|k| s.has_skill(k)))
+                .count()
+        };
+        let mut order: Vec<&ShiftSlot> = slots.iter().collect();
+        order.sort_by_key(|s| (s.start_ms, eligible_count(s), s.id.clone()));
+
+        let mut kpi = RosterKpi::default();
+        for slot in order {
+            kpi.required_positions += slot.required_staff;
+
+            for position in 0..slot.required_staff {
+                let chosen = staff
+                    .iter()
+                    .filter(|s| self.is_eligible(s, slot, &schedule))
+                    .min_by(|a, b| {
+                        let pa = a.shift_preference(slot.start_ms, slot.end_ms);
+                        let pb = b.shift_preference(slot.start_ms, slot.end_ms);
+                        let load = |r: &Resource| {
+                            if self.ignore_fairness {
+                                0
+                            } else {
+                                workload.get(r.id.as_str()).copied().unwrap_or(0)
+                            }
+                        };
+                        pb.partial_cmp(&pa)
+                            .unwrap_or(std::cmp::Ordering::Equal)
+                            .then_with(|| load(a).cmp(&load(b)))
+                            .then_with(|| a.id.cmp(&b.id))
+                    });
+
+                match chosen {
+                    Some(person) => {
+                        schedule.add_assignment(Assignment::new(
+                            &format!("{}#{}", slot.id, position + 1),
+                            &slot.id,
+                            &person.id,
+                            slot.start_ms,
+                            slot.end_ms,
+                        ));
+                        *workload.entry(person.id.as_str()).or_insert(0) += slot.duration_ms();
+                        kpi.filled_positions += 1;
+                    }
+                    None => {
+                        let open = slot.required_staff - position;
+                        schedule.add_violation(Violation::resource_unavailable(
+                            &slot.id,
+                            &format!("Slot {} is short by {} staff", slot.id, open),
+                        ));
+                        kpi.understaffed_slots.push(slot.id.clone());
+                        break;
+                    }
+                }
+            }
+        }
+
+        let staff_ids: Vec<String> = staff.iter().map(|s| s.id.clone()).collect();
+        kpi.coverage_rate = if kpi.required_positions == 0 {
+            1.0
+        } else {
+            kpi.filled_positions as f64 / kpi.required_positions as f64
+        };
+        kpi.violation_count = schedule.violations.len();
+        kpi.workload_deviation_ms = schedule.workload_deviation_ms(&staff_ids);
+        kpi.preference_satisfaction =
+            PreferenceReport::evaluate(&schedule, &[], staff).satisfaction_rate();
+
+        RosterResult { schedule, kpi }
+    }
+
+    /// Check if a person can take a position in a slot
+    fn is_eligible(&self, person: &Resource, slot: &ShiftSlot, schedule: &Schedule) -> bool {
+        if !slot.required_skills.iter().all(|k| person.has_skill(k)) {
+            return false;
+        }
+        if !person.is_available_between(slot.start_ms, slot.end_ms) {
+            return false;
+        }
+
+        let booked = schedule.assignments_for_resource(&person.id);
+        if booked
+            .iter()
+            .any(|a| a.task_id == slot.id || (a.start_ms < slot.end_ms && a.end_ms > slot.start_ms))
+        {
+            return false;
+        }
+
+        person
+            .working_time
+            .as_ref()
+            .is_none_or(|rules| rules.allows(&booked, slot.start_ms, slot.end_ms))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Calendar, WorkingTimeRules};
+
+    const HOUR: i64 = 3_600_000;
+
+    fn week_of_day_shifts() -> Vec<ShiftSlot> {
+        (0..5)
+            .map(|d| {
+                ShiftSlot::new(
+                    &format!("D{}", d),
+                    d * 24 * HOUR + 8 * HOUR,
+                    d * 24 * HOUR + 16 * HOUR,
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_full_coverage_is_balanced() {
+        let staff = vec![Resource::human("N1"), Resource::human("N2")];
+
+        let result = RosterScheduler::new().roster(&week_of_day_shifts(), &staff);
+
+        assert_eq!(result.kpi.coverage_rate, 1.0);
+        assert!(result.kpi.understaffed_slots.is_empty());
+        assert!(result.kpi.workload_deviation_ms <= 8 * HOUR);
+    }
+
+    #[test]
+    fn test_skills_and_understaffing() {
+        let slots = vec![ShiftSlot::new("ICU", 0, 8 * HOUR)
+            .with_staff(2)
+            .with_skill("icu")];
+        let staff = vec![
+            Resource::human("N1").with_skill("icu", 0.9),
+            Resource::human("N2"),
+        ];
+
+        let result = RosterScheduler::new().roster(&slots, &staff);
+
+        assert_eq!(result.kpi.filled_positions, 1);
+        assert_eq!(result.kpi.coverage_rate, 0.5);
+        assert_eq!(result.kpi.understaffed_slots, vec!["ICU".to_string()]);
+        assert_eq!(result.kpi.violation_count, 1);
+        assert_eq!(result.schedule.assignments[0].resource_id, "N1");
+    }
+
+    #[test]
+    fn test_contract_rules_and_availability() {
+        let staff = vec![
+            Resource::human("N1")
+                .with_working_time_rules(WorkingTimeRules::new().with_max_consecutive_days(2)),
+            // N2 only works the first three days
+            Resource::human("N2").with_calendar(Calendar::new("part").with_window(0, 72 * HOUR)),
+        ];
+
+        let result = RosterScheduler::new()
+            .without_fairness()
+            .roster(&week_of_day_shifts(), &staff);
+
+        assert_eq!(result.kpi.coverage_rate, 1.0);
+        for a in &result.schedule.assignments {
+            if a.resource_id == "N2" {
+                assert!(a.end_ms <= 72 * HOUR);
+            }
+        }
+        let n1 = result.schedule.assignments_for_resource("N1");
+        assert!(staff[0]
+            .working_time
+            .as_ref()
+            .unwrap()
+            .violations("N1", &n1)
+            .is_empty());
+    }
+
+    #[test]
+    fn test_shift_preference() {
+        let slots = vec![ShiftSlot::new("Night", 20 * HOUR, 28 * HOUR)];
+        let staff = vec![
+            Resource::human("N1"),
+            Resource::human("N2").with_preferred_shift(20 * HOUR, 28 * HOUR, 1.0),
+        ];
+
+        let result = RosterScheduler::new().roster(&slots, &staff);
+
+        assert_eq!(result.schedule.assignments[0].resource_id, "N2");
+        assert_eq!(result.kpi.preference_satisfaction, 1.0);
+    }
+}
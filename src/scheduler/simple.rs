@@ -2,21 +2,87 @@
 //!
 //! Fast heuristic scheduler for baseline solutions
 
-use crate::models::{Assignment, Resource, Schedule, Task, TransitionMatrixCollection};
+use super::deadline::{propagate_deadlines, LatestTimes};
+use super::jit::JustInTime;
+use crate::models::schedule::Violation;
+use crate::models::{
+    Activity, Assignment, Baseline, BaselineState, Calendar, CandidateOption, CandidateOutcome,
+    CandidateRecord, Compatibility, Constraint, Decision, DecisionTrace, Downtime, EnergyTariff,
+    Malleability, OverlapAllowance, PlacementExplanation, Resource, Schedule, SlotGrid,
+    StartDriver, Task, TransitionMatrixCollection, TravelTimeMatrix, WorkingTimeRules,
+};
+use crate::telemetry::{self, Stopwatch};
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+/// Rounds of raising a gang's common start before giving up
+const GANG_START_ROUNDS: usize = 64;
+
 /// Simple priority-based scheduler
+///
+/// Tasks are taken in priority order; each activity goes to the candidate
+/// resource where it finishes earliest, counting setup and resource
+/// efficiency, and may be inserted into an idle gap when nothing else
+/// (travel, slots, working-time rules, energy shifting, handoffs) moves
+/// its start afterwards. Gang activities (requirements with quantity > 1)
+/// take the earliest-available distinct candidates and start together;
+/// malleable activities additionally pick the number of units that
+/// finishes earliest. Setups on a resource with a setup crew wait for
+/// the earliest free crew member, who is booked for the setup time.
+#[derive(Clone)]
 pub struct SimpleScheduler {
     /// Transition matrices for setup times
     transition_matrices: TransitionMatrixCollection,
+    /// Energy tariff and maximum delay allowed to chase cheaper prices
+    energy: Option<(EnergyTariff, i64)>,
+    /// Break start-time ties toward the least loaded resource
+    balance_workload: bool,
+    /// Maximum delay accepted to honor a fully weighted preference
+    preference_delay_ms: Option<i64>,
+    /// Travel times between locations
+    travel_times: Option<TravelTimeMatrix>,
+    /// Honored constraints (time windows, pickup-delivery pairs, handoffs)
+    constraints: Vec<Constraint>,
+    /// Slot grid activities snap to
+    slots: Option<SlotGrid>,
+    /// Earliest time each listed resource becomes usable
+    release_times: HashMap<String, i64>,
+    /// Maximum delay accepted to keep the most critical work off a
+    /// certainly revoked resource
+    reliability_delay_ms: Option<i64>,
+    /// Stretch activities over resource calendars
+    respect_calendars: bool,
+    /// Confidence level for planned durations
+    confidence: Option<f64>,
+    /// Confidence level per task category (overrides `confidence`)
+    category_confidence: HashMap<String, f64>,
+    /// Avoid candidates that start past the activity's latest start
+    deadline_pruning: bool,
+    /// Record why each activity was placed where it is
+    explain: bool,
+    /// Record every placement decision in a trace
+    trace: bool,
+    /// Existing plan the run continues from
+    baseline: Option<Baseline>,
+    /// Weight of start shifts and penalty of resource changes against
+    /// the baseline plan
+    stability: Option<(f64, i64)>,
+    /// Deadline buffer of the just-in-time pass, if enabled
+    just_in_time_ms: Option<i64>,
 }
 
 /// Request for scheduling
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct ScheduleRequest {
     pub tasks: Vec<Task>,
     pub resources: Vec<Resource>,
     pub start_time_ms: i64,
+    #[serde(default)]
     pub transition_matrices: TransitionMatrixCollection,
+    /// Attribute-matching eligibility rules
+    #[serde(default)]
+    pub compatibility: Compatibility,
 }
 
 impl ScheduleRequest {
@@ -26,6 +92,7 @@ impl ScheduleRequest {
             resources,
             start_time_ms: 0,
             transition_matrices: TransitionMatrixCollection::new(),
+            compatibility: Compatibility::new(),
         }
     }
 
@@ -38,6 +105,16 @@ impl ScheduleRequest {
         self.transition_matrices = matrices;
         self
     }
+
+    pub fn with_compatibility(mut self, compatibility: Compatibility) -> Self {
+        self.compatibility = compatibility;
+        self
+    }
+
+    /// Tasks with candidates narrowed by the compatibility rules
+    pub fn eligible_tasks(&self) -> Vec<Task> {
+        self.compatibility.apply(&self.tasks, &self.resources)
+    }
 }
 
 impl SimpleScheduler {
@@ -45,6 +122,23 @@ impl SimpleScheduler {
     pub fn new() -> Self {
         Self {
             transition_matrices: TransitionMatrixCollection::new(),
+            energy: None,
+            balance_workload: false,
+            preference_delay_ms: None,
+            travel_times: None,
+            constraints: Vec::new(),
+            slots: None,
+            release_times: HashMap::new(),
+            reliability_delay_ms: None,
+            respect_calendars: false,
+            confidence: None,
+            category_confidence: HashMap::new(),
+            deadline_pruning: false,
+            explain: false,
+            trace: false,
+            baseline: None,
+            stability: None,
+            just_in_time_ms: None,
         }
     }
 
@@ -54,188 +148,3706 @@ impl SimpleScheduler {
         self
     }
 
+    /// Shift energy-consuming activities into cheaper tariff periods
+    ///
+    /// An activity may be delayed by up to `max_delay_ms` past its earliest
+    /// feasible start if that lowers its energy cost.
+    pub fn with_energy_tariff(mut self, tariff: EnergyTariff, max_delay_ms: i64) -> Self {
+        self.energy = Some((tariff, max_delay_ms));
+        self
+    }
+
+    /// Spread work evenly when several resources can start at the same time
+    ///
+    /// Among candidates with equal earliest start, the one with the least
+    /// assigned time so far wins instead of the first listed.
+    pub fn with_workload_balancing(mut self, enabled: bool) -> Self {
+        self.balance_workload = enabled;
+        self
+    }
+
+    /// Honor soft preferences (preferred resources and shifts)
+    ///
+    /// A candidate with total preference weight `w` competes as if it
+    /// started `w * max_delay_ms` earlier than it actually does.
+    pub fn with_preferences(mut self, max_delay_ms: i64) -> Self {
+        self.preference_delay_ms = Some(max_delay_ms);
+        self
+    }
+
+    /// Insert travel time between activities at different locations
+    ///
+    /// Applies both to consecutive activities of a task and to
+    /// consecutive activities of a (mobile) resource.
+    pub fn with_travel_times(mut self, matrix: TravelTimeMatrix) -> Self {
+        self.travel_times = Some(matrix);
+        self
+    }
+
+    /// Honor constraints while scheduling
+    ///
+    /// Supports `TimeWindow` (start no earlier than the window; a
+    /// violation is recorded if the activity ends after it),
+    /// `PickupDelivery` (delivery on the pickup's resource, after it) and
+    /// `Handoff` (the handing-over activity is delayed to meet the
+    /// receiver when possible, otherwise a violation is recorded),
+    /// `Overlap` (the successor starts as soon as its allowance permits,
+    /// but finishes no earlier than its predecessor) and `MaxDelay` (the
+    /// predecessor is delayed like a handoff when the successor has to
+    /// wait too long).
+    pub fn with_constraints(mut self, constraints: Vec<Constraint>) -> Self {
+        self.constraints = constraints;
+        self
+    }
+
+    /// Snap activities to a slot grid (appointments, class periods)
+    ///
+    /// Activities start on a slot boundary and occupy whole slots; an
+    /// activity that fits no remaining slot is left unscheduled with a
+    /// violation.
+    pub fn with_slots(mut self, grid: SlotGrid) -> Self {
+        self.slots = Some(grid);
+        self
+    }
+
+    /// Prefer reliable resources for critical work
+    ///
+    /// A candidate with revocation probability `p` competes as if it
+    /// started `p * criticality * max_delay_ms` later, where criticality
+    /// is the task priority relative to the highest priority.
+    pub fn with_reliability_preference(mut self, max_delay_ms: i64) -> Self {
+        self.reliability_delay_ms = Some(max_delay_ms);
+        self
+    }
+
+    /// Run activities only in their resource's calendar working time
+    ///
+    /// Activities start at the next working time and are stretched over
+    /// breaks, holidays and closures; an activity the calendar cannot
+    /// fit is not placed on that resource.
+    pub fn with_calendars(mut self, enabled: bool) -> Self {
+        self.respect_calendars = enabled;
+        self
+    }
+
+    /// Plan with buffered durations at a confidence level (0.85 for P85)
+    ///
+    /// Activities with a duration distribution take its duration at that
+    /// confidence instead of their nominal process time; the level is
+    /// recorded in `Schedule::planned_confidence`.
+    pub fn with_confidence(mut self, confidence: f64) -> Self {
+        self.confidence = Some(confidence.clamp(0.0, 1.0));
+        self
+    }
+
+    /// Plan tasks of a category at their own confidence level
+    pub fn with_category_confidence(mut self, category: &str, confidence: f64) -> Self {
+        self.category_confidence
+            .insert(category.to_string(), confidence.clamp(0.0, 1.0));
+        self
+    }
+
+    /// Make a resource usable no earlier than `time_ms` (e.g., startup delay)
+    pub fn with_release_time(mut self, resource_id: &str, time_ms: i64) -> Self {
+        self.release_times.insert(resource_id.to_string(), time_ms);
+        self
+    }
+
+    /// Prune placements that certainly miss a deadline
+    ///
+    /// Deadlines are propagated to latest starts (see
+    /// [`propagate_deadlines`]); a candidate starting later loses to any
+    /// candidate that does not. An activity placed late anyway records a
+    /// deadline-miss violation right away.
+    pub fn with_deadline_pruning(mut self, enabled: bool) -> Self {
+        self.deadline_pruning = enabled;
+        self
+    }
+
+    /// Record placement explanations (see [`Schedule::explain`])
+    ///
+    /// Each explanation names what determined the start (task
+    /// predecessor, a constraint, the resource becoming free or a later
+    /// adjustment), lists every candidate with its earliest finish, and
+    /// the setup incurred.
+    pub fn with_explanations(mut self, enabled: bool) -> Self {
+        self.explain = enabled;
+        self
+    }
+
+    /// Record a decision trace (see [`Schedule::trace`])
+    ///
+    /// Every placement decision is logged in order with all candidates,
+    /// their scores, the chosen resource and why the others lost.
+    pub fn with_trace(mut self, enabled: bool) -> Self {
+        self.trace = enabled;
+        self
+    }
+
+    /// Continue from an existing plan (continuous planning)
+    ///
+    /// Scheduling starts no earlier than the baseline time. Completed
+    /// activities are skipped; in-progress ones keep their assignments,
+    /// occupy their resources and hold back the rest of their task.
+    /// Pending activities keep their previous resource unless another
+    /// candidate finishes earlier. Activities with reported progress stay
+    /// on their resource from their actual start and end after their
+    /// remaining processing time (at the resource's efficiency).
+    pub fn with_baseline(mut self, baseline: Baseline) -> Self {
+        self.baseline = Some(baseline);
+        self
+    }
+
+    /// Penalize deviation from the baseline plan (re-optimization)
+    ///
+    /// A pending activity's candidate competes as if it finished
+    /// `weight * |start shift|` later, plus `resource_change_ms` when it
+    /// is not the planned resource. Weight 0 only discourages resource
+    /// changes; weight 1 trades a millisecond of finish time for a
+    /// millisecond of shift. Needs [`with_baseline`](Self::with_baseline).
+    pub fn with_stability(mut self, weight: f64, resource_change_ms: i64) -> Self {
+        self.stability = Some((weight.max(0.0), resource_change_ms.max(0)));
+        self
+    }
+
+    /// Delay work toward task deadlines (just-in-time, see [`JustInTime`])
+    ///
+    /// After the forward pass, idle time is inserted so that tasks finish
+    /// `buffer_ms` before their deadlines instead of as early as possible,
+    /// minimizing earliness without adding tardiness. Late tasks and tasks
+    /// without a deadline keep their completion; with a baseline, started
+    /// activities stay. Constraints from
+    /// [`with_constraints`](Self::with_constraints) are not re-checked.
+    pub fn with_just_in_time(mut self, buffer_ms: i64) -> Self {
+        self.just_in_time_ms = Some(buffer_ms.max(0));
+        self
+    }
+
     /// Schedule tasks on resources
     pub fn schedule(&self, tasks: &[Task], resources: &[Resource], start_time_ms: i64) -> Schedule {
+        let clock = Stopwatch::start();
+        let start_time_ms = self
+            .baseline
+            .as_ref()
+            .map_or(start_time_ms, |b| start_time_ms.max(b.as_of_ms));
         let mut schedule = Schedule::new();
-        let mut resource_available: HashMap<String, i64> = HashMap::new();
-        let mut last_category: HashMap<String, String> = HashMap::new();
+        if self.trace {
+            schedule.trace = Some(DecisionTrace::new());
+        }
+        let buffered;
+        let tasks = if self.confidence.is_some() || !self.category_confidence.is_empty() {
+            let (tasks, confidence) = self.buffered_tasks(tasks);
+            schedule.planned_confidence = confidence;
+            buffered = tasks;
+            &buffered[..]
+        } else {
+            tasks
+        };
+        let lookups = Lookups::new(self, tasks, resources, start_time_ms);
+        let mut progress = Progress {
+            schedule,
+            resource_available: HashMap::new(),
+            last_category: HashMap::new(),
+            repetitions: HashMap::new(),
+            usage: HashMap::new(),
+            workload: HashMap::new(),
+            resource_location: HashMap::new(),
+            exclusive: HashMap::new(),
+        };
 
         // Initialize resource availability
         for resource in resources {
-            resource_available.insert(resource.id.clone(), start_time_ms);
+            let release = self.release_times.get(&resource.id).copied();
+            progress.resource_available.insert(
+                resource.id.clone(),
+                release.map_or(start_time_ms, |t| t.max(start_time_ms)),
+            );
+            if let Some(location) = &resource.location {
+                progress
+                    .resource_location
+                    .insert(resource.id.clone(), location.clone());
+            }
+        }
+
+        // Work in progress stays where it is
+        let mut anchored: Vec<Assignment> = Vec::new();
+        if let Some(baseline) = &self.baseline {
+            for task in tasks {
+                for activity in &task.activities {
+                    match baseline.progress_of(&activity.id) {
+                        Some(p) if p.is_complete() => {}
+                        Some(p) => {
+                            let duration = lookups
+                                .resource_map
+                                .get(p.resource_id.as_str())
+                                .map_or(activity.duration.process_ms, |r| {
+                                    r.processing_time(activity.duration.process_ms)
+                                });
+                            anchored.push(p.anchor(&task.id, duration, start_time_ms));
+                        }
+                        None => anchored.extend(
+                            baseline
+                                .frozen
+                                .iter()
+                                .filter(|a| a.activity_id == activity.id)
+                                .cloned(),
+                        ),
+                    }
+                }
+            }
+        }
+        let anchored_end: HashMap<String, i64> =
+            anchored.iter().fold(HashMap::new(), |mut ends, a| {
+                let end = ends.entry(a.activity_id.clone()).or_insert(a.end_ms);
+                *end = (*end).max(a.end_ms);
+                ends
+            });
+        for frozen in anchored {
+            let available = progress
+                .resource_available
+                .entry(frozen.resource_id.clone())
+                .or_insert(start_time_ms);
+            *available = (*available).max(frozen.end_ms);
+            if let Some(category) = lookups.category_of.get(frozen.task_id.as_str()) {
+                progress
+                    .last_category
+                    .insert(frozen.resource_id.clone(), category.to_string());
+                *progress
+                    .repetitions
+                    .entry((frozen.resource_id.clone(), category.to_string()))
+                    .or_insert(0) += 1;
+            }
+            progress.schedule.add_assignment(frozen);
         }
 
-        // Sort tasks by priority (descending)
+        // Sort tasks by priority class, then priority (descending); later
+        // tasks only take what earlier ones leave, so lower classes never
+        // delay higher ones
         let mut sorted_tasks: Vec<&Task> = tasks.iter().collect();
-        sorted_tasks.sort_by(|a, b| b.priority.cmp(&a.priority));
+        sorted_tasks.sort_by_key(|t| std::cmp::Reverse((t.priority_class, t.priority)));
 
         // Schedule each task
         for task in sorted_tasks {
             let mut task_start = start_time_ms;
+            let mut task_location: Option<&str> = None;
 
             for activity in &task.activities {
-                // Find best resource
-                let candidates = activity.candidate_resources();
-                if candidates.is_empty() {
-                    continue;
+                let state = self.baseline.as_ref().and_then(|b| b.state(&activity.id));
+                match state {
+                    Some(BaselineState::Completed) => continue,
+                    Some(BaselineState::InProgress) => {
+                        let end = anchored_end.get(&activity.id).copied();
+                        task_start = task_start.max(end.unwrap_or(task_start));
+                        continue;
+                    }
+                    _ => {}
                 }
+                self.place_activity(
+                    &lookups,
+                    &mut progress,
+                    task,
+                    activity,
+                    &mut task_start,
+                    &mut task_location,
+                );
+            }
+        }
+
+        let mut schedule = progress.schedule;
+        if let Some(buffer_ms) = self.just_in_time_ms {
+            let mut jit = JustInTime::new().with_buffer_ms(buffer_ms);
+            if self.baseline.is_some() {
+                jit = jit.with_frozen_before(start_time_ms);
+            }
+            schedule = jit.apply(&schedule, tasks, resources);
+        }
+
+        telemetry::schedule_run(&clock, &schedule);
+        schedule
+    }
 
-                // Select resource with earliest availability
-                let mut best_resource: Option<&str> = None;
-                let mut best_start = i64::MAX;
+    /// Place one activity, advancing its task's ready time and location
+    fn place_activity<'a>(
+        &'a self,
+        lookups: &Lookups<'a>,
+        progress: &mut Progress<'a>,
+        task: &'a Task,
+        activity: &'a Activity,
+        task_start: &mut i64,
+        task_location: &mut Option<&'a str>,
+    ) {
+        let Some((mut placing, candidates)) = lookups.placing(
+            &progress.schedule,
+            task,
+            activity,
+            *task_start,
+            *task_location,
+        ) else {
+            return;
+        };
 
-                for candidate in &candidates {
-                    if let Some(&available) = resource_available.get(candidate) {
-                        let actual_start = available.max(task_start);
-                        if actual_start < best_start {
-                            best_start = actual_start;
-                            best_resource = Some(candidate);
-                        }
+        // Gang and malleable activities occupy several resources at once
+        if activity.is_gang() || activity.malleable.is_some() {
+            if let Some(end) = self.place_gang(lookups, progress, &placing, &candidates) {
+                *task_start = end;
+            }
+            return;
+        }
+
+        // Gap insertion only when nothing moves the start after selection
+        placing.gap_insertion = placing.handoff.is_none()
+            && placing.overlap.is_none()
+            && placing.max_delay.is_none()
+            && !lookups.exclusion_groups.contains_key(activity.id.as_str())
+            && self.travel_times.is_none()
+            && self.slots.is_none()
+            && (self.energy.is_none() || activity.energy_kw <= 0.0);
+
+        // Select resource with earliest finish
+        let mut best: Option<Evaluation> = None;
+        let mut considered: Vec<CandidateRecord> = Vec::new();
+        let mut overloaded = 0;
+        for candidate in &candidates {
+            match self.evaluate(lookups, progress, &placing, candidate) {
+                Ok(option) => {
+                    if lookups.recording {
+                        considered.push(CandidateRecord {
+                            resource_id: candidate.clone(),
+                            start_ms: Some(option.start),
+                            finish_ms: Some(option.finish),
+                            score: Some(option.score),
+                            outcome: CandidateOutcome::Outscored,
+                        });
+                    }
+                    if best
+                        .as_ref()
+                        .is_none_or(|best| self.beats(progress, &option, best))
+                    {
+                        best = Some(option);
                     }
                 }
-
-                if let Some(resource_id) = best_resource {
-                    // Calculate setup time
-                    let setup_time = if let Some(prev_cat) = last_category.get(resource_id) {
-                        self.transition_matrices.get_transition_time(
-                            resource_id,
-                            prev_cat,
-                            &task.category,
-                        )
-                    } else {
-                        0
-                    };
-
-                    let start = best_start;
-                    let end = start + setup_time + activity.duration.process_ms;
-
-                    // Create assignment
-                    let assignment = Assignment {
-                        activity_id: activity.id.clone(),
-                        task_id: task.id.clone(),
-                        resource_id: resource_id.to_string(),
-                        start_ms: start,
-                        end_ms: end,
-                        setup_ms: setup_time,
-                    };
-
-                    schedule.add_assignment(assignment);
-
-                    // Update state
-                    resource_available.insert(resource_id.to_string(), end);
-                    last_category.insert(resource_id.to_string(), task.category.clone());
-                    task_start = end; // Next activity can't start before this one ends
+                Err(outcome) => {
+                    if outcome == CandidateOutcome::InsufficientCapacity {
+                        overloaded += 1;
+                    }
+                    if lookups.recording {
+                        considered.push(infeasible(candidate, outcome));
+                    }
                 }
             }
         }
 
-        schedule
+        let Some(best) = best else {
+            if let Some(trace) = &mut progress.schedule.trace {
+                trace.record(decision(task, activity, placing.ready, considered, None));
+            }
+            if overloaded > 0 {
+                progress
+                    .schedule
+                    .add_violation(Violation::capacity_exceeded(
+                        &activity.id,
+                        &format!(
+                            "No candidate can carry the load of task {} or host the demands of {}",
+                            task.id, activity.id
+                        ),
+                    ));
+            }
+            return;
+        };
+        settle_outcomes(
+            &mut considered,
+            best.resource_id,
+            best.score,
+            placing.latest_start,
+        );
+
+        let resource_id = best.resource_id;
+        let setup_time = best.work.setup;
+        let Some((start, end)) = self.shift_selected(lookups, progress, activity, &best) else {
+            progress.schedule.add_violation(Violation::time_window(
+                &activity.id,
+                &format!("No slot left for activity {}", activity.id),
+            ));
+            if let Some(trace) = &mut progress.schedule.trace {
+                trace.record(decision(task, activity, placing.ready, considered, None));
+            }
+            return;
+        };
+        progress.book_exclusive(lookups, activity, start, end);
+        self.sync_predecessors(lookups, progress, &placing, start);
+
+        // Create assignment
+        let assignment = Assignment {
+            activity_id: activity.id.clone(),
+            task_id: task.id.clone(),
+            resource_id: resource_id.to_string(),
+            start_ms: start,
+            end_ms: end,
+            setup_ms: setup_time,
+        };
+
+        progress.schedule.add_assignment(assignment);
+
+        let resource = lookups.resource_map.get(resource_id).copied();
+        progress.track_usage(resource, start, best.work.exec, best.replacement);
+
+        // The setter is booked for the setup part only
+        let setter = resource
+            .filter(|_| setup_time > 0)
+            .and_then(|r| earliest_setter(&r.setup_crew, &progress.resource_available))
+            .map(|(id, _)| id.to_string());
+        if let Some(setter) = setter {
+            progress.schedule.add_assignment(Assignment {
+                activity_id: activity.id.clone(),
+                task_id: task.id.clone(),
+                resource_id: setter.clone(),
+                start_ms: start,
+                end_ms: start + setup_time,
+                setup_ms: setup_time,
+            });
+            *progress.workload.entry(setter.clone()).or_insert(0) += setup_time;
+            progress
+                .resource_available
+                .insert(setter, start + setup_time);
+        }
+
+        // Update state (an inserted activity leaves the resource tail as is)
+        let shared = resource.is_some_and(|r| r.is_multi_dimensional());
+        progress.occupy(
+            resource_id,
+            &task.category,
+            (start, end),
+            best.inserted,
+            shared,
+        );
+        *task_start = end; // Next activity can't start before this one ends
+        let placed = Placed {
+            resources: vec![resource_id.to_string()],
+            start,
+            end,
+            setup: setup_time,
+            free: best.free,
+        };
+        self.record_placement(progress, &placing, placed, considered);
+        if self.travel_times.is_some() {
+            if let Some(location) = location_of(activity, resource.as_ref()) {
+                progress
+                    .resource_location
+                    .insert(resource_id.to_string(), location.to_string());
+                *task_location = Some(location);
+            }
+        }
     }
 
-    /// Schedule from request
-    pub fn schedule_request(&self, request: &ScheduleRequest) -> Schedule {
-        let scheduler = self
-            .clone()
-            .with_transition_matrices(request.transition_matrices.clone());
+    /// Earliest placement of an activity on one candidate, or why the
+    /// candidate cannot take it
+    fn evaluate<'c>(
+        &self,
+        lookups: &Lookups,
+        progress: &Progress,
+        placing: &Placing,
+        candidate: &'c str,
+    ) -> Result<Evaluation<'c>, CandidateOutcome> {
+        let (task, activity, ready) = (placing.task, placing.activity, placing.ready);
+        let Some(&available) = progress.resource_available.get(candidate) else {
+            return Err(CandidateOutcome::UnknownResource);
+        };
+        let resource = lookups.resource_map.get(candidate).copied();
+        if resource
+            .is_some_and(|r| !r.can_carry(&task.loads) || !r.can_host(&activity.dimension_demands))
+        {
+            return Err(CandidateOutcome::InsufficientCapacity);
+        }
+        // Multi-dimensional resources are shared while demands fit
+        let packed = resource.filter(|r| r.is_multi_dimensional());
+        let available = match packed {
+            Some(r) => packed_start(
+                r,
+                &progress.schedule.assignments_for_resource(candidate),
+                &lookups.demand_of,
+                &activity.dimension_demands,
+                ready.max(available),
+                activity.duration.process_ms,
+            ),
+            None => available,
+        };
+        let process_ms =
+            progress.learned_work(resource, &task.category, activity.duration.process_ms);
+        let exec = resource.map_or(process_ms, |r| r.processing_time(process_ms));
+        let calendar = self.calendar_of(resource);
+        let varying = resource.filter(|r| !r.efficiency_periods.is_empty());
+        let inserted = if placing.gap_insertion
+            && packed.is_none()
+            && calendar.is_none()
+            && varying.is_none()
+            && resource.is_none_or(|r| r.setup_crew.is_empty() && r.usage_budget.is_none())
+            && !lookups.rules.contains_key(candidate)
+        {
+            let floor = self
+                .release_times
+                .get(candidate)
+                .map_or(ready, |&t| t.max(ready));
+            self.insertion_gap(
+                lookups,
+                &progress.schedule,
+                candidate,
+                task,
+                floor.max(lookups.start_time_ms),
+                exec,
+            )
+        } else {
+            None
+        };
+        let setup = match inserted {
+            Some((_, setup)) => setup,
+            None => self.setup_time(progress, candidate, &task.category),
+        };
+        let mut start = available.max(ready);
+        if let (Some(travel), Some(location)) =
+            (&self.travel_times, location_of(activity, resource.as_ref()))
+        {
+            let resource_ready = progress
+                .resource_location
+                .get(candidate)
+                .map_or(available, |from| {
+                    available + travel.travel_time(from, location)
+                });
+            let task_ready = placing.task_location.map_or(ready, |from| {
+                ready.max(placing.task_start + travel.travel_time(from, location))
+            });
+            start = resource_ready.max(task_ready);
+        }
+        if let Some((gap_start, _)) = inserted {
+            start = gap_start;
+        }
+        // A worn-out tool is replaced once the resource is free
+        let replacement = progress.replacement_due(resource, exec);
+        if let Some(replacement_ms) = replacement {
+            start = start.max(available + replacement_ms);
+        }
+        let free = inserted.map_or(available.max(ready), |(s, _)| s);
+        // An overlapping successor must not finish before its predecessor
+        if let Some((end, _)) = placing.overlap {
+            start = start.max(end - setup - exec);
+        }
+        // Setups wait for a member of the resource's setup crew
+        if let Some(r) = resource.filter(|r| setup > 0 && !r.setup_crew.is_empty()) {
+            let (_, crew_free) = earliest_setter(&r.setup_crew, &progress.resource_available)
+                .ok_or(CandidateOutcome::NoSetupCrew)?;
+            start = start.max(crew_free);
+        }
+        let work = Work {
+            setup,
+            process: process_ms,
+            exec,
+        };
+        let (start, finish) =
+            self.feasible_start(lookups, progress, activity, candidate, start, work)?;
+        // Loads already on board stay until their delivery
+        if !lookups.load_fits(&progress.schedule, resource, task, start, finish) {
+            return Err(CandidateOutcome::InsufficientCapacity);
+        }
+        // Efficiency in effect at the start, not the nominal one
+        let exec = match varying {
+            Some(_) if calendar.is_none() => finish - start - setup,
+            _ => exec,
+        };
+        let score = self.score(
+            placing,
+            resource,
+            candidate,
+            start,
+            finish,
+            lookups.max_priority,
+        );
+        // Best fit: prefer the packed resource left with least slack
+        let slack = packed.map_or(f64::INFINITY, |r| {
+            packing_slack(
+                r,
+                &progress.schedule.assignments_for_resource(candidate),
+                &lookups.demand_of,
+                &activity.dimension_demands,
+                start,
+            )
+        });
+        Ok(Evaluation {
+            resource_id: candidate,
+            start,
+            finish,
+            work: Work { exec, ..work },
+            inserted: inserted.is_some(),
+            free,
+            replacement,
+            score,
+            slack,
+            late: placing.latest_start.is_some_and(|l| start > l),
+        })
+    }
 
-        scheduler.schedule(&request.tasks, &request.resources, request.start_time_ms)
+    /// Earliest start at or after `start` where an activity clears its
+    /// mutual-exclusion groups, the resource's working-time rules, the slot
+    /// grid and the resource calendar, with the finish it reaches there
+    fn feasible_start(
+        &self,
+        lookups: &Lookups,
+        progress: &Progress,
+        activity: &Activity,
+        resource_id: &str,
+        start: i64,
+        work: Work,
+    ) -> Result<(i64, i64), CandidateOutcome> {
+        // Wait until no other member of the activity's groups runs
+        let booked = progress.excluded(lookups, activity);
+        let mut start = start;
+        loop {
+            let (s, f) = self.fit(lookups, progress, resource_id, start, work)?;
+            match overlap_end(&booked, s, f) {
+                Some(end) => start = end,
+                None => return Ok((s, f)),
+            }
+        }
     }
-}
 
-impl Default for SimpleScheduler {
-    fn default() -> Self {
-        Self::new()
+    /// Earliest start at or after `start` the resource's working-time
+    /// rules, the slot grid and the resource calendar accept, with the
+    /// finish it reaches there
+    fn fit(
+        &self,
+        lookups: &Lookups,
+        progress: &Progress,
+        resource_id: &str,
+        start: i64,
+        work: Work,
+    ) -> Result<(i64, i64), CandidateOutcome> {
+        let resource = lookups.resource_map.get(resource_id).copied();
+        let duration = work.setup + work.exec;
+        let mut start = start;
+        if let Some(rules) = lookups.rules.get(resource_id) {
+            let booked = progress.schedule.assignments_for_resource(resource_id);
+            start = rules
+                .earliest_start(&booked, start, duration)
+                .ok_or(CandidateOutcome::WorkingTimeRules)?;
+        }
+        if let Some(grid) = &self.slots {
+            start = grid.next_start(start).ok_or(CandidateOutcome::NoSlot)?;
+        }
+        let varying = resource.filter(|r| !r.efficiency_periods.is_empty());
+        let finish = match self.calendar_of(resource) {
+            Some(calendar) => {
+                start = calendar.next_available_time(start);
+                match varying {
+                    Some(r) => r.finish_time(start, work.setup, work.process, Some(calendar)),
+                    None => calendar.add_working_duration(start, duration),
+                }
+                .ok_or(CandidateOutcome::CalendarExhausted)?
+            }
+            None => varying
+                .and_then(|r| r.finish_time(start, work.setup, work.process, None))
+                .unwrap_or(start + duration),
+        };
+        Ok((start, finish))
     }
-}
 
-impl Clone for SimpleScheduler {
-    fn clone(&self) -> Self {
-        Self {
-            transition_matrices: self.transition_matrices.clone(),
+    /// Finish time a candidate competes with: preferences pull it earlier,
+    /// revocation risk and deviation from the baseline push it later
+    fn score(
+        &self,
+        placing: &Placing,
+        resource: Option<&Resource>,
+        candidate: &str,
+        start: i64,
+        finish: i64,
+        max_priority: i32,
+    ) -> i64 {
+        // Preferred resources/shifts may win despite finishing later
+        let score = match self.preference_delay_ms {
+            Some(max_delay) => {
+                let shift = resource.map_or(0.0, |r| r.shift_preference(start, finish));
+                let weight = placing.activity.resource_preference(candidate) + shift;
+                finish - (weight * max_delay as f64) as i64
+            }
+            None => finish,
+        };
+        // Critical work avoids revocable resources
+        let score = match (self.reliability_delay_ms, resource) {
+            (Some(max_delay), Some(r)) if r.is_preemptible() => {
+                let criticality = placing.task.priority.max(0) as f64 / max_priority as f64;
+                let risk = r.revocation_probability.clamp(0.0, 1.0);
+                score + (risk * criticality * max_delay as f64) as i64
+            }
+            _ => score,
+        };
+        // Re-plans stay close to the baseline
+        match (self.stability, placing.planned) {
+            (Some((weight, change_ms)), Some(planned)) => {
+                let shift = (start - planned.start_ms).abs();
+                let change = if planned.resource_id == candidate {
+                    0
+                } else {
+                    change_ms
+                };
+                score + (weight * shift as f64) as i64 + change
+            }
+            _ => score,
         }
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::models::{Activity, ActivityDuration};
+    /// Whether `option` wins over the best candidate so far
+    ///
+    /// Candidates meeting the latest start beat late ones; then the lower
+    /// score wins, ties going to the less loaded resource (workload
+    /// balancing) or the tighter packing.
+    fn beats(&self, progress: &Progress, option: &Evaluation, best: &Evaluation) -> bool {
+        if option.late != best.late {
+            return !option.late;
+        }
+        let load = |id: &str| progress.workload.get(id).copied().unwrap_or(0);
+        let less_loaded = self.balance_workload
+            && option.score == best.score
+            && load(option.resource_id) < load(best.resource_id);
+        let tighter = option.score == best.score && option.slack < best.slack;
+        option.score < best.score || less_loaded || tighter
+    }
 
-    fn create_test_scenario() -> (Vec<Task>, Vec<Resource>) {
-        let tasks = vec![
-            Task::new("T1").with_priority(5).with_activity(
-                Activity::new("T1-A1", "T1", 1)
-                    .with_duration(ActivityDuration::fixed(5000))
-                    .with_resources("machine", vec!["M1".into(), "M2".into()]),
-            ),
-            Task::new("T2").with_priority(3).with_activity(
-                Activity::new("T2-A1", "T2", 1)
-                    .with_duration(ActivityDuration::fixed(3000))
-                    .with_resources("machine", vec!["M1".into()]),
+    /// Start and end on the chosen resource after the shifts only made
+    /// once it is chosen: cheaper energy, working-time rules, packing, slot
+    /// snapping and calendars; `None` if no slot is left
+    ///
+    /// A shift landing on another member of the activity's mutual-exclusion
+    /// groups is pushed past it and the shifts are made again.
+    fn shift_selected(
+        &self,
+        lookups: &Lookups,
+        progress: &Progress,
+        activity: &Activity,
+        best: &Evaluation,
+    ) -> Option<(i64, i64)> {
+        let duration = best.work.setup + best.work.exec;
+        let mut start = match &self.energy {
+            Some((tariff, max_delay)) if activity.energy_kw > 0.0 => tariff.cheapest_start(
+                best.start,
+                best.start + max_delay,
+                duration,
+                activity.energy_kw,
             ),
-        ];
+            _ => best.start,
+        };
+        let booked = progress.excluded(lookups, activity);
+        loop {
+            let (s, e) = self.settle(lookups, progress, activity, best, start)?;
+            match overlap_end(&booked, s, e) {
+                Some(end) => start = end,
+                None => return Some((s, e)),
+            }
+        }
+    }
 
-        let resources = vec![
-            Resource::primary("M1").with_efficiency(1.0),
-            Resource::primary("M2").with_efficiency(0.9),
-        ];
+    /// Start and end on the chosen resource from `start` after working-time
+    /// rules, packing, slot snapping and calendars
+    fn settle(
+        &self,
+        lookups: &Lookups,
+        progress: &Progress,
+        activity: &Activity,
+        best: &Evaluation,
+        start: i64,
+    ) -> Option<(i64, i64)> {
+        let resource_id = best.resource_id;
+        let resource = lookups.resource_map.get(resource_id).copied();
+        let duration = best.work.setup + best.work.exec;
+        let start = match lookups.rules.get(resource_id) {
+            Some(rules) => rules
+                .earliest_start(
+                    &progress.schedule.assignments_for_resource(resource_id),
+                    start,
+                    duration,
+                )
+                .unwrap_or(start),
+            None => start,
+        };
+        let start = match resource.filter(|r| r.is_multi_dimensional()) {
+            Some(r) => packed_start(
+                r,
+                &progress.schedule.assignments_for_resource(resource_id),
+                &lookups.demand_of,
+                &activity.dimension_demands,
+                start,
+                duration,
+            ),
+            None => start,
+        };
+        if let Some(grid) = &self.slots {
+            return grid.snap(start, duration);
+        }
+        let calendar = self.calendar_of(resource);
+        let start = calendar.map_or(start, |c| c.next_available_time(start));
+        let end = match resource.filter(|r| !r.efficiency_periods.is_empty()) {
+            Some(r) => r.finish_time(start, best.work.setup, best.work.process, calendar),
+            None => calendar.map_or(Some(start + duration), |c| {
+                c.add_working_duration(start, duration)
+            }),
+        };
+        Some((start, end.unwrap_or(start + duration)))
+    }
 
-        (tasks, resources)
+    /// Setup on a resource after the category it ran last
+    fn setup_time(&self, progress: &Progress, resource_id: &str, category: &str) -> i64 {
+        progress.last_category.get(resource_id).map_or(0, |prev| {
+            self.transition_matrices
+                .get_transition_time(resource_id, prev, category)
+        })
     }
 
-    #[test]
-    fn test_simple_scheduling() {
-        let (tasks, resources) = create_test_scenario();
-        let scheduler = SimpleScheduler::new();
+    /// Place a gang or malleable activity on several resources at once,
+    /// returning its end
+    ///
+    /// All members start together after the longest setup among them, at
+    /// the first start every member's calendar, rules and exclusion groups
+    /// accept; the activity ends with its slowest member. Setup crews are
+    /// not booked for gangs.
+    fn place_gang<'a>(
+        &self,
+        lookups: &Lookups<'a>,
+        progress: &mut Progress<'a>,
+        placing: &Placing,
+        candidates: &[String],
+    ) -> Option<i64> {
+        let (task, activity, ready) = (placing.task, placing.activity, placing.ready);
+        let resource_map = &lookups.resource_map;
+        let placement = match &activity.malleable {
+            Some(malleability) => malleable_members(
+                activity,
+                malleability,
+                &progress.resource_available,
+                resource_map,
+                ready,
+            ),
+            None => gang_members(activity, &progress.resource_available, resource_map, ready)
+                .map(|members| (members, activity.duration.process_ms)),
+        };
+        let Some((members, work_ms)) = placement else {
+            progress
+                .schedule
+                .add_violation(Violation::resource_unavailable(
+                    &activity.id,
+                    &format!(
+                        "Not enough candidates for activity {} ({} needed)",
+                        activity.id,
+                        activity
+                            .malleable
+                            .as_ref()
+                            .map_or(activity.gang_size(), |m| m.min_units as usize)
+                    ),
+                ));
+            if let Some(trace) = &mut progress.schedule.trace {
+                let records = gang_records(candidates, &[], &progress.resource_available, 0, 0);
+                trace.record(decision(task, activity, ready, records, None));
+            }
+            return None;
+        };
+        let free = members.iter().map(|m| m.1).max().unwrap_or(ready);
+        let setup = members
+            .iter()
+            .map(|(id, _)| self.setup_time(progress, id, &task.category))
+            .max()
+            .unwrap_or(0);
+        let mut start = free;
+        let mut crew: Vec<(&str, Work, Option<i64>)> = Vec::new();
+        for (id, available) in &members {
+            let resource = resource_map.get(id.as_str()).copied();
+            let process = progress.learned_work(resource, &task.category, work_ms);
+            let exec = resource.map_or(process, |r| r.processing_time(process));
+            let replacement = progress.replacement_due(resource, exec);
+            if let Some(replacement_ms) = replacement {
+                start = start.max(available + replacement_ms);
+            }
+            let work = Work {
+                setup,
+                process,
+                exec,
+            };
+            crew.push((id.as_str(), work, replacement));
+        }
+        if let Some((end, _)) = placing.overlap {
+            let longest = crew.iter().map(|(_, w, _)| w.exec).max().unwrap_or(0);
+            start = start.max(end - setup - longest);
+        }
 
-        let schedule = scheduler.schedule(&tasks, &resources, 0);
+        // Raise the common start until every member accepts it
+        let mut end = start;
+        let mut agreed = false;
+        'rounds: for _ in 0..GANG_START_ROUNDS {
+            let mut next = start;
+            end = start;
+            for &(id, work, _) in &crew {
+                let Ok((s, f)) = self.feasible_start(lookups, progress, activity, id, start, work)
+                else {
+                    break 'rounds;
+                };
+                next = next.max(s);
+                end = end.max(f);
+            }
+            if next == start {
+                agreed = true;
+                break;
+            }
+            start = next;
+        }
+        if !agreed {
+            progress
+                .schedule
+                .add_violation(Violation::resource_unavailable(
+                    &activity.id,
+                    &format!("No common start for activity {}", activity.id),
+                ));
+            return None;
+        }
 
-        assert_eq!(schedule.assignment_count(), 2);
-        assert!(schedule.makespan_ms > 0);
+        let chosen: Vec<String> = members.iter().map(|(id, _)| id.clone()).collect();
+        let considered = if lookups.recording {
+            gang_records(
+                candidates,
+                &chosen,
+                &progress.resource_available,
+                start,
+                end,
+            )
+        } else {
+            Vec::new()
+        };
+        progress.book_exclusive(lookups, activity, start, end);
+        self.sync_predecessors(lookups, progress, placing, start);
+        for &(id, work, replacement) in &crew {
+            progress.schedule.add_assignment(
+                Assignment::new(&activity.id, &task.id, id, start, end).with_setup(setup),
+            );
+            let resource = resource_map.get(id).copied();
+            progress.track_usage(resource, start, work.exec, replacement);
+            progress.occupy(id, &task.category, (start, end), false, false);
+        }
+        let placed = Placed {
+            resources: chosen,
+            start,
+            end,
+            setup,
+            free,
+        };
+        self.record_placement(progress, placing, placed, considered);
+        Some(end)
     }
 
-    #[test]
-    fn test_priority_ordering() {
-        let (tasks, resources) = create_test_scenario();
-        let scheduler = SimpleScheduler::new();
+    /// Delay handoff and maximum-delay predecessors toward the start of
+    /// their successor, flagging gaps that cannot be closed
+    fn sync_predecessors<'a>(
+        &self,
+        lookups: &Lookups<'a>,
+        progress: &mut Progress<'a>,
+        placing: &Placing,
+        start: i64,
+    ) {
+        let activity = placing.activity;
+        if let Some((from, tolerance)) = placing.handoff {
+            if let Some(gap) = self.sync_predecessor(lookups, progress, from, start, tolerance) {
+                progress
+                    .schedule
+                    .add_violation(Violation::precedence_violation(
+                        &activity.id,
+                        &format!(
+                            "Handoff {} -> {} is off by {} ms (tolerance {} ms)",
+                            from, activity.id, gap, tolerance
+                        ),
+                    ));
+            }
+        }
+        if let Some((from, max_delay_ms)) = placing.max_delay {
+            if let Some(gap) = self.sync_predecessor(lookups, progress, from, start, max_delay_ms) {
+                progress
+                    .schedule
+                    .add_violation(Violation::precedence_violation(
+                        &activity.id,
+                        &format!(
+                            "Activity {} starts {} ms after {} ends (max {} ms)",
+                            activity.id, gap, from, max_delay_ms
+                        ),
+                    ));
+            }
+        }
+    }
 
-        let schedule = scheduler.schedule(&tasks, &resources, 0);
+    /// Delay the handing-over activity so it ends within `tolerance_ms` of
+    /// the receiving start, if nothing was scheduled after it on its
+    /// resources (setter included), in its task or in its mutual-exclusion
+    /// groups, and the moved interval still fits its time window, resource
+    /// calendars, working-time rules and its own handoff or maximum delay
+    ///
+    /// The moved interval replaces the old one in `exclusive`; workload is a
+    /// sum of durations and is unchanged by the shift.
+    /// Returns the gap left when the activity cannot be delayed.
+    fn sync_predecessor<'a>(
+        &self,
+        lookups: &Lookups<'a>,
+        progress: &mut Progress<'a>,
+        from: &str,
+        to_start: i64,
+        tolerance_ms: i64,
+    ) -> Option<i64> {
+        let schedule = &progress.schedule;
+        let moved: Vec<usize> = schedule
+            .assignments
+            .iter()
+            .enumerate()
+            .filter(|(_, a)| a.activity_id == from)
+            .map(|(i, _)| i)
+            .collect();
+        let handover = &schedule.assignments[*moved.first()?];
+        let (start, end) = (handover.start_ms, handover.end_ms);
+        let gap = to_start - end;
+        if gap <= tolerance_ms {
+            return None;
+        }
+        let delta = gap - tolerance_ms;
+        let (new_start, new_end) = (start + delta, end + delta);
 
-        // T1 (priority 5) should be scheduled before T2 (priority 3)
-        let t1_end = schedule.task_completion_time("T1").unwrap();
-        let t2_start = schedule.assignment_for_activity("T2-A1").unwrap().start_ms;
+        let resources_free = moved.iter().all(|&i| {
+            let a = &schedule.assignments[i];
+            progress.resource_available.get(&a.resource_id) == Some(&a.end_ms)
+        });
+        let task_free = !schedule
+            .assignments
+            .iter()
+            .any(|a| a.task_id == handover.task_id && a.activity_id != from && a.start_ms >= end);
+        let groups = lookups
+            .exclusion_groups
+            .get(from)
+            .map_or(&[][..], Vec::as_slice);
+        let groups_free = groups
+            .iter()
+            .filter_map(|g| progress.exclusive.get(g))
+            .flatten()
+            .all(|&(s, e)| (s, e) == (start, end) || e <= new_start || new_end <= s);
+        let in_window = lookups
+            .windows
+            .get(from)
+            .is_none_or(|&(_, window_end)| new_end <= window_end);
+        let resources_open = moved.iter().all(|&i| {
+            let a = &schedule.assignments[i];
+            let id = a.resource_id.as_str();
+            let (s, e) = (a.start_ms, a.end_ms);
+            let resource = lookups.resource_map.get(id).copied();
+            let calendar_open = self.calendar_of(resource).is_none_or(|c| {
+                c.working_duration_between(s + delta, e + delta) >= c.working_duration_between(s, e)
+            });
+            let rules_kept = lookups.rules.get(id).is_none_or(|rules| {
+                let others: Vec<&Assignment> = schedule
+                    .assignments_for_resource(id)
+                    .into_iter()
+                    .filter(|a| a.activity_id != from)
+                    .collect();
+                rules.allows(&others, s + delta, e + delta)
+            });
+            calendar_open && rules_kept
+        });
+        // Its own handoff or maximum delay must still hold after the move
+        let incoming_kept = [lookups.handoffs.get(from), lookups.max_delays.get(from)]
+            .into_iter()
+            .flatten()
+            .all(|&(before, limit)| {
+                schedule
+                    .assignment_for_activity(before)
+                    .is_none_or(|b| new_start - b.end_ms <= limit)
+            });
+        if !(resources_free
+            && task_free
+            && groups_free
+            && in_window
+            && resources_open
+            && incoming_kept)
+        {
+            return Some(gap);
+        }
 
-        // T1 should start at 0 or T2 should start after T1
-        let t1_start = schedule.assignment_for_activity("T1-A1").unwrap().start_ms;
-        assert!(t1_start <= t2_start || t1_end <= t2_start);
+        for &i in &moved {
+            let a = &mut progress.schedule.assignments[i];
+            a.start_ms += delta;
+            a.end_ms += delta;
+            progress
+                .resource_available
+                .insert(a.resource_id.clone(), a.end_ms);
+        }
+        for group in groups {
+            if let Some(booked) = progress
+                .exclusive
+                .get_mut(group)
+                .and_then(|b| b.iter_mut().find(|b| **b == (start, end)))
+            {
+                *booked = (new_start, new_end);
+            }
+        }
+        if let Some(explanation) = progress.schedule.explanations.get_mut(from) {
+            explanation.start_ms += delta;
+        }
+        progress.schedule.reindex();
+        None
     }
 
-    #[test]
-    fn test_multiple_activities() {
-        let task = Task::new("T1")
-            .with_activity(
-                Activity::new("T1-A1", "T1", 1)
-                    .with_duration(ActivityDuration::fixed(3000))
-                    .with_resources("machine", vec!["M1".into()]),
-            )
-            .with_activity(
-                Activity::new("T1-A2", "T1", 2)
-                    .with_duration(ActivityDuration::fixed(2000))
-                    .with_resources("machine", vec!["M1".into()]),
+    /// Explain and trace a placement, flagging a start past the latest
+    /// start and an end past the time window
+    fn record_placement(
+        &self,
+        progress: &mut Progress,
+        placing: &Placing,
+        placed: Placed,
+        considered: Vec<CandidateRecord>,
+    ) {
+        let (task, activity, ready) = (placing.task, placing.activity, placing.ready);
+        let (start, end) = (placed.start, placed.end);
+        if self.explain {
+            let driver = if start <= ready {
+                placing.driver
+            } else if start == placed.free {
+                StartDriver::ResourceAvailable
+            } else {
+                StartDriver::Adjusted
+            };
+            progress.schedule.explanations.insert(
+                activity.id.clone(),
+                PlacementExplanation {
+                    activity_id: activity.id.clone(),
+                    resource_id: placed.resources.join(" + "),
+                    start_ms: start,
+                    ready_ms: ready,
+                    driver,
+                    setup_ms: placed.setup,
+                    candidates: considered
+                        .iter()
+                        .map(|c| CandidateOption {
+                            resource_id: c.resource_id.clone(),
+                            start_ms: c.start_ms,
+                            finish_ms: c.finish_ms,
+                        })
+                        .collect(),
+                },
             );
+        }
+        if let Some(trace) = &mut progress.schedule.trace {
+            let placed = Some((placed.resources, start, end));
+            trace.record(decision(task, activity, ready, considered, placed));
+        }
+        if let Some(latest) = placing.latest_start.filter(|&l| start > l) {
+            progress.schedule.add_violation(Violation::deadline_miss(
+                &task.id,
+                &format!(
+                    "Activity {} starts at {} ms after its latest start {} ms",
+                    activity.id, start, latest
+                ),
+            ));
+        }
+        if let Some((_, window_end)) = placing.window {
+            if end > window_end {
+                progress.schedule.add_violation(Violation::time_window(
+                    &activity.id,
+                    &format!(
+                        "Activity {} ends at {} ms after its window closes at {} ms",
+                        activity.id, end, window_end
+                    ),
+                ));
+            }
+        }
+    }
 
-        let resources = vec![Resource::primary("M1")];
-        let scheduler = SimpleScheduler::new();
+    /// Calendar an activity on `resource` is stretched over, if honored
+    fn calendar_of<'r>(&self, resource: Option<&'r Resource>) -> Option<&'r Calendar> {
+        resource
+            .and_then(|r| r.calendar.as_ref())
+            .filter(|_| self.respect_calendars)
+    }
 
-        let schedule = scheduler.schedule(&[task], &resources, 0);
+    /// Tasks with process times at their confidence level, and the level
+    /// used per activity
+    fn buffered_tasks(&self, tasks: &[Task]) -> (Vec<Task>, HashMap<String, f64>) {
+        let mut confidence_of = HashMap::new();
+        let buffered = tasks
+            .iter()
+            .map(|task| {
+                let mut task = task.clone();
+                let confidence = self
+                    .category_confidence
+                    .get(&task.category)
+                    .copied()
+                    .or(self.confidence);
+                if let Some(confidence) = confidence {
+                    for activity in &mut task.activities {
+                        if let Some(distribution) = &activity.duration_distribution {
+                            activity.duration.process_ms =
+                                distribution.duration_at_confidence(confidence);
+                            confidence_of.insert(activity.id.clone(), confidence);
+                        }
+                    }
+                }
+                task
+            })
+            .collect();
+        (buffered, confidence_of)
+    }
 
-        assert_eq!(schedule.assignment_count(), 2);
+    /// Earliest idle gap before a resource's last assignment that fits
+    /// `exec_ms` plus setup and the loads on board, as `(start, setup)`
+    ///
+    /// The following assignment keeps its times, so the inserted activity
+    /// and the changeover into the following assignment must both end by
+    /// the time that assignment starts.
+    fn insertion_gap(
+        &self,
+        lookups: &Lookups,
+        schedule: &Schedule,
+        resource_id: &str,
+        task: &Task,
+        ready_ms: i64,
+        exec_ms: i64,
+    ) -> Option<(i64, i64)> {
+        let resource = lookups.resource_map.get(resource_id).copied();
+        let (category_of, category) = (&lookups.category_of, task.category.as_str());
+        let transition = |from: Option<&&str>, to: &str| {
+            from.map_or(0, |from| {
+                self.transition_matrices
+                    .get_transition_time(resource_id, from, to)
+            })
+        };
 
-        // A2 should start after A1
-        let a1 = schedule.assignment_for_activity("T1-A1").unwrap();
-        let a2 = schedule.assignment_for_activity("T1-A2").unwrap();
+        let booked = schedule.assignments_for_resource(resource_id);
+        let mut prev: Option<&Assignment> = None;
+        for next in booked {
+            let setup = transition(
+                prev.and_then(|p| category_of.get(p.task_id.as_str())),
+                category,
+            );
+            let start = prev.map_or(ready_ms, |p| p.end_ms.max(ready_ms));
+            let next_setup = category_of
+                .get(next.task_id.as_str())
+                .map_or(0, |c| transition(Some(&category), c));
+            if start + setup + exec_ms + next_setup <= next.start_ms
+                && lookups.load_fits(schedule, resource, task, start, start + setup + exec_ms)
+            {
+                return Some((start, setup));
+            }
+            prev = Some(next);
+        }
+        None
+    }
 
-        assert!(a2.start_ms >= a1.end_ms);
+    /// Schedule from request
+    pub fn schedule_request(&self, request: &ScheduleRequest) -> Schedule {
+        let scheduler = self
+            .clone()
+            .with_transition_matrices(request.transition_matrices.clone());
+
+        scheduler.schedule(
+            &request.eligible_tasks(),
+            &request.resources,
+            request.start_time_ms,
+        )
+    }
+}
+
+/// Lookup tables of one scheduling run
+struct Lookups<'a> {
+    /// Run start (no earlier than the baseline time)
+    start_time_ms: i64,
+    /// Candidates are recorded for explanations or the trace
+    recording: bool,
+    /// Highest task priority (at least 1), for criticality
+    max_priority: i32,
+    baseline: Option<&'a Baseline>,
+    resource_map: HashMap<&'a str, &'a Resource>,
+    rules: HashMap<&'a str, &'a WorkingTimeRules>,
+    category_of: HashMap<&'a str, &'a str>,
+    /// Loads per task, for vehicle capacity
+    loads_of: HashMap<&'a str, &'a HashMap<String, f64>>,
+    /// Latest times from propagated deadlines (deadline pruning)
+    latest_times: HashMap<String, LatestTimes>,
+    /// Dimension demands per activity, for packing
+    demand_of: HashMap<&'a str, &'a HashMap<String, f64>>,
+    windows: HashMap<&'a str, (i64, i64)>,
+    pickups: HashMap<&'a str, &'a str>,
+    handoffs: HashMap<&'a str, (&'a str, i64)>,
+    overlaps: HashMap<&'a str, (&'a str, OverlapAllowance)>,
+    max_delays: HashMap<&'a str, (&'a str, i64)>,
+    exclusion_groups: HashMap<&'a str, Vec<&'a str>>,
+}
+
+impl<'a> Lookups<'a> {
+    fn new(
+        scheduler: &'a SimpleScheduler,
+        tasks: &'a [Task],
+        resources: &'a [Resource],
+        start_time_ms: i64,
+    ) -> Self {
+        let mut lookups = Self {
+            start_time_ms,
+            recording: scheduler.explain || scheduler.trace,
+            max_priority: tasks.iter().map(|t| t.priority).max().unwrap_or(0).max(1),
+            baseline: scheduler.baseline.as_ref(),
+            resource_map: resources.iter().map(|r| (r.id.as_str(), r)).collect(),
+            rules: resources
+                .iter()
+                .filter_map(|r| r.working_time.as_ref().map(|w| (r.id.as_str(), w)))
+                .collect(),
+            category_of: tasks
+                .iter()
+                .map(|t| (t.id.as_str(), t.category.as_str()))
+                .collect(),
+            loads_of: tasks
+                .iter()
+                .filter(|t| !t.loads.is_empty())
+                .map(|t| (t.id.as_str(), &t.loads))
+                .collect(),
+            latest_times: if scheduler.deadline_pruning {
+                propagate_deadlines(tasks, resources)
+            } else {
+                HashMap::new()
+            },
+            demand_of: tasks
+                .iter()
+                .flat_map(|t| t.activities.iter())
+                .map(|a| (a.id.as_str(), &a.dimension_demands))
+                .collect(),
+            windows: HashMap::new(),
+            pickups: HashMap::new(),
+            handoffs: HashMap::new(),
+            overlaps: HashMap::new(),
+            max_delays: HashMap::new(),
+            exclusion_groups: HashMap::new(),
+        };
+        for constraint in &scheduler.constraints {
+            match constraint {
+                Constraint::MutualExclusion {
+                    group,
+                    activity_ids,
+                } => {
+                    for id in activity_ids {
+                        lookups
+                            .exclusion_groups
+                            .entry(id.as_str())
+                            .or_default()
+                            .push(group.as_str());
+                    }
+                }
+                Constraint::TimeWindow {
+                    activity_id,
+                    start_ms,
+                    end_ms,
+                } => {
+                    lookups
+                        .windows
+                        .insert(activity_id.as_str(), (*start_ms, *end_ms));
+                }
+                Constraint::PickupDelivery { pickup, delivery } => {
+                    lookups.pickups.insert(delivery.as_str(), pickup.as_str());
+                }
+                Constraint::Handoff {
+                    from,
+                    to,
+                    tolerance_ms,
+                } => {
+                    lookups
+                        .handoffs
+                        .insert(to.as_str(), (from.as_str(), *tolerance_ms));
+                }
+                Constraint::Overlap {
+                    before,
+                    after,
+                    allowance,
+                } => {
+                    lookups
+                        .overlaps
+                        .insert(after.as_str(), (before.as_str(), *allowance));
+                }
+                Constraint::MaxDelay {
+                    before,
+                    after,
+                    max_delay_ms,
+                } => {
+                    lookups
+                        .max_delays
+                        .insert(after.as_str(), (before.as_str(), *max_delay_ms));
+                }
+                _ => {}
+            }
+        }
+        lookups
+    }
+
+    /// Check that a vehicle carries the task's load over its on-board span
+    /// once `[start, end)` is added, with the loads already on board
+    ///
+    /// A task is on board from the first to the last of its assignments on
+    /// the vehicle (loaded at pickup, unloaded at delivery).
+    fn load_fits(
+        &self,
+        schedule: &Schedule,
+        vehicle: Option<&Resource>,
+        task: &Task,
+        start: i64,
+        end: i64,
+    ) -> bool {
+        let Some(vehicle) = vehicle.filter(|r| !r.load_capacity.is_empty()) else {
+            return true;
+        };
+        let mut spans: HashMap<&str, (i64, i64)> = HashMap::new();
+        spans.insert(task.id.as_str(), (start, end));
+        for a in schedule.assignments_for_resource(&vehicle.id) {
+            let span = spans
+                .entry(a.task_id.as_str())
+                .or_insert((a.start_ms, a.end_ms));
+            span.0 = span.0.min(a.start_ms);
+            span.1 = span.1.max(a.end_ms);
+        }
+        let (from, to) = spans[task.id.as_str()];
+        // The load peaks when something is loaded within the span
+        let mut loading = spans
+            .values()
+            .map(|&(s, _)| s)
+            .filter(|&s| from < s && s < to)
+            .chain([from]);
+        let on_board = |t: i64, dimension: &str| -> f64 {
+            spans
+                .iter()
+                .filter(|&(_, &(s, e))| s <= t && t < e)
+                .filter_map(|(id, _)| self.loads_of.get(id))
+                .map(|loads| loads.get(dimension).copied().unwrap_or(0.0))
+                .sum()
+        };
+        loading.all(|t| {
+            vehicle
+                .load_capacity
+                .iter()
+                .all(|(dimension, capacity)| on_board(t, dimension) <= capacity + 1e-9)
+        })
+    }
+
+    /// An activity ready to be placed, with its candidates; `None` if it
+    /// has no candidates
+    ///
+    /// The ready time is raised past the task's previous activity, an
+    /// overlap allowance, the pickup, a handoff or maximum delay
+    /// predecessor and the time window, blaming whatever raised it last.
+    fn placing(
+        &self,
+        schedule: &Schedule,
+        task: &'a Task,
+        activity: &'a Activity,
+        task_start: i64,
+        task_location: Option<&'a str>,
+    ) -> Option<(Placing<'a>, Vec<String>)> {
+        let id = activity.id.as_str();
+        let mut candidates = activity.candidate_resources();
+        if candidates.is_empty() {
+            return None;
+        }
+        // The previous resource is tried first and so wins ties
+        let planned = self.baseline.and_then(|b| b.pending(id));
+        if let Some(hint) = planned.map(|a| a.resource_id.as_str()) {
+            if let Some(pos) = candidates.iter().position(|c| c == hint) {
+                candidates[..=pos].rotate_right(1);
+            }
+        }
+
+        // Overlapping successors may start before the previous activity ends
+        let overlap = self.overlaps.get(id).and_then(|(from, allowance)| {
+            schedule.assignment_for_activity(from).map(|a| {
+                let start =
+                    allowance.earliest_start(a.start_ms, a.end_ms, activity.duration.process_ms);
+                (a.end_ms, start)
+            })
+        });
+
+        // Deliveries ride their pickup's resource; windows delay the start
+        let mut driver = if task_start > self.start_time_ms {
+            StartDriver::Predecessor
+        } else {
+            StartDriver::ScheduleStart
+        };
+        let mut ready = match overlap {
+            Some((end, start)) if end == task_start => {
+                driver = StartDriver::Overlap;
+                start
+            }
+            Some((_, start)) => {
+                let mut ready = task_start;
+                raise(&mut ready, &mut driver, start, StartDriver::Overlap);
+                ready
+            }
+            None => task_start,
+        };
+        if let Some(pickup) = self
+            .pickups
+            .get(id)
+            .and_then(|p| schedule.assignment_for_activity(p))
+        {
+            candidates = vec![pickup.resource_id.clone()];
+            raise(&mut ready, &mut driver, pickup.end_ms, StartDriver::Pickup);
+        }
+        let handoff = self.handoffs.get(id).copied();
+        if let Some(from) = handoff.and_then(|(from, _)| schedule.assignment_for_activity(from)) {
+            let receive = from.end_ms - handoff.map_or(0, |(_, tol)| tol);
+            raise(&mut ready, &mut driver, receive, StartDriver::Handoff);
+        }
+        let max_delay = self.max_delays.get(id).copied();
+        if let Some(from) = max_delay.and_then(|(from, _)| schedule.assignment_for_activity(from)) {
+            raise(&mut ready, &mut driver, from.end_ms, StartDriver::MaxDelay);
+        }
+        let window = self.windows.get(id).copied();
+        if let Some((window_start, _)) = window {
+            raise(
+                &mut ready,
+                &mut driver,
+                window_start,
+                StartDriver::TimeWindow,
+            );
+        }
+
+        let placing = Placing {
+            task,
+            activity,
+            task_start,
+            task_location,
+            planned,
+            ready,
+            driver,
+            overlap,
+            handoff,
+            max_delay,
+            window,
+            latest_start: self.latest_times.get(id).map(|t| t.latest_start_ms),
+            gap_insertion: false,
+        };
+        Some((placing, candidates))
+    }
+}
+
+/// State of a run, updated as activities are placed
+struct Progress<'a> {
+    schedule: Schedule,
+    /// When each resource is free again
+    resource_available: HashMap<String, i64>,
+    /// Category last run per resource, for setups
+    last_category: HashMap<String, String>,
+    /// Runs per (resource, category), for learning curves
+    repetitions: HashMap<(String, String), u32>,
+    /// Processing time and runs since the last replacement (tool life)
+    usage: HashMap<String, (i64, u32)>,
+    /// Assigned time per resource, for workload balancing
+    workload: HashMap<String, i64>,
+    /// Current location per resource, for travel
+    resource_location: HashMap<String, String>,
+    /// Intervals already placed per mutual-exclusion group
+    exclusive: HashMap<&'a str, Vec<(i64, i64)>>,
+}
+
+impl<'a> Progress<'a> {
+    /// Work on a resource after the runs of the category it has done
+    fn learned_work(&self, resource: Option<&Resource>, category: &str, process_ms: i64) -> i64 {
+        resource.map_or(process_ms, |r| {
+            let done = self
+                .repetitions
+                .get(&(r.id.clone(), category.to_string()))
+                .copied()
+                .unwrap_or(0);
+            r.learned_work(process_ms, done)
+        })
+    }
+
+    /// Replacement time if the resource's tool wears out on this run
+    fn replacement_due(&self, resource: Option<&Resource>, exec: i64) -> Option<i64> {
+        let r = resource?;
+        let budget = r.usage_budget.as_ref()?;
+        let (used, runs) = self
+            .usage
+            .get(&r.id)
+            .copied()
+            .unwrap_or((budget.used_ms, budget.used_activities));
+        budget
+            .needs_replacement(used, runs, exec)
+            .then_some(budget.replacement_ms)
+    }
+
+    /// Count a run against the resource's tool life, replacing the tool
+    /// (as downtime before `start`) if due
+    fn track_usage(
+        &mut self,
+        resource: Option<&Resource>,
+        start: i64,
+        exec: i64,
+        replacement: Option<i64>,
+    ) {
+        let Some(r) = resource else {
+            return;
+        };
+        let Some(budget) = r.usage_budget.as_ref() else {
+            return;
+        };
+        let used = self
+            .usage
+            .entry(r.id.clone())
+            .or_insert((budget.used_ms, budget.used_activities));
+        if let Some(replacement_ms) = replacement {
+            self.schedule.downtime.push(Downtime {
+                resource_id: r.id.clone(),
+                start_ms: start - replacement_ms,
+                end_ms: start,
+                reason: "tool replacement".to_string(),
+            });
+            *used = (0, 0);
+        }
+        used.0 += exec;
+        used.1 += 1;
+    }
+
+    /// Take a resource for an interval; an inserted activity keeps the
+    /// resource tail and category, a shared resource its availability
+    fn occupy(
+        &mut self,
+        resource_id: &str,
+        category: &str,
+        (start, end): (i64, i64),
+        inserted: bool,
+        shared: bool,
+    ) {
+        if !shared && !inserted {
+            self.resource_available.insert(resource_id.to_string(), end);
+        }
+        *self.workload.entry(resource_id.to_string()).or_insert(0) += end - start;
+        if !inserted {
+            self.last_category
+                .insert(resource_id.to_string(), category.to_string());
+        }
+        *self
+            .repetitions
+            .entry((resource_id.to_string(), category.to_string()))
+            .or_insert(0) += 1;
+    }
+
+    /// Intervals booked in the activity's mutual-exclusion groups
+    fn excluded(&self, lookups: &Lookups, activity: &Activity) -> Vec<(i64, i64)> {
+        lookups
+            .exclusion_groups
+            .get(activity.id.as_str())
+            .into_iter()
+            .flatten()
+            .filter_map(|g| self.exclusive.get(g))
+            .flatten()
+            .copied()
+            .collect()
+    }
+
+    /// Book a placed interval in the activity's mutual-exclusion groups,
+    /// flagging overlaps with other members
+    fn book_exclusive(&mut self, lookups: &Lookups<'a>, activity: &Activity, start: i64, end: i64) {
+        for group in lookups
+            .exclusion_groups
+            .get(activity.id.as_str())
+            .into_iter()
+            .flatten()
+        {
+            let booked = self.exclusive.entry(group).or_default();
+            if booked.iter().any(|&(s, e)| s < end && start < e) {
+                self.schedule.add_violation(Violation::capacity_exceeded(
+                    group,
+                    &format!(
+                        "Activity {} overlaps another member of group {}",
+                        activity.id, group
+                    ),
+                ));
+            }
+            booked.push((start, end));
+        }
+    }
+}
+
+/// An activity being placed, with everything known before candidates
+/// are compared
+struct Placing<'a> {
+    task: &'a Task,
+    activity: &'a Activity,
+    /// End of the task's previous activity
+    task_start: i64,
+    /// Where the task's previous activity took place
+    task_location: Option<&'a str>,
+    /// Baseline assignment of a pending activity
+    planned: Option<&'a Assignment>,
+    /// Earliest start allowed by the task and constraints
+    ready: i64,
+    /// What determined `ready`
+    driver: StartDriver,
+    /// Predecessor end and earliest start of an overlapping successor
+    overlap: Option<(i64, i64)>,
+    /// Handing-over activity and tolerance
+    handoff: Option<(&'a str, i64)>,
+    /// Preceding activity and maximum delay after it
+    max_delay: Option<(&'a str, i64)>,
+    window: Option<(i64, i64)>,
+    /// Latest start meeting downstream deadlines (deadline pruning)
+    latest_start: Option<i64>,
+    /// May be inserted into an idle gap (nothing moves the start after
+    /// selection)
+    gap_insertion: bool,
+}
+
+/// Time an activity takes on a resource
+#[derive(Debug, Clone, Copy)]
+struct Work {
+    setup: i64,
+    /// Work after learning, before efficiency
+    process: i64,
+    /// Execution time at the resource's efficiency
+    exec: i64,
+}
+
+/// Earliest placement of an activity on one candidate resource
+struct Evaluation<'c> {
+    resource_id: &'c str,
+    start: i64,
+    finish: i64,
+    work: Work,
+    /// Placed in an idle gap before the resource's last assignment
+    inserted: bool,
+    /// When the resource could take the activity
+    free: i64,
+    /// Tool replacement due before the start
+    replacement: Option<i64>,
+    /// Finish time the candidate competes with
+    score: i64,
+    /// Capacity left on a multi-dimensional resource (best fit)
+    slack: f64,
+    /// Starts past the activity's latest start
+    late: bool,
+}
+
+/// Where and when an activity was placed
+struct Placed {
+    resources: Vec<String>,
+    start: i64,
+    end: i64,
+    setup: i64,
+    /// When the resources could take the activity
+    free: i64,
+}
+
+/// Raise the ready time to `to`, blaming `why` if it moves it
+fn raise(ready: &mut i64, driver: &mut StartDriver, to: i64, why: StartDriver) {
+    if to > *ready {
+        *ready = to;
+        *driver = why;
+    }
+}
+
+/// Trace record of a candidate that cannot take the activity
+fn infeasible(resource_id: &str, outcome: CandidateOutcome) -> CandidateRecord {
+    CandidateRecord {
+        resource_id: resource_id.to_string(),
+        start_ms: None,
+        finish_ms: None,
+        score: None,
+        outcome,
+    }
+}
+
+/// Verdicts of the feasible candidates once the best one is known
+fn settle_outcomes(
+    records: &mut [CandidateRecord],
+    best: &str,
+    best_score: i64,
+    latest_start: Option<i64>,
+) {
+    for record in records.iter_mut().filter(|r| r.score.is_some()) {
+        let late = latest_start.is_some_and(|l| record.start_ms.is_some_and(|s| s > l));
+        record.outcome = if record.resource_id == best {
+            CandidateOutcome::Chosen
+        } else if record.score > Some(best_score) {
+            CandidateOutcome::Outscored
+        } else if late {
+            CandidateOutcome::PastLatestStart
+        } else {
+            CandidateOutcome::TieBreak
+        };
+    }
+}
+
+/// Trace records of a gang: members are chosen, the rest were free later
+fn gang_records(
+    candidates: &[String],
+    chosen: &[String],
+    resource_available: &HashMap<String, i64>,
+    start: i64,
+    end: i64,
+) -> Vec<CandidateRecord> {
+    candidates
+        .iter()
+        .map(|c| match resource_available.get(c) {
+            Some(_) if chosen.contains(c) => CandidateRecord {
+                resource_id: c.clone(),
+                start_ms: Some(start),
+                finish_ms: Some(end),
+                score: None,
+                outcome: CandidateOutcome::Chosen,
+            },
+            Some(&available) => CandidateRecord {
+                resource_id: c.clone(),
+                start_ms: Some(available),
+                finish_ms: None,
+                score: None,
+                outcome: CandidateOutcome::Outscored,
+            },
+            None => infeasible(c, CandidateOutcome::UnknownResource),
+        })
+        .collect()
+}
+
+/// Trace entry of an activity's placement (`placed`: resources, start, end)
+fn decision(
+    task: &Task,
+    activity: &Activity,
+    ready_ms: i64,
+    candidates: Vec<CandidateRecord>,
+    placed: Option<(Vec<String>, i64, i64)>,
+) -> Decision {
+    let (chosen, start_ms, end_ms) = match placed {
+        Some((chosen, start, end)) => (chosen, Some(start), Some(end)),
+        None => (Vec::new(), None, None),
+    };
+    Decision {
+        sequence: 0,
+        task_id: task.id.clone(),
+        activity_id: activity.id.clone(),
+        ready_ms,
+        candidates,
+        chosen,
+        start_ms,
+        end_ms,
+    }
+}
+
+/// Latest end of the booked intervals overlapping `[start, end)`
+fn overlap_end(booked: &[(i64, i64)], start: i64, end: i64) -> Option<i64> {
+    booked
+        .iter()
+        .filter(|&&(s, e)| s < end && start < e)
+        .map(|&(_, e)| e)
+        .max()
+}
+
+/// Setup crew member free earliest, with its availability
+fn earliest_setter<'a>(
+    crew: &'a [String],
+    resource_available: &HashMap<String, i64>,
+) -> Option<(&'a str, i64)> {
+    crew.iter()
+        .filter_map(|c| resource_available.get(c).map(|&t| (c.as_str(), t)))
+        .min_by_key(|&(_, t)| t)
+}
+
+/// Resources of a multi-resource activity with their earliest availability
+type Members = Vec<(String, i64)>;
+
+/// Resources for a gang activity with their earliest availability
+///
+/// Each requirement takes its `quantity` earliest-available distinct
+/// candidates; `None` if some requirement cannot be staffed.
+fn gang_members(
+    activity: &Activity,
+    resource_available: &HashMap<String, i64>,
+    resource_map: &HashMap<&str, &Resource>,
+    ready_ms: i64,
+) -> Option<Members> {
+    let mut members: Vec<(String, i64)> = Vec::new();
+    for requirement in &activity.resource_requirements {
+        let picked = earliest_candidates(
+            activity,
+            &requirement.candidates,
+            requirement.quantity.max(1) as usize,
+            &members,
+            resource_available,
+            resource_map,
+            ready_ms,
+        )?;
+        members.extend(picked);
+    }
+    Some(members)
+}
+
+/// Allocation of a malleable activity finishing earliest, with the
+/// nominal work duration at that level
+///
+/// Units come from the first requirement; ties go to fewer units.
+fn malleable_members(
+    activity: &Activity,
+    malleability: &Malleability,
+    resource_available: &HashMap<String, i64>,
+    resource_map: &HashMap<&str, &Resource>,
+    ready_ms: i64,
+) -> Option<(Members, i64)> {
+    let requirement = activity.resource_requirements.first()?;
+    let mut best: Option<(i64, Members, i64)> = None;
+    for units in malleability.min_units..=malleability.max_units {
+        let Some(members) = earliest_candidates(
+            activity,
+            &requirement.candidates,
+            units as usize,
+            &[],
+            resource_available,
+            resource_map,
+            ready_ms,
+        ) else {
+            break;
+        };
+        let work_ms = malleability.duration_ms(activity.duration.process_ms, units);
+        let finish = members.iter().map(|m| m.1).max().unwrap_or(ready_ms) + work_ms;
+        if best.as_ref().is_none_or(|(f, _, _)| finish < *f) {
+            best = Some((finish, members, work_ms));
+        }
+    }
+    best.map(|(_, members, work_ms)| (members, work_ms))
+}
+
+/// `count` earliest-available distinct candidates not already `taken`
+fn earliest_candidates(
+    activity: &Activity,
+    candidates: &[String],
+    count: usize,
+    taken: &[(String, i64)],
+    resource_available: &HashMap<String, i64>,
+    resource_map: &HashMap<&str, &Resource>,
+    ready_ms: i64,
+) -> Option<Members> {
+    let mut options: Vec<(i64, &String)> = candidates
+        .iter()
+        .filter(|c| taken.iter().all(|(m, _)| m != *c))
+        .filter(|c| {
+            resource_map
+                .get(c.as_str())
+                .is_none_or(|r| r.can_host(&activity.dimension_demands))
+        })
+        .filter_map(|c| resource_available.get(c).map(|&t| (t.max(ready_ms), c)))
+        .collect();
+    options.sort();
+    options.dedup_by(|a, b| a.1 == b.1);
+
+    if options.len() < count {
+        return None;
+    }
+    Some(
+        options
+            .into_iter()
+            .take(count)
+            .map(|(t, c)| (c.clone(), t))
+            .collect(),
+    )
+}
+
+/// Earliest start at or after `ready_ms` where `demands` fit beside the
+/// activities already running on a multi-dimensional resource
+fn packed_start(
+    resource: &Resource,
+    booked: &[&Assignment],
+    demand_of: &HashMap<&str, &HashMap<String, f64>>,
+    demands: &HashMap<String, f64>,
+    ready_ms: i64,
+    duration_ms: i64,
+) -> i64 {
+    let mut times: Vec<i64> = std::iter::once(ready_ms)
+        .chain(
+            booked
+                .iter()
+                .map(|a| a.end_ms)
+                .filter(|&end| end > ready_ms),
+        )
+        .collect();
+    times.sort_unstable();
+    let last = *times.last().unwrap_or(&ready_ms);
+    times
+        .into_iter()
+        .find(|&t| {
+            resource.dimension_capacity.iter().all(|(dim, cap)| {
+                let demand = demands.get(dim).copied().unwrap_or(0.0);
+                demand == 0.0
+                    || peak_usage(booked, demand_of, dim, t, t + duration_ms) + demand <= cap + 1e-9
+            })
+        })
+        .unwrap_or(last)
+}
+
+/// Highest summed demand in a dimension over `[start_ms, end_ms)`
+fn peak_usage(
+    booked: &[&Assignment],
+    demand_of: &HashMap<&str, &HashMap<String, f64>>,
+    dimension: &str,
+    start_ms: i64,
+    end_ms: i64,
+) -> f64 {
+    let usage_at = |t: i64| -> f64 {
+        booked
+            .iter()
+            .filter(|a| a.start_ms <= t && t < a.end_ms)
+            .filter_map(|a| demand_of.get(a.activity_id.as_str()))
+            .map(|d| d.get(dimension).copied().unwrap_or(0.0))
+            .sum()
+    };
+    std::iter::once(start_ms)
+        .chain(
+            booked
+                .iter()
+                .map(|a| a.start_ms)
+                .filter(|&s| s > start_ms && s < end_ms),
+        )
+        .map(usage_at)
+        .fold(0.0, f64::max)
+}
+
+/// Remaining capacity fraction (summed over dimensions) after placing
+/// `demands` at `start_ms`
+fn packing_slack(
+    resource: &Resource,
+    booked: &[&Assignment],
+    demand_of: &HashMap<&str, &HashMap<String, f64>>,
+    demands: &HashMap<String, f64>,
+    start_ms: i64,
+) -> f64 {
+    resource
+        .dimension_capacity
+        .iter()
+        .filter(|(_, cap)| **cap > 0.0)
+        .map(|(dim, cap)| {
+            let used = peak_usage(booked, demand_of, dim, start_ms, start_ms + 1);
+            (cap - used - demands.get(dim).copied().unwrap_or(0.0)) / cap
+        })
+        .sum()
+}
+
+/// Where an activity takes place on a resource
+fn location_of<'a>(activity: &'a Activity, resource: Option<&&'a Resource>) -> Option<&'a str> {
+    activity
+        .location
+        .as_deref()
+        .or_else(|| resource.and_then(|r| r.location.as_deref()))
+}
+
+impl Default for SimpleScheduler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{
+        Activity, ActivityDuration, ActivityProgress, PreferenceReport, PriorityClass,
+    };
+
+    fn create_test_scenario() -> (Vec<Task>, Vec<Resource>) {
+        let tasks = vec![
+            Task::new("T1").with_priority(5).with_activity(
+                Activity::new("T1-A1", "T1", 1)
+                    .with_duration(ActivityDuration::fixed(5000))
+                    .with_resources("machine", vec!["M1".into(), "M2".into()]),
+            ),
+            Task::new("T2").with_priority(3).with_activity(
+                Activity::new("T2-A1", "T2", 1)
+                    .with_duration(ActivityDuration::fixed(3000))
+                    .with_resources("machine", vec!["M1".into()]),
+            ),
+        ];
+
+        let resources = vec![
+            Resource::primary("M1").with_efficiency(1.0),
+            Resource::primary("M2").with_efficiency(0.9),
+        ];
+
+        (tasks, resources)
+    }
+
+    #[test]
+    fn test_simple_scheduling() {
+        let (tasks, resources) = create_test_scenario();
+        let scheduler = SimpleScheduler::new();
+
+        let schedule = scheduler.schedule(&tasks, &resources, 0);
+
+        assert_eq!(schedule.assignment_count(), 2);
+        assert!(schedule.makespan_ms > 0);
+    }
+
+    #[test]
+    fn test_priority_ordering() {
+        let (tasks, resources) = create_test_scenario();
+        let scheduler = SimpleScheduler::new();
+
+        let schedule = scheduler.schedule(&tasks, &resources, 0);
+
+        // T1 (priority 5) should be scheduled before T2 (priority 3)
+        let t1_end = schedule.task_completion_time("T1").unwrap();
+        let t2_start = schedule.assignment_for_activity("T2-A1").unwrap().start_ms;
+
+        // T1 should start at 0 or T2 should start after T1
+        let t1_start = schedule.assignment_for_activity("T1-A1").unwrap().start_ms;
+        assert!(t1_start <= t2_start || t1_end <= t2_start);
+    }
+
+    #[test]
+    fn test_multiple_activities() {
+        let task = Task::new("T1")
+            .with_activity(
+                Activity::new("T1-A1", "T1", 1)
+                    .with_duration(ActivityDuration::fixed(3000))
+                    .with_resources("machine", vec!["M1".into()]),
+            )
+            .with_activity(
+                Activity::new("T1-A2", "T1", 2)
+                    .with_duration(ActivityDuration::fixed(2000))
+                    .with_resources("machine", vec!["M1".into()]),
+            );
+
+        let resources = vec![Resource::primary("M1")];
+        let scheduler = SimpleScheduler::new();
+
+        let schedule = scheduler.schedule(&[task], &resources, 0);
+
+        assert_eq!(schedule.assignment_count(), 2);
+
+        // A2 should start after A1
+        let a1 = schedule.assignment_for_activity("T1-A1").unwrap();
+        let a2 = schedule.assignment_for_activity("T1-A2").unwrap();
+
+        assert!(a2.start_ms >= a1.end_ms);
+    }
+
+    #[test]
+    fn test_energy_tariff_shifting() {
+        let hour = 3_600_000;
+        let tasks = vec![Task::new("T1").with_activity(
+            Activity::new("T1-A1", "T1", 1)
+                .with_duration(ActivityDuration::fixed(hour))
+                .with_energy_demand(50.0)
+                .with_resources("machine", vec!["M1".into()]),
+        )];
+        let resources = vec![Resource::primary("M1")];
+        let tariff = EnergyTariff::new(0.1).with_period(0, 2 * hour, 0.5);
+
+        let plain = SimpleScheduler::new().schedule(&tasks, &resources, 0);
+        let shifted = SimpleScheduler::new()
+            .with_energy_tariff(tariff.clone(), 4 * hour)
+            .schedule(&tasks, &resources, 0);
+
+        assert_eq!(
+            shifted.assignment_for_activity("T1-A1").unwrap().start_ms,
+            2 * hour
+        );
+        assert!(tariff.schedule_cost(&shifted, &tasks) < tariff.schedule_cost(&plain, &tasks));
+    }
+
+    #[test]
+    fn test_workload_balancing() {
+        // Staggered prep steps: both nurses are idle whenever a care
+        // activity becomes ready
+        let tasks: Vec<Task> = (0..4)
+            .map(|i| {
+                let id = format!("T{}", i);
+                Task::new(&id)
+                    .with_activity(
+                        Activity::new(&format!("{}-prep", id), &id, 1)
+                            .with_duration(ActivityDuration::fixed(1000 * (i + 1)))
+                            .with_resources("room", vec![format!("R{}", i)]),
+                    )
+                    .with_activity(
+                        Activity::new(&format!("{}-care", id), &id, 2)
+                            .with_duration(ActivityDuration::fixed(100))
+                            .with_resources("nurse", vec!["N1".into(), "N2".into()]),
+                    )
+            })
+            .collect();
+        let resources: Vec<Resource> = (0..4)
+            .map(|i| Resource::primary(&format!("R{}", i)))
+            .chain([Resource::human("N1"), Resource::human("N2")])
+            .collect();
+
+        let greedy = SimpleScheduler::new().schedule(&tasks, &resources, 0);
+        let balanced = SimpleScheduler::new()
+            .with_workload_balancing(true)
+            .schedule(&tasks, &resources, 0);
+
+        let group = vec!["N1".to_string(), "N2".to_string()];
+        assert_eq!(greedy.workload_deviation_ms(&group), 400);
+        assert_eq!(balanced.workload_deviation_ms(&group), 0);
+    }
+
+    #[test]
+    fn test_working_time_rules() {
+        let hour = 3_600_000;
+        let tasks: Vec<Task> = (0..3)
+            .map(|i| {
+                let id = format!("T{}", i);
+                Task::new(&id).with_activity(
+                    Activity::new(&format!("{}-A1", id), &id, 1)
+                        .with_duration(ActivityDuration::fixed(4 * hour))
+                        .with_resources("nurse", vec!["N1".into()]),
+                )
+            })
+            .collect();
+        let resources = vec![Resource::human("N1").with_working_time_rules(
+            WorkingTimeRules::new()
+                .with_max_daily(8 * hour)
+                .with_min_rest(hour),
+        )];
+
+        let schedule = SimpleScheduler::new().schedule(&tasks, &resources, 0);
+        let mut starts: Vec<i64> = schedule.assignments.iter().map(|a| a.start_ms).collect();
+        starts.sort();
+
+        // Rest gap after the first shift; third shift moves to the next day
+        assert_eq!(starts, vec![0, 5 * hour, 24 * hour]);
+        let booked = schedule.assignments_for_resource("N1");
+        assert!(resources[0]
+            .working_time
+            .as_ref()
+            .unwrap()
+            .violations("N1", &booked)
+            .is_empty());
+    }
+
+    #[test]
+    fn test_preferences() {
+        let tasks = vec![
+            Task::new("T1").with_priority(2).with_activity(
+                Activity::new("T1-A1", "T1", 1)
+                    .with_duration(ActivityDuration::fixed(1000))
+                    .with_resources("instructor", vec!["I1".into(), "I2".into()]),
+            ),
+            Task::new("T2").with_priority(1).with_activity(
+                Activity::new("T2-A1", "T2", 1)
+                    .with_duration(ActivityDuration::fixed(1000))
+                    .with_resources("instructor", vec!["I1".into(), "I2".into()])
+                    .with_preferred_resource("I1", 1.0),
+            ),
+        ];
+        let resources = vec![Resource::human("I1"), Resource::human("I2")];
+
+        let plain = SimpleScheduler::new().schedule(&tasks, &resources, 0);
+        let preferred = SimpleScheduler::new()
+            .with_preferences(2000)
+            .schedule(&tasks, &resources, 0);
+
+        assert_eq!(
+            plain.assignment_for_activity("T2-A1").unwrap().resource_id,
+            "I2"
+        );
+        let a = preferred.assignment_for_activity("T2-A1").unwrap();
+        assert_eq!((a.resource_id.as_str(), a.start_ms), ("I1", 1000));
+
+        let rate =
+            |s: &Schedule| PreferenceReport::evaluate(s, &tasks, &resources).satisfaction_rate();
+        assert!(rate(&preferred) > rate(&plain));
+    }
+
+    #[test]
+    fn test_travel_times() {
+        let task = Task::new("T1")
+            .with_activity(
+                Activity::new("T1-A1", "T1", 1)
+                    .with_duration(ActivityDuration::fixed(1000))
+                    .with_resources("machine", vec!["M1".into()]),
+            )
+            .with_activity(
+                Activity::new("T1-A2", "T1", 2)
+                    .with_duration(ActivityDuration::fixed(1000))
+                    .with_resources("machine", vec!["M2".into()]),
+            );
+        let resources = vec![
+            Resource::primary("M1").with_location("hall-a"),
+            Resource::primary("M2").with_location("hall-b"),
+        ];
+        let travel = TravelTimeMatrix::new().with_symmetric("hall-a", "hall-b", 500);
+
+        let schedule =
+            SimpleScheduler::new()
+                .with_travel_times(travel)
+                .schedule(&[task], &resources, 0);
+
+        let a2 = schedule.assignment_for_activity("T1-A2").unwrap();
+        assert_eq!(a2.start_ms, 1500);
+    }
+
+    #[test]
+    fn test_mobile_resource_travel() {
+        let tasks = vec![
+            Task::new("J1").with_priority(2).with_activity(
+                Activity::new("J1-A1", "J1", 1)
+                    .with_duration(ActivityDuration::fixed(1000))
+                    .with_location("site-1")
+                    .with_resources("technician", vec!["W1".into()]),
+            ),
+            Task::new("J2").with_priority(1).with_activity(
+                Activity::new("J2-A1", "J2", 1)
+                    .with_duration(ActivityDuration::fixed(1000))
+                    .with_location("site-2")
+                    .with_resources("technician", vec!["W1".into()]),
+            ),
+        ];
+        let resources = vec![Resource::human("W1").with_location("depot")];
+        let travel = TravelTimeMatrix::new()
+            .with_symmetric("depot", "site-1", 300)
+            .with_symmetric("site-1", "site-2", 700);
+
+        let schedule = SimpleScheduler::new()
+            .with_travel_times(travel)
+            .schedule(&tasks, &resources, 0);
+
+        assert_eq!(
+            schedule.assignment_for_activity("J1-A1").unwrap().start_ms,
+            300
+        );
+        assert_eq!(
+            schedule.assignment_for_activity("J2-A1").unwrap().start_ms,
+            2000
+        );
+    }
+
+    #[test]
+    fn test_vehicle_capacity() {
+        let tasks = vec![
+            Task::new("S1").with_load("kg", 800.0).with_activity(
+                Activity::new("S1-A1", "S1", 1)
+                    .with_duration(ActivityDuration::fixed(1000))
+                    .with_resources("vehicle", vec!["Van".into(), "Truck".into()]),
+            ),
+            Task::new("S2").with_load("kg", 5000.0).with_activity(
+                Activity::new("S2-A1", "S2", 1)
+                    .with_duration(ActivityDuration::fixed(1000))
+                    .with_resources("vehicle", vec!["Van".into(), "Truck".into()]),
+            ),
+        ];
+        let resources = vec![
+            Resource::primary("Van").with_load_capacity("kg", 1000.0),
+            Resource::primary("Truck").with_load_capacity("kg", 3000.0),
+        ];
+
+        let schedule = SimpleScheduler::new().schedule(&tasks, &resources, 0);
+
+        assert_eq!(schedule.assignment_count(), 1);
+        assert_eq!(schedule.violations.len(), 1);
+        assert_eq!(schedule.violations[0].entity_id, "S2-A1");
+    }
+
+    #[test]
+    fn test_vehicle_load_on_board() {
+        // S1 stays on board from its pickup until its delivery at 10000
+        let stop = |id: &str, task: &str, seq: i32| {
+            Activity::new(id, task, seq)
+                .with_duration(ActivityDuration::fixed(1000))
+                .with_resources("vehicle", vec!["V1".into()])
+        };
+        let tasks = vec![
+            Task::new("S1")
+                .with_priority(5)
+                .with_load("kg", 800.0)
+                .with_activity(stop("S1-P", "S1", 1))
+                .with_activity(stop("S1-D", "S1", 2)),
+            Task::new("S2")
+                .with_priority(1)
+                .with_load("kg", 800.0)
+                .with_activity(stop("S2-P", "S2", 1)),
+        ];
+        let resources = vec![Resource::primary("V1").with_load_capacity("kg", 1000.0)];
+        let constraints = vec![
+            Constraint::pickup_delivery("S1-P", "S1-D"),
+            Constraint::time_window("S1-D", 10_000, 20_000),
+        ];
+
+        let schedule = SimpleScheduler::new()
+            .with_constraints(constraints)
+            .schedule(&tasks, &resources, 0);
+
+        // The idle gap would overload V1; S2 waits for the delivery
+        let s2 = schedule.assignment_for_activity("S2-P").unwrap();
+        assert_eq!((s2.start_ms, s2.end_ms), (11_000, 12_000));
+        assert!(schedule.violations.is_empty());
+        assert!(crate::validation::validate_load_capacity(&schedule, &tasks, &resources).is_valid);
+    }
+
+    #[test]
+    fn test_pickup_delivery() {
+        let shipment = |id: &str, priority: i32| {
+            Task::new(id)
+                .with_priority(priority)
+                .with_activity(
+                    Activity::new(&format!("{}-P", id), id, 1)
+                        .with_duration(ActivityDuration::fixed(100))
+                        .with_resources("vehicle", vec!["V1".into(), "V2".into()]),
+                )
+                .with_activity(
+                    Activity::new(&format!("{}-D", id), id, 2)
+                        .with_duration(ActivityDuration::fixed(100))
+                        .with_resources("vehicle", vec!["V2".into(), "V1".into()]),
+                )
+        };
+        let tasks = vec![shipment("S1", 2), shipment("S2", 1)];
+        let resources = vec![Resource::primary("V1"), Resource::primary("V2")];
+        let constraints = vec![
+            Constraint::pickup_delivery("S1-P", "S1-D"),
+            Constraint::pickup_delivery("S2-P", "S2-D"),
+            Constraint::time_window("S2-P", 1000, 2000),
+            Constraint::time_window("S2-D", 0, 1050),
+        ];
+
+        let schedule = SimpleScheduler::new()
+            .with_constraints(constraints.clone())
+            .schedule(&tasks, &resources, 0);
+
+        let (p1, d1) = (
+            schedule.assignment_for_activity("S1-P").unwrap(),
+            schedule.assignment_for_activity("S1-D").unwrap(),
+        );
+        assert_eq!(p1.resource_id, d1.resource_id);
+        assert!(d1.start_ms >= p1.end_ms);
+        assert_eq!(
+            schedule.assignment_for_activity("S2-P").unwrap().start_ms,
+            1000
+        );
+
+        // S2 delivery cannot meet its window once pickup waits until 1000
+        assert_eq!(schedule.violations.len(), 1);
+        assert_eq!(schedule.violations[0].entity_id, "S2-D");
+        let result = crate::validation::validate_schedule(&schedule, &tasks, &constraints);
+        assert_eq!(result.errors.len(), 1);
+    }
+
+    #[test]
+    fn test_multi_dimensional_packing() {
+        let vm = |id: &str, cpu: f64, mem: f64| {
+            Task::new(id).with_activity(
+                Activity::new(&format!("{}-run", id), id, 1)
+                    .with_duration(ActivityDuration::fixed(1000))
+                    .with_dimension_demand("cpu", cpu)
+                    .with_dimension_demand("mem", mem)
+                    .with_resources("host", vec!["Small".into(), "Large".into()]),
+            )
+        };
+        let tasks = vec![
+            vm("VM1", 4.0, 8.0),
+            vm("VM2", 2.0, 4.0),
+            vm("VM3", 2.0, 12.0),
+            vm("VM4", 16.0, 1.0),
+        ];
+        let resources = vec![
+            Resource::primary("Large")
+                .with_dimension_capacity("cpu", 8.0)
+                .with_dimension_capacity("mem", 16.0),
+            Resource::primary("Small")
+                .with_dimension_capacity("cpu", 4.0)
+                .with_dimension_capacity("mem", 8.0),
+        ];
+
+        let schedule = SimpleScheduler::new().schedule(&tasks, &resources, 0);
+
+        // VM1 fills Small exactly (best fit); VM2 and VM3 share Large
+        let placed = |id: &str| schedule.assignment_for_activity(id).unwrap();
+        assert_eq!(placed("VM1-run").resource_id, "Small");
+        assert_eq!(placed("VM2-run").resource_id, "Large");
+        assert_eq!(placed("VM3-run").start_ms, 0);
+        assert!(schedule.assignment_for_activity("VM4-run").is_none());
+        assert_eq!(schedule.violations.len(), 1);
+        assert!(
+            crate::validation::validate_dimension_capacity(&schedule, &tasks, &resources).is_valid
+        );
+    }
+
+    #[test]
+    fn test_reliability_preference() {
+        let job = |id: &str, priority: i32| {
+            Task::new(id).with_priority(priority).with_activity(
+                Activity::new(&format!("{}-A1", id), id, 1)
+                    .with_duration(ActivityDuration::fixed(1000))
+                    .with_resources("vm", vec!["Spot".into(), "OnDemand".into()]),
+            )
+        };
+        let tasks = vec![job("Critical", 10), job("Batch", 1)];
+        let resources = vec![
+            Resource::primary("Spot").with_revocation_risk(0.5),
+            Resource::primary("OnDemand"),
+        ];
+
+        let plain = SimpleScheduler::new().schedule(&tasks, &resources, 0);
+        let robust = SimpleScheduler::new()
+            .with_reliability_preference(2000)
+            .schedule(&tasks, &resources, 0);
+
+        let resource_of =
+            |s: &Schedule, id: &str| s.assignment_for_activity(id).unwrap().resource_id.clone();
+        assert_eq!(resource_of(&plain, "Critical-A1"), "Spot");
+        assert_eq!(resource_of(&robust, "Critical-A1"), "OnDemand");
+        assert_eq!(resource_of(&robust, "Batch-A1"), "Spot");
+
+        let report = crate::scheduler::RevocationSimulator::new(500).evaluate(&robust, &resources);
+        assert_eq!(report.task_survival["Critical"], 1.0);
+    }
+
+    #[test]
+    fn test_gang_scheduling() {
+        let tasks = vec![
+            Task::new("Busy").with_priority(5).with_activity(
+                Activity::new("Busy-A1", "Busy", 1)
+                    .with_duration(ActivityDuration::fixed(2000))
+                    .with_resources("crane", vec!["C1".into()]),
+            ),
+            Task::new("Lift").with_priority(1).with_activity(
+                Activity::new("Lift-A1", "Lift", 1)
+                    .with_duration(ActivityDuration::fixed(1000))
+                    .with_gang("crane", vec!["C1".into(), "C2".into(), "C3".into()], 2)
+                    .with_resources("operator", vec!["O1".into()]),
+            ),
+        ];
+        let resources = vec![
+            Resource::primary("C1"),
+            Resource::primary("C2"),
+            Resource::primary("C3"),
+            Resource::human("O1"),
+        ];
+
+        let schedule = SimpleScheduler::new().schedule(&tasks, &resources, 0);
+
+        let lift = schedule.assignments_for_activity("Lift-A1");
+        let mut used: Vec<&str> = lift.iter().map(|a| a.resource_id.as_str()).collect();
+        used.sort();
+        assert_eq!(used, vec!["C2", "C3", "O1"]);
+        assert!(lift.iter().all(|a| a.start_ms == 0 && a.end_ms == 1000));
+        assert!(crate::validation::validate_gang(&schedule, &tasks).is_valid);
+
+        // Not enough cranes
+        let schedule = SimpleScheduler::new().schedule(&tasks, &resources[..2], 0);
+        assert!(schedule.assignment_for_activity("Lift-A1").is_none());
+        assert_eq!(schedule.violations.len(), 1);
+    }
+
+    #[test]
+    fn test_malleable_allocation() {
+        let workers = vec!["W1".to_string(), "W2".to_string(), "W3".to_string()];
+        let tasks = vec![
+            Task::new("Busy").with_priority(5).with_activity(
+                Activity::new("Busy-A1", "Busy", 1)
+                    .with_duration(ActivityDuration::fixed(6000))
+                    .with_resources("worker", vec!["W1".into()]),
+            ),
+            Task::new("Dig").with_priority(1).with_activity(
+                Activity::new("Dig-A1", "Dig", 1)
+                    .with_duration(ActivityDuration::fixed(8000))
+                    .with_resources("worker", workers.clone())
+                    .with_malleability(1, 3, 1.0),
+            ),
+        ];
+        let resources: Vec<Resource> = workers.iter().map(|w| Resource::human(w)).collect();
+
+        let schedule = SimpleScheduler::new().schedule(&tasks, &resources, 0);
+
+        // Two free workers finish at 4000; waiting for W1 would finish later
+        let dig = schedule.assignments_for_activity("Dig-A1");
+        let mut used: Vec<&str> = dig.iter().map(|a| a.resource_id.as_str()).collect();
+        used.sort();
+        assert_eq!(used, vec!["W2", "W3"]);
+        assert!(dig.iter().all(|a| a.start_ms == 0 && a.end_ms == 4000));
+        assert!(crate::validation::validate_malleable(&schedule, &tasks).is_valid);
+    }
+
+    #[test]
+    fn test_gang_with_calendars() {
+        let tasks = vec![Task::new("G").with_activity(
+            Activity::new("G-A1", "G", 1)
+                .with_duration(ActivityDuration::fixed(1000))
+                .with_gang("crane", vec!["C1".into(), "C2".into()], 2),
+        )];
+        let resources = vec![
+            Resource::primary("C1")
+                .with_calendar(crate::models::Calendar::new("late").with_window(5000, 20_000)),
+            Resource::primary("C2")
+                .with_calendar(crate::models::Calendar::new("early").with_window(3000, 20_000)),
+        ];
+
+        // Both cranes wait for the later calendar
+        let schedule = SimpleScheduler::new()
+            .with_calendars(true)
+            .schedule(&tasks, &resources, 0);
+        let lift = schedule.assignments_for_activity("G-A1");
+        assert_eq!(lift.len(), 2);
+        assert!(lift.iter().all(|a| a.start_ms == 5000 && a.end_ms == 6000));
+        assert!(schedule.violations.is_empty());
+    }
+
+    #[test]
+    fn test_gang_with_setups() {
+        let tasks = vec![
+            Task::new("W")
+                .with_category("A")
+                .with_priority(5)
+                .with_activity(
+                    Activity::new("W-A1", "W", 1)
+                        .with_duration(ActivityDuration::fixed(1000))
+                        .with_resources("crane", vec!["C1".into()]),
+                ),
+            Task::new("G")
+                .with_category("B")
+                .with_priority(1)
+                .with_activity(
+                    Activity::new("G-A1", "G", 1)
+                        .with_duration(ActivityDuration::fixed(1000))
+                        .with_gang("crane", vec!["C1".into(), "C2".into()], 2),
+                ),
+        ];
+        let resources = vec![Resource::primary("C1"), Resource::primary("C2")];
+        let mut matrix = crate::models::TransitionMatrix::new("setup", "C1");
+        matrix.set_transition("A", "B", 500);
+        let mut matrices = TransitionMatrixCollection::new();
+        matrices.add(matrix);
+
+        let schedule = SimpleScheduler::new()
+            .with_transition_matrices(matrices)
+            .schedule(&tasks, &resources, 0);
+
+        // C1's changeover holds both members
+        let lift = schedule.assignments_for_activity("G-A1");
+        assert_eq!(lift.len(), 2);
+        assert!(lift
+            .iter()
+            .all(|a| (a.start_ms, a.end_ms, a.setup_ms) == (1000, 2500, 500)));
+    }
+
+    #[test]
+    fn test_calendar_stretching() {
+        const HOUR: i64 = 3_600_000;
+        let tasks = vec![Task::new("T1").with_activity(
+            Activity::new("T1-A1", "T1", 1)
+                .with_duration(ActivityDuration::fixed(10 * HOUR))
+                .with_resources("machine", vec!["Day".into(), "Always".into()]),
+        )];
+        let resources = vec![
+            Resource::primary("Day").with_calendar(
+                crate::models::Calendar::new("day")
+                    .with_window(0, 8 * HOUR)
+                    .with_window(9 * HOUR, 17 * HOUR)
+                    .with_window(24 * HOUR, 32 * HOUR),
+            ),
+            Resource::primary("Always").with_efficiency(0.8),
+        ];
+
+        // Ignoring calendars, Day looks fastest
+        let schedule = SimpleScheduler::new().schedule(&tasks, &resources, 0);
+        assert_eq!(schedule.assignments[0].resource_id, "Day");
+        assert_eq!(schedule.assignments[0].end_ms, 10 * HOUR);
+
+        // Stretched over the break, Day finishes at 11h, still before 12.5h
+        let schedule = SimpleScheduler::new()
+            .with_calendars(true)
+            .schedule(&tasks, &resources, 0);
+        assert_eq!(schedule.assignments[0].resource_id, "Day");
+        assert_eq!(schedule.assignments[0].end_ms, 11 * HOUR);
+
+        // Starting inside the break moves to the next shift and spills
+        // into the next day
+        let schedule =
+            SimpleScheduler::new()
+                .with_calendars(true)
+                .schedule(&tasks, &resources[..1], 8 * HOUR);
+        assert_eq!(schedule.assignments[0].start_ms, 9 * HOUR);
+        assert_eq!(schedule.assignments[0].end_ms, 26 * HOUR);
+
+        // Not enough working time left
+        let schedule = SimpleScheduler::new().with_calendars(true).schedule(
+            &tasks,
+            &resources[..1],
+            20 * HOUR,
+        );
+        assert_eq!(schedule.assignment_count(), 0);
+    }
+
+    #[test]
+    fn test_learning_curve() {
+        use crate::models::LearningCurve;
+        let task = |id: &str, category: &str| {
+            Task::new(id).with_category(category).with_activity(
+                Activity::new(&format!("{}-A1", id), id, 1)
+                    .with_duration(ActivityDuration::fixed(1000))
+                    .with_resources("worker", vec!["W1".into(), "W2".into()]),
+            )
+        };
+        let resources = vec![
+            Resource::human("W1").with_learning_curve(LearningCurve::new(0.5)),
+            Resource::human("W2"),
+        ];
+
+        // The second weld on W1 takes half as long
+        let tasks = vec![task("T1", "weld"), task("T2", "weld"), task("T3", "weld")];
+        let schedule = SimpleScheduler::new().schedule(&tasks, &resources, 0);
+        let t3 = schedule.assignment_for_activity("T3-A1").unwrap();
+        assert_eq!((t3.resource_id.as_str(), t3.end_ms), ("W1", 1500));
+
+        // Experience does not carry over to another category
+        let tasks = vec![task("T1", "weld"), task("T2", "weld"), task("T3", "paint")];
+        let schedule = SimpleScheduler::new().schedule(&tasks, &resources, 0);
+        assert_eq!(
+            schedule.assignment_for_activity("T3-A1").unwrap().end_ms,
+            2000
+        );
+    }
+
+    #[test]
+    fn test_tool_life() {
+        use crate::models::{ResourceType, UsageBudget};
+        let tasks: Vec<Task> = (1..=3)
+            .map(|i| {
+                let id = format!("T{}", i);
+                Task::new(&id).with_activity(
+                    Activity::new(&format!("{}-A1", id), &id, 1)
+                        .with_duration(ActivityDuration::fixed(1000))
+                        .with_resources("tool", vec!["ToolA".into(), "ToolB".into()]),
+                )
+            })
+            .collect();
+        let resources = |replacement_ms| {
+            vec![
+                Resource::new("ToolA", ResourceType::Secondary)
+                    .with_usage_budget(UsageBudget::new(replacement_ms).with_max_activities(2)),
+                Resource::new("ToolB", ResourceType::Secondary).with_efficiency(0.25),
+            ]
+        };
+
+        // The third run waits for a quick replacement
+        let schedule = SimpleScheduler::new().schedule(&tasks, &resources(500), 0);
+        let t3 = schedule.assignment_for_activity("T3-A1").unwrap();
+        assert_eq!((t3.resource_id.as_str(), t3.start_ms), ("ToolA", 2500));
+        assert_eq!(schedule.downtime.len(), 1);
+        assert_eq!(
+            (schedule.downtime[0].start_ms, schedule.downtime[0].end_ms),
+            (2000, 2500)
+        );
+
+        // A slow replacement makes the spare tool worth it
+        let schedule = SimpleScheduler::new().schedule(&tasks, &resources(5000), 0);
+        let t3 = schedule.assignment_for_activity("T3-A1").unwrap();
+        assert_eq!((t3.resource_id.as_str(), t3.end_ms), ("ToolB", 4000));
+        assert!(schedule.downtime.is_empty());
+    }
+
+    #[test]
+    fn test_efficiency_periods() {
+        const HOUR: i64 = 3_600_000;
+        let tasks = vec![Task::new("T1").with_activity(
+            Activity::new("T1-A1", "T1", 1)
+                .with_duration(ActivityDuration::fixed(4 * HOUR))
+                .with_resources("machine", vec!["Night".into(), "Steady".into()]),
+        )];
+        let resources = vec![
+            Resource::primary("Night").with_efficiency_period(0, 4 * HOUR, 0.5),
+            Resource::primary("Steady").with_efficiency(0.8),
+        ];
+
+        // Half speed for the first four hours: 2h of work, then 2h at full speed
+        let schedule = SimpleScheduler::new().schedule(&tasks, &resources[..1], 0);
+        assert_eq!(schedule.assignments[0].end_ms, 6 * HOUR);
+
+        // Full speed by the time the work starts
+        let schedule = SimpleScheduler::new().schedule(&tasks, &resources[..1], 4 * HOUR);
+        assert_eq!(schedule.assignments[0].end_ms, 8 * HOUR);
+
+        // A steady 0.8 (5h) beats the slow start
+        let schedule = SimpleScheduler::new().schedule(&tasks, &resources, 0);
+        assert_eq!(schedule.assignments[0].resource_id, "Steady");
+        assert_eq!(schedule.assignments[0].end_ms, 5 * HOUR);
+    }
+
+    #[test]
+    fn test_confidence_buffers() {
+        let tasks = vec![
+            Task::new("Rush").with_category("rush").with_activity(
+                Activity::new("Rush-A1", "Rush", 1)
+                    .with_duration(ActivityDuration::fixed(1000))
+                    .with_duration_distribution(crate::models::DurationDistribution::Uniform {
+                        min_ms: 1000,
+                        max_ms: 3000,
+                    })
+                    .with_resources("machine", vec!["M1".into()]),
+            ),
+            Task::new("Std").with_activity(
+                Activity::new("Std-A1", "Std", 1)
+                    .with_duration(ActivityDuration::fixed(1000))
+                    .with_duration_distribution(crate::models::DurationDistribution::Uniform {
+                        min_ms: 1000,
+                        max_ms: 3000,
+                    })
+                    .with_resources("machine", vec!["M2".into()]),
+            ),
+        ];
+        let resources = vec![Resource::primary("M1"), Resource::primary("M2")];
+
+        let nominal = SimpleScheduler::new().schedule(&tasks, &resources, 0);
+        assert_eq!(nominal.makespan_ms, 1000);
+        assert!(nominal.planned_confidence.is_empty());
+
+        let schedule = SimpleScheduler::new()
+            .with_confidence(0.5)
+            .with_category_confidence("rush", 0.9)
+            .schedule(&tasks, &resources, 0);
+        let duration = |id: &str| schedule.assignment_for_activity(id).unwrap().duration_ms();
+        assert_eq!(duration("Rush-A1"), 2800);
+        assert_eq!(duration("Std-A1"), 2000);
+        assert_eq!(schedule.planned_confidence["Rush-A1"], 0.9);
+        assert_eq!(schedule.planned_confidence["Std-A1"], 0.5);
+    }
+
+    #[test]
+    fn test_setup_crew() {
+        let job = |id: &str, category: &str, machine: &str, priority: i32| {
+            Task::new(id)
+                .with_category(category)
+                .with_priority(priority)
+                .with_activity(
+                    Activity::new(&format!("{}-A1", id), id, 1)
+                        .with_duration(ActivityDuration::fixed(1000))
+                        .with_resources("machine", vec![machine.into()]),
+                )
+        };
+        let tasks = vec![
+            job("W1", "A", "M1", 5),
+            job("W2", "A", "M2", 4),
+            job("J1", "B", "M1", 3),
+            job("J2", "B", "M2", 2),
+        ];
+        let crew = vec!["Setter".to_string()];
+        let resources = vec![
+            Resource::primary("M1").with_setup_crew(crew.clone()),
+            Resource::primary("M2").with_setup_crew(crew),
+            Resource::human("Setter"),
+        ];
+        let mut matrices = TransitionMatrixCollection::new();
+        for machine in ["M1", "M2"] {
+            let mut matrix = crate::models::TransitionMatrix::new("setup", machine);
+            matrix.set_transition("A", "B", 500);
+            matrices.add(matrix);
+        }
+
+        let schedule = SimpleScheduler::new()
+            .with_transition_matrices(matrices)
+            .schedule(&tasks, &resources, 0);
+
+        // J2's changeover waits until the setter finishes on M1
+        let j2 = schedule.assignment_for_activity("J2-A1").unwrap();
+        assert_eq!((j2.start_ms, j2.end_ms, j2.setup_ms), (1500, 3000, 500));
+        let setter = schedule.assignments_for_resource("Setter");
+        assert_eq!(setter.len(), 2);
+        assert_eq!((setter[0].start_ms, setter[0].end_ms), (1000, 1500));
+        assert_eq!((setter[1].start_ms, setter[1].end_ms), (1500, 2000));
+    }
+
+    #[test]
+    fn test_gap_insertion() {
+        // T1 leaves M1 idle from 1000 to 3000 while its second step runs on M2
+        let step = |id: &str, task: &str, seq: i32, ms: i64, resource: &str| {
+            Activity::new(id, task, seq)
+                .with_duration(ActivityDuration::fixed(ms))
+                .with_resources("machine", vec![resource.into()])
+        };
+        let tasks = vec![
+            Task::new("T1")
+                .with_priority(3)
+                .with_activity(step("T1-A1", "T1", 1, 1000, "M1"))
+                .with_activity(step("T1-A2", "T1", 2, 2000, "M2"))
+                .with_activity(step("T1-A3", "T1", 3, 1000, "M1")),
+            Task::new("T2")
+                .with_priority(1)
+                .with_activity(step("T2-A1", "T2", 1, 1500, "M1")),
+        ];
+        let resources = vec![Resource::primary("M1"), Resource::primary("M2")];
+
+        let schedule = SimpleScheduler::new().schedule(&tasks, &resources, 0);
+
+        let inserted = schedule.assignment_for_activity("T2-A1").unwrap();
+        assert_eq!((inserted.start_ms, inserted.end_ms), (1000, 2500));
+        assert_eq!(schedule.makespan_ms, 4000);
+    }
+
+    /// Fail if two assignments on one resource overlap
+    fn assert_no_overlap(schedule: &Schedule) {
+        let mut by_resource: HashMap<&str, Vec<&Assignment>> = HashMap::new();
+        for a in &schedule.assignments {
+            by_resource.entry(&a.resource_id).or_default().push(a);
+        }
+        for (resource, mut booked) in by_resource {
+            booked.sort_by_key(|a| a.start_ms);
+            for pair in booked.windows(2) {
+                assert!(
+                    pair[0].end_ms <= pair[1].start_ms,
+                    "{} on {} ({}..{}) overlaps {} ({}..{})",
+                    pair[0].activity_id,
+                    resource,
+                    pair[0].start_ms,
+                    pair[0].end_ms,
+                    pair[1].activity_id,
+                    pair[1].start_ms,
+                    pair[1].end_ms
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_gap_insertion_respects_next_setup() {
+        // M1 idles from 4000 until T3-A2 starts its 500 ms changeover at 6000
+        let step = |id: &str, task: &str, seq: i32, ms: i64, resource: &str| {
+            Activity::new(id, task, seq)
+                .with_duration(ActivityDuration::fixed(ms))
+                .with_resources("machine", vec![resource.into()])
+        };
+        let tasks = vec![
+            Task::new("T1")
+                .with_category("a")
+                .with_priority(3)
+                .with_activity(step("T1-A1", "T1", 1, 1000, "M1"))
+                .with_activity(step("T1-A2", "T1", 2, 2000, "M2"))
+                .with_activity(step("T1-A3", "T1", 3, 1000, "M1")),
+            Task::new("T3")
+                .with_category("b")
+                .with_priority(2)
+                .with_activity(step("T3-A1", "T3", 1, 3000, "M2"))
+                .with_activity(step("T3-A2", "T3", 2, 1000, "M1")),
+            Task::new("T2")
+                .with_category("b")
+                .with_priority(1)
+                .with_activity(step("T2-A1", "T2", 1, 2000, "M1")),
+        ];
+        let resources = vec![Resource::primary("M1"), Resource::primary("M2")];
+        let mut matrix = crate::models::TransitionMatrix::new("setup", "M1").with_default(500);
+        matrix.set_transition("a", "a", 0);
+        matrix.set_transition("b", "b", 0);
+        let mut matrices = TransitionMatrixCollection::new();
+        matrices.add(matrix);
+
+        let schedule = SimpleScheduler::new()
+            .with_transition_matrices(matrices)
+            .schedule(&tasks, &resources, 0);
+
+        // The gap cannot hold T2-A1 without running into T3-A2
+        let next = schedule.assignment_for_activity("T3-A2").unwrap();
+        assert_eq!((next.start_ms, next.setup_ms), (6000, 500));
+        let appended = schedule.assignment_for_activity("T2-A1").unwrap();
+        assert_eq!(appended.start_ms, 7500);
+        assert_no_overlap(&schedule);
+    }
+
+    #[test]
+    fn test_earliest_finish_with_efficiency_and_setup() {
+        let job = |id: &str, category: &str, candidates: Vec<String>| {
+            Task::new(id).with_category(category).with_activity(
+                Activity::new(&format!("{}-A1", id), id, 1)
+                    .with_duration(ActivityDuration::fixed(1000))
+                    .with_resources("machine", candidates),
+            )
+        };
+
+        // Slow is free now, Fast frees up at 500 but finishes first
+        let tasks = vec![
+            job("Warmup", "A", vec!["Fast".into()]),
+            job("J1", "A", vec!["Slow".into(), "Fast".into()]),
+        ];
+        let resources = vec![
+            Resource::primary("Slow").with_efficiency(0.5),
+            Resource::primary("Fast").with_efficiency(2.0),
+        ];
+        let schedule = SimpleScheduler::new().schedule(&tasks, &resources, 0);
+        let j1 = schedule.assignment_for_activity("J1-A1").unwrap();
+        assert_eq!(j1.resource_id, "Fast");
+        assert_eq!(j1.end_ms, 1000);
+
+        // A long changeover on M1 makes the later M2 finish earlier
+        let tasks = vec![
+            job("Warmup", "A", vec!["M1".into()]),
+            job("Busy", "A", vec!["M2".into()]),
+            job("J2", "B", vec!["M1".into(), "M2".into()]),
+        ];
+        let resources = vec![Resource::primary("M1"), Resource::primary("M2")];
+        let mut matrices = TransitionMatrixCollection::new();
+        let mut m1 = crate::models::TransitionMatrix::new("setup", "M1");
+        m1.set_transition("A", "B", 3000);
+        matrices.add(m1);
+        let schedule = SimpleScheduler::new()
+            .with_transition_matrices(matrices)
+            .schedule(&tasks, &resources, 0);
+        let j2 = schedule.assignment_for_activity("J2-A1").unwrap();
+        assert_eq!(j2.resource_id, "M2");
+        assert_eq!(j2.setup_ms, 0);
+    }
+
+    #[test]
+    fn test_slot_mode() {
+        const SLOT: i64 = 15 * 60_000;
+        let tasks: Vec<Task> = (1..=3)
+            .map(|i| {
+                let id = format!("P{}", i);
+                Task::new(&id).with_priority(10 - i).with_activity(
+                    Activity::new(&format!("{}-visit", id), &id, 1)
+                        .with_duration(ActivityDuration::fixed(10 * 60_000))
+                        .with_resources("doctor", vec!["Dr1".into()]),
+                )
+            })
+            .collect();
+        let resources = vec![Resource::human("Dr1")];
+        let grid = SlotGrid::fixed(0, SLOT);
+
+        let schedule = SimpleScheduler::new()
+            .with_slots(grid.clone())
+            .schedule(&tasks, &resources, 60_000);
+
+        let starts: Vec<i64> = ["P1-visit", "P2-visit", "P3-visit"]
+            .iter()
+            .map(|id| schedule.assignment_for_activity(id).unwrap().start_ms)
+            .collect();
+        assert_eq!(starts, vec![SLOT, 2 * SLOT, 3 * SLOT]);
+        assert!(crate::validation::validate_slots(&schedule, &grid).is_valid);
+        assert_eq!(grid.bookings(&schedule).len(), 3);
+    }
+
+    #[test]
+    fn test_overlap_allowance() {
+        let task = |cut_ms: i64, weld_ms: i64| {
+            vec![Task::new("T1")
+                .with_activity(
+                    Activity::new("Cut", "T1", 1)
+                        .with_duration(ActivityDuration::fixed(cut_ms))
+                        .with_resources("cutter", vec!["Cutter".into()]),
+                )
+                .with_activity(
+                    Activity::new("Weld", "T1", 2)
+                        .with_duration(ActivityDuration::fixed(weld_ms))
+                        .with_resources("welder", vec!["Welder".into()]),
+                )]
+        };
+        let resources = vec![Resource::primary("Cutter"), Resource::primary("Welder")];
+        let constraints = vec![Constraint::overlap(
+            "Cut",
+            "Weld",
+            OverlapAllowance::Fraction(0.5),
+        )];
+        let scheduler = SimpleScheduler::new().with_constraints(constraints.clone());
+
+        // Welding starts once half of the cut batch is done
+        let schedule = scheduler.schedule(&task(1000, 1000), &resources, 0);
+        assert_eq!(
+            schedule.assignment_for_activity("Weld").unwrap().start_ms,
+            500
+        );
+        assert_eq!(schedule.makespan_ms, 1500);
+
+        // A short weld is held back so it does not finish before the cut
+        let schedule = scheduler.schedule(&task(1000, 200), &resources, 0);
+        let weld = schedule.assignment_for_activity("Weld").unwrap();
+        assert_eq!((weld.start_ms, weld.end_ms), (800, 1000));
+        assert!(crate::validation::validate_schedule(&schedule, &[], &constraints).is_valid);
+    }
+
+    #[test]
+    fn test_mutual_exclusion() {
+        // Three lifts on separate crews share one crane
+        let tasks: Vec<Task> = (1..=3)
+            .map(|i| {
+                let id = format!("T{}", i);
+                Task::new(&id).with_activity(
+                    Activity::new(&format!("Lift{}", i), &id, 1)
+                        .with_duration(ActivityDuration::fixed(1000))
+                        .with_resources("crew", vec![format!("Crew{}", i)]),
+                )
+            })
+            .collect();
+        let resources: Vec<Resource> = (1..=3)
+            .map(|i| Resource::human(&format!("Crew{}", i)))
+            .collect();
+        let constraints = vec![Constraint::mutual_exclusion(
+            "crane",
+            vec!["Lift1".into(), "Lift3".into()],
+        )];
+
+        let schedule = SimpleScheduler::new()
+            .with_constraints(constraints.clone())
+            .schedule(&tasks, &resources, 0);
+        let start = |id: &str| schedule.assignment_for_activity(id).unwrap().start_ms;
+        assert_eq!(
+            (start("Lift1"), start("Lift2"), start("Lift3")),
+            (0, 0, 1000)
+        );
+        assert!(schedule.violations.is_empty());
+        assert!(crate::validation::validate_schedule(&schedule, &tasks, &constraints).is_valid);
+    }
+
+    #[test]
+    fn test_mutual_exclusion_with_gap() {
+        // T2-A1 would fit M1's idle gap, but must wait for T3-A1 (same group)
+        let step = |id: &str, task: &str, seq: i32, ms: i64, resource: &str| {
+            Activity::new(id, task, seq)
+                .with_duration(ActivityDuration::fixed(ms))
+                .with_resources("machine", vec![resource.into()])
+        };
+        let tasks = vec![
+            Task::new("T1")
+                .with_priority(3)
+                .with_activity(step("T1-A1", "T1", 1, 1000, "M1"))
+                .with_activity(step("T1-A2", "T1", 2, 2000, "M2"))
+                .with_activity(step("T1-A3", "T1", 3, 1000, "M1")),
+            Task::new("T3")
+                .with_priority(2)
+                .with_activity(step("T3-A1", "T3", 1, 1800, "M3")),
+            Task::new("T2")
+                .with_priority(1)
+                .with_activity(step("T2-A1", "T2", 1, 1500, "M1")),
+        ];
+        let resources = vec![
+            Resource::primary("M1"),
+            Resource::primary("M2"),
+            Resource::primary("M3"),
+        ];
+        let constraints = vec![Constraint::mutual_exclusion(
+            "crane",
+            vec!["T3-A1".into(), "T2-A1".into()],
+        )];
+
+        let schedule = SimpleScheduler::new()
+            .with_constraints(constraints.clone())
+            .schedule(&tasks, &resources, 0);
+
+        assert_no_overlap(&schedule);
+        assert!(schedule.violations.is_empty());
+        assert!(crate::validation::validate_schedule(&schedule, &tasks, &constraints).is_valid);
+        let t2 = schedule.assignment_for_activity("T2-A1").unwrap();
+        assert_eq!((t2.start_ms, t2.end_ms), (4000, 5500));
+    }
+
+    #[test]
+    fn test_mutual_exclusion_with_gang() {
+        let tasks = vec![
+            Task::new("N").with_priority(5).with_activity(
+                Activity::new("N", "N", 1)
+                    .with_duration(ActivityDuration::fixed(1000))
+                    .with_resources("machine", vec!["M1".into()]),
+            ),
+            Task::new("G").with_priority(1).with_activity(
+                Activity::new("G", "G", 1)
+                    .with_duration(ActivityDuration::fixed(1000))
+                    .with_gang("crane", vec!["C1".into(), "C2".into()], 2),
+            ),
+        ];
+        let resources = vec![
+            Resource::primary("M1"),
+            Resource::primary("C1"),
+            Resource::primary("C2"),
+        ];
+        let constraints = vec![Constraint::mutual_exclusion(
+            "zone",
+            vec!["N".into(), "G".into()],
+        )];
+
+        let schedule = SimpleScheduler::new()
+            .with_constraints(constraints)
+            .schedule(&tasks, &resources, 0);
+
+        let lift = schedule.assignments_for_activity("G");
+        assert_eq!(lift.len(), 2);
+        assert!(lift.iter().all(|a| a.start_ms == 1000 && a.end_ms == 2000));
+        assert!(schedule.violations.is_empty());
+    }
+
+    #[test]
+    fn test_mutual_exclusion_after_energy_shift() {
+        // Both runs move to the cheap period but cannot share it
+        let hour = 3_600_000;
+        let run = |id: &str, priority: i32, machine: &str| {
+            Task::new(id).with_priority(priority).with_activity(
+                Activity::new(id, id, 1)
+                    .with_duration(ActivityDuration::fixed(hour))
+                    .with_energy_demand(50.0)
+                    .with_resources("machine", vec![machine.into()]),
+            )
+        };
+        let tasks = vec![run("R1", 5, "M1"), run("R2", 1, "M2")];
+        let resources = vec![Resource::primary("M1"), Resource::primary("M2")];
+        let tariff = EnergyTariff::new(0.1).with_period(0, 2 * hour, 0.5);
+        let constraints = vec![Constraint::mutual_exclusion(
+            "feeder",
+            vec!["R1".into(), "R2".into()],
+        )];
+
+        let schedule = SimpleScheduler::new()
+            .with_energy_tariff(tariff, 4 * hour)
+            .with_constraints(constraints)
+            .schedule(&tasks, &resources, 0);
+
+        let start = |id: &str| schedule.assignment_for_activity(id).unwrap().start_ms;
+        assert_eq!((start("R1"), start("R2")), (2 * hour, 3 * hour));
+        assert!(schedule.violations.is_empty());
+    }
+
+    #[test]
+    fn test_max_delay() {
+        // Oven is busy until 3000; parts must be baked within 500 ms of coating
+        let tasks = vec![
+            Task::new("Busy").with_priority(3).with_activity(
+                Activity::new("Busy-A1", "Busy", 1)
+                    .with_duration(ActivityDuration::fixed(3000))
+                    .with_resources("oven", vec!["Oven".into()]),
+            ),
+            Task::new("P1")
+                .with_priority(2)
+                .with_activity(
+                    Activity::new("Coat", "P1", 1)
+                        .with_duration(ActivityDuration::fixed(1000))
+                        .with_resources("coater", vec!["Coater".into()]),
+                )
+                .with_activity(
+                    Activity::new("Bake", "P1", 2)
+                        .with_duration(ActivityDuration::fixed(2000))
+                        .with_resources("oven", vec!["Oven".into()]),
+                ),
+        ];
+        let resources = vec![Resource::primary("Coater"), Resource::primary("Oven")];
+        let constraints = vec![Constraint::max_delay("Coat", "Bake", 500)];
+
+        let schedule = SimpleScheduler::new()
+            .with_constraints(constraints.clone())
+            .schedule(&tasks, &resources, 0);
+
+        // Coating is postponed so the part waits only 500 ms
+        let coat = schedule.assignment_for_activity("Coat").unwrap();
+        assert_eq!((coat.start_ms, coat.end_ms), (1500, 2500));
+        assert_eq!(
+            schedule.assignment_for_activity("Bake").unwrap().start_ms,
+            3000
+        );
+        assert!(schedule.violations.is_empty());
+        assert!(crate::validation::validate_schedule(&schedule, &[], &constraints).is_valid);
+
+        let mut late = schedule.clone();
+        for a in late
+            .assignments
+            .iter_mut()
+            .filter(|a| a.activity_id == "Coat")
+        {
+            a.start_ms = 0;
+            a.end_ms = 1000;
+        }
+        let result = crate::validation::validate_schedule(&late, &[], &constraints);
+        assert!(result.errors.iter().any(|e| e.code == "MAX_DELAY_EXCEEDED"));
+    }
+
+    #[test]
+    fn test_deadline_pruning() {
+        use chrono::{TimeZone, Utc};
+
+        // Fast is preferred but busy until 5000; the job is due at 3000
+        let tasks = vec![
+            Task::new("Busy").with_priority(9).with_activity(
+                Activity::new("Busy-A1", "Busy", 1)
+                    .with_duration(ActivityDuration::fixed(10_000))
+                    .with_resources("machine", vec!["Fast".into()]),
+            ),
+            Task::new("Job")
+                .with_deadline(Utc.timestamp_millis_opt(3000).unwrap())
+                .with_activity(
+                    Activity::new("Job-A1", "Job", 1)
+                        .with_duration(ActivityDuration::fixed(2000))
+                        .with_resources("machine", vec!["Fast".into(), "Slow".into()])
+                        .with_preferred_resource("Fast", 1.0),
+                ),
+        ];
+        let resources = vec![
+            Resource::primary("Fast").with_efficiency(2.0),
+            Resource::primary("Slow"),
+        ];
+        let scheduler = SimpleScheduler::new().with_preferences(20_000);
+
+        let schedule = scheduler.schedule(&tasks, &resources, 0);
+        assert_eq!(
+            schedule
+                .assignment_for_activity("Job-A1")
+                .unwrap()
+                .resource_id,
+            "Fast"
+        );
+
+        let schedule = scheduler
+            .clone()
+            .with_deadline_pruning(true)
+            .schedule(&tasks, &resources, 0);
+        let job = schedule.assignment_for_activity("Job-A1").unwrap();
+        assert_eq!((job.resource_id.as_str(), job.start_ms), ("Slow", 0));
+        assert!(schedule.violations.is_empty());
+
+        // No candidate can make it: placed anyway and flagged early
+        let late = vec![
+            tasks[0].clone(),
+            tasks[1]
+                .clone()
+                .with_deadline(Utc.timestamp_millis_opt(500).unwrap()),
+        ];
+        let schedule = SimpleScheduler::new()
+            .with_deadline_pruning(true)
+            .schedule(&late, &resources, 0);
+        assert!(schedule.assignment_for_activity("Job-A1").is_some());
+        assert_eq!(schedule.violations.len(), 1);
+    }
+
+    #[test]
+    fn test_explanations() {
+        let tasks = vec![
+            Task::new("Busy").with_priority(3).with_activity(
+                Activity::new("Busy-A1", "Busy", 1)
+                    .with_duration(ActivityDuration::fixed(3000))
+                    .with_resources("machine", vec!["M1".into()]),
+            ),
+            Task::new("T1")
+                .with_priority(2)
+                .with_activity(
+                    Activity::new("T1-A1", "T1", 1)
+                        .with_duration(ActivityDuration::fixed(1000))
+                        .with_resources("machine", vec!["M1".into(), "M2".into()]),
+                )
+                .with_activity(
+                    Activity::new("T1-A2", "T1", 2)
+                        .with_duration(ActivityDuration::fixed(1000))
+                        .with_resources("machine", vec!["M1".into(), "Gone".into()]),
+                ),
+        ];
+        let resources = vec![
+            Resource::primary("M1"),
+            Resource::primary("M2").with_efficiency(0.25),
+        ];
+
+        let schedule = SimpleScheduler::new()
+            .with_explanations(true)
+            .schedule(&tasks, &resources, 0);
+
+        let first = schedule.explain("T1-A1").unwrap();
+        assert_eq!(first.resource_id, "M1");
+        assert_eq!(first.driver, StartDriver::ResourceAvailable);
+        assert_eq!((first.ready_ms, first.start_ms), (0, 3000));
+        // The slow alternative finishes later
+        let m2 = first
+            .candidates
+            .iter()
+            .find(|c| c.resource_id == "M2")
+            .unwrap();
+        assert_eq!(m2.finish_ms, Some(4000));
+
+        let second = schedule.explain("T1-A2").unwrap();
+        assert_eq!(second.driver, StartDriver::Predecessor);
+        let gone = second
+            .candidates
+            .iter()
+            .find(|c| c.resource_id == "Gone")
+            .unwrap();
+        assert_eq!(gone.start_ms, None);
+        assert!(second.summary().contains("T1-A2"));
+
+        // Off by default
+        let plain = SimpleScheduler::new().schedule(&tasks, &resources, 0);
+        assert!(plain.explain("T1-A1").is_none());
+    }
+
+    #[test]
+    fn test_decision_trace() {
+        let tasks = vec![
+            Task::new("T1").with_priority(2).with_activity(
+                Activity::new("T1-A1", "T1", 1)
+                    .with_duration(ActivityDuration::fixed(1000))
+                    .with_resources("machine", vec!["M1".into(), "M2".into(), "Gone".into()]),
+            ),
+            Task::new("T2").with_priority(1).with_activity(
+                Activity::new("T2-A1", "T2", 1)
+                    .with_duration(ActivityDuration::fixed(1000))
+                    .with_resources("machine", vec!["Gone".into()]),
+            ),
+        ];
+        let resources = vec![
+            Resource::primary("M1"),
+            Resource::primary("M2").with_efficiency(0.5),
+        ];
+
+        let schedule = SimpleScheduler::new()
+            .with_trace(true)
+            .schedule(&tasks, &resources, 0);
+        let trace = schedule.trace.as_ref().unwrap();
+
+        assert_eq!(trace.decisions.len(), 2);
+        let first = &trace.decisions[0];
+        assert_eq!(first.chosen, vec!["M1"]);
+        assert_eq!((first.start_ms, first.end_ms), (Some(0), Some(1000)));
+        let outcome = |id: &str| {
+            first
+                .candidates
+                .iter()
+                .find(|c| c.resource_id == id)
+                .unwrap()
+                .outcome
+        };
+        assert_eq!(outcome("M1"), CandidateOutcome::Chosen);
+        assert_eq!(outcome("M2"), CandidateOutcome::Outscored);
+        assert_eq!(outcome("Gone"), CandidateOutcome::UnknownResource);
+        assert_eq!(
+            first
+                .candidates
+                .iter()
+                .find(|c| c.resource_id == "M2")
+                .unwrap()
+                .score,
+            Some(2000)
+        );
+        assert_eq!(trace.unplaced(), vec!["T2-A1"]);
+
+        // Serializes with the schedule; off by default
+        let json = serde_json::to_string(&schedule).unwrap();
+        let back: Schedule = serde_json::from_str(&json).unwrap();
+        assert_eq!(back.trace.as_ref(), Some(trace));
+        assert!(SimpleScheduler::new()
+            .schedule(&tasks, &resources, 0)
+            .trace
+            .is_none());
+    }
+
+    #[test]
+    fn test_baseline() {
+        let activity = |id: &str, task: &str, seq: i32| {
+            Activity::new(id, task, seq)
+                .with_duration(ActivityDuration::fixed(1000))
+                .with_resources("machine", vec!["M1".into(), "M2".into()])
+        };
+        let tasks = vec![
+            Task::new("T1")
+                .with_priority(2)
+                .with_activity(activity("A1", "T1", 1))
+                .with_activity(activity("A2", "T1", 2))
+                .with_activity(activity("A3", "T1", 3)),
+            Task::new("T2")
+                .with_priority(1)
+                .with_activity(activity("B1", "T2", 1)),
+        ];
+        let resources = vec![Resource::primary("M1"), Resource::primary("M2")];
+        let plan = vec![
+            Assignment::new("A1", "T1", "M1", 0, 1000),
+            Assignment::new("A2", "T1", "M1", 1000, 2000),
+            Assignment::new("A3", "T1", "M2", 2000, 3000),
+            Assignment::new("B1", "T2", "M2", 3000, 4000),
+        ];
+
+        let schedule = SimpleScheduler::new()
+            .with_baseline(Baseline::from_assignments(plan.clone(), 1500))
+            .schedule(&tasks, &resources, 0);
+
+        // Completed work is dropped, work in progress kept as is
+        assert!(schedule.assignment_for_activity("A1").is_none());
+        let a2 = schedule.assignment_for_activity("A2").unwrap();
+        assert_eq!((a2.resource_id.as_str(), a2.start_ms), ("M1", 1000));
+        // Tie between M1 and M2: the hinted resource is kept
+        let a3 = schedule.assignment_for_activity("A3").unwrap();
+        assert_eq!((a3.resource_id.as_str(), a3.start_ms), ("M2", 2000));
+        // A hint does not outweigh an earlier finish
+        let b1 = schedule.assignment_for_activity("B1").unwrap();
+        assert_eq!((b1.resource_id.as_str(), b1.start_ms), ("M1", 2000));
+        assert_eq!(schedule.makespan_ms, 3000);
+
+        // A2 actually runs on M2 and is 25% done at 1500
+        let baseline = Baseline::from_assignments(plan, 1500)
+            .with_progress(ActivityProgress::new("A2", "M2", 1200, 0.25));
+        let replanned = SimpleScheduler::new()
+            .with_baseline(baseline)
+            .schedule(&tasks, &resources, 0);
+
+        let a2 = replanned.assignment_for_activity("A2").unwrap();
+        assert_eq!(
+            (a2.resource_id.as_str(), a2.start_ms, a2.end_ms),
+            ("M2", 1200, 2250)
+        );
+        let a3 = replanned.assignment_for_activity("A3").unwrap();
+        assert_eq!(a3.start_ms, 2250);
+        assert_eq!(replanned.assignments_for_activity("A2").len(), 1);
+    }
+
+    #[test]
+    fn test_handoff_sync() {
+        // Porter is busy until 3000; surgery ends at 1000 and must be
+        // picked up within 200 ms
+        let tasks = vec![
+            Task::new("Busy").with_priority(3).with_activity(
+                Activity::new("Busy-A1", "Busy", 1)
+                    .with_duration(ActivityDuration::fixed(3000))
+                    .with_resources("porter", vec!["Porter".into()]),
+            ),
+            Task::new("P1")
+                .with_priority(2)
+                .with_activity(
+                    Activity::new("Surgery", "P1", 1)
+                        .with_duration(ActivityDuration::fixed(1000))
+                        .with_resources("room", vec!["OR1".into()]),
+                )
+                .with_activity(
+                    Activity::new("Transfer", "P1", 2)
+                        .with_duration(ActivityDuration::fixed(500))
+                        .with_resources("porter", vec!["Porter".into()]),
+                ),
+        ];
+        let resources = vec![Resource::primary("OR1"), Resource::human("Porter")];
+        let constraints = vec![Constraint::handoff("Surgery", "Transfer", 200)];
+
+        let schedule = SimpleScheduler::new()
+            .with_constraints(constraints.clone())
+            .schedule(&tasks, &resources, 0);
+
+        let surgery = schedule.assignment_for_activity("Surgery").unwrap();
+        let transfer = schedule.assignment_for_activity("Transfer").unwrap();
+        assert_eq!(transfer.start_ms, 3000);
+        assert_eq!(surgery.end_ms, 2800);
+        assert!(schedule.violations.is_empty());
+        assert!(crate::validation::validate_schedule(&schedule, &tasks, &constraints).is_valid);
+    }
+
+    #[test]
+    fn test_handoff_sync_keeps_predecessor_feasible() {
+        // Delaying Surgery to 1800..2800 would leave its window and OR1's
+        // morning opening; it stays put and the gap is flagged
+        let tasks = vec![
+            Task::new("Busy").with_priority(3).with_activity(
+                Activity::new("Busy-A1", "Busy", 1)
+                    .with_duration(ActivityDuration::fixed(3000))
+                    .with_resources("porter", vec!["Porter".into()]),
+            ),
+            Task::new("P1")
+                .with_priority(2)
+                .with_activity(
+                    Activity::new("Surgery", "P1", 1)
+                        .with_duration(ActivityDuration::fixed(1000))
+                        .with_resources("room", vec!["OR1".into()]),
+                )
+                .with_activity(
+                    Activity::new("Transfer", "P1", 2)
+                        .with_duration(ActivityDuration::fixed(500))
+                        .with_resources("porter", vec!["Porter".into()]),
+                ),
+        ];
+        let handoff = Constraint::handoff("Surgery", "Transfer", 200);
+        let check = |schedule: &Schedule| {
+            let surgery = schedule.assignment_for_activity("Surgery").unwrap();
+            assert_eq!((surgery.start_ms, surgery.end_ms), (0, 1000));
+            assert_eq!(schedule.violations.len(), 1);
+            assert!(schedule.violations[0].message.contains("Handoff"));
+        };
+
+        let resources = vec![Resource::primary("OR1"), Resource::human("Porter")];
+        let schedule = SimpleScheduler::new()
+            .with_constraints(vec![
+                handoff.clone(),
+                Constraint::time_window("Surgery", 0, 1500),
+            ])
+            .schedule(&tasks, &resources, 0);
+        check(&schedule);
+
+        let resources = vec![
+            Resource::primary("OR1").with_calendar(
+                crate::models::Calendar::new("or")
+                    .with_window(0, 1500)
+                    .with_window(10_000, 100_000),
+            ),
+            Resource::human("Porter"),
+        ];
+        let schedule = SimpleScheduler::new()
+            .with_calendars(true)
+            .with_constraints(vec![handoff])
+            .schedule(&tasks, &resources, 0);
+        check(&schedule);
+    }
+
+    #[test]
+    fn test_handoff_sync_updates_exclusion() {
+        // Surgery is delayed to 1800..2800 by the handoff; Clean shares
+        // its exclusion group and must see the moved interval
+        let tasks = vec![
+            Task::new("Busy").with_priority(3).with_activity(
+                Activity::new("Busy-A1", "Busy", 1)
+                    .with_duration(ActivityDuration::fixed(3000))
+                    .with_resources("porter", vec!["Porter".into()]),
+            ),
+            Task::new("P1")
+                .with_priority(2)
+                .with_activity(
+                    Activity::new("Surgery", "P1", 1)
+                        .with_duration(ActivityDuration::fixed(1000))
+                        .with_resources("room", vec!["OR1".into()]),
+                )
+                .with_activity(
+                    Activity::new("Transfer", "P1", 2)
+                        .with_duration(ActivityDuration::fixed(500))
+                        .with_resources("porter", vec!["Porter".into()]),
+                ),
+            Task::new("P2").with_priority(1).with_activity(
+                Activity::new("Clean", "P2", 1)
+                    .with_duration(ActivityDuration::fixed(1000))
+                    .with_resources("room", vec!["OR2".into()]),
+            ),
+        ];
+        let resources = vec![
+            Resource::primary("OR1"),
+            Resource::primary("OR2"),
+            Resource::human("Porter"),
+        ];
+        let constraints = vec![
+            Constraint::handoff("Surgery", "Transfer", 200),
+            Constraint::mutual_exclusion("sterile", vec!["Surgery".into(), "Clean".into()]),
+        ];
+
+        let schedule = SimpleScheduler::new()
+            .with_constraints(constraints.clone())
+            .schedule(&tasks, &resources, 0);
+
+        let surgery = schedule.assignment_for_activity("Surgery").unwrap();
+        let clean = schedule.assignment_for_activity("Clean").unwrap();
+        assert_eq!((surgery.start_ms, surgery.end_ms), (1800, 2800));
+        assert!(clean.end_ms <= surgery.start_ms || surgery.end_ms <= clean.start_ms);
+        assert_eq!(schedule.assignments_for_resource("OR1")[0].end_ms, 2800);
+        assert!(schedule.violations.is_empty());
+        assert!(crate::validation::validate_schedule(&schedule, &tasks, &constraints).is_valid);
+    }
+
+    #[test]
+    fn test_just_in_time() {
+        let (mut tasks, resources) = create_test_scenario();
+        tasks[0].deadline = chrono::DateTime::from_timestamp_millis(20_000);
+        tasks[1].deadline = chrono::DateTime::from_timestamp_millis(10_000);
+
+        let early = SimpleScheduler::new().schedule(&tasks, &resources, 0);
+        let jit = SimpleScheduler::new()
+            .with_just_in_time(1000)
+            .schedule(&tasks, &resources, 0);
+        assert_eq!(jit.assignment_for_activity("T1-A1").unwrap().end_ms, 19_000);
+        assert_eq!(jit.assignment_for_activity("T2-A1").unwrap().end_ms, 9000);
+
+        let before = crate::scheduler::ScheduleKpi::calculate(&early, &tasks);
+        let after = crate::scheduler::ScheduleKpi::calculate(&jit, &tasks);
+        assert_eq!(after.total_earliness_ms, 2000);
+        assert!(before.total_earliness_ms > after.total_earliness_ms);
+        assert_eq!(after.total_tardiness_ms, 0);
+    }
+
+    #[test]
+    fn test_priority_classes() {
+        let (mut tasks, resources) = create_test_scenario();
+        // T2 has the lower priority but an urgent class
+        tasks[1].priority_class = PriorityClass::URGENT;
+
+        let schedule = SimpleScheduler::new().schedule(&tasks, &resources, 0);
+        let t2 = schedule.assignment_for_activity("T2-A1").unwrap();
+        assert_eq!((t2.resource_id.as_str(), t2.start_ms), ("M1", 0));
+        // Routine T1 takes what is left
+        let t1 = schedule.assignment_for_activity("T1-A1").unwrap();
+        assert!(t1.resource_id == "M2" || t1.start_ms >= t2.end_ms);
     }
 
     #[test]
@@ -0,0 +1,120 @@
+//! Stability - Churn between a baseline and a re-plan
+//!
+//! Measures how much a new schedule deviates from the one it replaces
+//! (moved starts, changed resources), the quantity stability-aware
+//! re-optimization (`SimpleScheduler::with_stability`) keeps small.
+
+use crate::models::Schedule;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// Deviation of a re-plan from its baseline
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StabilityReport {
+    /// Activities in both schedules
+    pub compared: usize,
+    /// Activities whose start moved
+    pub moved: Vec<String>,
+    /// Activities on a different resource
+    pub reassigned: Vec<String>,
+    /// Sum of absolute start shifts (ms)
+    pub total_shift_ms: i64,
+    /// Largest absolute start shift (ms)
+    pub max_shift_ms: i64,
+}
+
+impl StabilityReport {
+    /// Compare a re-plan with its baseline
+    ///
+    /// Activities are compared by their first assignment; activities in
+    /// only one of the schedules are ignored.
+    pub fn compare(baseline: &Schedule, schedule: &Schedule) -> Self {
+        let mut report = Self::default();
+        let mut seen = HashSet::new();
+        for before in &baseline.assignments {
+            if !seen.insert(before.activity_id.as_str()) {
+                continue;
+            }
+            let Some(after) = schedule.assignment_for_activity(&before.activity_id) else {
+                continue;
+            };
+            report.compared += 1;
+            let shift = (after.start_ms - before.start_ms).abs();
+            if shift > 0 {
+                report.moved.push(before.activity_id.clone());
+                report.total_shift_ms += shift;
+                report.max_shift_ms = report.max_shift_ms.max(shift);
+            }
+            if after.resource_id != before.resource_id {
+                report.reassigned.push(before.activity_id.clone());
+            }
+        }
+        report
+    }
+
+    /// Share of compared activities left untouched (0.0 to 1.0)
+    pub fn stability(&self) -> f64 {
+        if self.compared == 0 {
+            return 1.0;
+        }
+        let changed = self
+            .moved
+            .iter()
+            .chain(&self.reassigned)
+            .collect::<HashSet<_>>()
+            .len();
+        1.0 - changed as f64 / self.compared as f64
+    }
+
+    /// Deviation penalty with the weights of `with_stability`
+    pub fn penalty(&self, weight: f64, resource_change_ms: i64) -> i64 {
+        (weight * self.total_shift_ms as f64) as i64
+            + self.reassigned.len() as i64 * resource_change_ms
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Activity, ActivityDuration, Assignment, Baseline, Resource, Task};
+    use crate::scheduler::SimpleScheduler;
+
+    #[test]
+    fn test_stable_replan() {
+        let task = |id: &str, priority: i32| {
+            Task::new(id).with_priority(priority).with_activity(
+                Activity::new(&format!("{}-A1", id), id, 1)
+                    .with_duration(ActivityDuration::fixed(1000))
+                    .with_resources("machine", vec!["M1".into(), "M2".into()]),
+            )
+        };
+        let resources = vec![Resource::primary("M1"), Resource::primary("M2")];
+        let mut baseline = Schedule::new();
+        baseline.add_assignment(Assignment::new("J1-A1", "J1", "M1", 0, 1000));
+        baseline.add_assignment(Assignment::new("J2-A1", "J2", "M2", 0, 1000));
+        // A rush order arrives and gets the highest priority
+        let tasks = vec![task("Rush", 3), task("J1", 2), task("J2", 1)];
+
+        let replan = |scheduler: SimpleScheduler| {
+            scheduler
+                .with_baseline(Baseline::from_schedule(&baseline, 0))
+                .schedule(&tasks, &resources, 0)
+        };
+        let greedy = replan(SimpleScheduler::new());
+        let stable = replan(SimpleScheduler::new().with_stability(1.0, 5000));
+
+        // The rush order takes M1. Greedy moves J1 to M2 and J2 behind it;
+        // the stable re-plan only delays J1 on its machine
+        let churn = StabilityReport::compare(&baseline, &greedy);
+        assert_eq!(churn.reassigned, vec!["J1-A1"]);
+        assert_eq!(churn.moved, vec!["J2-A1"]);
+        assert_eq!(churn.penalty(1.0, 5000), 6000);
+
+        let kept = StabilityReport::compare(&baseline, &stable);
+        assert_eq!(kept.compared, 2);
+        assert!(kept.reassigned.is_empty());
+        assert_eq!(kept.moved, vec!["J1-A1"]);
+        assert_eq!(kept.penalty(1.0, 5000), 1000);
+        assert_eq!(kept.stability(), 0.5);
+    }
+}
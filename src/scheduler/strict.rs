@@ -0,0 +1,423 @@
+//! Strict import - Deserialization with semantic checks
+//!
+//! The lenient `serde_json::from_str` path accepts misspelled fields
+//! (silently defaulted), negative durations and dangling references.
+//! `ScheduleRequest::from_json_strict` rejects all of these and reports
+//! every problem found, not just the first, so an instance can be fixed
+//! in one pass before it reaches a solver.
+
+use super::ScheduleRequest;
+use crate::format::{self, DocumentKind};
+use crate::graph::PrecedenceGraph;
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+/// One problem found in an imported document
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportProblem {
+    /// Where the problem is (`tasks[0].activities[1].duration.process_ms`)
+    pub path: String,
+    /// What was wrong
+    pub message: String,
+}
+
+impl fmt::Display for ImportProblem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.path.is_empty() {
+            write!(f, "{}", self.message)
+        } else {
+            write!(f, "{}: {}", self.path, self.message)
+        }
+    }
+}
+
+/// Strict import failure, listing every problem found
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StrictImportError {
+    pub problems: Vec<ImportProblem>,
+}
+
+impl fmt::Display for StrictImportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} problem(s) in imported request", self.problems.len())?;
+        for problem in &self.problems {
+            write!(f, "\n  {}", problem)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for StrictImportError {}
+
+impl ScheduleRequest {
+    /// Deserialize a request, rejecting anything suspicious
+    ///
+    /// On top of ordinary deserialization this denies unknown fields,
+    /// negative durations and non-finite or negative quantities, and
+    /// checks that IDs are unique and every reference (activity parent
+    /// task, predecessor, candidate and preferred resource) resolves.
+    /// Precedence cycles are reported too. Older format versions are
+    /// migrated first (see `format`). Syntax and type errors stop the
+    /// import at once; all other problems are collected.
+    pub fn from_json_strict(text: &str) -> Result<Self, StrictImportError> {
+        let fail = |message: String| StrictImportError {
+            problems: vec![ImportProblem {
+                path: String::new(),
+                message,
+            }],
+        };
+        let mut input: Value = serde_json::from_str(text).map_err(|e| fail(e.to_string()))?;
+        format::migrate(&mut input, DocumentKind::Problem).map_err(|e| fail(e.to_string()))?;
+        let request: ScheduleRequest =
+            serde_json::from_value(input.clone()).map_err(|e| fail(e.to_string()))?;
+
+        let mut problems = Vec::new();
+        // A field is known iff it survives a round trip through the model
+        match serde_json::to_value(&request) {
+            Ok(known) => unknown_fields(&input, &known, "", &mut problems),
+            Err(e) => problems.push(ImportProblem {
+                path: String::new(),
+                message: e.to_string(),
+            }),
+        }
+        check_request(&request, &mut problems);
+
+        if problems.is_empty() {
+            Ok(request)
+        } else {
+            Err(StrictImportError { problems })
+        }
+    }
+}
+
+/// Report keys of `input` that are absent from the re-serialized `known`
+fn unknown_fields(input: &Value, known: &Value, path: &str, problems: &mut Vec<ImportProblem>) {
+    match (input, known) {
+        (Value::Object(input), Value::Object(known)) => {
+            for (key, value) in input {
+                let child = if path.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{}.{}", path, key)
+                };
+                match known.get(key) {
+                    Some(known) => unknown_fields(value, known, &child, problems),
+                    None => problems.push(ImportProblem {
+                        path: child,
+                        message: "unknown field".to_string(),
+                    }),
+                }
+            }
+        }
+        (Value::Array(input), Value::Array(known)) => {
+            for (i, (value, known)) in input.iter().zip(known).enumerate() {
+                unknown_fields(value, known, &format!("{}[{}]", path, i), problems);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Semantic checks on a deserialized request
+fn check_request(request: &ScheduleRequest, problems: &mut Vec<ImportProblem>) {
+    let mut report = |path: String, message: String| problems.push(ImportProblem { path, message });
+
+    let mut resource_ids = HashSet::new();
+    for (r, resource) in request.resources.iter().enumerate() {
+        let path = format!("resources[{}]", r);
+        if resource.id.is_empty() {
+            report(format!("{}.id", path), "empty id".to_string());
+        } else if !resource_ids.insert(resource.id.as_str()) {
+            report(
+                format!("{}.id", path),
+                format!("duplicate resource id '{}'", resource.id),
+            );
+        }
+        if resource.capacity < 0 {
+            report(
+                format!("{}.capacity", path),
+                format!("negative capacity {}", resource.capacity),
+            );
+        }
+        if !resource.efficiency.is_finite() || resource.efficiency <= 0.0 {
+            report(
+                format!("{}.efficiency", path),
+                format!("efficiency must be positive, got {}", resource.efficiency),
+            );
+        }
+        if !(0.0..=1.0).contains(&resource.revocation_probability) {
+            report(
+                format!("{}.revocation_probability", path),
+                format!(
+                    "probability must be within [0, 1], got {}",
+                    resource.revocation_probability
+                ),
+            );
+        }
+        if let Some(cost) = resource.cost_per_hour {
+            check_quantity(&mut report, format!("{}.cost_per_hour", path), cost);
+        }
+        for (dimension, &capacity) in &resource.load_capacity {
+            check_quantity(
+                &mut report,
+                format!("{}.load_capacity.{}", path, dimension),
+                capacity,
+            );
+        }
+    }
+
+    let mut task_ids = HashSet::new();
+    let mut activity_ids = HashMap::new();
+    for (t, task) in request.tasks.iter().enumerate() {
+        let path = format!("tasks[{}]", t);
+        if task.id.is_empty() {
+            report(format!("{}.id", path), "empty id".to_string());
+        } else if !task_ids.insert(task.id.as_str()) {
+            report(
+                format!("{}.id", path),
+                format!("duplicate task id '{}'", task.id),
+            );
+        }
+        if let Some(weight) = task.weight {
+            check_quantity(&mut report, format!("{}.weight", path), weight);
+        }
+        for (dimension, &load) in &task.loads {
+            check_quantity(&mut report, format!("{}.loads.{}", path, dimension), load);
+        }
+        for (a, activity) in task.activities.iter().enumerate() {
+            let path = format!("{}.activities[{}]", path, a);
+            if activity.id.is_empty() {
+                report(format!("{}.id", path), "empty id".to_string());
+            } else if activity_ids
+                .insert(activity.id.as_str(), path.clone())
+                .is_some()
+            {
+                report(
+                    format!("{}.id", path),
+                    format!("duplicate activity id '{}'", activity.id),
+                );
+            }
+        }
+    }
+
+    for (t, task) in request.tasks.iter().enumerate() {
+        for (a, activity) in task.activities.iter().enumerate() {
+            let path = format!("tasks[{}].activities[{}]", t, a);
+            if activity.task_id != task.id {
+                report(
+                    format!("{}.task_id", path),
+                    format!(
+                        "'{}' does not match parent task '{}'",
+                        activity.task_id, task.id
+                    ),
+                );
+            }
+            let duration = &activity.duration;
+            for (field, ms) in [
+                ("setup_ms", duration.setup_ms),
+                ("process_ms", duration.process_ms),
+                ("teardown_ms", duration.teardown_ms),
+            ] {
+                if ms < 0 {
+                    report(
+                        format!("{}.duration.{}", path, field),
+                        format!("negative duration {} ms", ms),
+                    );
+                }
+            }
+            if activity.min_split_ms < 0 {
+                report(
+                    format!("{}.min_split_ms", path),
+                    format!("negative duration {} ms", activity.min_split_ms),
+                );
+            }
+            check_quantity(
+                &mut report,
+                format!("{}.energy_kw", path),
+                activity.energy_kw,
+            );
+            for (quantity, &demand) in &activity.demands {
+                check_quantity(
+                    &mut report,
+                    format!("{}.demands.{}", path, quantity),
+                    demand,
+                );
+            }
+            for (dimension, &demand) in &activity.dimension_demands {
+                check_quantity(
+                    &mut report,
+                    format!("{}.dimension_demands.{}", path, dimension),
+                    demand,
+                );
+            }
+            if let Some(malleable) = &activity.malleable {
+                if !(0.0..=1.0).contains(&malleable.efficiency) {
+                    report(
+                        format!("{}.malleable.efficiency", path),
+                        format!(
+                            "efficiency must be in 0.0..=1.0, got {}",
+                            malleable.efficiency
+                        ),
+                    );
+                }
+                if malleable.min_units > malleable.max_units {
+                    report(
+                        format!("{}.malleable", path),
+                        format!(
+                            "min_units {} exceeds max_units {}",
+                            malleable.min_units, malleable.max_units
+                        ),
+                    );
+                }
+            }
+
+            for (p, predecessor) in activity.predecessors.iter().enumerate() {
+                if !activity_ids.contains_key(predecessor.as_str()) {
+                    report(
+                        format!("{}.predecessors[{}]", path, p),
+                        format!("unknown activity '{}'", predecessor),
+                    );
+                }
+            }
+            for (q, requirement) in activity.resource_requirements.iter().enumerate() {
+                let path = format!("{}.resource_requirements[{}]", path, q);
+                if requirement.quantity < 1 {
+                    report(
+                        format!("{}.quantity", path),
+                        format!("quantity must be at least 1, got {}", requirement.quantity),
+                    );
+                }
+                for (c, candidate) in requirement.candidates.iter().enumerate() {
+                    if !resource_ids.contains(candidate.as_str()) {
+                        report(
+                            format!("{}.candidates[{}]", path, c),
+                            format!("unknown resource '{}'", candidate),
+                        );
+                    }
+                }
+            }
+            let mut preferred: Vec<(&String, &f64)> = activity.preferred_resources.iter().collect();
+            preferred.sort_by(|x, y| x.0.cmp(y.0));
+            for (resource, &weight) in preferred {
+                let path = format!("{}.preferred_resources.{}", path, resource);
+                if !resource_ids.contains(resource.as_str()) {
+                    report(path.clone(), format!("unknown resource '{}'", resource));
+                }
+                if !weight.is_finite() {
+                    report(path, format!("weight must be finite, got {}", weight));
+                }
+            }
+        }
+    }
+
+    for cycle in PrecedenceGraph::from_tasks(&request.tasks).cycles() {
+        let path = activity_ids
+            .get(cycle[0].as_str())
+            .map(|p| format!("{}.predecessors", p))
+            .unwrap_or_default();
+        report(
+            path,
+            format!("precedence cycle among activities {}", cycle.join(", ")),
+        );
+    }
+}
+
+/// Quantities (loads, demands, weights, costs) must be finite and non-negative
+fn check_quantity(report: &mut impl FnMut(String, String), path: String, value: f64) {
+    if !value.is_finite() || value < 0.0 {
+        report(
+            path,
+            format!("must be finite and non-negative, got {}", value),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Activity, ActivityDuration, Resource, ResourceType, Task};
+
+    fn request() -> ScheduleRequest {
+        let task = Task::new("T1")
+            .with_activity(
+                Activity::new("A1", "T1", 0)
+                    .with_duration(ActivityDuration::fixed(1000))
+                    .with_resources("machine", vec!["M1".into()]),
+            )
+            .with_activity(
+                Activity::new("A2", "T1", 1)
+                    .with_duration(ActivityDuration::fixed(2000))
+                    .with_predecessor("A1"),
+            );
+        ScheduleRequest::new(vec![task], vec![Resource::new("M1", ResourceType::Primary)])
+    }
+
+    #[test]
+    fn test_strict_accepts_valid_request() {
+        let json = serde_json::to_string(&request()).unwrap();
+        let parsed = ScheduleRequest::from_json_strict(&json).unwrap();
+        assert_eq!(parsed.tasks[0].activities.len(), 2);
+
+        let json = format::Versioned::to_versioned_json(&request()).unwrap();
+        assert!(ScheduleRequest::from_json_strict(&json).is_ok());
+    }
+
+    #[test]
+    fn test_strict_reports_all_problems() {
+        let mut value = serde_json::to_value(request()).unwrap();
+        value["tasks"][0]["activities"][0]["duraton"] = 5.into();
+        value["tasks"][0]["activities"][0]["duration"]["process_ms"] = (-10).into();
+        value["tasks"][0]["activities"][1]["predecessors"][0] = "A9".into();
+        value["tasks"][0]["activities"][0]["resource_requirements"][0]["candidates"][0] =
+            "M2".into();
+        value["resources"][0]["efficiency"] = 0.into();
+        value["resources"][0]["revocation_probability"] = 1.5.into();
+
+        let err = ScheduleRequest::from_json_strict(&value.to_string()).unwrap_err();
+        let paths: Vec<&str> = err.problems.iter().map(|p| p.path.as_str()).collect();
+
+        assert_eq!(
+            paths,
+            vec![
+                "tasks[0].activities[0].duraton",
+                "resources[0].efficiency",
+                "resources[0].revocation_probability",
+                "tasks[0].activities[0].duration.process_ms",
+                "tasks[0].activities[0].resource_requirements[0].candidates[0]",
+                "tasks[0].activities[1].predecessors[0]",
+            ]
+        );
+        assert!(err.to_string().starts_with("6 problem(s)"));
+
+        // The lenient path lets all of this through
+        assert!(serde_json::from_value::<ScheduleRequest>(value).is_ok());
+    }
+
+    #[test]
+    fn test_strict_duplicates_and_cycles() {
+        let mut request = request();
+        request.tasks[0].activities[0]
+            .predecessors
+            .push("A2".into());
+        request.tasks.push(Task::new("T1"));
+        request.tasks[0].activities[1].task_id = "T2".into();
+
+        let err = ScheduleRequest::from_json_strict(&serde_json::to_string(&request).unwrap())
+            .unwrap_err();
+        let messages: Vec<String> = err.problems.iter().map(|p| p.to_string()).collect();
+
+        assert!(messages.contains(&"tasks[1].id: duplicate task id 'T1'".to_string()));
+        assert!(messages.contains(
+            &"tasks[0].activities[1].task_id: 'T2' does not match parent task 'T1'".to_string()
+        ));
+        assert!(messages.iter().any(|m| m.contains("precedence cycle")));
+    }
+
+    #[test]
+    fn test_strict_syntax_error() {
+        let err = ScheduleRequest::from_json_strict("{\"tasks\": [").unwrap_err();
+        assert_eq!(err.problems.len(), 1);
+        assert!(err.problems[0].path.is_empty());
+    }
+}
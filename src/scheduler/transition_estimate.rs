@@ -0,0 +1,257 @@
+//! Transition estimate - Setup matrices from history
+//!
+//! Learns category-to-category setup times per resource from executed
+//! schedules and execution logs, producing a `TransitionMatrixCollection`
+//! to plan with.
+
+use crate::models::{ExecutionLog, Schedule, Task, TransitionMatrix, TransitionMatrixCollection};
+use std::collections::{BTreeMap, HashMap};
+
+/// Setup time estimator
+///
+/// Every pair of consecutive runs on a resource yields one observation
+/// for (resource, previous category, next category): the setup recorded
+/// on a schedule assignment, or the idle gap between two finished runs
+/// of an execution log. Estimates are medians, so occasional waits do
+/// not dominate; gaps above the idle cutoff are not counted at all.
+#[derive(Debug, Clone)]
+pub struct TransitionEstimator {
+    observations: BTreeMap<(String, String, String), Vec<i64>>,
+    min_observations: usize,
+    max_gap_ms: Option<i64>,
+}
+
+impl Default for TransitionEstimator {
+    fn default() -> Self {
+        Self {
+            observations: BTreeMap::new(),
+            min_observations: 1,
+            max_gap_ms: None,
+        }
+    }
+}
+
+impl TransitionEstimator {
+    /// Create empty estimator
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set observations a pair needs before it is estimated (default 1)
+    pub fn with_min_observations(mut self, min_observations: usize) -> Self {
+        self.min_observations = min_observations.max(1);
+        self
+    }
+
+    /// Treat log gaps longer than this as idle time, not setup
+    ///
+    /// Applies to execution logs added afterwards.
+    pub fn with_max_gap(mut self, max_gap_ms: i64) -> Self {
+        self.max_gap_ms = Some(max_gap_ms);
+        self
+    }
+
+    /// Learn from the recorded setups of an executed schedule
+    pub fn with_schedule(mut self, schedule: &Schedule, tasks: &[Task]) -> Self {
+        self.add_schedule(schedule, tasks);
+        self
+    }
+
+    /// Learn from the recorded setups of an executed schedule
+    pub fn add_schedule(&mut self, schedule: &Schedule, tasks: &[Task]) {
+        let runs = schedule.assignments.iter().map(|a| Run {
+            resource_id: &a.resource_id,
+            task_id: &a.task_id,
+            start_ms: a.start_ms,
+            end_ms: a.end_ms,
+            setup_ms: a.setup_ms,
+        });
+        self.observe(runs, tasks, |_, next| Some(next.setup_ms));
+    }
+
+    /// Learn from the gaps between finished runs of an execution log
+    pub fn with_execution_log(mut self, log: &ExecutionLog, tasks: &[Task]) -> Self {
+        self.add_execution_log(log, tasks);
+        self
+    }
+
+    /// Learn from the gaps between finished runs of an execution log
+    pub fn add_execution_log(&mut self, log: &ExecutionLog, tasks: &[Task]) {
+        let max_gap_ms = self.max_gap_ms;
+        let runs = log.records.iter().filter_map(|r| {
+            Some(Run {
+                resource_id: &r.resource_id,
+                task_id: &r.task_id,
+                start_ms: r.actual_start_ms,
+                end_ms: r.actual_end_ms?,
+                setup_ms: 0,
+            })
+        });
+        self.observe(runs, tasks, |prev, next| {
+            let gap = (next.start_ms - prev.end_ms).max(0);
+            max_gap_ms.is_none_or(|max| gap <= max).then_some(gap)
+        });
+    }
+
+    /// Observations of a transition so far
+    pub fn observation_count(&self, resource_id: &str, from: &str, to: &str) -> usize {
+        self.observations
+            .get(&(resource_id.to_string(), from.to_string(), to.to_string()))
+            .map_or(0, Vec::len)
+    }
+
+    /// Estimated setup matrices, one per observed resource
+    ///
+    /// Each pair with enough observations gets its median; the matrix
+    /// default (for unseen pairs) is the median over all observed
+    /// category changes on the resource, or 0 if there were none.
+    pub fn estimate(&self) -> TransitionMatrixCollection {
+        let mut resources: Vec<&str> = self.observations.keys().map(|k| k.0.as_str()).collect();
+        resources.dedup();
+
+        let mut collection = TransitionMatrixCollection::new();
+        for resource in resources {
+            let pairs = self
+                .observations
+                .iter()
+                .filter(|((r, _, _), _)| r == resource);
+            let mut changes: Vec<i64> = pairs
+                .clone()
+                .filter(|((_, from, to), _)| from != to)
+                .flat_map(|(_, times)| times.iter().copied())
+                .collect();
+            let mut matrix =
+                TransitionMatrix::new("estimated", resource).with_default(median(&mut changes));
+            for ((_, from, to), times) in pairs {
+                if times.len() >= self.min_observations {
+                    matrix.set_transition(from, to, median(&mut times.clone()));
+                }
+            }
+            collection.add(matrix);
+        }
+        collection
+    }
+
+    /// Record one observation per consecutive pair of runs on a resource
+    fn observe<'a>(
+        &mut self,
+        runs: impl Iterator<Item = Run<'a>>,
+        tasks: &[Task],
+        setup: impl Fn(&Run, &Run) -> Option<i64>,
+    ) {
+        let category: HashMap<&str, &str> = tasks
+            .iter()
+            .map(|t| (t.id.as_str(), t.category.as_str()))
+            .collect();
+        let mut by_resource: HashMap<&str, Vec<Run>> = HashMap::new();
+        for run in runs {
+            by_resource.entry(run.resource_id).or_default().push(run);
+        }
+        for (resource, mut runs) in by_resource {
+            runs.sort_by_key(|r| (r.start_ms, r.end_ms));
+            for pair in runs.windows(2) {
+                let Some(ms) = setup(&pair[0], &pair[1]) else {
+                    continue;
+                };
+                let from = category.get(pair[0].task_id).copied().unwrap_or("");
+                let to = category.get(pair[1].task_id).copied().unwrap_or("");
+                self.observations
+                    .entry((resource.to_string(), from.to_string(), to.to_string()))
+                    .or_default()
+                    .push(ms);
+            }
+        }
+    }
+}
+
+/// One run of a task on a resource
+struct Run<'a> {
+    resource_id: &'a str,
+    task_id: &'a str,
+    start_ms: i64,
+    end_ms: i64,
+    setup_ms: i64,
+}
+
+/// Median (mean of the middle two for even counts, 0 if empty)
+fn median(values: &mut [i64]) -> i64 {
+    if values.is_empty() {
+        return 0;
+    }
+    values.sort_unstable();
+    let mid = values.len() / 2;
+    if values.len().is_multiple_of(2) {
+        (values[mid - 1] + values[mid]) / 2
+    } else {
+        values[mid]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Assignment, ExecutionRecord};
+
+    fn tasks() -> Vec<Task> {
+        ["red", "blue", "red", "blue", "blue"]
+            .iter()
+            .enumerate()
+            .map(|(i, c)| Task::new(&format!("T{}", i)).with_category(c))
+            .collect()
+    }
+
+    #[test]
+    fn test_from_schedule() {
+        let tasks = tasks();
+        let mut schedule = Schedule::new();
+        let setups = [0, 400, 600, 500, 0];
+        for (i, setup) in setups.iter().enumerate() {
+            let start = i as i64 * 2000;
+            schedule.add_assignment(
+                Assignment::new(
+                    &format!("A{}", i),
+                    &format!("T{}", i),
+                    "M1",
+                    start,
+                    start + 1500,
+                )
+                .with_setup(*setup),
+            );
+        }
+
+        let matrices = TransitionEstimator::new()
+            .with_schedule(&schedule, &tasks)
+            .estimate();
+
+        assert_eq!(matrices.get_transition_time("M1", "red", "blue"), 450);
+        assert_eq!(matrices.get_transition_time("M1", "blue", "red"), 600);
+        assert_eq!(matrices.get_transition_time("M1", "blue", "blue"), 0);
+        // Unseen pair falls back to the median category change
+        assert_eq!(matrices.get_transition_time("M1", "red", "red"), 500);
+        assert!(matrices.get_for_resource("M2").is_none());
+    }
+
+    #[test]
+    fn test_from_execution_log() {
+        let tasks = tasks();
+        let log = ExecutionLog::new()
+            .with_record(ExecutionRecord::new("A0", "T0", "M1", 0).with_end(1000))
+            .with_record(ExecutionRecord::new("A1", "T1", "M1", 1300).with_end(2000))
+            // Idle overnight, not a setup
+            .with_record(ExecutionRecord::new("A2", "T2", "M1", 90_000).with_end(91_000))
+            .with_record(ExecutionRecord::new("A3", "T3", "M1", 91_500).with_end(92_000))
+            .with_record(ExecutionRecord::new("A4", "T4", "M1", 92_500));
+
+        let estimator = TransitionEstimator::new()
+            .with_max_gap(10_000)
+            .with_min_observations(2)
+            .with_execution_log(&log, &tasks);
+
+        assert_eq!(estimator.observation_count("M1", "red", "blue"), 2);
+        assert_eq!(estimator.observation_count("M1", "blue", "red"), 0);
+        // Running activities are not observed
+        assert_eq!(estimator.observation_count("M1", "blue", "blue"), 0);
+        let matrices = estimator.estimate();
+        assert_eq!(matrices.get_transition_time("M1", "red", "blue"), 400);
+    }
+}
@@ -0,0 +1,77 @@
+//! Schema - JSON Schema of the serde models
+//!
+//! JSON Schemas (draft 2020-12, via schemars) of the problem format and
+//! the core models, so clients in other languages can validate payloads
+//! and generate typed bindings. Enabled by the `schema` feature.
+
+use crate::models::{Activity, Constraint, Resource, Schedule, Task};
+use crate::scheduler::ScheduleRequest;
+use schemars::JsonSchema;
+use std::collections::BTreeMap;
+
+pub use schemars::Schema;
+
+/// JSON Schema of any model type
+pub fn schema_of<T: JsonSchema>() -> Schema {
+    schemars::schema_for!(T)
+}
+
+/// Schemas of the problem format (`ScheduleRequest`) and the core models
+/// (`Task`, `Activity`, `Resource`, `Constraint`, `Schedule`), by type name
+pub fn model_schemas() -> BTreeMap<&'static str, Schema> {
+    BTreeMap::from([
+        ("ScheduleRequest", schema_of::<ScheduleRequest>()),
+        ("Task", schema_of::<Task>()),
+        ("Activity", schema_of::<Activity>()),
+        ("Resource", schema_of::<Resource>()),
+        ("Constraint", schema_of::<Constraint>()),
+        ("Schedule", schema_of::<Schedule>()),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ActivityDuration;
+    use serde_json::Value;
+
+    /// Property names and required names of an object schema
+    fn fields(schema: &Value) -> (Vec<&str>, Vec<&str>) {
+        let properties = schema["properties"].as_object().unwrap();
+        let required = schema["required"].as_array().unwrap();
+        (
+            properties.keys().map(String::as_str).collect(),
+            required.iter().filter_map(Value::as_str).collect(),
+        )
+    }
+
+    #[test]
+    fn test_request_schema() {
+        let schemas = model_schemas();
+        assert_eq!(schemas.len(), 6);
+        let request = schemas["ScheduleRequest"].as_value();
+        let (_, required) = fields(request);
+        assert_eq!(required, vec!["tasks", "resources", "start_time_ms"]);
+        // Rules travel as their source text
+        assert_eq!(request["$defs"]["AttrExpr"]["type"], "string");
+    }
+
+    #[test]
+    fn test_schema_matches_serialization() {
+        let task = Task::new("T1").with_activity(
+            Activity::new("A1", "T1", 1).with_duration(ActivityDuration::fixed(1000)),
+        );
+        let value = serde_json::to_value(&task).unwrap();
+        let keys: Vec<&str> = value
+            .as_object()
+            .unwrap()
+            .keys()
+            .map(String::as_str)
+            .collect();
+
+        let schema = schema_of::<Task>();
+        let (properties, required) = fields(schema.as_value());
+        assert!(keys.iter().all(|k| properties.contains(k)));
+        assert!(required.iter().all(|k| keys.contains(k)));
+    }
+}
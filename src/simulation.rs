@@ -0,0 +1,520 @@
+//! Simulation - Discrete-event simulation kernel
+//!
+//! Building blocks for event-driven simulations (dispatching, online
+//! scheduling), so each one does not re-implement time advance:
+//!
+//! - [`EventQueue`] - Future event list with the simulation clock.
+//!   Events at the same time fire in the order they were scheduled, so
+//!   runs are deterministic.
+//! - [`ResourceQueue`] - Waiting line and busy units of one resource,
+//!   with its [`QueueStats`]: queue length over time, waiting times,
+//!   utilization, starvation and blocking.
+//!
+//! ```
+//! use u_ras::simulation::EventQueue;
+//!
+//! let mut events = EventQueue::new(0);
+//! events.schedule_at(500, "done");
+//! events.schedule_in(100, "start");
+//! assert_eq!(events.pop(), Some((100, "start")));
+//! assert_eq!(events.now(), 100);
+//! assert_eq!(events.pop(), Some((500, "done")));
+//! assert_eq!(events.pop(), None);
+//! ```
+
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+
+/// Handle of a scheduled event
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct EventId(u64);
+
+/// Future event list and simulation clock
+#[derive(Debug, Clone)]
+pub struct EventQueue<E> {
+    /// Current time (ms)
+    now_ms: i64,
+    /// Id of the next scheduled event (also the tie-break order)
+    next_id: u64,
+    /// Pending (time, id), earliest first; cancelled ids are skipped
+    heap: BinaryHeap<Reverse<(i64, u64)>>,
+    /// Payloads of pending events
+    pending: HashMap<u64, E>,
+}
+
+impl<E> EventQueue<E> {
+    /// Create empty queue with the clock at `start_ms`
+    pub fn new(start_ms: i64) -> Self {
+        Self {
+            now_ms: start_ms,
+            next_id: 0,
+            heap: BinaryHeap::new(),
+            pending: HashMap::new(),
+        }
+    }
+
+    /// Current simulation time (ms)
+    pub fn now(&self) -> i64 {
+        self.now_ms
+    }
+
+    /// Number of pending events
+    pub fn len(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Whether no events are pending
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    /// Schedule an event at `time_ms` (the past means now)
+    pub fn schedule_at(&mut self, time_ms: i64, event: E) -> EventId {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.heap.push(Reverse((time_ms.max(self.now_ms), id)));
+        self.pending.insert(id, event);
+        EventId(id)
+    }
+
+    /// Schedule an event `delay_ms` from now
+    pub fn schedule_in(&mut self, delay_ms: i64, event: E) -> EventId {
+        self.schedule_at(self.now_ms.saturating_add(delay_ms.max(0)), event)
+    }
+
+    /// Cancel a pending event, returning it
+    pub fn cancel(&mut self, id: EventId) -> Option<E> {
+        self.pending.remove(&id.0)
+    }
+
+    /// Time of the next pending event
+    pub fn peek_time(&mut self) -> Option<i64> {
+        self.discard_cancelled();
+        self.heap.peek().map(|Reverse((time, _))| *time)
+    }
+
+    /// Pop the next event, advancing the clock to its time
+    pub fn pop(&mut self) -> Option<(i64, E)> {
+        self.discard_cancelled();
+        let Reverse((time, id)) = self.heap.pop()?;
+        self.now_ms = time;
+        self.pending.remove(&id).map(|event| (time, event))
+    }
+
+    /// Pop the next event if it is due now (the clock does not move)
+    pub fn pop_due(&mut self) -> Option<E> {
+        if self.peek_time()? <= self.now_ms {
+            self.pop().map(|(_, event)| event)
+        } else {
+            None
+        }
+    }
+
+    /// Advance the clock without firing events (never backwards)
+    pub fn advance_to(&mut self, time_ms: i64) {
+        self.now_ms = self.now_ms.max(time_ms);
+    }
+
+    fn discard_cancelled(&mut self) {
+        while let Some(Reverse((_, id))) = self.heap.peek() {
+            if self.pending.contains_key(id) {
+                break;
+            }
+            self.heap.pop();
+        }
+    }
+}
+
+/// Waiting line and busy units of a resource
+///
+/// Items wait in arrival order; the owner decides which one to serve
+/// (`take`) and seizes a unit for it. Every change is stamped with the
+/// simulation time, so the queue keeps its own [`QueueStats`].
+#[derive(Debug, Clone)]
+pub struct ResourceQueue<T> {
+    /// Units that can serve at once
+    capacity: usize,
+    /// Units serving
+    busy: usize,
+    /// Waiting items with their arrival time
+    waiting: VecDeque<(i64, T)>,
+    /// Statistics up to the last change
+    stats: QueueStats,
+}
+
+impl<T> ResourceQueue<T> {
+    /// Create idle queue with `capacity` units (at least one), observed
+    /// from `start_ms`
+    pub fn new(capacity: usize, start_ms: i64) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            busy: 0,
+            waiting: VecDeque::new(),
+            stats: QueueStats {
+                start_ms,
+                end_ms: start_ms,
+                length_history: vec![(start_ms, 0)],
+                ..Default::default()
+            },
+        }
+    }
+
+    /// Units that can serve at once
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Units serving
+    pub fn busy(&self) -> usize {
+        self.busy
+    }
+
+    /// Whether a unit is free
+    pub fn is_idle(&self) -> bool {
+        self.busy < self.capacity
+    }
+
+    /// Number of waiting items
+    pub fn len(&self) -> usize {
+        self.waiting.len()
+    }
+
+    /// Whether nothing waits
+    pub fn is_empty(&self) -> bool {
+        self.waiting.is_empty()
+    }
+
+    /// Waiting items in arrival order
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.waiting.iter().map(|(_, item)| item)
+    }
+
+    /// Arrival time of the `index`-th waiting item
+    pub fn arrival(&self, index: usize) -> Option<i64> {
+        self.waiting.get(index).map(|(at, _)| *at)
+    }
+
+    /// Add an item arriving at `now_ms`
+    pub fn enqueue(&mut self, now_ms: i64, item: T) {
+        self.observe(now_ms);
+        self.waiting.push_back((now_ms, item));
+        self.record_length(now_ms);
+    }
+
+    /// Remove the `index`-th waiting item for service at `now_ms`,
+    /// recording its waiting time
+    pub fn take(&mut self, now_ms: i64, index: usize) -> Option<(i64, T)> {
+        self.observe(now_ms);
+        let taken = self.waiting.remove(index)?;
+        self.stats.waits_ms.push(now_ms - taken.0);
+        self.record_length(now_ms);
+        Some(taken)
+    }
+
+    /// Remove every waiting item matching `pred` without serving it, e.g.
+    /// a task another resource picked up
+    pub fn remove_where(&mut self, now_ms: i64, mut pred: impl FnMut(&T) -> bool) {
+        self.observe(now_ms);
+        self.waiting.retain(|(_, item)| !pred(item));
+        self.record_length(now_ms);
+    }
+
+    /// Occupy a free unit; false if all are busy
+    pub fn seize(&mut self, now_ms: i64) -> bool {
+        self.observe(now_ms);
+        let free = self.is_idle();
+        if free {
+            self.busy += 1;
+        }
+        free
+    }
+
+    /// Free a busy unit
+    pub fn release(&mut self, now_ms: i64) {
+        self.observe(now_ms);
+        self.busy = self.busy.saturating_sub(1);
+    }
+
+    /// Statistics from the start up to `now_ms`
+    pub fn stats(&self, now_ms: i64) -> QueueStats {
+        let mut stats = self.stats.clone();
+        self.accumulate(&mut stats, now_ms);
+        stats
+    }
+
+    fn observe(&mut self, now_ms: i64) {
+        let mut stats = std::mem::take(&mut self.stats);
+        self.accumulate(&mut stats, now_ms);
+        self.stats = stats;
+    }
+
+    /// Add the interval since the last change, in the current state
+    fn accumulate(&self, stats: &mut QueueStats, now_ms: i64) {
+        let span = now_ms - stats.end_ms;
+        if span <= 0 {
+            return;
+        }
+        stats.queue_area += self.waiting.len() as f64 * span as f64;
+        stats.busy_area += self.busy as f64 / self.capacity as f64 * span as f64;
+        match (self.is_idle(), self.waiting.is_empty()) {
+            (true, true) => stats.starved_ms += span,
+            (false, false) => stats.blocked_ms += span,
+            _ => {}
+        }
+        stats.end_ms = now_ms;
+    }
+
+    fn record_length(&mut self, now_ms: i64) {
+        let len = self.waiting.len();
+        let history = &mut self.stats.length_history;
+        match history.last_mut() {
+            Some(last) if last.0 == now_ms => last.1 = len,
+            Some(last) if last.1 == len => {}
+            _ => history.push((now_ms, len)),
+        }
+        self.stats.max_queue_length = self.stats.max_queue_length.max(len);
+    }
+}
+
+/// Queueing statistics of one resource over an observed period
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct QueueStats {
+    /// Start of the observed period (ms)
+    pub start_ms: i64,
+    /// End of the observed period (ms)
+    pub end_ms: i64,
+    /// Integral of the queue length over time (items * ms)
+    pub queue_area: f64,
+    /// Integral of the busy share of the units over time (ms)
+    pub busy_area: f64,
+    /// Longest queue seen
+    pub max_queue_length: usize,
+    /// Time idle with nothing waiting (ms): the resource is starved
+    pub starved_ms: i64,
+    /// Time fully busy with work waiting (ms): the resource blocks its queue
+    pub blocked_ms: i64,
+    /// Waiting time of every served item (ms), in service order
+    pub waits_ms: Vec<i64>,
+    /// Queue length over time: (time, length) at every change
+    pub length_history: Vec<(i64, usize)>,
+}
+
+impl QueueStats {
+    /// Length of the observed period (ms)
+    pub fn duration_ms(&self) -> i64 {
+        self.end_ms - self.start_ms
+    }
+
+    /// Time-averaged queue length
+    pub fn mean_queue_length(&self) -> f64 {
+        self.per_ms(self.queue_area)
+    }
+
+    /// Busy share of the capacity (0.0 - 1.0)
+    pub fn utilization(&self) -> f64 {
+        self.per_ms(self.busy_area)
+    }
+
+    /// Starved share of the period (0.0 - 1.0)
+    pub fn starvation(&self) -> f64 {
+        self.per_ms(self.starved_ms as f64)
+    }
+
+    /// Blocked share of the period (0.0 - 1.0)
+    pub fn blocking(&self) -> f64 {
+        self.per_ms(self.blocked_ms as f64)
+    }
+
+    /// Summary of the waiting time distribution
+    pub fn waiting(&self) -> WaitStats {
+        let mut waits = self.waits_ms.clone();
+        waits.sort_unstable();
+        let percentile = |p: f64| -> i64 {
+            if waits.is_empty() {
+                return 0;
+            }
+            // Nearest rank
+            let rank = (p * waits.len() as f64).ceil() as usize;
+            waits[rank.clamp(1, waits.len()) - 1]
+        };
+        WaitStats {
+            count: waits.len(),
+            mean_ms: if waits.is_empty() {
+                0.0
+            } else {
+                waits.iter().sum::<i64>() as f64 / waits.len() as f64
+            },
+            p50_ms: percentile(0.5),
+            p90_ms: percentile(0.9),
+            max_ms: waits.last().copied().unwrap_or(0),
+        }
+    }
+
+    fn per_ms(&self, value: f64) -> f64 {
+        if self.duration_ms() > 0 {
+            value / self.duration_ms() as f64
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Waiting time distribution summary
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct WaitStats {
+    /// Served items
+    pub count: usize,
+    /// Mean wait (ms)
+    pub mean_ms: f64,
+    /// Median wait (ms)
+    pub p50_ms: i64,
+    /// 90th percentile wait (ms)
+    pub p90_ms: i64,
+    /// Longest wait (ms)
+    pub max_ms: i64,
+}
+
+/// Queueing statistics of one resource in a [`QueueReport`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ResourceQueueStats {
+    /// Resource ID
+    pub resource_id: String,
+    /// Statistics
+    pub stats: QueueStats,
+}
+
+/// Queueing statistics of all resources of a simulation run
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct QueueReport {
+    /// Per resource, in resource order
+    pub resources: Vec<ResourceQueueStats>,
+}
+
+impl QueueReport {
+    /// Statistics of a resource
+    pub fn get(&self, resource_id: &str) -> Option<&QueueStats> {
+        self.resources
+            .iter()
+            .find(|r| r.resource_id == resource_id)
+            .map(|r| &r.stats)
+    }
+
+    /// Bottleneck: the resource with the longest average queue, ties
+    /// broken by utilization
+    pub fn bottleneck(&self) -> Option<&ResourceQueueStats> {
+        self.resources.iter().max_by(|a, b| {
+            let key = |r: &ResourceQueueStats| (r.stats.mean_queue_length(), r.stats.utilization());
+            key(a)
+                .partial_cmp(&key(b))
+                .unwrap_or(std::cmp::Ordering::Equal)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_event_order_and_cancel() {
+        let mut events = EventQueue::new(1000);
+        events.schedule_at(2000, 'b');
+        let c = events.schedule_at(1500, 'c');
+        events.schedule_at(2000, 'd');
+        // The past is clamped to now
+        events.schedule_at(0, 'a');
+        assert_eq!(events.len(), 4);
+
+        assert_eq!(events.cancel(c), Some('c'));
+        assert_eq!(events.cancel(c), None);
+        assert_eq!(events.pop_due(), Some('a'));
+        assert_eq!(events.pop_due(), None);
+        assert_eq!(events.peek_time(), Some(2000));
+        // Simultaneous events fire in scheduling order
+        assert_eq!(events.pop(), Some((2000, 'b')));
+        assert_eq!(events.pop_due(), Some('d'));
+        assert!(events.is_empty());
+        assert_eq!(events.now(), 2000);
+
+        events.advance_to(1000);
+        assert_eq!(events.now(), 2000);
+        events.schedule_in(-5, 'e');
+        assert_eq!(events.pop(), Some((2000, 'e')));
+    }
+
+    #[test]
+    fn test_resource_queue() {
+        let mut queue = ResourceQueue::new(2, 0);
+        queue.enqueue(0, "J1");
+        queue.enqueue(10, "J2");
+        queue.enqueue(20, "J3");
+
+        assert!(queue.seize(20));
+        assert!(queue.seize(20));
+        assert!(!queue.seize(20));
+        assert!(!queue.is_idle());
+        assert_eq!(queue.take(30, 1), Some((10, "J2")));
+        queue.release(40);
+        assert_eq!(queue.busy(), 1);
+
+        queue.remove_where(40, |j| *j == "J1");
+        assert_eq!(queue.iter().collect::<Vec<_>>(), vec![&"J3"]);
+        assert_eq!(queue.arrival(0), Some(20));
+    }
+
+    #[test]
+    fn test_queue_stats() {
+        // One unit: A arrives at 0 and is served at once for 100 ms, B and
+        // C arrive at 20 and 40 and wait, then the unit idles after 300
+        let mut queue = ResourceQueue::new(1, 0);
+        queue.enqueue(0, 'A');
+        queue.take(0, 0);
+        queue.seize(0);
+        queue.enqueue(20, 'B');
+        queue.enqueue(40, 'C');
+        queue.release(100);
+        queue.take(100, 0);
+        queue.seize(100);
+        queue.release(200);
+        queue.take(200, 0);
+        queue.seize(200);
+        queue.release(300);
+
+        let stats = queue.stats(400);
+        assert_eq!(stats.duration_ms(), 400);
+        assert_eq!(stats.utilization(), 0.75);
+        assert_eq!(stats.starved_ms, 100);
+        // B waits 20..100 and C 40..200 behind a busy unit
+        assert_eq!(stats.blocked_ms, 180);
+        assert_eq!(stats.max_queue_length, 2);
+        assert_eq!(stats.mean_queue_length(), (80.0 + 60.0 + 100.0) / 400.0);
+        assert_eq!(
+            stats.length_history,
+            vec![(0, 0), (20, 1), (40, 2), (100, 1), (200, 0)]
+        );
+
+        let waiting = stats.waiting();
+        assert_eq!(waiting.count, 3);
+        assert_eq!(waiting.mean_ms, 80.0);
+        assert_eq!(
+            (waiting.p50_ms, waiting.p90_ms, waiting.max_ms),
+            (80, 160, 160)
+        );
+
+        let report = QueueReport {
+            resources: vec![
+                ResourceQueueStats {
+                    resource_id: "M1".into(),
+                    stats: QueueStats::default(),
+                },
+                ResourceQueueStats {
+                    resource_id: "M2".into(),
+                    stats,
+                },
+            ],
+        };
+        assert_eq!(report.bottleneck().unwrap().resource_id, "M2");
+        assert_eq!(report.get("M1").unwrap().utilization(), 0.0);
+    }
+}
@@ -0,0 +1,378 @@
+//! Storage - SQLite persistence
+//!
+//! Saves problem instances, schedules and KPI snapshots (feature
+//! `sqlite`) so runs can be reproduced and compared over time. Payloads
+//! are stored as the models' JSON next to a few indexed columns; problems
+//! and schedules as versioned documents (`format`), migrated on load.
+//!
+//! ```no_run
+//! use u_ras::scheduler::{ScheduleKpi, ScheduleRequest, SimpleScheduler};
+//! use u_ras::storage::ScheduleStore;
+//!
+//! # fn run(request: ScheduleRequest) -> Result<(), u_ras::storage::StorageError> {
+//! let store = ScheduleStore::open("plans.db")?;
+//! let problem_id = store.save_problem("week 12", &request)?;
+//!
+//! let schedule = SimpleScheduler::new().schedule_request(&request);
+//! let schedule_id = store.save_schedule(Some(problem_id), "greedy", &schedule)?;
+//! store.save_kpi(schedule_id, &ScheduleKpi::calculate(&schedule, &request.tasks))?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::format::{FormatError, Versioned};
+use crate::models::Schedule;
+use crate::scheduler::{ScheduleKpi, ScheduleRequest};
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Schema version stored in `PRAGMA user_version`
+const SCHEMA_VERSION: i64 = 1;
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS problems (
+    id INTEGER PRIMARY KEY,
+    name TEXT NOT NULL,
+    created_at_ms INTEGER NOT NULL,
+    data TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS schedules (
+    id INTEGER PRIMARY KEY,
+    problem_id INTEGER REFERENCES problems(id) ON DELETE SET NULL,
+    name TEXT NOT NULL,
+    created_at_ms INTEGER NOT NULL,
+    makespan_ms INTEGER NOT NULL,
+    data TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS schedules_problem ON schedules(problem_id);
+CREATE TABLE IF NOT EXISTS kpi_snapshots (
+    id INTEGER PRIMARY KEY,
+    schedule_id INTEGER NOT NULL REFERENCES schedules(id) ON DELETE CASCADE,
+    created_at_ms INTEGER NOT NULL,
+    data TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS kpi_snapshots_schedule ON kpi_snapshots(schedule_id);
+";
+
+/// Storage error
+#[derive(Debug)]
+pub enum StorageError {
+    /// SQLite failure
+    Sqlite(rusqlite::Error),
+    /// Stored payload could not be (de)serialized
+    Json(serde_json::Error),
+    /// Stored problem or schedule could not be migrated or loaded
+    Format(FormatError),
+    /// Database written by a newer schema version
+    UnsupportedVersion(i64),
+}
+
+impl std::fmt::Display for StorageError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StorageError::Sqlite(e) => write!(f, "sqlite error: {}", e),
+            StorageError::Json(e) => write!(f, "invalid stored payload: {}", e),
+            StorageError::Format(e) => write!(f, "invalid stored payload: {}", e),
+            StorageError::UnsupportedVersion(v) => {
+                write!(
+                    f,
+                    "unsupported schema version {} (expected <= {})",
+                    v, SCHEMA_VERSION
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for StorageError {}
+
+impl From<rusqlite::Error> for StorageError {
+    fn from(e: rusqlite::Error) -> Self {
+        StorageError::Sqlite(e)
+    }
+}
+
+impl From<serde_json::Error> for StorageError {
+    fn from(e: serde_json::Error) -> Self {
+        StorageError::Json(e)
+    }
+}
+
+impl From<FormatError> for StorageError {
+    fn from(e: FormatError) -> Self {
+        StorageError::Format(e)
+    }
+}
+
+/// Storage result
+pub type StorageResult<T> = Result<T, StorageError>;
+
+/// Listing entry of a stored problem or schedule
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StoredEntry {
+    /// Row id
+    pub id: i64,
+    /// Name given on save
+    pub name: String,
+    /// Save time (epoch ms)
+    pub created_at_ms: i64,
+    /// Problem the schedule was solved for (schedules only)
+    pub problem_id: Option<i64>,
+    /// Makespan (schedules only)
+    pub makespan_ms: Option<i64>,
+}
+
+/// Stored KPI snapshot
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KpiSnapshot {
+    /// Row id
+    pub id: i64,
+    /// Schedule the KPIs were measured on
+    pub schedule_id: i64,
+    /// Save time (epoch ms)
+    pub created_at_ms: i64,
+    /// KPIs
+    pub kpi: ScheduleKpi,
+}
+
+/// SQLite store of problems, schedules and KPI snapshots
+pub struct ScheduleStore {
+    conn: Connection,
+}
+
+impl ScheduleStore {
+    /// Open (or create) a database file
+    pub fn open(path: impl AsRef<Path>) -> StorageResult<Self> {
+        Self::init(Connection::open(path)?)
+    }
+
+    /// Open a private in-memory database
+    pub fn open_in_memory() -> StorageResult<Self> {
+        Self::init(Connection::open_in_memory()?)
+    }
+
+    fn init(conn: Connection) -> StorageResult<Self> {
+        let version: i64 = conn.query_row("PRAGMA user_version", [], |row| row.get(0))?;
+        if version > SCHEMA_VERSION {
+            return Err(StorageError::UnsupportedVersion(version));
+        }
+        conn.execute_batch("PRAGMA foreign_keys = ON;")?;
+        conn.execute_batch(SCHEMA)?;
+        conn.pragma_update(None, "user_version", SCHEMA_VERSION)?;
+        Ok(Self { conn })
+    }
+
+    /// Save a problem instance, returning its id
+    pub fn save_problem(&self, name: &str, request: &ScheduleRequest) -> StorageResult<i64> {
+        self.conn.execute(
+            "INSERT INTO problems (name, created_at_ms, data) VALUES (?1, ?2, ?3)",
+            params![name, now_ms(), request.to_versioned_json()?],
+        )?;
+        Ok(self.conn.last_insert_rowid())
+    }
+
+    /// Load a problem instance
+    pub fn load_problem(&self, id: i64) -> StorageResult<Option<ScheduleRequest>> {
+        self.load_versioned("SELECT data FROM problems WHERE id = ?1", id)
+    }
+
+    /// All problems, newest first
+    pub fn list_problems(&self) -> StorageResult<Vec<StoredEntry>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, name, created_at_ms FROM problems ORDER BY created_at_ms DESC, id DESC",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok(StoredEntry {
+                id: row.get(0)?,
+                name: row.get(1)?,
+                created_at_ms: row.get(2)?,
+                problem_id: None,
+                makespan_ms: None,
+            })
+        })?;
+        Ok(rows.collect::<Result<_, _>>()?)
+    }
+
+    /// Save a schedule, optionally linked to the problem it solves
+    pub fn save_schedule(
+        &self,
+        problem_id: Option<i64>,
+        name: &str,
+        schedule: &Schedule,
+    ) -> StorageResult<i64> {
+        self.conn.execute(
+            "INSERT INTO schedules (problem_id, name, created_at_ms, makespan_ms, data)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                problem_id,
+                name,
+                now_ms(),
+                schedule.makespan_ms,
+                schedule.to_versioned_json()?
+            ],
+        )?;
+        Ok(self.conn.last_insert_rowid())
+    }
+
+    /// Load a schedule
+    pub fn load_schedule(&self, id: i64) -> StorageResult<Option<Schedule>> {
+        self.load_versioned("SELECT data FROM schedules WHERE id = ?1", id)
+    }
+
+    /// Schedules, newest first (only those of `problem_id` if given)
+    pub fn list_schedules(&self, problem_id: Option<i64>) -> StorageResult<Vec<StoredEntry>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, name, created_at_ms, problem_id, makespan_ms FROM schedules
+             WHERE ?1 IS NULL OR problem_id = ?1
+             ORDER BY created_at_ms DESC, id DESC",
+        )?;
+        let rows = stmt.query_map([problem_id], |row| {
+            Ok(StoredEntry {
+                id: row.get(0)?,
+                name: row.get(1)?,
+                created_at_ms: row.get(2)?,
+                problem_id: row.get(3)?,
+                makespan_ms: row.get(4)?,
+            })
+        })?;
+        Ok(rows.collect::<Result<_, _>>()?)
+    }
+
+    /// Save a KPI snapshot of a schedule
+    pub fn save_kpi(&self, schedule_id: i64, kpi: &ScheduleKpi) -> StorageResult<i64> {
+        self.conn.execute(
+            "INSERT INTO kpi_snapshots (schedule_id, created_at_ms, data) VALUES (?1, ?2, ?3)",
+            params![schedule_id, now_ms(), serde_json::to_string(kpi)?],
+        )?;
+        Ok(self.conn.last_insert_rowid())
+    }
+
+    /// KPI snapshots, oldest first (only those of `schedule_id` if given)
+    pub fn kpi_history(&self, schedule_id: Option<i64>) -> StorageResult<Vec<KpiSnapshot>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, schedule_id, created_at_ms, data FROM kpi_snapshots
+             WHERE ?1 IS NULL OR schedule_id = ?1
+             ORDER BY created_at_ms, id",
+        )?;
+        let rows = stmt.query_map([schedule_id], |row| {
+            Ok((
+                row.get(0)?,
+                row.get(1)?,
+                row.get(2)?,
+                row.get::<_, String>(3)?,
+            ))
+        })?;
+        rows.map(|row| {
+            let (id, schedule_id, created_at_ms, data) = row?;
+            Ok(KpiSnapshot {
+                id,
+                schedule_id,
+                created_at_ms,
+                kpi: serde_json::from_str(&data)?,
+            })
+        })
+        .collect()
+    }
+
+    /// Delete a problem (its schedules are kept, unlinked)
+    pub fn delete_problem(&self, id: i64) -> StorageResult<bool> {
+        Ok(self
+            .conn
+            .execute("DELETE FROM problems WHERE id = ?1", [id])?
+            > 0)
+    }
+
+    /// Delete a schedule and its KPI snapshots
+    pub fn delete_schedule(&self, id: i64) -> StorageResult<bool> {
+        Ok(self
+            .conn
+            .execute("DELETE FROM schedules WHERE id = ?1", [id])?
+            > 0)
+    }
+
+    fn load_versioned<T: Versioned>(&self, sql: &str, id: i64) -> StorageResult<Option<T>> {
+        let data: Option<String> = self
+            .conn
+            .query_row(sql, [id], |row| row.get(0))
+            .optional()?;
+        data.map(|data| T::from_versioned_json(&data).map_err(StorageError::from))
+            .transpose()
+    }
+}
+
+fn now_ms() -> i64 {
+    chrono::Utc::now().timestamp_millis()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Activity, ActivityDuration, Resource, Task};
+    use crate::scheduler::SimpleScheduler;
+
+    fn request() -> ScheduleRequest {
+        let task = Task::new("T1").with_activity(
+            Activity::new("A1", "T1", 1)
+                .with_duration(ActivityDuration::fixed(1000))
+                .with_resources("machine", vec!["M1".into()]),
+        );
+        ScheduleRequest::new(vec![task], vec![Resource::primary("M1")]).with_start_time(500)
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let store = ScheduleStore::open_in_memory().unwrap();
+        let request = request();
+        let problem_id = store.save_problem("p1", &request).unwrap();
+
+        let loaded = store.load_problem(problem_id).unwrap().unwrap();
+        assert_eq!(loaded.start_time_ms, 500);
+        assert_eq!(loaded.tasks[0].activities[0].id, "A1");
+        assert!(store.load_problem(problem_id + 1).unwrap().is_none());
+
+        let schedule = SimpleScheduler::new().schedule_request(&loaded);
+        let first = store
+            .save_schedule(Some(problem_id), "greedy", &schedule)
+            .unwrap();
+        let second = store.save_schedule(None, "manual", &schedule).unwrap();
+        let kpi = ScheduleKpi::calculate(&schedule, &loaded.tasks);
+        store.save_kpi(first, &kpi).unwrap();
+        store.save_kpi(second, &kpi).unwrap();
+
+        let restored = store.load_schedule(first).unwrap().unwrap();
+        assert_eq!(restored.makespan_ms, schedule.makespan_ms);
+
+        let listed = store.list_schedules(Some(problem_id)).unwrap();
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].name, "greedy");
+        assert_eq!(listed[0].makespan_ms, Some(1500));
+        assert_eq!(store.list_schedules(None).unwrap().len(), 2);
+        assert_eq!(store.list_problems().unwrap()[0].name, "p1");
+
+        let history = store.kpi_history(Some(first)).unwrap();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].kpi.makespan_ms, kpi.makespan_ms);
+
+        // Deleting a schedule drops its snapshots
+        assert!(store.delete_schedule(first).unwrap());
+        assert!(store.kpi_history(Some(first)).unwrap().is_empty());
+        assert_eq!(store.kpi_history(None).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_unversioned_rows_migrate() {
+        let store = ScheduleStore::open_in_memory().unwrap();
+        // Row written before payloads were versioned
+        store
+            .conn
+            .execute(
+                "INSERT INTO problems (name, created_at_ms, data) VALUES ('old', 0, ?1)",
+                [serde_json::to_string(&request()).unwrap()],
+            )
+            .unwrap();
+
+        let loaded = store.load_problem(1).unwrap().unwrap();
+        assert_eq!(loaded.start_time_ms, 500);
+    }
+}
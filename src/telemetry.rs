@@ -0,0 +1,206 @@
+//! Telemetry - Solver metrics export
+//!
+//! With the `metrics` feature enabled, solvers report counters, gauges
+//! and histograms through the [`metrics`](https://docs.rs/metrics)
+//! facade; an embedding service wires them to Prometheus, OpenTelemetry
+//! or any other backend by installing a recorder. Without the feature the
+//! calls compile to nothing.
+//!
+//! | Metric | Kind | Reported by |
+//! |--------|------|-------------|
+//! | `u_ras_ga_generations_total` | counter | `Population::evolve` |
+//! | `u_ras_ga_best_fitness` | gauge (incumbent per generation) | `Population::evolve` |
+//! | `u_ras_cp_solves_total` | counter, label `status` | `SimpleCpSolver::solve` |
+//! | `u_ras_cp_nodes_explored_total` | counter | `SimpleCpSolver::solve` |
+//! | `u_ras_cp_solve_time_ms` | histogram | `SimpleCpSolver::solve` |
+//! | `u_ras_cp_objective` | gauge | `SimpleCpSolver::solve` |
+//! | `u_ras_schedule_runs_total` | counter | `SimpleScheduler::schedule` |
+//! | `u_ras_schedule_time_ms` | histogram | `SimpleScheduler::schedule` |
+//! | `u_ras_schedule_makespan_ms` | histogram | `SimpleScheduler::schedule` |
+
+use crate::cp::CpSolution;
+use crate::models::Schedule;
+use std::time::Instant;
+
+/// GA generations evolved
+pub const GA_GENERATIONS: &str = "u_ras_ga_generations_total";
+/// Best fitness found so far, updated every generation
+pub const GA_BEST_FITNESS: &str = "u_ras_ga_best_fitness";
+/// CP solves, by status
+pub const CP_SOLVES: &str = "u_ras_cp_solves_total";
+/// CP search nodes explored
+pub const CP_NODES: &str = "u_ras_cp_nodes_explored_total";
+/// CP solve time (ms)
+pub const CP_SOLVE_TIME: &str = "u_ras_cp_solve_time_ms";
+/// Objective of the last CP solution
+pub const CP_OBJECTIVE: &str = "u_ras_cp_objective";
+/// Greedy scheduler runs
+pub const SCHEDULE_RUNS: &str = "u_ras_schedule_runs_total";
+/// Greedy scheduler run time (ms)
+pub const SCHEDULE_TIME: &str = "u_ras_schedule_time_ms";
+/// Makespan of greedy schedules (ms)
+pub const SCHEDULE_MAKESPAN: &str = "u_ras_schedule_makespan_ms";
+
+/// Report one GA generation and the incumbent fitness
+pub(crate) fn ga_generation(best_fitness: Option<f64>) {
+    #[cfg(feature = "metrics")]
+    {
+        metrics::counter!(GA_GENERATIONS).increment(1);
+        if let Some(fitness) = best_fitness {
+            metrics::gauge!(GA_BEST_FITNESS).set(fitness);
+        }
+    }
+    #[cfg(not(feature = "metrics"))]
+    let _ = best_fitness;
+}
+
+/// Report a finished CP solve
+pub(crate) fn cp_solve(solution: &CpSolution) {
+    #[cfg(feature = "metrics")]
+    {
+        let status = format!("{:?}", solution.status).to_lowercase();
+        metrics::counter!(CP_SOLVES, "status" => status).increment(1);
+        metrics::counter!(CP_NODES).increment(solution.num_nodes);
+        metrics::histogram!(CP_SOLVE_TIME).record(solution.solve_time_ms as f64);
+        if let Some(objective) = solution.objective_value {
+            metrics::gauge!(CP_OBJECTIVE).set(objective);
+        }
+    }
+    #[cfg(not(feature = "metrics"))]
+    let _ = solution;
+}
+
+/// Report a finished greedy scheduling run
+pub(crate) fn schedule_run(clock: &Stopwatch, schedule: &Schedule) {
+    #[cfg(feature = "metrics")]
+    {
+        metrics::counter!(SCHEDULE_RUNS).increment(1);
+        metrics::histogram!(SCHEDULE_TIME).record(clock.elapsed_ms() as f64);
+        metrics::histogram!(SCHEDULE_MAKESPAN).record(schedule.makespan_ms as f64);
+    }
+    #[cfg(not(feature = "metrics"))]
+    let _ = (clock, schedule);
+}
+
+/// Wall-clock timer
+///
+/// `wasm32-unknown-unknown` has no clock (`Instant::now` panics there),
+/// so it always reads zero on that target.
+pub(crate) struct Stopwatch(Option<Instant>);
+
+impl Stopwatch {
+    /// Start timing
+    pub(crate) fn start() -> Self {
+        let clockless = cfg!(all(target_arch = "wasm32", target_os = "unknown"));
+        Self((!clockless).then(Instant::now))
+    }
+
+    /// Milliseconds since start
+    pub(crate) fn elapsed_ms(&self) -> i64 {
+        self.0.map_or(0, |t| t.elapsed().as_millis() as i64)
+    }
+}
+
+#[cfg(all(test, feature = "metrics"))]
+mod tests {
+    use super::*;
+    use crate::cp::{CpModel, CpSolver, IntervalVar, SimpleCpSolver, SolverConfig};
+    use crate::models::{Activity, ActivityDuration, Resource, Task};
+    use crate::scheduler::SimpleScheduler;
+    use metrics::{
+        Counter, CounterFn, Gauge, GaugeFn, Histogram, HistogramFn, Key, KeyName, Metadata,
+        Recorder, SharedString, Unit,
+    };
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex};
+
+    /// Values reported to one metric
+    #[derive(Default)]
+    struct Values(Mutex<Vec<f64>>);
+
+    impl CounterFn for Values {
+        fn increment(&self, value: u64) {
+            self.0.lock().unwrap().push(value as f64);
+        }
+        fn absolute(&self, value: u64) {
+            self.0.lock().unwrap().push(value as f64);
+        }
+    }
+
+    impl GaugeFn for Values {
+        fn increment(&self, value: f64) {
+            self.0.lock().unwrap().push(value);
+        }
+        fn decrement(&self, value: f64) {
+            self.0.lock().unwrap().push(-value);
+        }
+        fn set(&self, value: f64) {
+            self.0.lock().unwrap().push(value);
+        }
+    }
+
+    impl HistogramFn for Values {
+        fn record(&self, value: f64) {
+            self.0.lock().unwrap().push(value);
+        }
+    }
+
+    /// Recorder keeping every reported value by metric name
+    #[derive(Default)]
+    struct Recorded(Mutex<HashMap<String, Arc<Values>>>);
+
+    impl Recorded {
+        fn values(&self, key: &Key) -> Arc<Values> {
+            let mut metrics = self.0.lock().unwrap();
+            metrics.entry(key.name().to_string()).or_default().clone()
+        }
+
+        fn get(&self, name: &str) -> Vec<f64> {
+            self.0
+                .lock()
+                .unwrap()
+                .get(name)
+                .map_or(Vec::new(), |v| v.0.lock().unwrap().clone())
+        }
+    }
+
+    impl Recorder for Recorded {
+        fn describe_counter(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+        fn describe_gauge(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+        fn describe_histogram(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+        fn register_counter(&self, key: &Key, _: &Metadata<'_>) -> Counter {
+            Counter::from_arc(self.values(key))
+        }
+        fn register_gauge(&self, key: &Key, _: &Metadata<'_>) -> Gauge {
+            Gauge::from_arc(self.values(key))
+        }
+        fn register_histogram(&self, key: &Key, _: &Metadata<'_>) -> Histogram {
+            Histogram::from_arc(self.values(key))
+        }
+    }
+
+    #[test]
+    fn test_solvers_report_metrics() {
+        let recorder = Recorded::default();
+        metrics::with_local_recorder(&recorder, || {
+            let mut model = CpModel::new("m", 10_000);
+            model.add_interval(IntervalVar::new("A", 0, 10_000, 1000, 20_000));
+            model.add_interval(IntervalVar::new("B", 0, 10_000, 2000, 20_000));
+            SimpleCpSolver::new().solve(&model, &SolverConfig::default());
+
+            let task = Task::new("T1").with_activity(
+                Activity::new("A1", "T1", 1)
+                    .with_duration(ActivityDuration::fixed(3000))
+                    .with_resources("machine", vec!["M1".into()]),
+            );
+            SimpleScheduler::new().schedule(&[task], &[Resource::primary("M1")], 0);
+        });
+
+        assert_eq!(recorder.get(CP_SOLVES), vec![1.0]);
+        assert_eq!(recorder.get(CP_NODES), vec![2.0]);
+        assert_eq!(recorder.get(CP_OBJECTIVE), vec![2000.0]);
+        assert_eq!(recorder.get(CP_SOLVE_TIME).len(), 1);
+        assert_eq!(recorder.get(SCHEDULE_RUNS), vec![1.0]);
+        assert_eq!(recorder.get(SCHEDULE_MAKESPAN), vec![3000.0]);
+    }
+}
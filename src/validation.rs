@@ -2,7 +2,12 @@
 //!
 //! Ensures data integrity before scheduling
 
-use crate::models::{Resource, Task};
+use crate::graph::PrecedenceGraph;
+use crate::models::{
+    Compatibility, Constraint, RenewablePool, Resource, Schedule, SlotGrid, Task,
+    TransitionMatrixCollection,
+};
+use std::collections::HashMap;
 
 /// Validation result
 #[derive(Debug, Clone)]
@@ -82,13 +87,722 @@ pub fn validate_input(tasks: &[Task], resources: &[Resource]) -> ValidationResul
         }
     }
 
+    // Check precedence cycles
+    for cycle in PrecedenceGraph::from_tasks(tasks).cycles() {
+        result = result.with_error(
+            "PRECEDENCE_CYCLE",
+            &format!("Precedence cycle among activities {}", cycle.join(", ")),
+        );
+    }
+
+    result
+}
+
+/// Validate a produced schedule against global constraints
+pub fn validate_schedule(
+    schedule: &Schedule,
+    tasks: &[Task],
+    constraints: &[Constraint],
+) -> ValidationResult {
+    let mut result = ValidationResult::ok();
+
+    for constraint in constraints {
+        match constraint {
+            Constraint::PeakLoad { quantity, max_load } => {
+                let (peak, at_ms) = peak_load(schedule, tasks, quantity);
+                if peak > *max_load + 1e-9 {
+                    result = result.with_error(
+                        "PEAK_LOAD_EXCEEDED",
+                        &format!(
+                            "Peak {} load {} exceeds cap {} at {} ms",
+                            quantity, peak, max_load, at_ms
+                        ),
+                    );
+                }
+            }
+            Constraint::Fairness {
+                resource_ids,
+                max_deviation_ms,
+            } => {
+                let deviation = schedule.workload_deviation_ms(resource_ids);
+                if deviation > *max_deviation_ms {
+                    result = result.with_error(
+                        "WORKLOAD_UNFAIR",
+                        &format!(
+                            "Workload spread {} ms across {:?} exceeds {} ms",
+                            deviation, resource_ids, max_deviation_ms
+                        ),
+                    );
+                }
+            }
+            Constraint::PickupDelivery { pickup, delivery } => {
+                let (Some(p), Some(d)) = (
+                    schedule.assignment_for_activity(pickup),
+                    schedule.assignment_for_activity(delivery),
+                ) else {
+                    continue;
+                };
+                if p.resource_id != d.resource_id || d.start_ms < p.end_ms {
+                    result = result.with_error(
+                        "PICKUP_DELIVERY_VIOLATED",
+                        &format!(
+                            "Delivery {} must follow pickup {} on the same resource",
+                            delivery, pickup
+                        ),
+                    );
+                }
+            }
+            Constraint::Handoff {
+                from,
+                to,
+                tolerance_ms,
+            } => {
+                let (Some(f), Some(t)) = (
+                    schedule.assignment_for_activity(from),
+                    schedule.assignment_for_activity(to),
+                ) else {
+                    continue;
+                };
+                let offset = t.start_ms - f.end_ms;
+                if offset.abs() > *tolerance_ms {
+                    result = result.with_error(
+                        "HANDOFF_VIOLATED",
+                        &format!(
+                            "Handoff {} -> {} is off by {} ms (tolerance {} ms)",
+                            from, to, offset, tolerance_ms
+                        ),
+                    );
+                }
+            }
+            Constraint::MaxDelay {
+                before,
+                after,
+                max_delay_ms,
+            } => {
+                let (Some(b), Some(a)) = (
+                    schedule.assignment_for_activity(before),
+                    schedule.assignment_for_activity(after),
+                ) else {
+                    continue;
+                };
+                let lag = a.start_ms - b.end_ms;
+                if lag < 0 || lag > *max_delay_ms {
+                    result = result.with_error(
+                        "MAX_DELAY_EXCEEDED",
+                        &format!(
+                            "Activity {} starts {} ms after {} ends (allowed 0-{} ms)",
+                            after, lag, before, max_delay_ms
+                        ),
+                    );
+                }
+            }
+            Constraint::Overlap {
+                before,
+                after,
+                allowance,
+            } => {
+                let (Some(b), Some(a)) = (
+                    schedule.assignment_for_activity(before),
+                    schedule.assignment_for_activity(after),
+                ) else {
+                    continue;
+                };
+                let lead = allowance.lead_ms(b.end_ms - b.start_ms);
+                if a.start_ms < b.end_ms - lead || a.end_ms < b.end_ms {
+                    result = result.with_error(
+                        "OVERLAP_EXCEEDED",
+                        &format!(
+                            "Activity {} overlaps {} beyond its allowance ({} ms lead)",
+                            after, before, lead
+                        ),
+                    );
+                }
+            }
+            Constraint::MutualExclusion {
+                group,
+                activity_ids,
+            } => {
+                let members: Vec<_> = activity_ids
+                    .iter()
+                    .flat_map(|id| schedule.assignments_for_activity(id))
+                    .collect();
+                let clash = members.iter().enumerate().find_map(|(i, a)| {
+                    members[i + 1..]
+                        .iter()
+                        .find(|b| {
+                            a.activity_id != b.activity_id
+                                && a.start_ms < b.end_ms
+                                && b.start_ms < a.end_ms
+                        })
+                        .map(|b| (a, b))
+                });
+                if let Some((a, b)) = clash {
+                    result = result.with_error(
+                        "MUTUAL_EXCLUSION_VIOLATED",
+                        &format!(
+                            "Activities {} and {} of group {} run at the same time",
+                            a.activity_id, b.activity_id, group
+                        ),
+                    );
+                }
+            }
+            Constraint::TimeWindow {
+                activity_id,
+                start_ms,
+                end_ms,
+            } => {
+                if let Some(a) = schedule.assignment_for_activity(activity_id) {
+                    if a.start_ms < *start_ms || a.end_ms > *end_ms {
+                        result = result.with_error(
+                            "TIME_WINDOW_VIOLATED",
+                            &format!(
+                                "Activity {} runs {}-{} ms outside window {}-{} ms",
+                                activity_id, a.start_ms, a.end_ms, start_ms, end_ms
+                            ),
+                        );
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    result
+}
+
+/// Check every resource requirement keeps a compatible candidate
+pub fn validate_eligibility(
+    tasks: &[Task],
+    resources: &[Resource],
+    compatibility: &Compatibility,
+) -> ValidationResult {
+    let mut result = ValidationResult::ok();
+
+    for task in tasks {
+        for activity in &task.activities {
+            for requirement in &activity.resource_requirements {
+                if requirement.candidates.is_empty() {
+                    continue;
+                }
+                let eligible =
+                    compatibility.eligible_candidates(task, activity, requirement, resources);
+                if eligible.is_empty() {
+                    result.is_valid = false;
+                    result.errors.push(ValidationError {
+                        code: "NO_ELIGIBLE_RESOURCE".to_string(),
+                        message: format!(
+                            "No {} candidate of activity {} satisfies the compatibility rules",
+                            requirement.resource_type, activity.id
+                        ),
+                        entity_id: Some(activity.id.clone()),
+                    });
+                } else if eligible.len() < requirement.candidates.len() {
+                    result.warnings.push(format!(
+                        "Activity {}: {} of {} {} candidates are incompatible",
+                        activity.id,
+                        requirement.candidates.len() - eligible.len(),
+                        requirement.candidates.len(),
+                        requirement.resource_type
+                    ));
+                }
+            }
+        }
+    }
+
+    result
+}
+
+/// Check assignments only use compatible resources
+pub fn validate_compatibility(
+    schedule: &Schedule,
+    tasks: &[Task],
+    resources: &[Resource],
+    compatibility: &Compatibility,
+) -> ValidationResult {
+    let mut result = ValidationResult::ok();
+    let resource_map: HashMap<&str, &Resource> =
+        resources.iter().map(|r| (r.id.as_str(), r)).collect();
+
+    for task in tasks {
+        for activity in &task.activities {
+            for a in schedule.assignments_for_activity(&activity.id) {
+                let Some(resource) = resource_map.get(a.resource_id.as_str()) else {
+                    continue;
+                };
+                if !compatibility.allows(task, activity, resource) {
+                    result.is_valid = false;
+                    result.errors.push(ValidationError {
+                        code: "INCOMPATIBLE_RESOURCE".to_string(),
+                        message: format!(
+                            "Activity {} is assigned to incompatible resource {}",
+                            activity.id, resource.id
+                        ),
+                        entity_id: Some(activity.id.clone()),
+                    });
+                }
+            }
+        }
+    }
+
+    result
+}
+
+/// Check resources' working-time rules against a schedule
+pub fn validate_working_time(schedule: &Schedule, resources: &[Resource]) -> ValidationResult {
+    let mut result = ValidationResult::ok();
+
+    for resource in resources {
+        if let Some(rules) = &resource.working_time {
+            let booked = schedule.assignments_for_resource(&resource.id);
+            for violation in rules.violations(&resource.id, &booked) {
+                result.is_valid = false;
+                result.errors.push(ValidationError {
+                    code: "WORKING_TIME_VIOLATION".to_string(),
+                    message: violation.message,
+                    entity_id: Some(resource.id.clone()),
+                });
+            }
+        }
+    }
+
+    result
+}
+
+/// Check vehicle load capacity over time
+///
+/// A task's load occupies a resource from the first to the last of its
+/// assignments on that resource (loaded at pickup, unloaded at delivery).
+pub fn validate_load_capacity(
+    schedule: &Schedule,
+    tasks: &[Task],
+    resources: &[Resource],
+) -> ValidationResult {
+    let mut result = ValidationResult::ok();
+    let task_map: HashMap<&str, &Task> = tasks.iter().map(|t| (t.id.as_str(), t)).collect();
+
+    for vehicle in resources.iter().filter(|r| !r.load_capacity.is_empty()) {
+        // On-board span per task
+        let mut spans: HashMap<&str, (i64, i64)> = HashMap::new();
+        for a in schedule.assignments_for_resource(&vehicle.id) {
+            let span = spans
+                .entry(a.task_id.as_str())
+                .or_insert((a.start_ms, a.end_ms));
+            span.0 = span.0.min(a.start_ms);
+            span.1 = span.1.max(a.end_ms);
+        }
+
+        for (dimension, capacity) in &vehicle.load_capacity {
+            let mut events: Vec<(i64, i32, f64)> = Vec::new();
+            for (task_id, (start, end)) in &spans {
+                let load = task_map.get(task_id).map_or(0.0, |t| t.load(dimension));
+                if load != 0.0 {
+                    events.push((*start, 1, load));
+                    events.push((*end, 0, -load));
+                }
+            }
+            events.sort_by_key(|e| (e.0, e.1));
+
+            let mut on_board = 0.0;
+            for (time, _, delta) in events {
+                on_board += delta;
+                if on_board > *capacity + 1e-9 {
+                    result.is_valid = false;
+                    result.errors.push(ValidationError {
+                        code: "LOAD_CAPACITY_EXCEEDED".to_string(),
+                        message: format!(
+                            "Resource {} carries {} {} at {} ms (capacity {})",
+                            vehicle.id, on_board, dimension, time, capacity
+                        ),
+                        entity_id: Some(vehicle.id.clone()),
+                    });
+                    break;
+                }
+            }
+        }
+    }
+
+    result
+}
+
+/// Check gang activities occupy all their resources simultaneously
+pub fn validate_gang(schedule: &Schedule, tasks: &[Task]) -> ValidationResult {
+    let mut result = ValidationResult::ok();
+
+    for activity in tasks
+        .iter()
+        .flat_map(|t| t.activities.iter())
+        .filter(|a| a.is_gang())
+    {
+        let assigned = schedule.assignments_for_activity(&activity.id);
+        if assigned.is_empty() {
+            continue;
+        }
+        let mut resources: Vec<&str> = assigned.iter().map(|a| a.resource_id.as_str()).collect();
+        resources.sort();
+        resources.dedup();
+
+        let (code, message) = if resources.len() < activity.gang_size() {
+            (
+                "GANG_INCOMPLETE",
+                format!(
+                    "Gang activity {} holds {} of {} resources",
+                    activity.id,
+                    resources.len(),
+                    activity.gang_size()
+                ),
+            )
+        } else if assigned
+            .iter()
+            .any(|a| a.start_ms != assigned[0].start_ms || a.end_ms != assigned[0].end_ms)
+        {
+            (
+                "GANG_NOT_SYNCHRONIZED",
+                format!(
+                    "Gang activity {} resources are not used simultaneously",
+                    activity.id
+                ),
+            )
+        } else {
+            continue;
+        };
+        result.is_valid = false;
+        result.errors.push(ValidationError {
+            code: code.to_string(),
+            message,
+            entity_id: Some(activity.id.clone()),
+        });
+    }
+
+    result
+}
+
+/// Check malleable activities use a permitted allocation level
+///
+/// The number of distinct resources must lie within the activity's unit
+/// range, and the assignment may not be shorter than the duration at
+/// that level.
+pub fn validate_malleable(schedule: &Schedule, tasks: &[Task]) -> ValidationResult {
+    let mut result = ValidationResult::ok();
+
+    for activity in tasks.iter().flat_map(|t| t.activities.iter()) {
+        let Some(malleability) = &activity.malleable else {
+            continue;
+        };
+        let assigned = schedule.assignments_for_activity(&activity.id);
+        if assigned.is_empty() {
+            continue;
+        }
+        let mut resources: Vec<&str> = assigned.iter().map(|a| a.resource_id.as_str()).collect();
+        resources.sort();
+        resources.dedup();
+        let units = resources.len() as u32;
+
+        let message = if units < malleability.min_units || units > malleability.max_units {
+            format!(
+                "Malleable activity {} uses {} units (allowed {}..={})",
+                activity.id, units, malleability.min_units, malleability.max_units
+            )
+        } else {
+            let required = malleability.duration_ms(activity.duration.process_ms, units);
+            match assigned.iter().find(|a| a.duration_ms() < required) {
+                Some(short) => format!(
+                    "Malleable activity {} on {} runs {}ms, {} units need {}ms",
+                    activity.id,
+                    short.resource_id,
+                    short.duration_ms(),
+                    units,
+                    required
+                ),
+                None => continue,
+            }
+        };
+        result.is_valid = false;
+        result.errors.push(ValidationError {
+            code: "MALLEABLE_ALLOCATION_INVALID".to_string(),
+            message,
+            entity_id: Some(activity.id.clone()),
+        });
+    }
+
+    result
+}
+
+/// Check multi-dimensional resource capacities (CPU, memory) over time
+///
+/// Concurrent activities on a resource may not together demand more than
+/// its capacity in any dimension.
+pub fn validate_dimension_capacity(
+    schedule: &Schedule,
+    tasks: &[Task],
+    resources: &[Resource],
+) -> ValidationResult {
+    let mut result = ValidationResult::ok();
+    let activities: HashMap<&str, &crate::models::Activity> = tasks
+        .iter()
+        .flat_map(|t| t.activities.iter())
+        .map(|a| (a.id.as_str(), a))
+        .collect();
+
+    for resource in resources.iter().filter(|r| r.is_multi_dimensional()) {
+        let booked = schedule.assignments_for_resource(&resource.id);
+        for (dimension, capacity) in &resource.dimension_capacity {
+            let mut events: Vec<(i64, i32, f64)> = Vec::new();
+            for a in &booked {
+                let demand = activities
+                    .get(a.activity_id.as_str())
+                    .map_or(0.0, |act| act.dimension_demand(dimension));
+                if demand != 0.0 {
+                    events.push((a.start_ms, 1, demand));
+                    events.push((a.end_ms, 0, -demand));
+                }
+            }
+            events.sort_by_key(|e| (e.0, e.1));
+
+            let mut used = 0.0;
+            for (time, _, delta) in events {
+                used += delta;
+                if used > *capacity + 1e-9 {
+                    result.is_valid = false;
+                    result.errors.push(ValidationError {
+                        code: "DIMENSION_CAPACITY_EXCEEDED".to_string(),
+                        message: format!(
+                            "Resource {} uses {} {} at {} ms (capacity {})",
+                            resource.id, used, dimension, time, capacity
+                        ),
+                        entity_id: Some(resource.id.clone()),
+                    });
+                    break;
+                }
+            }
+        }
+    }
+
+    result
+}
+
+/// Check that every assignment starts and ends on slot boundaries
+pub fn validate_slots(schedule: &Schedule, grid: &SlotGrid) -> ValidationResult {
+    let mut result = ValidationResult::ok();
+
+    for a in &schedule.assignments {
+        if !grid.is_aligned(a.start_ms, a.end_ms) {
+            result.is_valid = false;
+            result.errors.push(ValidationError {
+                code: "SLOT_MISALIGNED".to_string(),
+                message: format!(
+                    "Activity {} ({} - {} ms) is not aligned to slots",
+                    a.activity_id, a.start_ms, a.end_ms
+                ),
+                entity_id: Some(a.activity_id.clone()),
+            });
+        }
+    }
+
+    result
+}
+
+/// Check that every task completes by its hard deadline
+///
+/// Missing a target date is lateness, not infeasibility, and is not
+/// reported here.
+pub fn validate_deadlines(schedule: &Schedule, tasks: &[Task]) -> ValidationResult {
+    let mut result = ValidationResult::ok();
+
+    for task in tasks {
+        let (Some(deadline), Some(end)) = (task.deadline, schedule.task_completion_time(&task.id))
+        else {
+            continue;
+        };
+        if end > deadline.timestamp_millis() {
+            result.is_valid = false;
+            result.errors.push(ValidationError {
+                code: "DEADLINE_MISSED".to_string(),
+                message: format!(
+                    "Task {} completes at {} ms, after its deadline {} ms",
+                    task.id,
+                    end,
+                    deadline.timestamp_millis()
+                ),
+                entity_id: Some(task.id.clone()),
+            });
+        }
+    }
+
+    result
+}
+
+/// Check that renewable pool units in use never exceed capacity
+///
+/// An activity holds its demanded units from its earliest start to its
+/// latest end in the schedule. Demands on undeclared pools count against
+/// capacity 0.
+pub fn validate_pool_capacity(
+    schedule: &Schedule,
+    tasks: &[Task],
+    pools: &[RenewablePool],
+) -> ValidationResult {
+    let mut result = ValidationResult::ok();
+    let capacity: HashMap<&str, u32> = pools.iter().map(|p| (p.id.as_str(), p.capacity)).collect();
+
+    let mut events: HashMap<&str, Vec<(i64, i32, i64)>> = HashMap::new();
+    for activity in tasks.iter().flat_map(|t| t.activities.iter()) {
+        let assignments = schedule.assignments_for_activity(&activity.id);
+        let (Some(start), Some(end)) = (
+            assignments.iter().map(|a| a.start_ms).min(),
+            assignments.iter().map(|a| a.end_ms).max(),
+        ) else {
+            continue;
+        };
+        for (pool, &units) in activity.pool_demands.iter().filter(|(_, &u)| u > 0) {
+            let pool_events = events.entry(pool.as_str()).or_default();
+            pool_events.push((start, 1, units as i64));
+            pool_events.push((end, 0, -(units as i64)));
+        }
+    }
+
+    let mut pools_used: Vec<&str> = events.keys().copied().collect();
+    pools_used.sort_unstable();
+    for pool in pools_used {
+        let pool_events = events.get_mut(pool).expect("listed pool");
+        pool_events.sort_by_key(|e| (e.0, e.1));
+        let limit = capacity.get(pool).copied().unwrap_or(0) as i64;
+        let mut in_use = 0;
+        for &(time, _, delta) in pool_events.iter() {
+            in_use += delta;
+            if in_use > limit {
+                result.is_valid = false;
+                result.errors.push(ValidationError {
+                    code: "POOL_CAPACITY_EXCEEDED".to_string(),
+                    message: format!(
+                        "Pool {} has {} units in use at {} ms (capacity {})",
+                        pool, in_use, time, limit
+                    ),
+                    entity_id: Some(pool.to_string()),
+                });
+                break;
+            }
+        }
+    }
+
+    result
+}
+
+/// Check setup matrices for the given task categories
+///
+/// Negative times are errors. Asymmetric pairs (`a -> b` differing from
+/// `b -> a`) and category changes that fall back to a zero default are
+/// reported as warnings: both are legitimate, but often typos in a
+/// maintained spreadsheet.
+pub fn validate_transitions(
+    transitions: &TransitionMatrixCollection,
+    categories: &[&str],
+) -> ValidationResult {
+    let mut result = ValidationResult::ok();
+
+    for matrix in &transitions.matrices {
+        let mut entries: Vec<(String, i64)> = matrix
+            .transitions
+            .iter()
+            .map(|((from, to), &ms)| (format!("Transition {} -> {}", from, to), ms))
+            .collect();
+        entries.sort();
+        entries.push(("Default transition".to_string(), matrix.default_ms));
+        for (what, ms) in entries.into_iter().filter(|(_, ms)| *ms < 0) {
+            result.is_valid = false;
+            result.errors.push(ValidationError {
+                code: "NEGATIVE_TRANSITION".to_string(),
+                message: format!("{} on {} is negative ({} ms)", what, matrix.resource_id, ms),
+                entity_id: Some(matrix.resource_id.clone()),
+            });
+        }
+
+        for (i, from) in categories.iter().enumerate() {
+            for to in &categories[i + 1..] {
+                let there = matrix.get_transition(from, to);
+                let back = matrix.get_transition(to, from);
+                if there != back {
+                    result.warnings.push(format!(
+                        "Transition {} -> {} on {} is {} ms but {} -> {} is {} ms",
+                        from, to, matrix.resource_id, there, to, from, back
+                    ));
+                }
+            }
+        }
+        if matrix.default_ms == 0 {
+            for from in categories {
+                for to in categories.iter().filter(|to| *to != from) {
+                    let key = (from.to_string(), to.to_string());
+                    if !matrix.transitions.contains_key(&key) {
+                        result.warnings.push(format!(
+                            "Transition {} -> {} on {} is not listed and defaults to 0 ms",
+                            from, to, matrix.resource_id
+                        ));
+                    }
+                }
+            }
+        }
+    }
+
     result
 }
 
+/// Maximum simultaneous demand of a quantity and when it occurs
+///
+/// An activity draws its demand once while any of its assignments runs,
+/// however many resources (gang members, setters) it holds.
+fn peak_load(schedule: &Schedule, tasks: &[Task], quantity: &str) -> (f64, i64) {
+    let demands: HashMap<&str, f64> = tasks
+        .iter()
+        .flat_map(|t| t.activities.iter())
+        .map(|a| (a.id.as_str(), a.demand(quantity)))
+        .filter(|(_, d)| *d != 0.0)
+        .collect();
+
+    let mut running: HashMap<&str, Vec<(i64, i64)>> = HashMap::new();
+    for a in &schedule.assignments {
+        if demands.contains_key(a.activity_id.as_str()) {
+            running
+                .entry(a.activity_id.as_str())
+                .or_default()
+                .push((a.start_ms, a.end_ms));
+        }
+    }
+
+    // Sweep line over the union of each activity's intervals: ends sort
+    // before starts at the same instant
+    let mut events: Vec<(i64, i32, f64)> = Vec::new();
+    for (activity_id, mut intervals) in running {
+        let demand = demands[activity_id];
+        intervals.sort_unstable();
+        let mut merged: Vec<(i64, i64)> = Vec::new();
+        for (start, end) in intervals {
+            match merged.last_mut() {
+                Some(last) if start <= last.1 => last.1 = last.1.max(end),
+                _ => merged.push((start, end)),
+            }
+        }
+        for (start, end) in merged {
+            events.push((start, 1, demand));
+            events.push((end, 0, -demand));
+        }
+    }
+    events.sort_by_key(|e| (e.0, e.1));
+
+    let mut load = 0.0;
+    let mut peak = (0.0, 0);
+    for (time, _, delta) in events {
+        load += delta;
+        if load > peak.0 {
+            peak = (load, time);
+        }
+    }
+    peak
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::models::Activity;
+    use crate::models::{Activity, ActivityDuration, Assignment};
 
     #[test]
     fn test_valid_input() {
@@ -110,6 +824,43 @@ mod tests {
         assert!(!result.is_valid);
     }
 
+    #[test]
+    fn test_precedence_cycle() {
+        let tasks = vec![Task::new("T1")
+            .with_activity(Activity::new("A1", "T1", 1).with_predecessor("A2"))
+            .with_activity(Activity::new("A2", "T1", 2).with_predecessor("A1"))];
+
+        let result = validate_input(&tasks, &[]);
+        assert!(!result.is_valid);
+        assert_eq!(result.errors[0].code, "PRECEDENCE_CYCLE");
+    }
+
+    #[test]
+    fn test_peak_load() {
+        let tasks = vec![
+            Task::new("T1").with_activity(Activity::new("A1", "T1", 1).with_demand("power", 60.0)),
+            Task::new("T2").with_activity(Activity::new("A2", "T2", 1).with_demand("power", 50.0)),
+        ];
+        let constraints = vec![Constraint::peak_load("power", 100.0)];
+
+        let mut parallel = Schedule::new();
+        parallel.add_assignment(Assignment::new("A1", "T1", "M1", 0, 1000));
+        parallel.add_assignment(Assignment::new("A2", "T2", "M2", 500, 1500));
+        assert!(!validate_schedule(&parallel, &tasks, &constraints).is_valid);
+
+        let mut serial = Schedule::new();
+        serial.add_assignment(Assignment::new("A1", "T1", "M1", 0, 1000));
+        serial.add_assignment(Assignment::new("A2", "T2", "M2", 1000, 2000));
+        assert!(validate_schedule(&serial, &tasks, &constraints).is_valid);
+
+        // A gang activity on two machines draws its demand once
+        let mut gang = Schedule::new();
+        gang.add_assignment(Assignment::new("A1", "T1", "M1", 0, 1000));
+        gang.add_assignment(Assignment::new("A1", "T1", "M2", 0, 1000));
+        assert!(validate_schedule(&gang, &tasks, &constraints).is_valid);
+        assert_eq!(peak_load(&gang, &tasks, "power"), (60.0, 0));
+    }
+
     #[test]
     fn test_invalid_resource_reference() {
         let tasks = vec![Task::new("T1").with_activity(
@@ -120,4 +871,310 @@ mod tests {
         let result = validate_input(&tasks, &resources);
         assert!(!result.is_valid);
     }
+
+    #[test]
+    fn test_fairness() {
+        let group = vec!["N1".to_string(), "N2".to_string()];
+        let constraints = vec![Constraint::fairness(group, 2000)];
+
+        let mut lopsided = Schedule::new();
+        lopsided.add_assignment(Assignment::new("A1", "T1", "N1", 0, 4000));
+        lopsided.add_assignment(Assignment::new("A2", "T2", "N1", 4000, 8000));
+        assert!(!validate_schedule(&lopsided, &[], &constraints).is_valid);
+
+        let mut balanced = Schedule::new();
+        balanced.add_assignment(Assignment::new("A1", "T1", "N1", 0, 4000));
+        balanced.add_assignment(Assignment::new("A2", "T2", "N2", 0, 4000));
+        assert!(validate_schedule(&balanced, &[], &constraints).is_valid);
+    }
+
+    #[test]
+    fn test_working_time() {
+        let hour = 3_600_000;
+        let resources = vec![Resource::human("N1")
+            .with_working_time_rules(crate::models::WorkingTimeRules::new().with_min_rest(hour))];
+
+        let mut schedule = Schedule::new();
+        schedule.add_assignment(Assignment::new("A1", "T1", "N1", 0, 4 * hour));
+        schedule.add_assignment(Assignment::new("A2", "T2", "N1", 4 * hour, 8 * hour));
+
+        let result = validate_working_time(&schedule, &resources);
+        assert!(!result.is_valid);
+        assert_eq!(result.errors[0].entity_id.as_deref(), Some("N1"));
+    }
+
+    #[test]
+    fn test_compatibility() {
+        let tasks = vec![Task::new("T1")
+            .with_attribute("temp_class", "hot")
+            .with_activity(
+                Activity::new("A1", "T1", 1)
+                    .with_duration(ActivityDuration::fixed(1000))
+                    .with_resources("oven", vec!["O1".into(), "O2".into()]),
+            )];
+        let resources = vec![
+            Resource::primary("O1").with_attribute("temp_class", "cold"),
+            Resource::primary("O2").with_attribute("temp_class", "hot"),
+        ];
+        let rules =
+            Compatibility::parse(&[r#"resource.attr("temp_class") == task.attr("temp_class")"#])
+                .unwrap();
+
+        let result = validate_eligibility(&tasks, &resources, &rules);
+        assert!(result.is_valid);
+        assert_eq!(result.warnings.len(), 1);
+
+        // O1 would win the tie without the rules
+        let mut schedule = Schedule::new();
+        schedule.add_assignment(Assignment::new("A1", "T1", "O1", 0, 1000));
+        let result = validate_compatibility(&schedule, &tasks, &resources, &rules);
+        assert_eq!(result.errors[0].code, "INCOMPATIBLE_RESOURCE");
+
+        let request = crate::scheduler::ScheduleRequest::new(tasks.clone(), resources.clone())
+            .with_compatibility(rules.clone());
+        let schedule = crate::scheduler::SimpleScheduler::new().schedule_request(&request);
+        assert_eq!(schedule.assignments[0].resource_id, "O2");
+        assert!(validate_compatibility(&schedule, &tasks, &resources, &rules).is_valid);
+
+        let strict = rules.with_rule("resource.attr(\"size\") > 3".parse().unwrap());
+        let result = validate_eligibility(&tasks, &resources, &strict);
+        assert_eq!(result.errors[0].code, "NO_ELIGIBLE_RESOURCE");
+    }
+
+    #[test]
+    fn test_load_capacity() {
+        let tasks = vec![
+            Task::new("S1").with_load("kg", 600.0),
+            Task::new("S2").with_load("kg", 600.0),
+        ];
+        let resources = vec![Resource::primary("V1").with_load_capacity("kg", 1000.0)];
+
+        // S1 still on board when S2 is picked up
+        let mut overlapping = Schedule::new();
+        overlapping.add_assignment(Assignment::new("S1-P", "S1", "V1", 0, 100));
+        overlapping.add_assignment(Assignment::new("S2-P", "S2", "V1", 100, 200));
+        overlapping.add_assignment(Assignment::new("S1-D", "S1", "V1", 200, 300));
+        let result = validate_load_capacity(&overlapping, &tasks, &resources);
+        assert!(!result.is_valid);
+        assert_eq!(result.errors[0].code, "LOAD_CAPACITY_EXCEEDED");
+
+        let mut sequential = Schedule::new();
+        sequential.add_assignment(Assignment::new("S1-P", "S1", "V1", 0, 100));
+        sequential.add_assignment(Assignment::new("S1-D", "S1", "V1", 100, 200));
+        sequential.add_assignment(Assignment::new("S2-P", "S2", "V1", 200, 300));
+        assert!(validate_load_capacity(&sequential, &tasks, &resources).is_valid);
+    }
+
+    #[test]
+    fn test_pickup_delivery_and_time_window() {
+        let constraints = vec![
+            Constraint::pickup_delivery("P1", "D1"),
+            Constraint::time_window("D1", 0, 500),
+        ];
+
+        let mut ok = Schedule::new();
+        ok.add_assignment(Assignment::new("P1", "S1", "V1", 0, 100));
+        ok.add_assignment(Assignment::new("D1", "S1", "V1", 200, 300));
+        assert!(validate_schedule(&ok, &[], &constraints).is_valid);
+
+        let mut wrong_vehicle = Schedule::new();
+        wrong_vehicle.add_assignment(Assignment::new("P1", "S1", "V1", 0, 100));
+        wrong_vehicle.add_assignment(Assignment::new("D1", "S1", "V2", 200, 300));
+        let result = validate_schedule(&wrong_vehicle, &[], &constraints);
+        assert_eq!(result.errors[0].code, "PICKUP_DELIVERY_VIOLATED");
+
+        let mut late = Schedule::new();
+        late.add_assignment(Assignment::new("P1", "S1", "V1", 0, 100));
+        late.add_assignment(Assignment::new("D1", "S1", "V1", 400, 600));
+        let result = validate_schedule(&late, &[], &constraints);
+        assert_eq!(result.errors[0].code, "TIME_WINDOW_VIOLATED");
+    }
+
+    #[test]
+    fn test_handoff() {
+        let constraints = vec![Constraint::handoff("Surgery", "Transfer", 300)];
+
+        let mut synced = Schedule::new();
+        synced.add_assignment(Assignment::new("Surgery", "P1", "OR1", 0, 1000));
+        synced.add_assignment(Assignment::new("Transfer", "P1", "Porter", 1200, 1500));
+        assert!(validate_schedule(&synced, &[], &constraints).is_valid);
+
+        let mut waiting = Schedule::new();
+        waiting.add_assignment(Assignment::new("Surgery", "P1", "OR1", 0, 1000));
+        waiting.add_assignment(Assignment::new("Transfer", "P1", "Porter", 2000, 2300));
+        let result = validate_schedule(&waiting, &[], &constraints);
+        assert_eq!(result.errors[0].code, "HANDOFF_VIOLATED");
+    }
+
+    #[test]
+    fn test_mutual_exclusion() {
+        let constraints = vec![Constraint::mutual_exclusion(
+            "crane",
+            vec!["Lift1".into(), "Lift2".into()],
+        )];
+
+        let mut apart = Schedule::new();
+        apart.add_assignment(Assignment::new("Lift1", "T1", "Crew1", 0, 1000));
+        apart.add_assignment(Assignment::new("Lift2", "T2", "Crew2", 1000, 2000));
+        assert!(validate_schedule(&apart, &[], &constraints).is_valid);
+
+        // Different resources, same time
+        let mut together = Schedule::new();
+        together.add_assignment(Assignment::new("Lift1", "T1", "Crew1", 0, 1000));
+        together.add_assignment(Assignment::new("Lift2", "T2", "Crew2", 500, 1500));
+        let result = validate_schedule(&together, &[], &constraints);
+        assert_eq!(result.errors[0].code, "MUTUAL_EXCLUSION_VIOLATED");
+    }
+
+    #[test]
+    fn test_dimension_capacity() {
+        let tasks = vec![
+            Task::new("VM1").with_activity(
+                Activity::new("VM1-run", "VM1", 1).with_dimension_demand("cpu", 3.0),
+            ),
+            Task::new("VM2").with_activity(
+                Activity::new("VM2-run", "VM2", 1).with_dimension_demand("cpu", 2.0),
+            ),
+        ];
+        let resources = vec![Resource::primary("Host1").with_dimension_capacity("cpu", 4.0)];
+
+        let mut schedule = Schedule::new();
+        schedule.add_assignment(Assignment::new("VM1-run", "VM1", "Host1", 0, 1000));
+        schedule.add_assignment(Assignment::new("VM2-run", "VM2", "Host1", 500, 1500));
+        let result = validate_dimension_capacity(&schedule, &tasks, &resources);
+        assert_eq!(result.errors[0].code, "DIMENSION_CAPACITY_EXCEEDED");
+
+        let mut schedule = Schedule::new();
+        schedule.add_assignment(Assignment::new("VM1-run", "VM1", "Host1", 0, 1000));
+        schedule.add_assignment(Assignment::new("VM2-run", "VM2", "Host1", 1000, 2000));
+        assert!(validate_dimension_capacity(&schedule, &tasks, &resources).is_valid);
+    }
+
+    #[test]
+    fn test_gang() {
+        let tasks = vec![
+            Task::new("T1").with_activity(Activity::new("Lift", "T1", 1).with_gang(
+                "crane",
+                vec!["C1".into(), "C2".into()],
+                2,
+            )),
+        ];
+
+        let mut synced = Schedule::new();
+        synced.add_assignment(Assignment::new("Lift", "T1", "C1", 100, 200));
+        synced.add_assignment(Assignment::new("Lift", "T1", "C2", 100, 200));
+        assert!(validate_gang(&synced, &tasks).is_valid);
+
+        let mut staggered = Schedule::new();
+        staggered.add_assignment(Assignment::new("Lift", "T1", "C1", 100, 200));
+        staggered.add_assignment(Assignment::new("Lift", "T1", "C2", 150, 250));
+        let result = validate_gang(&staggered, &tasks);
+        assert_eq!(result.errors[0].code, "GANG_NOT_SYNCHRONIZED");
+
+        let mut partial = Schedule::new();
+        partial.add_assignment(Assignment::new("Lift", "T1", "C1", 100, 200));
+        let result = validate_gang(&partial, &tasks);
+        assert_eq!(result.errors[0].code, "GANG_INCOMPLETE");
+    }
+
+    #[test]
+    fn test_malleable() {
+        let tasks = vec![Task::new("T1").with_activity(
+            Activity::new("Dig", "T1", 1)
+                .with_duration(ActivityDuration::fixed(4000))
+                .with_resources("worker", vec!["W1".into(), "W2".into(), "W3".into()])
+                .with_malleability(1, 2, 1.0),
+        )];
+
+        let mut pair = Schedule::new();
+        pair.add_assignment(Assignment::new("Dig", "T1", "W1", 0, 2000));
+        pair.add_assignment(Assignment::new("Dig", "T1", "W2", 0, 2000));
+        assert!(validate_malleable(&pair, &tasks).is_valid);
+
+        let mut rushed = Schedule::new();
+        rushed.add_assignment(Assignment::new("Dig", "T1", "W1", 0, 2000));
+        assert!(!validate_malleable(&rushed, &tasks).is_valid);
+
+        let mut crowded = pair.clone();
+        crowded.add_assignment(Assignment::new("Dig", "T1", "W3", 0, 2000));
+        let result = validate_malleable(&crowded, &tasks);
+        assert_eq!(result.errors[0].code, "MALLEABLE_ALLOCATION_INVALID");
+    }
+
+    #[test]
+    fn test_slots() {
+        let grid = SlotGrid::fixed(0, 900);
+
+        let mut schedule = Schedule::new();
+        schedule.add_assignment(Assignment::new("A1", "T1", "Dr1", 0, 1800));
+        assert!(validate_slots(&schedule, &grid).is_valid);
+
+        schedule.add_assignment(Assignment::new("A2", "T2", "Dr1", 1800, 2000));
+        let result = validate_slots(&schedule, &grid);
+        assert_eq!(result.errors[0].code, "SLOT_MISALIGNED");
+        assert_eq!(result.errors[0].entity_id.as_deref(), Some("A2"));
+    }
+
+    #[test]
+    fn test_deadlines() {
+        let at = |ms| chrono::DateTime::from_timestamp_millis(ms).unwrap();
+        let tasks = vec![
+            Task::new("T1")
+                .with_target_date(at(1000))
+                .with_deadline(at(3000)),
+            Task::new("T2").with_deadline(at(1000)),
+        ];
+        let mut schedule = Schedule::new();
+        // Past the target, within the deadline
+        schedule.add_assignment(Assignment::new("A1", "T1", "M1", 0, 2000));
+        assert!(validate_deadlines(&schedule, &tasks).is_valid);
+
+        schedule.add_assignment(Assignment::new("A2", "T2", "M1", 2000, 2500));
+        let result = validate_deadlines(&schedule, &tasks);
+        assert_eq!(result.errors.len(), 1);
+        assert_eq!(result.errors[0].code, "DEADLINE_MISSED");
+        assert_eq!(result.errors[0].entity_id.as_deref(), Some("T2"));
+    }
+
+    #[test]
+    fn test_transitions() {
+        use crate::models::TransitionMatrix;
+
+        let mut matrix = TransitionMatrix::new("colors", "M1");
+        matrix.set_transition("red", "blue", 900);
+        matrix.set_transition("blue", "red", 600);
+        matrix.set_transition("red", "green", -1);
+        let mut transitions = TransitionMatrixCollection::new();
+        transitions.add(matrix);
+
+        let result = validate_transitions(&transitions, &["red", "blue", "green"]);
+        assert!(!result.is_valid);
+        assert_eq!(result.errors.len(), 1);
+        assert_eq!(result.errors[0].code, "NEGATIVE_TRANSITION");
+        // red/blue and red/green asymmetric; three changes unlisted
+        assert_eq!(result.warnings.len(), 2 + 3);
+
+        transitions.matrices[0].set_transition("red", "green", 300);
+        transitions.matrices[0].default_ms = 300;
+        let result = validate_transitions(&transitions, &["red", "blue", "green"]);
+        assert!(result.is_valid);
+        assert_eq!(result.warnings.len(), 1);
+    }
+
+    #[test]
+    fn test_pool_capacity() {
+        let tasks = vec![Task::new("P")
+            .with_activity(Activity::new("A", "P", 1).with_pool_demand("crew", 3))
+            .with_activity(Activity::new("B", "P", 2).with_pool_demand("crew", 2))];
+        let pools = vec![RenewablePool::new("crew", 4)];
+        let mut schedule = Schedule::new();
+        schedule.add_assignment(Assignment::new("A", "P", "crew", 0, 1000));
+        schedule.add_assignment(Assignment::new("B", "P", "crew", 1000, 2000));
+        assert!(validate_pool_capacity(&schedule, &tasks, &pools).is_valid);
+
+        schedule.add_assignment(Assignment::new("B", "P", "crew", 500, 1000));
+        let result = validate_pool_capacity(&schedule, &tasks, &pools);
+        assert!(!result.is_valid);
+        assert_eq!(result.errors[0].code, "POOL_CAPACITY_EXCEEDED");
+    }
 }
@@ -0,0 +1,123 @@
+//! WASM - JavaScript bindings
+//!
+//! wasm-bindgen wrappers (feature `wasm`) for in-browser scheduling.
+//! Inputs and outputs are plain JS objects in the serde shape of the
+//! models (field names as in Rust, maps as objects, times as epoch ms
+//! numbers or RFC 3339 strings for deadlines).
+//!
+//! ```js
+//! import init, { schedule, scheduleGa, calculateKpi } from "./pkg/u_ras.js";
+//!
+//! await init();
+//! const plan = schedule(tasks, resources, Date.now());
+//! const kpi = calculateKpi(plan, tasks);
+//! ```
+
+use crate::ga::{GaParams, GaScheduler};
+use crate::models::{Resource, Schedule, Task};
+use crate::scheduler::{ScheduleKpi, SimpleScheduler};
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+
+/// GA options accepted by `scheduleGa` (all optional)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct GaOptions {
+    /// Population size
+    pub population_size: Option<usize>,
+    /// Maximum generations
+    pub max_generations: Option<usize>,
+    /// Time limit (ms)
+    pub time_limit_ms: Option<i64>,
+    /// Random seed
+    pub seed: Option<u64>,
+}
+
+impl GaOptions {
+    /// GA scheduler with these options over the fast preset
+    pub fn scheduler(&self) -> GaScheduler {
+        let preset = GaParams::fast();
+        let params = GaParams {
+            population_size: self.population_size.unwrap_or(preset.population_size),
+            max_generations: self.max_generations.unwrap_or(preset.max_generations),
+            time_limit_ms: self.time_limit_ms.or(preset.time_limit_ms),
+            ..preset
+        };
+        let scheduler = GaScheduler::new(params);
+        match self.seed {
+            Some(seed) => scheduler.with_seed(seed),
+            None => scheduler,
+        }
+    }
+}
+
+/// Schedule with the priority-based greedy scheduler
+#[wasm_bindgen(js_name = schedule)]
+pub fn schedule_js(
+    tasks: JsValue,
+    resources: JsValue,
+    start_time_ms: f64,
+) -> Result<JsValue, JsError> {
+    let tasks: Vec<Task> = from_js(tasks)?;
+    let resources: Vec<Resource> = from_js(resources)?;
+    to_js(&SimpleScheduler::new().schedule(&tasks, &resources, start_time_ms as i64))
+}
+
+/// Schedule with the genetic algorithm (minimizes makespan)
+#[wasm_bindgen(js_name = scheduleGa)]
+pub fn schedule_ga_js(
+    tasks: JsValue,
+    resources: JsValue,
+    start_time_ms: f64,
+    options: JsValue,
+) -> Result<JsValue, JsError> {
+    let tasks: Vec<Task> = from_js(tasks)?;
+    let resources: Vec<Resource> = from_js(resources)?;
+    let options: GaOptions = if options.is_undefined() || options.is_null() {
+        GaOptions::default()
+    } else {
+        from_js(options)?
+    };
+    to_js(
+        &options
+            .scheduler()
+            .schedule(&tasks, &resources, start_time_ms as i64),
+    )
+}
+
+/// KPIs of a schedule
+#[wasm_bindgen(js_name = calculateKpi)]
+pub fn calculate_kpi_js(schedule: JsValue, tasks: JsValue) -> Result<JsValue, JsError> {
+    let schedule: Schedule = from_js(schedule)?;
+    let tasks: Vec<Task> = from_js(tasks)?;
+    to_js(&ScheduleKpi::calculate(&schedule, &tasks))
+}
+
+fn from_js<T: for<'de> Deserialize<'de>>(value: JsValue) -> Result<T, JsError> {
+    serde_wasm_bindgen::from_value(value).map_err(|e| JsError::new(&e.to_string()))
+}
+
+fn to_js<T: Serialize>(value: &T) -> Result<JsValue, JsError> {
+    value
+        .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+        .map_err(|e| JsError::new(&e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ga_options() {
+        let options: GaOptions =
+            serde_json::from_str(r#"{"populationSize": 12, "seed": 7}"#).unwrap();
+        let scheduler = options.scheduler();
+
+        assert_eq!(scheduler.params.population_size, 12);
+        assert_eq!(
+            scheduler.params.max_generations,
+            GaParams::fast().max_generations
+        );
+        assert_eq!(scheduler.seed, 7);
+    }
+}